@@ -0,0 +1,38 @@
+/*
+ * SearchDeadCode C API
+ *
+ * Build: cargo rustc --profile release-ffi --lib --features ffi --crate-type cdylib
+ *
+ * All strings are NUL-terminated UTF-8. Strings returned by sdc_analyze are
+ * owned by the caller and must be released with sdc_string_free.
+ */
+
+#ifndef SEARCHDEADCODE_H
+#define SEARCHDEADCODE_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Analyze the project at `path`.
+ *
+ * `config_json` is the configuration as JSON (same keys as .deadcode.yml),
+ * or NULL to load the project's config file.
+ *
+ * Returns {"version", "files_analyzed", "dead_code": [...]} on success or
+ * {"version", "error"} on failure. Returns NULL only on an internal error.
+ */
+char *sdc_analyze(const char *path, const char *config_json);
+
+/* Release a string returned by sdc_analyze. NULL is ignored. */
+void sdc_string_free(char *s);
+
+/* Library version. Static; do not free. */
+const char *sdc_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SEARCHDEADCODE_H */
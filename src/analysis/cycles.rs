@@ -9,7 +9,6 @@
 // This is inspired by Meta's SCARF system which detects mutually dependent dead code.
 
 use crate::graph::{DeclarationId, DeclarationKind, Graph};
-use petgraph::algo::tarjan_scc;
 use std::collections::HashSet;
 use tracing::debug;
 
@@ -44,13 +43,13 @@ impl CycleDetector {
         graph: &Graph,
         reachable: &HashSet<DeclarationId>,
     ) -> Vec<CycleInfo> {
-        // Use Tarjan's algorithm to find strongly connected components
+        // Strongly connected components, shared with reachability analysis
         let inner = graph.inner();
-        let sccs = tarjan_scc(inner);
+        let condensation = graph.condensation();
 
         let mut dead_cycles = Vec::new();
 
-        for scc in sccs {
+        for (component, scc) in condensation.components().enumerate() {
             // Skip single-node SCCs (not really cycles, unless self-referential)
             if scc.len() < 2 {
                 continue;
@@ -71,10 +70,7 @@ impl CycleDetector {
             }
 
             // Check if this cycle has any external incoming edges
-            let member_set: HashSet<_> = member_ids.iter().collect();
-            let has_external_reference = self.has_external_incoming_edge(graph, &member_set);
-
-            if has_external_reference {
+            if condensation.is_referenced_externally(component) {
                 // Something outside the cycle references it
                 continue;
             }
@@ -112,25 +108,11 @@ impl CycleDetector {
         }
 
         // Sort by size (largest first)
-        dead_cycles.sort_by(|a, b| b.size.cmp(&a.size));
+        dead_cycles.sort_by_key(|c| std::cmp::Reverse(c.size));
 
         dead_cycles
     }
 
-    /// Check if any declaration outside the given set references a member of the set
-    fn has_external_incoming_edge(&self, graph: &Graph, members: &HashSet<&DeclarationId>) -> bool {
-        for member_id in members {
-            let refs = graph.get_references_to(member_id);
-            for (ref_decl, _) in refs {
-                if !members.contains(&ref_decl.id) {
-                    // External reference found
-                    return true;
-                }
-            }
-        }
-        false
-    }
-
     /// Find potential zombie code - declarations that only reference each other
     /// without being part of a proper cycle (for smaller mutual references)
     pub fn find_zombie_pairs(
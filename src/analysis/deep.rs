@@ -6,7 +6,10 @@
 // 3. Detects unused members even in reachable classes
 // 4. Uses heuristics for common dead code patterns
 
+use super::reachability::{default_retain_annotations, with_retained_roots};
 use super::{Confidence, DeadCode, DeadCodeIssue};
+use crate::cancel::CancellationToken;
+use crate::discovery::SourceSetKind;
 use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph, Language, ReferenceKind};
 use petgraph::visit::Dfs;
 use rayon::prelude::*;
@@ -19,6 +22,9 @@ pub struct DeepAnalyzer {
     detect_unused_members: bool,
     /// Use parallel processing
     parallel: bool,
+    /// Annotations whose declarations are reachability roots
+    retain_annotations: Vec<String>,
+    cancel: CancellationToken,
 }
 
 impl DeepAnalyzer {
@@ -26,6 +32,8 @@ impl DeepAnalyzer {
         Self {
             detect_unused_members: true,
             parallel: true,
+            retain_annotations: default_retain_annotations(),
+            cancel: CancellationToken::new(),
         }
     }
 
@@ -39,6 +47,22 @@ impl DeepAnalyzer {
         self
     }
 
+    /// Annotations whose declarations (and the members of annotated
+    /// types) are never reported
+    pub fn with_retain_annotations(mut self, annotations: Vec<String>) -> Self {
+        self.retain_annotations = annotations;
+        self
+    }
+
+    /// Skip the remaining steps once `cancel` fires
+    ///
+    /// The results of a cancelled run are incomplete; callers check the
+    /// token afterwards.
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
     /// Analyze the graph and find dead code
     pub fn analyze(
         &self,
@@ -48,6 +72,7 @@ impl DeepAnalyzer {
         info!("Running deep analysis...");
 
         // Step 1: Find truly reachable declarations (not all class members)
+        let entry_points = &with_retained_roots(graph, entry_points, &self.retain_annotations);
         let reachable = self.find_reachable_strict(graph, entry_points);
 
         info!(
@@ -55,12 +80,15 @@ impl DeepAnalyzer {
             reachable.len(),
             graph.declarations().count()
         );
+        if self.cancel.is_cancelled() {
+            return (Vec::new(), reachable);
+        }
 
         // Step 2: Find unreachable declarations
         let mut dead_code = self.find_unreachable(graph, &reachable);
 
         // Step 3: Find unused members in reachable classes
-        if self.detect_unused_members {
+        if self.detect_unused_members && !self.cancel.is_cancelled() {
             let unused_members = self.find_unused_members(graph, &reachable);
             info!(
                 "Found {} unused members in reachable classes",
@@ -160,7 +188,10 @@ impl DeepAnalyzer {
             .cloned()
             .collect();
 
-        for type_id in reachable_types {
+        for type_id in reachable_types
+            .into_iter()
+            .take_while(|_| !self.cancel.is_cancelled())
+        {
             // Find all members of this type
             for decl in graph.declarations() {
                 if decl.parent.as_ref() == Some(&type_id) {
@@ -460,7 +491,10 @@ impl DeepAnalyzer {
     ) -> Vec<DeadCode> {
         let mut unused = Vec::new();
 
-        for decl in graph.declarations() {
+        for decl in graph
+            .declarations()
+            .take_while(|_| !self.cancel.is_cancelled())
+        {
             // Skip if already marked unreachable
             if !reachable.contains(&decl.id) {
                 continue;
@@ -503,8 +537,23 @@ impl DeepAnalyzer {
                 continue;
             }
 
-            // Skip const val (inlined at compile time)
-            if self.is_const_val(decl) {
+            // Skip const val - the dedicated UnusedConstantDetector reports them
+            if decl.is_const() {
+                continue;
+            }
+
+            // Skip extensions - the dedicated UnusedExtensionDetector reports them
+            if decl.is_extension() {
+                continue;
+            }
+
+            // Skip enum constants - the dedicated UnusedEnumCaseDetector reports them
+            if decl.kind == DeclarationKind::EnumCase {
+                continue;
+            }
+
+            // Skip test sources - the UnusedTestHelperDetector reports them
+            if decl.is_test_source() {
                 continue;
             }
 
@@ -526,11 +575,6 @@ impl DeepAnalyzer {
                 continue;
             }
 
-            // Skip declarations with @VisibleForTesting
-            if decl.annotations.iter().any(|a| a.contains("VisibleForTesting")) {
-                continue;
-            }
-
             // Skip test methods and test rules (called by test framework)
             let is_test = decl.annotations.iter().any(|a| {
                 a.contains("Test") || a.contains("Before") || a.contains("After")
@@ -605,7 +649,10 @@ impl DeepAnalyzer {
     ) -> Vec<DeadCode> {
         let mut pattern_dead = Vec::new();
 
-        for decl in graph.declarations() {
+        for decl in graph
+            .declarations()
+            .take_while(|_| !self.cancel.is_cancelled())
+        {
             if reachable.contains(&decl.id) {
                 continue;
             }
@@ -688,8 +735,9 @@ impl DeepAnalyzer {
         }
 
         // Check if in debug source set
-        let file_path = decl.location.file.to_string_lossy();
-        if file_path.contains("/debug/") || file_path.contains("/staging/") {
+        if decl.source_set == SourceSetKind::Debug
+            || decl.location.file.to_string_lossy().contains("/staging/")
+        {
             return true;
         }
 
@@ -710,8 +758,7 @@ impl DeepAnalyzer {
         ];
 
         // Only flag if in main source (not in test directories)
-        let file_path = decl.location.file.to_string_lossy();
-        if file_path.contains("/test/") || file_path.contains("/androidTest/") {
+        if decl.is_test_source() {
             return false;
         }
 
@@ -786,8 +833,29 @@ impl DeepAnalyzer {
             }
         }
 
-        // Skip Kotlin const val properties (they are inlined at compile time)
-        if self.is_const_val(decl) {
+        // Skip Kotlin const val properties - let the dedicated UnusedConstantDetector
+        // handle them, so each unused constant is reported once under its own rule
+        if decl.is_const() {
+            return true;
+        }
+
+        // Extensions with no call sites are reported by the UnusedExtensionDetector
+        if decl.is_extension() && !graph.is_referenced(&decl.id) {
+            return true;
+        }
+
+        // Type aliases with no references are reported by the UnusedTypeAliasDetector
+        if decl.kind == DeclarationKind::TypeAlias && !graph.is_referenced(&decl.id) {
+            return true;
+        }
+
+        // Enum constants are reported by the UnusedEnumCaseDetector
+        if decl.kind == DeclarationKind::EnumCase {
+            return true;
+        }
+
+        // Test sources are reported by the UnusedTestHelperDetector
+        if decl.is_test_source() {
             return true;
         }
 
@@ -814,11 +882,6 @@ impl DeepAnalyzer {
             return true;
         }
 
-        // Skip declarations with @VisibleForTesting - they are public only for testing
-        if decl.annotations.iter().any(|a| a.contains("VisibleForTesting")) {
-            return true;
-        }
-
         // Skip test methods and test rules (called by test framework)
         if decl.annotations.iter().any(|a| {
             a.contains("Test") || a.contains("Before") || a.contains("After")
@@ -833,22 +896,6 @@ impl DeepAnalyzer {
         false
     }
 
-    /// Check if a declaration is a Kotlin const val property
-    /// These are inlined at compile time, so they appear unused even when used
-    fn is_const_val(&self, decl: &Declaration) -> bool {
-        if decl.kind != DeclarationKind::Property {
-            return false;
-        }
-
-        // Only Kotlin has const val
-        if decl.language != Language::Kotlin {
-            return false;
-        }
-
-        // Check for const modifier
-        decl.modifiers.iter().any(|m| m == "const")
-    }
-
     /// Check if a declaration is a data class
     fn is_data_class(&self, decl: &Declaration) -> bool {
         if decl.kind != DeclarationKind::Class {
@@ -909,68 +956,13 @@ impl DeepAnalyzer {
         graph: &Graph,
         reachable: &HashSet<DeclarationId>,
     ) -> HashSet<DeclarationId> {
-        // First, find all sealed classes that are reachable - build a HashSet for O(1) lookup
-        let sealed_names: HashSet<String> = graph
+        graph
             .declarations()
             .filter(|d| reachable.contains(&d.id) && self.is_sealed_class(d))
-            .flat_map(|d| {
-                let fqn = d
-                    .fully_qualified_name
-                    .clone()
-                    .unwrap_or_else(|| d.name.clone());
-                let simple = fqn.split('.').next_back().unwrap_or(&fqn).to_string();
-                vec![fqn, simple]
-            })
-            .collect();
-
-        if sealed_names.is_empty() {
-            return HashSet::new();
-        }
-
-        // Find all classes that extend these sealed classes - single pass with HashSet lookups
-        let declarations: Vec<_> = graph.declarations().collect();
-
-        if self.parallel {
-            declarations
-                .par_iter()
-                .filter_map(|decl| {
-                    if reachable.contains(&decl.id) {
-                        return None;
-                    }
-
-                    for super_type in &decl.super_types {
-                        if sealed_names.contains(super_type) {
-                            return Some(decl.id.clone());
-                        }
-                        let simple = super_type.split('.').next_back().unwrap_or(super_type);
-                        if sealed_names.contains(simple) {
-                            return Some(decl.id.clone());
-                        }
-                    }
-                    None
-                })
-                .collect()
-        } else {
-            declarations
-                .iter()
-                .filter_map(|decl| {
-                    if reachable.contains(&decl.id) {
-                        return None;
-                    }
-
-                    for super_type in &decl.super_types {
-                        if sealed_names.contains(super_type) {
-                            return Some(decl.id.clone());
-                        }
-                        let simple = super_type.split('.').next_back().unwrap_or(super_type);
-                        if sealed_names.contains(simple) {
-                            return Some(decl.id.clone());
-                        }
-                    }
-                    None
-                })
-                .collect()
-        }
+            .flat_map(|d| graph.subtypes(&d.id))
+            .filter(|subtype| !reachable.contains(&subtype.id))
+            .map(|subtype| subtype.id.clone())
+            .collect()
     }
 
     /// Find all interface implementations and mark them as reachable when the interface is reachable
@@ -979,68 +971,13 @@ impl DeepAnalyzer {
         graph: &Graph,
         reachable: &HashSet<DeclarationId>,
     ) -> HashSet<DeclarationId> {
-        // Build a HashSet of interface names for O(1) lookup
-        let interface_names: HashSet<String> = graph
+        graph
             .declarations()
             .filter(|d| reachable.contains(&d.id) && d.kind == DeclarationKind::Interface)
-            .flat_map(|d| {
-                let fqn = d
-                    .fully_qualified_name
-                    .clone()
-                    .unwrap_or_else(|| d.name.clone());
-                let simple = fqn.split('.').next_back().unwrap_or(&fqn).to_string();
-                vec![fqn, simple]
-            })
-            .collect();
-
-        if interface_names.is_empty() {
-            return HashSet::new();
-        }
-
-        // Find all classes that implement these interfaces - single pass with HashSet lookups
-        let declarations: Vec<_> = graph.declarations().collect();
-
-        if self.parallel {
-            declarations
-                .par_iter()
-                .filter_map(|decl| {
-                    if reachable.contains(&decl.id) {
-                        return None;
-                    }
-
-                    for super_type in &decl.super_types {
-                        if interface_names.contains(super_type) {
-                            return Some(decl.id.clone());
-                        }
-                        let simple = super_type.split('.').next_back().unwrap_or(super_type);
-                        if interface_names.contains(simple) {
-                            return Some(decl.id.clone());
-                        }
-                    }
-                    None
-                })
-                .collect()
-        } else {
-            declarations
-                .iter()
-                .filter_map(|decl| {
-                    if reachable.contains(&decl.id) {
-                        return None;
-                    }
-
-                    for super_type in &decl.super_types {
-                        if interface_names.contains(super_type) {
-                            return Some(decl.id.clone());
-                        }
-                        let simple = super_type.split('.').next_back().unwrap_or(super_type);
-                        if interface_names.contains(simple) {
-                            return Some(decl.id.clone());
-                        }
-                    }
-                    None
-                })
-                .collect()
-        }
+            .flat_map(|d| graph.subtypes(&d.id))
+            .filter(|implementation| !reachable.contains(&implementation.id))
+            .map(|implementation| implementation.id.clone())
+            .collect()
     }
 
     /// Check if a function is a suspend function (used in coroutines)
@@ -1122,8 +1059,7 @@ impl DeepAnalyzer {
             // Event handlers
             "Subscribe",
             "OnClick",
-            // Compose
-            "Composable",
+            // Compose (composables themselves are reached through their call sites)
             "Preview",
         ];
 
@@ -1168,4 +1104,77 @@ mod tests {
         let (dead_code, _) = analyzer.analyze(&graph, &entry_points);
         assert!(dead_code.is_empty());
     }
+
+    #[test]
+    fn test_dead_composable_trees() {
+        use crate::analysis::EntryPointDetector;
+        use crate::config::Config;
+        use crate::discovery::{FileType, SourceFile};
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("HomeScreen.kt");
+        std::fs::write(
+            &path,
+            r#"package com.app.ui
+
+class MainActivity : ComponentActivity() {
+    override fun onCreate(savedInstanceState: Bundle?) {
+        setContent { HomeScreen() }
+    }
+}
+
+@Composable
+fun HomeScreen() {
+    Scaffold(topBar = { HomeTopBar() }) { padding ->
+        LazyColumn { item { FeedRow() } }
+    }
+}
+
+@Composable
+fun HomeTopBar() {}
+
+@Composable
+fun FeedRow() {}
+
+@Composable
+fun LegacyScreen() {
+    LegacyHeader()
+}
+
+@Composable
+fun LegacyHeader() {}
+
+@Preview
+@Composable
+fun FeedRowPreview() {
+    FeedRow()
+}
+"#,
+        )
+        .unwrap();
+
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(path, FileType::Kotlin))
+            .unwrap();
+        let graph = builder.build();
+        let config = Config::default();
+        let entry_points = EntryPointDetector::new(&config)
+            .with_composable_entry_points(false)
+            .detect(&graph, temp.path())
+            .unwrap();
+
+        let (dead_code, _) = DeepAnalyzer::new()
+            .with_parallel(false)
+            .analyze(&graph, &entry_points);
+
+        let mut dead: Vec<_> = dead_code
+            .iter()
+            .filter(|d| d.declaration.kind == DeclarationKind::Function)
+            .map(|d| d.declaration.name.as_str())
+            .collect();
+        dead.sort_unstable();
+        assert_eq!(dead, ["LegacyHeader", "LegacyScreen"]);
+    }
 }
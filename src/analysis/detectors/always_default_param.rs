@@ -0,0 +1,354 @@
+//! Always-Default Parameter Detector
+//!
+//! Detects Kotlin parameters with a default value that no call site ever
+//! overrides. Such a parameter is dead configuration: it can be removed and
+//! its default inlined into the function body. A parameter that every call
+//! passes the same literal to is reported as well, with low confidence,
+//! since the literal could become the body's constant instead.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find functions and constructors with defaulted parameters
+//! 2. Skip functions used other than by direct calls (function references,
+//!    Java callers, calls whose arguments weren't parsed)
+//! 3. Bind each call's arguments to parameters: named arguments by name,
+//!    the rest by position, a trailing lambda to the last parameter. Calls
+//!    that don't fit the signature belong to another overload and are ignored
+//! 4. Report defaulted parameters no fitting call binds
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! fun load(id: String, retries: Int = 3, cache: Boolean = true) { ... }
+//!
+//! load("a")
+//! load("b", retries = 5)   // `cache` is never passed
+//! ```
+
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{CallArguments, Declaration, DeclarationKind, Graph, Language, ReferenceKind};
+
+/// Detector for defaulted parameters no call site overrides
+pub struct AlwaysDefaultParamDetector;
+
+impl AlwaysDefaultParamDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Findings for the parameters of one function
+    fn check_function(graph: &Graph, function: &Declaration) -> Vec<DeadCode> {
+        let mut params: Vec<&Declaration> = graph
+            .get_children(&function.id)
+            .into_iter()
+            .filter_map(|id| graph.get_declaration(id))
+            .filter(|d| d.kind == DeclarationKind::Parameter)
+            .collect();
+        if !params.iter().any(|p| has_modifier(p, "default")) {
+            return Vec::new();
+        }
+        params.sort_by_key(|p| p.id.start);
+
+        let mut calls = Vec::new();
+        for (from, reference) in graph.get_references_to(&function.id) {
+            match (&reference.kind, &reference.arguments) {
+                (ReferenceKind::Import, _) => {}
+                (ReferenceKind::Call, Some(arguments)) if from.language == Language::Kotlin => {
+                    calls.push(arguments)
+                }
+                // Function references, Java callers or unparsed arguments
+                _ => return Vec::new(),
+            }
+        }
+
+        let bindings: Vec<Vec<Option<&str>>> = calls
+            .into_iter()
+            .filter_map(|call| bind(&params, call))
+            .collect();
+        if bindings.is_empty() {
+            return Vec::new();
+        }
+        let composable = function.has_annotation("Composable");
+
+        let mut issues = Vec::new();
+        for (index, param) in params.iter().enumerate() {
+            if !has_modifier(param, "default") || (composable && param.name == "modifier") {
+                continue;
+            }
+            let values: Vec<Option<&str>> = bindings.iter().map(|b| b[index]).collect();
+            let (message, confidence) = if values.iter().all(Option::is_none) {
+                (
+                    format!(
+                        "Parameter '{}' of '{}' always uses its default value",
+                        param.name, function.name
+                    ),
+                    Confidence::Medium,
+                )
+            } else {
+                match values[0] {
+                    Some(first)
+                        if values.len() >= 2
+                            && is_literal(first)
+                            && values.iter().all(|v| *v == Some(first)) =>
+                    {
+                        (
+                            format!(
+                                "Parameter '{}' of '{}' is always passed {}",
+                                param.name, function.name, first
+                            ),
+                            Confidence::Low,
+                        )
+                    }
+                    _ => continue,
+                }
+            };
+            issues.push(
+                DeadCode::new((*param).clone(), DeadCodeIssue::AlwaysDefaultParam)
+                    .with_message(message)
+                    .with_confidence(confidence),
+            );
+        }
+        issues
+    }
+}
+
+impl Default for AlwaysDefaultParamDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Detector for AlwaysDefaultParamDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::AlwaysDefaultParam)
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues: Vec<DeadCode> = graph
+            .declarations()
+            .filter(|decl| decl.kind.is_callable() && decl.language == Language::Kotlin)
+            .flat_map(|function| Self::check_function(graph, function))
+            .collect();
+
+        issues.sort_by(|a, b| {
+            let (a, b) = (&a.declaration.location, &b.declaration.location);
+            a.file.cmp(&b.file).then(a.start_byte.cmp(&b.start_byte))
+        });
+        issues
+    }
+}
+
+/// The argument bound to each parameter, or `None` when the call doesn't
+/// fit the parameter list
+fn bind<'a>(params: &[&Declaration], call: &'a CallArguments) -> Option<Vec<Option<&'a str>>> {
+    let mut bound: Vec<Option<&str>> = vec![None; params.len()];
+    let mut is_bound = vec![false; params.len()];
+    let mut next = 0;
+
+    for argument in &call.arguments {
+        let index = match &argument.name {
+            Some(name) => params.iter().position(|p| &p.name == name)?,
+            None => {
+                let index = next.min(params.len().checked_sub(1)?);
+                if next >= params.len() && !has_modifier(params[index], "vararg") {
+                    return None;
+                }
+                if !has_modifier(params[index], "vararg") {
+                    next += 1;
+                }
+                index
+            }
+        };
+        // Varargs take several values, so they never count as one literal
+        bound[index] = if has_modifier(params[index], "vararg") || is_bound[index] {
+            Some("")
+        } else {
+            Some(argument.value.as_str())
+        };
+        is_bound[index] = true;
+    }
+
+    if call.trailing_lambda {
+        let last = params.len().checked_sub(1)?;
+        if is_bound[last] {
+            return None;
+        }
+        bound[last] = Some("");
+        is_bound[last] = true;
+    }
+
+    // Every parameter without a default must be passed
+    params
+        .iter()
+        .zip(&is_bound)
+        .all(|(p, bound)| *bound || has_modifier(p, "default") || has_modifier(p, "vararg"))
+        .then_some(bound)
+}
+
+fn has_modifier(decl: &Declaration, modifier: &str) -> bool {
+    decl.modifiers.iter().any(|m| m == modifier)
+}
+
+/// Number, boolean, `null`, character or template-free string literal
+fn is_literal(value: &str) -> bool {
+    matches!(value, "true" | "false" | "null")
+        || value
+            .trim_start_matches('-')
+            .starts_with(|c: char| c.is_ascii_digit())
+        || (value.len() >= 2
+            && value.starts_with('"')
+            && value.ends_with('"')
+            && !value.contains('$'))
+        || (value.len() >= 3 && value.starts_with('\'') && value.ends_with('\''))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{CallArgument, DeclarationId, Location, Reference};
+    use std::path::PathBuf;
+
+    fn declaration(
+        graph: &mut Graph,
+        name: &str,
+        kind: DeclarationKind,
+        start: usize,
+        parent: Option<&DeclarationId>,
+        modifiers: &[&str],
+    ) -> DeclarationId {
+        let file = PathBuf::from("Loader.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), start, start + 10),
+            name.to_string(),
+            kind,
+            Location::new(file, start, 1, start, start + 10),
+            Language::Kotlin,
+        );
+        decl.parent = parent.cloned();
+        decl.modifiers = modifiers.iter().map(|m| m.to_string()).collect();
+        graph.add_declaration(decl)
+    }
+
+    fn call(graph: &mut Graph, to: &DeclarationId, args: &[(Option<&str>, &str)], lambda: bool) {
+        let start = 900 + graph.declarations().count() * 20;
+        let caller = declaration(graph, "caller", DeclarationKind::Function, start, None, &[]);
+        let arguments = CallArguments {
+            arguments: args
+                .iter()
+                .map(|(name, value)| CallArgument {
+                    name: name.map(str::to_string),
+                    value: value.to_string(),
+                    spread: false,
+                })
+                .collect(),
+            trailing_lambda: lambda,
+        };
+        let location = Location::new(caller.file.clone(), 1, 1, caller.start, caller.end);
+        graph.add_reference(
+            &caller,
+            to,
+            Reference::new(ReferenceKind::Call, location, String::new())
+                .with_arguments(Some(arguments)),
+        );
+    }
+
+    #[test]
+    fn test_always_default_params() {
+        let mut graph = Graph::new();
+        let load = declaration(&mut graph, "load", DeclarationKind::Function, 10, None, &[]);
+        let param = |graph: &mut Graph, name: &str, start: usize, modifiers: &[&str]| {
+            declaration(
+                graph,
+                name,
+                DeclarationKind::Parameter,
+                start,
+                Some(&load),
+                modifiers,
+            );
+        };
+        param(&mut graph, "id", 11, &[]);
+        param(&mut graph, "retries", 12, &["default"]);
+        param(&mut graph, "cache", 13, &["default"]);
+        param(&mut graph, "tag", 14, &["default"]);
+        param(&mut graph, "onDone", 15, &["default"]);
+
+        call(&mut graph, &load, &[(None, "\"a\"")], false);
+        call(&mut graph, &load, &[(None, "\"b\""), (None, "5")], true);
+        call(
+            &mut graph,
+            &load,
+            &[(None, "\"c\""), (Some("tag"), "\"sync\"")],
+            false,
+        );
+        call(
+            &mut graph,
+            &load,
+            &[(None, "id"), (Some("tag"), "\"sync\"")],
+            false,
+        );
+        // Another overload's call: too many arguments
+        call(
+            &mut graph,
+            &load,
+            &(0..6).map(|_| (None, "1")).collect::<Vec<_>>(),
+            false,
+        );
+
+        let issues = AlwaysDefaultParamDetector::new().detect(&graph);
+
+        let found: Vec<_> = issues
+            .iter()
+            .map(|i| (i.message.as_str(), i.confidence))
+            .collect();
+        assert_eq!(
+            found,
+            [(
+                "Parameter 'cache' of 'load' always uses its default value",
+                Confidence::Medium
+            )]
+        );
+
+        // Once a function is referenced other than by a parsed call, it is skipped
+        let caller = declaration(&mut graph, "ref", DeclarationKind::Function, 950, None, &[]);
+        let location = Location::new(caller.file.clone(), 1, 1, 950, 960);
+        graph.add_reference(
+            &caller,
+            &load,
+            Reference::new(ReferenceKind::Call, location, String::new()),
+        );
+        assert!(AlwaysDefaultParamDetector::new().detect(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_always_same_literal() {
+        let mut graph = Graph::new();
+        let retry = declaration(
+            &mut graph,
+            "retry",
+            DeclarationKind::Function,
+            10,
+            None,
+            &[],
+        );
+        declaration(
+            &mut graph,
+            "times",
+            DeclarationKind::Parameter,
+            11,
+            Some(&retry),
+            &["default"],
+        );
+        call(&mut graph, &retry, &[(None, "3")], false);
+        call(&mut graph, &retry, &[(Some("times"), "3")], false);
+
+        let issues = AlwaysDefaultParamDetector::new().detect(&graph);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].message,
+            "Parameter 'times' of 'retry' is always passed 3"
+        );
+        assert_eq!(issues[0].confidence, Confidence::Low);
+    }
+}
@@ -1,5 +1,5 @@
-use super::Detector;
-use crate::analysis::DeadCode;
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{DeadCode, DeadCodeIssue};
 use crate::graph::Graph;
 
 pub struct AssignOnlyDetector;
@@ -9,6 +9,10 @@ impl AssignOnlyDetector {
     }
 }
 impl Detector for AssignOnlyDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::AssignOnly)
+    }
+
     fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
         Vec::new()
     }
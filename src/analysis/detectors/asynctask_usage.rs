@@ -27,7 +27,7 @@
 //! - java.util.concurrent.Executor
 //! - WorkManager for persistent work
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Language};
 
@@ -60,6 +60,10 @@ impl Default for AsyncTaskUsageDetector {
 }
 
 impl Detector for AsyncTaskUsageDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::AsyncTaskUsage)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -37,7 +37,7 @@
 //! }
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Language};
 
@@ -92,6 +92,10 @@ impl Default for BusinessLogicInComposableDetector {
 }
 
 impl Detector for BusinessLogicInComposableDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::BusinessLogicInComposable)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -31,7 +31,7 @@
 //! // Single pass, lazy evaluation
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -101,6 +101,10 @@ impl Default for CollectionWithoutSequenceDetector {
 }
 
 impl Detector for CollectionWithoutSequenceDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::CollectionWithoutSequence)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
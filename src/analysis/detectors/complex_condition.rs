@@ -25,7 +25,7 @@
 //! - Create helper methods
 //! - Use extension functions
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -63,6 +63,10 @@ impl Default for ComplexConditionDetector {
 }
 
 impl Detector for ComplexConditionDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::ComplexCondition)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
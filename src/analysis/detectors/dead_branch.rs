@@ -1,5 +1,5 @@
-use super::Detector;
-use crate::analysis::DeadCode;
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{DeadCode, DeadCodeIssue};
 use crate::graph::Graph;
 
 pub struct DeadBranchDetector;
@@ -9,6 +9,10 @@ impl DeadBranchDetector {
     }
 }
 impl Detector for DeadBranchDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::DeadBranch)
+    }
+
     fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
         Vec::new()
     }
@@ -0,0 +1,297 @@
+//! Dead Callback Detector
+//!
+//! Detects listener and callback interfaces that are implemented (or passed
+//! around as lambdas and stored through setters) but whose methods nothing
+//! ever invokes. The interface, its implementations and the setter all look
+//! used to reachability, yet none of the callback code can run.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find project interfaces named like callbacks (`*Listener`,
+//!    `*Callback`, `*Observer`, ...) that don't extend another type, so
+//!    the framework can't be the one calling them
+//! 2. Require the interface to be used: implemented by a class or object,
+//!    or named as a parameter, property or type argument
+//! 3. Report it when none of its methods has a call site
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! interface OnRefreshListener {
+//!     fun onRefresh()
+//! }
+//!
+//! class FeedView {
+//!     private var refreshListener: OnRefreshListener? = null
+//!     fun setOnRefreshListener(listener: OnRefreshListener) {
+//!         refreshListener = listener  // stored, never invoked
+//!     }
+//! }
+//!
+//! class FeedFragment : OnRefreshListener {
+//!     override fun onRefresh() { reload() }  // DEAD: never called
+//! }
+//! ```
+
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Graph, ReferenceKind};
+
+/// Name suffixes of callback interfaces
+const CALLBACK_SUFFIXES: &[&str] = &[
+    "Listener",
+    "Callback",
+    "Callbacks",
+    "Observer",
+    "Delegate",
+    "Handler",
+    "Watcher",
+];
+
+/// Detector for callback interfaces whose methods are never invoked
+pub struct DeadCallbackDetector;
+
+impl DeadCallbackDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Why the callback is dead, if it is
+    fn check(graph: &Graph, interface: &Declaration) -> Option<String> {
+        let methods: Vec<&Declaration> = graph
+            .get_children(&interface.id)
+            .into_iter()
+            .filter_map(|id| graph.get_declaration(id))
+            .filter(|d| d.kind.is_callable())
+            .collect();
+        if methods.is_empty() {
+            return None;
+        }
+
+        // Overrides name the method too, but don't invoke it
+        let invoked = methods.iter().any(|method| {
+            graph.get_references_to(&method.id).iter().any(|(from, r)| {
+                let is_override = from.kind.is_callable() && from.name == method.name;
+                !is_override
+                    && !matches!(
+                        r.kind,
+                        ReferenceKind::Import
+                            | ReferenceKind::Override
+                            | ReferenceKind::Inheritance
+                    )
+            })
+        });
+        if invoked {
+            return None;
+        }
+
+        let mut implementers: Vec<&str> = Vec::new();
+        let mut passed = false;
+        for (from, reference) in graph.get_references_to(&interface.id) {
+            if from.parent.as_ref() == Some(&interface.id) || from.id == interface.id {
+                continue;
+            }
+            match reference.kind {
+                ReferenceKind::Inheritance => {
+                    if !implementers.contains(&from.name.as_str()) {
+                        implementers.push(&from.name);
+                    }
+                }
+                ReferenceKind::Import => {}
+                _ => passed = true,
+            }
+        }
+        for decl in graph.subtypes(&interface.id) {
+            if !implementers.contains(&decl.name.as_str()) {
+                implementers.push(&decl.name);
+            }
+        }
+
+        let names: Vec<&str> = methods.iter().map(|m| m.name.as_str()).collect();
+        if !implementers.is_empty() {
+            implementers.sort_unstable();
+            Some(format!(
+                "Callback '{}' is implemented by '{}' but '{}' is never invoked",
+                interface.name,
+                implementers.join("', '"),
+                names.join("', '")
+            ))
+        } else if passed {
+            Some(format!(
+                "Callback '{}' is passed around but '{}' is never invoked",
+                interface.name,
+                names.join("', '")
+            ))
+        } else {
+            // Entirely unused interfaces are reported as unused classes
+            None
+        }
+    }
+}
+
+impl Default for DeadCallbackDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Detector for DeadCallbackDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::DeadCallback)
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues: Vec<DeadCode> = graph
+            .declarations()
+            .filter(|d| d.kind == DeclarationKind::Interface && !d.is_test_source())
+            .filter(|d| d.super_types.is_empty())
+            .filter(|d| CALLBACK_SUFFIXES.iter().any(|s| d.name.ends_with(s)))
+            .filter_map(|interface| {
+                let message = Self::check(graph, interface)?;
+                Some(
+                    DeadCode::new(interface.clone(), DeadCodeIssue::DeadCallback)
+                        .with_message(message)
+                        .with_confidence(Confidence::Medium),
+                )
+            })
+            .collect();
+
+        issues.sort_by(|a, b| {
+            let (a, b) = (&a.declaration.location, &b.declaration.location);
+            a.file.cmp(&b.file).then(a.start_byte.cmp(&b.start_byte))
+        });
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Language, Location, Reference};
+    use std::path::PathBuf;
+
+    fn declaration(
+        graph: &mut Graph,
+        name: &str,
+        kind: DeclarationKind,
+        line: usize,
+        parent: Option<&DeclarationId>,
+    ) -> DeclarationId {
+        let file = PathBuf::from("Feed.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            kind,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        );
+        decl.parent = parent.cloned();
+        graph.add_declaration(decl)
+    }
+
+    fn reference(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId, kind: ReferenceKind) {
+        let location = Location::new(from.file.clone(), 1, 1, from.start, from.end);
+        graph.add_reference(from, to, Reference::new(kind, location, String::new()));
+    }
+
+    #[test]
+    fn test_dead_callbacks() {
+        let mut graph = Graph::new();
+
+        // Implemented, stored by a setter, never invoked
+        let refresh = declaration(
+            &mut graph,
+            "OnRefreshListener",
+            DeclarationKind::Interface,
+            1,
+            None,
+        );
+        let on_refresh = declaration(
+            &mut graph,
+            "onRefresh",
+            DeclarationKind::Method,
+            2,
+            Some(&refresh),
+        );
+        let fragment = declaration(&mut graph, "FeedFragment", DeclarationKind::Class, 10, None);
+        reference(&mut graph, &fragment, &refresh, ReferenceKind::Inheritance);
+        let override_ = declaration(
+            &mut graph,
+            "onRefresh",
+            DeclarationKind::Method,
+            11,
+            Some(&fragment),
+        );
+        reference(&mut graph, &override_, &on_refresh, ReferenceKind::Call);
+        let setter = declaration(
+            &mut graph,
+            "setOnRefreshListener",
+            DeclarationKind::Method,
+            20,
+            None,
+        );
+        reference(&mut graph, &setter, &refresh, ReferenceKind::ParameterType);
+
+        // Passed as a lambda, never invoked
+        let scroll = declaration(
+            &mut graph,
+            "ScrollCallback",
+            DeclarationKind::Interface,
+            30,
+            None,
+        );
+        declaration(
+            &mut graph,
+            "onScrolled",
+            DeclarationKind::Method,
+            31,
+            Some(&scroll),
+        );
+        reference(&mut graph, &setter, &scroll, ReferenceKind::ParameterType);
+
+        // Invoked
+        let click = declaration(
+            &mut graph,
+            "OnItemClickListener",
+            DeclarationKind::Interface,
+            40,
+            None,
+        );
+        let on_click = declaration(
+            &mut graph,
+            "onItemClick",
+            DeclarationKind::Method,
+            41,
+            Some(&click),
+        );
+        reference(&mut graph, &fragment, &click, ReferenceKind::Inheritance);
+        reference(&mut graph, &setter, &on_click, ReferenceKind::Call);
+
+        // Not used at all: left to the unused class rule
+        let unused = declaration(
+            &mut graph,
+            "LegacyListener",
+            DeclarationKind::Interface,
+            50,
+            None,
+        );
+        declaration(
+            &mut graph,
+            "onLegacy",
+            DeclarationKind::Method,
+            51,
+            Some(&unused),
+        );
+
+        let issues = DeadCallbackDetector::new().detect(&graph);
+
+        let messages: Vec<_> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Callback 'OnRefreshListener' is implemented by 'FeedFragment' but 'onRefresh' is never invoked",
+                "Callback 'ScrollCallback' is passed around but 'onScrolled' is never invoked",
+            ]
+        );
+    }
+}
@@ -27,9 +27,9 @@
 //! - Use delegation for code reuse
 //! - Keep inheritance chains shallow (1-2 levels)
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
-use crate::graph::{DeclarationKind, Graph};
+use crate::graph::{DeclarationId, DeclarationKind, Graph};
 use std::collections::HashMap;
 
 /// Detector for deep inheritance chains
@@ -87,29 +87,14 @@ impl DeepInheritanceDetector {
 
     /// Calculate inheritance depth for a class
     fn calculate_depth(&self, decl: &crate::graph::Declaration, graph: &Graph) -> usize {
-        let mut depth = 0;
-
-        // Count super_types that are in the codebase (not framework classes)
-        for super_type in &decl.super_types {
-            if self.is_framework_class(super_type) {
-                continue;
-            }
-
-            // Try to find this supertype in the graph
-            let super_decls = graph.find_by_name(super_type);
-            if !super_decls.is_empty() {
-                // Found in codebase, add to depth
-                depth += 1;
-
-                // Recursively check parent's depth
-                for super_decl in super_decls {
-                    let parent_depth = self.calculate_depth(super_decl, graph);
-                    depth = depth.max(1 + parent_depth);
-                }
-            }
-        }
-
-        depth
+        // Count super types that are in the codebase (not framework classes)
+        graph
+            .supertypes(&decl.id)
+            .into_iter()
+            .filter(|super_decl| !self.is_framework_class(&super_decl.name))
+            .map(|super_decl| 1 + self.calculate_depth(super_decl, graph))
+            .max()
+            .unwrap_or(0)
     }
 }
 
@@ -120,11 +105,15 @@ impl Default for DeepInheritanceDetector {
 }
 
 impl Detector for DeepInheritanceDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::DeepInheritance)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues = Vec::new();
 
         // Build inheritance depth cache
-        let mut depth_cache: HashMap<String, usize> = HashMap::new();
+        let mut depth_cache: HashMap<&DeclarationId, usize> = HashMap::new();
 
         // Find all classes
         for decl in graph.declarations() {
@@ -138,11 +127,11 @@ impl Detector for DeepInheritanceDetector {
             }
 
             // Calculate inheritance depth
-            let depth = if let Some(&cached) = depth_cache.get(&decl.name) {
+            let depth = if let Some(&cached) = depth_cache.get(&decl.id) {
                 cached
             } else {
                 let d = self.calculate_depth(decl, graph);
-                depth_cache.insert(decl.name.clone(), d);
+                depth_cache.insert(&decl.id, d);
                 d
             };
 
@@ -16,7 +16,7 @@
 //! import kotlin.collections.List  // DUPLICATE
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 use std::collections::{HashMap, HashSet};
@@ -37,6 +37,10 @@ impl Default for DuplicateImportDetector {
 }
 
 impl Detector for DuplicateImportDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::DuplicateImport)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues = Vec::new();
 
@@ -118,6 +122,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::default_constructed_unit_structs)]
     fn test_detector_creation() {
         let detector = DuplicateImportDetector::new();
         let default_detector = DuplicateImportDetector::default();
@@ -28,7 +28,7 @@
 //! - Navigation component for navigation events
 //! - ViewModel + LiveData for UI state
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -82,6 +82,10 @@ impl Default for EventBusPatternDetector {
 }
 
 impl Detector for EventBusPatternDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::EventBusPattern)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues = Vec::new();
 
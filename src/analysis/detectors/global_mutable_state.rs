@@ -26,7 +26,7 @@
 //! - Use private `var` with controlled access
 //! - Use proper state management (ViewModel, StateFlow)
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Visibility};
 
@@ -96,6 +96,10 @@ impl Default for GlobalMutableStateDetector {
 }
 
 impl Detector for GlobalMutableStateDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::GlobalMutableState)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues = Vec::new();
 
@@ -31,7 +31,7 @@
 //! - Use custom CoroutineScope with proper cancellation
 //! - Use WorkManager for background work that should survive process death
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -55,12 +55,10 @@ impl GlobalScopeUsageDetector {
         self
     }
 
-    /// Check if file is a test file
+    /// Check if the file name marks a test file
     fn is_test_file(path: &std::path::Path) -> bool {
         let path_str = path.to_string_lossy();
-        path_str.contains("/test/")
-            || path_str.contains("/androidTest/")
-            || path_str.contains("Test.kt")
+        path_str.contains("Test.kt")
             || path_str.contains("Tests.kt")
             || path_str.ends_with("Spec.kt")
     }
@@ -83,13 +81,17 @@ impl Default for GlobalScopeUsageDetector {
 }
 
 impl Detector for GlobalScopeUsageDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::GlobalScopeUsage)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
         // Check declarations for GlobalScope usage
         for decl in graph.declarations() {
             // Skip test files - runBlocking and GlobalScope are OK in tests
-            if Self::is_test_file(&decl.location.file) {
+            if decl.is_test_source() || Self::is_test_file(&decl.location.file) {
                 continue;
             }
 
@@ -130,16 +132,16 @@ impl Detector for GlobalScopeUsageDetector {
             }
 
             // Check properties/fields for GlobalScope references
-            if matches!(decl.kind, DeclarationKind::Property | DeclarationKind::Field) {
-                if Self::indicates_globalscope(&decl.name) {
-                    let mut dead = DeadCode::new(decl.clone(), DeadCodeIssue::GlobalScopeUsage);
-                    dead = dead.with_message(format!(
-                        "Property '{}' references GlobalScope. Consider using a lifecycle-aware scope.",
-                        decl.name
-                    ));
-                    dead = dead.with_confidence(Confidence::Medium);
-                    issues.push(dead);
-                }
+            if matches!(decl.kind, DeclarationKind::Property | DeclarationKind::Field)
+                && Self::indicates_globalscope(&decl.name)
+            {
+                let mut dead = DeadCode::new(decl.clone(), DeadCodeIssue::GlobalScopeUsage);
+                dead = dead.with_message(format!(
+                    "Property '{}' references GlobalScope. Consider using a lifecycle-aware scope.",
+                    decl.name
+                ));
+                dead = dead.with_confidence(Confidence::Medium);
+                issues.push(dead);
             }
         }
 
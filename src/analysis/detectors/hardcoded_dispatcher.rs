@@ -35,7 +35,7 @@
 //! }
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -66,8 +66,9 @@ impl HardcodedDispatcherDetector {
 
     /// Check if in a test file
     fn is_test_file(decl: &crate::graph::Declaration) -> bool {
-        let file_path = decl.location.file.to_string_lossy().to_lowercase();
-        file_path.contains("test") || file_path.contains("androidtest")
+        let file_name = decl.location.file.file_stem().unwrap_or_default();
+        let file_name = file_name.to_string_lossy();
+        decl.is_test_source() || file_name.ends_with("Test") || file_name.ends_with("Tests")
     }
 }
 
@@ -78,6 +79,10 @@ impl Default for HardcodedDispatcherDetector {
 }
 
 impl Detector for HardcodedDispatcherDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::HardcodedDispatcher)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
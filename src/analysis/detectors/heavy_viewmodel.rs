@@ -32,7 +32,7 @@
 //! - Use use cases / interactors to encapsulate business logic
 //! - Facade pattern to group related dependencies
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 use std::collections::HashMap;
@@ -100,6 +100,10 @@ impl Default for HeavyViewModelDetector {
 }
 
 impl Detector for HeavyViewModelDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::HeavyViewModel)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
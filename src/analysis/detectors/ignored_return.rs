@@ -28,11 +28,20 @@
 //! adapter.submitList(sorted)
 //! ```
 
-use super::Detector;
-use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue, Severity};
 use crate::graph::{DeclarationKind, Graph, ReferenceKind};
 use std::collections::HashSet;
 
+/// Ignored return values have no dedicated issue kind yet
+const METADATA: DetectorMetadata = DetectorMetadata::new(
+    "DC017",
+    "Ignored return values",
+    "Dead Code",
+    Severity::Warning,
+)
+.with_enabled_by_default(false);
+
 /// Functions that return a transformed collection (pure functions with no side effects)
 const PURE_COLLECTION_FUNCTIONS: &[&str] = &[
     // Transformations
@@ -220,6 +229,10 @@ impl Default for IgnoredReturnValueDetector {
 }
 
 impl Detector for IgnoredReturnValueDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        METADATA
+    }
+
     fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
         // This detector requires AST-level analysis that we don't have in the graph
         // The graph tracks declarations and references, but not expression statements
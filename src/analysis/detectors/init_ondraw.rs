@@ -31,7 +31,7 @@
 //! }
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Language};
 
@@ -83,6 +83,10 @@ impl Default for InitOnDrawDetector {
 }
 
 impl Detector for InitOnDrawDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::InitOnDraw)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
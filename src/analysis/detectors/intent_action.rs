@@ -0,0 +1,354 @@
+//! Custom Intent Action Detector
+//!
+//! Detects custom intent actions that are sent but never handled, or
+//! handled but never sent. Actions are matched by value, so a constant
+//! (`ACTION_SYNC = "com.app.action.SYNC"`), its qualified uses
+//! (`SyncReceiver.ACTION_SYNC`) and the raw string all count as one action.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Collect custom `ACTION_*` string constants
+//! 2. Collect send sites: `Intent(ACTION_X)`, `setAction(ACTION_X)`,
+//!    `intent.action = ACTION_X`
+//! 3. Collect handlers: `<action android:name>` in manifest intent-filters,
+//!    `IntentFilter(ACTION_X)` / `addAction(ACTION_X)` registrations and
+//!    receiver branches (`ACTION_X ->`, `== ACTION_X`, `ACTION_X.equals(..)`,
+//!    `case ACTION_X:`)
+//! 4. Report actions found on only one side
+//!
+//! Framework actions (`android.*`, `Intent.ACTION_VIEW`) are never reported.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! const val ACTION_SYNC = "com.app.action.SYNC"
+//! const val ACTION_LEGACY_REFRESH = "com.app.action.LEGACY_REFRESH"
+//!
+//! sendBroadcast(Intent(ACTION_SYNC))             // handled by SyncReceiver
+//! sendBroadcast(Intent(ACTION_LEGACY_REFRESH))   // DEAD: nothing handles it
+//!
+//! class SyncReceiver : BroadcastReceiver() {
+//!     override fun onReceive(context: Context, intent: Intent) {
+//!         when (intent.action) {
+//!             ACTION_SYNC -> sync()
+//!         }
+//!     }
+//! }
+//! ```
+
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::unused_intent_extra::walk_sources;
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Language};
+
+/// Prefixes of framework and Play services actions
+const SYSTEM_ACTION_PREFIXES: &[&str] = &["android.", "com.google.android."];
+
+/// A place an action is sent or handled
+#[derive(Debug, Clone)]
+pub struct ActionLocation {
+    pub file: PathBuf,
+    pub line: usize,
+    /// Action string (e.g., "com.app.action.SYNC")
+    pub action: String,
+    /// Constant naming the action, when it was used by name
+    pub constant: Option<String>,
+}
+
+/// Result of intent action analysis
+#[derive(Debug, Default)]
+pub struct IntentActionAnalysis {
+    /// Actions sent but never handled, at their first send site
+    pub unhandled: Vec<ActionLocation>,
+    /// Actions handled but never sent, at their first handler
+    pub unsent: Vec<ActionLocation>,
+    /// Distinct custom actions sent or handled
+    pub total_actions: usize,
+}
+
+impl IntentActionAnalysis {
+    /// Convert the findings to `DC031` issues
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        let sides = [
+            (&self.unhandled, "is sent but never handled"),
+            (&self.unsent, "is handled but never sent"),
+        ];
+        sides
+            .into_iter()
+            .flat_map(|(sites, problem)| {
+                sites.iter().map(move |site| {
+                    let name = site.constant.as_deref().unwrap_or(&site.action);
+                    let language = Language::from_path(&site.file).unwrap_or(Language::Kotlin);
+                    let decl = Declaration::at_line(
+                        &site.file,
+                        site.line,
+                        name,
+                        DeclarationKind::Property,
+                        language,
+                    );
+                    DeadCode::new(decl, DeadCodeIssue::UnmatchedIntentAction)
+                        .with_message(format!("Intent action '{}' {}", site.action, problem))
+                        .with_confidence(Confidence::Medium)
+                })
+            })
+            .collect()
+    }
+}
+
+/// Detector for custom intent actions without a sender or a handler
+pub struct IntentActionDetector {
+    constant_pattern: Regex,
+    operand_pattern: Regex,
+    assignment_pattern: Regex,
+    manifest_action_pattern: Regex,
+}
+
+impl IntentActionDetector {
+    pub fn new() -> Self {
+        Self {
+            // const val ACTION_SYNC = "..." / static final String ACTION_SYNC = "..."
+            constant_pattern: Regex::new(r#"\b(ACTION_\w+)\s*(?::\s*String\s*)?=\s*"([^"$]+)""#)
+                .unwrap(),
+            // ACTION_SYNC, Keys.ACTION_SYNC or "com.app.action.SYNC"
+            operand_pattern: Regex::new(r#"\b(ACTION_\w+)\b|"((?:\w+\.)+[A-Z][A-Z0-9_]*)""#)
+                .unwrap(),
+            assignment_pattern: Regex::new(r"\baction\s*=[^=]").unwrap(),
+            manifest_action_pattern: Regex::new(r#"<action\s[^>]*android:name="([^"]+)""#).unwrap(),
+        }
+    }
+
+    /// Analyze a directory for unmatched custom intent actions
+    pub fn analyze(&self, root: &Path) -> IntentActionAnalysis {
+        let mut constants: HashMap<String, String> = HashMap::new();
+        let mut sources: Vec<(PathBuf, String)> = Vec::new();
+        walk_sources(root, &["kt", "java"], |path, content| {
+            for caps in self.constant_pattern.captures_iter(content) {
+                constants.insert(caps[1].to_string(), caps[2].to_string());
+            }
+            sources.push((path.to_path_buf(), content.to_string()));
+        });
+
+        let mut sent: BTreeMap<String, ActionLocation> = BTreeMap::new();
+        let mut handled: BTreeMap<String, ActionLocation> = BTreeMap::new();
+
+        for (path, content) in &sources {
+            for (line_num, line) in content.lines().enumerate() {
+                let sites = if is_handler_line(line) {
+                    &mut handled
+                } else if line.contains("Intent(")
+                    || line.contains("setAction(")
+                    || self.assignment_pattern.is_match(line)
+                {
+                    &mut sent
+                } else {
+                    continue;
+                };
+                for location in self.actions_in(line, &constants, path, line_num + 1) {
+                    sites.entry(location.action.clone()).or_insert(location);
+                }
+            }
+        }
+
+        walk_sources(root, &["xml"], |path, content| {
+            if path
+                .file_name()
+                .is_some_and(|name| name == "AndroidManifest.xml")
+            {
+                for (line_num, line) in content.lines().enumerate() {
+                    for caps in self.manifest_action_pattern.captures_iter(line) {
+                        let action = caps[1].to_string();
+                        if is_custom(&action) {
+                            handled.entry(action.clone()).or_insert(ActionLocation {
+                                file: path.to_path_buf(),
+                                line: line_num + 1,
+                                action,
+                                constant: None,
+                            });
+                        }
+                    }
+                }
+            }
+        });
+
+        let total_actions = sent
+            .keys()
+            .chain(handled.keys().filter(|a| !sent.contains_key(*a)))
+            .count();
+        let mut analysis = IntentActionAnalysis {
+            unhandled: sent
+                .iter()
+                .filter(|(action, _)| !handled.contains_key(*action))
+                .map(|(_, location)| location.clone())
+                .collect(),
+            unsent: handled
+                .iter()
+                .filter(|(action, _)| !sent.contains_key(*action))
+                .map(|(_, location)| location.clone())
+                .collect(),
+            total_actions,
+        };
+        for locations in [&mut analysis.unhandled, &mut analysis.unsent] {
+            locations.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        }
+        analysis
+    }
+
+    /// Custom actions named on a line, by constant or by value
+    fn actions_in(
+        &self,
+        line: &str,
+        constants: &HashMap<String, String>,
+        file: &Path,
+        line_num: usize,
+    ) -> Vec<ActionLocation> {
+        self.operand_pattern
+            .captures_iter(line)
+            .filter_map(|caps| {
+                let (action, constant) = match (caps.get(1), caps.get(2)) {
+                    // Constants defined outside the project are framework actions
+                    (Some(name), _) => (constants.get(name.as_str())?.clone(), Some(name)),
+                    (None, Some(value)) => (value.as_str().to_string(), None),
+                    (None, None) => return None,
+                };
+                is_custom(&action).then(|| ActionLocation {
+                    file: file.to_path_buf(),
+                    line: line_num,
+                    action,
+                    constant: constant.map(|c| c.as_str().to_string()),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for IntentActionDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for IntentActionDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnmatchedIntentAction)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.root).to_issues()
+    }
+}
+
+/// Whether a line registers for or branches on an action
+fn is_handler_line(line: &str) -> bool {
+    [
+        "IntentFilter(",
+        "addAction(",
+        "==",
+        ".equals(",
+        "case ",
+        "->",
+    ]
+    .iter()
+    .any(|marker| line.contains(marker))
+}
+
+fn is_custom(action: &str) -> bool {
+    !SYSTEM_ACTION_PREFIXES
+        .iter()
+        .any(|prefix| action.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unmatched_intent_actions() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/src/main/java/com/app/Actions.kt",
+            r#"object Actions {
+    const val ACTION_SYNC = "com.app.action.SYNC"
+    const val ACTION_LEGACY_REFRESH = "com.app.action.LEGACY_REFRESH"
+    const val ACTION_UPLOAD = "com.app.action.UPLOAD"
+    const val ACTION_CLEANUP = "com.app.action.CLEANUP"
+}"#,
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/Scheduler.kt",
+            r#"fun schedule(context: Context) {
+    context.sendBroadcast(Intent(Actions.ACTION_SYNC))
+    context.sendBroadcast(Intent(Actions.ACTION_LEGACY_REFRESH))
+    val upload = Intent(context, UploadReceiver::class.java)
+    upload.action = Actions.ACTION_UPLOAD
+    context.startActivity(Intent(Intent.ACTION_VIEW, uri))
+}"#,
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/SyncReceiver.kt",
+            r#"class SyncReceiver : BroadcastReceiver() {
+    override fun onReceive(context: Context, intent: Intent) {
+        when (intent.action) {
+            Actions.ACTION_SYNC -> sync()
+            Actions.ACTION_CLEANUP -> cleanup()
+        }
+    }
+}"#,
+        );
+        write(
+            root,
+            "app/src/main/AndroidManifest.xml",
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android">
+    <receiver android:name=".UploadReceiver">
+        <intent-filter>
+            <action android:name="com.app.action.UPLOAD" />
+            <action android:name="android.intent.action.BOOT_COMPLETED" />
+            <action android:name="com.app.action.SHARE" />
+        </intent-filter>
+    </receiver>
+</manifest>"#,
+        );
+
+        let analysis = IntentActionDetector::new().analyze(root);
+
+        let unhandled: Vec<_> = analysis
+            .unhandled
+            .iter()
+            .map(|a| (a.constant.as_deref(), a.line))
+            .collect();
+        assert_eq!(unhandled, [(Some("ACTION_LEGACY_REFRESH"), 3)]);
+        let unsent: Vec<_> = analysis.unsent.iter().map(|a| a.action.as_str()).collect();
+        assert_eq!(unsent, ["com.app.action.SHARE", "com.app.action.CLEANUP"]);
+        assert_eq!(analysis.total_actions, 5);
+
+        let issues = analysis.to_issues();
+        let names: Vec<_> = issues
+            .iter()
+            .map(|dc| (dc.declaration.name.as_str(), dc.declaration.language))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("ACTION_LEGACY_REFRESH", Language::Kotlin),
+                ("com.app.action.SHARE", Language::Xml),
+                ("ACTION_CLEANUP", Language::Kotlin)
+            ]
+        );
+        assert_eq!(
+            issues[0].message,
+            "Intent action 'com.app.action.LEGACY_REFRESH' is sent but never handled"
+        );
+        assert!(issues
+            .iter()
+            .all(|dc| dc.issue == DeadCodeIssue::UnmatchedIntentAction));
+    }
+}
@@ -28,7 +28,7 @@
 //! - Apply SOLID principles
 //! - Create domain-specific services
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationId, DeclarationKind, Graph};
 use std::collections::HashMap;
@@ -71,6 +71,10 @@ impl Default for LargeClassDetector {
 }
 
 impl Detector for LargeClassDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::LargeClass)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
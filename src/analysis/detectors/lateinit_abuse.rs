@@ -31,7 +31,7 @@
 //! - Nullable types with default null for truly optional
 //! - Only use lateinit with @Inject annotation for DI
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationId, DeclarationKind, Graph};
 use std::collections::HashMap;
@@ -74,6 +74,10 @@ impl Default for LateinitAbuseDetector {
 }
 
 impl Detector for LateinitAbuseDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::LateinitAbuse)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -30,7 +30,7 @@
 //! }
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Language};
 
@@ -76,6 +76,10 @@ impl Default for LaunchedEffectWithoutKeyDetector {
 }
 
 impl Detector for LaunchedEffectWithoutKeyDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::LaunchedEffectWithoutKey)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -0,0 +1,400 @@
+//! Legacy Parcelable Boilerplate Detector
+//!
+//! Detects hand-written `Parcelable` implementations (`CREATOR`,
+//! `writeToParcel`, `describeContents`) that can be deleted:
+//!
+//! 1. On classes annotated `@Parcelize`, where the Parcelize plugin already
+//!    generates all three
+//! 2. On classes that are never sent through a Parcel: no `putExtra()`,
+//!    `putParcelable()`, `writeParcelable()`, `bundleOf()`, `SavedStateHandle`
+//!    or navigation argument site names them
+//!
+//! A `Parceler` companion is the supported way to customize `@Parcelize`
+//! and is never reported.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! @Parcelize
+//! class User(val name: String) : Parcelable {
+//!     override fun describeContents() = 0  // REDUNDANT: generated by @Parcelize
+//! }
+//!
+//! // Never passed to an Intent, Bundle or Parcel
+//! class Draft(val text: String) : Parcelable {
+//!     override fun writeToParcel(parcel: Parcel, flags: Int) { ... }
+//!     override fun describeContents() = 0
+//!     companion object CREATOR : Parcelable.Creator<Draft> { ... }
+//! }
+//! ```
+
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::discovery::SourceSetKind;
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
+
+/// Members the Parcelable contract requires a class to write by hand
+const BOILERPLATE_MEMBERS: &[&str] = &["CREATOR", "writeToParcel", "describeContents"];
+
+/// APIs that move a Parcelable through a Parcel or Bundle
+const PARCEL_APIS: &[&str] = &[
+    "putExtra",
+    "putParcelable",
+    "getParcelable",
+    "parcelable<",
+    "parcelableArrayList",
+    "writeParcelable",
+    "readParcelable",
+    "writeTypedObject",
+    "readTypedObject",
+    "writeTypedList",
+    "createTypedArrayList",
+    "bundleOf",
+    "SavedStateHandle",
+    "rememberSaveable",
+    "navArgs",
+];
+
+/// A hand-written Parcelable implementation
+#[derive(Debug, Clone)]
+pub struct ParcelableClass {
+    /// The class implementing `Parcelable`
+    pub class: Declaration,
+    /// Its hand-written `CREATOR`, `writeToParcel` and `describeContents`
+    pub boilerplate: Vec<Declaration>,
+    /// Whether the class is also annotated `@Parcelize`
+    pub parcelized: bool,
+}
+
+/// Result of Parcelable boilerplate analysis
+#[derive(Debug, Default)]
+pub struct ParcelableAnalysis {
+    /// Boilerplate on `@Parcelize` classes
+    pub parcelized: Vec<ParcelableClass>,
+    /// Hand-written implementations never sent through a Parcel
+    pub never_parceled: Vec<ParcelableClass>,
+    /// Total classes with hand-written Parcelable members
+    pub total_classes: usize,
+}
+
+impl ParcelableAnalysis {
+    /// Convert the findings to `DC023` issues
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        let redundant = self.parcelized.iter().flat_map(|parcelable| {
+            parcelable.boilerplate.iter().map(|member| {
+                DeadCode::new(member.clone(), DeadCodeIssue::LegacyParcelable)
+                    .with_message(format!(
+                        "'{}' in @Parcelize class '{}' is redundant; the Parcelize plugin generates it",
+                        member.name, parcelable.class.name
+                    ))
+                    .with_confidence(Confidence::High)
+            })
+        });
+        let unsent = self.never_parceled.iter().map(|parcelable| {
+            let members: Vec<&str> = parcelable
+                .boilerplate
+                .iter()
+                .map(|m| m.name.as_str())
+                .collect();
+            DeadCode::new(parcelable.class.clone(), DeadCodeIssue::LegacyParcelable)
+                .with_message(format!(
+                    "Parcelable '{}' is never sent through a Parcel; its hand-written {} can be removed",
+                    parcelable.class.name,
+                    members.join(", ")
+                ))
+                .with_confidence(Confidence::Medium)
+        });
+        redundant.chain(unsent).collect()
+    }
+}
+
+/// Detector for hand-written Parcelable implementations that can be removed
+pub struct LegacyParcelableDetector {
+    type_name_pattern: Regex,
+}
+
+impl LegacyParcelableDetector {
+    pub fn new() -> Self {
+        Self {
+            type_name_pattern: Regex::new(r"\b[A-Z]\w*\b").unwrap(),
+        }
+    }
+
+    /// Analyze the Parcelable classes in `graph` against the sources under `root`
+    pub fn analyze(&self, graph: &Graph, root: &Path) -> ParcelableAnalysis {
+        let classes = parcelable_classes(graph);
+        if classes.is_empty() {
+            return ParcelableAnalysis::default();
+        }
+
+        let parceled = self.parceled_type_names(root);
+
+        let mut analysis = ParcelableAnalysis {
+            total_classes: classes.len(),
+            ..Default::default()
+        };
+        for parcelable in classes {
+            if parcelable.parcelized {
+                analysis.parcelized.push(parcelable);
+            } else if graph.is_referenced(&parcelable.class.id)
+                && !parceled.contains(parcelable.class.name.as_str())
+            {
+                // Unreferenced classes are reported as unused classes instead
+                analysis.never_parceled.push(parcelable);
+            }
+        }
+        analysis
+    }
+
+    /// Type names mentioned in sources that use a Parcel API, plus
+    /// `argType`s of navigation graphs and `parcelable` AIDL declarations
+    fn parceled_type_names(&self, root: &Path) -> HashSet<String> {
+        use ignore::WalkBuilder;
+
+        let mut names = HashSet::new();
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+
+            // Skip test files
+            let path_str = path.to_string_lossy();
+            if SourceSetKind::from_path(path).is_test() {
+                continue;
+            }
+
+            let extension = path.extension().and_then(|e| e.to_str());
+            let is_navigation = extension == Some("xml") && path_str.contains("/navigation");
+            if !matches!(extension, Some("kt") | Some("java") | Some("aidl")) && !is_navigation {
+                continue;
+            }
+
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+
+            let uses_parcel = match extension {
+                Some("kt") | Some("java") => PARCEL_APIS.iter().any(|api| content.contains(api)),
+                _ => true,
+            };
+            if uses_parcel {
+                names.extend(
+                    self.type_name_pattern
+                        .find_iter(&content)
+                        .map(|m| m.as_str().to_string()),
+                );
+            }
+        }
+        names
+    }
+}
+
+impl Default for LegacyParcelableDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for LegacyParcelableDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::LegacyParcelable)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.graph, project.root).to_issues()
+    }
+}
+
+/// Classes with hand-written Parcelable members, in source order
+fn parcelable_classes(graph: &Graph) -> Vec<ParcelableClass> {
+    let mut boilerplate: HashMap<&DeclarationId, Vec<Declaration>> = HashMap::new();
+    for member in graph
+        .declarations()
+        .filter(|d| BOILERPLATE_MEMBERS.contains(&d.name.as_str()))
+        .filter(|d| !is_parceler(d))
+    {
+        if let Some(class) = owner(graph, member) {
+            boilerplate
+                .entry(&class.id)
+                .or_default()
+                .push(member.clone());
+        }
+    }
+
+    let mut classes: Vec<ParcelableClass> = boilerplate
+        .into_iter()
+        .filter_map(|(id, mut members)| {
+            let class = graph.get_declaration(id)?;
+            let parcelized = class.has_annotation("Parcelize");
+            let implements = class
+                .super_types
+                .iter()
+                .any(|s| super_type_name(s) == "Parcelable");
+            if !parcelized && !implements {
+                return None;
+            }
+            members.sort_by_key(|m| m.location.start_byte);
+            Some(ParcelableClass {
+                class: class.clone(),
+                boilerplate: members,
+                parcelized,
+            })
+        })
+        .collect();
+
+    classes.sort_by(|a, b| {
+        let (a, b) = (&a.class.location, &b.class.location);
+        a.file.cmp(&b.file).then(a.start_byte.cmp(&b.start_byte))
+    });
+    classes
+}
+
+/// The class a Parcelable member belongs to, looking through companion objects
+fn owner<'a>(graph: &'a Graph, member: &Declaration) -> Option<&'a Declaration> {
+    let parent = graph.get_declaration(member.parent.as_ref()?)?;
+    if parent.kind == DeclarationKind::Object
+        && (parent.modifiers.iter().any(|m| m == "companion") || parent.name == "Companion")
+    {
+        return graph.get_declaration(parent.parent.as_ref()?);
+    }
+    parent.kind.is_type().then_some(parent)
+}
+
+/// `companion object CREATOR : Parceler<User>` customizes `@Parcelize` rather
+/// than replacing it
+fn is_parceler(decl: &Declaration) -> bool {
+    decl.super_types
+        .iter()
+        .any(|s| super_type_name(s) == "Parceler")
+}
+
+/// `android.os.Parcelable` / `Parcelable.Creator<User>` -> `Parcelable` / `Creator`
+fn super_type_name(super_type: &str) -> &str {
+    let name = super_type.trim();
+    let name = name.strip_prefix("extends ").unwrap_or(name);
+    let name = name.strip_prefix("implements ").unwrap_or(name);
+    let name = &name[..name.find(['(', '<']).unwrap_or(name.len())];
+    name.rsplit('.').next().unwrap_or(name).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Language, Location, Reference, ReferenceKind};
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn declaration(name: &str, line: usize, kind: DeclarationKind) -> Declaration {
+        let file = PathBuf::from("Models.kt");
+        Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            kind,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        )
+    }
+
+    fn parcelable(
+        graph: &mut Graph,
+        name: &str,
+        line: usize,
+        annotations: &[&str],
+    ) -> DeclarationId {
+        let mut class = declaration(name, line, DeclarationKind::Class);
+        class.super_types = vec!["Parcelable".to_string()];
+        class.annotations = annotations.iter().map(|a| a.to_string()).collect();
+        let class = graph.add_declaration(class);
+
+        let mut write = declaration("writeToParcel", line + 1, DeclarationKind::Method);
+        write.parent = Some(class.clone());
+        graph.add_declaration(write);
+        let mut creator = declaration("CREATOR", line + 2, DeclarationKind::Object);
+        creator.modifiers = vec!["companion".to_string()];
+        creator.super_types = vec!["Parcelable.Creator<T>".to_string()];
+        creator.parent = Some(class.clone());
+        graph.add_declaration(creator);
+
+        let main =
+            graph.add_declaration(declaration("main", 1000 + line, DeclarationKind::Function));
+        let location = Location::new(PathBuf::from("Main.kt"), 1, 1, 0, 10);
+        graph.add_reference(
+            &main,
+            &class,
+            Reference::new(ReferenceKind::Instantiation, location, name.to_string()),
+        );
+        class
+    }
+
+    #[test]
+    fn test_super_type_name() {
+        assert_eq!(super_type_name("android.os.Parcelable"), "Parcelable");
+        assert_eq!(super_type_name("Parcelable.Creator<User>"), "Creator");
+        assert_eq!(super_type_name("Parceler<User>"), "Parceler");
+    }
+
+    #[test]
+    fn test_legacy_parcelables() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let path = root.join("app/src/main/java/com/app/DetailActivity.kt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            path,
+            "fun open(user: User) = Intent(ctx, DetailActivity::class.java).putExtra(KEY, user)",
+        )
+        .unwrap();
+
+        let mut graph = Graph::new();
+        parcelable(&mut graph, "User", 1, &[]);
+        parcelable(&mut graph, "Draft", 10, &[]);
+        parcelable(&mut graph, "Address", 20, &["@Parcelize"]);
+
+        // The Parcelize-approved way to customize serialization
+        let mut settings = declaration("Settings", 30, DeclarationKind::Class);
+        settings.annotations = vec!["@Parcelize".to_string()];
+        let settings = graph.add_declaration(settings);
+        let mut parceler = declaration("CREATOR", 31, DeclarationKind::Object);
+        parceler.super_types = vec!["Parceler<Settings>".to_string()];
+        parceler.parent = Some(settings);
+        graph.add_declaration(parceler);
+
+        let analysis = LegacyParcelableDetector::new().analyze(&graph, root);
+
+        assert_eq!(analysis.total_classes, 3);
+        let parcelized: Vec<_> = analysis
+            .parcelized
+            .iter()
+            .map(|p| p.class.name.as_str())
+            .collect();
+        assert_eq!(parcelized, ["Address"]);
+        let never_parceled: Vec<_> = analysis
+            .never_parceled
+            .iter()
+            .map(|p| p.class.name.as_str())
+            .collect();
+        assert_eq!(never_parceled, ["Draft"]);
+
+        let issues = analysis.to_issues();
+        let found: Vec<_> = issues
+            .iter()
+            .map(|i| (i.declaration.name.as_str(), i.confidence))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("writeToParcel", Confidence::High),
+                ("CREATOR", Confidence::High),
+                ("Draft", Confidence::Medium),
+            ]
+        );
+        assert_eq!(
+            issues[2].message,
+            "Parcelable 'Draft' is never sent through a Parcel; its hand-written writeToParcel, CREATOR can be removed"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! Long Method Detector
 //!
-//! Detects methods that exceed a configurable line threshold.
+//! Detects methods with more lines of code than a configurable threshold.
+//! Blank and comment-only lines don't count.
 //! Long methods are harder to understand, test, and maintain.
 //!
 //! ## Anti-Pattern
@@ -26,13 +27,13 @@
 //! - Apply single responsibility principle
 //! - Consider using strategy or command pattern
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
 /// Detector for methods that are too long
 pub struct LongMethodDetector {
-    /// Maximum allowed lines per method
+    /// Maximum allowed lines of code per method
     max_lines: usize,
 }
 
@@ -47,13 +48,6 @@ impl LongMethodDetector {
         self.max_lines = max;
         self
     }
-
-    /// Calculate approximate line count from byte range
-    fn estimate_lines(decl: &crate::graph::Declaration) -> usize {
-        let byte_range = decl.location.end_byte.saturating_sub(decl.location.start_byte);
-        // Rough estimate: average 40 bytes per line
-        (byte_range / 40).max(1)
-    }
 }
 
 impl Default for LongMethodDetector {
@@ -63,6 +57,10 @@ impl Default for LongMethodDetector {
 }
 
 impl Detector for LongMethodDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::LongMethod)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -80,14 +78,13 @@ impl Detector for LongMethodDetector {
                 continue;
             }
 
-            // Estimate line count
-            let estimated_lines = Self::estimate_lines(decl);
+            let lines = decl.metrics.loc;
 
-            if estimated_lines > self.max_lines {
+            if lines > self.max_lines {
                 let mut dead = DeadCode::new(decl.clone(), DeadCodeIssue::LongMethod);
                 dead = dead.with_message(format!(
-                    "Method '{}' has approximately {} lines (max recommended: {}). Consider breaking into smaller methods.",
-                    decl.name, estimated_lines, self.max_lines
+                    "Method '{}' has {} lines of code (max recommended: {}). Consider breaking into smaller methods.",
+                    decl.name, lines, self.max_lines
                 ));
                 dead = dead.with_confidence(Confidence::Medium);
                 issues.push(dead);
@@ -115,20 +112,24 @@ impl Detector for LongMethodDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::{Declaration, DeclarationId, Language, Location};
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::{Declaration, DeclarationId, GraphBuilder, Language, Location};
     use std::path::PathBuf;
 
-    fn create_method(name: &str, line: usize, byte_size: usize) -> Declaration {
+    fn create_method(name: &str, line: usize, loc: usize) -> Declaration {
         let path = PathBuf::from("test.kt");
         let start_byte = line * 100;
-        let end_byte = start_byte + byte_size;
-        Declaration::new(
+        let end_byte = start_byte + loc * 40;
+        let mut decl = Declaration::new(
             DeclarationId::new(path.clone(), start_byte, end_byte),
             name.to_string(),
             DeclarationKind::Method,
             Location::new(path, line, 1, start_byte, end_byte),
             Language::Kotlin,
-        )
+        );
+        decl.metrics.lines = loc;
+        decl.metrics.loc = loc;
+        decl
     }
 
     #[test]
@@ -144,14 +145,35 @@ mod tests {
     }
 
     #[test]
-    fn test_estimate_lines() {
-        // 2000 bytes / 40 = 50 lines
-        let method = create_method("longMethod", 1, 2000);
-        assert_eq!(LongMethodDetector::estimate_lines(&method), 50);
-
-        // 4000 bytes / 40 = 100 lines
-        let longer = create_method("longerMethod", 1, 4000);
-        assert_eq!(LongMethodDetector::estimate_lines(&longer), 100);
+    fn test_comments_and_blank_lines_not_counted() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Screen.kt");
+        let body: String = (0..30)
+            .map(|i| format!("        // step {}\n\n        log({})\n", i, i))
+            .collect();
+        std::fs::write(
+            &path,
+            format!("class Screen {{\n    fun render() {{\n{}    }}\n}}\n", body),
+        )
+        .unwrap();
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(path, FileType::Kotlin))
+            .unwrap();
+        let graph = builder.build();
+
+        // 92 lines, of which 32 hold code
+        let render = graph.find_by_name("render")[0];
+        assert_eq!(render.metrics.lines, 92);
+        assert_eq!(render.metrics.loc, 32);
+        assert!(LongMethodDetector::new().detect(&graph).is_empty());
+        assert_eq!(
+            LongMethodDetector::new()
+                .with_max_lines(30)
+                .detect(&graph)
+                .len(),
+            1
+        );
     }
 
     #[test]
@@ -165,8 +187,7 @@ mod tests {
     #[test]
     fn test_short_method_ok() {
         let mut graph = Graph::new();
-        // 800 bytes ≈ 20 lines
-        graph.add_declaration(create_method("shortMethod", 1, 800));
+        graph.add_declaration(create_method("shortMethod", 1, 20));
 
         let detector = LongMethodDetector::new();
         let issues = detector.detect(&graph);
@@ -177,22 +198,21 @@ mod tests {
     #[test]
     fn test_long_method_detected() {
         let mut graph = Graph::new();
-        // 2400 bytes ≈ 60 lines (exceeds 50)
-        graph.add_declaration(create_method("veryLongMethod", 1, 2400));
+        // 60 lines (exceeds 50)
+        graph.add_declaration(create_method("veryLongMethod", 1, 60));
 
         let detector = LongMethodDetector::new();
         let issues = detector.detect(&graph);
 
         assert_eq!(issues.len(), 1);
         assert_eq!(issues[0].declaration.name, "veryLongMethod");
-        assert!(issues[0].message.contains("60 lines"));
+        assert!(issues[0].message.contains("60 lines of code"));
     }
 
     #[test]
     fn test_custom_threshold() {
         let mut graph = Graph::new();
-        // 1200 bytes ≈ 30 lines
-        graph.add_declaration(create_method("mediumMethod", 1, 1200));
+        graph.add_declaration(create_method("mediumMethod", 1, 30));
 
         // Default (50) should not flag
         let detector = LongMethodDetector::new();
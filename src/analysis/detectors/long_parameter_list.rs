@@ -29,7 +29,7 @@
 //! - Use builder pattern
 //! - Split into smaller functions
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -83,6 +83,10 @@ impl Default for LongParameterListDetector {
 }
 
 impl Detector for LongParameterListDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::LongParameterList)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
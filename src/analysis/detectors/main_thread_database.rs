@@ -23,7 +23,7 @@
 //! - Use LiveData/Flow (Room handles threading)
 //! - Use background thread with callback
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Language};
 
@@ -83,6 +83,10 @@ impl Default for MainThreadDatabaseDetector {
 }
 
 impl Detector for MainThreadDatabaseDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::MainThreadDatabase)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -23,7 +23,7 @@
 //! - Clear references in onDestroy/onCleared
 //! - Use lifecycle-aware components
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -84,6 +84,10 @@ impl Default for MemoryLeakRiskDetector {
 }
 
 impl Detector for MemoryLeakRiskDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::MemoryLeakRisk)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -3,26 +3,50 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+mod always_default_param;
 mod assign_only;
 mod dead_branch;
+mod dead_callback;
 mod duplicate_import;
 mod ignored_return;
+mod intent_action;
+mod legacy_parcelable;
+mod never_enqueued_worker;
+mod orphan_test;
 mod prefer_isempty;
 mod redundant_null_init;
 mod redundant_override;
 mod redundant_parens;
 mod redundant_public;
 mod redundant_this;
+mod registry;
 mod sealed_variant;
+mod stale_consumer_rule;
+mod stale_preview;
+mod unregistered_receiver;
+mod unused_aidl_method;
+mod unused_catalog_entry;
 mod unused_class;
+mod unused_constant;
+mod unused_dagger_binding;
+mod unused_dynamic_feature;
 mod unused_enum_case;
+mod unused_extension;
 mod unused_import;
 mod unused_intent_extra;
+mod unused_koin_definition;
 mod unused_method;
 mod unused_param;
 mod unused_property;
+mod unused_proto_message;
+mod unused_test_helper;
+mod unused_typealias;
+#[cfg(feature = "wasm-plugins")]
+mod wasm_plugin;
 mod write_only;
+mod write_only_column;
 mod write_only_dao;
+mod write_only_datastore;
 mod write_only_prefs;
 
 // Anti-pattern detectors (inspired by "8 anti-patterns in Android codebase")
@@ -72,26 +96,56 @@ mod navcontroller_passing;
 mod state_without_remember;
 
 // These detectors are reserved for future advanced analysis modes
+pub use always_default_param::AlwaysDefaultParamDetector;
 pub use assign_only::AssignOnlyDetector;
 pub use dead_branch::DeadBranchDetector;
+pub use dead_callback::DeadCallbackDetector;
 pub use duplicate_import::DuplicateImportDetector;
 pub use ignored_return::IgnoredReturnValueDetector;
+pub use intent_action::{ActionLocation, IntentActionAnalysis, IntentActionDetector};
+pub use legacy_parcelable::{LegacyParcelableDetector, ParcelableAnalysis, ParcelableClass};
+pub use never_enqueued_worker::{NeverEnqueuedWorkerDetector, WorkerAnalysis, WorkerClass};
+pub use orphan_test::OrphanTestDetector;
 pub use prefer_isempty::PreferIsEmptyDetector;
 pub use redundant_null_init::RedundantNullInitDetector;
 pub use redundant_override::RedundantOverrideDetector;
 pub use redundant_parens::RedundantParenthesesDetector;
 pub use redundant_public::RedundantPublicDetector;
 pub use redundant_this::RedundantThisDetector;
+pub use registry::{DetectorMetadata, DetectorRegistry};
 pub use sealed_variant::UnusedSealedVariantDetector;
+pub use stale_consumer_rule::{ConsumerRuleAnalysis, StaleConsumerRule, StaleConsumerRuleDetector};
+pub use stale_preview::StalePreviewDetector;
+pub use unregistered_receiver::{ReceiverAnalysis, UnregisteredReceiverDetector};
+pub use unused_aidl_method::{AidlAnalysis, UnusedAidlMethod, UnusedAidlMethodDetector};
+pub use unused_catalog_entry::{CatalogAnalysis, UnusedCatalogEntry, UnusedCatalogEntryDetector};
 pub use unused_class::UnusedClassDetector;
+pub use unused_constant::UnusedConstantDetector;
+pub use unused_dagger_binding::{
+    DaggerAnalysis, DaggerBinding, DaggerModule, UnusedDaggerBindingDetector,
+};
+pub use unused_dynamic_feature::{
+    DynamicFeatureAnalysis, DynamicFeatureModule, UnusedDynamicFeatureDetector,
+};
 pub use unused_enum_case::UnusedEnumCaseDetector;
+pub use unused_extension::UnusedExtensionDetector;
 pub use unused_import::UnusedImportDetector;
 pub use unused_intent_extra::{ExtraLocation, IntentExtraAnalysis, UnusedIntentExtraDetector};
+pub use unused_koin_definition::{KoinAnalysis, KoinDefinition, UnusedKoinDefinitionDetector};
 pub use unused_method::UnusedMethodDetector;
 pub use unused_param::UnusedParamDetector;
 pub use unused_property::UnusedPropertyDetector;
+pub use unused_proto_message::{ProtoAnalysis, UnusedProtoMessage, UnusedProtoMessageDetector};
+pub use unused_test_helper::UnusedTestHelperDetector;
+pub use unused_typealias::UnusedTypeAliasDetector;
+#[cfg(feature = "wasm-plugins")]
+pub use wasm_plugin::{WasmDetector, PLUGIN_ABI_VERSION};
 pub use write_only::WriteOnlyDetector;
+pub use write_only_column::{
+    ColumnAnalysis, EntityColumn, RoomEntity, WriteOnlyColumn, WriteOnlyColumnDetector,
+};
 pub use write_only_dao::{DaoAnalysis, DaoCollectionAnalysis, WriteOnlyDaoDetector};
+pub use write_only_datastore::{DataStoreAnalysis, DataStoreKey, WriteOnlyDataStoreDetector};
 pub use write_only_prefs::{SharedPrefsAnalysis, WriteOnlyPrefsDetector};
 
 // Anti-pattern detectors
@@ -141,10 +195,49 @@ pub use navcontroller_passing::NavControllerPassingDetector;
 pub use state_without_remember::StateWithoutRememberDetector;
 
 use crate::analysis::DeadCode;
+use crate::discovery::SourceFile;
 use crate::graph::Graph;
+use std::path::Path;
 
 /// Trait for dead code detectors
-pub trait Detector {
+///
+/// Implement this to add a custom rule and register it with a
+/// [`DetectorRegistry`]; findings should use [`crate::analysis::DeadCodeIssue::Custom`]
+/// with the same metadata so they are grouped and reported under its code.
+pub trait Detector: Send + Sync {
+    /// Rule code, category and defaults of this detector
+    fn metadata(&self) -> DetectorMetadata;
+
     /// Run the detector on the graph and return found issues
     fn detect(&self, graph: &Graph) -> Vec<DeadCode>;
 }
+
+/// What a [`ProjectDetector`] gets to look at
+#[derive(Clone, Copy)]
+pub struct ProjectContext<'a> {
+    /// Root of the analyzed project
+    pub root: &'a Path,
+    /// Source files discovered under the root
+    pub files: &'a [SourceFile],
+    /// Reference graph built from those files
+    pub graph: &'a Graph,
+}
+
+/// Trait for detectors that read project files the graph doesn't cover
+/// (manifests, build scripts, `.proto` and `.aidl` files, ...)
+///
+/// Registered with [`DetectorRegistry::register_project`] and run next to
+/// the graph detectors, under the same configuration and reporting.
+pub trait ProjectDetector: Send + Sync {
+    /// Rule code, category and defaults of this detector
+    fn metadata(&self) -> DetectorMetadata;
+
+    /// Run the detector on the project and return found issues
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode>;
+
+    /// Whether this detector's findings replace reachability findings on the
+    /// same declarations, matched by name and file
+    fn replaces_reachability(&self) -> bool {
+        false
+    }
+}
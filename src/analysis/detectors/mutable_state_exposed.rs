@@ -32,7 +32,7 @@
 //! }
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Visibility};
 
@@ -89,6 +89,10 @@ impl Default for MutableStateExposedDetector {
 }
 
 impl Detector for MutableStateExposedDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::MutableStateExposed)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -32,7 +32,7 @@
 //! - Use RxJava/Flow operators
 //! - Break into smaller functions
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -74,6 +74,10 @@ impl Default for NestedCallbackDetector {
 }
 
 impl Detector for NestedCallbackDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::NestedCallback)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -0,0 +1,433 @@
+//! Never-Enqueued Worker Detector
+//!
+//! Detects WorkManager workers (`Worker`, `CoroutineWorker`, `RxWorker`,
+//! `ListenableWorker` subclasses) that nothing ever schedules. WorkManager
+//! instantiates workers by reflection, so a worker looks alive to the graph
+//! as long as its class exists; it only runs if a work request names it.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find concrete classes whose superclass chain reaches a WorkManager
+//!    worker base class (including project base classes such as
+//!    `abstract class BaseWorker : CoroutineWorker(...)`)
+//! 2. Collect worker references: `OneTimeWorkRequestBuilder<T>()`,
+//!    `PeriodicWorkRequestBuilder<T>(...)`, class literals (`T::class`,
+//!    `T.class`) as passed to `OneTimeWorkRequest.from()`, request builders,
+//!    `@WorkerKey` and `WorkerFactory` mappings, and class-name strings
+//! 3. Report workers that are never referenced
+//!
+//! Test sources are ignored, so a worker only driven by
+//! `TestListenableWorkerBuilder` is reported.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! class SyncWorker(ctx: Context, params: WorkerParameters) : CoroutineWorker(ctx, params) {
+//!     override suspend fun doWork() = Result.success()
+//! }
+//!
+//! // DEAD: no work request ever names LegacyUploadWorker
+//! class LegacyUploadWorker(ctx: Context, params: WorkerParameters) : Worker(ctx, params) {
+//!     override fun doWork() = Result.success()
+//! }
+//!
+//! WorkManager.getInstance(context).enqueue(OneTimeWorkRequestBuilder<SyncWorker>().build())
+//! ```
+
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::discovery::SourceSetKind;
+use crate::graph::{Declaration, DeclarationKind, Graph, Language};
+
+/// WorkManager base classes
+const WORKER_BASES: &[&str] = &["Worker", "CoroutineWorker", "RxWorker", "ListenableWorker"];
+
+/// A worker class
+#[derive(Debug, Clone)]
+pub struct WorkerClass {
+    pub name: String,
+    /// WorkManager base class it ultimately extends (e.g., "CoroutineWorker")
+    pub base: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Result of worker analysis
+#[derive(Debug, Default)]
+pub struct WorkerAnalysis {
+    /// Workers that are never scheduled
+    pub unused_workers: Vec<WorkerClass>,
+    /// Total concrete workers found
+    pub total_workers: usize,
+}
+
+impl WorkerAnalysis {
+    /// Convert the findings to `DC032` issues, on the graph's declaration of
+    /// each worker class when there is one
+    pub fn to_issues(&self, graph: &Graph) -> Vec<DeadCode> {
+        self.unused_workers
+            .iter()
+            .map(|worker| {
+                let decl = graph
+                    .find_by_name(&worker.name)
+                    .into_iter()
+                    .find(|decl| decl.location.file.as_path() == worker.file)
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        let language =
+                            Language::from_path(&worker.file).unwrap_or(Language::Kotlin);
+                        Declaration::at_line(
+                            &worker.file,
+                            worker.line,
+                            worker.name.as_str(),
+                            DeclarationKind::Class,
+                            language,
+                        )
+                    });
+                DeadCode::new(decl, DeadCodeIssue::NeverEnqueuedWorker)
+                    .with_message(format!(
+                        "Worker '{}' ({}) is never enqueued",
+                        worker.name, worker.base
+                    ))
+                    .with_confidence(Confidence::Medium)
+            })
+            .collect()
+    }
+}
+
+/// A class declaration and its superclass, before worker resolution
+struct ClassHeader {
+    name: String,
+    superclass: String,
+    is_abstract: bool,
+    file: PathBuf,
+    line: usize,
+}
+
+/// Detector for workers that are never enqueued
+pub struct NeverEnqueuedWorkerDetector {
+    // class Foo(...) : Bar(...) / class Foo extends Bar
+    class_pattern: Regex,
+    kotlin_superclass_pattern: Regex,
+    java_superclass_pattern: Regex,
+    // OneTimeWorkRequestBuilder<Foo>, PeriodicWorkRequestBuilder<Foo>
+    request_builder_pattern: Regex,
+    // Foo::class, Foo.class
+    class_literal_pattern: Regex,
+    // "com.app.Foo"
+    class_name_string_pattern: Regex,
+}
+
+impl NeverEnqueuedWorkerDetector {
+    pub fn new() -> Self {
+        Self {
+            class_pattern: Regex::new(
+                r"(?m)^[ \t]*(?:@[\w.]+(?:\([^)\n]*\))?\s+)*((?:[a-z]+[ \t]+)*)class\s+([A-Z]\w*)",
+            )
+            .unwrap(),
+            kotlin_superclass_pattern: Regex::new(
+                r"(?:^|[:,])\s*(?:\w+\.)*([A-Z]\w*)\s*(?:<[^>]*>)?\s*\(",
+            )
+            .unwrap(),
+            java_superclass_pattern: Regex::new(r"\bextends\s+(?:\w+\.)*([A-Z]\w*)").unwrap(),
+            request_builder_pattern: Regex::new(
+                r"WorkRequestBuilder\s*<\s*(?:\w+\.)*([A-Z]\w*)\s*>",
+            )
+            .unwrap(),
+            class_literal_pattern: Regex::new(r"\b([A-Z]\w*)\s*(?:::\s*class|\.class)\b").unwrap(),
+            class_name_string_pattern: Regex::new(r#""(?:\w+\.)*([A-Z]\w*)""#).unwrap(),
+        }
+    }
+
+    /// Analyze a directory for workers that are never enqueued
+    pub fn analyze(&self, root: &Path) -> WorkerAnalysis {
+        use ignore::WalkBuilder;
+
+        let mut classes = Vec::new();
+        let mut referenced = HashSet::new();
+
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+
+            // Only process Kotlin and Java files
+            let ext = path.extension().and_then(|e| e.to_str());
+            let is_java = match ext {
+                Some("kt") => false,
+                Some("java") => true,
+                _ => continue,
+            };
+
+            // Skip test files
+            if SourceSetKind::from_path(path).is_test() {
+                continue;
+            }
+
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+
+            classes.extend(self.collect_classes(&content, path, is_java));
+            self.collect_references(&content, &mut referenced);
+        }
+
+        let workers = resolve_workers(&classes);
+        let mut analysis = WorkerAnalysis {
+            total_workers: workers.len(),
+            ..Default::default()
+        };
+        analysis.unused_workers = workers
+            .into_iter()
+            .filter(|worker| !referenced.contains(&worker.name))
+            .collect();
+        analysis
+            .unused_workers
+            .sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        analysis
+    }
+
+    /// Find class declarations and their superclass
+    fn collect_classes(&self, content: &str, file: &Path, is_java: bool) -> Vec<ClassHeader> {
+        let matches: Vec<_> = self.class_pattern.captures_iter(content).collect();
+        let mut classes = Vec::new();
+        for (i, caps) in matches.iter().enumerate() {
+            let whole = caps.get(0).unwrap();
+            let modifiers = &caps[1];
+            if modifiers.contains("annotation") || modifiers.contains("enum") {
+                continue;
+            }
+
+            // The header runs up to the class body, or to the next class for
+            // body-less Kotlin classes
+            let next = matches
+                .get(i + 1)
+                .map_or(content.len(), |m| m.get(0).unwrap().start());
+            let rest = &content[whole.end()..next];
+            let header = &rest[..rest.find('{').unwrap_or(rest.len())];
+            let superclass = if is_java {
+                self.java_superclass_pattern.captures(header)
+            } else {
+                kotlin_supertypes(header).and_then(|s| self.kotlin_superclass_pattern.captures(s))
+            };
+            let Some(superclass) = superclass else {
+                continue;
+            };
+
+            classes.push(ClassHeader {
+                name: caps[2].to_string(),
+                superclass: superclass[1].to_string(),
+                is_abstract: modifiers.contains("abstract") || modifiers.contains("sealed"),
+                file: file.to_path_buf(),
+                line: content[..caps.get(2).unwrap().start()]
+                    .matches('\n')
+                    .count()
+                    + 1,
+            });
+        }
+        classes
+    }
+
+    /// Collect the class names work requests and worker factories refer to
+    fn collect_references(&self, content: &str, referenced: &mut HashSet<String>) {
+        let patterns = [
+            &self.request_builder_pattern,
+            &self.class_literal_pattern,
+            &self.class_name_string_pattern,
+        ];
+        for pattern in patterns {
+            for caps in pattern.captures_iter(content) {
+                referenced.insert(caps[1].to_string());
+            }
+        }
+    }
+}
+
+impl Default for NeverEnqueuedWorkerDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for NeverEnqueuedWorkerDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::NeverEnqueuedWorker)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.root).to_issues(project.graph)
+    }
+}
+
+/// The supertype list of a Kotlin class header: what follows the `:` after
+/// the (possibly multi-line) primary constructor
+fn kotlin_supertypes(header: &str) -> Option<&str> {
+    let mut depth = 0i32;
+    let mut prev = ' ';
+    for (i, c) in header.char_indices() {
+        match c {
+            '(' | '<' => depth += 1,
+            // `->` in function types isn't a closing bracket
+            '>' if prev == '-' => {}
+            ')' | '>' => depth -= 1,
+            ':' if depth == 0 => return Some(&header[i + 1..]),
+            _ => {}
+        }
+        prev = c;
+    }
+    None
+}
+
+/// Concrete classes whose superclass chain reaches a WorkManager base class
+fn resolve_workers(classes: &[ClassHeader]) -> Vec<WorkerClass> {
+    let superclasses: HashMap<&str, &str> = classes
+        .iter()
+        .map(|c| (c.name.as_str(), c.superclass.as_str()))
+        .collect();
+
+    classes
+        .iter()
+        .filter(|c| !c.is_abstract)
+        .filter_map(|class| {
+            let mut current = class.superclass.as_str();
+            let mut seen = HashSet::new();
+            loop {
+                if WORKER_BASES.contains(&current) {
+                    return Some(WorkerClass {
+                        name: class.name.clone(),
+                        base: current.to_string(),
+                        file: class.file.clone(),
+                        line: class.line,
+                    });
+                }
+                if !seen.insert(current) {
+                    return None;
+                }
+                current = superclasses.get(current)?;
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    fn unused_names(analysis: &WorkerAnalysis) -> Vec<&str> {
+        analysis
+            .unused_workers
+            .iter()
+            .map(|w| w.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_kotlin_workers() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/src/main/java/app/work/Workers.kt",
+            r#"package app.work
+
+class SyncWorker(
+    context: Context,
+    params: WorkerParameters,
+) : CoroutineWorker(context, params) {
+    override suspend fun doWork() = Result.success()
+}
+
+@HiltWorker
+class UploadWorker @AssistedInject constructor(
+    @Assisted context: Context,
+    @Assisted params: WorkerParameters,
+) : BaseWorker(context, params)
+
+abstract class BaseWorker(context: Context, params: WorkerParameters) :
+    CoroutineWorker(context, params)
+
+class LegacyCleanupWorker(context: Context, params: WorkerParameters) : Worker(context, params) {
+    override fun doWork(): Result = Result.success()
+}
+
+class NotAWorker(val name: String) : Exception(name)
+"#,
+        );
+        write(
+            root,
+            "app/src/main/java/app/App.kt",
+            r#"class App : Application() {
+    fun schedule() {
+        val sync = PeriodicWorkRequestBuilder<SyncWorker>(1, TimeUnit.HOURS).build()
+        val upload = OneTimeWorkRequest.from(UploadWorker::class.java)
+        WorkManager.getInstance(this).enqueue(listOf(sync, upload))
+    }
+}
+"#,
+        );
+        write(
+            root,
+            "app/src/test/java/app/LegacyCleanupWorkerTest.kt",
+            "val worker = TestListenableWorkerBuilder<LegacyCleanupWorker>(context).build()",
+        );
+
+        let analysis = NeverEnqueuedWorkerDetector::new().analyze(root);
+
+        assert_eq!(analysis.total_workers, 3);
+        assert_eq!(unused_names(&analysis), ["LegacyCleanupWorker"]);
+        let worker = &analysis.unused_workers[0];
+        assert_eq!((worker.base.as_str(), worker.line), ("Worker", 19));
+
+        let issues = analysis.to_issues(&Graph::new());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue, DeadCodeIssue::NeverEnqueuedWorker);
+        assert_eq!(issues[0].declaration.location.line, 19);
+        assert_eq!(
+            issues[0].message,
+            "Worker 'LegacyCleanupWorker' (Worker) is never enqueued"
+        );
+    }
+
+    #[test]
+    fn test_java_workers_and_factories() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/java/app/ReportWorker.java",
+            r#"public class ReportWorker extends androidx.work.Worker {
+    public ReportWorker(Context context, WorkerParameters params) { super(context, params); }
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/app/ImageWorker.java",
+            r#"public final class ImageWorker extends ListenableWorker {
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/app/AppWorkerFactory.kt",
+            r#"class AppWorkerFactory : WorkerFactory() {
+    override fun createWorker(ctx: Context, name: String, params: WorkerParameters) =
+        when (name) {
+            "app.ReportWorker" -> ReportWorker(ctx, params)
+            else -> null
+        }
+}
+"#,
+        );
+
+        let analysis = NeverEnqueuedWorkerDetector::new().analyze(root);
+
+        assert_eq!(unused_names(&analysis), ["ImageWorker"]);
+    }
+}
@@ -22,7 +22,7 @@
 //! - Use let for scoping with transformation
 //! - Use require/checkNotNull for preconditions
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Language};
 
@@ -63,6 +63,10 @@ impl Default for NullabilityOverloadDetector {
 }
 
 impl Detector for NullabilityOverloadDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::NullabilityOverload)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -33,7 +33,7 @@
 //! }
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Language};
 
@@ -68,7 +68,7 @@ impl ObjectAllocationInLoopDetector {
 
     /// Check if method is a hot path (called frequently)
     fn is_hot_method(&self, name: &str) -> bool {
-        self.hot_methods.iter().any(|&hot| name == hot)
+        self.hot_methods.contains(&name)
     }
 
     /// Check if method likely contains loops based on size
@@ -97,6 +97,10 @@ impl Default for ObjectAllocationInLoopDetector {
 }
 
 impl Detector for ObjectAllocationInLoopDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::ObjectAllocationInLoop)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -0,0 +1,269 @@
+//! Orphan Test Detector
+//!
+//! Detects test classes that outlived the production code they were written
+//! for. Tests are entry points for the test reachability pass, so a test of
+//! a deleted class keeps compiling (against fakes and fixtures) and is never
+//! reported as unused.
+//!
+//! A test class is an orphan when:
+//!
+//! 1. Nothing it (or any of its members) references is production code, or
+//! 2. Its subject by naming convention (`UserRepositoryTest` ->
+//!    `UserRepository`, `StringUtilsTest` -> `StringUtils.kt`) is no longer
+//!    declared in any main source set
+//!
+//! The second case is reported with low confidence, since a test can be
+//! named after a feature rather than a class.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! // src/test/java/com/app/LegacySyncManagerTest.kt
+//! class LegacySyncManagerTest {       // ORPHAN: LegacySyncManager was deleted
+//!     private val fake = FakeSyncApi()
+//!
+//!     @Test
+//!     fun syncsOnStart() { ... }
+//! }
+//! ```
+
+use std::collections::HashSet;
+
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
+
+/// Suffixes naming a test class after its subject, longest first
+const TEST_CLASS_SUFFIXES: &[&str] = &[
+    "InstrumentedTest",
+    "IntegrationTest",
+    "UnitTest",
+    "Tests",
+    "Test",
+    "Spec",
+    "IT",
+];
+
+/// Detector for test classes whose production code is gone
+pub struct OrphanTestDetector;
+
+impl OrphanTestDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether the test class or any of its members references production code
+    fn uses_production(graph: &Graph, test: &DeclarationId) -> bool {
+        let mut worklist = vec![test];
+        let mut seen = HashSet::new();
+        while let Some(id) = worklist.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            if graph
+                .get_references_from(id)
+                .iter()
+                .any(|(target, _)| !target.is_test_source())
+            {
+                return true;
+            }
+            worklist.extend(graph.get_children(id));
+        }
+        false
+    }
+}
+
+impl Default for OrphanTestDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Detector for OrphanTestDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::OrphanTest)
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let tests: Vec<(&Declaration, &str)> = graph
+            .declarations()
+            .filter(|d| d.kind == DeclarationKind::Class && d.parent.is_none())
+            .filter(|d| d.is_test_source() && !d.modifiers.iter().any(|m| m == "abstract"))
+            .filter_map(|d| Some((d, subject_name(&d.name)?)))
+            .collect();
+        if tests.is_empty() {
+            return Vec::new();
+        }
+
+        // Production type names and file stems a test can be named after
+        let mut production = HashSet::new();
+        for decl in graph.declarations().filter(|d| !d.is_test_source()) {
+            if decl.kind.is_type() || decl.kind == DeclarationKind::Function {
+                production.insert(decl.name.as_str());
+            }
+            if let Some(stem) = decl.location.file.file_stem().and_then(|s| s.to_str()) {
+                production.insert(stem);
+            }
+        }
+
+        let mut issues: Vec<DeadCode> = tests
+            .into_iter()
+            .filter_map(|(test, subject)| {
+                let (message, confidence) = if !Self::uses_production(graph, &test.id) {
+                    (
+                        format!("Test class '{}' doesn't use any production code", test.name),
+                        Confidence::Medium,
+                    )
+                } else if !production.contains(subject) {
+                    (
+                        format!(
+                            "Test class '{}' tests '{}', which no longer exists",
+                            test.name, subject
+                        ),
+                        Confidence::Low,
+                    )
+                } else {
+                    return None;
+                };
+                Some(
+                    DeadCode::new(test.clone(), DeadCodeIssue::OrphanTest)
+                        .with_message(message)
+                        .with_confidence(confidence),
+                )
+            })
+            .collect();
+
+        issues.sort_by(|a, b| {
+            let (a, b) = (&a.declaration.location, &b.declaration.location);
+            a.file.cmp(&b.file).then(a.start_byte.cmp(&b.start_byte))
+        });
+        issues
+    }
+}
+
+/// `UserRepositoryTest` -> `UserRepository`
+fn subject_name(test_name: &str) -> Option<&str> {
+    TEST_CLASS_SUFFIXES
+        .iter()
+        .find_map(|suffix| test_name.strip_suffix(suffix))
+        .filter(|subject| !subject.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Language, Location, Reference, ReferenceKind};
+    use std::path::PathBuf;
+
+    fn declaration(graph: &mut Graph, file: &str, name: &str, line: usize) -> DeclarationId {
+        let file = PathBuf::from(file);
+        graph.add_declaration(Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        ))
+    }
+
+    fn reference(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId) {
+        let location = Location::new(from.file.clone(), 1, 1, from.start, from.end);
+        graph.add_reference(
+            from,
+            to,
+            Reference::new(ReferenceKind::Call, location, String::new()),
+        );
+    }
+
+    #[test]
+    fn test_orphan_tests() {
+        const MAIN: &str = "app/src/main/java/com/app";
+        const TEST: &str = "app/src/test/java/com/app";
+
+        let mut graph = Graph::new();
+        let repository = declaration(
+            &mut graph,
+            &format!("{MAIN}/UserRepository.kt"),
+            "UserRepository",
+            1,
+        );
+        let format = declaration(
+            &mut graph,
+            &format!("{MAIN}/StringUtils.kt"),
+            "Formatter",
+            1,
+        );
+        let fake = declaration(
+            &mut graph,
+            &format!("{TEST}/FakeSyncApi.kt"),
+            "FakeSyncApi",
+            1,
+        );
+
+        let repository_test = declaration(
+            &mut graph,
+            &format!("{TEST}/UserRepositoryTest.kt"),
+            "UserRepositoryTest",
+            1,
+        );
+        reference(&mut graph, &repository_test, &repository);
+        let utils_test = declaration(
+            &mut graph,
+            &format!("{TEST}/StringUtilsTest.kt"),
+            "StringUtilsTest",
+            1,
+        );
+        reference(&mut graph, &utils_test, &format);
+        let renamed_test = declaration(
+            &mut graph,
+            &format!("{TEST}/LoginManagerTest.kt"),
+            "LoginManagerTest",
+            1,
+        );
+        reference(&mut graph, &renamed_test, &repository);
+
+        // Only touches test doubles, through a member
+        let legacy_test = declaration(
+            &mut graph,
+            &format!("{TEST}/LegacySyncTest.kt"),
+            "LegacySyncTest",
+            1,
+        );
+        let mut member = Declaration::new(
+            DeclarationId::new(PathBuf::from(format!("{TEST}/LegacySyncTest.kt")), 300, 350),
+            "syncsOnStart".to_string(),
+            DeclarationKind::Method,
+            Location::new(
+                PathBuf::from(format!("{TEST}/LegacySyncTest.kt")),
+                3,
+                1,
+                300,
+                350,
+            ),
+            Language::Kotlin,
+        );
+        member.parent = Some(legacy_test.clone());
+        let member = graph.add_declaration(member);
+        reference(&mut graph, &member, &fake);
+
+        let issues = OrphanTestDetector::new().detect(&graph);
+
+        let found: Vec<_> = issues
+            .iter()
+            .map(|i| (i.message.as_str(), i.confidence))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "Test class 'LegacySyncTest' doesn't use any production code",
+                    Confidence::Medium
+                ),
+                (
+                    "Test class 'LoginManagerTest' tests 'LoginManager', which no longer exists",
+                    Confidence::Low
+                ),
+            ]
+        );
+    }
+}
@@ -30,7 +30,7 @@
 //! }
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::Graph;
 
@@ -69,6 +69,10 @@ impl Default for PreferIsEmptyDetector {
 }
 
 impl Detector for PreferIsEmptyDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::PreferIsEmpty)
+    }
+
     fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -29,7 +29,7 @@
 //! - Skip abstract method implementations (required)
 //! - Skip if method has different visibility than parent (intentional restriction)
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -109,6 +109,10 @@ impl Default for RedundantOverrideDetector {
 }
 
 impl Detector for RedundantOverrideDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::RedundantOverride)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues = Vec::new();
 
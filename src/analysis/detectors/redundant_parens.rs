@@ -33,7 +33,7 @@
 //! }
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::Graph;
 
@@ -75,6 +75,10 @@ impl Default for RedundantParenthesesDetector {
 }
 
 impl Detector for RedundantParenthesesDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::RedundantParentheses)
+    }
+
     fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -1,20 +1,416 @@
-use super::Detector;
-use crate::analysis::DeadCode;
-use crate::graph::Graph;
+//! Redundant Public Detector
+//!
+//! Detects `public` and Kotlin `internal` declarations that nothing outside
+//! their own module references, and suggests the tightest visibility their
+//! references allow. Works at module level, so it only runs on projects with
+//! more than one Gradle module: in a single module, every public declaration
+//! would be reported, including the API of published libraries.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! // :core
+//! class Formatter {                 // used by :app, stays public
+//!     fun format() = trim()
+//!     fun trim() = ...              // only used in this class: could be private
+//! }
+//! class TextCache                   // only used in :core: could be internal
+//! ```
+//!
+//! In Java, where nothing sits between public and package-private without
+//! the module system, only declarations used within their package are
+//! reported.
+//!
+//! Overrides, abstract and open members, interface members, Android entry
+//! points and annotated declarations are left alone: their visibility is
+//! dictated by a supertype or a framework. So are declarations named in the
+//! signature of, or extended by, a declaration other modules use, and
+//! declarations found through reflection or resources.
 
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{
+    Declaration, DeclarationId, DeclarationKind, Graph, Language, Reach, ReferenceKind, Visibility,
+};
+use std::collections::HashMap;
+
+/// Detector for declarations more visible than their references need
 pub struct RedundantPublicDetector;
+
 impl RedundantPublicDetector {
     pub fn new() -> Self {
         Self
     }
-}
-impl Detector for RedundantPublicDetector {
-    fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
-        Vec::new()
+
+    /// Whether a declaration's visibility is free to tighten
+    fn is_candidate(graph: &Graph, decl: &Declaration) -> bool {
+        let candidate_kind = matches!(
+            decl.kind,
+            DeclarationKind::Class
+                | DeclarationKind::Interface
+                | DeclarationKind::Object
+                | DeclarationKind::Enum
+                | DeclarationKind::TypeAlias
+                | DeclarationKind::Function
+                | DeclarationKind::Method
+                | DeclarationKind::Property
+                | DeclarationKind::Field
+        );
+        let in_interface = decl
+            .parent
+            .as_ref()
+            .and_then(|parent| graph.get_declaration(parent))
+            .is_some_and(|parent| parent.kind == DeclarationKind::Interface);
+        candidate_kind
+            && matches!(decl.visibility, Visibility::Public | Visibility::Internal)
+            && decl.module.is_some()
+            && !decl.is_test_source()
+            && !decl.is_android_entry_point()
+            && !decl.is_companion()
+            && decl.annotations.is_empty()
+            && !in_interface
+            && !decl.modifiers.iter().any(|m| {
+                matches!(
+                    m.as_str(),
+                    "override" | "abstract" | "open" | "expect" | "actual" | "external"
+                )
+            })
+            && !(decl.name == "main" && decl.parent.is_none())
+    }
+
+    /// Widest boundary the declaration's references cross, counting a
+    /// declaration other modules use as crossing modules when it names this
+    /// one in its signature or extends it; `None` when nothing references it
+    /// or something finds it through reflection or a resource
+    fn needed_reach(graph: &Graph, decl: &Declaration) -> Option<Reach> {
+        if !graph.string_references_to(&decl.id).is_empty() {
+            return None;
+        }
+        let mut widest: Option<Reach> = None;
+        for (from, reference) in graph.get_references_to(&decl.id) {
+            if reference.kind == ReferenceKind::Reflection
+                || !matches!(from.language, Language::Kotlin | Language::Java)
+            {
+                return None;
+            }
+            let mut reach = graph.reach(&from.id, &decl.id);
+            if reference.kind.is_signature() || reference.kind == ReferenceKind::Inheritance {
+                let exposer = match from.kind {
+                    DeclarationKind::Parameter => from.parent.as_ref().unwrap_or(&from.id),
+                    _ => &from.id,
+                };
+                if graph.widest_reach(exposer) == Some(Reach::Project) {
+                    reach = Reach::Project;
+                }
+            }
+            widest = widest.max(Some(reach));
+        }
+        widest
+    }
+
+    /// Whether every reference to the declaration comes from inside its
+    /// declaring type
+    fn used_in_type_only(graph: &Graph, decl: &Declaration) -> bool {
+        let Some(owner) = &decl.parent else {
+            return false;
+        };
+        graph
+            .get_references_to(&decl.id)
+            .iter()
+            .all(|(from, _)| Self::encloses(graph, owner, &from.id))
+    }
+
+    fn encloses(graph: &Graph, outer: &DeclarationId, inner: &DeclarationId) -> bool {
+        let mut current = Some(inner);
+        while let Some(id) = current {
+            if id == outer {
+                return true;
+            }
+            current = graph.get_declaration(id).and_then(|d| d.parent.as_ref());
+        }
+        false
+    }
+
+    /// Tightest visibility the references allow, when tighter than the
+    /// declaration's own
+    fn suggestion(graph: &Graph, decl: &Declaration, reach: Reach) -> Option<&'static str> {
+        let is_member = decl.parent.is_some();
+        let suggested = match decl.language {
+            Language::Kotlin => {
+                let private = if is_member {
+                    Self::used_in_type_only(graph, decl)
+                } else {
+                    reach == Reach::File
+                };
+                if private {
+                    "private"
+                } else if reach < Reach::Project {
+                    "internal"
+                } else {
+                    return None;
+                }
+            }
+            Language::Java => {
+                if is_member && Self::used_in_type_only(graph, decl) {
+                    "private"
+                } else if reach <= Reach::Package {
+                    "package-private"
+                } else {
+                    return None;
+                }
+            }
+            _ => return None,
+        };
+        (suggested != decl.visibility.display_name()).then_some(suggested)
     }
 }
+
 impl Default for RedundantPublicDetector {
     fn default() -> Self {
         Self::new()
     }
 }
+
+impl Detector for RedundantPublicDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::RedundantPublic)
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        if graph.module_index().modules().nth(1).is_none() {
+            return Vec::new();
+        }
+
+        let mut suggestions: HashMap<&DeclarationId, (&Declaration, &'static str)> = HashMap::new();
+        for decl in graph.declarations() {
+            if !Self::is_candidate(graph, decl) {
+                continue;
+            }
+            let Some(reach) = Self::needed_reach(graph, decl) else {
+                continue;
+            };
+            if let Some(suggested) = Self::suggestion(graph, decl, reach) {
+                suggestions.insert(&decl.id, (decl, suggested));
+            }
+        }
+
+        // Tightening a type tightens its members: report the outermost
+        let mut issues: Vec<DeadCode> = suggestions
+            .values()
+            .filter(|(decl, _)| {
+                let mut parent = decl.parent.as_ref();
+                while let Some(id) = parent {
+                    if suggestions.contains_key(id) {
+                        return false;
+                    }
+                    parent = graph.get_declaration(id).and_then(|d| d.parent.as_ref());
+                }
+                true
+            })
+            .map(|(decl, suggested)| {
+                let used_in = match (suggested, &decl.module) {
+                    (&"private", _) if decl.parent.is_some() => "its type".to_string(),
+                    (&"private", _) => "its file".to_string(),
+                    (&"package-private", _) => "its package".to_string(),
+                    (_, Some(module)) => format!("module {}", module),
+                    (_, None) => "its module".to_string(),
+                };
+                DeadCode::new((*decl).clone(), DeadCodeIssue::RedundantPublic)
+                    .with_message(format!(
+                        "{} '{}' is only used in {}; it could be {}",
+                        decl.kind.display_name(),
+                        decl.name,
+                        used_in,
+                        suggested
+                    ))
+                    .with_confidence(Confidence::Medium)
+            })
+            .collect();
+
+        issues.sort_by(|a, b| {
+            a.declaration
+                .location
+                .file
+                .cmp(&b.declaration.location.file)
+                .then(
+                    a.declaration
+                        .location
+                        .line
+                        .cmp(&b.declaration.location.line),
+                )
+        });
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    fn build(files: &[(&str, &str, &str)]) -> (tempfile::TempDir, Graph) {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut builder = GraphBuilder::new();
+        for (name, module, contents) in files {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            let file_type = if name.ends_with(".java") {
+                FileType::Java
+            } else {
+                FileType::Kotlin
+            };
+            let file = SourceFile::new(path, file_type).with_module(Some(module.to_string()));
+            builder.process_file(&file).unwrap();
+        }
+        (temp, builder.build())
+    }
+
+    fn messages(graph: &Graph) -> Vec<String> {
+        RedundantPublicDetector::new()
+            .detect(graph)
+            .into_iter()
+            .map(|dc| dc.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_suggests_tighter_visibility() {
+        let (_temp, graph) = build(&[
+            (
+                "Screen.kt",
+                ":app",
+                r#"package com.app
+
+import com.app.core.Formatter
+
+class Screen {
+    fun show() = Formatter().format()
+}
+"#,
+            ),
+            (
+                "Formatter.kt",
+                ":core",
+                r#"package com.app.core
+
+class Formatter {
+    fun format() = trim() + TextCache().get()
+    fun trim() = ""
+}
+
+internal fun helper() = Unit
+fun caller() = helper()
+"#,
+            ),
+            (
+                "TextCache.kt",
+                ":core",
+                r#"package com.app.core
+
+class TextCache {
+    fun get() = ""
+}
+"#,
+            ),
+        ]);
+
+        assert_eq!(
+            messages(&graph),
+            [
+                "method 'trim' is only used in its type; it could be private",
+                "function 'helper' is only used in its file; it could be private",
+                "class 'TextCache' is only used in module :core; it could be internal",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exposed_and_framework_declarations_kept() {
+        let (_temp, graph) = build(&[
+            (
+                "Screen.kt",
+                ":app",
+                r#"package com.app
+
+import com.app.core.Repository
+
+class Screen {
+    fun show() = Repository().load()
+}
+"#,
+            ),
+            (
+                "Repository.kt",
+                ":core",
+                r#"package com.app.core
+
+class Repository : Base() {
+    fun load(): Item = Item()
+    override fun close() = Unit
+}
+
+open class Base {
+    open fun close() = Unit
+}
+
+class Item
+"#,
+            ),
+        ]);
+
+        // Item is returned by, and Base extended by, what :app uses
+        assert!(messages(&graph).is_empty(), "{:?}", messages(&graph));
+    }
+
+    #[test]
+    fn test_java_package_private() {
+        let (_temp, graph) = build(&[
+            (
+                "Main.kt",
+                ":app",
+                r#"package com.app
+
+import com.app.core.Api
+
+fun main() = Api.call()
+"#,
+            ),
+            (
+                "Api.java",
+                ":core",
+                r#"package com.app.core;
+
+public class Api {
+    public static void call() { new Helper().run(); }
+}
+"#,
+            ),
+            (
+                "Helper.java",
+                ":core",
+                r#"package com.app.core;
+
+public class Helper {
+    public void run() {}
+}
+"#,
+            ),
+        ]);
+
+        assert_eq!(
+            messages(&graph),
+            ["class 'Helper' is only used in its package; it could be package-private"]
+        );
+    }
+
+    #[test]
+    fn test_single_module_skipped() {
+        let (_temp, graph) = build(&[(
+            "Formatter.kt",
+            ":app",
+            "package com.app\n\nclass Formatter\n\nfun use() = Formatter()\n",
+        )]);
+        assert!(messages(&graph).is_empty());
+    }
+}
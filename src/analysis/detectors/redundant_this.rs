@@ -33,7 +33,7 @@
 //! }
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::Graph;
 
@@ -65,6 +65,10 @@ impl Default for RedundantThisDetector {
 }
 
 impl Detector for RedundantThisDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::RedundantThis)
+    }
+
     fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -27,7 +27,7 @@
 //! - Interface-based polymorphism
 //! - Code generation (kapt/ksp)
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Language};
 
@@ -57,8 +57,9 @@ impl ReflectionOveruseDetector {
 
     /// Check if in test file (reflection in tests is OK)
     fn is_test_file(decl: &crate::graph::Declaration) -> bool {
-        let file_path = decl.location.file.to_string_lossy().to_lowercase();
-        file_path.contains("test")
+        let file_name = decl.location.file.file_stem().unwrap_or_default();
+        let file_name = file_name.to_string_lossy();
+        decl.is_test_source() || file_name.ends_with("Test") || file_name.ends_with("Tests")
     }
 }
 
@@ -69,6 +70,10 @@ impl Default for ReflectionOveruseDetector {
 }
 
 impl Detector for ReflectionOveruseDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::ReflectionOveruse)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -0,0 +1,717 @@
+//! Detector registry
+//!
+//! Keeps the set of detectors that run on top of reachability analysis,
+//! keyed by rule code. Built-in detectors and detectors registered by
+//! library users go through the same path: they describe themselves with
+//! [`DetectorMetadata`], are switched on or off by [`DetectionConfig`], and
+//! their findings are grouped and reported by rule code and category.
+//! [`Detector`]s look at the reference graph only; [`ProjectDetector`]s also
+//! read project files the graph doesn't cover, such as manifests and build
+//! scripts.
+//!
+//! ```
+//! use searchdeadcode::analysis::detectors::{Detector, DetectorMetadata, DetectorRegistry};
+//! use searchdeadcode::analysis::{DeadCode, DeadCodeIssue, Severity};
+//! use searchdeadcode::Graph;
+//!
+//! const METADATA: DetectorMetadata =
+//!     DetectorMetadata::new("ACME001", "Legacy helpers", "Acme", Severity::Warning);
+//!
+//! struct LegacyHelperDetector;
+//!
+//! impl Detector for LegacyHelperDetector {
+//!     fn metadata(&self) -> DetectorMetadata {
+//!         METADATA
+//!     }
+//!
+//!     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+//!         graph
+//!             .declarations()
+//!             .filter(|decl| decl.name.ends_with("LegacyHelper"))
+//!             .map(|decl| DeadCode::new(decl.clone(), DeadCodeIssue::Custom(METADATA)))
+//!             .collect()
+//!     }
+//! }
+//!
+//! let mut registry = DetectorRegistry::with_builtins();
+//! registry.register(Box::new(LegacyHelperDetector))?;
+//! assert!(registry.get("ACME001").is_some());
+//! # Ok::<(), miette::Report>(())
+//! ```
+
+use super::*;
+use crate::analysis::{DeadCode, DeadCodeIssue, Severity};
+use crate::cancel::{CancellationToken, Cancelled};
+use crate::config::DetectionConfig;
+use crate::graph::Symbol;
+use miette::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::debug;
+
+/// Static description of a detector's rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DetectorMetadata {
+    /// Rule code (e.g. "DC003", "AP008"), unique within a registry
+    pub code: &'static str,
+    /// Short plural description used as the group heading in reports
+    pub name: &'static str,
+    /// Report category ("Dead Code", "Kotlin", "Android", ...)
+    pub category: &'static str,
+    /// Severity of findings unless the detector overrides it
+    pub default_severity: Severity,
+    /// Whether the rule runs when the configuration doesn't mention it
+    pub enabled_by_default: bool,
+}
+
+impl DetectorMetadata {
+    pub const fn new(
+        code: &'static str,
+        name: &'static str,
+        category: &'static str,
+        default_severity: Severity,
+    ) -> Self {
+        Self {
+            code,
+            name,
+            category,
+            default_severity,
+            enabled_by_default: true,
+        }
+    }
+
+    pub const fn with_enabled_by_default(mut self, enabled: bool) -> Self {
+        self.enabled_by_default = enabled;
+        self
+    }
+
+    /// Metadata of a built-in rule
+    pub fn for_issue(issue: DeadCodeIssue) -> Self {
+        if let DeadCodeIssue::Custom(metadata) = issue {
+            return metadata;
+        }
+
+        // Match the CLI defaults: unused params, write-only, sealed variants,
+        // enum cases, unused constants, extensions, type aliases, test helpers
+        // and the project file rules are on
+        let enabled_by_default = matches!(
+            issue,
+            DeadCodeIssue::AssignOnly
+                | DeadCodeIssue::UnusedParameter
+                | DeadCodeIssue::UnusedSealedVariant
+                | DeadCodeIssue::UnusedEnumCase
+                | DeadCodeIssue::UnusedConstant
+                | DeadCodeIssue::UnusedExtension
+                | DeadCodeIssue::UnusedTypeAlias
+                | DeadCodeIssue::UnusedTestHelper
+                | DeadCodeIssue::UnusedBinding
+                | DeadCodeIssue::UnregisteredReceiver
+                | DeadCodeIssue::LegacyParcelable
+                | DeadCodeIssue::WriteOnlyColumn
+                | DeadCodeIssue::UnusedCatalogEntry
+                | DeadCodeIssue::UnmatchedIntentAction
+                | DeadCodeIssue::NeverEnqueuedWorker
+                | DeadCodeIssue::UnusedKoinDefinition
+                | DeadCodeIssue::WriteOnlyDataStoreKey
+                | DeadCodeIssue::UnusedDynamicFeature
+                | DeadCodeIssue::StaleConsumerRule
+                | DeadCodeIssue::UnusedAidlMethod
+        );
+
+        Self::new(
+            issue.code(),
+            issue.description(),
+            issue.category(),
+            issue.default_severity(),
+        )
+        .with_enabled_by_default(enabled_by_default)
+    }
+
+    /// Check whether the rule is enabled by the detection configuration
+    ///
+    /// Explicit `disabled_rules` win over `enabled_rules`, which win over the
+    /// anti-pattern group switches and finally the rule's own default.
+    pub fn is_enabled(&self, config: &DetectionConfig) -> bool {
+        let listed = |rules: &[String]| rules.iter().any(|r| r.eq_ignore_ascii_case(self.code));
+
+        if listed(&config.disabled_rules) {
+            return false;
+        }
+        if listed(&config.enabled_rules) {
+            return true;
+        }
+
+        let groups = &config.anti_patterns;
+        let group_enabled = match self.category {
+            "Architecture" => groups.architecture,
+            "Kotlin" => groups.kotlin,
+            "Performance" => groups.performance,
+            "Android" => groups.android,
+            "Compose" => groups.compose,
+            _ => false,
+        };
+
+        group_enabled
+            || (groups.enabled && is_anti_pattern_category(self.category))
+            || self.enabled_by_default
+    }
+}
+
+/// Deserialized form of [`DetectorMetadata`] before its strings are interned
+#[derive(Deserialize)]
+struct OwnedMetadata {
+    code: String,
+    name: String,
+    category: String,
+    default_severity: Severity,
+    #[serde(default = "enabled")]
+    enabled_by_default: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl<'de> Deserialize<'de> for DetectorMetadata {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        OwnedMetadata::deserialize(deserializer).map(Into::into)
+    }
+}
+
+impl From<OwnedMetadata> for DetectorMetadata {
+    fn from(owned: OwnedMetadata) -> Self {
+        Self::new(
+            Symbol::new(&owned.code).as_str(),
+            Symbol::new(&owned.name).as_str(),
+            Symbol::new(&owned.category).as_str(),
+            owned.default_severity,
+        )
+        .with_enabled_by_default(owned.enabled_by_default)
+    }
+}
+
+fn is_anti_pattern_category(category: &str) -> bool {
+    matches!(
+        category,
+        "Architecture" | "Kotlin" | "Performance" | "Android" | "Compose"
+    )
+}
+
+/// A registered detector of either kind
+enum Registered {
+    Graph(Box<dyn Detector>),
+    Project(Box<dyn ProjectDetector>),
+}
+
+impl Registered {
+    fn metadata(&self) -> DetectorMetadata {
+        match self {
+            Registered::Graph(detector) => detector.metadata(),
+            Registered::Project(detector) => detector.metadata(),
+        }
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        match self {
+            Registered::Graph(detector) => detector.detect(project.graph),
+            Registered::Project(detector) => detector.detect(project),
+        }
+    }
+}
+
+/// Set of detectors keyed by rule code
+#[derive(Default)]
+pub struct DetectorRegistry {
+    detectors: Vec<Registered>,
+}
+
+impl DetectorRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry holding every built-in detector
+    pub fn with_builtins() -> Self {
+        let graph_detectors: Vec<Box<dyn Detector>> = vec![
+            // Dead code
+            Box::new(UnusedParamDetector::new()),
+            Box::new(WriteOnlyDetector::new()),
+            Box::new(UnusedSealedVariantDetector::new()),
+            Box::new(UnusedEnumCaseDetector::new()),
+            Box::new(RedundantOverrideDetector::new()),
+            Box::new(DuplicateImportDetector::new()),
+            Box::new(RedundantNullInitDetector::new()),
+            Box::new(UnusedConstantDetector::new()),
+            Box::new(UnusedExtensionDetector::new()),
+            Box::new(UnusedTypeAliasDetector::new()),
+            Box::new(StalePreviewDetector::new()),
+            Box::new(AlwaysDefaultParamDetector::new()),
+            Box::new(DeadCallbackDetector::new()),
+            Box::new(RedundantPublicDetector::new()),
+            // Test code
+            Box::new(UnusedTestHelperDetector::new()),
+            Box::new(OrphanTestDetector::new()),
+            // Architecture patterns (AP001-AP004)
+            Box::new(GlobalMutableStateDetector::new()),
+            Box::new(DeepInheritanceDetector::new()),
+            Box::new(SingleImplInterfaceDetector::new()),
+            Box::new(EventBusPatternDetector::new()),
+            // Kotlin patterns (AP007-AP010, AP021-AP025)
+            Box::new(HeavyViewModelDetector::new()),
+            Box::new(GlobalScopeUsageDetector::new()),
+            Box::new(LateinitAbuseDetector::new()),
+            Box::new(ScopeFunctionChainingDetector::new()),
+            Box::new(NullabilityOverloadDetector::new()),
+            Box::new(ReflectionOveruseDetector::new()),
+            Box::new(LongParameterListDetector::new()),
+            Box::new(ComplexConditionDetector::new()),
+            Box::new(StringLiteralDuplicationDetector::new()),
+            // Performance patterns (AP011-AP015)
+            Box::new(MemoryLeakRiskDetector::new()),
+            Box::new(LongMethodDetector::new()),
+            Box::new(LargeClassDetector::new()),
+            Box::new(CollectionWithoutSequenceDetector::new()),
+            Box::new(ObjectAllocationInLoopDetector::new()),
+            // Android patterns (AP016-AP020, AP026-AP030)
+            Box::new(MutableStateExposedDetector::new()),
+            Box::new(ViewLogicInViewModelDetector::new()),
+            Box::new(MissingUseCaseDetector::new()),
+            Box::new(NestedCallbackDetector::new()),
+            Box::new(HardcodedDispatcherDetector::new()),
+            Box::new(UnclosedResourceDetector::new()),
+            Box::new(MainThreadDatabaseDetector::new()),
+            Box::new(WakeLockAbuseDetector::new()),
+            Box::new(AsyncTaskUsageDetector::new()),
+            Box::new(InitOnDrawDetector::new()),
+            // Compose patterns (AP031-AP034)
+            Box::new(StateWithoutRememberDetector::new()),
+            Box::new(LaunchedEffectWithoutKeyDetector::new()),
+            Box::new(BusinessLogicInComposableDetector::new()),
+            Box::new(NavControllerPassingDetector::new()),
+        ];
+        let project_detectors: Vec<Box<dyn ProjectDetector>> = vec![
+            Box::new(UnusedDaggerBindingDetector::new()),
+            Box::new(UnregisteredReceiverDetector::new()),
+            Box::new(LegacyParcelableDetector::new()),
+            Box::new(WriteOnlyColumnDetector::new()),
+            Box::new(UnusedCatalogEntryDetector::new()),
+            Box::new(IntentActionDetector::new()),
+            Box::new(NeverEnqueuedWorkerDetector::new()),
+            Box::new(UnusedKoinDefinitionDetector::new()),
+            Box::new(WriteOnlyDataStoreDetector::new()),
+            Box::new(UnusedDynamicFeatureDetector::new()),
+            Box::new(StaleConsumerRuleDetector::new()),
+            Box::new(UnusedAidlMethodDetector::new()),
+            Box::new(UnusedProtoMessageDetector::new()),
+        ];
+
+        let detectors = graph_detectors
+            .into_iter()
+            .map(Registered::Graph)
+            .chain(project_detectors.into_iter().map(Registered::Project))
+            .collect();
+        Self { detectors }
+    }
+
+    /// Register a detector, rejecting rule codes that are already taken
+    pub fn register(&mut self, detector: Box<dyn Detector>) -> Result<()> {
+        self.insert(Registered::Graph(detector))
+    }
+
+    /// Register a detector that reads project files, rejecting rule codes
+    /// that are already taken
+    pub fn register_project(&mut self, detector: Box<dyn ProjectDetector>) -> Result<()> {
+        self.insert(Registered::Project(detector))
+    }
+
+    fn insert(&mut self, detector: Registered) -> Result<()> {
+        let code = detector.metadata().code;
+        if self.get(code).is_some() {
+            miette::bail!("A detector for rule {} is already registered", code);
+        }
+        self.detectors.push(detector);
+        Ok(())
+    }
+
+    /// Load WASM detector plugins and register them
+    ///
+    /// Requires the `wasm-plugins` feature; without it, any plugin is an error.
+    pub fn load_plugins<P: AsRef<Path>>(&mut self, paths: &[P]) -> Result<()> {
+        #[cfg(feature = "wasm-plugins")]
+        for path in paths {
+            self.register(Box::new(WasmDetector::load(path.as_ref())?))?;
+        }
+
+        #[cfg(not(feature = "wasm-plugins"))]
+        if let Some(path) = paths.first() {
+            miette::bail!(
+                "Cannot load plugin {}: built without the `wasm-plugins` feature",
+                path.as_ref().display()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Look up the metadata of a registered detector by rule code
+    pub fn get(&self, code: &str) -> Option<DetectorMetadata> {
+        self.metadata()
+            .find(|metadata| metadata.code.eq_ignore_ascii_case(code))
+    }
+
+    /// Metadata of every registered detector, in registration order
+    pub fn metadata(&self) -> impl Iterator<Item = DetectorMetadata> + '_ {
+        self.detectors.iter().map(|d| d.metadata())
+    }
+
+    pub fn len(&self) -> usize {
+        self.detectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.detectors.is_empty()
+    }
+
+    /// Whether findings of the rule replace reachability findings on the
+    /// same declarations (see [`ProjectDetector::replaces_reachability`])
+    pub fn replaces_reachability(&self, code: &str) -> bool {
+        self.detectors.iter().any(|d| match d {
+            Registered::Project(detector) => {
+                detector.replaces_reachability() && detector.metadata().code == code
+            }
+            Registered::Graph(_) => false,
+        })
+    }
+
+    /// Metadata of the detectors enabled by the configuration
+    pub fn enabled<'a>(
+        &'a self,
+        config: &'a DetectionConfig,
+    ) -> impl Iterator<Item = DetectorMetadata> + 'a {
+        self.metadata()
+            .filter(move |metadata| metadata.is_enabled(config))
+    }
+
+    fn enabled_detectors(&self, config: &DetectionConfig) -> Vec<&Registered> {
+        self.detectors
+            .iter()
+            .filter(|d| d.metadata().is_enabled(config))
+            .collect()
+    }
+
+    /// Run every enabled detector in parallel, handing each detector's findings
+    /// to `on_result` as soon as it finishes
+    pub fn run_each<F>(&self, project: &ProjectContext, config: &DetectionConfig, on_result: F)
+    where
+        F: Fn(DetectorMetadata, Vec<DeadCode>) + Sync,
+    {
+        let _ = self.run_each_cancellable(project, config, &CancellationToken::new(), on_result);
+    }
+
+    /// [`run_each`](Self::run_each) that skips detectors not yet started once
+    /// `cancel` fires
+    ///
+    /// Detectors already running finish and are still reported.
+    pub fn run_each_cancellable<F>(
+        &self,
+        project: &ProjectContext,
+        config: &DetectionConfig,
+        cancel: &CancellationToken,
+        on_result: F,
+    ) -> Result<(), Cancelled>
+    where
+        F: Fn(DetectorMetadata, Vec<DeadCode>) + Sync,
+    {
+        self.enabled_detectors(config)
+            .par_iter()
+            .filter(|_| !cancel.is_cancelled())
+            .for_each(|detector| {
+                let metadata = detector.metadata();
+                let issues = detector.detect(project);
+                debug!(
+                    "{} ({}): {} issues",
+                    metadata.code,
+                    metadata.name,
+                    issues.len()
+                );
+                on_result(metadata, issues);
+            });
+        cancel.check()
+    }
+
+    /// Run every enabled detector and collect their findings in registration order
+    pub fn run(&self, project: &ProjectContext, config: &DetectionConfig) -> Vec<DeadCode> {
+        self.run_cancellable(project, config, &CancellationToken::new())
+            .unwrap_or_default()
+    }
+
+    /// [`run`](Self::run) that stops starting detectors once `cancel` fires
+    pub fn run_cancellable(
+        &self,
+        project: &ProjectContext,
+        config: &DetectionConfig,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<DeadCode>, Cancelled> {
+        let issues = self
+            .enabled_detectors(config)
+            .par_iter()
+            .filter(|_| !cancel.is_cancelled())
+            .flat_map_iter(|detector| {
+                let issues = detector.detect(project);
+                debug!(
+                    "{} ({}): {} issues",
+                    detector.metadata().code,
+                    detector.metadata().name,
+                    issues.len()
+                );
+                issues
+            })
+            .collect();
+        cancel.check()?;
+        Ok(issues)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+    use std::path::PathBuf;
+
+    const TODO_METADATA: DetectorMetadata = DetectorMetadata::new(
+        "TEST001",
+        "Leftover TODO classes",
+        "Custom",
+        Severity::Error,
+    );
+
+    struct TodoClassDetector;
+
+    impl Detector for TodoClassDetector {
+        fn metadata(&self) -> DetectorMetadata {
+            TODO_METADATA
+        }
+
+        fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+            graph
+                .declarations()
+                .filter(|d| d.name.starts_with("Todo"))
+                .map(|d| DeadCode::new(d.clone(), DeadCodeIssue::Custom(TODO_METADATA)))
+                .collect()
+        }
+    }
+
+    fn graph_with_class(name: &str) -> Graph {
+        let path = PathBuf::from("Test.kt");
+        let mut graph = Graph::new();
+        graph.add_declaration(Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        ));
+        graph
+    }
+
+    fn project(graph: &Graph) -> ProjectContext<'_> {
+        ProjectContext {
+            root: Path::new("."),
+            files: &[],
+            graph,
+        }
+    }
+
+    #[test]
+    fn test_builtin_codes_are_unique() {
+        let registry = DetectorRegistry::with_builtins();
+        let mut codes: Vec<_> = registry.metadata().map(|m| m.code).collect();
+        let total = codes.len();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), total);
+    }
+
+    #[test]
+    fn test_register_rejects_duplicate_code() {
+        let mut registry = DetectorRegistry::new();
+        registry.register(Box::new(TodoClassDetector)).unwrap();
+        assert!(registry.register(Box::new(TodoClassDetector)).is_err());
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_default_enabled_rules() {
+        let registry = DetectorRegistry::with_builtins();
+        let config = DetectionConfig::default();
+        let enabled: Vec<_> = registry.enabled(&config).map(|m| m.code).collect();
+        assert_eq!(
+            enabled,
+            vec![
+                "DC003", "DC002", "DC008", "DC005", "DC019", "DC020", "DC028", "DC021", "DC017",
+                "DC018", "DC023", "DC024", "DC029", "DC031", "DC032", "DC033", "DC034", "DC035",
+                "DC036", "DC037"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_enables_and_disables_rules() {
+        let registry = DetectorRegistry::with_builtins();
+        let mut config = DetectionConfig::default();
+        config.anti_patterns.compose = true;
+        config.enabled_rules.push("dc009".to_string());
+        config.enabled_rules.push("DC038".to_string());
+        config.disabled_rules.push("DC003".to_string());
+        config.disabled_rules.push("AP034".to_string());
+
+        let enabled: Vec<_> = registry.enabled(&config).map(|m| m.code).collect();
+        assert!(enabled.contains(&"DC009"));
+        assert!(enabled.contains(&"DC038"));
+        assert!(enabled.contains(&"AP031"));
+        assert!(!enabled.contains(&"AP034"));
+        assert!(!enabled.contains(&"DC003"));
+        assert!(!enabled.contains(&"AP001"));
+    }
+
+    #[test]
+    fn test_custom_detector_participates_like_builtins() {
+        let mut registry = DetectorRegistry::new();
+        registry.register(Box::new(TodoClassDetector)).unwrap();
+        let graph = graph_with_class("TodoLater");
+
+        let findings = registry.run(&project(&graph), &DetectionConfig::default());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].issue.code(), "TEST001");
+        assert_eq!(findings[0].issue.category(), "Custom");
+        assert_eq!(findings[0].severity, Severity::Error);
+
+        let mut config = DetectionConfig::default();
+        config.disabled_rules.push("TEST001".to_string());
+        assert!(registry.run(&project(&graph), &config).is_empty());
+    }
+
+    #[test]
+    fn test_run_cancellable_stops_when_cancelled() {
+        let mut registry = DetectorRegistry::new();
+        registry.register(Box::new(TodoClassDetector)).unwrap();
+        let graph = graph_with_class("TodoLater");
+        let config = DetectionConfig::default();
+
+        let cancel = CancellationToken::new();
+        let findings = registry.run_cancellable(&project(&graph), &config, &cancel);
+        assert_eq!(findings.unwrap().len(), 1);
+
+        cancel.cancel();
+        let findings = registry.run_cancellable(&project(&graph), &config, &cancel);
+        assert_eq!(findings.unwrap_err(), Cancelled::Requested);
+    }
+
+    #[test]
+    fn test_run_each_reports_every_enabled_detector() {
+        let mut registry = DetectorRegistry::with_builtins();
+        registry.register(Box::new(TodoClassDetector)).unwrap();
+        let graph = graph_with_class("TodoLater");
+        let config = DetectionConfig::default();
+        let temp = tempfile::tempdir().unwrap();
+        let project = ProjectContext {
+            root: temp.path(),
+            files: &[],
+            graph: &graph,
+        };
+
+        let finished = std::sync::Mutex::new(Vec::new());
+        registry.run_each(&project, &config, |metadata, findings| {
+            finished
+                .lock()
+                .unwrap()
+                .push((metadata.code, findings.len()));
+        });
+
+        let mut finished = finished.into_inner().unwrap();
+        finished.sort_unstable();
+        assert_eq!(
+            finished,
+            vec![
+                ("DC002", 0),
+                ("DC003", 0),
+                ("DC005", 0),
+                ("DC008", 0),
+                ("DC017", 0),
+                ("DC018", 0),
+                ("DC019", 0),
+                ("DC020", 0),
+                ("DC021", 0),
+                ("DC023", 0),
+                ("DC024", 0),
+                ("DC028", 0),
+                ("DC029", 0),
+                ("DC031", 0),
+                ("DC032", 0),
+                ("DC033", 0),
+                ("DC034", 0),
+                ("DC035", 0),
+                ("DC036", 0),
+                ("DC037", 0),
+                ("TEST001", 1)
+            ]
+        );
+    }
+
+    struct LegacyFileDetector;
+
+    impl ProjectDetector for LegacyFileDetector {
+        fn metadata(&self) -> DetectorMetadata {
+            TODO_METADATA
+        }
+
+        fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+            let path = project.root.join("legacy.txt");
+            if !path.exists() {
+                return Vec::new();
+            }
+            let decl = Declaration::new(
+                DeclarationId::new(path.clone(), 0, 0),
+                "legacy.txt",
+                DeclarationKind::File,
+                Location::new(path, 1, 1, 0, 0),
+                Language::Kotlin,
+            );
+            vec![DeadCode::new(decl, DeadCodeIssue::Custom(TODO_METADATA))]
+        }
+    }
+
+    #[test]
+    fn test_project_detector_reads_project_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut registry = DetectorRegistry::new();
+        registry
+            .register_project(Box::new(LegacyFileDetector))
+            .unwrap();
+        assert!(registry.register(Box::new(TodoClassDetector)).is_err());
+
+        let graph = Graph::new();
+        let config = DetectionConfig::default();
+        let project = ProjectContext {
+            root: temp.path(),
+            files: &[],
+            graph: &graph,
+        };
+        assert!(registry.run(&project, &config).is_empty());
+
+        crate::test_util::write(temp.path(), "legacy.txt", "");
+        let findings = registry.run(&project, &config);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].declaration.name, "legacy.txt");
+    }
+
+    #[cfg(not(feature = "wasm-plugins"))]
+    #[test]
+    fn test_plugins_require_feature() {
+        let mut registry = DetectorRegistry::new();
+        assert!(registry.load_plugins::<&str>(&[]).is_ok());
+        assert!(registry.load_plugins(&["rules.wasm"]).is_err());
+    }
+}
@@ -39,7 +39,7 @@
 //! - Use early returns with `?: return`
 //! - Consider `when` expressions for complex branching
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -110,6 +110,10 @@ impl Default for ScopeFunctionChainingDetector {
 }
 
 impl Detector for ScopeFunctionChainingDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::ScopeFunctionChaining)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
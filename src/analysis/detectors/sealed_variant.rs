@@ -22,8 +22,18 @@
 //!     object Empty : UiState()            // DEAD: never emitted
 //! }
 //! ```
+//!
+//! Java sealed types are handled the same way: records and classes listed
+//! in `permits` are variants, and `case Circle c` / `case Point(var x, var y)`
+//! switch patterns count as uses. The `permits` clause itself does not.
+//!
+//! ```java
+//! sealed interface Shape permits Circle, Square {}
+//! record Circle(double radius) implements Shape {}   // Used in a switch
+//! final class Square implements Shape {}             // DEAD: never created
+//! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, ReferenceKind};
 use std::collections::HashSet;
@@ -54,8 +64,10 @@ impl UnusedSealedVariantDetector {
     ) -> bool {
         // Check if any of the super types is a sealed class/interface
         decl.super_types.iter().any(|st| {
+            // Java superclasses are written "extends Foo"
+            let base_type = st.trim_start_matches("extends ");
             // Handle generic types like "Foo<Bar>" -> "Foo"
-            let base_type = st.split('<').next().unwrap_or(st);
+            let base_type = base_type.split('<').next().unwrap_or(base_type);
             // Strip constructor parens: "UiState()" -> "UiState"
             let base_type = base_type.split('(').next().unwrap_or(base_type);
             // Trim whitespace
@@ -80,7 +92,7 @@ impl UnusedSealedVariantDetector {
                     return true;
                 }
                 // Type references in certain patterns indicate potential instantiation
-                ReferenceKind::Type => {
+                ReferenceKind::Type | ReferenceKind::TypeReference => {
                     // If referenced as a type, it might be instantiated via factory/reflection
                     // This is a conservative check to reduce false positives
                     return true;
@@ -106,28 +118,15 @@ impl Default for UnusedSealedVariantDetector {
 }
 
 impl Detector for UnusedSealedVariantDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedSealedVariant)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues = Vec::new();
 
         // Step 1: Find all sealed classes/interfaces
-        let sealed_types: HashSet<String> = graph
-            .declarations()
-            .filter(|d| self.is_sealed(d))
-            .filter_map(|d| {
-                d.fully_qualified_name
-                    .clone()
-                    .or_else(|| Some(d.name.clone()))
-            })
-            .collect();
-
-        // Also collect simple names for matching
-        let sealed_simple_names: HashSet<String> = graph
-            .declarations()
-            .filter(|d| self.is_sealed(d))
-            .map(|d| d.name.clone())
-            .collect();
-
-        if sealed_types.is_empty() {
+        if !graph.declarations().any(|d| self.is_sealed(d)) {
             return issues;
         }
 
@@ -149,15 +148,10 @@ impl Detector for UnusedSealedVariantDetector {
             }
 
             // Check if this is a subclass of a sealed type
-            let is_sealed_sub = decl.super_types.iter().any(|st| {
-                // Strip generic args: "Foo<Bar>" -> "Foo"
-                let base_type = st.split('<').next().unwrap_or(st);
-                // Strip constructor parens: "UiState()" -> "UiState"
-                let base_type = base_type.split('(').next().unwrap_or(base_type);
-                // Trim whitespace
-                let base_type = base_type.trim();
-                sealed_types.contains(base_type) || sealed_simple_names.contains(base_type)
-            });
+            let is_sealed_sub = graph
+                .supertypes(&decl.id)
+                .into_iter()
+                .any(|super_type| self.is_sealed(super_type));
 
             if !is_sealed_sub {
                 continue;
@@ -278,4 +272,51 @@ mod tests {
         );
         assert!(detector.is_sealed_subclass(&generic, &sealed_types));
     }
+
+    #[test]
+    fn test_java_records_and_permitted_subclasses() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Shapes.java");
+        std::fs::write(
+            &path,
+            r#"package com.app;
+
+public sealed interface Shape permits Circle, Square, Triangle {}
+
+record Circle(double radius) implements Shape {}
+
+final class Square implements Shape {}
+
+non-sealed class Triangle extends Polygon implements Shape {}
+
+abstract sealed class Polygon permits Triangle, Hexagon {}
+
+final class Hexagon extends Polygon {}
+
+class Area {
+    static double of(Shape shape) {
+        return switch (shape) {
+            case Circle(var r) -> r * r;
+            case Triangle t -> 1;
+            default -> 0;
+        };
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let mut builder = crate::graph::GraphBuilder::new();
+        builder
+            .process_file(&crate::discovery::SourceFile::new(
+                path,
+                crate::discovery::FileType::Java,
+            ))
+            .unwrap();
+        let graph = builder.build();
+
+        let issues = UnusedSealedVariantDetector::new().detect(&graph);
+        let names: Vec<_> = issues.iter().map(|i| i.declaration.name.as_str()).collect();
+        assert_eq!(names, ["Square", "Hexagon"]);
+    }
 }
@@ -33,10 +33,9 @@
 //! - Use class directly when single implementation
 //! - Extract interface when second implementation is needed
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
-use std::collections::HashMap;
 
 /// Detector for interfaces with only one implementation
 pub struct SingleImplInterfaceDetector {
@@ -98,6 +97,10 @@ impl Default for SingleImplInterfaceDetector {
 }
 
 impl Detector for SingleImplInterfaceDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::SingleImplInterface)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues = Vec::new();
 
@@ -107,34 +110,18 @@ impl Detector for SingleImplInterfaceDetector {
             .filter(|d| d.kind == DeclarationKind::Interface)
             .collect();
 
-        // Count implementations for each interface
-        let mut impl_count: HashMap<&str, usize> = HashMap::new();
-        for interface in &interfaces {
-            impl_count.insert(&interface.name, 0);
-        }
-
-        // Find all classes that implement interfaces
-        for decl in graph.declarations() {
-            if !matches!(decl.kind, DeclarationKind::Class) {
-                continue;
-            }
-
-            // Check super_types for interface implementations
-            for super_type in &decl.super_types {
-                if let Some(count) = impl_count.get_mut(super_type.as_str()) {
-                    *count += 1;
-                }
-            }
-        }
-
         // Report interfaces with exactly 1 implementation
         for interface in interfaces {
             if self.should_skip(&interface.name) {
                 continue;
             }
 
-            let count = impl_count.get(interface.name.as_str()).unwrap_or(&0);
-            if *count == 1 {
+            let count = graph
+                .subtypes(&interface.id)
+                .iter()
+                .filter(|d| d.kind == DeclarationKind::Class)
+                .count();
+            if count == 1 {
                 let mut dead = DeadCode::new(interface.clone(), DeadCodeIssue::SingleImplInterface);
                 dead = dead.with_message(format!(
                     "Interface '{}' has only 1 implementation. Consider using the class directly unless needed for testing.",
@@ -0,0 +1,285 @@
+//! Stale Consumer Rule Detector
+//!
+//! Detects keep rules in a library module's consumer rules
+//! (`consumer-rules.pro`, or the files named by `consumerProguardFiles`)
+//! that refer to classes the module no longer declares. Consumer rules are
+//! packaged into the AAR and applied to every app that depends on it, so a
+//! stale rule keeps shipping downstream long after its class was deleted.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find consumer rule files and the module each belongs to
+//! 2. Collect the fully qualified names of the classes the module declares
+//! 3. For each rule, check its class pattern, annotation and supertype. A
+//!    name inside one of the module's packages that matches no class of the
+//!    module is stale. Names outside the module's packages (rules for
+//!    dependencies, `androidx.*`, ...) are never reported.
+//!
+//! ## Examples Detected
+//!
+//! ```proguard
+//! # consumer-rules.pro of :core:network
+//! -keep class com.app.network.model.** { *; }       # still matches classes
+//! -keep class com.app.network.LegacyInterceptor      # DEAD: class was deleted
+//! ```
+
+use regex::Regex;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::unused_intent_extra::walk_sources;
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Language};
+use crate::proguard::{class_name_matches, ProguardRules};
+
+/// A consumer keep rule that refers to a missing class
+#[derive(Debug, Clone)]
+pub struct StaleConsumerRule {
+    pub file: PathBuf,
+    pub line: usize,
+    /// The rule on a single line
+    pub rule: String,
+    /// Class name or pattern that matches nothing in the module
+    pub missing: String,
+}
+
+/// Result of consumer rule analysis
+#[derive(Debug, Default)]
+pub struct ConsumerRuleAnalysis {
+    /// Rules referring to classes the module no longer declares
+    pub stale_rules: Vec<StaleConsumerRule>,
+    /// Total rules with a class specification in consumer rule files
+    pub total_rules: usize,
+}
+
+impl ConsumerRuleAnalysis {
+    /// Convert the findings to `DC036` issues
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        self.stale_rules
+            .iter()
+            .map(|rule| {
+                let decl = Declaration::at_line(
+                    &rule.file,
+                    rule.line,
+                    rule.missing.as_str(),
+                    DeclarationKind::Class,
+                    Language::Proguard,
+                );
+                DeadCode::new(decl, DeadCodeIssue::StaleConsumerRule)
+                    .with_message(format!(
+                        "Keep rule for '{}' matches no class in the module: {}",
+                        rule.missing, rule.rule
+                    ))
+                    .with_confidence(Confidence::High)
+            })
+            .collect()
+    }
+}
+
+/// Detector for consumer keep rules of deleted classes
+pub struct StaleConsumerRuleDetector {
+    // consumerProguardFiles("consumer-rules.pro", 'extra.pro')
+    consumer_files_pattern: Regex,
+    quoted_pattern: Regex,
+    package_pattern: Regex,
+    class_pattern: Regex,
+}
+
+impl StaleConsumerRuleDetector {
+    pub fn new() -> Self {
+        Self {
+            consumer_files_pattern: Regex::new(
+                r"consumerProguardFiles?\s*(?:\+?=)?\s*\(?([^)\n]*)",
+            )
+            .unwrap(),
+            quoted_pattern: Regex::new(r#"["']([^"']+\.(?:pro|txt|cfg))["']"#).unwrap(),
+            package_pattern: Regex::new(r"(?m)^\s*package\s+([\w.]+)").unwrap(),
+            class_pattern: Regex::new(r"\b(?:class|object|interface|enum)\s+([A-Z]\w*)").unwrap(),
+        }
+    }
+
+    /// Analyze a directory for stale consumer rules
+    pub fn analyze(&self, root: &Path) -> ConsumerRuleAnalysis {
+        // (module dir, rule file)
+        let mut rule_files: BTreeSet<(PathBuf, PathBuf)> = BTreeSet::new();
+        walk_sources(root, &["gradle", "kts"], |path, content| {
+            let Some(module) = path.parent() else {
+                return;
+            };
+            for declaration in self.consumer_files_pattern.captures_iter(content) {
+                for file in self.quoted_pattern.captures_iter(&declaration[1]) {
+                    let rules = module.join(&file[1]);
+                    if rules.is_file() {
+                        rule_files.insert((module.to_path_buf(), rules));
+                    }
+                }
+            }
+        });
+        walk_sources(root, &["pro"], |path, _| {
+            if path.file_name().is_some_and(|n| n == "consumer-rules.pro") {
+                if let Some(module) = path.parent() {
+                    rule_files.insert((module.to_path_buf(), path.to_path_buf()));
+                }
+            }
+        });
+
+        let mut analysis = ConsumerRuleAnalysis::default();
+        for (module, file) in rule_files {
+            let Ok(rules) = ProguardRules::parse(&file) else {
+                continue;
+            };
+            let (classes, packages) = self.module_classes(&module);
+            analysis.total_rules += rules.rules.len();
+
+            for rule in rules.rules {
+                let missing = rule.referenced_types().find(|name| {
+                    in_packages(name, &packages)
+                        && !classes
+                            .iter()
+                            .any(|class| class_name_matches(outer_class(name), class))
+                });
+                if let Some(missing) = missing {
+                    analysis.stale_rules.push(StaleConsumerRule {
+                        file: file.clone(),
+                        line: rule.line,
+                        missing: missing.to_string(),
+                        rule: rule.text,
+                    });
+                }
+            }
+        }
+        analysis
+    }
+
+    /// Fully qualified class names and packages declared in a module
+    fn module_classes(&self, module: &Path) -> (HashSet<String>, HashSet<String>) {
+        let mut classes = HashSet::new();
+        let mut packages = HashSet::new();
+        walk_sources(module, &["kt", "java"], |_, content| {
+            let Some(package) = self.package_pattern.captures(content) else {
+                return;
+            };
+            for cap in self.class_pattern.captures_iter(content) {
+                classes.insert(format!("{}.{}", &package[1], &cap[1]));
+            }
+            packages.insert(package[1].to_string());
+        });
+        (classes, packages)
+    }
+}
+
+impl Default for StaleConsumerRuleDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for StaleConsumerRuleDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::StaleConsumerRule)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.root).to_issues()
+    }
+}
+
+/// Whether the literal package part of a name or pattern lies inside or
+/// above one of the module's packages
+fn in_packages(name: &str, packages: &HashSet<String>) -> bool {
+    let literal = &name[..name.find(['*', '?', '<']).unwrap_or(name.len())];
+    let Some(dot) = literal.rfind('.') else {
+        return false;
+    };
+    let package = &literal[..dot];
+    packages.iter().any(|declared| {
+        declared == package
+            || declared.starts_with(&format!("{}.", package))
+            || package.starts_with(&format!("{}.", declared))
+    })
+}
+
+/// `com.app.Outer$Inner` -> `com.app.Outer`, since nested classes are
+/// collected by their outer class
+fn outer_class(name: &str) -> &str {
+    &name[..name.find('$').unwrap_or(name.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_stale_consumer_rules() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "core/network/build.gradle.kts",
+            r#"android { defaultConfig { consumerProguardFiles("consumer-rules.pro", "retrofit-rules.pro") } }"#,
+        );
+        write(
+            root,
+            "core/network/src/main/java/com/app/network/Api.kt",
+            "package com.app.network\n\ninterface Api\nclass AuthInterceptor",
+        );
+        write(
+            root,
+            "core/network/src/main/java/com/app/network/model/User.kt",
+            "package com.app.network.model\n\ndata class User(val id: String)",
+        );
+        write(
+            root,
+            "core/network/consumer-rules.pro",
+            r#"-keep class com.app.network.model.** { *; }
+-keep class com.app.network.LegacyInterceptor
+-keepnames class com.app.network.AuthInterceptor$Companion
+-keep class okhttp3.internal.** { *; }
+-keepclassmembers class * extends com.app.network.BaseResponse {
+    <fields>;
+}
+"#,
+        );
+        write(
+            root,
+            "core/network/retrofit-rules.pro",
+            "-keep,allowobfuscation interface com.app.network.Api\n-keep class com.app.network.dto.** { *; }",
+        );
+
+        let analysis = StaleConsumerRuleDetector::new().analyze(root);
+
+        let stale: Vec<_> = analysis
+            .stale_rules
+            .iter()
+            .map(|r| {
+                (
+                    r.file.file_name().unwrap().to_str().unwrap(),
+                    r.line,
+                    r.missing.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            stale,
+            [
+                ("consumer-rules.pro", 2, "com.app.network.LegacyInterceptor"),
+                ("consumer-rules.pro", 5, "com.app.network.BaseResponse"),
+                ("retrofit-rules.pro", 2, "com.app.network.dto.**"),
+            ]
+        );
+        assert_eq!(analysis.total_rules, 7);
+
+        let issues = analysis.to_issues();
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[0].issue, DeadCodeIssue::StaleConsumerRule);
+        assert_eq!(issues[0].declaration.language, Language::Proguard);
+        assert_eq!(
+            issues[0].message,
+            "Keep rule for 'com.app.network.LegacyInterceptor' matches no class in the module: \
+             -keep class com.app.network.LegacyInterceptor"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! Stale Preview Detector
+//!
+//! Detects `@Preview` composables that have rotted. Previews are entry
+//! points, so they keep everything they call alive and are never reported
+//! as unused themselves.
+//!
+//! A preview is stale when it:
+//!
+//! 1. Previews nothing from the project: none of its calls resolve to a
+//!    project composable (the composable it was written for is gone), or
+//! 2. Previews only composables that nothing but previews use, so both the
+//!    preview and its subject are dead, or
+//! 3. Still uses `@Deprecated` declarations: a deprecated composable, or
+//!    deprecated values passed as its arguments
+//!
+//! Theme wrappers (`AppTheme { ... }`) don't count as a previewed subject.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! @Preview
+//! @Composable
+//! fun LegacyCardPreview() {
+//!     AppTheme { LegacyCard(title = "Title") }  // LegacyCard is only used here
+//! }
+//! ```
+
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Graph, ReferenceKind};
+
+/// Detector for previews of removed, unused or deprecated composables
+pub struct StalePreviewDetector;
+
+impl StalePreviewDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Why the preview is stale, if it is
+    fn staleness(graph: &Graph, preview: &Declaration) -> Option<(String, Confidence)> {
+        let targets: Vec<&Declaration> = graph
+            .get_references_from(&preview.id)
+            .into_iter()
+            .map(|(target, _)| target)
+            .filter(|target| target.id != preview.id)
+            .collect();
+
+        // Deprecated composables, parameter types, constants or enum values
+        if let Some(deprecated) = targets.iter().find(|t| t.has_annotation("Deprecated")) {
+            return Some((
+                format!(
+                    "Preview '{}' still uses deprecated {} '{}'",
+                    preview.name,
+                    deprecated.kind.display_name(),
+                    deprecated.name
+                ),
+                Confidence::Medium,
+            ));
+        }
+
+        let subjects: Vec<&Declaration> = targets
+            .into_iter()
+            .filter(|target| target.has_annotation("Composable") && !is_preview(target))
+            .filter(|target| !target.name.ends_with("Theme"))
+            .collect();
+
+        if subjects.is_empty() {
+            return Some((
+                format!(
+                    "Preview '{}' doesn't render any composable from the project",
+                    preview.name
+                ),
+                Confidence::Medium,
+            ));
+        }
+
+        // Imports don't count as a use
+        let only_previewed = subjects.iter().all(|subject| {
+            graph
+                .get_references_to(&subject.id)
+                .iter()
+                .filter(|(from, r)| {
+                    r.kind != ReferenceKind::Import && from.kind != DeclarationKind::File
+                })
+                .all(|(from, _)| is_preview(from))
+        });
+        if only_previewed {
+            let names: Vec<&str> = subjects.iter().map(|s| s.name.as_str()).collect();
+            return Some((
+                format!(
+                    "Preview '{}' renders '{}', which only previews use",
+                    preview.name,
+                    names.join("', '")
+                ),
+                Confidence::Medium,
+            ));
+        }
+
+        None
+    }
+}
+
+impl Default for StalePreviewDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Detector for StalePreviewDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::StalePreview)
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues: Vec<DeadCode> = graph
+            .declarations()
+            .filter(|decl| decl.kind.is_callable() && is_preview(decl))
+            .filter_map(|preview| {
+                let (message, confidence) = Self::staleness(graph, preview)?;
+                Some(
+                    DeadCode::new(preview.clone(), DeadCodeIssue::StalePreview)
+                        .with_message(message)
+                        .with_confidence(confidence),
+                )
+            })
+            .collect();
+
+        issues.sort_by(|a, b| {
+            let (a, b) = (&a.declaration.location, &b.declaration.location);
+            a.file.cmp(&b.file).then(a.start_byte.cmp(&b.start_byte))
+        });
+        issues
+    }
+}
+
+/// `@Preview`, `@PreviewLightDark`, `@PreviewScreenSizes` or a multipreview
+/// annotation such as `@DevicePreviews` - but not `@PreviewParameter`
+fn is_preview(decl: &Declaration) -> bool {
+    decl.annotation_names().any(|name| {
+        (name.starts_with("Preview") || name.ends_with("Preview") || name.ends_with("Previews"))
+            && name != "PreviewParameter"
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Language, Location, Reference};
+    use std::path::PathBuf;
+
+    fn composable(
+        graph: &mut Graph,
+        name: &str,
+        line: usize,
+        annotations: &[&str],
+    ) -> DeclarationId {
+        let file = PathBuf::from("Cards.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            DeclarationKind::Function,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        );
+        decl.annotations = annotations.iter().map(|a| a.to_string()).collect();
+        graph.add_declaration(decl)
+    }
+
+    fn call(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId) {
+        let location = Location::new(from.file.clone(), 1, 1, from.start, from.end);
+        graph.add_reference(
+            from,
+            to,
+            Reference::new(ReferenceKind::Call, location, String::new()),
+        );
+    }
+
+    #[test]
+    fn test_stale_previews() {
+        let mut graph = Graph::new();
+        let screen = composable(&mut graph, "HomeScreen", 1, &["@Composable"]);
+        let card = composable(&mut graph, "UserCard", 2, &["@Composable"]);
+        let legacy = composable(&mut graph, "LegacyCard", 3, &["@Composable"]);
+        let old = composable(
+            &mut graph,
+            "OldBadge",
+            4,
+            &["@Composable", "@Deprecated(\"Use Badge\")"],
+        );
+        let theme = composable(&mut graph, "AppTheme", 5, &["@Composable"]);
+        call(&mut graph, &screen, &card);
+
+        let preview = ["@Preview(showBackground = true)", "@Composable"];
+        let fresh = composable(&mut graph, "UserCardPreview", 10, &preview);
+        call(&mut graph, &fresh, &theme);
+        call(&mut graph, &fresh, &card);
+
+        let only_previewed = composable(
+            &mut graph,
+            "LegacyCardPreview",
+            11,
+            &["@PreviewLightDark", "@Composable"],
+        );
+        call(&mut graph, &only_previewed, &legacy);
+
+        let deprecated = composable(&mut graph, "OldBadgePreview", 12, &preview);
+        call(&mut graph, &deprecated, &old);
+
+        let orphan = composable(&mut graph, "RemovedPreview", 13, &preview);
+        call(&mut graph, &orphan, &theme);
+
+        let issues = StalePreviewDetector::new().detect(&graph);
+
+        let messages: Vec<_> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Preview 'LegacyCardPreview' renders 'LegacyCard', which only previews use",
+                "Preview 'OldBadgePreview' still uses deprecated function 'OldBadge'",
+                "Preview 'RemovedPreview' doesn't render any composable from the project",
+            ]
+        );
+        assert!(issues
+            .iter()
+            .all(|i| i.issue == DeadCodeIssue::StalePreview));
+    }
+}
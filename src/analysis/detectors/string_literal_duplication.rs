@@ -26,7 +26,7 @@
 //! - Use sealed class/enum for keys
 //! - Use object with const properties
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -76,6 +76,10 @@ impl Default for StringLiteralDuplicationDetector {
 }
 
 impl Detector for StringLiteralDuplicationDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::StringLiteralDuplication)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
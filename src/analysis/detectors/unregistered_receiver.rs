@@ -0,0 +1,287 @@
+//! Unregistered BroadcastReceiver Detector
+//!
+//! Detects `BroadcastReceiver` subclasses that are neither declared in an
+//! `AndroidManifest.xml` nor registered at runtime. Receivers are Android
+//! entry points, so reachability never reports them, yet such a receiver can
+//! never receive a broadcast.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find concrete classes whose superclass chain reaches `BroadcastReceiver`
+//!    (or `AppWidgetProvider` / `DeviceAdminReceiver`)
+//! 2. Collect `<receiver android:name>` entries from every manifest
+//! 3. Collect the class names used in files that call `registerReceiver()`
+//!    (`Context`, `ContextCompat` and `LocalBroadcastManager` alike)
+//! 4. Report receivers found in neither
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! // DEAD: not in the manifest, never passed to registerReceiver()
+//! class LegacyBootReceiver : BroadcastReceiver() {
+//!     override fun onReceive(context: Context, intent: Intent) { ... }
+//! }
+//! ```
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::discovery::SourceSetKind;
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
+use crate::parser::simple_name;
+use crate::parser::xml::ManifestParser;
+
+/// Framework classes a receiver can extend
+const RECEIVER_BASES: &[&str] = &[
+    "BroadcastReceiver",
+    "WakefulBroadcastReceiver",
+    "AppWidgetProvider",
+    "DeviceAdminReceiver",
+];
+
+/// Result of receiver registration analysis
+#[derive(Debug, Default)]
+pub struct ReceiverAnalysis {
+    /// Receivers that are never registered
+    pub unregistered: Vec<Declaration>,
+    /// Total concrete receivers found
+    pub total_receivers: usize,
+}
+
+impl ReceiverAnalysis {
+    /// Convert the findings to `DC018` issues
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        self.unregistered
+            .iter()
+            .map(|decl| {
+                DeadCode::new(decl.clone(), DeadCodeIssue::UnregisteredReceiver)
+                    .with_message(format!(
+                        "Receiver '{}' is not declared in any AndroidManifest.xml and no registerReceiver() call site uses it",
+                        decl.name
+                    ))
+                    .with_confidence(Confidence::Medium)
+            })
+            .collect()
+    }
+}
+
+/// Detector for receivers that are never registered
+pub struct UnregisteredReceiverDetector {
+    manifest_parser: ManifestParser,
+    type_name_pattern: Regex,
+}
+
+impl UnregisteredReceiverDetector {
+    pub fn new() -> Self {
+        Self {
+            manifest_parser: ManifestParser::new(),
+            type_name_pattern: Regex::new(r"\b[A-Z]\w*\b").unwrap(),
+        }
+    }
+
+    /// Analyze the receivers in `graph` against the manifests and sources under `root`
+    pub fn analyze(&self, graph: &Graph, root: &Path) -> ReceiverAnalysis {
+        use ignore::WalkBuilder;
+
+        let receivers = receiver_classes(graph);
+        if receivers.is_empty() {
+            return ReceiverAnalysis::default();
+        }
+
+        let mut registered = HashSet::new();
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+
+            // Skip test files
+            if SourceSetKind::from_path(path).is_test() {
+                continue;
+            }
+
+            let is_manifest = path.file_name().is_some_and(|n| n == "AndroidManifest.xml");
+            let is_source = matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("kt") | Some("java")
+            );
+            if !is_manifest && !is_source {
+                continue;
+            }
+
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+
+            if is_manifest {
+                if let Ok(result) = self.manifest_parser.parse(path, &content) {
+                    registered.extend(result.class_references.iter().map(|c| simple_name(c)));
+                }
+            } else if content.contains("registerReceiver") {
+                registered.extend(
+                    self.type_name_pattern
+                        .find_iter(&content)
+                        .map(|m| m.as_str().to_string()),
+                );
+            }
+        }
+
+        let mut analysis = ReceiverAnalysis {
+            total_receivers: receivers.len(),
+            ..Default::default()
+        };
+        analysis.unregistered = receivers
+            .into_iter()
+            .filter(|decl| !registered.contains(decl.name.as_str()))
+            .cloned()
+            .collect();
+        analysis.unregistered.sort_by(|a, b| {
+            a.location
+                .file
+                .cmp(&b.location.file)
+                .then(a.location.line.cmp(&b.location.line))
+        });
+        analysis
+    }
+}
+
+impl Default for UnregisteredReceiverDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for UnregisteredReceiverDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnregisteredReceiver)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.graph, project.root).to_issues()
+    }
+}
+
+/// Concrete receiver classes: classes whose superclass chain reaches a
+/// framework receiver and that no other class extends
+fn receiver_classes(graph: &Graph) -> Vec<&Declaration> {
+    graph
+        .declarations()
+        .filter(|d| d.kind == DeclarationKind::Class)
+        .filter(|d| !d.is_abstract && !d.modifiers.iter().any(|m| m == "abstract"))
+        .filter(|d| graph.subtypes(&d.id).is_empty())
+        .filter(|d| extends_receiver(graph, d, &mut HashSet::new()))
+        .collect()
+}
+
+fn extends_receiver<'a>(
+    graph: &'a Graph,
+    decl: &'a Declaration,
+    seen: &mut HashSet<&'a DeclarationId>,
+) -> bool {
+    if !seen.insert(&decl.id) {
+        return false;
+    }
+    decl.super_types
+        .iter()
+        .any(|super_type| RECEIVER_BASES.contains(&super_type_name(super_type).as_str()))
+        || graph
+            .supertypes(&decl.id)
+            .into_iter()
+            .filter(|d| d.kind == DeclarationKind::Class)
+            .any(|d| extends_receiver(graph, d, seen))
+}
+
+/// `androidx.core.BroadcastReceiver()` / `extends BroadcastReceiver` -> `BroadcastReceiver`
+fn super_type_name(super_type: &str) -> String {
+    let name = super_type.trim();
+    let name = name.strip_prefix("extends ").unwrap_or(name);
+    let name = &name[..name.find(['(', '<']).unwrap_or(name.len())];
+    simple_name(name.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Language, Location};
+    use crate::test_util::write;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn class(graph: &mut Graph, name: &str, line: usize, super_type: &str) {
+        let file = PathBuf::from("Receivers.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        );
+        decl.super_types = vec![super_type.to_string()];
+        graph.add_declaration(decl);
+    }
+
+    #[test]
+    fn test_super_type_name() {
+        assert_eq!(super_type_name("BroadcastReceiver()"), "BroadcastReceiver");
+        assert_eq!(
+            super_type_name("extends android.content.BroadcastReceiver"),
+            "BroadcastReceiver"
+        );
+    }
+
+    #[test]
+    fn test_unregistered_receivers() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/src/main/AndroidManifest.xml",
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.app">
+    <application>
+        <receiver android:name=".BootReceiver" android:exported="false" />
+    </application>
+</manifest>"#,
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/MainActivity.kt",
+            r#"class MainActivity : AppCompatActivity() {
+    private val receiver = NetworkReceiver()
+
+    override fun onStart() {
+        super.onStart()
+        ContextCompat.registerReceiver(this, receiver, filter, RECEIVER_NOT_EXPORTED)
+    }
+}"#,
+        );
+        write(
+            root,
+            "app/src/test/java/com/app/ReceiverTest.kt",
+            "val r = LegacyReceiver(); context.registerReceiver(r, filter)",
+        );
+
+        let mut graph = Graph::new();
+        class(&mut graph, "BootReceiver", 1, "BroadcastReceiver()");
+        class(&mut graph, "BaseReceiver", 5, "BroadcastReceiver()");
+        class(&mut graph, "NetworkReceiver", 10, "BaseReceiver()");
+        class(&mut graph, "LegacyReceiver", 15, "BaseReceiver()");
+        class(&mut graph, "WidgetProvider", 20, "AppWidgetProvider()");
+        class(&mut graph, "Repository", 25, "Base()");
+
+        let analysis = UnregisteredReceiverDetector::new().analyze(&graph, root);
+
+        assert_eq!(analysis.total_receivers, 4);
+        let names: Vec<_> = analysis
+            .unregistered
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(names, ["LegacyReceiver", "WidgetProvider"]);
+
+        let issues = analysis.to_issues();
+        assert_eq!(issues[0].issue, DeadCodeIssue::UnregisteredReceiver);
+        assert!(issues[0].message.contains("registerReceiver()"));
+    }
+}
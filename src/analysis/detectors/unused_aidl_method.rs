@@ -0,0 +1,225 @@
+//! Unused AIDL Method Detector
+//!
+//! Detects methods of AIDL interfaces that no client in the project calls.
+//! Implementations of the generated `Stub` are retained as entry points,
+//! since the binder framework dispatches to them, so a method nobody calls
+//! any more still looks used.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Parse `.aidl` files for interfaces and their methods
+//! 2. Only consider interfaces the project binds to itself
+//!    (`IFoo.Stub.asInterface(binder)`); services exposed to other apps
+//!    have clients this analysis can't see
+//! 3. Report methods never called on a receiver (`service.foo(...)`,
+//!    `service::foo`) in any Kotlin or Java source
+//!
+//! ## Examples Detected
+//!
+//! ```aidl
+//! interface IPlayerService {
+//!     void play(in Track track);
+//!     int getLegacyPosition();    // DEAD: no client calls it
+//! }
+//! ```
+
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+use super::unused_intent_extra::walk_sources;
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Language};
+use crate::parser::aidl::AidlParser;
+
+/// An AIDL method no client calls
+#[derive(Debug, Clone)]
+pub struct UnusedAidlMethod {
+    pub file: PathBuf,
+    pub line: usize,
+    pub interface: String,
+    pub method: String,
+}
+
+/// Result of AIDL method analysis
+#[derive(Debug, Default)]
+pub struct AidlAnalysis {
+    /// Methods of locally bound interfaces that are never called
+    pub unused_methods: Vec<UnusedAidlMethod>,
+    /// Total methods of locally bound interfaces
+    pub total_methods: usize,
+}
+
+impl AidlAnalysis {
+    /// Convert the findings to `DC037` issues
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        self.unused_methods
+            .iter()
+            .map(|method| {
+                let name = format!("{}.{}", method.interface, method.method);
+                let decl = Declaration::at_line(
+                    &method.file,
+                    method.line,
+                    name.as_str(),
+                    DeclarationKind::Method,
+                    Language::Aidl,
+                );
+                DeadCode::new(decl, DeadCodeIssue::UnusedAidlMethod)
+                    .with_message(format!(
+                        "AIDL method '{}' is never called by a client",
+                        name
+                    ))
+                    .with_confidence(Confidence::Medium)
+            })
+            .collect()
+    }
+}
+
+/// Detector for AIDL methods no client calls
+pub struct UnusedAidlMethodDetector {
+    parser: AidlParser,
+    // foo( / ::foo, on a receiver
+    call_pattern: Regex,
+}
+
+impl UnusedAidlMethodDetector {
+    pub fn new() -> Self {
+        Self {
+            parser: AidlParser::new(),
+            call_pattern: Regex::new(r"(?:\.|::)\s*([A-Za-z_]\w*)\b").unwrap(),
+        }
+    }
+
+    /// Analyze a directory for unused AIDL methods
+    pub fn analyze(&self, root: &Path) -> AidlAnalysis {
+        let mut interfaces = Vec::new();
+        walk_sources(root, &["aidl"], |path, content| {
+            if let Ok(file) = self.parser.parse(path, content) {
+                for interface in file.interfaces {
+                    interfaces.push((path.to_path_buf(), interface));
+                }
+            }
+        });
+
+        let mut analysis = AidlAnalysis::default();
+        if interfaces.is_empty() {
+            return analysis;
+        }
+
+        let mut called = std::collections::HashSet::new();
+        let mut bound = std::collections::HashSet::new();
+        walk_sources(root, &["kt", "java"], |_, content| {
+            for cap in self.call_pattern.captures_iter(content) {
+                called.insert(cap[1].to_string());
+            }
+            for (_, interface) in &interfaces {
+                if content.contains(&format!("{}.Stub.asInterface", interface.name)) {
+                    bound.insert(interface.name.clone());
+                }
+            }
+        });
+
+        for (file, interface) in &interfaces {
+            if !bound.contains(&interface.name) {
+                continue;
+            }
+            analysis.total_methods += interface.methods.len();
+            for method in &interface.methods {
+                if !called.contains(&method.name) {
+                    analysis.unused_methods.push(UnusedAidlMethod {
+                        file: file.clone(),
+                        line: method.line,
+                        interface: interface.name.clone(),
+                        method: method.name.clone(),
+                    });
+                }
+            }
+        }
+        analysis
+    }
+}
+
+impl Default for UnusedAidlMethodDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for UnusedAidlMethodDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedAidlMethod)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.root).to_issues()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unused_aidl_methods() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/src/main/aidl/com/app/IPlayerService.aidl",
+            r#"package com.app;
+interface IPlayerService {
+    void play(String id);
+    int getLegacyPosition();
+    void stop();
+}
+"#,
+        );
+        write(
+            root,
+            "app/src/main/aidl/com/app/IExportService.aidl",
+            "package com.app;\ninterface IExportService {\n    void export();\n}\n",
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/PlayerBinder.kt",
+            r#"class PlayerBinder : IPlayerService.Stub() {
+    override fun play(id: String) {}
+    override fun getLegacyPosition(): Int = 0
+    override fun stop() {}
+}
+"#,
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/PlayerClient.kt",
+            r#"class PlayerClient {
+    fun onServiceConnected(binder: IBinder) {
+        val service = IPlayerService.Stub.asInterface(binder)
+        service.play("intro")
+        binder.let(service::stop)
+    }
+}
+"#,
+        );
+
+        let analysis = UnusedAidlMethodDetector::new().analyze(root);
+
+        let unused: Vec<_> = analysis
+            .unused_methods
+            .iter()
+            .map(|m| (m.interface.as_str(), m.method.as_str(), m.line))
+            .collect();
+        assert_eq!(unused, [("IPlayerService", "getLegacyPosition", 4)]);
+        assert_eq!(analysis.total_methods, 3);
+
+        let issues = analysis.to_issues();
+        assert_eq!(issues[0].issue, DeadCodeIssue::UnusedAidlMethod);
+        assert_eq!(issues[0].declaration.language, Language::Aidl);
+        assert_eq!(
+            issues[0].message,
+            "AIDL method 'IPlayerService.getLegacyPosition' is never called by a client"
+        );
+    }
+}
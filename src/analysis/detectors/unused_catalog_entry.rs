@@ -0,0 +1,164 @@
+//! Unused Version Catalog Entry Detector
+//!
+//! Detects entries of Gradle version catalogs (`gradle/libs.versions.toml`)
+//! that no build script uses. Catalogs collect every dependency a project
+//! ever declared, and entries outlive the `implementation(...)` lines that
+//! used them.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Read the aliases of each catalog's `[versions]`, `[libraries]`,
+//!    `[bundles]` and `[plugins]` tables
+//! 2. Collect the catalog accessors (`libs.androidx.core.ktx`,
+//!    `libs.plugins.kotlin.android`) and name lookups (`findLibrary("...")`)
+//!    of every Gradle script and of `buildSrc` / `build-logic` sources
+//! 3. Report entries that are never used; libraries in a used bundle and
+//!    versions named by another entry's `version.ref` count as used
+//!
+//! ## Examples Detected
+//!
+//! ```toml
+//! [libraries]
+//! androidx-core-ktx = { module = "androidx.core:core-ktx", version = "1.13.1" }
+//! legacy-support = "androidx.legacy:legacy-support-v4:1.0.0"  # DEAD: no script uses libs.legacy.support
+//! ```
+
+use std::path::Path;
+
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::discovery::catalog::{CatalogEntry, CatalogReferences, VersionCatalog};
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+
+/// A catalog entry that no script uses
+#[derive(Debug, Clone)]
+pub struct UnusedCatalogEntry {
+    /// Accessor scripts would use (e.g., "libs.legacy.support")
+    pub accessor: String,
+    pub entry: CatalogEntry,
+    pub file: std::path::PathBuf,
+}
+
+/// Result of version catalog analysis
+#[derive(Debug, Default)]
+pub struct CatalogAnalysis {
+    /// Entries no script uses
+    pub unused: Vec<UnusedCatalogEntry>,
+    /// Total entries across all catalogs
+    pub total_entries: usize,
+}
+
+impl CatalogAnalysis {
+    /// Convert the findings to `DC029` issues
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        self.unused
+            .iter()
+            .map(|unused| {
+                let line = unused.entry.line;
+                let mut decl = Declaration::new(
+                    DeclarationId::new(unused.file.clone(), line, line),
+                    unused.accessor.clone(),
+                    DeclarationKind::Property,
+                    Location::new(unused.file.clone(), line, 1, line, line),
+                    Language::Toml,
+                );
+                decl.type_name = Some(unused.entry.section.as_str().to_string());
+                DeadCode::new(decl, DeadCodeIssue::UnusedCatalogEntry)
+                    .with_message(format!(
+                        "Version catalog {} '{}' is not used by any build script",
+                        unused.entry.section.as_str(),
+                        unused.accessor
+                    ))
+                    .with_confidence(Confidence::Medium)
+            })
+            .collect()
+    }
+}
+
+/// Detector for version catalog entries no build script uses
+pub struct UnusedCatalogEntryDetector;
+
+impl UnusedCatalogEntryDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Analyze the catalogs of the project rooted at `root`
+    pub fn analyze(&self, root: &Path) -> CatalogAnalysis {
+        let catalogs = VersionCatalog::find(root);
+        let references = CatalogReferences::scan(root, &catalogs);
+
+        let mut analysis = CatalogAnalysis::default();
+        for catalog in &catalogs {
+            analysis.total_entries += catalog.entries.len();
+            for entry in catalog.unused_entries(&references) {
+                analysis.unused.push(UnusedCatalogEntry {
+                    accessor: catalog.accessor(entry),
+                    entry: entry.clone(),
+                    file: catalog.file.clone(),
+                });
+            }
+        }
+        analysis
+    }
+}
+
+impl Default for UnusedCatalogEntryDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for UnusedCatalogEntryDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedCatalogEntry)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.root).to_issues()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reports_unused_entries_of_every_catalog() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "gradle/libs.versions.toml",
+            "[libraries]\ntimber = \"com.jakewharton.timber:timber:5.0.1\"\npicasso = \"com.squareup.picasso:picasso:2.8\"\n",
+        );
+        write(
+            root,
+            "gradle/testLibs.versions.toml",
+            "[libraries]\njunit = \"junit:junit:4.13.2\"\nmockk = \"io.mockk:mockk:1.13.11\"\n",
+        );
+        write(
+            root,
+            "app/build.gradle",
+            "dependencies {\n    implementation libs.timber\n    testImplementation testLibs.junit\n}\n",
+        );
+
+        let analysis = UnusedCatalogEntryDetector::new().analyze(root);
+
+        assert_eq!(analysis.total_entries, 4);
+        let issues = analysis.to_issues();
+        let reported: Vec<(&str, usize)> = issues
+            .iter()
+            .map(|dc| (dc.declaration.name.as_str(), dc.declaration.location.line))
+            .collect();
+        assert_eq!(reported, [("libs.picasso", 3), ("testLibs.mockk", 3)]);
+        assert_eq!(issues[0].issue, DeadCodeIssue::UnusedCatalogEntry);
+        assert!(issues[0]
+            .declaration
+            .location
+            .file
+            .ends_with("gradle/libs.versions.toml"));
+    }
+}
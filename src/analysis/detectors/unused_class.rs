@@ -1,5 +1,5 @@
-use super::Detector;
-use crate::analysis::DeadCode;
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{DeadCode, DeadCodeIssue};
 use crate::graph::Graph;
 
 pub struct UnusedClassDetector;
@@ -11,6 +11,10 @@ impl UnusedClassDetector {
 }
 
 impl Detector for UnusedClassDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::Unreferenced)
+    }
+
     fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
         // Detection is handled by ReachabilityAnalyzer
         Vec::new()
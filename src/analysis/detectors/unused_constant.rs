@@ -0,0 +1,160 @@
+//! Unused Constant Detector
+//!
+//! Detects Kotlin `const val` declarations (top-level, in objects and in
+//! companion objects) that nothing references.
+//!
+//! Constants are inlined by the compiler, but every use still names the
+//! constant in source, so references resolve across files like any other
+//! property: `Keys.EXTRA_ID`, `Keys.Companion.EXTRA_ID`, static imports,
+//! string templates (`"$TAG: ..."`, `"${Keys.TAG}"`), annotation arguments,
+//! `when` branches and Java call sites. Reachability analysis leaves
+//! constants to this rule.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! class DetailActivity : AppCompatActivity() {
+//!     companion object {
+//!         const val EXTRA_ID = "id"          // used by the caller's putExtra()
+//!         const val EXTRA_LEGACY = "legacy"  // DEAD: never referenced
+//!     }
+//! }
+//! ```
+
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Graph, Visibility};
+
+/// Detector for unreferenced constants
+pub struct UnusedConstantDetector;
+
+impl UnusedConstantDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for UnusedConstantDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Detector for UnusedConstantDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedConstant)
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues: Vec<DeadCode> = graph
+            .declarations()
+            .filter(|decl| decl.is_const())
+            .filter(|decl| !graph.is_referenced(&decl.id))
+            // Developer explicitly acknowledges the constant is unused
+            .filter(|decl| {
+                !decl
+                    .annotations
+                    .iter()
+                    .any(|a| a.contains("Suppress") && a.contains("unused"))
+            })
+            .map(|decl| {
+                let in_companion = decl
+                    .parent
+                    .as_ref()
+                    .and_then(|id| graph.get_declaration(id))
+                    .is_some_and(|parent| parent.modifiers.iter().any(|m| m == "companion"));
+                let confidence = if decl.visibility == Visibility::Private {
+                    Confidence::High
+                } else {
+                    Confidence::Medium
+                };
+                DeadCode::new(decl.clone(), DeadCodeIssue::UnusedConstant)
+                    .with_message(if in_companion {
+                        format!(
+                            "Companion object constant '{}' is never referenced",
+                            decl.name
+                        )
+                    } else {
+                        format!("Constant '{}' is never referenced", decl.name)
+                    })
+                    .with_confidence(confidence)
+            })
+            .collect();
+
+        issues.sort_by(|a, b| {
+            a.declaration
+                .location
+                .file
+                .cmp(&b.declaration.location.file)
+                .then(
+                    a.declaration
+                        .location
+                        .line
+                        .cmp(&b.declaration.location.line),
+                )
+        });
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        Declaration, DeclarationId, DeclarationKind, Language, Location, Reference, ReferenceKind,
+    };
+    use std::path::PathBuf;
+
+    fn declaration(name: &str, line: usize, kind: DeclarationKind) -> Declaration {
+        let file = PathBuf::from("Keys.kt");
+        Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            kind,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        )
+    }
+
+    fn constant(name: &str, line: usize, parent: &DeclarationId) -> Declaration {
+        let mut decl = declaration(name, line, DeclarationKind::Property);
+        decl.modifiers = vec!["const".to_string(), "val".to_string()];
+        decl.parent = Some(parent.clone());
+        decl
+    }
+
+    #[test]
+    fn test_reports_unreferenced_constants() {
+        let mut graph = Graph::new();
+        let activity =
+            graph.add_declaration(declaration("DetailActivity", 1, DeclarationKind::Class));
+        let mut companion = declaration("Companion", 2, DeclarationKind::Object);
+        companion.modifiers.push("companion".to_string());
+        companion.parent = Some(activity.clone());
+        let companion = graph.add_declaration(companion);
+
+        let used = graph.add_declaration(constant("EXTRA_ID", 3, &companion));
+        graph.add_declaration(constant("EXTRA_LEGACY", 4, &companion));
+        graph.add_declaration(constant("MAX_RETRIES", 9, &activity));
+        let plain = graph.add_declaration(declaration("NOT_CONST", 10, DeclarationKind::Property));
+
+        let caller = graph.add_declaration(declaration("onCreate", 20, DeclarationKind::Method));
+        let reference = Reference::new(
+            ReferenceKind::Read,
+            Location::new(PathBuf::from("Keys.kt"), 20, 1, 2000, 2050),
+            "EXTRA_ID".to_string(),
+        );
+        graph.add_reference(&caller, &used, reference);
+
+        let issues = UnusedConstantDetector::new().detect(&graph);
+
+        let names: Vec<_> = issues.iter().map(|i| i.declaration.name.as_str()).collect();
+        assert_eq!(names, ["EXTRA_LEGACY", "MAX_RETRIES"]);
+        assert!(issues
+            .iter()
+            .all(|i| i.issue == DeadCodeIssue::UnusedConstant));
+        assert!(issues[0].message.starts_with("Companion object constant"));
+        assert!(issues[1].message.starts_with("Constant"));
+        assert!(!issues.iter().any(|i| i.declaration.id == plain));
+    }
+}
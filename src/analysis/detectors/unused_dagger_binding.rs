@@ -0,0 +1,751 @@
+//! Unused Dagger/Hilt Binding Detector
+//!
+//! Detects `@Provides` and `@Binds` methods whose bound type is never
+//! requested anywhere in the injection graph, and `@Module` classes where
+//! every binding is unused. Reachability analysis keeps these methods alive
+//! as DI entry points, so without this rule they are never reported.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. In `@Module` files, find every `@Provides` / `@Binds` method and its
+//!    bound (return) type. Multibindings (`@IntoSet`, `@IntoMap`,
+//!    `@ElementsIntoSet`) are skipped: they are consumed as collections.
+//! 2. Collect requested types: parameters of `@Inject` / `@AssistedInject`
+//!    constructors, `@Inject` fields, and provision methods/properties of
+//!    `@Component`, `@Subcomponent` and `@EntryPoint` interfaces.
+//!    `Provider<T>`, `Lazy<T>` and `Optional<T>` request `T`.
+//! 3. A binding whose type is requested (and every multibinding) is used,
+//!    and requests its own parameter types in turn; repeat until no new
+//!    type is requested.
+//! 4. Report bindings that are never used, and modules whose bindings are
+//!    all unused.
+//!
+//! Qualifiers (`@Named`, custom `@Qualifier`s) are ignored, so a binding is
+//! kept when any binding of the same type is requested.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! @Module
+//! @InstallIn(SingletonComponent::class)
+//! object NetworkModule {
+//!     @Provides fun provideClient(): OkHttpClient = OkHttpClient()
+//!     @Provides fun provideLegacyApi(client: OkHttpClient): LegacyApi = ...  // DEAD
+//! }
+//!
+//! class Repository @Inject constructor(private val client: OkHttpClient)
+//! ```
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::discovery::SourceSetKind;
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+use crate::parser::line_of;
+
+/// Annotations that contribute to a multibound collection instead of a type
+const MULTIBINDING_ANNOTATIONS: &[&str] = &["IntoSet", "IntoMap", "ElementsIntoSet", "Multibinds"];
+
+/// Wrappers that request the wrapped type
+const REQUEST_WRAPPERS: &[&str] = &["Provider", "Lazy", "Optional"];
+
+/// A `@Provides` or `@Binds` method
+#[derive(Debug, Clone)]
+pub struct DaggerBinding {
+    /// Method name (e.g., "provideOkHttpClient")
+    pub name: String,
+    /// "Provides" or "Binds"
+    pub annotation: String,
+    /// Bound type, without package qualifiers (e.g., "OkHttpClient")
+    pub bound_type: String,
+    /// Parameter types Dagger injects into the method
+    pub dependencies: Vec<String>,
+    /// Whether the method contributes to a multibound collection
+    pub multibound: bool,
+    /// Enclosing `@Module` class
+    pub module: String,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Byte range of the method signature
+    pub span: (usize, usize),
+}
+
+/// A `@Module` class with at least one binding
+#[derive(Debug, Clone)]
+pub struct DaggerModule {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub span: (usize, usize),
+}
+
+/// Result of Dagger/Hilt binding analysis
+#[derive(Debug, Default)]
+pub struct DaggerAnalysis {
+    /// Bindings whose type is never requested
+    pub unused_bindings: Vec<DaggerBinding>,
+    /// Modules whose bindings are all unused
+    pub unused_modules: Vec<DaggerModule>,
+    /// Total bindings found (multibindings excluded)
+    pub total_bindings: usize,
+    /// Distinct types requested from the graph
+    pub total_requested: usize,
+}
+
+impl DaggerAnalysis {
+    /// Convert the findings to `DC017` issues
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        let modules = self.unused_modules.iter().map(|module| {
+            let decl = synthetic_declaration(
+                &module.name,
+                DeclarationKind::Class,
+                &module.file,
+                module.line,
+                module.span,
+            );
+            DeadCode::new(decl, DeadCodeIssue::UnusedBinding)
+                .with_message(format!(
+                    "Module '{}' has no binding that is ever injected",
+                    module.name
+                ))
+                .with_confidence(Confidence::Medium)
+        });
+
+        let bindings = self.unused_bindings.iter().map(|binding| {
+            let decl = synthetic_declaration(
+                &binding.name,
+                DeclarationKind::Method,
+                &binding.file,
+                binding.line,
+                binding.span,
+            );
+            DeadCode::new(decl, DeadCodeIssue::UnusedBinding)
+                .with_message(format!(
+                    "@{} '{}' binds {}, which is never injected",
+                    binding.annotation, binding.name, binding.bound_type
+                ))
+                .with_confidence(Confidence::Medium)
+        });
+
+        modules.chain(bindings).collect()
+    }
+}
+
+fn synthetic_declaration(
+    name: &str,
+    kind: DeclarationKind,
+    file: &Path,
+    line: usize,
+    span: (usize, usize),
+) -> Declaration {
+    let language = match file.extension().and_then(|e| e.to_str()) {
+        Some("java") => Language::Java,
+        _ => Language::Kotlin,
+    };
+    Declaration::new(
+        DeclarationId::new(file.to_path_buf(), span.0, span.1),
+        name.to_string(),
+        kind,
+        Location::new(file.to_path_buf(), line, 1, span.0, span.1),
+        language,
+    )
+}
+
+/// Detector for unused Dagger/Hilt bindings
+pub struct UnusedDaggerBindingDetector {
+    // @Module ... class/object/interface Name
+    module_pattern: Regex,
+    // @Provides / @Binds
+    binding_pattern: Regex,
+    // Kotlin: fun name(
+    kotlin_fun_pattern: Regex,
+    // Java: Type name(
+    java_method_pattern: Regex,
+    // @Inject constructor( / @AssistedInject constructor(
+    kotlin_inject_constructor_pattern: Regex,
+    // @Inject public Name(
+    java_inject_constructor_pattern: Regex,
+    // @Inject lateinit var name: Type
+    kotlin_inject_field_pattern: Regex,
+    // @Inject Type name;
+    java_inject_field_pattern: Regex,
+    // @Component / @Subcomponent / @EntryPoint ... interface Name {
+    component_pattern: Regex,
+    // fun name(): Type / val name: Type
+    kotlin_provision_pattern: Regex,
+    // Type name();
+    java_provision_pattern: Regex,
+    // Leading annotations, e.g. @Named("x")
+    annotation_pattern: Regex,
+}
+
+impl UnusedDaggerBindingDetector {
+    pub fn new() -> Self {
+        Self {
+            module_pattern: Regex::new(
+                r"@Module\b(?:\([^)]*\))?[^{;]*?\b(?:class|object|interface)\s+(\w+)",
+            )
+            .unwrap(),
+            binding_pattern: Regex::new(r"@(Provides|Binds)\b").unwrap(),
+            kotlin_fun_pattern: Regex::new(r"\bfun\s+(?:<[^>]*>\s*)?(\w+)\s*\(").unwrap(),
+            java_method_pattern: Regex::new(r"([\w.]+(?:<[^()]*>)?(?:\[\])?)\s+(\w+)\s*\(").unwrap(),
+            kotlin_inject_constructor_pattern: Regex::new(
+                r"@(?:Inject|AssistedInject)\s+constructor\s*\(",
+            )
+            .unwrap(),
+            java_inject_constructor_pattern: Regex::new(
+                r"@(?:Inject|AssistedInject)\s+(?:(?:public|protected|private)\s+)?[A-Z]\w*\s*\(",
+            )
+            .unwrap(),
+            kotlin_inject_field_pattern: Regex::new(
+                r"@(?:field:)?Inject\s+(?:@[\w:]+(?:\([^)]*\))?\s+)*(?:(?:internal|protected|public|private)\s+)?(?:lateinit\s+)?(?:var|val)\s+\w+\s*:\s*([^=\n]+)",
+            )
+            .unwrap(),
+            java_inject_field_pattern: Regex::new(
+                r"@Inject\s+(?:@\w+(?:\([^)]*\))?\s+)*(?:(?:public|protected|private|final)\s+)*([\w.]+(?:<[^;()]*>)?)\s+\w+\s*;",
+            )
+            .unwrap(),
+            component_pattern: Regex::new(
+                r"@(?:Component|Subcomponent|EntryPoint|DefineComponent)\b(?:\((?:[^()]|\([^()]*\))*\))?[^{;]*?\b(?:interface|class)\s+\w+[^{]*\{",
+            )
+            .unwrap(),
+            kotlin_provision_pattern: Regex::new(
+                r"\bfun\s+\w+\s*\(\s*\)\s*:\s*([^\n={]+)|\bval\s+\w+\s*:\s*([^\n={]+)",
+            )
+            .unwrap(),
+            java_provision_pattern: Regex::new(r"([\w.]+(?:<[^;()]*>)?)\s+\w+\s*\(\s*\)\s*;")
+                .unwrap(),
+            annotation_pattern: Regex::new(r"@[\w:.]+(?:\((?:[^()]|\([^()]*\))*\))?").unwrap(),
+        }
+    }
+
+    /// Analyze a directory for unused bindings
+    pub fn analyze(&self, root: &Path) -> DaggerAnalysis {
+        use ignore::WalkBuilder;
+
+        let mut modules: Vec<(DaggerModule, Vec<DaggerBinding>)> = Vec::new();
+        let mut requested = HashSet::new();
+
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+
+            // Only process Kotlin and Java files
+            let ext = path.extension().and_then(|e| e.to_str());
+            let is_java = match ext {
+                Some("kt") => false,
+                Some("java") => true,
+                _ => continue,
+            };
+
+            // Skip test files
+            if SourceSetKind::from_path(path).is_test() {
+                continue;
+            }
+
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            if !content.contains("Inject")
+                && !content.contains("@Module")
+                && !content.contains("@Component")
+                && !content.contains("@Subcomponent")
+                && !content.contains("@EntryPoint")
+            {
+                continue;
+            }
+
+            modules.extend(self.collect_modules(&content, path, is_java));
+            self.collect_requests(&content, is_java, &mut requested);
+        }
+
+        // A binding's parameters are only injected when the binding is, so
+        // follow dependencies from the requested types until nothing changes
+        let mut used = HashSet::new();
+        loop {
+            let newly_used: Vec<&DaggerBinding> = modules
+                .iter()
+                .flat_map(|(_, bindings)| bindings)
+                .filter(|b| !used.contains(&(&b.file, b.span)))
+                .filter(|b| b.multibound || requested.contains(&b.bound_type))
+                .collect();
+            if newly_used.is_empty() {
+                break;
+            }
+            for binding in newly_used {
+                used.insert((&binding.file, binding.span));
+                requested.extend(binding.dependencies.iter().cloned());
+            }
+        }
+
+        let mut analysis = DaggerAnalysis {
+            total_requested: requested.len(),
+            ..Default::default()
+        };
+        for (module, bindings) in &modules {
+            let bindings: Vec<&DaggerBinding> = bindings.iter().filter(|b| !b.multibound).collect();
+            analysis.total_bindings += bindings.len();
+            let unused: Vec<DaggerBinding> = bindings
+                .iter()
+                .filter(|b| !used.contains(&(&b.file, b.span)))
+                .map(|b| (*b).clone())
+                .collect();
+            if !bindings.is_empty() && unused.len() == bindings.len() {
+                analysis.unused_modules.push(module.clone());
+            }
+            analysis.unused_bindings.extend(unused);
+        }
+
+        analysis
+            .unused_modules
+            .sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        analysis
+            .unused_bindings
+            .sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        analysis
+    }
+
+    /// Find `@Module` classes and their bindings
+    fn collect_modules(
+        &self,
+        content: &str,
+        file: &Path,
+        is_java: bool,
+    ) -> Vec<(DaggerModule, Vec<DaggerBinding>)> {
+        let mut modules: Vec<(DaggerModule, Vec<DaggerBinding>)> = self
+            .module_pattern
+            .captures_iter(content)
+            .map(|caps| {
+                let whole = caps.get(0).unwrap();
+                let module = DaggerModule {
+                    name: caps[1].to_string(),
+                    file: file.to_path_buf(),
+                    line: line_of(content, whole.start()),
+                    span: (whole.start(), whole.end()),
+                };
+                (module, Vec::new())
+            })
+            .collect();
+        if modules.is_empty() {
+            return modules;
+        }
+
+        for caps in self.binding_pattern.captures_iter(content) {
+            let annotation = caps.get(0).unwrap();
+            let Some(binding) = self.parse_binding(content, annotation.start(), &caps[1], is_java)
+            else {
+                continue;
+            };
+
+            // Bindings belong to the closest module declared before them
+            let module = modules
+                .iter_mut()
+                .rev()
+                .find(|(m, _)| m.span.0 < annotation.start());
+            if let Some((module, bindings)) = module {
+                bindings.push(DaggerBinding {
+                    module: module.name.clone(),
+                    file: file.to_path_buf(),
+                    ..binding
+                });
+            }
+        }
+
+        modules
+    }
+
+    /// Parse the method following a `@Provides` / `@Binds` annotation
+    ///
+    fn parse_binding(
+        &self,
+        content: &str,
+        start: usize,
+        annotation: &str,
+        is_java: bool,
+    ) -> Option<DaggerBinding> {
+        let rest = &content[start..];
+        let (name, header_len, params_start) = if is_java {
+            // Blank out annotations so `@Named("x")` isn't read as the return
+            // type; the signature ends at the method body or `;`
+            let end = rest.find(['{', ';']).unwrap_or(rest.len());
+            let header = self
+                .annotation_pattern
+                .replace_all(&rest[..end], |caps: &regex::Captures| {
+                    " ".repeat(caps[0].len())
+                });
+            let caps = self.java_method_pattern.captures(&header)?;
+            let whole = caps.get(0)?;
+            (caps[2].to_string(), whole.start(), whole.end())
+        } else {
+            let caps = self.kotlin_fun_pattern.captures(rest)?;
+            let whole = caps.get(0)?;
+            (caps[1].to_string(), whole.start(), whole.end())
+        };
+
+        let annotations = &rest[..header_len];
+        let multibound = MULTIBINDING_ANNOTATIONS
+            .iter()
+            .any(|a| annotations.contains(&format!("@{}", a)));
+
+        let params = balanced_args(&rest[params_start..])?;
+        let params_end = params_start + params.len() + 1;
+        let dependencies: Vec<String> = split_top_level(params)
+            .filter_map(|param| self.param_type(param, is_java))
+            .collect();
+
+        let bound_type = if is_java {
+            let header = self
+                .annotation_pattern
+                .replace_all(&rest[..params_start], " ");
+            let caps = self.java_method_pattern.captures(&header)?;
+            normalize_type(&caps[1])
+        } else {
+            let after = rest[params_end..].trim_start();
+            let ty = after.strip_prefix(':')?;
+            let end = ty.find(['=', '{', '\n']).unwrap_or(ty.len());
+            normalize_type(&ty[..end])
+        };
+        if bound_type.is_empty() {
+            return None;
+        }
+
+        Some(DaggerBinding {
+            name,
+            annotation: annotation.to_string(),
+            bound_type,
+            dependencies,
+            multibound,
+            module: String::new(),
+            file: PathBuf::new(),
+            line: line_of(content, start),
+            span: (start, start + params_end),
+        })
+    }
+
+    /// Collect types requested by injection sites and component interfaces
+    fn collect_requests(&self, content: &str, is_java: bool, requested: &mut HashSet<String>) {
+        let constructor_pattern = if is_java {
+            &self.java_inject_constructor_pattern
+        } else {
+            &self.kotlin_inject_constructor_pattern
+        };
+        for m in constructor_pattern.find_iter(content) {
+            if let Some(params) = balanced_args(&content[m.end()..]) {
+                requested.extend(
+                    split_top_level(params).filter_map(|param| self.param_type(param, is_java)),
+                );
+            }
+        }
+
+        let field_pattern = if is_java {
+            &self.java_inject_field_pattern
+        } else {
+            &self.kotlin_inject_field_pattern
+        };
+        for caps in field_pattern.captures_iter(content) {
+            requested.insert(normalize_type(&caps[1]));
+        }
+
+        for m in self.component_pattern.find_iter(content) {
+            let Some(body) = balanced_body(&content[m.end()..]) else {
+                continue;
+            };
+            if is_java {
+                for caps in self.java_provision_pattern.captures_iter(body) {
+                    requested.insert(normalize_type(&caps[1]));
+                }
+            } else {
+                for caps in self.kotlin_provision_pattern.captures_iter(body) {
+                    if let Some(ty) = caps.get(1).or(caps.get(2)) {
+                        requested.insert(normalize_type(ty.as_str()));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Type of a single parameter (`@Named("x") client: OkHttpClient` / `OkHttpClient client`)
+    fn param_type(&self, param: &str, is_java: bool) -> Option<String> {
+        let param = self.annotation_pattern.replace_all(param, " ");
+        let param = param.trim();
+        let ty = if is_java {
+            let ty = param.rsplit_once(char::is_whitespace)?.0;
+            ty.trim_start_matches("final ").trim()
+        } else {
+            let ty = param.split_once(':')?.1;
+            ty.split('=').next().unwrap_or(ty)
+        };
+        let ty = normalize_type(ty);
+        (!ty.is_empty()).then_some(ty)
+    }
+}
+
+impl Default for UnusedDaggerBindingDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for UnusedDaggerBindingDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedBinding)
+    }
+
+    // Reachability reports the same bindings and modules; the DC017 finding
+    // names the type that is never injected
+    fn replaces_reachability(&self) -> bool {
+        true
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.root).to_issues()
+    }
+}
+
+/// Canonical form of a type for matching bindings to requests
+///
+/// Drops package qualifiers, nullability, whitespace and
+/// `@JvmSuppressWildcards`, and unwraps `Provider<T>` / `Lazy<T>` / `Optional<T>`.
+fn normalize_type(ty: &str) -> String {
+    let mut ty: String = ty
+        .replace("@JvmSuppressWildcards", "")
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '?')
+        .collect();
+    ty = strip_packages(&ty);
+
+    loop {
+        let unwrapped = REQUEST_WRAPPERS.iter().find_map(|wrapper| {
+            ty.strip_prefix(wrapper)
+                .and_then(|rest| rest.strip_prefix('<'))
+                .and_then(|rest| rest.strip_suffix('>'))
+                .map(str::to_string)
+        });
+        match unwrapped {
+            Some(inner) => ty = inner,
+            None => return ty,
+        }
+    }
+}
+
+/// `java.util.List<com.app.Foo>` -> `List<Foo>`
+fn strip_packages(ty: &str) -> String {
+    let mut result = String::with_capacity(ty.len());
+    let mut segment = String::new();
+    for c in ty.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else if c == '.' && segment.starts_with(|c: char| c.is_lowercase()) {
+            segment.clear();
+        } else {
+            result.push_str(&segment);
+            segment.clear();
+            result.push(c);
+        }
+    }
+    result.push_str(&segment);
+    result
+}
+
+/// Text up to the `)` matching an already consumed `(`
+fn balanced_args(text: &str) -> Option<&str> {
+    balanced(text, '(', ')')
+}
+
+/// Text up to the `}` matching an already consumed `{`
+fn balanced_body(text: &str) -> Option<&str> {
+    balanced(text, '{', '}')
+}
+
+fn balanced(text: &str, open: char, close: char) -> Option<&str> {
+    let mut depth = 1;
+    for (i, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(&text[..i]);
+            }
+        }
+    }
+    None
+}
+
+/// Split on commas that aren't nested in brackets
+fn split_top_level(text: &str) -> impl Iterator<Item = &str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '<' | '[' | '{' => depth += 1,
+            ')' | '>' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts.into_iter().filter(|p| !p.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_normalize_type() {
+        assert_eq!(normalize_type(" okhttp3.OkHttpClient? "), "OkHttpClient");
+        assert_eq!(normalize_type("Provider<com.app.Repo>"), "Repo");
+        assert_eq!(normalize_type("dagger.Lazy<Provider<Repo>>"), "Repo");
+        assert_eq!(
+            normalize_type("Set<@JvmSuppressWildcards Interceptor>"),
+            "Set<Interceptor>"
+        );
+        assert_eq!(
+            normalize_type("Map.Entry<String, Foo>"),
+            "Map.Entry<String,Foo>"
+        );
+    }
+
+    #[test]
+    fn test_kotlin_bindings() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/src/main/java/app/di/NetworkModule.kt",
+            r#"package app.di
+
+@Module
+@InstallIn(SingletonComponent::class)
+object NetworkModule {
+    @Provides
+    @Singleton
+    fun provideClient(): OkHttpClient = OkHttpClient()
+
+    @Provides
+    fun provideLegacyApi(@Named("legacy") client: OkHttpClient): LegacyApi =
+        LegacyApi(client)
+
+    @Provides
+    @IntoSet
+    fun provideInterceptor(): Interceptor = LoggingInterceptor()
+}
+
+@Module
+@InstallIn(SingletonComponent::class)
+abstract class RepositoryModule {
+    @Binds
+    abstract fun bindRepository(impl: UserRepositoryImpl): UserRepository
+}
+
+@Module
+interface OldModule {
+    @Binds fun bindCache(impl: DiskCache): Cache
+}
+"#,
+        );
+        write(
+            root,
+            "app/src/main/java/app/data/UserRepositoryImpl.kt",
+            r#"class UserRepositoryImpl @Inject constructor(
+    private val client: Provider<OkHttpClient>,
+) : UserRepository
+"#,
+        );
+        write(
+            root,
+            "app/src/main/java/app/ui/MainActivity.kt",
+            r#"@AndroidEntryPoint
+class MainActivity : AppCompatActivity() {
+    @Inject lateinit var repository: UserRepository
+}
+"#,
+        );
+
+        let analysis = UnusedDaggerBindingDetector::new().analyze(root);
+
+        assert_eq!(analysis.total_bindings, 4);
+        let unused: Vec<_> = analysis
+            .unused_bindings
+            .iter()
+            .map(|b| (b.module.as_str(), b.name.as_str(), b.bound_type.as_str()))
+            .collect();
+        assert_eq!(
+            unused,
+            [
+                ("NetworkModule", "provideLegacyApi", "LegacyApi"),
+                ("OldModule", "bindCache", "Cache"),
+            ]
+        );
+        let modules: Vec<_> = analysis
+            .unused_modules
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(modules, ["OldModule"]);
+
+        let issues = analysis.to_issues();
+        assert_eq!(issues.len(), 3);
+        assert!(issues
+            .iter()
+            .all(|i| i.issue == DeadCodeIssue::UnusedBinding));
+        assert_eq!(issues[1].declaration.location.line, 10);
+    }
+
+    #[test]
+    fn test_java_bindings_and_components() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/java/app/AppModule.java",
+            r#"@Module
+public class AppModule {
+    @Provides
+    @Singleton
+    static Gson provideGson() { return new Gson(); }
+
+    @Provides
+    @Named("clock")
+    public java.time.Clock provideClock() { return Clock.systemUTC(); }
+
+    @Provides
+    Analytics provideAnalytics(Gson gson) { return new Analytics(gson); }
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/app/AppComponent.java",
+            r#"@Singleton
+@Component(modules = {AppModule.class})
+public interface AppComponent {
+    Clock clock();
+    void inject(MainActivity activity);
+}
+"#,
+        );
+
+        let analysis = UnusedDaggerBindingDetector::new().analyze(root);
+
+        let unused: Vec<_> = analysis
+            .unused_bindings
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect();
+        // Gson is only injected into the unused Analytics binding
+        assert_eq!(unused, ["provideGson", "provideAnalytics"]);
+        assert!(analysis.unused_modules.is_empty());
+    }
+}
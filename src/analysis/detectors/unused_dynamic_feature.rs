@@ -0,0 +1,308 @@
+//! Unused Dynamic Feature Module Detector
+//!
+//! Detects dynamic feature modules that are built and shipped but never
+//! installed or launched. Nothing in the base module depends on a feature
+//! module, so the graph can't tell whether it is reachable: it only runs
+//! once code requests it by name or opens one of its screens.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find modules applying the `com.android.dynamic-feature` plugin and
+//!    read their delivery type from the `dist:module` manifest entry
+//! 2. Collect the fully qualified names of the classes each module declares
+//! 3. Outside the module, look for a request or launch: the module name as
+//!    a string (`SplitInstallRequest.newBuilder().addModule("chat")`,
+//!    `deferredInstall(listOf("chat"))`, `app:moduleName="chat"` in a
+//!    navigation graph) or one of its classes by name
+//!    (`setClassName(context, "com.app.chat.ChatActivity")`, imports)
+//! 4. Report modules with neither
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! // feature/chat/build.gradle.kts
+//! plugins { id("com.android.dynamic-feature") }
+//!
+//! // feature/legacy_reports/build.gradle.kts - DEAD: never requested
+//! plugins { id("com.android.dynamic-feature") }
+//!
+//! // app
+//! splitInstallManager.startInstall(
+//!     SplitInstallRequest.newBuilder().addModule("chat").build()
+//! )
+//! ```
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::unused_intent_extra::walk_sources;
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Language};
+use crate::parser::line_of;
+
+/// A dynamic feature module
+#[derive(Debug, Clone)]
+pub struct DynamicFeatureModule {
+    /// Module name used by `SplitInstallRequest` (e.g., "chat")
+    pub name: String,
+    pub dir: PathBuf,
+    /// Build script applying the dynamic feature plugin
+    pub build_file: PathBuf,
+    pub line: usize,
+    /// "on-demand", "install-time" or "conditional"
+    pub delivery: String,
+}
+
+/// Result of dynamic feature analysis
+#[derive(Debug, Default)]
+pub struct DynamicFeatureAnalysis {
+    /// Feature modules that are never requested or launched
+    pub unused_modules: Vec<DynamicFeatureModule>,
+    /// Total dynamic feature modules found
+    pub total_modules: usize,
+}
+
+impl DynamicFeatureAnalysis {
+    /// Convert the findings to `DC035` issues, reported on each module's
+    /// build script
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        self.unused_modules
+            .iter()
+            .map(|module| {
+                let language = Language::from_path(&module.build_file).unwrap_or(Language::Gradle);
+                let decl = Declaration::at_line(
+                    &module.build_file,
+                    module.line,
+                    module.name.as_str(),
+                    DeclarationKind::Package,
+                    language,
+                );
+                DeadCode::new(decl, DeadCodeIssue::UnusedDynamicFeature)
+                    .with_message(format!(
+                        "Dynamic feature module '{}' ({}) is never requested or launched",
+                        module.name, module.delivery
+                    ))
+                    .with_confidence(Confidence::Medium)
+            })
+            .collect()
+    }
+}
+
+/// Detector for dynamic feature modules nothing installs or launches
+pub struct UnusedDynamicFeatureDetector {
+    // id("com.android.dynamic-feature"), apply plugin: '...', alias(libs.plugins....)
+    plugin_pattern: Regex,
+    package_pattern: Regex,
+    class_pattern: Regex,
+    string_pattern: Regex,
+    import_pattern: Regex,
+}
+
+impl UnusedDynamicFeatureDetector {
+    pub fn new() -> Self {
+        Self {
+            plugin_pattern: Regex::new(
+                r"com\.android\.dynamic-feature|plugins\.(?:android\.)?dynamic\.feature\b",
+            )
+            .unwrap(),
+            package_pattern: Regex::new(r"(?m)^\s*package\s+([\w.]+)").unwrap(),
+            class_pattern: Regex::new(r"\b(?:class|object|interface)\s+([A-Z]\w*)").unwrap(),
+            string_pattern: Regex::new(r#""([\w.:-]+)""#).unwrap(),
+            import_pattern: Regex::new(r"(?m)^\s*import\s+(?:static\s+)?([\w.]+)").unwrap(),
+        }
+    }
+
+    /// Analyze a directory for dynamic feature modules that are never used
+    pub fn analyze(&self, root: &Path) -> DynamicFeatureAnalysis {
+        let mut modules = Vec::new();
+        walk_sources(root, &["gradle", "kts"], |path, content| {
+            let is_build_script = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("build.gradle"));
+            let Some(found) = self.plugin_pattern.find(content) else {
+                return;
+            };
+            let Some(dir) = path.parent().filter(|_| is_build_script) else {
+                return;
+            };
+            let Some(name) = dir.file_name().and_then(|n| n.to_str()) else {
+                return;
+            };
+            modules.push(DynamicFeatureModule {
+                name: name.to_string(),
+                dir: dir.to_path_buf(),
+                build_file: path.to_path_buf(),
+                line: line_of(content, found.start()),
+                delivery: delivery(dir),
+            });
+        });
+
+        let mut sources: Vec<(PathBuf, String)> = Vec::new();
+        walk_sources(root, &["kt", "java", "xml"], |path, content| {
+            sources.push((path.to_path_buf(), content.to_string()));
+        });
+
+        let total_modules = modules.len();
+        let mut unused_modules: Vec<DynamicFeatureModule> = modules
+            .into_iter()
+            .filter(|module| {
+                let classes = self.declared_classes(&module.dir, &sources);
+                let used = sources
+                    .iter()
+                    .filter(|(path, _)| !path.starts_with(&module.dir))
+                    .any(|(_, content)| {
+                        self.string_pattern
+                            .captures_iter(content)
+                            .any(|cap| cap[1] == module.name || classes.contains(&cap[1]))
+                            || self
+                                .import_pattern
+                                .captures_iter(content)
+                                .any(|cap| classes.contains(&cap[1]))
+                    });
+                !used
+            })
+            .collect();
+        unused_modules.sort_by(|a, b| a.build_file.cmp(&b.build_file));
+
+        DynamicFeatureAnalysis {
+            unused_modules,
+            total_modules,
+        }
+    }
+
+    /// Fully qualified names of the classes declared in a module's sources
+    fn declared_classes(&self, dir: &Path, sources: &[(PathBuf, String)]) -> HashSet<String> {
+        let mut classes = HashSet::new();
+        for (path, content) in sources {
+            let is_code = path
+                .extension()
+                .is_some_and(|ext| ext == "kt" || ext == "java");
+            if !is_code || !path.starts_with(dir) {
+                continue;
+            }
+            let Some(package) = self.package_pattern.captures(content) else {
+                continue;
+            };
+            for cap in self.class_pattern.captures_iter(content) {
+                classes.insert(format!("{}.{}", &package[1], &cap[1]));
+            }
+        }
+        classes
+    }
+}
+
+impl Default for UnusedDynamicFeatureDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for UnusedDynamicFeatureDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedDynamicFeature)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.root).to_issues()
+    }
+}
+
+/// Delivery type declared by the module's `dist:module` manifest entry
+fn delivery(module_dir: &Path) -> String {
+    let manifest = std::fs::read_to_string(module_dir.join("src/main/AndroidManifest.xml"))
+        .unwrap_or_default();
+    if manifest.contains("<dist:conditions") {
+        "conditional"
+    } else if manifest.contains("<dist:install-time") {
+        "install-time"
+    } else {
+        "on-demand"
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unused_dynamic_features() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let feature = r#"plugins {
+    id("com.android.dynamic-feature")
+}"#;
+        for module in ["chat", "camera", "reports"] {
+            write(
+                root,
+                &format!("feature/{}/build.gradle.kts", module),
+                feature,
+            );
+            write(
+                root,
+                &format!("feature/{0}/src/main/java/com/app/{0}/Screen.kt", module),
+                &format!(
+                    "package com.app.{}\n\nclass {}Activity : AppCompatActivity()",
+                    module,
+                    module.to_uppercase()
+                ),
+            );
+        }
+        write(
+            root,
+            "feature/reports/src/main/AndroidManifest.xml",
+            r#"<manifest><dist:module><dist:delivery><dist:install-time/></dist:delivery></dist:module></manifest>"#,
+        );
+        write(
+            root,
+            "feature/reports/src/main/java/com/app/reports/Deeplink.kt",
+            r#"package com.app.reports
+val target = "reports""#,
+        );
+        write(
+            root,
+            "app/build.gradle.kts",
+            r#"plugins { id("com.android.application") }
+android { dynamicFeatures += setOf(":feature:chat", ":feature:camera", ":feature:reports") }"#,
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/Installer.kt",
+            r#"package com.app
+
+fun install(manager: SplitInstallManager) {
+    manager.startInstall(SplitInstallRequest.newBuilder().addModule("chat").build())
+}"#,
+        );
+        write(
+            root,
+            "app/src/main/res/navigation/main.xml",
+            r#"<navigation>
+    <activity android:id="@+id/camera" android:name="com.app.camera.CAMERAActivity" />
+</navigation>"#,
+        );
+
+        let analysis = UnusedDynamicFeatureDetector::new().analyze(root);
+
+        let unused: Vec<_> = analysis
+            .unused_modules
+            .iter()
+            .map(|m| (m.name.as_str(), m.delivery.as_str(), m.line))
+            .collect();
+        assert_eq!(unused, [("reports", "install-time", 2)]);
+        assert_eq!(analysis.total_modules, 3);
+
+        let issues = analysis.to_issues();
+        assert_eq!(issues[0].issue, DeadCodeIssue::UnusedDynamicFeature);
+        assert_eq!(issues[0].declaration.language, Language::Kotlin);
+        assert_eq!(
+            issues[0].message,
+            "Dynamic feature module 'reports' (install-time) is never requested or launched"
+        );
+    }
+}
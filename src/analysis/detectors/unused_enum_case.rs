@@ -1,20 +1,197 @@
-use super::Detector;
-use crate::analysis::DeadCode;
-use crate::graph::Graph;
+//! Unused Enum Case Detector
+//!
+//! Detects enum constants that are never referenced by name and never
+//! produced by a mapper.
+//!
+//! A constant that nothing names can still be reached dynamically when the
+//! enum is enumerated (`values()`, `entries`, `valueOf()`, `enumValues<T>()`,
+//! `EnumSet.allOf()`) or deserialized. Those constants are still reported,
+//! but with low confidence instead of being suppressed. Constants mapped
+//! explicitly with `@SerializedName`, `@SerialName`, `@Json` or
+//! `@JsonProperty` are produced by deserialization and never reported.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! enum class Status { ACTIVE, INACTIVE, LEGACY }  // LEGACY: never referenced
+//!
+//! fun Status.label() = when (this) {
+//!     Status.ACTIVE -> "On"
+//!     else -> "Off"
+//! }
+//! ```
 
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Graph, ReferenceKind, Visibility};
+
+/// Annotations that map a constant to a serialized name
+const MAPPED_CASE_ANNOTATIONS: &[&str] = &["SerializedName", "SerialName", "Json", "JsonProperty"];
+
+/// Annotations that let a serializer produce any constant of the enum
+const SERIALIZABLE_ENUM_ANNOTATIONS: &[&str] = &["Serializable", "JsonClass"];
+
+/// Detector for enum constants that are never used
 pub struct UnusedEnumCaseDetector;
+
 impl UnusedEnumCaseDetector {
     pub fn new() -> Self {
         Self
     }
+
+    /// Whether every constant of the enum can be produced dynamically
+    fn is_enumerated(graph: &Graph, enum_decl: &Declaration) -> bool {
+        enum_decl
+            .annotation_names()
+            .any(|name| SERIALIZABLE_ENUM_ANNOTATIONS.contains(&name))
+            || graph.get_references_to(&enum_decl.id).iter().any(|(_, r)| {
+                matches!(
+                    r.kind,
+                    ReferenceKind::EnumValues | ReferenceKind::Reflection
+                )
+            })
+    }
 }
+
 impl Detector for UnusedEnumCaseDetector {
-    fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
-        Vec::new()
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedEnumCase)
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues: Vec<DeadCode> = graph
+            .declarations()
+            .filter(|decl| decl.kind == DeclarationKind::EnumCase)
+            .filter(|decl| !graph.is_referenced(&decl.id))
+            .filter(|decl| {
+                !decl
+                    .annotation_names()
+                    .any(|name| MAPPED_CASE_ANNOTATIONS.contains(&name))
+            })
+            // Developer explicitly acknowledges the constant is unused
+            .filter(|decl| {
+                !decl
+                    .annotations
+                    .iter()
+                    .any(|a| a.contains("Suppress") && a.contains("unused"))
+            })
+            .filter_map(|decl| {
+                let enum_decl = graph.get_declaration(decl.parent.as_ref()?)?;
+                // An unreferenced enum is reported as a whole
+                if !graph.is_referenced(&enum_decl.id) {
+                    return None;
+                }
+
+                let issue = DeadCode::new(decl.clone(), DeadCodeIssue::UnusedEnumCase);
+                Some(if Self::is_enumerated(graph, enum_decl) {
+                    issue
+                        .with_message(format!(
+                            "Enum case '{}' is never referenced by name; '{}' is enumerated or deserialized, so it may still be produced at runtime",
+                            decl.name, enum_decl.name
+                        ))
+                        .with_confidence(Confidence::Low)
+                } else {
+                    let confidence = if enum_decl.visibility == Visibility::Private {
+                        Confidence::High
+                    } else {
+                        Confidence::Medium
+                    };
+                    issue
+                        .with_message(format!(
+                            "Enum case '{}.{}' is never referenced",
+                            enum_decl.name, decl.name
+                        ))
+                        .with_confidence(confidence)
+                })
+            })
+            .collect();
+
+        // Enum constants often share a line, so order by position within the file
+        issues.sort_by(|a, b| {
+            let (a, b) = (&a.declaration.location, &b.declaration.location);
+            a.file.cmp(&b.file).then(a.start_byte.cmp(&b.start_byte))
+        });
+        issues
     }
 }
+
 impl Default for UnusedEnumCaseDetector {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Language, Location, Reference};
+    use std::path::PathBuf;
+
+    fn declaration(name: &str, line: usize, kind: DeclarationKind) -> Declaration {
+        let file = PathBuf::from("Enums.kt");
+        Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            kind,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        )
+    }
+
+    fn case(graph: &mut Graph, name: &str, line: usize, parent: &DeclarationId) -> DeclarationId {
+        let mut decl = declaration(name, line, DeclarationKind::EnumCase);
+        decl.parent = Some(parent.clone());
+        graph.add_declaration(decl)
+    }
+
+    fn reference(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId, kind: ReferenceKind) {
+        let location = Location::new(PathBuf::from("Main.kt"), 1, 1, 0, 10);
+        graph.add_reference(from, to, Reference::new(kind, location, String::new()));
+    }
+
+    #[test]
+    fn test_unused_enum_cases() {
+        let mut graph = Graph::new();
+        let main = graph.add_declaration(declaration("main", 50, DeclarationKind::Function));
+
+        // Status.ACTIVE is used, LEGACY is not
+        let status = graph.add_declaration(declaration("Status", 1, DeclarationKind::Enum));
+        let active = case(&mut graph, "ACTIVE", 2, &status);
+        case(&mut graph, "LEGACY", 3, &status);
+        reference(&mut graph, &main, &status, ReferenceKind::Read);
+        reference(&mut graph, &main, &active, ReferenceKind::Read);
+
+        // Mode.entries is iterated, so SLOW may still be produced
+        let mode = graph.add_declaration(declaration("Mode", 10, DeclarationKind::Enum));
+        case(&mut graph, "SLOW", 11, &mode);
+        reference(&mut graph, &main, &mode, ReferenceKind::EnumValues);
+
+        // Level.UNKNOWN is mapped by the JSON deserializer
+        let level = graph.add_declaration(declaration("Level", 20, DeclarationKind::Enum));
+        let mut unknown = declaration("UNKNOWN", 21, DeclarationKind::EnumCase);
+        unknown.parent = Some(level.clone());
+        unknown.annotations = vec!["@SerializedName(\"unknown\")".to_string()];
+        graph.add_declaration(unknown);
+        reference(&mut graph, &main, &level, ReferenceKind::Type);
+
+        // Unused enums are reported as a whole, not case by case
+        let orphan = graph.add_declaration(declaration("Orphan", 30, DeclarationKind::Enum));
+        case(&mut graph, "A", 31, &orphan);
+
+        let issues = UnusedEnumCaseDetector::new().detect(&graph);
+
+        let found: Vec<_> = issues
+            .iter()
+            .map(|i| (i.declaration.name.as_str(), i.confidence))
+            .collect();
+        assert_eq!(
+            found,
+            [("LEGACY", Confidence::Medium), ("SLOW", Confidence::Low)]
+        );
+        assert_eq!(
+            issues[0].message,
+            "Enum case 'Status.LEGACY' is never referenced"
+        );
+        assert!(issues[1].message.contains("enumerated"));
+    }
+}
@@ -0,0 +1,205 @@
+//! Unused Extension Detector
+//!
+//! Detects Kotlin extension functions and extension properties with zero
+//! call sites. Extensions collect in `StringExt.kt` / `ViewExtensions.kt`
+//! style utility files, where they outlive the code that needed them.
+//!
+//! Call sites are linked by name, so `text.isValidEmail()`, `view.visible`
+//! and implicit-receiver calls inside `with` / `apply` blocks all count.
+//! Operator extensions are skipped because they are invoked through
+//! operator syntax (`a + b`, `by`, `in`) rather than by name.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! // StringExt.kt
+//! fun String.isValidEmail(): Boolean = ...       // used by the sign-up form
+//! fun String.toTitleCase(): String = ...         // DEAD: never called
+//! val String.wordCount: Int get() = split(" ").size  // DEAD: never read
+//! ```
+
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Graph, Visibility};
+
+/// File name suffixes of extension utility files (`StringExt.kt`, `ViewExtensions.kt`)
+const UTILITY_FILE_SUFFIXES: &[&str] = &["Ext", "Exts", "Extension", "Extensions", "Ktx"];
+
+/// Detector for extension functions and properties that are never used
+pub struct UnusedExtensionDetector;
+
+impl UnusedExtensionDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for UnusedExtensionDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Detector for UnusedExtensionDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedExtension)
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues: Vec<DeadCode> = graph
+            .declarations()
+            .filter(|decl| decl.is_extension())
+            .filter(|decl| !graph.is_referenced(&decl.id))
+            .filter(|decl| {
+                !decl
+                    .modifiers
+                    .iter()
+                    .any(|m| m == "operator" || m == "override")
+            })
+            // Developer explicitly acknowledges the extension is unused
+            .filter(|decl| {
+                !decl
+                    .annotations
+                    .iter()
+                    .any(|a| a.contains("Suppress") && a.contains("unused"))
+            })
+            // Members of an unreferenced class are reported with the class
+            .filter(|decl| {
+                match decl
+                    .parent
+                    .as_ref()
+                    .and_then(|id| graph.get_declaration(id))
+                {
+                    Some(parent) if parent.kind.is_type() => graph.is_referenced(&parent.id),
+                    _ => true,
+                }
+            })
+            .map(|decl| {
+                let confidence = if decl.visibility == Visibility::Private {
+                    Confidence::High
+                } else {
+                    Confidence::Medium
+                };
+                DeadCode::new(decl.clone(), DeadCodeIssue::UnusedExtension)
+                    .with_message(message(decl))
+                    .with_confidence(confidence)
+            })
+            .collect();
+
+        issues.sort_by(|a, b| {
+            a.declaration
+                .location
+                .file
+                .cmp(&b.declaration.location.file)
+                .then(
+                    a.declaration
+                        .location
+                        .line
+                        .cmp(&b.declaration.location.line),
+                )
+        });
+        issues
+    }
+}
+
+fn message(decl: &Declaration) -> String {
+    let mut message = if decl.kind == DeclarationKind::Property {
+        format!("Extension property '{}' is never used", decl.name)
+    } else {
+        format!("Extension function '{}' is never called", decl.name)
+    };
+    if let Some(file) = utility_file_name(decl) {
+        message.push_str(&format!(" (in extension file {})", file));
+    }
+    message
+}
+
+/// The file name when the declaration lives in an extension utility file
+fn utility_file_name(decl: &Declaration) -> Option<&str> {
+    let path = &decl.location.file;
+    let stem = path.file_stem()?.to_str()?;
+    UTILITY_FILE_SUFFIXES
+        .iter()
+        .any(|suffix| stem.ends_with(suffix))
+        .then(|| path.file_name()?.to_str())
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, Language, Location, Reference, ReferenceKind};
+    use std::path::PathBuf;
+
+    fn declaration(file: &str, name: &str, line: usize, kind: DeclarationKind) -> Declaration {
+        let file = PathBuf::from(file);
+        Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            kind,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        )
+    }
+
+    fn extension(file: &str, name: &str, line: usize, kind: DeclarationKind) -> Declaration {
+        let mut decl = declaration(file, name, line, kind);
+        decl.modifiers.push("extension".to_string());
+        decl
+    }
+
+    #[test]
+    fn test_reports_extensions_without_call_sites() {
+        let mut graph = Graph::new();
+        let used = graph.add_declaration(extension(
+            "StringExt.kt",
+            "isValidEmail",
+            1,
+            DeclarationKind::Function,
+        ));
+        graph.add_declaration(extension(
+            "StringExt.kt",
+            "toTitleCase",
+            2,
+            DeclarationKind::Function,
+        ));
+        graph.add_declaration(extension(
+            "StringExt.kt",
+            "wordCount",
+            3,
+            DeclarationKind::Property,
+        ));
+        let mut operator = extension("Money.kt", "plus", 1, DeclarationKind::Function);
+        operator.modifiers.push("operator".to_string());
+        graph.add_declaration(operator);
+        graph.add_declaration(declaration(
+            "Util.kt",
+            "helper",
+            1,
+            DeclarationKind::Function,
+        ));
+
+        let caller = graph.add_declaration(declaration(
+            "SignUp.kt",
+            "submit",
+            5,
+            DeclarationKind::Function,
+        ));
+        let reference = Reference::new(
+            ReferenceKind::Call,
+            Location::new(PathBuf::from("SignUp.kt"), 6, 1, 600, 650),
+            "isValidEmail".to_string(),
+        );
+        graph.add_reference(&caller, &used, reference);
+
+        let issues = UnusedExtensionDetector::new().detect(&graph);
+
+        let names: Vec<_> = issues.iter().map(|i| i.declaration.name.as_str()).collect();
+        assert_eq!(names, ["toTitleCase", "wordCount"]);
+        assert_eq!(
+            issues[0].message,
+            "Extension function 'toTitleCase' is never called (in extension file StringExt.kt)"
+        );
+        assert!(issues[1].message.starts_with("Extension property"));
+    }
+}
@@ -27,6 +27,8 @@ use regex::Regex;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+use crate::discovery::SourceSetKind;
+
 /// Android system extras that are read by external apps (not our code)
 /// These should NOT be flagged as unused
 const SYSTEM_EXTRAS: &[&str] = &[
@@ -100,63 +102,43 @@ impl UnusedIntentExtraDetector {
 
     /// Analyze a directory for unused intent extras
     pub fn analyze(&self, root: &Path) -> IntentExtraAnalysis {
-        use ignore::WalkBuilder;
-
         // Collect all put_extra keys with locations
         let mut put_extras: HashMap<String, Vec<ExtraLocation>> = HashMap::new();
         // Collect all get_extra keys (including hasExtra)
         let mut get_extras: HashSet<String> = HashSet::new();
 
-        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
-
-        for entry in walker.flatten() {
-            let path = entry.path();
-
-            // Only process Kotlin and Java files
-            let ext = path.extension().and_then(|e| e.to_str());
-            if !matches!(ext, Some("kt") | Some("java")) {
-                continue;
-            }
-
-            // Skip test files
-            let path_str = path.to_string_lossy();
-            if path_str.contains("/test/") || path_str.contains("/androidTest/") {
-                continue;
-            }
-
-            if let Ok(content) = std::fs::read_to_string(path) {
-                for (line_num, line) in content.lines().enumerate() {
-                    // Find putExtra calls
-                    for caps in self.put_extra_pattern.captures_iter(line) {
-                        if let Some(key) = caps.get(1) {
-                            let key_str = key.as_str().to_string();
-                            put_extras
-                                .entry(key_str.clone())
-                                .or_default()
-                                .push(ExtraLocation {
-                                    file: path.to_path_buf(),
-                                    line: line_num + 1,
-                                    key: key_str,
-                                });
-                        }
+        walk_sources(root, &["kt", "java"], |path, content| {
+            for (line_num, line) in content.lines().enumerate() {
+                // Find putExtra calls
+                for caps in self.put_extra_pattern.captures_iter(line) {
+                    if let Some(key) = caps.get(1) {
+                        let key_str = key.as_str().to_string();
+                        put_extras
+                            .entry(key_str.clone())
+                            .or_default()
+                            .push(ExtraLocation {
+                                file: path.to_path_buf(),
+                                line: line_num + 1,
+                                key: key_str,
+                            });
                     }
+                }
 
-                    // Find getXxxExtra calls
-                    for caps in self.get_extra_pattern.captures_iter(line) {
-                        if let Some(key) = caps.get(1) {
-                            get_extras.insert(key.as_str().to_string());
-                        }
+                // Find getXxxExtra calls
+                for caps in self.get_extra_pattern.captures_iter(line) {
+                    if let Some(key) = caps.get(1) {
+                        get_extras.insert(key.as_str().to_string());
                     }
+                }
 
-                    // Find hasExtra calls
-                    for caps in self.has_extra_pattern.captures_iter(line) {
-                        if let Some(key) = caps.get(1) {
-                            get_extras.insert(key.as_str().to_string());
-                        }
+                // Find hasExtra calls
+                for caps in self.has_extra_pattern.captures_iter(line) {
+                    if let Some(key) = caps.get(1) {
+                        get_extras.insert(key.as_str().to_string());
                     }
                 }
             }
-        }
+        });
 
         let total_put = put_extras.values().map(|v| v.len()).sum();
         let total_get = get_extras.len();
@@ -200,6 +182,31 @@ impl Default for UnusedIntentExtraDetector {
     }
 }
 
+/// Visit the non-test files under `root` with one of `extensions`
+pub(super) fn walk_sources(root: &Path, extensions: &[&str], mut visit: impl FnMut(&Path, &str)) {
+    use ignore::WalkBuilder;
+
+    let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+
+    for entry in walker.flatten() {
+        let path = entry.path();
+
+        let ext = path.extension().and_then(|e| e.to_str());
+        if !ext.is_some_and(|ext| extensions.contains(&ext)) {
+            continue;
+        }
+
+        // Skip test files
+        if SourceSetKind::from_path(path).is_test() {
+            continue;
+        }
+
+        if let Ok(content) = std::fs::read_to_string(path) {
+            visit(path, &content);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
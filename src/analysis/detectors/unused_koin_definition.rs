@@ -0,0 +1,472 @@
+//! Unused Koin Definition Detector
+//!
+//! Detects Koin definitions (`single`, `factory`, `scoped`, `viewModel`,
+//! `worker` and their `...Of(::Type)` forms) whose provided type is never
+//! requested. A definition is dead when no `get()`, `inject()`, `viewModel()`
+//! or constructor resolution ever asks Koin for it.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. In files that use Koin (`org.koin` imports), find every definition and
+//!    the types it provides: the explicit type argument (`single<Repo>`), the
+//!    `...Of(::Type)` reference or the constructed class, plus `bind`/`binds`
+//! 2. Collect requested types from `get<T>()`, `inject<T>()`,
+//!    `by viewModel<T>()`, `koinViewModel<T>()`, ... and from typed
+//!    declarations (`val repo: Repo by inject()`, `val vm: MainViewModel = koinViewModel()`)
+//! 3. Constructor resolution: every parameter type of a defined class's
+//!    primary constructor is requested, since `Foo(get(), get())` and
+//!    `singleOf(::Foo)` resolve them from Koin
+//! 4. Report definitions none of whose provided types is requested
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! val appModule = module {
+//!     single<UserRepository> { UserRepositoryImpl(get()) }
+//!     single { ApiClient() }                  // used by UserRepositoryImpl
+//!     factory { LegacyMapper() }              // DEAD: never injected
+//!     viewModel { ProfileViewModel(get()) }
+//! }
+//!
+//! class ProfileFragment : Fragment() {
+//!     private val viewModel: ProfileViewModel by viewModel()
+//! }
+//! ```
+
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::discovery::SourceSetKind;
+use crate::graph::{Declaration, DeclarationKind, Language};
+use crate::parser::{line_of, simple_name};
+
+/// Functions that resolve a dependency from Koin
+const RESOLVERS: &str = "get|getOrNull|inject|injectOrNull|viewModel|activityViewModel|\
+sharedViewModel|navGraphViewModel|koinViewModel|koinNavViewModel|getViewModel|koinInject|getAll";
+
+/// A Koin definition inside a `module { }` block
+#[derive(Debug, Clone)]
+pub struct KoinDefinition {
+    /// DSL keyword (`single`, `factory`, `viewModel`, ...)
+    pub kind: String,
+    /// Simple names of the types this definition can be resolved as
+    pub provided: Vec<String>,
+    /// Class the definition constructs, when it can be told
+    pub implementation: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+impl KoinDefinition {
+    /// Human-readable form, e.g. `single<UserRepository>`
+    pub fn display(&self) -> String {
+        format!("{}<{}>", self.kind, self.provided.join(", "))
+    }
+}
+
+/// Result of Koin definition analysis
+#[derive(Debug, Default)]
+pub struct KoinAnalysis {
+    /// Definitions whose provided types are never requested
+    pub unused_definitions: Vec<KoinDefinition>,
+    /// Total definitions found
+    pub total_definitions: usize,
+    /// Distinct types requested from Koin
+    pub total_requested: usize,
+}
+
+impl KoinAnalysis {
+    /// Convert the findings to `DC033` issues
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        self.unused_definitions
+            .iter()
+            .map(|definition| {
+                let decl = Declaration::at_line(
+                    &definition.file,
+                    definition.line,
+                    definition.display(),
+                    DeclarationKind::Function,
+                    Language::Kotlin,
+                );
+                DeadCode::new(decl, DeadCodeIssue::UnusedKoinDefinition)
+                    .with_message(format!(
+                        "Koin definition '{}' is never injected",
+                        definition.display()
+                    ))
+                    .with_confidence(Confidence::Medium)
+            })
+            .collect()
+    }
+}
+
+/// Detector for unused Koin definitions
+pub struct UnusedKoinDefinitionDetector {
+    // single { Foo() }, single<Foo> { ... }, factory(named("x")) { ... }
+    definition_pattern: Regex,
+    // singleOf(::Foo), viewModelOf(::MainViewModel)
+    definition_of_pattern: Regex,
+    // bind<Repo>(), bind Repo::class, binds arrayOf(A::class, B::class)
+    bind_pattern: Regex,
+    class_ref_pattern: Regex,
+    // get<Foo>(), inject<Foo>(), koinViewModel<MainViewModel>()
+    typed_request_pattern: Regex,
+    // val foo: Foo by inject(), val vm: MainViewModel = koinViewModel()
+    declared_request_pattern: Regex,
+    // class Foo(...) / class Foo @Inject constructor(...)
+    class_pattern: Regex,
+}
+
+impl UnusedKoinDefinitionDetector {
+    pub fn new() -> Self {
+        let definition_pattern = Regex::new(
+            r"\b(single|factory|scoped|viewModel|worker)\s*(?:<\s*([A-Z][\w.]*)[^>]*>)?\s*(?:\((?:[^()]|\([^()]*\))*\))?\s*\{\s*(?:\w+\s*->\s*)?([A-Z][\w.]*)?",
+        )
+        .unwrap();
+        let definition_of_pattern =
+            Regex::new(r"\b(single|factory|scoped|viewModel|worker)Of\s*\(\s*::\s*([A-Z][\w.]*)")
+                .unwrap();
+        let bind_pattern = Regex::new(
+            r"\bbind\s*<\s*([A-Z][\w.]*)|\bbind\s*\(?\s*([A-Z][\w.]*)::class|\bbinds\s*\(?\s*(?:arrayOf|listOf)?\s*\(([^)]*)\)",
+        )
+        .unwrap();
+        let class_ref_pattern = Regex::new(r"([A-Z][\w.]*)::class").unwrap();
+        let typed_request_pattern =
+            Regex::new(&format!(r"\b(?:{})\s*<\s*((?:\w+\.)*[A-Z]\w*)", RESOLVERS)).unwrap();
+        let declared_request_pattern = Regex::new(&format!(
+            r":\s*((?:\w+\.)*[A-Z]\w*)(?:<[^=]*>)?\??\s*(?:by|=)\s*(?:{})\s*\(",
+            RESOLVERS
+        ))
+        .unwrap();
+        let class_pattern =
+            Regex::new(r"\bclass\s+([A-Z]\w*)(?:<[^>]*>)?\s*(?:[\w@]+\s+)*?(?:constructor\s*)?\(")
+                .unwrap();
+
+        Self {
+            definition_pattern,
+            definition_of_pattern,
+            bind_pattern,
+            class_ref_pattern,
+            typed_request_pattern,
+            declared_request_pattern,
+            class_pattern,
+        }
+    }
+
+    /// Analyze a directory for unused Koin definitions
+    pub fn analyze(&self, root: &Path) -> KoinAnalysis {
+        use ignore::WalkBuilder;
+
+        let mut definitions = Vec::new();
+        let mut requested = HashSet::new();
+        let mut constructors = HashMap::new();
+
+        let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+
+            // Only process Kotlin and Java files
+            let ext = path.extension().and_then(|e| e.to_str());
+            if !matches!(ext, Some("kt") | Some("java")) {
+                continue;
+            }
+
+            // Skip test files
+            if SourceSetKind::from_path(path).is_test() {
+                continue;
+            }
+
+            if let Ok(content) = std::fs::read_to_string(path) {
+                self.collect_constructors(&content, &mut constructors);
+                if content.contains("org.koin") {
+                    definitions.extend(self.collect_definitions(&content, path));
+                    self.collect_requests(&content, &mut requested);
+                }
+            }
+        }
+
+        // Constructor resolution: dependencies of defined classes are requested
+        for definition in &definitions {
+            let classes = definition.implementation.iter().chain(&definition.provided);
+            for class in classes {
+                if let Some(params) = constructors.get(class) {
+                    requested.extend(params.iter().cloned());
+                }
+            }
+        }
+
+        let total_definitions = definitions.len();
+        let mut unused_definitions: Vec<KoinDefinition> = definitions
+            .into_iter()
+            .filter(|d| !d.provided.iter().any(|t| requested.contains(t)))
+            .collect();
+        unused_definitions.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+
+        KoinAnalysis {
+            unused_definitions,
+            total_definitions,
+            total_requested: requested.len(),
+        }
+    }
+
+    /// Find Koin definitions and the types they provide
+    fn collect_definitions(&self, content: &str, file: &Path) -> Vec<KoinDefinition> {
+        let definition =
+            |start: usize, kind: &str, declared: Option<String>, implementation| KoinDefinition {
+                kind: kind.to_string(),
+                provided: declared.into_iter().collect(),
+                implementation,
+                file: file.to_path_buf(),
+                line: line_of(content, start),
+            };
+
+        // (match start, match end, definition)
+        let mut found = Vec::new();
+        for caps in self.definition_pattern.captures_iter(content) {
+            let whole = caps.get(0).unwrap();
+            let implementation = caps.get(3).map(|m| simple_name(m.as_str()));
+            let declared = caps
+                .get(2)
+                .map(|m| simple_name(m.as_str()))
+                .or(implementation.clone());
+            let def = definition(whole.start(), &caps[1], declared, implementation);
+            found.push((whole.start(), whole.end(), def));
+        }
+        for caps in self.definition_of_pattern.captures_iter(content) {
+            let whole = caps.get(0).unwrap();
+            let implementation = simple_name(&caps[2]);
+            let def = definition(
+                whole.start(),
+                &caps[1],
+                Some(implementation.clone()),
+                Some(implementation),
+            );
+            found.push((whole.start(), whole.end(), def));
+        }
+        found.sort_by_key(|(start, ..)| *start);
+
+        let starts: Vec<usize> = found.iter().map(|(start, ..)| *start).collect();
+        let mut definitions = Vec::new();
+        for (i, (_, end, mut def)) in found.into_iter().enumerate() {
+            // Types bound after the definition, up to the next one
+            let next = starts.get(i + 1).copied().unwrap_or(content.len());
+            for caps in self.bind_pattern.captures_iter(&content[end..next]) {
+                if let Some(ty) = caps.get(1).or(caps.get(2)) {
+                    def.provided.push(simple_name(ty.as_str()));
+                } else if let Some(list) = caps.get(3) {
+                    def.provided.extend(
+                        self.class_ref_pattern
+                            .captures_iter(list.as_str())
+                            .map(|c| simple_name(&c[1])),
+                    );
+                }
+            }
+            def.provided.dedup();
+
+            // Without a type we can't tell whether it is used
+            if !def.provided.is_empty() {
+                definitions.push(def);
+            }
+        }
+
+        definitions
+    }
+
+    /// Collect types requested from Koin
+    fn collect_requests(&self, content: &str, requested: &mut HashSet<String>) {
+        for caps in self.typed_request_pattern.captures_iter(content) {
+            requested.insert(simple_name(&caps[1]));
+        }
+        for caps in self.declared_request_pattern.captures_iter(content) {
+            requested.insert(simple_name(&caps[1]));
+        }
+    }
+
+    /// Record primary constructor parameter types per class
+    fn collect_constructors(&self, content: &str, constructors: &mut HashMap<String, Vec<String>>) {
+        for caps in self.class_pattern.captures_iter(content) {
+            let params_start = caps.get(0).unwrap().end();
+            let Some(params) = balanced_args(&content[params_start..]) else {
+                continue;
+            };
+            let types = split_top_level(params)
+                .filter_map(|param| param.split_once(':'))
+                .map(|(_, ty)| simple_name(ty.split('=').next().unwrap_or(ty).trim()))
+                .filter(|ty| !ty.is_empty())
+                .collect();
+            constructors.insert(caps[1].to_string(), types);
+        }
+    }
+}
+
+impl Default for UnusedKoinDefinitionDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for UnusedKoinDefinitionDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedKoinDefinition)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.root).to_issues()
+    }
+}
+
+/// Text up to the `)` matching an already consumed `(`
+fn balanced_args(text: &str) -> Option<&str> {
+    let mut depth = 1;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[..i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split on commas that aren't nested in brackets
+fn split_top_level(text: &str) -> impl Iterator<Item = &str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '<' | '[' | '{' => depth += 1,
+            ')' | '>' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_collect_definitions() {
+        let detector = UnusedKoinDefinitionDetector::new();
+        let source = r#"
+            val appModule = module {
+                single<UserRepository> { UserRepositoryImpl(get()) }
+                factory(named("legacy")) { LegacyMapper() }
+                single { Analytics(get()) } bind Tracker::class
+                viewModelOf(::ProfileViewModel)
+                singleOf(::Cache) { bind<Store>() }
+            }
+        "#;
+
+        let definitions = detector.collect_definitions(source, Path::new("Module.kt"));
+        let found: Vec<_> = definitions.iter().map(|d| d.display()).collect();
+        assert_eq!(
+            found,
+            [
+                "single<UserRepository>",
+                "factory<LegacyMapper>",
+                "single<Analytics, Tracker>",
+                "viewModel<ProfileViewModel>",
+                "single<Cache, Store>",
+            ]
+        );
+        assert_eq!(definitions[1].line, 4);
+    }
+
+    #[test]
+    fn test_collect_requests() {
+        let detector = UnusedKoinDefinitionDetector::new();
+        let source = r#"
+            private val repo: UserRepository by inject()
+            private val viewModel: ProfileViewModel by viewModel()
+            val settings: com.app.Settings? = getOrNull()
+            val tracker = get<Tracker>()
+            val home = koinViewModel<HomeViewModel>()
+        "#;
+
+        let mut requested = HashSet::new();
+        detector.collect_requests(source, &mut requested);
+        for ty in [
+            "UserRepository",
+            "ProfileViewModel",
+            "Settings",
+            "Tracker",
+            "HomeViewModel",
+        ] {
+            assert!(requested.contains(ty), "{ty} not requested");
+        }
+    }
+
+    #[test]
+    fn test_unused_definitions() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/src/main/java/app/di/AppModule.kt",
+            r#"import org.koin.dsl.module
+
+val appModule = module {
+    single<UserRepository> { UserRepositoryImpl(get()) }
+    single { ApiClient() }
+    factory { LegacyMapper() }
+    viewModel { ProfileViewModel(get()) }
+}
+"#,
+        );
+        write(
+            root,
+            "app/src/main/java/app/data/UserRepositoryImpl.kt",
+            "class UserRepositoryImpl(private val api: ApiClient) : UserRepository",
+        );
+        write(
+            root,
+            "app/src/main/java/app/ui/ProfileViewModel.kt",
+            "class ProfileViewModel(private val repo: UserRepository) : ViewModel()",
+        );
+        write(
+            root,
+            "app/src/main/java/app/ui/ProfileFragment.kt",
+            r#"import org.koin.androidx.viewmodel.ext.android.viewModel
+
+class ProfileFragment : Fragment() {
+    private val viewModel: ProfileViewModel by viewModel()
+}
+"#,
+        );
+
+        let analysis = UnusedKoinDefinitionDetector::new().analyze(root);
+
+        assert_eq!(analysis.total_definitions, 4);
+        assert_eq!(analysis.unused_definitions.len(), 1);
+        let unused = &analysis.unused_definitions[0];
+        assert_eq!(unused.display(), "factory<LegacyMapper>");
+        assert_eq!(unused.line, 6);
+
+        let issues = analysis.to_issues();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue, DeadCodeIssue::UnusedKoinDefinition);
+        assert_eq!(issues[0].declaration.location.line, 6);
+        assert_eq!(
+            issues[0].message,
+            "Koin definition 'factory<LegacyMapper>' is never injected"
+        );
+    }
+}
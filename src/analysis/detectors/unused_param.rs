@@ -3,7 +3,7 @@
 //! This detector finds function/method parameters that are declared but never
 //! used within the function body.
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -62,10 +62,15 @@ impl UnusedParamDetector {
                 }
             }
 
-            // Skip interface methods
+            // Skip expect/actual functions (every platform shares the signature)
+            if parent.is_expect() || parent.is_actual() {
+                return true;
+            }
+
+            // Skip interface methods and members of expect classes
             if let Some(grandparent_id) = &parent.parent {
                 if let Some(grandparent) = graph.get_declaration(grandparent_id) {
-                    if grandparent.kind == DeclarationKind::Interface {
+                    if grandparent.kind == DeclarationKind::Interface || grandparent.is_expect() {
                         return true;
                     }
                 }
@@ -94,6 +99,10 @@ impl UnusedParamDetector {
 }
 
 impl Detector for UnusedParamDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedParameter)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut dead_code = Vec::new();
 
@@ -1,5 +1,5 @@
-use super::Detector;
-use crate::analysis::DeadCode;
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{DeadCode, DeadCodeIssue};
 use crate::graph::Graph;
 
 pub struct UnusedPropertyDetector;
@@ -9,6 +9,10 @@ impl UnusedPropertyDetector {
     }
 }
 impl Detector for UnusedPropertyDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::Unreferenced)
+    }
+
     fn detect(&self, _graph: &Graph) -> Vec<DeadCode> {
         Vec::new()
     }
@@ -0,0 +1,265 @@
+//! Unused Proto Message Detector
+//!
+//! Detects protobuf messages that no Kotlin or Java code ever constructs or
+//! parses. Generated message classes are retained as entry points, since
+//! they're used through builders and reflection, so a message nothing
+//! creates any more still looks used.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Parse `.proto` files for messages, their field types and services
+//! 2. A message is constructed when a source creates or parses it
+//!    (`Foo.newBuilder()`, `Foo.parseFrom(...)`, `Foo.getDefaultInstance()`,
+//!    `Foo.parser()`, the Kotlin DSL `foo { ... }`)
+//! 3. Messages used as rpc requests or responses are constructed by the
+//!    gRPC runtime, and fields of a constructed message are constructed
+//!    along with it
+//! 4. Report every other message
+//!
+//! ## Examples Detected
+//!
+//! ```proto
+//! message FeedItem { string id = 1; }
+//! message LegacyFeedItem { string id = 1; }   // DEAD: never built or parsed
+//! ```
+
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::unused_intent_extra::walk_sources;
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationKind, Language};
+use crate::parser::proto::{ProtoMessage, ProtoParser};
+
+/// A proto message nothing constructs
+#[derive(Debug, Clone)]
+pub struct UnusedProtoMessage {
+    pub file: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+/// Result of proto message analysis
+#[derive(Debug, Default)]
+pub struct ProtoAnalysis {
+    /// Messages never constructed or parsed
+    pub unused_messages: Vec<UnusedProtoMessage>,
+    /// Total messages declared in `.proto` files
+    pub total_messages: usize,
+}
+
+impl ProtoAnalysis {
+    /// Convert the findings to `DC038` issues
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        self.unused_messages
+            .iter()
+            .map(|message| {
+                let decl = Declaration::at_line(
+                    &message.file,
+                    message.line,
+                    message.message.as_str(),
+                    DeclarationKind::Class,
+                    Language::Proto,
+                );
+                DeadCode::new(decl, DeadCodeIssue::UnusedProtoMessage)
+                    .with_message(format!(
+                        "Proto message '{}' is never built or parsed",
+                        message.message
+                    ))
+                    .with_confidence(Confidence::Medium)
+            })
+            .collect()
+    }
+}
+
+/// Detector for proto messages nothing constructs
+pub struct UnusedProtoMessageDetector {
+    parser: ProtoParser,
+    // Foo.newBuilder( / Foo.parseFrom( / Foo.getDefaultInstance( / Foo.parser(
+    construction_pattern: Regex,
+    // foo { (Kotlin DSL builder)
+    dsl_pattern: Regex,
+}
+
+impl UnusedProtoMessageDetector {
+    pub fn new() -> Self {
+        Self {
+            parser: ProtoParser::new(),
+            construction_pattern: Regex::new(
+                r"\b([A-Z]\w*)\s*\.\s*(?:newBuilder|parseFrom|parseDelimitedFrom|getDefaultInstance|parser)\s*\(",
+            )
+            .unwrap(),
+            dsl_pattern: Regex::new(r"\b([a-z]\w*)\s*\{").unwrap(),
+        }
+    }
+
+    /// Analyze a directory for unused proto messages
+    pub fn analyze(&self, root: &Path) -> ProtoAnalysis {
+        let mut messages: Vec<(PathBuf, ProtoMessage)> = Vec::new();
+        let mut rpc_types = HashSet::new();
+        walk_sources(root, &["proto"], |path, content| {
+            if let Ok(file) = self.parser.parse(path, content) {
+                for service in &file.services {
+                    for rpc in &service.rpcs {
+                        rpc_types.insert(rpc.request.clone());
+                        rpc_types.insert(rpc.response.clone());
+                    }
+                }
+                for message in file.messages {
+                    messages.push((path.to_path_buf(), message));
+                }
+            }
+        });
+
+        let mut analysis = ProtoAnalysis {
+            total_messages: messages.len(),
+            ..Default::default()
+        };
+        if messages.is_empty() {
+            return analysis;
+        }
+
+        let mut constructed = rpc_types;
+        let mut dsl_calls = HashSet::new();
+        walk_sources(root, &["kt", "java"], |path, content| {
+            for cap in self.construction_pattern.captures_iter(content) {
+                constructed.insert(cap[1].to_string());
+            }
+            if path.extension().is_some_and(|e| e == "kt") {
+                for cap in self.dsl_pattern.captures_iter(content) {
+                    dsl_calls.insert(cap[1].to_string());
+                }
+            }
+        });
+        for (_, message) in &messages {
+            if dsl_calls.contains(&dsl_name(&message.name)) {
+                constructed.insert(message.name.clone());
+            }
+        }
+
+        // Fields of constructed messages are constructed with them
+        let fields: HashMap<&str, &[String]> = messages
+            .iter()
+            .map(|(_, m)| (m.name.as_str(), m.field_types.as_slice()))
+            .collect();
+        let mut pending: Vec<String> = constructed.iter().cloned().collect();
+        while let Some(name) = pending.pop() {
+            for field in fields.get(name.as_str()).copied().unwrap_or_default() {
+                if constructed.insert(field.clone()) {
+                    pending.push(field.clone());
+                }
+            }
+        }
+
+        for (file, message) in &messages {
+            if !constructed.contains(&message.name) {
+                analysis.unused_messages.push(UnusedProtoMessage {
+                    file: file.clone(),
+                    line: message.line,
+                    message: message.name.clone(),
+                });
+            }
+        }
+        analysis
+    }
+}
+
+impl Default for UnusedProtoMessageDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for UnusedProtoMessageDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedProtoMessage)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        self.analyze(project.root).to_issues()
+    }
+}
+
+/// Name of the Kotlin DSL builder function (`FeedItem` -> `feedItem`)
+fn dsl_name(message: &str) -> String {
+    let mut chars = message.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unused_proto_messages() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/src/main/proto/feed.proto",
+            r#"syntax = "proto3";
+package app.feed;
+
+message FeedItem {
+    string id = 1;
+    Author author = 2;
+}
+
+message Author {
+    string name = 1;
+}
+
+message Settings {
+    bool compact = 1;
+}
+
+message FeedRequest {
+    int32 page = 1;
+}
+
+message LegacyFeedItem {
+    string id = 1;
+}
+
+service FeedService {
+    rpc GetFeed (FeedRequest) returns (stream FeedItem);
+}
+"#,
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/SettingsStore.kt",
+            r#"class SettingsStore {
+    fun defaults() = settings {
+        compact = false
+    }
+}
+"#,
+        );
+
+        let analysis = UnusedProtoMessageDetector::new().analyze(root);
+
+        let unused: Vec<_> = analysis
+            .unused_messages
+            .iter()
+            .map(|m| (m.message.as_str(), m.line))
+            .collect();
+        assert_eq!(unused, [("LegacyFeedItem", 21)]);
+        assert_eq!(analysis.total_messages, 5);
+
+        let issues = analysis.to_issues();
+        assert_eq!(issues[0].issue, DeadCodeIssue::UnusedProtoMessage);
+        assert_eq!(issues[0].declaration.language, Language::Proto);
+        assert_eq!(
+            issues[0].message,
+            "Proto message 'LegacyFeedItem' is never built or parsed"
+        );
+    }
+}
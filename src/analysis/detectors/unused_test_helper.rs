@@ -0,0 +1,304 @@
+//! Unused Test Helper Detector
+//!
+//! Runs reachability inside test source sets (`src/test`, `src/androidTest`,
+//! `src/testFixtures`, ...) with test methods as roots, and reports test
+//! utilities, fixtures, fake builders and base test classes that no test
+//! reaches.
+//!
+//! Findings use their own rule and the "Test Code" category, and the
+//! production reachability pass skips test sources, so test cleanup never
+//! shows up in (or churns) a production baseline.
+//!
+//! ## Roots
+//!
+//! - JUnit 4/5 lifecycle and test methods (`@Test`, `@Before`, `@ParameterizedTest`, ...)
+//! - `@Rule` / `@ClassRule` properties
+//! - Classes annotated `@RunWith`, `@ExtendWith` or `@HiltAndroidTest`
+//! - Kotest / Spek specs and instrumentation runners (by supertype)
+//! - Test declarations referenced from production code (e.g. `testFixtures`)
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! // src/test/java/com/app/Fixtures.kt
+//! fun aUser(name: String = "Ada") = User(name)    // used by UserRepositoryTest
+//! fun aLegacyAccount() = Account(legacy = true)   // DEAD: no test calls it
+//!
+//! abstract class BaseViewModelTest                // DEAD: no test extends it
+//! ```
+
+use std::collections::HashSet;
+
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
+
+/// Annotations that make the test runner call a declaration
+const TEST_ROOT_ANNOTATIONS: &[&str] = &[
+    "Test",
+    "Before",
+    "After",
+    "BeforeClass",
+    "AfterClass",
+    "BeforeEach",
+    "AfterEach",
+    "BeforeAll",
+    "AfterAll",
+    "ParameterizedTest",
+    "RepeatedTest",
+    "TestFactory",
+    "TestTemplate",
+    "Rule",
+    "ClassRule",
+    "RunWith",
+    "ExtendWith",
+    "HiltAndroidTest",
+    "Parameters",
+];
+
+/// Supertype suffixes of classes the test framework instantiates itself
+const TEST_ROOT_SUPERTYPES: &[&str] = &["Spec", "Runner", "TestCase"];
+
+/// Detector for test helpers no test reaches
+pub struct UnusedTestHelperDetector;
+
+impl UnusedTestHelperDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn is_root(graph: &Graph, decl: &Declaration) -> bool {
+        decl.annotation_names()
+            .any(|name| TEST_ROOT_ANNOTATIONS.contains(&name))
+            || decl.super_types.iter().any(|s| {
+                let name = s.trim_start_matches("extends ");
+                let name = name[..name.find(['(', '<']).unwrap_or(name.len())].trim();
+                TEST_ROOT_SUPERTYPES
+                    .iter()
+                    .any(|suffix| name.ends_with(suffix))
+            })
+            || graph
+                .get_references_to(&decl.id)
+                .iter()
+                .any(|(from, _)| !from.is_test_source() && from.kind != DeclarationKind::File)
+    }
+
+    /// Declarations reachable from the test roots
+    fn reachable(graph: &Graph, tests: &[&Declaration]) -> HashSet<DeclarationId> {
+        let mut reachable = HashSet::new();
+        let mut worklist: Vec<DeclarationId> = tests
+            .iter()
+            .filter(|d| Self::is_root(graph, d))
+            .map(|d| d.id.clone())
+            .collect();
+
+        while let Some(id) = worklist.pop() {
+            if !reachable.insert(id.clone()) {
+                continue;
+            }
+            // The enclosing class of a reached member is used too
+            if let Some(parent) = graph.get_declaration(&id).and_then(|d| d.parent.clone()) {
+                worklist.push(parent);
+            }
+            for (target, _) in graph.get_references_from(&id) {
+                worklist.push(target.id.clone());
+            }
+        }
+        reachable
+    }
+
+    /// Whether an unreached declaration is worth reporting on its own
+    fn is_reportable(
+        graph: &Graph,
+        decl: &Declaration,
+        reachable: &HashSet<DeclarationId>,
+    ) -> bool {
+        if decl.modifiers.iter().any(|m| m == "override")
+            || decl.annotations.iter().any(|a| a.contains("Override"))
+        {
+            return false;
+        }
+        let parent = decl
+            .parent
+            .as_ref()
+            .and_then(|id| graph.get_declaration(id));
+        match decl.kind {
+            DeclarationKind::Class
+            | DeclarationKind::Interface
+            | DeclarationKind::Object
+            | DeclarationKind::Enum => {
+                // Companion objects live and die with their class
+                !decl.modifiers.iter().any(|m| m == "companion")
+                    && parent.map_or(true, |p| reachable.contains(&p.id))
+            }
+            DeclarationKind::Function => true,
+            // Members are reported when their class is used; otherwise the class is
+            DeclarationKind::Method => parent.is_some_and(|p| reachable.contains(&p.id)),
+            // Top-level test data (`val TEST_USER = ...`)
+            DeclarationKind::Property => parent.is_none(),
+            _ => false,
+        }
+    }
+}
+
+impl Default for UnusedTestHelperDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Detector for UnusedTestHelperDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedTestHelper)
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let tests: Vec<&Declaration> = graph
+            .declarations()
+            .filter(|d| d.is_test_source())
+            .collect();
+        if tests.is_empty() {
+            return Vec::new();
+        }
+
+        let reachable = Self::reachable(graph, &tests);
+
+        let mut issues: Vec<DeadCode> = tests
+            .into_iter()
+            .filter(|d| !reachable.contains(&d.id))
+            .filter(|d| Self::is_reportable(graph, d, &reachable))
+            .filter(|d| {
+                !d.annotations
+                    .iter()
+                    .any(|a| a.contains("Suppress") && a.contains("unused"))
+            })
+            .map(|d| {
+                // Anything that nothing at all references is certainly unused
+                let confidence = if graph.is_referenced(&d.id) {
+                    Confidence::Medium
+                } else {
+                    Confidence::High
+                };
+                DeadCode::new(d.clone(), DeadCodeIssue::UnusedTestHelper)
+                    .with_message(format!(
+                        "Test {} '{}' is not used by any test",
+                        d.kind.display_name(),
+                        d.name
+                    ))
+                    .with_confidence(confidence)
+            })
+            .collect();
+
+        issues.sort_by(|a, b| {
+            let (a, b) = (&a.declaration.location, &b.declaration.location);
+            a.file.cmp(&b.file).then(a.start_byte.cmp(&b.start_byte))
+        });
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Language, Location, Reference, ReferenceKind};
+    use std::path::PathBuf;
+
+    fn declaration(file: &str, name: &str, line: usize, kind: DeclarationKind) -> Declaration {
+        let file = PathBuf::from(file);
+        Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            kind,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        )
+    }
+
+    fn call(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId) {
+        let location = Location::new(from.file.clone(), 1, 1, from.start, from.end);
+        graph.add_reference(
+            from,
+            to,
+            Reference::new(ReferenceKind::Call, location, String::new()),
+        );
+    }
+
+    #[test]
+    fn test_is_test_source() {
+        let decl = |file| declaration(file, "x", 1, DeclarationKind::Function);
+        assert!(decl("app/src/test/java/A.kt").is_test_source());
+        assert!(decl("app/src/androidTest/java/A.kt").is_test_source());
+        assert!(decl("app/src/testFixtures/kotlin/A.kt").is_test_source());
+        assert!(!decl("app/src/main/java/com/app/test/A.kt").is_test_source());
+        assert!(!decl("app/src/testing/A.kt").is_test_source());
+    }
+
+    #[test]
+    fn test_unused_test_helpers() {
+        const TEST: &str = "app/src/test/java/com/app/UserTest.kt";
+        const FIXTURES: &str = "app/src/test/java/com/app/Fixtures.kt";
+
+        let mut graph = Graph::new();
+        let test_class =
+            graph.add_declaration(declaration(TEST, "UserTest", 1, DeclarationKind::Class));
+        let mut test_method = declaration(TEST, "loadsUser", 3, DeclarationKind::Method);
+        test_method.annotations = vec!["@Test".to_string()];
+        test_method.parent = Some(test_class.clone());
+        let test_method = graph.add_declaration(test_method);
+        let mut unused_method = declaration(TEST, "oldHelper", 8, DeclarationKind::Method);
+        unused_method.parent = Some(test_class.clone());
+        graph.add_declaration(unused_method);
+
+        let used =
+            graph.add_declaration(declaration(FIXTURES, "aUser", 1, DeclarationKind::Function));
+        let legacy = graph.add_declaration(declaration(
+            FIXTURES,
+            "aLegacyAccount",
+            2,
+            DeclarationKind::Function,
+        ));
+        let chained = graph.add_declaration(declaration(
+            FIXTURES,
+            "legacyAddress",
+            3,
+            DeclarationKind::Function,
+        ));
+        graph.add_declaration(declaration(
+            FIXTURES,
+            "BaseViewModelTest",
+            5,
+            DeclarationKind::Class,
+        ));
+        call(&mut graph, &test_method, &used);
+        call(&mut graph, &legacy, &chained);
+
+        // Production code is reported by reachability, not here
+        graph.add_declaration(declaration(
+            "app/src/main/java/com/app/User.kt",
+            "User",
+            1,
+            DeclarationKind::Class,
+        ));
+
+        let issues = UnusedTestHelperDetector::new().detect(&graph);
+
+        let found: Vec<_> = issues
+            .iter()
+            .map(|i| (i.declaration.name.as_str(), i.confidence))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("aLegacyAccount", Confidence::High),
+                ("legacyAddress", Confidence::Medium),
+                ("BaseViewModelTest", Confidence::High),
+                ("oldHelper", Confidence::High),
+            ]
+        );
+        assert_eq!(issues[0].issue.category(), "Test Code");
+        assert_eq!(
+            issues[0].message,
+            "Test function 'aLegacyAccount' is not used by any test"
+        );
+    }
+}
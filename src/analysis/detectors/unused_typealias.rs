@@ -0,0 +1,158 @@
+//! Unused Type Alias Detector
+//!
+//! Detects Kotlin `typealias` declarations that nothing refers to. Aliases
+//! are often introduced for a single callback or a long generic type and
+//! left behind once the code using them is rewritten.
+//!
+//! References through an alias are linked to the aliased declaration as
+//! well, so an alias keeps its target alive but is reported on its own
+//! once no code names it. Import statements alone do not count as a use.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! typealias Repo = UserRepository               // used: `Repo()`
+//! typealias Callback = (Result) -> Unit         // DEAD: never named
+//! typealias UserMap = Map<String, User>         // DEAD: never named
+//! ```
+
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::graph::{DeclarationKind, Graph, ReferenceKind, Visibility};
+
+/// Detector for type aliases that are never used
+pub struct UnusedTypeAliasDetector;
+
+impl UnusedTypeAliasDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for UnusedTypeAliasDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Detector for UnusedTypeAliasDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::UnusedTypeAlias)
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        let mut issues: Vec<DeadCode> = graph
+            .declarations()
+            .filter(|decl| decl.kind == DeclarationKind::TypeAlias)
+            .filter(|decl| !decl.is_test_source())
+            .filter(|decl| {
+                graph
+                    .get_references_to(&decl.id)
+                    .iter()
+                    .all(|(_, r)| r.kind == ReferenceKind::Import)
+            })
+            // Developer explicitly acknowledges the alias is unused
+            .filter(|decl| {
+                !decl
+                    .annotations
+                    .iter()
+                    .any(|a| a.contains("Suppress") && a.contains("unused"))
+            })
+            .map(|decl| {
+                let confidence = if decl.visibility == Visibility::Private {
+                    Confidence::High
+                } else {
+                    Confidence::Medium
+                };
+                DeadCode::new(decl.clone(), DeadCodeIssue::UnusedTypeAlias)
+                    .with_confidence(confidence)
+            })
+            .collect();
+
+        issues.sort_by(|a, b| {
+            a.declaration
+                .location
+                .file
+                .cmp(&b.declaration.location.file)
+                .then(
+                    a.declaration
+                        .location
+                        .line
+                        .cmp(&b.declaration.location.line),
+                )
+        });
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Declaration, DeclarationId, Language, Location, Reference};
+    use std::path::PathBuf;
+
+    fn declaration(file: &str, name: &str, line: usize, kind: DeclarationKind) -> Declaration {
+        let file = PathBuf::from(file);
+        Declaration::new(
+            DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+            name.to_string(),
+            kind,
+            Location::new(file, line, 1, line * 100, line * 100 + 50),
+            Language::Kotlin,
+        )
+    }
+
+    fn reference(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId, kind: ReferenceKind) {
+        let location = Location::new(from.file.clone(), 1, 1, from.start, from.end);
+        graph.add_reference(from, to, Reference::new(kind, location, String::new()));
+    }
+
+    #[test]
+    fn test_reports_unreferenced_type_aliases() {
+        let mut graph = Graph::new();
+        let repo = graph.add_declaration(declaration(
+            "Aliases.kt",
+            "Repo",
+            1,
+            DeclarationKind::TypeAlias,
+        ));
+        let callback = graph.add_declaration(declaration(
+            "Aliases.kt",
+            "Callback",
+            2,
+            DeclarationKind::TypeAlias,
+        ));
+        let mut suppressed = declaration("Aliases.kt", "Legacy", 3, DeclarationKind::TypeAlias);
+        suppressed
+            .annotations
+            .push("Suppress(\"unused\")".to_string());
+        graph.add_declaration(suppressed);
+        graph.add_declaration(declaration(
+            "src/test/kotlin/Fixtures.kt",
+            "TestRepo",
+            1,
+            DeclarationKind::TypeAlias,
+        ));
+
+        let screen = graph.add_declaration(declaration(
+            "Screen.kt",
+            "Screen",
+            1,
+            DeclarationKind::Class,
+        ));
+        let file = graph.add_declaration(declaration(
+            "Screen.kt",
+            "Screen.kt",
+            0,
+            DeclarationKind::File,
+        ));
+        reference(&mut graph, &screen, &repo, ReferenceKind::Call);
+        reference(&mut graph, &file, &callback, ReferenceKind::Import);
+
+        let issues = UnusedTypeAliasDetector::new().detect(&graph);
+
+        let names: Vec<_> = issues.iter().map(|i| i.declaration.name.as_str()).collect();
+        assert_eq!(names, ["Callback"]);
+        assert_eq!(issues[0].message, "Type alias 'Callback' is never used");
+    }
+}
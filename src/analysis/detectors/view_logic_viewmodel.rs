@@ -24,7 +24,7 @@
 //! - Pass data, not Views
 //! - Use LiveData/StateFlow to communicate with UI
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph};
 
@@ -205,6 +205,10 @@ impl Default for ViewLogicInViewModelDetector {
 }
 
 impl Detector for ViewLogicInViewModelDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::ViewLogicInViewModel)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
@@ -24,7 +24,7 @@
 //! - Always release in finally block
 //! - Consider using WorkManager instead
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Language};
 
@@ -71,6 +71,10 @@ impl Default for WakeLockAbuseDetector {
 }
 
 impl Detector for WakeLockAbuseDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::WakeLockAbuse)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues: Vec<DeadCode> = Vec::new();
 
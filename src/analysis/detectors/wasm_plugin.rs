@@ -0,0 +1,506 @@
+//! WASM Detector Plugins
+//!
+//! Loads third-party detectors compiled to WebAssembly so teams can ship
+//! proprietary rules without forking the crate. Plugins run in the `wasmi`
+//! interpreter with fuel metering, so a misbehaving plugin can't hang or
+//! crash an analysis run.
+//!
+//! ## Host API (ABI version 1)
+//!
+//! Data crosses the boundary as UTF-8 JSON in the plugin's linear memory.
+//! Functions returning a buffer pack it into an `i64` as `(ptr << 32) | len`.
+//!
+//! Required exports:
+//! - `memory` - the plugin's linear memory
+//! - `sdc_abi_version() -> i32` - must return `1`
+//! - `sdc_alloc(len: i32) -> i32` - reserve `len` bytes for the host to write into
+//! - `sdc_metadata() -> i64` - rule metadata:
+//!   `{"code", "name", "category", "severity"?, "enabled_by_default"?}`
+//! - `sdc_detect(ptr: i32, len: i32) -> i64` - receives the graph snapshot
+//!   and returns findings: `[{"declaration", "message"?, "severity"?, "confidence"?}]`
+//!
+//! Optional import:
+//! - `sdc.log(ptr: i32, len: i32)` - write a debug message to the host log
+//!
+//! The graph snapshot is `{"abi_version", "declarations", "references"}`;
+//! findings point at declarations by their index in `declarations`, and
+//! references use the same indices for `from`/`to`. See `docs/plugins.md`.
+
+use super::{Detector, DetectorMetadata};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue, Severity};
+use crate::graph::{DeclarationKind, Graph, Language, ReferenceKind, Symbol, Visibility};
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use petgraph::visit::EdgeRef;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+use wasmi::{Caller, Config, Engine, Extern, Instance, Linker, Memory, Module, Store};
+
+/// Version of the host API implemented by this build
+pub const PLUGIN_ABI_VERSION: i32 = 1;
+
+/// Default instruction budget for a single plugin call
+const DEFAULT_FUEL: u64 = 5_000_000_000;
+
+/// A detector implemented by a WASM module
+pub struct WasmDetector {
+    source: PathBuf,
+    engine: Engine,
+    module: Module,
+    metadata: DetectorMetadata,
+    fuel: u64,
+}
+
+impl WasmDetector {
+    /// Load a plugin from a `.wasm` file
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read plugin {}", path.display()))?;
+        Self::from_bytes(path, &bytes)
+    }
+
+    /// Load a plugin from an in-memory module; `source` is only used in messages
+    pub fn from_bytes(source: &Path, wasm: &[u8]) -> Result<Self> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm)
+            .map_err(|e| miette!("Invalid WASM plugin {}: {}", source.display(), e))?;
+
+        let mut detector = Self {
+            source: source.to_path_buf(),
+            engine,
+            module,
+            fuel: DEFAULT_FUEL,
+            // Replaced below once the plugin has described itself
+            metadata: DetectorMetadata::new("", "", "", Severity::Warning),
+        };
+        detector.metadata = detector.read_metadata()?;
+        debug!(
+            "Loaded WASM plugin {} ({})",
+            detector.metadata.code,
+            source.display()
+        );
+        Ok(detector)
+    }
+
+    /// Path the plugin was loaded from
+    pub fn source(&self) -> &Path {
+        &self.source
+    }
+
+    /// Limit the instructions a single `sdc_detect` call may execute
+    pub fn with_fuel(mut self, fuel: u64) -> Self {
+        self.fuel = fuel;
+        self
+    }
+
+    fn read_metadata(&self) -> Result<DetectorMetadata> {
+        let mut session = self.instantiate()?;
+        let packed = session.call_packed("sdc_metadata", ())?;
+        let json = session.read_packed(packed)?;
+        let metadata: PluginMetadata = serde_json::from_slice(&json)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Invalid metadata from {}", self.source.display()))?;
+
+        if metadata.code.is_empty() {
+            return Err(miette!(
+                "Plugin {} returned an empty rule code",
+                self.source.display()
+            ));
+        }
+
+        Ok(DetectorMetadata::new(
+            Symbol::new(&metadata.code).as_str(),
+            Symbol::new(&metadata.name).as_str(),
+            Symbol::new(metadata.category.as_deref().unwrap_or("Plugins")).as_str(),
+            metadata
+                .severity
+                .as_deref()
+                .and_then(|severity| severity.parse().ok())
+                .unwrap_or(Severity::Warning),
+        )
+        .with_enabled_by_default(metadata.enabled_by_default.unwrap_or(true)))
+    }
+
+    fn instantiate(&self) -> Result<PluginSession<'_>> {
+        let mut store = Store::new(&self.engine, ());
+        store.set_fuel(self.fuel).map_err(wasm_error)?;
+
+        let mut linker = Linker::<()>::new(&self.engine);
+        linker
+            .func_wrap(
+                "sdc",
+                "log",
+                |caller: Caller<'_, ()>, ptr: i32, len: i32| {
+                    let memory = caller.get_export("memory").and_then(Extern::into_memory);
+                    if let Some(bytes) = memory.and_then(|m| read_memory(m.data(&caller), ptr, len))
+                    {
+                        debug!("[plugin] {}", String::from_utf8_lossy(bytes));
+                    }
+                },
+            )
+            .map_err(wasm_error)?;
+
+        let instance = linker
+            .instantiate(&mut store, &self.module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| miette!("Failed to instantiate {}: {}", self.source.display(), e))?;
+
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| miette!("Plugin {} does not export memory", self.source.display()))?;
+
+        let mut session = PluginSession {
+            store,
+            instance,
+            memory,
+            source: &self.source,
+        };
+
+        let version: i32 = session.call("sdc_abi_version", ())?;
+        if version != PLUGIN_ABI_VERSION {
+            return Err(miette!(
+                "Plugin {} targets host API v{}, but this build provides v{}",
+                self.source.display(),
+                version,
+                PLUGIN_ABI_VERSION
+            ));
+        }
+
+        Ok(session)
+    }
+
+    fn run(&self, graph: &Graph) -> Result<Vec<DeadCode>> {
+        let snapshot = GraphSnapshot::from_graph(graph);
+        let input = serde_json::to_vec(&snapshot).into_diagnostic()?;
+
+        let mut session = self.instantiate()?;
+        let len = i32::try_from(input.len())
+            .map_err(|_| miette!("Graph snapshot is too large for a WASM plugin"))?;
+        let ptr: i32 = session.call("sdc_alloc", len)?;
+        session
+            .memory
+            .write(&mut session.store, ptr as u32 as usize, &input)
+            .map_err(wasm_error)?;
+
+        let packed = session.call_packed("sdc_detect", (ptr, len))?;
+        let output = session.read_packed(packed)?;
+        let findings: Vec<PluginFinding> = serde_json::from_slice(&output)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Invalid findings from {}", self.source.display()))?;
+
+        let issue = DeadCodeIssue::Custom(self.metadata);
+        let mut dead_code = Vec::with_capacity(findings.len());
+        for finding in findings {
+            let Some(decl) = snapshot
+                .ids
+                .get(finding.declaration)
+                .and_then(|id| graph.get_declaration(id))
+            else {
+                warn!(
+                    "Plugin {} reported unknown declaration index {}",
+                    self.metadata.code, finding.declaration
+                );
+                continue;
+            };
+
+            let mut dc = DeadCode::new(decl.clone(), issue);
+            if let Some(message) = finding.message {
+                dc = dc.with_message(message);
+            }
+            if let Some(severity) = finding.severity.as_deref() {
+                dc = dc.with_severity(severity.parse().unwrap_or(Severity::Warning));
+            }
+            if let Some(confidence) = finding.confidence.as_deref() {
+                dc = dc.with_confidence(confidence.parse().unwrap_or(Confidence::Medium));
+            }
+            dead_code.push(dc);
+        }
+
+        Ok(dead_code)
+    }
+}
+
+impl Detector for WasmDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        self.metadata
+    }
+
+    fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
+        match self.run(graph) {
+            Ok(dead_code) => dead_code,
+            Err(e) => {
+                warn!("Plugin {} failed: {}", self.metadata.code, e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// A live instance of a plugin, used for a single call sequence
+struct PluginSession<'a> {
+    store: Store<()>,
+    instance: Instance,
+    memory: Memory,
+    source: &'a Path,
+}
+
+impl PluginSession<'_> {
+    fn call<Params, Results>(&mut self, name: &str, params: Params) -> Result<Results>
+    where
+        Params: wasmi::WasmParams,
+        Results: wasmi::WasmResults,
+    {
+        let func = self
+            .instance
+            .get_typed_func::<Params, Results>(&self.store, name)
+            .map_err(|e| miette!("Plugin {} export `{}`: {}", self.source.display(), name, e))?;
+        func.call(&mut self.store, params).map_err(|e| {
+            miette!(
+                "Plugin {} trapped in `{}`: {}",
+                self.source.display(),
+                name,
+                e
+            )
+        })
+    }
+
+    fn call_packed<Params: wasmi::WasmParams>(
+        &mut self,
+        name: &str,
+        params: Params,
+    ) -> Result<i64> {
+        self.call::<Params, i64>(name, params)
+    }
+
+    /// Copy out a `(ptr << 32) | len` buffer
+    fn read_packed(&self, packed: i64) -> Result<Vec<u8>> {
+        let ptr = (packed as u64 >> 32) as u32 as i32;
+        let len = packed as u32 as i32;
+        read_memory(self.memory.data(&self.store), ptr, len)
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| {
+                miette!(
+                    "Plugin {} returned a buffer outside its memory",
+                    self.source.display()
+                )
+            })
+    }
+}
+
+fn read_memory(data: &[u8], ptr: i32, len: i32) -> Option<&[u8]> {
+    let start = ptr as u32 as usize;
+    let end = start.checked_add(len as u32 as usize)?;
+    data.get(start..end)
+}
+
+fn wasm_error(e: impl std::fmt::Display) -> miette::Report {
+    miette!("WASM runtime error: {}", e)
+}
+
+#[derive(Deserialize)]
+struct PluginMetadata {
+    code: String,
+    name: String,
+    category: Option<String>,
+    severity: Option<String>,
+    enabled_by_default: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct PluginFinding {
+    declaration: usize,
+    message: Option<String>,
+    severity: Option<String>,
+    confidence: Option<String>,
+}
+
+/// Read-only view of the graph handed to plugins
+#[derive(Serialize)]
+struct GraphSnapshot<'a> {
+    abi_version: i32,
+    declarations: Vec<SnapshotDeclaration<'a>>,
+    references: Vec<SnapshotReference>,
+    #[serde(skip)]
+    ids: Vec<crate::graph::DeclarationId>,
+}
+
+#[derive(Serialize)]
+struct SnapshotDeclaration<'a> {
+    name: &'a str,
+    fqn: Option<&'a str>,
+    kind: DeclarationKind,
+    visibility: Visibility,
+    language: Language,
+    file: &'a Path,
+    line: usize,
+    column: usize,
+    parent: Option<usize>,
+    annotations: &'a [String],
+    super_types: &'a [String],
+    modifiers: &'a [String],
+    type_name: Option<&'a str>,
+    is_static: bool,
+    is_abstract: bool,
+}
+
+#[derive(Serialize)]
+struct SnapshotReference {
+    from: usize,
+    to: usize,
+    kind: ReferenceKind,
+}
+
+impl<'a> GraphSnapshot<'a> {
+    fn from_graph(graph: &'a Graph) -> Self {
+        let ids: Vec<_> = graph.declaration_ids().cloned().collect();
+        let index: HashMap<_, _> = ids.iter().enumerate().map(|(i, id)| (id, i)).collect();
+
+        let declarations = ids
+            .iter()
+            .filter_map(|id| graph.get_declaration(id))
+            .map(|decl| SnapshotDeclaration {
+                name: &decl.name,
+                fqn: decl.fully_qualified_name.as_deref(),
+                kind: decl.kind,
+                visibility: decl.visibility,
+                language: decl.language,
+                file: &decl.location.file,
+                line: decl.location.line,
+                column: decl.location.column,
+                parent: decl.parent.as_ref().and_then(|p| index.get(p).copied()),
+                annotations: &decl.annotations,
+                super_types: &decl.super_types,
+                modifiers: &decl.modifiers,
+                type_name: decl.type_name.as_deref(),
+                is_static: decl.is_static,
+                is_abstract: decl.is_abstract,
+            })
+            .collect();
+
+        let inner = graph.inner();
+        let references = inner
+            .edge_references()
+            .filter_map(|edge| {
+                let from = index.get(&inner[edge.source()])?;
+                let to = index.get(&inner[edge.target()])?;
+                Some(SnapshotReference {
+                    from: *from,
+                    to: *to,
+                    kind: edge.weight().kind,
+                })
+            })
+            .collect();
+
+        Self {
+            abi_version: PLUGIN_ABI_VERSION,
+            declarations,
+            references,
+            ids,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Declaration, DeclarationId, Location};
+
+    /// Plugin that flags the first declaration it is given
+    const FLAG_FIRST: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (data (i32.const 0) "{\"code\":\"WASM001\",\"name\":\"Flagged by plugin\",\"category\":\"Acme\",\"severity\":\"error\"}")
+          (data (i32.const 256) "[{\"declaration\":0,\"message\":\"flagged\",\"confidence\":\"high\"}]")
+          (func (export "sdc_abi_version") (result i32) i32.const 1)
+          (func (export "sdc_alloc") (param i32) (result i32) i32.const 1024)
+          (func (export "sdc_metadata") (result i64) i64.const 82)
+          (func (export "sdc_detect") (param i32 i32) (result i64)
+            i64.const 256
+            i64.const 32
+            i64.shl
+            i64.const 59
+            i64.or))
+    "#;
+
+    fn plugin(wat: &str) -> Result<WasmDetector> {
+        let wasm = ::wat::parse_str(wat).unwrap();
+        WasmDetector::from_bytes(Path::new("test.wasm"), &wasm)
+    }
+
+    fn graph_with_class(name: &str) -> Graph {
+        let path = PathBuf::from("Test.kt");
+        let mut graph = Graph::new();
+        graph.add_declaration(Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        ));
+        graph
+    }
+
+    #[test]
+    fn test_plugin_metadata() {
+        let detector = plugin(FLAG_FIRST).unwrap();
+        let metadata = detector.metadata();
+        assert_eq!(metadata.code, "WASM001");
+        assert_eq!(metadata.name, "Flagged by plugin");
+        assert_eq!(metadata.category, "Acme");
+        assert_eq!(metadata.default_severity, Severity::Error);
+        assert!(metadata.enabled_by_default);
+    }
+
+    #[test]
+    fn test_plugin_findings_map_to_declarations() {
+        let detector = plugin(FLAG_FIRST).unwrap();
+        let findings = detector.detect(&graph_with_class("Legacy"));
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].declaration.name, "Legacy");
+        assert_eq!(findings[0].issue.code(), "WASM001");
+        assert_eq!(findings[0].message, "flagged");
+        assert_eq!(findings[0].confidence, Confidence::High);
+        assert_eq!(findings[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_unknown_declaration_index_is_skipped() {
+        let detector = plugin(FLAG_FIRST).unwrap();
+        assert!(detector.detect(&Graph::new()).is_empty());
+    }
+
+    #[test]
+    fn test_rejects_other_abi_version() {
+        let wat = FLAG_FIRST.replace("(result i32) i32.const 1)", "(result i32) i32.const 2)");
+        let err = plugin(&wat).err().unwrap();
+        assert!(err.to_string().contains("host API v2"));
+    }
+
+    #[test]
+    fn test_runaway_plugin_runs_out_of_fuel() {
+        let wat = FLAG_FIRST.replace(
+            "(func (export \"sdc_detect\") (param i32 i32) (result i64)",
+            "(func (export \"sdc_detect\") (param i32 i32) (result i64) (loop (br 0))",
+        );
+        let detector = plugin(&wat).unwrap().with_fuel(100_000);
+        assert!(detector.run(&graph_with_class("Legacy")).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_indexes_references() {
+        let graph = graph_with_class("Legacy");
+        let snapshot = GraphSnapshot::from_graph(&graph);
+        assert_eq!(snapshot.declarations.len(), 1);
+        assert!(snapshot.references.is_empty());
+
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["abi_version"], PLUGIN_ABI_VERSION);
+        assert_eq!(json["declarations"][0]["name"], "Legacy");
+        assert_eq!(json["declarations"][0]["kind"], "Class");
+    }
+}
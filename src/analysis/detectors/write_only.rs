@@ -23,7 +23,7 @@
 //! }
 //! ```
 
-use super::Detector;
+use super::{Detector, DetectorMetadata};
 use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{DeclarationKind, Graph, Visibility};
 
@@ -98,6 +98,10 @@ impl Default for WriteOnlyDetector {
 }
 
 impl Detector for WriteOnlyDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::AssignOnly)
+    }
+
     fn detect(&self, graph: &Graph) -> Vec<DeadCode> {
         let mut issues = Vec::new();
 
@@ -127,6 +131,9 @@ impl Detector for WriteOnlyDetector {
                     decl.name, write_count
                 ));
                 dead = dead.with_confidence(Confidence::High);
+                for (_, reference) in refs.iter().filter(|(_, r)| r.kind.is_write()) {
+                    dead = dead.with_related(reference.location.clone(), "Assigned here");
+                }
                 issues.push(dead);
             }
         }
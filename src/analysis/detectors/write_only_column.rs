@@ -0,0 +1,605 @@
+//! Write-Only Room Column Detector
+//!
+//! Goes one level deeper than the write-only DAO detector: for every Room
+//! `@Entity` that some DAO inserts, updates or upserts, finds columns that
+//! no `@Query` or `@DatabaseView` ever names - not in a projection, a
+//! WHERE clause, an ORDER BY or a JOIN. Such a column is stored on every
+//! insert and never read back, so it is dead weight in the schema.
+//!
+//! Tables only loaded with `SELECT *` read every column; there a column
+//! is reported (with low confidence) only when its property is never
+//! accessed in code either. Tables no query mentions at all are left to
+//! the write-only DAO rule.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Collect `@Entity` classes and their columns (constructor and body
+//!    properties, honoring `@ColumnInfo(name = ...)` and skipping
+//!    `@PrimaryKey`, `@Ignore`, `@Embedded` and `@Relation`)
+//! 2. Collect the SQL of every `@Query` / `@DatabaseView`
+//! 3. Collect entity types passed to `@Insert` / `@Update` / `@Upsert`
+//! 4. Report columns of written entities that no query names
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! @Entity(tableName = "users")
+//! data class User(
+//!     @PrimaryKey val id: Long,
+//!     val name: String,
+//!     val legacyFlag: Boolean,  // DEAD: inserted, never queried
+//! )
+//!
+//! @Dao
+//! interface UserDao {
+//!     @Insert suspend fun insert(user: User)
+//!     @Query("SELECT id, name FROM users") fun names(): List<NameTuple>
+//! }
+//! ```
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::discovery::FileType;
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+use crate::parser::line_of;
+
+/// Property annotations that keep a property out of the table, or make Room
+/// use it without a query naming it
+const NON_COLUMN_ANNOTATIONS: &[&str] = &["PrimaryKey", "Ignore", "Embedded", "Relation"];
+
+/// A column of a Room entity
+#[derive(Debug, Clone)]
+pub struct EntityColumn {
+    /// Kotlin property name
+    pub property: String,
+    /// Column name (`@ColumnInfo(name = ...)` or the property name)
+    pub column: String,
+    pub line: usize,
+    pub start_byte: usize,
+    pub end_byte: usize,
+}
+
+/// A Room `@Entity` class
+#[derive(Debug, Clone)]
+pub struct RoomEntity {
+    pub name: String,
+    /// Table name (`@Entity(tableName = ...)` or the class name)
+    pub table: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub columns: Vec<EntityColumn>,
+}
+
+/// A column that is written but never queried
+#[derive(Debug, Clone)]
+pub struct WriteOnlyColumn {
+    pub entity: String,
+    pub table: String,
+    pub file: PathBuf,
+    pub column: EntityColumn,
+    /// Whether the table is read with `SELECT *` (so the column is loaded,
+    /// but its property is never accessed)
+    pub star_selected: bool,
+}
+
+/// Result of column analysis across all files
+#[derive(Debug, Default)]
+pub struct ColumnAnalysis {
+    pub entities: Vec<RoomEntity>,
+    /// SQL of every `@Query` / `@DatabaseView`
+    pub queries: Vec<String>,
+    /// Types passed to `@Insert` / `@Update` / `@Upsert` methods
+    pub written: HashSet<String>,
+    /// Member names accessed anywhere (`user.name`, `User::name`)
+    pub accessed: HashSet<String>,
+}
+
+impl ColumnAnalysis {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge the analysis of another file
+    pub fn extend(&mut self, other: ColumnAnalysis) {
+        self.entities.extend(other.entities);
+        self.queries.extend(other.queries);
+        self.written.extend(other.written);
+        self.accessed.extend(other.accessed);
+    }
+
+    /// Columns of written entities that no query names
+    pub fn write_only_columns(&self) -> Vec<WriteOnlyColumn> {
+        let count_star = Regex::new(r"(?i)count\s*\(\s*\*\s*\)").unwrap();
+        let queries: Vec<(HashSet<String>, bool)> = self
+            .queries
+            .iter()
+            .map(|sql| {
+                let star = count_star.replace_all(sql, "").contains('*');
+                (sql_identifiers(sql), star)
+            })
+            .collect();
+
+        let mut columns = Vec::new();
+        for entity in &self.entities {
+            if !self.written.contains(&entity.name) {
+                continue;
+            }
+            let table = entity.table.to_lowercase();
+            let table_queries: Vec<&(HashSet<String>, bool)> = queries
+                .iter()
+                .filter(|(identifiers, _)| identifiers.contains(&table))
+                .collect();
+            // A table no query mentions is a write-only DAO finding
+            if table_queries.is_empty() {
+                continue;
+            }
+            let star_selected = table_queries.iter().any(|(_, star)| *star);
+
+            for column in &entity.columns {
+                let name = column.column.to_lowercase();
+                if queries
+                    .iter()
+                    .any(|(identifiers, _)| identifiers.contains(&name))
+                {
+                    continue;
+                }
+                if star_selected && self.accessed.contains(&column.property) {
+                    continue;
+                }
+                columns.push(WriteOnlyColumn {
+                    entity: entity.name.clone(),
+                    table: entity.table.clone(),
+                    file: entity.file.clone(),
+                    column: column.clone(),
+                    star_selected,
+                });
+            }
+        }
+        columns
+    }
+
+    /// Convert the write-only columns to `DC024` issues
+    pub fn to_issues(&self) -> Vec<DeadCode> {
+        self.write_only_columns()
+            .into_iter()
+            .map(|found| {
+                let column = &found.column;
+                let decl = Declaration::new(
+                    DeclarationId::new(found.file.clone(), column.start_byte, column.end_byte),
+                    column.property.clone(),
+                    DeclarationKind::Property,
+                    Location::new(
+                        found.file.clone(),
+                        column.line,
+                        1,
+                        column.start_byte,
+                        column.end_byte,
+                    ),
+                    Language::Kotlin,
+                );
+                let issue = DeadCode::new(decl, DeadCodeIssue::WriteOnlyColumn);
+                if found.star_selected {
+                    issue
+                        .with_message(format!(
+                            "Column '{}' of table '{}' is only loaded by SELECT * and '{}.{}' is never read",
+                            column.column, found.table, found.entity, column.property
+                        ))
+                        .with_confidence(Confidence::Low)
+                } else {
+                    issue
+                        .with_message(format!(
+                            "Column '{}' of table '{}' is written but never used by any @Query",
+                            column.column, found.table
+                        ))
+                        .with_confidence(Confidence::Medium)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Detector for Room entity columns that are written but never queried
+pub struct WriteOnlyColumnDetector {
+    column_info_pattern: Regex,
+    table_name_pattern: Regex,
+    member_access_pattern: Regex,
+    type_name_pattern: Regex,
+}
+
+impl WriteOnlyColumnDetector {
+    pub fn new() -> Self {
+        Self {
+            column_info_pattern: Regex::new(r#"ColumnInfo\s*\(\s*(?:name\s*=\s*)?"([^"]+)""#)
+                .unwrap(),
+            table_name_pattern: Regex::new(r#"tableName\s*=\s*"([^"]+)""#).unwrap(),
+            member_access_pattern: Regex::new(r"(?:\.|::)\s*([a-zA-Z_]\w*)").unwrap(),
+            type_name_pattern: Regex::new(r"\b[A-Z]\w*\b").unwrap(),
+        }
+    }
+
+    /// Collect entities, queries, written types and member accesses from a source file
+    pub fn analyze_source(&self, source: &str, file: &Path) -> ColumnAnalysis {
+        let mut analysis = ColumnAnalysis::new();
+
+        for (start, end) in annotations(source, &["@Query", "@DatabaseView"]) {
+            analysis.queries.push(source[start..end].to_string());
+        }
+
+        for (start, end) in annotations(source, &["@Insert", "@Update", "@Upsert"]) {
+            // `@Insert(entity = User::class)` and the method's parameter types
+            let mut signature = &source[start..end];
+            if let Some(fun) = source[end..].find("fun ").map(|i| end + i) {
+                if let Some(open) = source[fun..].find('(').map(|i| fun + i) {
+                    let close = matching_close(source, open).unwrap_or(source.len());
+                    signature = &source[start..close];
+                }
+            }
+            analysis.written.extend(
+                self.type_name_pattern
+                    .find_iter(signature)
+                    .map(|m| m.as_str().to_string()),
+            );
+        }
+
+        for capture in self.member_access_pattern.captures_iter(source) {
+            let member = &capture[1];
+            analysis.accessed.insert(member.to_string());
+            // Java getters: `user.getLegacyFlag()` / `user.isActive()`
+            if let Some(property) = member
+                .strip_prefix("get")
+                .or_else(|| member.strip_prefix("is"))
+            {
+                let mut chars = property.chars();
+                if let Some(first) = chars.next().filter(|c| c.is_uppercase()) {
+                    analysis
+                        .accessed
+                        .insert(first.to_lowercase().chain(chars).collect());
+                }
+            }
+        }
+
+        for (start, end) in annotations(source, &["@Entity"]) {
+            if let Some(entity) = self.parse_entity(source, file, &source[start..end], end) {
+                analysis.entities.push(entity);
+            }
+        }
+
+        analysis
+    }
+
+    /// Parse the entity class following an `@Entity` annotation
+    fn parse_entity(
+        &self,
+        source: &str,
+        file: &Path,
+        annotation: &str,
+        after: usize,
+    ) -> Option<RoomEntity> {
+        let class = after + source[after..].find("class ")? + "class ".len();
+        let name_len = source[class..]
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(source.len() - class);
+        let name = source[class..class + name_len].to_string();
+        if name.is_empty() {
+            return None;
+        }
+        let table = self
+            .table_name_pattern
+            .captures(annotation)
+            .map(|c| c[1].to_string())
+            .unwrap_or_else(|| name.clone());
+
+        let mut entity = RoomEntity {
+            name,
+            table,
+            file: file.to_path_buf(),
+            line: line_of(source, class),
+            columns: Vec::new(),
+        };
+
+        // Primary constructor properties
+        let mut cursor = class + name_len;
+        let rest = &source[cursor..];
+        if rest.trim_start().starts_with('(') {
+            let open = cursor + rest.find('(')?;
+            let close = matching_close(source, open)?;
+            let mut start = open + 1;
+            for end in top_level_commas(source, open + 1, close) {
+                self.push_column(&mut entity, source, annotation, start, end);
+                start = end + 1;
+            }
+            cursor = close + 1;
+        }
+
+        // Body properties with a backing field
+        let rest = &source[cursor..];
+        let body_open = rest.find('{').map(|i| cursor + i);
+        let next_declaration = ["class ", "fun ", "\n@"]
+            .iter()
+            .filter_map(|k| rest.find(k))
+            .min()
+            .map(|i| cursor + i);
+        if let Some(open) = body_open.filter(|&open| next_declaration.map_or(true, |n| open < n)) {
+            let close = matching_close(source, open)?;
+            let mut start = open + 1;
+            let mut depth = 0;
+            for (i, c) in source[open + 1..close].char_indices() {
+                match c {
+                    '{' | '(' | '[' => depth += 1,
+                    '}' | ')' | ']' => depth -= 1,
+                    '\n' if depth == 0 => {
+                        let statement = source[start..open + 1 + i].trim();
+                        let is_property = statement.contains("val ") || statement.contains("var ");
+                        let has_field = statement.contains('=') || statement.contains("lateinit");
+                        let is_member = ["fun ", "object ", "class ", " by ", "get()"]
+                            .iter()
+                            .any(|k| statement.contains(k));
+                        if is_property && has_field && !is_member {
+                            self.push_column(&mut entity, source, annotation, start, open + 1 + i);
+                        }
+                        // Annotations on their own line belong to the next property
+                        if !statement.starts_with('@') || is_property {
+                            start = open + 2 + i;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Some(entity)
+    }
+
+    /// Add the property declared in `source[start..end]` as a column
+    fn push_column(
+        &self,
+        entity: &mut RoomEntity,
+        source: &str,
+        entity_annotation: &str,
+        start: usize,
+        end: usize,
+    ) {
+        let text = &source[start..end];
+        let Some(keyword) = ["val ", "var "].iter().filter_map(|k| text.rfind(k)).max() else {
+            return;
+        };
+        let (modifiers, declaration) = text.split_at(keyword);
+        if NON_COLUMN_ANNOTATIONS
+            .iter()
+            .any(|a| modifiers.contains(&format!("@{}", a)))
+        {
+            return;
+        }
+        let property: String = declaration[4..]
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        if property.is_empty() {
+            return;
+        }
+        let column = self
+            .column_info_pattern
+            .captures(modifiers)
+            .map(|c| c[1].to_string())
+            .unwrap_or_else(|| property.clone());
+        // Columns named by `indices`, `primaryKeys` or `foreignKeys` are used by the schema
+        if entity_annotation.contains(&format!("\"{}\"", column)) {
+            return;
+        }
+
+        let offset = start + text.len() - text.trim_start().len();
+        entity.columns.push(EntityColumn {
+            property,
+            column,
+            line: line_of(source, offset),
+            start_byte: offset,
+            end_byte: start + text.trim_end().len(),
+        });
+    }
+}
+
+impl Default for WriteOnlyColumnDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for WriteOnlyColumnDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::WriteOnlyColumn)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        let mut analysis = ColumnAnalysis::new();
+        let sources = project.files.iter().filter(|file| {
+            matches!(file.file_type, FileType::Kotlin | FileType::Java) && !file.generated
+        });
+        for file in sources {
+            if let Ok(content) = std::fs::read_to_string(&file.path) {
+                analysis.extend(self.analyze_source(&content, &file.path));
+            }
+        }
+        analysis.to_issues()
+    }
+}
+
+/// Byte ranges of the annotations named `names`, including their arguments
+fn annotations(source: &str, names: &[&str]) -> Vec<(usize, usize)> {
+    let mut found = Vec::new();
+    for name in names {
+        for (start, _) in source.match_indices(name) {
+            let after = start + name.len();
+            if source[after..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_')
+            {
+                continue;
+            }
+            let end = if source[after..].starts_with('(') {
+                matching_close(source, after).map_or(source.len(), |close| close + 1)
+            } else {
+                after
+            };
+            found.push((start, end));
+        }
+    }
+    found.sort_unstable();
+    found
+}
+
+/// Index of the bracket closing the one at `open`, skipping string literals
+fn matching_close(source: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in source[open..].char_indices() {
+        if in_string {
+            match c {
+                '\\' if !escaped => escaped = true,
+                '"' if !escaped => in_string = false,
+                _ => escaped = false,
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Positions of the parameter separators in `source[start..end]`, plus `end`
+fn top_level_commas(source: &str, start: usize, end: usize) -> Vec<usize> {
+    let mut commas = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut previous = ' ';
+    for (i, c) in source[start..end].char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            // `(Int) -> Unit` is not a type argument list
+            '>' if previous == '-' => {}
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' | '>' => depth -= 1,
+            ',' if depth == 0 => commas.push(start + i),
+            _ => {}
+        }
+        previous = c;
+    }
+    commas.push(end);
+    commas
+}
+
+/// Lowercased identifiers of a SQL statement (SQLite names are case-insensitive)
+fn sql_identifiers(sql: &str) -> HashSet<String> {
+    sql.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTITIES: &str = r#"
+@Entity(tableName = "users", indices = [Index(value = ["created_at"])])
+data class User(
+    @PrimaryKey val id: Long,
+    val name: String,
+    @ColumnInfo(name = "email_address") val email: String,
+    @ColumnInfo(name = "created_at") val createdAt: Long,
+    val legacyFlag: Boolean,
+    @Ignore val cached: String = "",
+) {
+    var nickname: String? = null
+    val display: String get() = name
+}
+
+@Entity
+class Event(@PrimaryKey val id: Long, val payload: String, val source: String)
+
+@Entity
+class AuditLog(@PrimaryKey val id: Long, val message: String)
+"#;
+
+    const DAOS: &str = r#"
+@Dao
+interface UserDao {
+    @Insert
+    suspend fun insert(user: User)
+
+    @Upsert
+    suspend fun save(events: List<Event>)
+
+    @Query("SELECT id, name FROM users WHERE email_address = :email ORDER BY nickname")
+    suspend fun byEmail(email: String): List<NameTuple>
+
+    @Query("SELECT * FROM Event")
+    fun events(): Flow<List<Event>>
+}
+
+fun render(event: Event) = event.payload
+"#;
+
+    fn analyze() -> ColumnAnalysis {
+        let detector = WriteOnlyColumnDetector::new();
+        let mut analysis = detector.analyze_source(ENTITIES, Path::new("Entities.kt"));
+        analysis.extend(detector.analyze_source(DAOS, Path::new("UserDao.kt")));
+        analysis
+    }
+
+    #[test]
+    fn test_parse_entities() {
+        let analysis = analyze();
+        let user = &analysis.entities[0];
+        assert_eq!(user.table, "users");
+        let columns: Vec<_> = user.columns.iter().map(|c| c.column.as_str()).collect();
+        assert_eq!(columns, ["name", "email_address", "legacyFlag", "nickname"]);
+        assert_eq!(user.columns[2].line, 8);
+        assert_eq!(analysis.entities[1].table, "Event");
+        assert!(analysis.written.contains("User"));
+        assert!(analysis.written.contains("Event"));
+        assert!(!analysis.written.contains("AuditLog"));
+    }
+
+    #[test]
+    fn test_write_only_columns() {
+        let analysis = analyze();
+        let found: Vec<_> = analysis
+            .write_only_columns()
+            .into_iter()
+            .map(|c| (c.column.property, c.star_selected))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("legacyFlag".to_string(), false),
+                ("source".to_string(), true)
+            ]
+        );
+
+        let issues = analysis.to_issues();
+        assert_eq!(issues[0].issue, DeadCodeIssue::WriteOnlyColumn);
+        assert_eq!(
+            issues[0].message,
+            "Column 'legacyFlag' of table 'users' is written but never used by any @Query"
+        );
+        assert_eq!(issues[1].confidence, Confidence::Low);
+    }
+}
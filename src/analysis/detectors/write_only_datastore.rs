@@ -0,0 +1,401 @@
+//! Write-Only DataStore Detector
+//!
+//! Detects Jetpack Preferences DataStore keys that are written inside
+//! `edit {}` blocks but never read back from the preferences flow. This is
+//! the DataStore counterpart of the SharedPreferences detector.
+//!
+//! ## Detection Algorithm
+//!
+//! 1. Find key definitions (`val KEY = stringPreferencesKey("name")`,
+//!    `intPreferencesKey`, `booleanPreferencesKey`, ...) across all files
+//! 2. Classify every use of a key: `prefs[KEY] = value`, `remove(KEY)` and
+//!    `prefs -= KEY` are writes; anything else (`prefs[KEY]`,
+//!    `contains(KEY)`, passing the key around) is a read
+//! 3. Report keys that are written but never read
+//!
+//! Keys are identified by their name string, so two constants for the same
+//! name, or an inline `stringPreferencesKey("name")`, count as one key.
+//!
+//! ## Examples Detected
+//!
+//! ```kotlin
+//! object Keys {
+//!     val THEME = stringPreferencesKey("theme")
+//!     val LAST_OPENED = longPreferencesKey("last_opened")
+//! }
+//!
+//! suspend fun save(theme: String) = dataStore.edit { prefs ->
+//!     prefs[Keys.THEME] = theme
+//!     prefs[Keys.LAST_OPENED] = System.currentTimeMillis()  // DEAD: never read
+//! }
+//!
+//! val theme: Flow<String> = dataStore.data.map { it[Keys.THEME] ?: "light" }
+//! ```
+
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::write_only_prefs::PrefKeyLocation;
+use super::{DetectorMetadata, ProjectContext, ProjectDetector};
+use crate::analysis::{Confidence, DeadCode, DeadCodeIssue};
+use crate::discovery::FileType;
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+use crate::parser::line_of;
+
+/// A `xxxPreferencesKey("name")` constant
+#[derive(Debug, Clone)]
+pub struct DataStoreKey {
+    /// Constant name (e.g., "THEME")
+    pub constant: String,
+    /// Key name stored in the DataStore (e.g., "theme")
+    pub key: String,
+    /// Value type (e.g., "string", "int", "stringSet")
+    pub value_type: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Result of DataStore analysis
+#[derive(Debug, Default)]
+pub struct DataStoreAnalysis {
+    /// Key constants found
+    pub keys: Vec<DataStoreKey>,
+    /// Keys that are written (key name -> locations)
+    pub writes: HashMap<String, Vec<PrefKeyLocation>>,
+    /// Keys that are read (key name -> locations)
+    pub reads: HashMap<String, Vec<PrefKeyLocation>>,
+}
+
+impl DataStoreAnalysis {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a write location for a key
+    pub fn add_write(&mut self, key: String, file: PathBuf, line: usize) {
+        self.writes
+            .entry(key.clone())
+            .or_default()
+            .push(PrefKeyLocation {
+                key,
+                file,
+                line,
+                is_write: true,
+            });
+    }
+
+    /// Add a read location for a key
+    pub fn add_read(&mut self, key: String, file: PathBuf, line: usize) {
+        self.reads
+            .entry(key.clone())
+            .or_default()
+            .push(PrefKeyLocation {
+                key,
+                file,
+                line,
+                is_write: false,
+            });
+    }
+
+    /// Get keys that are written but never read, sorted by name
+    pub fn get_write_only_keys(&self) -> Vec<&String> {
+        let mut keys: Vec<&String> = self
+            .writes
+            .keys()
+            .filter(|key| !self.reads.contains_key(*key))
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Check if a specific key is write-only
+    pub fn is_write_only(&self, key: &str) -> bool {
+        self.writes.contains_key(key) && !self.reads.contains_key(key)
+    }
+}
+
+/// Detector for write-only DataStore keys
+pub struct WriteOnlyDataStoreDetector {
+    // val NAME = stringPreferencesKey("name")
+    definition_pattern: Regex,
+    // stringPreferencesKey("name") used inline
+    inline_key_pattern: Regex,
+    identifier_pattern: Regex,
+}
+
+impl WriteOnlyDataStoreDetector {
+    pub fn new() -> Self {
+        Self {
+            definition_pattern: Regex::new(
+                r#"\b(?:val|var)\s+(\w+)\s*(?::[^=\n]+)?=\s*(\w+)PreferencesKey\s*(?:<[^>]*>)?\(\s*"([^"]*)"\s*\)"#,
+            )
+            .unwrap(),
+            inline_key_pattern: Regex::new(
+                r#"\b(\w+)PreferencesKey\s*(?:<[^>]*>)?\(\s*"([^"]*)"\s*\)"#,
+            )
+            .unwrap(),
+            identifier_pattern: Regex::new(r"\b[A-Za-z_]\w*\b").unwrap(),
+        }
+    }
+
+    /// Analyze Kotlin sources for DataStore key usage
+    ///
+    /// Keys are usually defined in one file and used in others, so all
+    /// sources are needed at once.
+    pub fn analyze_sources(&self, sources: &[(PathBuf, String)]) -> DataStoreAnalysis {
+        let mut analysis = DataStoreAnalysis::new();
+        for (file, source) in sources {
+            self.collect_keys(source, file, &mut analysis);
+        }
+
+        let mut constants: HashMap<&str, HashSet<&str>> = HashMap::new();
+        for key in &analysis.keys {
+            constants
+                .entry(key.constant.as_str())
+                .or_default()
+                .insert(key.key.as_str());
+        }
+
+        let mut uses = Vec::new();
+        for (file, source) in sources {
+            if constants.is_empty() && !source.contains("PreferencesKey") {
+                continue;
+            }
+            // Byte ranges of key definitions, which are neither reads nor writes
+            let definitions: Vec<(usize, usize)> = self
+                .definition_pattern
+                .find_iter(source)
+                .map(|m| (m.start(), m.end()))
+                .collect();
+            let in_definition = |pos: usize| definitions.iter().any(|&(s, e)| s <= pos && pos < e);
+
+            for m in self.identifier_pattern.find_iter(source) {
+                if in_definition(m.start()) {
+                    continue;
+                }
+                let Some(keys) = constants.get(m.as_str()) else {
+                    continue;
+                };
+                let is_write = is_write(source, m.start(), m.end());
+                for key in keys {
+                    uses.push((key.to_string(), file, line_of(source, m.start()), is_write));
+                }
+            }
+
+            for caps in self.inline_key_pattern.captures_iter(source) {
+                let m = caps.get(0).unwrap();
+                if in_definition(m.start()) {
+                    continue;
+                }
+                let is_write = is_write(source, m.start(), m.end());
+                uses.push((
+                    caps[2].to_string(),
+                    file,
+                    line_of(source, m.start()),
+                    is_write,
+                ));
+            }
+        }
+
+        for (key, file, line, is_write) in uses {
+            if is_write {
+                analysis.add_write(key, file.clone(), line);
+            } else {
+                analysis.add_read(key, file.clone(), line);
+            }
+        }
+
+        analysis
+    }
+
+    /// Record `xxxPreferencesKey` constants defined in a source file
+    fn collect_keys(&self, source: &str, file: &Path, analysis: &mut DataStoreAnalysis) {
+        for caps in self.definition_pattern.captures_iter(source) {
+            analysis.keys.push(DataStoreKey {
+                constant: caps[1].to_string(),
+                key: caps[3].to_string(),
+                value_type: caps[2].to_string(),
+                file: file.to_path_buf(),
+                line: line_of(source, caps.get(0).unwrap().start()),
+            });
+        }
+    }
+}
+
+impl Default for WriteOnlyDataStoreDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectDetector for WriteOnlyDataStoreDetector {
+    fn metadata(&self) -> DetectorMetadata {
+        DetectorMetadata::for_issue(DeadCodeIssue::WriteOnlyDataStoreKey)
+    }
+
+    fn detect(&self, project: &ProjectContext) -> Vec<DeadCode> {
+        // Keys are usually defined in one file and used in another
+        let sources: Vec<_> = project
+            .files
+            .iter()
+            .filter(|file| file.file_type == FileType::Kotlin && !file.generated)
+            .filter_map(|file| Some((file.path.clone(), std::fs::read_to_string(&file.path).ok()?)))
+            .collect();
+        analysis_to_issues(&self.analyze_sources(&sources))
+    }
+}
+
+/// Whether the key expression at `start..end` is being written
+///
+/// Writes are `prefs[KEY] = value`, `prefs.remove(KEY)` and `prefs -= KEY`,
+/// which is all `MutablePreferences` offers inside `edit {}`.
+fn is_write(source: &str, mut start: usize, end: usize) -> bool {
+    // Include qualifiers such as `Keys.` in `prefs[Keys.THEME]`
+    while source[..start].ends_with('.') {
+        let qualifier = source[..start - 1]
+            .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_')
+            .len();
+        if qualifier == start - 1 {
+            break;
+        }
+        start = qualifier;
+    }
+
+    let before = source[..start].trim_end();
+    let after = source[end..].trim_start();
+
+    if before.ends_with('[') {
+        if let Some(rest) = after.strip_prefix(']') {
+            let rest = rest.trim_start();
+            return rest.starts_with('=') && !rest.starts_with("==");
+        }
+        return false;
+    }
+
+    before.ends_with("remove(") || before.ends_with("-=") || before.ends_with("minusAssign(")
+}
+
+/// Convert analysis results to DeadCode issues
+pub fn analysis_to_issues(analysis: &DataStoreAnalysis) -> Vec<DeadCode> {
+    let mut issues = Vec::new();
+
+    for key in analysis.get_write_only_keys() {
+        if let Some(locations) = analysis.writes.get(key) {
+            for loc in locations {
+                // Create a synthetic declaration for the DataStore key
+                let decl = Declaration::new(
+                    DeclarationId::new(loc.file.clone(), loc.line, 0),
+                    format!("DataStore key '{}'", key),
+                    DeclarationKind::Property,
+                    Location::new(loc.file.clone(), loc.line, 1, 0, 0),
+                    Language::Kotlin,
+                );
+
+                let mut dead = DeadCode::new(decl, DeadCodeIssue::WriteOnlyDataStoreKey)
+                    .with_message(format!("DataStore key '{}' is written but never read", key))
+                    .with_confidence(Confidence::High);
+                for other in locations.iter().filter(|other| !std::ptr::eq(*other, loc)) {
+                    dead = dead.with_related(
+                        Location::new(other.file.clone(), other.line, 1, 0, 0),
+                        "Also written here",
+                    );
+                }
+                issues.push(dead);
+            }
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(sources: &[(&str, &str)]) -> DataStoreAnalysis {
+        let sources: Vec<(PathBuf, String)> = sources
+            .iter()
+            .map(|(file, source)| (PathBuf::from(file), source.to_string()))
+            .collect();
+        WriteOnlyDataStoreDetector::new().analyze_sources(&sources)
+    }
+
+    #[test]
+    fn test_is_write() {
+        let source = "prefs[Keys.THEME] = theme";
+        let start = source.find("THEME").unwrap();
+        assert!(is_write(source, start, start + 5));
+
+        let source = "val t = prefs[THEME] ?: \"light\"";
+        let start = source.find("THEME").unwrap();
+        assert!(!is_write(source, start, start + 5));
+
+        let source = "if (prefs[THEME] == null) prefs.remove(THEME)";
+        let first = source.find("THEME").unwrap();
+        let second = source.rfind("THEME").unwrap();
+        assert!(!is_write(source, first, first + 5));
+        assert!(is_write(source, second, second + 5));
+    }
+
+    #[test]
+    fn test_write_only_keys_across_files() {
+        let analysis = analyze(&[
+            (
+                "Keys.kt",
+                r#"
+object Keys {
+    val THEME = stringPreferencesKey("theme")
+    val LAST_OPENED: Preferences.Key<Long> = longPreferencesKey("last_opened")
+    val COUNTER = intPreferencesKey("counter")
+}
+"#,
+            ),
+            (
+                "SettingsRepository.kt",
+                r#"
+class SettingsRepository(private val dataStore: DataStore<Preferences>) {
+    val theme: Flow<String> = dataStore.data.map { it[Keys.THEME] ?: "light" }
+
+    suspend fun save(theme: String) {
+        dataStore.edit { prefs ->
+            prefs[Keys.THEME] = theme
+            prefs[Keys.LAST_OPENED] = System.currentTimeMillis()
+            prefs[Keys.COUNTER] = (prefs[Keys.COUNTER] ?: 0) + 1
+        }
+    }
+}
+"#,
+            ),
+        ]);
+
+        assert_eq!(analysis.keys.len(), 3);
+        assert_eq!(analysis.keys[1].value_type, "long");
+        assert_eq!(analysis.get_write_only_keys(), ["last_opened"]);
+
+        let issues = analysis_to_issues(&analysis);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue, DeadCodeIssue::WriteOnlyDataStoreKey);
+        assert_eq!(issues[0].declaration.location.line, 8);
+    }
+
+    #[test]
+    fn test_inline_keys_and_removal() {
+        let analysis = analyze(&[(
+            "Session.kt",
+            r#"
+suspend fun logout() = dataStore.edit {
+    it.remove(stringPreferencesKey("token"))
+    it -= stringPreferencesKey("refresh_token")
+    it[booleanPreferencesKey("onboarded")] = true
+}
+
+val onboarded = dataStore.data.map { it[booleanPreferencesKey("onboarded")] == true }
+"#,
+        )]);
+
+        assert!(analysis.is_write_only("token"));
+        assert!(analysis.is_write_only("refresh_token"));
+        assert!(!analysis.is_write_only("onboarded"));
+    }
+}
@@ -233,6 +233,12 @@ pub fn analysis_to_issues(analysis: &SharedPrefsAnalysis) -> Vec<DeadCode> {
                     key
                 ));
                 dead = dead.with_confidence(Confidence::High);
+                for other in locations.iter().filter(|other| !std::ptr::eq(*other, loc)) {
+                    dead = dead.with_related(
+                        Location::new(other.file.clone(), other.line, 1, 0, 0),
+                        "Also written here",
+                    );
+                }
                 issues.push(dead);
             }
         }
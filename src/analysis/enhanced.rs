@@ -1,6 +1,7 @@
 // Enhanced dead code analyzer with parallel processing
 // and ProGuard cross-validation
 
+use super::reachability::{default_retain_annotations, with_retained_roots};
 use super::{Confidence, DeadCode, DeadCodeIssue};
 use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
 use crate::proguard::ProguardUsage;
@@ -15,6 +16,8 @@ pub struct EnhancedAnalyzer {
     proguard: Option<Arc<ProguardUsage>>,
     /// Whether to use strict mode (report more items)
     strict_mode: bool,
+    /// Annotations whose declarations are reachability roots
+    retain_annotations: Vec<String>,
 }
 
 impl EnhancedAnalyzer {
@@ -22,6 +25,7 @@ impl EnhancedAnalyzer {
         Self {
             proguard: None,
             strict_mode: false,
+            retain_annotations: default_retain_annotations(),
         }
     }
 
@@ -35,6 +39,13 @@ impl EnhancedAnalyzer {
         self
     }
 
+    /// Annotations whose declarations (and the members of annotated
+    /// types) are never reported
+    pub fn with_retain_annotations(mut self, annotations: Vec<String>) -> Self {
+        self.retain_annotations = annotations;
+        self
+    }
+
     /// Analyze the graph and find dead code with parallel processing
     pub fn analyze(
         &self,
@@ -44,6 +55,7 @@ impl EnhancedAnalyzer {
         info!("Running enhanced analysis with parallelism...");
 
         // Step 1: Build reachability set (parallel BFS from entry points)
+        let entry_points = &with_retained_roots(graph, entry_points, &self.retain_annotations);
         let reachable = self.find_reachable_parallel(graph, entry_points);
 
         // Step 2: Find unreachable declarations in parallel
@@ -194,6 +206,34 @@ impl EnhancedAnalyzer {
             return true;
         }
 
+        // Skip Kotlin const val - let the dedicated UnusedConstantDetector handle them
+        if decl.is_const() {
+            return true;
+        }
+
+        // Skip enum constants - let the dedicated UnusedEnumCaseDetector handle them,
+        // it knows when values()/entries make a constant reachable by enumeration
+        if decl.kind == DeclarationKind::EnumCase {
+            return true;
+        }
+
+        // Skip test sources - the UnusedTestHelperDetector runs reachability from
+        // the tests themselves and reports under its own category
+        if decl.is_test_source() {
+            return true;
+        }
+
+        // Skip extensions with no call sites - the dedicated UnusedExtensionDetector
+        // reports them. Extensions only called from dead code are still reported here.
+        if decl.is_extension() && !graph.is_referenced(&decl.id) {
+            return true;
+        }
+
+        // Skip type aliases with no references - the UnusedTypeAliasDetector reports them
+        if decl.kind == DeclarationKind::TypeAlias && !graph.is_referenced(&decl.id) {
+            return true;
+        }
+
         // Skip members of unreachable classes (report class instead)
         if !self.strict_mode {
             if let Some(parent_id) = &decl.parent {
@@ -251,16 +291,19 @@ impl EnhancedAnalyzer {
         let mut fqn_map: HashMap<String, String> = HashMap::new();
         for decl in graph.declarations() {
             if let Some(fqn) = &decl.fully_qualified_name {
-                fqn_map.insert(decl.name.clone(), fqn.clone());
+                fqn_map.insert(decl.name.to_string(), fqn.to_string());
             }
         }
 
         // Update confidence based on ProGuard confirmation
         for dc in &mut dead_code {
             let class_fqn = dc.declaration.fully_qualified_name.as_deref();
-            let member_name = &dc.declaration.name;
+            // Members renamed with `@JvmName` are listed under that name
+            let confidence_boost = std::iter::once(dc.declaration.name.as_str())
+                .chain(dc.declaration.alternate_names.iter().map(String::as_str))
+                .find_map(|member_name| proguard.get_confidence_for(class_fqn, member_name));
 
-            if let Some(confidence_boost) = proguard.get_confidence_for(class_fqn, member_name) {
+            if let Some(confidence_boost) = confidence_boost {
                 if confidence_boost >= 1.0 {
                     dc.confidence = Confidence::Confirmed;
                     dc.runtime_confirmed = true;
@@ -340,4 +383,52 @@ mod tests {
         let (dead_code, _) = analyzer.analyze(&graph, &entry_points);
         assert!(dead_code.is_empty());
     }
+
+    #[test]
+    fn test_proguard_matches_jvm_name() {
+        use crate::discovery::{FileType, SourceFile};
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Api.kt");
+        std::fs::write(
+            &path,
+            r#"package com.example
+
+class Api {
+    @JvmName("legacyApi")
+    fun api() {}
+
+    fun other() {}
+}
+"#,
+        )
+        .unwrap();
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(path, FileType::Kotlin))
+            .unwrap();
+        let graph = builder.build();
+
+        // usage.txt lists the method under the name the JVM sees
+        let usage = ProguardUsage::parse_content(
+            r#"com.example.Api
+    void legacyApi()
+"#,
+        )
+        .unwrap();
+        let analyzer = EnhancedAnalyzer::new()
+            .with_strict_mode(true)
+            .with_proguard(usage);
+        let (dead_code, _) = analyzer.analyze(&graph, &HashSet::new());
+
+        let confidence = |name: &str| {
+            dead_code
+                .iter()
+                .find(|dc| dc.declaration.name == name)
+                .map(|dc| dc.confidence)
+        };
+        assert_eq!(confidence("api"), Some(Confidence::High));
+        assert_ne!(confidence("other"), Some(Confidence::High));
+    }
 }
@@ -1,13 +1,198 @@
+use crate::analysis::detectors::UnusedDaggerBindingDetector;
+use crate::cancel::CancellationToken;
 use crate::config::Config;
 use crate::discovery::FileFinder;
-use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph};
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph, TypeResolver};
+use crate::parser::aidl::AidlParser;
+use crate::parser::baseline_profile::BaselineProfile;
+use crate::parser::jni::{JniParser, JniReferences};
+use crate::parser::proto::ProtoParser;
 use crate::parser::xml::{
     LayoutParser, ManifestParser, MenuParser, NavigationParser, XmlParseResult,
 };
+use crate::proguard::class_name_matches;
 use miette::Result;
+use regex::Regex;
 use std::collections::HashSet;
 use std::path::Path;
-use tracing::{debug, info};
+use std::sync::LazyLock;
+use tracing::{debug, info, warn};
+
+/// WorkManager base classes, instantiated by reflection
+const WORKER_BASES: &[&str] = &["Worker", "CoroutineWorker", "RxWorker", "ListenableWorker"];
+
+/// Worker methods WorkManager calls
+const WORKER_CALLBACKS: &[&str] = &[
+    "doWork",
+    "createWork",
+    "startWork",
+    "getForegroundInfo",
+    "getForegroundInfoAsync",
+    "onStopped",
+];
+
+/// Push messaging services the messaging SDK starts: Firebase Cloud
+/// Messaging, Huawei Push Kit and the legacy GCM / instance ID services
+const PUSH_SERVICE_BASES: &[&str] = &[
+    "FirebaseMessagingService",
+    "FirebaseInstanceIdService",
+    "HmsMessageService",
+    "GcmListenerService",
+    "InstanceIDListenerService",
+];
+
+/// Push service methods the messaging SDK calls
+const PUSH_CALLBACKS: &[&str] = &[
+    "onMessageReceived",
+    "onNewToken",
+    "onDeletedMessages",
+    "onMessageSent",
+    "onSendError",
+    "onTokenRefresh",
+    "onTokenError",
+    "onMessageDelivered",
+];
+
+/// Components the system binds to or broadcasts to once the user enables
+/// them in a system UI (home screen widgets, Quick Settings tiles,
+/// accessibility and keyboard settings, screen savers), with the methods
+/// it calls
+const SYSTEM_COMPONENTS: &[(&str, &[&str])] = &[
+    (
+        "AppWidgetProvider",
+        &[
+            "onReceive",
+            "onUpdate",
+            "onAppWidgetOptionsChanged",
+            "onDeleted",
+            "onEnabled",
+            "onDisabled",
+            "onRestored",
+        ],
+    ),
+    (
+        "TileService",
+        &[
+            "onTileAdded",
+            "onTileRemoved",
+            "onStartListening",
+            "onStopListening",
+            "onClick",
+        ],
+    ),
+    (
+        "AccessibilityService",
+        &[
+            "onServiceConnected",
+            "onAccessibilityEvent",
+            "onInterrupt",
+            "onGesture",
+            "onKeyEvent",
+        ],
+    ),
+    (
+        "InputMethodService",
+        &[
+            "onInitializeInterface",
+            "onCreateInputView",
+            "onCreateCandidatesView",
+            "onStartInput",
+            "onStartInputView",
+            "onFinishInput",
+            "onFinishInputView",
+            "onUpdateSelection",
+            "onDisplayCompletions",
+            "onEvaluateFullscreenMode",
+            "onComputeInsets",
+            "onKeyDown",
+            "onKeyUp",
+        ],
+    ),
+    (
+        "DreamService",
+        &[
+            "onAttachedToWindow",
+            "onDreamingStarted",
+            "onDreamingStopped",
+            "onDetachedFromWindow",
+        ],
+    ),
+];
+
+/// Service lifecycle methods the system calls on every component above
+const SYSTEM_LIFECYCLE_CALLBACKS: &[&str] = &["onCreate", "onBind", "onUnbind", "onDestroy"];
+
+/// Serialization calls that name the serialized type in their type
+/// arguments: `Json.decodeFromString<Foo>()`, `object : TypeToken<List<Foo>>()`,
+/// `moshi.adapter<Foo>()`, `gson.fromJson<Foo>()`
+static SERIALIZER_GENERIC_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b(?:decodeFrom\w+|encodeTo\w+|serializer|TypeToken|fromJson|toJson|adapter|readValue|convertValue)\s*<",
+    )
+    .unwrap()
+});
+
+/// Serialization calls that name the serialized type by class literal:
+/// `gson.fromJson(json, Foo::class.java)`, `moshi.adapter(Foo.class)`,
+/// `Types.newParameterizedType(List::class.java, Foo::class.java)`
+static SERIALIZER_CALL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b(?:decodeFrom\w+|encodeTo\w+|fromJson|toJson|adapter|readValue|convertValue|newParameterizedType)\s*\(",
+    )
+    .unwrap()
+});
+
+/// `Foo::class`, `Foo::class.java`, `Foo.class`
+static CLASS_LITERAL_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b([A-Z]\w*)\s*(?:::\s*class|\.class)\b").unwrap());
+
+/// kotlinx.serialization serializers: `Foo.serializer()`
+static SERIALIZER_OF_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b([A-Z]\w*)\s*\.\s*serializer\s*\(\s*\)").unwrap());
+
+/// Type names in a type argument list or property type
+static TYPE_NAME_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b[A-Z]\w*").unwrap());
+
+/// `androidx.startup.Initializer` methods the startup library calls
+const STARTUP_CALLBACKS: &[&str] = &["create", "dependencies"];
+
+/// Component methods the system calls with the intent that started them
+const INTENT_CALLBACKS: &[&str] = &[
+    "onCreate",
+    "onNewIntent",
+    "onStartCommand",
+    "onHandleIntent",
+    "onBind",
+    "onReceive",
+];
+
+/// Compose navigation destinations: `composable("detail/{id}", ...)`,
+/// `composable<Detail>(...)`, `dialog(...)`
+static DESTINATION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:composable|dialog)\s*(?:<\s*([A-Z][\w.]*)\s*>)?\s*\(").unwrap()
+});
+
+/// Calls of PascalCase functions (composables) or constructors
+static PASCAL_CALL_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b([A-Z]\w*)\s*[({]").unwrap());
+
+/// Keys read from worker `Data`: `inputData.getString(KEY_URL)`
+static DATA_READ_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b(?:inputData|getInputData\(\)|progress|getProgress\(\)|outputData|getOutputData\(\))\s*\.\s*(?:get\w*|hasKeyWithValueOfType)\s*(?:<[^>]*>)?\s*\(\s*([A-Za-z_][\w.]*)",
+    )
+    .unwrap()
+});
+
+/// Keys written through `Data.Builder`: `.putString(KEY_URL, url)`
+static DATA_PUT_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\.put(?:String|Int|Long|Boolean|Float|Double|Byte)(?:Array)?\s*\(\s*([A-Za-z_][\w.]*)\s*,")
+        .unwrap()
+});
+
+/// Keys of `workDataOf(KEY_URL to url)` pairs
+static DATA_PAIR_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"([A-Za-z_][\w.]*)\s+to\b").unwrap());
 
 /// Detects entry points in an Android project
 pub struct EntryPointDetector<'a> {
@@ -15,7 +200,13 @@ pub struct EntryPointDetector<'a> {
     manifest_parser: ManifestParser,
     layout_parser: LayoutParser,
     navigation_parser: NavigationParser,
+    aidl_parser: AidlParser,
+    proto_parser: ProtoParser,
     menu_parser: MenuParser,
+    jni_parser: JniParser,
+    /// Treat every `@Composable` function as an entry point
+    composable_entry_points: bool,
+    cancel: CancellationToken,
 }
 
 impl<'a> EntryPointDetector<'a> {
@@ -25,16 +216,41 @@ impl<'a> EntryPointDetector<'a> {
             manifest_parser: ManifestParser::new(),
             layout_parser: LayoutParser::new(),
             navigation_parser: NavigationParser::new(),
+            aidl_parser: AidlParser::new(),
+            proto_parser: ProtoParser::new(),
             menu_parser: MenuParser::new(),
+            jni_parser: JniParser::new(),
+            composable_entry_points: true,
+            cancel: CancellationToken::new(),
         }
     }
 
+    /// Whether every `@Composable` function is an entry point; when off,
+    /// composables are only reached through their call sites, so unused
+    /// trees of composables become visible (`@Preview` functions stay
+    /// entry points)
+    pub fn with_composable_entry_points(mut self, enabled: bool) -> Self {
+        self.composable_entry_points = enabled;
+        self
+    }
+
+    /// Stop scanning sources and resources and fail once `cancel` fires
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    fn file_finder(&self) -> FileFinder<'a> {
+        FileFinder::new(self.config).with_cancellation(self.cancel.clone())
+    }
+
     /// Detect all entry points in the project
     pub fn detect(&self, graph: &Graph, root: &Path) -> Result<HashSet<DeclarationId>> {
         let mut entry_points = HashSet::new();
 
         // 1. Detect entry points from code analysis
         self.detect_code_entry_points(graph, &mut entry_points);
+        self.cancel.check()?;
 
         // 2. Detect entry points from AndroidManifest.xml
         if self.config.android.parse_manifest {
@@ -52,11 +268,51 @@ impl<'a> EntryPointDetector<'a> {
         // 5. Detect entry points from menu XMLs
         self.detect_menu_entry_points(graph, root, &mut entry_points)?;
 
-        // 6. Add explicitly configured entry points
+        // 6. Detect entry points from AIDL interfaces
+        self.detect_aidl_entry_points(graph, root, &mut entry_points)?;
+
+        // 7. Detect entry points from protobuf definitions
+        if self.config.protobuf.enabled {
+            self.detect_proto_entry_points(graph, root, &mut entry_points)?;
+        }
+
+        // 8. Detect WorkManager workers and their data keys
+        self.detect_worker_entry_points(graph, root, &mut entry_points)?;
+
+        // 9. Detect push messaging services
+        self.detect_push_service_entry_points(graph, &mut entry_points);
+
+        // 10. Detect widgets, tiles and other system-bound components
+        self.detect_system_component_entry_points(graph, &mut entry_points);
+
+        // 11. Detect model classes serializers construct
+        self.detect_serialization_entry_points(graph, root, &mut entry_points)?;
+
+        // 12. Detect classes in baseline profiles and benchmark sources
+        self.detect_profile_entry_points(graph, root, &mut entry_points);
+
+        // 13. Detect Compose navigation destinations with deep links
+        self.detect_deep_link_entry_points(graph, root, &mut entry_points)?;
+
+        // 14. Detect Dagger/Hilt bindings whose type is injected
+        self.detect_binding_entry_points(graph, root, &mut entry_points);
+
+        // 15. Detect entry points from native (JNI) sources
+        if self.config.native.enabled {
+            self.detect_native_entry_points(graph, root, &mut entry_points);
+        }
+
+        // 16. Add explicitly configured entry points
         self.add_configured_entry_points(graph, &mut entry_points);
 
-        // 7. Apply retain patterns
+        // 17. Treat test sources as entry points
+        if self.config.tests_as_roots {
+            entry_points.extend(self.test_entry_points(graph));
+        }
+
+        // 18. Apply retain patterns
         self.apply_retain_patterns(graph, &mut entry_points);
+        self.cancel.check()?;
 
         info!("Detected {} entry points", entry_points.len());
 
@@ -65,7 +321,10 @@ impl<'a> EntryPointDetector<'a> {
 
     /// Detect entry points from code analysis (annotations, inheritance)
     fn detect_code_entry_points(&self, graph: &Graph, entry_points: &mut HashSet<DeclarationId>) {
-        for decl in graph.declarations() {
+        for decl in graph
+            .declarations()
+            .take_while(|_| !self.cancel.is_cancelled())
+        {
             if self.is_code_entry_point(decl) {
                 debug!(
                     "Code entry point: {} ({})",
@@ -77,20 +336,52 @@ impl<'a> EntryPointDetector<'a> {
         }
     }
 
+    /// Declarations in test source sets, which keep the code they use alive
+    /// in tests-as-roots mode
+    pub fn test_entry_points(&self, graph: &Graph) -> HashSet<DeclarationId> {
+        graph
+            .declarations()
+            .filter(|d| d.is_test_source())
+            .map(|d| d.id.clone())
+            .collect()
+    }
+
     /// Check if a declaration is an entry point based on code analysis
     fn is_code_entry_point(&self, decl: &Declaration) -> bool {
+        // Tests don't keep production code alive unless they are roots
+        // (`tests_as_roots`); test sources have their own reachability
+        // (`DC021`)
+        if decl.is_test_source() {
+            return false;
+        }
+
+        // Generated sources and layouts call into the project from outside
+        // its sources
+        if decl.kind == DeclarationKind::File {
+            return true;
+        }
+
         // Check Android components by inheritance
         if decl.is_android_entry_point() {
             return true;
         }
 
-        // Check annotations
-        for annotation in &decl.annotations {
-            if self.is_entry_point_annotation(annotation) {
-                return true;
+        // Check annotations; Dagger/Hilt bindings are entry points only when
+        // injected, whatever else (scopes, `@JvmStatic`) they are annotated with
+        let is_binding = decl.has_annotation("Provides") || decl.has_annotation("Binds");
+        if !is_binding {
+            for annotation in &decl.annotations {
+                if self.is_entry_point_annotation(annotation) {
+                    return true;
+                }
             }
         }
 
+        // Native methods are implemented, and often called, from native code
+        if decl.is_native() {
+            return true;
+        }
+
         // Check for main functions
         if decl.kind == DeclarationKind::Function && decl.name == "main" {
             return true;
@@ -129,18 +420,14 @@ impl<'a> EntryPointDetector<'a> {
             "PreviewParameter",
             // Dagger/Hilt
             "Inject",
-            "Provides",
-            "Binds",
             "BindsInstance",
             "BindsOptionalOf",
-            "Module",
             "Component",
             "Subcomponent",
             "HiltAndroidApp",
             "AndroidEntryPoint",
             "HiltViewModel",
             "EntryPoint",
-            "InstallIn",
             "Singleton",
             "Reusable",
             "ActivityScoped",
@@ -232,6 +519,9 @@ impl<'a> EntryPointDetector<'a> {
         ];
 
         for entry in &entry_annotations {
+            if *entry == "Composable" && !self.composable_entry_points {
+                continue;
+            }
             if annotation.contains(entry) {
                 return true;
             }
@@ -247,7 +537,7 @@ impl<'a> EntryPointDetector<'a> {
         root: &Path,
         entry_points: &mut HashSet<DeclarationId>,
     ) -> Result<()> {
-        let finder = FileFinder::new(self.config);
+        let finder = self.file_finder();
         let manifests = finder.find_manifests(root)?;
 
         for manifest in manifests {
@@ -255,11 +545,98 @@ impl<'a> EntryPointDetector<'a> {
             let result = self.manifest_parser.parse(&manifest.path, &contents)?;
 
             self.add_xml_references(graph, &result, entry_points);
+            self.add_startup_initializers(graph, &result.startup_initializers, entry_points);
+            self.add_intent_handlers(graph, &result.intent_filter_components, entry_points);
         }
 
         Ok(())
     }
 
+    /// Add the intent callbacks of components with intent filters: deep
+    /// links and implicit intents reach them without any call in the code
+    fn add_intent_handlers(
+        &self,
+        graph: &Graph,
+        components: &HashSet<String>,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) {
+        for name in components {
+            let Some(component) = graph.find_by_fqn(name) else {
+                continue;
+            };
+            debug!("Intent filter entry point: {}", component.name);
+            entry_points.insert(component.id.clone());
+            for child in graph.get_children(&component.id) {
+                if let Some(method) = graph.get_declaration(child) {
+                    if method.kind.is_callable() && INTENT_CALLBACKS.contains(&method.name.as_str())
+                    {
+                        entry_points.insert(method.id.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Add `androidx.startup` initializers: the `InitializationProvider`
+    /// runs each manifest-registered `Initializer`, and before it the
+    /// initializers its `dependencies()` lists, calling `create()` on each
+    fn add_startup_initializers(
+        &self,
+        graph: &Graph,
+        initializers: &HashSet<String>,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) {
+        let types = TypeResolver::new(graph);
+        let mut pending: Vec<&Declaration> = initializers
+            .iter()
+            .flat_map(|name| match graph.find_by_fqn(name) {
+                Some(decl) => vec![decl],
+                None => graph
+                    .find_by_name(name.rsplit('.').next().unwrap_or(name))
+                    .into_iter()
+                    .filter(|d| d.kind.is_type())
+                    .collect(),
+            })
+            .collect();
+        let mut seen = HashSet::new();
+
+        while let Some(initializer) = pending.pop() {
+            if !seen.insert(&initializer.id) {
+                continue;
+            }
+            debug!("Startup initializer entry point: {}", initializer.name);
+            entry_points.insert(initializer.id.clone());
+
+            for child in graph.get_children(&initializer.id) {
+                let Some(method) = graph.get_declaration(child) else {
+                    continue;
+                };
+                if !method.kind.is_callable() || !STARTUP_CALLBACKS.contains(&method.name.as_str())
+                {
+                    continue;
+                }
+                entry_points.insert(method.id.clone());
+                if method.name == "dependencies" {
+                    pending.extend(
+                        graph
+                            .get_references_from(&method.id)
+                            .into_iter()
+                            .map(|(target, _)| target)
+                            .filter(|target| {
+                                target.kind.is_type()
+                                    && inherits_any(
+                                        &types,
+                                        target,
+                                        &["Initializer"],
+                                        &mut HashSet::new(),
+                                    )
+                            }),
+                    );
+                }
+            }
+        }
+    }
+
     /// Detect entry points from layout XMLs
     fn detect_layout_entry_points(
         &self,
@@ -267,7 +644,7 @@ impl<'a> EntryPointDetector<'a> {
         root: &Path,
         entry_points: &mut HashSet<DeclarationId>,
     ) -> Result<()> {
-        let finder = FileFinder::new(self.config);
+        let finder = self.file_finder();
         let layouts = finder.find_layouts(root)?;
 
         let mut total_binding_vars = 0;
@@ -302,7 +679,7 @@ impl<'a> EntryPointDetector<'a> {
         root: &Path,
         entry_points: &mut HashSet<DeclarationId>,
     ) -> Result<()> {
-        let finder = FileFinder::new(self.config);
+        let finder = self.file_finder();
         let navigation_files = finder.find_navigation(root)?;
 
         if !navigation_files.is_empty() {
@@ -326,7 +703,7 @@ impl<'a> EntryPointDetector<'a> {
         root: &Path,
         entry_points: &mut HashSet<DeclarationId>,
     ) -> Result<()> {
-        let finder = FileFinder::new(self.config);
+        let finder = self.file_finder();
         let menu_files = finder.find_menus(root)?;
 
         if !menu_files.is_empty() {
@@ -343,162 +720,977 @@ impl<'a> EntryPointDetector<'a> {
         Ok(())
     }
 
-    /// Add entry points from XML parse results
-    fn add_xml_references(
+    /// Detect entry points from AIDL files: implementations of the generated
+    /// `Stub` with the methods binder clients call, and the hand-written
+    /// classes of declared parcelables
+    fn detect_aidl_entry_points(
         &self,
         graph: &Graph,
-        result: &XmlParseResult,
+        root: &Path,
         entry_points: &mut HashSet<DeclarationId>,
-    ) {
-        // Handle class references
-        for class_ref in &result.class_references {
-            // Try to find by fully qualified name
-            if let Some(decl) = graph.find_by_fqn(class_ref) {
-                debug!("XML entry point: {} (fqn)", decl.name);
-                entry_points.insert(decl.id.clone());
-                continue;
+    ) -> Result<()> {
+        let finder = self.file_finder();
+        let aidl_files = finder.find_aidl(root)?;
+
+        if !aidl_files.is_empty() {
+            debug!("Found {} AIDL files", aidl_files.len());
+        }
+
+        for aidl_file in aidl_files {
+            let contents = aidl_file.read_contents()?;
+            let aidl = self.aidl_parser.parse(&aidl_file.path, &contents)?;
+
+            for parcelable in &aidl.parcelables {
+                let fqn = aidl.qualified_name(parcelable);
+                let decl = graph.find_by_fqn(&fqn).or_else(|| {
+                    graph
+                        .find_by_name(parcelable)
+                        .into_iter()
+                        .find(|d| d.kind.is_type())
+                });
+                if let Some(decl) = decl {
+                    debug!("AIDL parcelable entry point: {}", decl.name);
+                    entry_points.insert(decl.id.clone());
+                }
             }
 
-            // Try to find by simple name (last component)
-            let simple_name = class_ref.split('.').next_back().unwrap_or(class_ref);
-            let candidates = graph.find_by_name(simple_name);
-            for candidate in candidates {
-                debug!("XML entry point: {} (simple)", candidate.name);
-                entry_points.insert(candidate.id.clone());
+            for interface in &aidl.interfaces {
+                let bases = interface.implementation_bases();
+                let implementations = graph.declarations().filter(|d| {
+                    d.kind.is_type()
+                        && d.super_types
+                            .iter()
+                            .any(|s| bases.iter().any(|base| extends_generated(s, base)))
+                });
+                for implementation in implementations {
+                    debug!(
+                        "AIDL entry point: {} implements {}",
+                        implementation.name, interface.name
+                    );
+                    entry_points.insert(implementation.id.clone());
+                    for child in graph.get_children(&implementation.id) {
+                        let Some(method) = graph.get_declaration(child) else {
+                            continue;
+                        };
+                        if method.kind.is_callable()
+                            && interface.methods.iter().any(|m| m.name == method.name)
+                        {
+                            entry_points.insert(method.id.clone());
+                        }
+                    }
+                }
             }
         }
 
-        // Handle method references from data binding
-        if !result.method_references.is_empty() {
-            debug!(
-                "Processing {} method references from data binding",
-                result.method_references.len()
-            );
-        }
-        for method_ref in &result.method_references {
-            debug!(
-                "Data binding method ref: {}.{}",
-                method_ref.class_fqn, method_ref.method_name
-            );
-            self.add_method_reference(graph, &method_ref.class_fqn, &method_ref.method_name, entry_points);
-        }
+        Ok(())
     }
 
-    /// Add a method reference as an entry point
-    fn add_method_reference(
+    /// Detect entry points from protobuf definitions: classes protoc
+    /// generates (used through builders and reflection) and gRPC service
+    /// implementations with the rpc methods the server dispatches to
+    fn detect_proto_entry_points(
         &self,
         graph: &Graph,
-        class_fqn: &str,
-        method_name: &str,
+        root: &Path,
         entry_points: &mut HashSet<DeclarationId>,
-    ) {
-        // Find the class first
-        let class_decl = if let Some(decl) = graph.find_by_fqn(class_fqn) {
-            Some(decl)
-        } else {
-            // Try by simple name
-            let simple_name = class_fqn.split('.').next_back().unwrap_or(class_fqn);
-            graph.find_by_name(simple_name).into_iter().next()
+    ) -> Result<()> {
+        let finder = self.file_finder();
+        let proto_files = finder.find_proto(root)?;
+
+        if !proto_files.is_empty() {
+            debug!("Found {} proto files", proto_files.len());
+        }
+
+        let mut generated: HashSet<String> = HashSet::new();
+        let mut services = Vec::new();
+        for proto_file in proto_files {
+            let contents = proto_file.read_contents()?;
+            let proto = self.proto_parser.parse(&proto_file.path, &contents)?;
+            generated.extend(proto.generated_classes());
+            services.extend(proto.services);
+        }
+
+        let protobuf = &self.config.protobuf;
+        let in_generated_dir = |decl: &Declaration| {
+            let path = decl.location.file.to_string_lossy().replace('\\', "/");
+            protobuf
+                .dirs
+                .iter()
+                .any(|dir| path.contains(&format!("/{}/", dir.trim_matches('/'))))
         };
+        for decl in graph.declarations() {
+            // Nested classes and members belong to their top-level class
+            let mut top = decl;
+            while let Some(parent) = top.parent.as_ref().and_then(|p| graph.get_declaration(p)) {
+                top = parent;
+            }
+            let Some(fqn) = top.fully_qualified_name.as_deref() else {
+                continue;
+            };
+            let in_package = protobuf.packages.iter().any(|p| {
+                fqn.strip_prefix(p.as_str())
+                    .is_some_and(|r| r.starts_with('.'))
+            });
+            if generated.contains(fqn) || in_package || in_generated_dir(decl) {
+                debug!("Protobuf entry point: {}", decl.name);
+                entry_points.insert(decl.id.clone());
+            }
+        }
 
-        if let Some(class) = class_decl {
-            // Find the method as a child of this class
-            let children = graph.get_children(&class.id);
-            for child in &children {
-                if let Some(child_decl) = graph.get_declaration(child) {
-                    if child_decl.name == method_name {
-                        debug!(
-                            "Data binding entry point: {}.{} (method)",
-                            class.name, method_name
-                        );
-                        entry_points.insert((*child).clone());
-                        return;
+        for service in &services {
+            let bases = service.implementation_bases();
+            let implementations = graph.declarations().filter(|d| {
+                d.kind.is_type()
+                    && d.super_types
+                        .iter()
+                        .any(|s| bases.iter().any(|base| extends_generated(s, base)))
+            });
+            for implementation in implementations {
+                debug!(
+                    "gRPC entry point: {} implements {}",
+                    implementation.name, service.name
+                );
+                entry_points.insert(implementation.id.clone());
+                for child in graph.get_children(&implementation.id) {
+                    let Some(method) = graph.get_declaration(child) else {
+                        continue;
+                    };
+                    if method.kind.is_callable()
+                        && service
+                            .rpcs
+                            .iter()
+                            .any(|rpc| rpc.method_name() == method.name)
+                    {
+                        entry_points.insert(method.id.clone());
                     }
                 }
             }
+        }
 
-            // Also search by name in case of inheritance or extension functions
-            let method_candidates = graph.find_by_name(method_name);
-            for candidate in method_candidates {
-                // Check if this method's parent matches the class
-                if let Some(parent_id) = &candidate.parent {
-                    if parent_id == &class.id {
-                        debug!(
-                            "Data binding entry point: {}.{} (by parent)",
-                            class.name, method_name
-                        );
-                        entry_points.insert(candidate.id.clone());
-                        return;
+        Ok(())
+    }
+
+    /// Detect WorkManager workers: WorkManager instantiates them by class
+    /// name, so work requests (`OneTimeWorkRequestBuilder<SyncWorker>()`)
+    /// and `WorkerFactory` strings are their only callers. The worker
+    /// callbacks are entry points too, as are key constants declared in a
+    /// worker and named by `workDataOf`, `Data.Builder` or `inputData` reads
+    fn detect_worker_entry_points(
+        &self,
+        graph: &Graph,
+        root: &Path,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) -> Result<()> {
+        let types = TypeResolver::new(graph);
+        let workers: HashSet<&DeclarationId> = graph
+            .declarations()
+            .filter(|d| d.kind == DeclarationKind::Class)
+            .filter(|d| extends_any(&types, d, WORKER_BASES, &mut HashSet::new()))
+            .map(|d| &d.id)
+            .collect();
+        if workers.is_empty() {
+            return Ok(());
+        }
+
+        for worker in &workers {
+            debug!("Worker entry point: {}", worker);
+            entry_points.insert((*worker).clone());
+            for child in graph.get_children(worker) {
+                if let Some(method) = graph.get_declaration(child) {
+                    if method.kind.is_callable() && WORKER_CALLBACKS.contains(&method.name.as_str())
+                    {
+                        entry_points.insert(method.id.clone());
                     }
                 }
             }
+        }
 
-            // Log at info level if we couldn't find the method
-            // Only log for methods that look like view model callbacks
-            if method_name.starts_with("on") && method_name.len() > 3 {
-                info!(
-                    "Data binding: could not find method {} in class {} (children: {})",
-                    method_name, class_fqn, children.len()
-                );
+        let mut keys = HashSet::new();
+        for file in self.file_finder().find_source_files(root)? {
+            let contents = file.read_contents()?;
+            keys.extend(worker_data_keys(&contents));
+        }
+        for decl in graph.declarations() {
+            if !matches!(
+                decl.kind,
+                DeclarationKind::Property | DeclarationKind::Field
+            ) || !keys.contains(decl.name.as_str())
+            {
+                continue;
+            }
+            // Key constants live in the worker or its companion object
+            let mut parent = decl.parent.as_ref();
+            while let Some(id) = parent {
+                if workers.contains(id) {
+                    debug!("Worker data key: {}", decl.name);
+                    entry_points.insert(decl.id.clone());
+                    break;
+                }
+                parent = graph.get_declaration(id).and_then(|p| p.parent.as_ref());
             }
-        } else {
-            // Log at info level if we couldn't find the class
-            info!(
-                "Data binding: could not find class {} for method {}",
-                class_fqn, method_name
-            );
         }
+
+        Ok(())
     }
 
-    /// Add explicitly configured entry points
-    fn add_configured_entry_points(
+    /// Detect push messaging services: classes whose superclass chain
+    /// reaches an FCM, HMS or GCM service are started by the messaging SDK
+    /// (often from a manifest in another module or flavor), which calls
+    /// their message and token callbacks
+    fn detect_push_service_entry_points(
         &self,
         graph: &Graph,
         entry_points: &mut HashSet<DeclarationId>,
     ) {
-        for entry_point in &self.config.entry_points {
-            if let Some(decl) = graph.find_by_fqn(entry_point) {
-                debug!("Configured entry point: {}", decl.name);
-                entry_points.insert(decl.id.clone());
-            } else {
-                // Try as simple name
-                for decl in graph.find_by_name(entry_point) {
-                    debug!("Configured entry point (by name): {}", decl.name);
-                    entry_points.insert(decl.id.clone());
+        let types = TypeResolver::new(graph);
+        for service in graph
+            .declarations()
+            .filter(|d| d.kind == DeclarationKind::Class)
+            .filter(|d| extends_any(&types, d, PUSH_SERVICE_BASES, &mut HashSet::new()))
+        {
+            debug!("Push service entry point: {}", service.name);
+            entry_points.insert(service.id.clone());
+            for child in graph.get_children(&service.id) {
+                if let Some(method) = graph.get_declaration(child) {
+                    if method.kind.is_callable() && PUSH_CALLBACKS.contains(&method.name.as_str()) {
+                        entry_points.insert(method.id.clone());
+                    }
                 }
             }
         }
     }
 
-    /// Apply retain patterns to mark additional entry points
-    fn apply_retain_patterns(&self, graph: &Graph, entry_points: &mut HashSet<DeclarationId>) {
-        for decl in graph.declarations() {
-            // Check config retain patterns
-            for pattern in &self.config.retain_patterns {
-                if decl.matches_pattern(pattern) {
-                    debug!("Retained by pattern '{}': {}", pattern, decl.name);
-                    entry_points.insert(decl.id.clone());
+    /// Detect app widgets, Quick Settings tiles, accessibility services,
+    /// input methods and dreams: the system instantiates them from their
+    /// manifest declaration once the user enables them, and calls their
+    /// callbacks, so a subclass of one of these bases (directly or through
+    /// project base classes) is an entry point
+    fn detect_system_component_entry_points(
+        &self,
+        graph: &Graph,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) {
+        let types = TypeResolver::new(graph);
+        for (base, callbacks) in SYSTEM_COMPONENTS {
+            for component in graph
+                .declarations()
+                .filter(|d| d.kind == DeclarationKind::Class)
+                .filter(|d| extends_any(&types, d, &[base], &mut HashSet::new()))
+            {
+                debug!("{} entry point: {}", base, component.name);
+                entry_points.insert(component.id.clone());
+                for child in graph.get_children(&component.id) {
+                    if let Some(method) = graph.get_declaration(child) {
+                        let name = method.name.as_str();
+                        if method.kind.is_callable()
+                            && (callbacks.contains(&name)
+                                || SYSTEM_LIFECYCLE_CALLBACKS.contains(&name))
+                        {
+                            entry_points.insert(method.id.clone());
+                        }
+                    }
                 }
             }
+        }
+    }
 
-            // Check Android component patterns
-            if self.config.android.auto_retain_components {
-                for pattern in &self.config.android.component_patterns {
-                    if decl.matches_pattern(pattern) {
-                        debug!("Retained by component pattern '{}': {}", pattern, decl.name);
-                        entry_points.insert(decl.id.clone());
+    /// Detect model classes only a serializer constructs or reads: types
+    /// named at kotlinx.serialization, Gson, Moshi or Jackson call sites
+    /// (`decodeFromString<Foo>()`, `TypeToken<List<Foo>>`,
+    /// `adapter(Foo::class.java)`, `Foo.serializer()`), the types of their
+    /// properties, which the serializer builds too, and the properties,
+    /// constructors and enum cases it sets through reflection
+    fn detect_serialization_entry_points(
+        &self,
+        graph: &Graph,
+        root: &Path,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) -> Result<()> {
+        let mut pending: Vec<String> = Vec::new();
+        for file in self.file_finder().find_source_files(root)? {
+            let contents = file.read_contents()?;
+            pending.extend(serialized_types(&contents));
+        }
+
+        let mut seen = HashSet::new();
+        while let Some(name) = pending.pop() {
+            for model in graph.find_by_name(&name) {
+                if !model.kind.is_type() || !seen.insert(&model.id) {
+                    continue;
+                }
+                debug!("Serialized model entry point: {}", model.name);
+                entry_points.insert(model.id.clone());
+                for child in graph.get_children(&model.id) {
+                    let Some(member) = graph.get_declaration(child) else {
+                        continue;
+                    };
+                    if !matches!(
+                        member.kind,
+                        DeclarationKind::Property
+                            | DeclarationKind::Field
+                            | DeclarationKind::Constructor
+                            | DeclarationKind::EnumCase
+                    ) {
+                        continue;
+                    }
+                    entry_points.insert(member.id.clone());
+                    if let Some(type_name) = &member.type_name {
+                        pending.extend(
+                            TYPE_NAME_PATTERN
+                                .find_iter(type_name)
+                                .map(|m| m.as_str().to_string()),
+                        );
                     }
                 }
             }
         }
+
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Detect classes and methods listed in baseline and startup profiles
+    /// (`baseline-prof.txt`), which the profile keeps compiled ahead of
+    /// time, and the code of `androidx.benchmark` benchmarks, which drives
+    /// the app from a test or benchmark module, so whatever it references
+    /// is used
+    fn detect_profile_entry_points(
+        &self,
+        graph: &Graph,
+        root: &Path,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) {
+        let mut rules = Vec::new();
+        for path in BaselineProfile::find_files(root) {
+            if let Ok(contents) = std::fs::read_to_string(&path) {
+                rules.extend(BaselineProfile::parse(&contents).rules);
+            }
+        }
 
-    #[test]
+        for rule in &rules {
+            let classes: Vec<&Declaration> = if rule.class_pattern.contains(['*', '?']) {
+                graph
+                    .declarations()
+                    .filter(|d| d.kind.is_type())
+                    .filter(|d| {
+                        d.fully_qualified_name
+                            .as_deref()
+                            .is_some_and(|fqn| class_name_matches(&rule.class_pattern, fqn))
+                    })
+                    .collect()
+            } else {
+                graph.find_by_fqn(&rule.class_pattern).into_iter().collect()
+            };
+
+            for class in classes {
+                debug!("Baseline profile entry point: {}", class.name);
+                entry_points.insert(class.id.clone());
+                let Some(method) = rule.method.as_deref() else {
+                    continue;
+                };
+                for child in graph.get_children(&class.id) {
+                    let Some(member) = graph.get_declaration(child) else {
+                        continue;
+                    };
+                    let listed = if method == "<init>" {
+                        member.kind == DeclarationKind::Constructor
+                    } else {
+                        member.kind.is_callable() && class_name_matches(method, &member.name)
+                    };
+                    if listed {
+                        entry_points.insert(member.id.clone());
+                    }
+                }
+            }
+        }
+
+        for decl in graph.declarations() {
+            let is_benchmark = graph.file_scope(&decl.location.file).is_some_and(|scope| {
+                scope
+                    .imports
+                    .iter()
+                    .any(|import| import.starts_with("androidx.benchmark."))
+            });
+            if is_benchmark {
+                debug!("Benchmark entry point: {}", decl.name);
+                entry_points.insert(decl.id.clone());
+            }
+        }
+    }
+
+    /// Detect Compose navigation destinations that declare deep links
+    /// (`deepLinks = listOf(navDeepLink { ... })`): the composables their
+    /// content calls, and the route class of type-safe destinations, are
+    /// opened from outside the app, so they are entry points even when
+    /// nothing in the app navigates to the route
+    fn detect_deep_link_entry_points(
+        &self,
+        graph: &Graph,
+        root: &Path,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) -> Result<()> {
+        let mut destinations = HashSet::new();
+        for file in self.file_finder().find_source_files(root)? {
+            let contents = file.read_contents()?;
+            if contents.contains("navDeepLink") {
+                destinations.extend(deep_link_destinations(&contents));
+            }
+        }
+
+        for name in &destinations {
+            for decl in graph.find_by_name(name) {
+                let is_screen =
+                    decl.kind == DeclarationKind::Function && decl.has_annotation("Composable");
+                if is_screen || decl.kind.is_type() {
+                    debug!("Deep link destination: {}", decl.name);
+                    entry_points.insert(decl.id.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Detect Dagger/Hilt bindings: a `@Provides` / `@Binds` method is an
+    /// entry point when Dagger injects its bound type somewhere, directly
+    /// or through another used binding (see `UnusedDaggerBindingDetector`).
+    /// Modules are reached through their used bindings; a `@Module` without
+    /// bindings (only `includes` or `subcomponents`) is an entry point itself
+    fn detect_binding_entry_points(
+        &self,
+        graph: &Graph,
+        root: &Path,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) {
+        let bindings: Vec<&Declaration> = graph
+            .declarations()
+            .filter(|d| d.kind.is_callable())
+            .filter(|d| d.has_annotation("Provides") || d.has_annotation("Binds"))
+            .collect();
+
+        let mut binding_owners = HashSet::new();
+        for binding in &bindings {
+            let mut parent = binding.parent.as_ref();
+            while let Some(id) = parent {
+                binding_owners.insert(id);
+                parent = graph.get_declaration(id).and_then(|p| p.parent.as_ref());
+            }
+        }
+        for decl in graph.declarations() {
+            if decl.has_annotation("Module") && !binding_owners.contains(&decl.id) {
+                debug!("Module without bindings: {}", decl.name);
+                entry_points.insert(decl.id.clone());
+            }
+        }
+        if bindings.is_empty() {
+            return;
+        }
+
+        // Bindings the detector couldn't parse stay entry points
+        let analysis = UnusedDaggerBindingDetector::new().analyze(root);
+        let unused: HashSet<(&Path, &str)> = analysis
+            .unused_bindings
+            .iter()
+            .map(|b| (b.file.as_path(), b.name.as_str()))
+            .collect();
+        for binding in bindings {
+            if !unused.contains(&(binding.location.file.as_path(), binding.name.as_str())) {
+                debug!("Injected binding: {}", binding.name);
+                entry_points.insert(binding.id.clone());
+            }
+        }
+    }
+
+    /// Detect entry points from C and C++ sources: classes and native
+    /// methods named by exported `Java_` symbols, classes named by
+    /// `FindClass`, and their members looked up by `GetMethodID` /
+    /// `GetFieldID` or registered through `RegisterNatives`
+    fn detect_native_entry_points(
+        &self,
+        graph: &Graph,
+        root: &Path,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) {
+        let mut references = JniReferences::default();
+        for path in self.file_finder().find_native_files(root) {
+            if let Ok(contents) = std::fs::read_to_string(&path) {
+                references.merge(self.jni_parser.parse(&contents));
+            }
+        }
+
+        for class_name in &references.classes {
+            let Some(class) = graph.find_by_fqn(class_name) else {
+                continue;
+            };
+            debug!("JNI entry point: {}", class.name);
+            entry_points.insert(class.id.clone());
+            for child in graph.get_children(&class.id) {
+                let Some(member) = graph.get_declaration(child) else {
+                    continue;
+                };
+                let native_method = references
+                    .native_methods
+                    .contains(&(class_name.clone(), member.name.to_string()));
+                if native_method || references.members.contains(member.name.as_str()) {
+                    debug!("JNI entry point: {}.{}", class.name, member.name);
+                    entry_points.insert(member.id.clone());
+                }
+            }
+        }
+    }
+
+    /// Add entry points from XML parse results
+    fn add_xml_references(
+        &self,
+        graph: &Graph,
+        result: &XmlParseResult,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) {
+        // Handle class references
+        for class_ref in &result.class_references {
+            // Try to find by fully qualified name
+            if let Some(decl) = graph.find_by_fqn(class_ref) {
+                debug!("XML entry point: {} (fqn)", decl.name);
+                entry_points.insert(decl.id.clone());
+                continue;
+            }
+
+            // Relative names (".ui.HomeFragment") are relative to the app's
+            // namespace, so any class ending with them matches
+            if class_ref.starts_with('.') {
+                let simple_name = class_ref.rsplit('.').next().unwrap_or(class_ref);
+                let matching: Vec<_> = graph
+                    .find_by_name(simple_name)
+                    .into_iter()
+                    .filter(|d| {
+                        d.fully_qualified_name
+                            .as_deref()
+                            .is_some_and(|fqn| fqn.ends_with(class_ref.as_str()))
+                    })
+                    .collect();
+                if !matching.is_empty() {
+                    for decl in matching {
+                        debug!("XML entry point: {} (relative)", decl.name);
+                        entry_points.insert(decl.id.clone());
+                    }
+                    continue;
+                }
+            }
+
+            // Try to find by simple name (last component)
+            let simple_name = class_ref.split('.').next_back().unwrap_or(class_ref);
+            let candidates = graph.find_by_name(simple_name);
+            for candidate in candidates {
+                debug!("XML entry point: {} (simple)", candidate.name);
+                entry_points.insert(candidate.id.clone());
+            }
+        }
+
+        // Handle method references from data binding
+        if !result.method_references.is_empty() {
+            debug!(
+                "Processing {} method references from data binding",
+                result.method_references.len()
+            );
+        }
+        for method_ref in &result.method_references {
+            debug!(
+                "Data binding method ref: {}.{}",
+                method_ref.class_fqn, method_ref.method_name
+            );
+            self.add_method_reference(
+                graph,
+                &method_ref.class_fqn,
+                &method_ref.method_name,
+                entry_points,
+            );
+        }
+    }
+
+    /// Add a method reference as an entry point
+    fn add_method_reference(
+        &self,
+        graph: &Graph,
+        class_fqn: &str,
+        method_name: &str,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) {
+        // Find the class first
+        let class_decl = if let Some(decl) = graph.find_by_fqn(class_fqn) {
+            Some(decl)
+        } else {
+            // Try by simple name
+            let simple_name = class_fqn.split('.').next_back().unwrap_or(class_fqn);
+            graph.find_by_name(simple_name).into_iter().next()
+        };
+
+        if let Some(class) = class_decl {
+            // Find the method as a child of this class
+            let children = graph.get_children(&class.id);
+            for child in &children {
+                if let Some(child_decl) = graph.get_declaration(child) {
+                    if child_decl.name == method_name {
+                        debug!(
+                            "Data binding entry point: {}.{} (method)",
+                            class.name, method_name
+                        );
+                        entry_points.insert((*child).clone());
+                        return;
+                    }
+                }
+            }
+
+            // Also search by name in case of inheritance or extension functions
+            let method_candidates = graph.find_by_name(method_name);
+            for candidate in method_candidates {
+                // Check if this method's parent matches the class
+                if let Some(parent_id) = &candidate.parent {
+                    if parent_id == &class.id {
+                        debug!(
+                            "Data binding entry point: {}.{} (by parent)",
+                            class.name, method_name
+                        );
+                        entry_points.insert(candidate.id.clone());
+                        return;
+                    }
+                }
+            }
+
+            // Log at info level if we couldn't find the method
+            // Only log for methods that look like view model callbacks
+            if method_name.starts_with("on") && method_name.len() > 3 {
+                info!(
+                    "Data binding: could not find method {} in class {} (children: {})",
+                    method_name,
+                    class_fqn,
+                    children.len()
+                );
+            }
+        } else {
+            // Log at info level if we couldn't find the class
+            info!(
+                "Data binding: could not find class {} for method {}",
+                class_fqn, method_name
+            );
+        }
+    }
+
+    /// Add explicitly configured entry points: named classes, and
+    /// declarations matching the configured annotation, name pattern,
+    /// supertype and file rules
+    fn add_configured_entry_points(
+        &self,
+        graph: &Graph,
+        entry_points: &mut HashSet<DeclarationId>,
+    ) {
+        let rules = &self.config.entry_points;
+        for entry_point in &rules.classes {
+            if let Some(decl) = graph.find_by_fqn(entry_point) {
+                debug!("Configured entry point: {}", decl.name);
+                entry_points.insert(decl.id.clone());
+            } else {
+                // Try as simple name
+                for decl in graph.find_by_name(entry_point) {
+                    debug!("Configured entry point (by name): {}", decl.name);
+                    entry_points.insert(decl.id.clone());
+                }
+            }
+        }
+
+        let patterns: Vec<Regex> = rules
+            .patterns
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    warn!("Ignoring invalid entry point pattern '{}': {}", pattern, e);
+                    None
+                }
+            })
+            .collect();
+        let supertypes: Vec<&str> = rules
+            .supertypes
+            .iter()
+            .map(|name| name.rsplit('.').next().unwrap_or(name))
+            .collect();
+        if rules.annotations.is_empty()
+            && patterns.is_empty()
+            && supertypes.is_empty()
+            && rules.files.is_empty()
+        {
+            return;
+        }
+
+        let types = TypeResolver::new(graph);
+        for decl in graph.declarations() {
+            if let Some(annotation) = rules.annotations.iter().find(|a| decl.has_annotation(a)) {
+                debug!("Configured entry point (@{}): {}", annotation, decl.name);
+                entry_points.insert(decl.id.clone());
+            }
+            if !patterns.is_empty() {
+                let name = qualified_name(graph, decl);
+                if patterns.iter().any(|regex| regex.is_match(&name)) {
+                    debug!("Configured entry point (pattern): {}", name);
+                    entry_points.insert(decl.id.clone());
+                }
+            }
+            if !supertypes.is_empty()
+                && decl.kind.is_type()
+                && inherits_any(&types, decl, &supertypes, &mut HashSet::new())
+            {
+                debug!("Configured entry point (supertype): {}", decl.name);
+                entry_points.insert(decl.id.clone());
+            }
+            if rules.matches_file(&decl.location.file) {
+                debug!("Configured entry point (file): {}", decl.name);
+                entry_points.insert(decl.id.clone());
+            }
+        }
+    }
+
+    /// Apply retain patterns to mark additional entry points
+    fn apply_retain_patterns(&self, graph: &Graph, entry_points: &mut HashSet<DeclarationId>) {
+        for decl in graph.declarations() {
+            // Members have no qualified name of their own; `<class pattern>.*`
+            // (`com.example.Api.*`) matches them through their class
+            let class_name = decl
+                .parent
+                .as_ref()
+                .and_then(|parent| graph.get_declaration(parent))
+                .and_then(|parent| parent.fully_qualified_name.as_deref());
+
+            // Check config retain patterns
+            for pattern in &self.config.retain_patterns {
+                let member_matches = pattern
+                    .strip_suffix(".*")
+                    .zip(class_name)
+                    .is_some_and(|(classes, class)| class_name_matches(classes, class));
+                if decl.matches_pattern(pattern) || member_matches {
+                    debug!("Retained by pattern '{}': {}", pattern, decl.name);
+                    entry_points.insert(decl.id.clone());
+                }
+            }
+
+            // Check Android component patterns
+            if self.config.android.auto_retain_components {
+                for pattern in &self.config.android.component_patterns {
+                    if decl.matches_pattern(pattern) {
+                        debug!("Retained by component pattern '{}': {}", pattern, decl.name);
+                        entry_points.insert(decl.id.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether a supertype as written (`IFoo.Stub()`, `extends com.app.IFoo.Stub`)
+/// names a generated class (`IFoo.Stub`)
+fn extends_generated(super_type: &str, generated: &str) -> bool {
+    let name = super_type
+        .trim_start_matches("extends ")
+        .trim_start_matches("implements ");
+    let name = name[..name.find(['(', '<']).unwrap_or(name.len())].trim();
+    name == generated || name.ends_with(&format!(".{}", generated))
+}
+
+/// Whether a class's superclass chain reaches one of `bases` (simple
+/// names of library classes)
+fn extends_any<'a>(
+    types: &TypeResolver<'a>,
+    decl: &'a Declaration,
+    bases: &[&str],
+    seen: &mut HashSet<&'a DeclarationId>,
+) -> bool {
+    if !seen.insert(&decl.id) {
+        return false;
+    }
+    decl.super_types.iter().any(|super_type| {
+        let name = super_type.trim();
+        let name = name.strip_prefix("extends ").unwrap_or(name);
+        let name = &name[..name.find(['(', '<']).unwrap_or(name.len())];
+        let name = name.trim().rsplit('.').next().unwrap_or(name);
+        bases.contains(&name)
+            || types
+                .resolve(super_type, decl)
+                .into_iter()
+                .filter(|d| d.kind == DeclarationKind::Class)
+                .any(|d| extends_any(types, d, bases, seen))
+    })
+}
+
+/// Whether a type inherits from one of `names` (simple names), directly or
+/// through project base types
+fn inherits_any<'a>(
+    types: &TypeResolver<'a>,
+    decl: &'a Declaration,
+    names: &[&str],
+    seen: &mut HashSet<&'a DeclarationId>,
+) -> bool {
+    if !seen.insert(&decl.id) {
+        return false;
+    }
+    decl.super_types.iter().any(|super_type| {
+        let name = super_type.trim();
+        let name = name.strip_prefix("extends ").unwrap_or(name);
+        let name = name.strip_prefix("implements ").unwrap_or(name);
+        let name = &name[..name.find(['(', '<']).unwrap_or(name.len())];
+        name.trim()
+            .rsplit('.')
+            .next()
+            .is_some_and(|name| names.contains(&name))
+            || types
+                .resolve(super_type, decl)
+                .into_iter()
+                .any(|d| inherits_any(types, d, names, seen))
+    })
+}
+
+/// Qualified name of a declaration; members, which have none of their own,
+/// are named `<class>.<member>`
+fn qualified_name(graph: &Graph, decl: &Declaration) -> String {
+    if let Some(fqn) = &decl.fully_qualified_name {
+        return fqn.to_string();
+    }
+    decl.parent
+        .as_ref()
+        .and_then(|parent| graph.get_declaration(parent))
+        .and_then(|parent| parent.fully_qualified_name.as_deref())
+        .map(|class| format!("{}.{}", class, decl.name))
+        .unwrap_or_else(|| decl.name.to_string())
+}
+
+/// Names of what Compose navigation destinations with deep links show: the
+/// PascalCase calls in their content lambda and their route type argument
+fn deep_link_destinations(contents: &str) -> HashSet<String> {
+    let mut destinations = HashSet::new();
+    for caps in DESTINATION_PATTERN.captures_iter(contents) {
+        let open = caps.get(0).unwrap().end() - 1;
+        let Some(args) = enclosed(&contents[open..]) else {
+            continue;
+        };
+        if !args.contains("navDeepLink") {
+            continue;
+        }
+        if let Some(route) = caps.get(1) {
+            let route = route.as_str();
+            destinations.insert(route.rsplit('.').next().unwrap_or(route).to_string());
+        }
+
+        // The content lambda trails the call or is its `content` argument
+        let after = &contents[open + args.len() + 2..];
+        let content = match after.trim_start().strip_prefix('{') {
+            Some(_) => enclosed(after.trim_start()).unwrap_or_default(),
+            None => args.split_once("content").map_or("", |(_, c)| c),
+        };
+        destinations.extend(
+            PASCAL_CALL_PATTERN
+                .captures_iter(content)
+                .map(|caps| caps[1].to_string()),
+        );
+    }
+    destinations
+}
+
+/// Text inside the brackets `text` opens with, up to the matching close
+fn enclosed(text: &str) -> Option<&str> {
+    let open = text.chars().next()?;
+    let close = match open {
+        '(' => ')',
+        '{' => '}',
+        '[' => ']',
+        '<' => '>',
+        _ => return None,
+    };
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(&text[1..i]);
+            }
+        }
+    }
+    None
+}
+
+/// Simple names of the types a source file hands to a serializer
+fn serialized_types(contents: &str) -> HashSet<String> {
+    let mut types = HashSet::new();
+    for m in SERIALIZER_GENERIC_PATTERN.find_iter(contents) {
+        if let Some(arguments) = enclosed(&contents[m.end() - 1..]) {
+            types.extend(
+                TYPE_NAME_PATTERN
+                    .find_iter(arguments)
+                    .map(|t| t.as_str().to_string()),
+            );
+        }
+    }
+    for m in SERIALIZER_CALL_PATTERN.find_iter(contents) {
+        if let Some(arguments) = enclosed(&contents[m.end() - 1..]) {
+            types.extend(
+                CLASS_LITERAL_PATTERN
+                    .captures_iter(arguments)
+                    .map(|caps| caps[1].to_string()),
+            );
+        }
+    }
+    types.extend(
+        SERIALIZER_OF_PATTERN
+            .captures_iter(contents)
+            .map(|caps| caps[1].to_string()),
+    );
+    types
+}
+
+/// Simple names of the keys a source file reads from or writes to worker
+/// `Data`; string literal keys are skipped
+fn worker_data_keys(contents: &str) -> HashSet<String> {
+    let last_segment = |key: &str| key.rsplit('.').next().unwrap_or(key).to_string();
+
+    let mut keys: HashSet<String> = DATA_READ_PATTERN
+        .captures_iter(contents)
+        .chain(DATA_PUT_PATTERN.captures_iter(contents))
+        .map(|caps| last_segment(&caps[1]))
+        .collect();
+
+    for (start, _) in contents.match_indices("workDataOf") {
+        let rest = &contents[start + "workDataOf".len()..];
+        let Some(open) = rest.find('(').filter(|&i| rest[..i].trim().is_empty()) else {
+            continue;
+        };
+        let mut depth = 0;
+        let mut end = rest.len();
+        for (i, c) in rest[open..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = open + i;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        keys.extend(
+            DATA_PAIR_PATTERN
+                .captures_iter(&rest[open + 1..end])
+                .map(|caps| last_segment(&caps[1])),
+        );
+    }
+
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+
+    #[test]
     fn test_is_entry_point_annotation() {
         let config = Config::default();
         let detector = EntryPointDetector::new(&config);
@@ -508,4 +1700,1070 @@ mod tests {
         assert!(detector.is_entry_point_annotation("@HiltViewModel"));
         assert!(!detector.is_entry_point_annotation("@Override"));
     }
+
+    #[test]
+    fn test_detect_fails_when_cancelled() {
+        let config = Config::default();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let temp = tempfile::TempDir::new().unwrap();
+
+        let err = EntryPointDetector::new(&config)
+            .with_cancellation(cancel)
+            .detect(&Graph::new(), temp.path())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<crate::cancel::Cancelled>(),
+            Some(&crate::cancel::Cancelled::Requested)
+        );
+    }
+
+    #[test]
+    fn test_navigation_destinations_are_entry_points() {
+        use crate::graph::{Language, Location};
+        use std::path::PathBuf;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let nav = temp.path().join("app/src/main/res/navigation/nav_main.xml");
+        std::fs::create_dir_all(nav.parent().unwrap()).unwrap();
+        std::fs::write(
+            &nav,
+            r#"<navigation xmlns:android="http://schemas.android.com/apk/res/android"
+    xmlns:app="http://schemas.android.com/apk/res-auto">
+    <dialog android:id="@+id/confirm" android:name=".ui.ConfirmDialog">
+        <argument android:name="item" app:argType="com.app.model.Item[]" />
+    </dialog>
+</navigation>"#,
+        )
+        .unwrap();
+
+        let mut graph = Graph::new();
+        let mut add = |fqn: &str, line: usize| {
+            let file = PathBuf::from("App.kt");
+            let mut decl = Declaration::new(
+                DeclarationId::new(file.clone(), line * 100, line * 100 + 50),
+                fqn.rsplit('.').next().unwrap().to_string(),
+                DeclarationKind::Class,
+                Location::new(file, line, 1, line * 100, line * 100 + 50),
+                Language::Kotlin,
+            );
+            decl.fully_qualified_name = Some(fqn.into());
+            graph.add_declaration(decl)
+        };
+        let dialog = add("com.app.ui.ConfirmDialog", 1);
+        let other = add("com.app.legacy.ConfirmDialog", 2);
+        let item = add("com.app.model.Item", 3);
+
+        let mut config = Config::default();
+        config.android.auto_retain_components = false;
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, temp.path())
+            .unwrap();
+
+        assert!(entry_points.contains(&dialog));
+        assert!(!entry_points.contains(&other));
+        assert!(entry_points.contains(&item));
+    }
+
+    #[test]
+    fn test_keep_rule_patterns_retain_classes_and_members() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/proguard-rules.pro",
+            "-keep class com.app.model.** { *; }\n-keep class com.app.Bridge\n",
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/model/User.kt",
+            "package com.app.model\n\nclass User {\n    fun toJson() {}\n}\n",
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/Bridge.kt",
+            "package com.app\n\nclass Bridge {\n    fun call() {}\n}\n\nclass Unkept\n",
+        );
+
+        let mut config = Config::default();
+        config.add_keep_rule_patterns(root);
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        assert!(is_entry("User"));
+        assert!(is_entry("toJson"));
+        assert!(is_entry("Bridge"));
+        assert!(!is_entry("call"));
+        assert!(!is_entry("Unkept"));
+    }
+
+    #[test]
+    fn test_aidl_stub_implementations_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/aidl/com/app/IPlayerService.aidl",
+            "package com.app;\ninterface IPlayerService {\n    void play(in Track track);\n}\nparcelable Track;\n",
+        );
+        write(
+            root,
+            "src/main/java/com/app/PlayerBinder.kt",
+            r#"package com.app
+
+class PlayerBinder : IPlayerService.Stub() {
+    override fun play(track: Track) {}
+    fun reset() {}
+}
+
+class Track
+"#,
+        );
+
+        let config = Config::default();
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        assert!(is_entry("PlayerBinder"));
+        assert!(is_entry("play"));
+        assert!(is_entry("Track"));
+        assert!(!is_entry("reset"));
+    }
+
+    #[test]
+    fn test_protobuf_generated_classes_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/proto/feed.proto",
+            r#"syntax = "proto3";
+package app.feed;
+option java_package = "com.app.proto";
+
+message FeedRequest { int32 page = 1; }
+message FeedItem { string id = 1; }
+
+service FeedService {
+    rpc GetFeed (FeedRequest) returns (FeedItem);
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/proto/Feed.java",
+            r#"package com.app.proto;
+
+public final class Feed {
+    public static final class FeedItem {
+        public String getId() { return ""; }
+    }
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/FeedServer.kt",
+            r#"package com.app
+
+class FeedServer : FeedServiceGrpcKt.FeedServiceCoroutineImplBase() {
+    override suspend fun getFeed(request: FeedRequest): FeedItem = TODO()
+    fun warmUp() {}
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/wire/Envelope.kt",
+            "package com.app.wire\n\nclass Envelope\n",
+        );
+
+        let mut config = Config::default();
+        config.protobuf.packages = vec!["com.app.wire".to_string()];
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        assert!(is_entry("Feed"));
+        assert!(is_entry("FeedItem"));
+        assert!(is_entry("getId"));
+        assert!(is_entry("FeedServer"));
+        assert!(is_entry("getFeed"));
+        assert!(is_entry("Envelope"));
+        assert!(!is_entry("warmUp"));
+    }
+
+    #[test]
+    fn test_workers_and_data_keys_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/java/com/app/work/UploadWorker.kt",
+            r#"package com.app.work
+
+abstract class BaseWorker(ctx: Context, params: WorkerParameters) : CoroutineWorker(ctx, params)
+
+class UploadWorker(ctx: Context, params: WorkerParameters) : BaseWorker(ctx, params) {
+    override suspend fun doWork(): Result {
+        val url = inputData.getString(KEY_URL)
+        return Result.success()
+    }
+
+    fun describe() = "upload"
+
+    companion object {
+        const val KEY_URL = "url"
+        const val KEY_RETRIES = "retries"
+        const val KEY_LEGACY = "legacy"
+    }
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/Scheduler.kt",
+            r#"package com.app
+
+fun schedule(url: String) {
+    val request = OneTimeWorkRequestBuilder<UploadWorker>()
+        .setInputData(workDataOf(UploadWorker.KEY_RETRIES to maxOf(1, 3)))
+        .build()
+}
+"#,
+        );
+
+        let config = Config::default();
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        assert!(is_entry("UploadWorker"));
+        assert!(is_entry("doWork"));
+        assert!(is_entry("KEY_URL"));
+        assert!(is_entry("KEY_RETRIES"));
+        assert!(!is_entry("describe"));
+        assert!(!is_entry("KEY_LEGACY"));
+    }
+
+    #[test]
+    fn test_startup_initializers_and_dependencies_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/AndroidManifest.xml",
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.app">
+    <application>
+        <provider android:name="androidx.startup.InitializationProvider" android:authorities="com.app.startup">
+            <meta-data android:name="com.app.init.LoggerInitializer" android:value="androidx.startup" />
+        </provider>
+    </application>
+</manifest>
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/init/Initializers.kt",
+            r#"package com.app.init
+
+class LoggerInitializer : Initializer<Logger> {
+    override fun create(context: Context): Logger = Logger()
+    override fun dependencies(): List<Class<out Initializer<*>>> = listOf(ClockInitializer::class.java)
+}
+
+class ClockInitializer : Initializer<Unit> {
+    override fun create(context: Context) {}
+    override fun dependencies(): List<Class<out Initializer<*>>> = emptyList()
+    fun reset() {}
+}
+
+class LegacyInitializer : Initializer<Unit> {
+    override fun create(context: Context) {}
+    override fun dependencies(): List<Class<out Initializer<*>>> = emptyList()
+}
+
+class Logger
+"#,
+        );
+
+        let config = Config::default();
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let entries = |class: &str| {
+            let class = graph.find_by_name(class)[0];
+            let mut members: Vec<&str> = graph
+                .get_children(&class.id)
+                .into_iter()
+                .filter(|id| entry_points.contains(*id))
+                .filter_map(|id| graph.get_declaration(id))
+                .map(|d| d.name.as_str())
+                .collect();
+            members.sort();
+            (entry_points.contains(&class.id), members)
+        };
+        assert_eq!(
+            entries("LoggerInitializer"),
+            (true, vec!["create", "dependencies"])
+        );
+        assert_eq!(
+            entries("ClockInitializer"),
+            (true, vec!["create", "dependencies"])
+        );
+        assert_eq!(entries("LegacyInitializer"), (false, vec![]));
+    }
+
+    #[test]
+    fn test_native_methods_and_jni_references_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/java/com/app/NativeBridge.kt",
+            r#"package com.app
+
+class NativeBridge {
+    external fun nativeInit()
+    fun onFrame(index: Int) {}
+    fun unusedHelper() {}
+}
+
+class Renderer {
+    fun draw() {}
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/cpp/bridge.cpp",
+            r#"#include <jni.h>
+
+extern "C" JNIEXPORT void JNICALL
+Java_com_app_NativeBridge_nativeInit(JNIEnv* env, jobject thiz) {
+    jclass bridge = env->FindClass("com/app/NativeBridge");
+    jmethodID onFrame = env->GetMethodID(bridge, "onFrame", "(I)V");
+}
+"#,
+        );
+
+        let mut config = Config::default();
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let is_entry = |entry_points: &HashSet<DeclarationId>, name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+        assert!(is_entry(&entry_points, "nativeInit"));
+        assert!(!is_entry(&entry_points, "onFrame"));
+
+        config.native.enabled = true;
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+        assert!(is_entry(&entry_points, "NativeBridge"));
+        assert!(is_entry(&entry_points, "onFrame"));
+        assert!(!is_entry(&entry_points, "unusedHelper"));
+        assert!(!is_entry(&entry_points, "Renderer"));
+    }
+
+    #[test]
+    fn test_injected_bindings_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/java/com/app/di/NetworkModule.kt",
+            r#"package com.app.di
+
+@Module
+@InstallIn(SingletonComponent::class)
+object NetworkModule {
+    @Provides
+    @Singleton
+    fun provideClient(cache: Cache): OkHttpClient = OkHttpClient(cache)
+
+    @Provides
+    fun provideCache(): Cache = Cache()
+
+    @Provides
+    @Singleton
+    fun provideLegacyApi(gson: Gson): LegacyApi = LegacyApi(gson)
+
+    @Provides
+    fun provideGson(): Gson = Gson()
+
+    @Provides
+    @IntoSet
+    fun provideInterceptor(): Interceptor = LoggingInterceptor()
+}
+
+@Module
+@InstallIn(SingletonComponent::class)
+object LegacyModule {
+    @Provides
+    fun provideLegacyClock(): LegacyClock = LegacyClock()
+}
+
+@Module(includes = [NetworkModule::class])
+@InstallIn(SingletonComponent::class)
+interface AppModule
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/data/Repository.kt",
+            r#"package com.app.data
+
+class Repository @Inject constructor(private val client: Lazy<OkHttpClient>)
+"#,
+        );
+
+        let config = Config::default();
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        assert!(is_entry("provideClient"));
+        assert!(is_entry("provideCache"));
+        assert!(is_entry("provideInterceptor"));
+        assert!(is_entry("AppModule"));
+        // Only injected into a binding nothing injects
+        assert!(!is_entry("provideLegacyApi"));
+        assert!(!is_entry("provideGson"));
+        assert!(!is_entry("provideLegacyClock"));
+        assert!(!is_entry("NetworkModule"));
+        assert!(!is_entry("LegacyModule"));
+    }
+
+    #[test]
+    fn test_intent_filters_and_deep_links_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/AndroidManifest.xml",
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android"
+    package="com.app">
+    <application>
+        <activity android:name=".LinkActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <data android:scheme="app" android:host="open" />
+            </intent-filter>
+        </activity>
+    </application>
+</manifest>
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/LinkActivity.java",
+            r#"package com.app;
+
+public class LinkActivity extends BaseActivity {
+    void onNewIntent(Intent intent) {}
+
+    void unusedHelper() {}
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/ui/AppNavHost.kt",
+            r#"package com.app.ui
+
+@Serializable
+data class Profile(val id: String)
+
+@Composable
+fun AppNavHost(navController: NavHostController) {
+    NavHost(navController, startDestination = "home") {
+        composable("home") { HomeScreen() }
+        composable(
+            route = "detail/{id}",
+            deepLinks = listOf(navDeepLink { uriPattern = "app://detail/{id}" }),
+        ) { entry ->
+            DetailScreen(entry.arguments?.getString("id"))
+        }
+        composable<Profile>(
+            deepLinks = listOf(navDeepLink<Profile>(basePath = "app://profile")),
+            content = { ProfileScreen() },
+        )
+    }
+}
+
+@Composable
+fun HomeScreen() {}
+
+@Composable
+fun DetailScreen(id: String?) {}
+
+@Composable
+fun ProfileScreen() {}
+"#,
+        );
+
+        let config = Config::default();
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .with_composable_entry_points(false)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        assert!(is_entry("LinkActivity"));
+        assert!(is_entry("onNewIntent"));
+        assert!(is_entry("DetailScreen"));
+        assert!(is_entry("ProfileScreen"));
+        assert!(is_entry("Profile"));
+        assert!(!is_entry("unusedHelper"));
+        assert!(!is_entry("HomeScreen"));
+    }
+
+    #[test]
+    fn test_push_services_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/java/com/app/push/AppPushService.kt",
+            r#"package com.app.push
+
+abstract class BasePush : com.google.firebase.messaging.FirebaseMessagingService()
+
+class AppPushService : BasePush() {
+    override fun onMessageReceived(message: RemoteMessage) {}
+
+    override fun onNewToken(token: String) {}
+
+    fun format(message: RemoteMessage) = message.data.toString()
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/push/HuaweiPush.java",
+            r#"package com.app.push;
+
+public class HuaweiPush extends HmsMessageService {
+    @Override
+    public void onMessageReceived(RemoteMessage message) {}
+}
+"#,
+        );
+
+        let config = Config::default();
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        let entries = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .filter(|d| entry_points.contains(&d.id))
+                .count()
+        };
+        assert!(is_entry("AppPushService"));
+        assert!(is_entry("HuaweiPush"));
+        assert_eq!(entries("onMessageReceived"), 2);
+        assert!(is_entry("onNewToken"));
+        assert!(!is_entry("format"));
+    }
+
+    #[test]
+    fn test_configured_entry_point_rules() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/java/com/app/plugins/Plugins.kt",
+            r#"package com.app.plugins
+
+interface Plugin
+
+abstract class BasePlugin : Plugin
+
+class SyncPlugin : BasePlugin()
+
+class ExportTask
+
+class Unrelated
+
+@Route("/home")
+class HomeScreen
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/tasks/Tasks.kt",
+            r#"package com.app.tasks
+
+class CleanupTask {
+    fun run() {}
+}
+
+class Helper
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/scripts/Script.kt",
+            r#"package com.app.scripts
+
+class Script
+"#,
+        );
+
+        let mut config: Config = serde_yaml::from_str(
+            r#"
+entry_points:
+  annotations: [Route]
+  patterns: ['Task$', 'CleanupTask\.run$', '(']
+  supertypes: [com.app.plugins.Plugin]
+  files: ["**/scripts/**"]
+"#,
+        )
+        .unwrap();
+        config.android.auto_retain_components = false;
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        assert!(is_entry("HomeScreen"));
+        assert!(is_entry("SyncPlugin"));
+        assert!(is_entry("BasePlugin"));
+        assert!(is_entry("ExportTask"));
+        assert!(is_entry("CleanupTask"));
+        assert!(is_entry("run"));
+        assert!(is_entry("Script"));
+        assert!(!is_entry("Unrelated"));
+        assert!(!is_entry("Helper"));
+    }
+
+    #[test]
+    fn test_system_components_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/java/com/app/widget/Widgets.kt",
+            r#"package com.app.widget
+
+abstract class BaseWidget : android.appwidget.AppWidgetProvider()
+
+class WeatherWidget : BaseWidget() {
+    override fun onUpdate(context: Context, manager: AppWidgetManager, ids: IntArray) {}
+
+    override fun onEnabled(context: Context) {}
+
+    fun render(context: Context) = Unit
+}
+
+class ReadingDream : DreamService() {
+    override fun onDreamingStarted() {}
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/tile/FlashlightTile.java",
+            r#"package com.app.tile;
+
+public class FlashlightTile extends TileService {
+    @Override
+    public void onClick() {}
+
+    @Override
+    public void onStartListening() {}
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/a11y/Services.kt",
+            r#"package com.app.a11y
+
+open class LoggingAccessibilityService : AccessibilityService() {
+    override fun onAccessibilityEvent(event: AccessibilityEvent) {}
+
+    override fun onInterrupt() {}
+}
+
+class ReaderService : LoggingAccessibilityService() {
+    override fun onServiceConnected() {}
+}
+
+class Keyboard : android.inputmethodservice.InputMethodService() {
+    override fun onCreateInputView(): View = TODO()
+
+    fun layoutFor(locale: Locale) = Unit
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/AndroidManifest.xml",
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android"
+    package="com.app">
+    <application>
+        <service android:name=".a11y.Keyboard"
+            android:permission="android.permission.BIND_INPUT_METHOD">
+            <meta-data android:name="android.view.im" android:resource="@xml/method" />
+        </service>
+    </application>
+</manifest>
+"#,
+        );
+
+        let config = Config::default();
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        for name in [
+            "WeatherWidget",
+            "onUpdate",
+            "onEnabled",
+            "ReadingDream",
+            "onDreamingStarted",
+            "FlashlightTile",
+            "onClick",
+            "onStartListening",
+            "ReaderService",
+            "onServiceConnected",
+            "onAccessibilityEvent",
+            "onInterrupt",
+            "Keyboard",
+            "onCreateInputView",
+        ] {
+            assert!(is_entry(name), "{name} should be an entry point");
+        }
+        assert!(!is_entry("render"));
+        assert!(!is_entry("layoutFor"));
+    }
+
+    #[test]
+    fn test_serialized_models_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "src/main/java/com/app/model/Models.kt",
+            r#"package com.app.model
+
+class Feed {
+    var title: String = ""
+    var items: List<FeedItem> = emptyList()
+}
+
+class FeedItem {
+    var id: Long = 0
+    var kind: ItemKind = ItemKind.TEXT
+}
+
+enum class ItemKind { TEXT, VIDEO }
+
+class Settings {
+    var theme: String = ""
+}
+
+class Profile {
+    var name: String = ""
+}
+
+class Draft {
+    var body: String = ""
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/model/Account.java",
+            r#"package com.app.model;
+
+public class Account {
+    public String email;
+}
+"#,
+        );
+        write(
+            root,
+            "src/main/java/com/app/data/Repository.kt",
+            r#"package com.app.data
+
+class Repository(private val gson: Gson, private val moshi: Moshi) {
+    fun feed(json: String) = Json.decodeFromString<Feed>(json)
+
+    fun settings(json: String): List<Settings> =
+        gson.fromJson(json, object : TypeToken<List<Settings>>() {}.type)
+
+    fun profile(json: String) = moshi.adapter(Profile::class.java).fromJson(json)
+
+    fun account(json: String) = Json.decodeFromString(Account.serializer(), json)
+}
+"#,
+        );
+
+        let config = Config::default();
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        for name in [
+            "Feed", "title", "items", "FeedItem", "id", "ItemKind", "VIDEO", "Settings", "theme",
+            "Profile", "name", "Account", "email",
+        ] {
+            assert!(is_entry(name), "{name} should be an entry point");
+        }
+        assert!(!is_entry("Draft"));
+        assert!(!is_entry("body"));
+    }
+
+    #[test]
+    fn test_profiles_and_benchmarks_are_entry_points() {
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/src/main/java/com/app/Startup.kt",
+            r#"package com.app
+
+class StartupCache {
+    fun warm() = Unit
+
+    fun clear() = Unit
+}
+
+class Unlisted {
+    fun run() = Unit
+}
+"#,
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/ui/Screens.kt",
+            r#"package com.app.ui
+
+class HomeScreen
+
+class DetailScreen
+"#,
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/Sorting.kt",
+            r#"package com.app
+
+class FastSorter {
+    fun sort(values: List<Int>) = values.sorted()
+}
+"#,
+        );
+        write(
+            root,
+            "app/src/main/baseline-prof.txt",
+            r#"HSPLcom/app/StartupCache;->warm()V
+Lcom/app/ui/**;
+"#,
+        );
+        write(
+            root,
+            "benchmark/src/androidTest/java/com/app/benchmark/SortBenchmark.kt",
+            r#"package com.app.benchmark
+
+import androidx.benchmark.junit4.BenchmarkRule
+import com.app.FastSorter
+
+class SortBenchmark {
+    val benchmarkRule = BenchmarkRule()
+
+    fun sort() {
+        FastSorter().sort(listOf(3, 1, 2))
+    }
+}
+"#,
+        );
+
+        let config = Config::default();
+        let mut builder = GraphBuilder::new();
+        for file in FileFinder::new(&config).find_files(root).unwrap() {
+            builder.process_file(&file).unwrap();
+        }
+        let graph = builder.build();
+        let entry_points = EntryPointDetector::new(&config)
+            .detect(&graph, root)
+            .unwrap();
+
+        let is_entry = |name: &str| {
+            graph
+                .find_by_name(name)
+                .iter()
+                .any(|d| entry_points.contains(&d.id))
+        };
+        for name in [
+            "StartupCache",
+            "warm",
+            "HomeScreen",
+            "DetailScreen",
+            "SortBenchmark",
+        ] {
+            assert!(is_entry(name), "{} should be an entry point", name);
+        }
+        for name in ["clear", "Unlisted", "run", "FastSorter"] {
+            assert!(!is_entry(name), "{} should not be an entry point", name);
+        }
+    }
 }
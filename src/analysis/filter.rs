@@ -0,0 +1,594 @@
+//! Filter expressions over findings (`--filter`)
+//!
+//! ```text
+//! rule in (DC001, DC003) && file ~ "feature/payments" && confidence >= high
+//! ```
+//!
+//! Comparisons are joined with `&&` and `||` (`&&` binds tighter), negated
+//! with `!` and grouped with parentheses. A comparison is a field, an
+//! operator and a value, bare (`high`, `DC001`, `:core:ui`) or quoted
+//! (`"feature/payments"`):
+//!
+//! - `==`, `!=` and `in (a, b, ...)` compare values; rules, kinds, scopes,
+//!   severities and confidences ignore case
+//! - `~` and `!~` match a regular expression anywhere in the value
+//! - `<`, `<=`, `>`, `>=` compare severities (info < warning < error),
+//!   confidences (low < medium < high < confirmed) and lines
+//!
+//! `owner` holds every owner of the finding's file: `owner == "@org/app"`
+//! matches findings any of whose owners is `@org/app`.
+
+use super::{Confidence, DeadCode, Severity};
+use miette::Diagnostic;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Syntax error in a filter expression, at a byte offset
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid filter at position {position}: {message}")]
+#[diagnostic(
+    code(searchdeadcode::filter),
+    help("Example: rule in (DC001, DC003) && file ~ \"feature/payments\" && confidence >= high")
+)]
+pub struct FilterError {
+    pub position: usize,
+    pub message: String,
+}
+
+/// A parsed filter expression
+#[derive(Debug, Clone)]
+pub struct FindingFilter {
+    expr: Expr,
+    /// Directory `file` values are relative to
+    base_path: Option<PathBuf>,
+}
+
+impl FindingFilter {
+    pub fn parse(source: &str) -> Result<Self, FilterError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            next: 0,
+            end: source.len(),
+        };
+        let expr = parser.or()?;
+        if let Some(token) = parser.peek() {
+            return Err(parser.error_at(token.position, "expected && or ||"));
+        }
+        Ok(Self {
+            expr,
+            base_path: None,
+        })
+    }
+
+    /// Match `file` against paths relative to this directory
+    pub fn with_base_path(mut self, path: PathBuf) -> Self {
+        self.base_path = Some(path);
+        self
+    }
+
+    pub fn matches(&self, dc: &DeadCode) -> bool {
+        self.expr.matches(dc, self.base_path.as_deref())
+    }
+}
+
+impl std::str::FromStr for FindingFilter {
+    type Err = FilterError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Test(Field, Test),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Rule,
+    Severity,
+    Confidence,
+    File,
+    Line,
+    Name,
+    Kind,
+    Module,
+    Scope,
+    Owner,
+    Author,
+    Message,
+}
+
+#[derive(Debug, Clone)]
+enum Test {
+    /// Equal to one of the values
+    In(Vec<String>),
+    NotIn(Vec<String>),
+    Matches(Regex),
+    NotMatches(Regex),
+    /// Rank of the field's value compared to a rank
+    Compare(std::cmp::Ordering, bool, usize),
+}
+
+impl Field {
+    const NAMES: &'static [(&'static str, Field)] = &[
+        ("rule", Field::Rule),
+        ("severity", Field::Severity),
+        ("confidence", Field::Confidence),
+        ("file", Field::File),
+        ("line", Field::Line),
+        ("name", Field::Name),
+        ("kind", Field::Kind),
+        ("module", Field::Module),
+        ("scope", Field::Scope),
+        ("owner", Field::Owner),
+        ("author", Field::Author),
+        ("message", Field::Message),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case(name))
+            .map(|(_, field)| *field)
+    }
+
+    /// Whether values are keywords, compared ignoring case
+    fn is_keyword(self) -> bool {
+        matches!(
+            self,
+            Field::Rule | Field::Severity | Field::Confidence | Field::Kind | Field::Scope
+        )
+    }
+
+    /// Rank of a value of an ordered field, or `None` for unordered fields
+    fn rank(self, value: &str) -> Option<Result<usize, String>> {
+        let levels: &[&str] = match self {
+            Field::Severity => &["info", "warning", "error"],
+            Field::Confidence => &["low", "medium", "high", "confirmed"],
+            Field::Line => {
+                return Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("'{}' is not a line number", value)),
+                )
+            }
+            _ => return None,
+        };
+        Some(
+            levels
+                .iter()
+                .position(|level| level.eq_ignore_ascii_case(value))
+                .ok_or_else(|| format!("expected one of {}", levels.join(", "))),
+        )
+    }
+
+    fn values(self, dc: &DeadCode, base_path: Option<&Path>) -> Vec<String> {
+        let one = |value: &str| vec![value.to_string()];
+        match self {
+            Field::Rule => one(dc.issue.code()),
+            Field::Severity => one(dc.severity.as_str()),
+            Field::Confidence => one(dc.confidence.as_str()),
+            Field::File => {
+                let file = dc.declaration.location.file.as_path();
+                let file = base_path
+                    .and_then(|base| file.strip_prefix(base).ok())
+                    .unwrap_or(file);
+                one(&file.to_string_lossy().replace('\\', "/"))
+            }
+            Field::Line => vec![dc.declaration.location.line.to_string()],
+            Field::Name => one(&dc.declaration.name),
+            Field::Kind => one(dc.declaration.kind.display_name()),
+            Field::Module => dc.module.iter().cloned().collect(),
+            Field::Scope => dc.scope.iter().map(|s| s.as_str().to_string()).collect(),
+            Field::Owner => dc.owners.clone(),
+            Field::Author => dc.last_author.iter().cloned().collect(),
+            Field::Message => one(&dc.message),
+        }
+    }
+
+    fn rank_of(self, dc: &DeadCode) -> usize {
+        match self {
+            Field::Severity => match dc.severity {
+                Severity::Info => 0,
+                Severity::Warning => 1,
+                Severity::Error => 2,
+            },
+            Field::Confidence => match dc.confidence {
+                Confidence::Low => 0,
+                Confidence::Medium => 1,
+                Confidence::High => 2,
+                Confidence::Confirmed => 3,
+            },
+            _ => dc.declaration.location.line,
+        }
+    }
+}
+
+impl Expr {
+    fn matches(&self, dc: &DeadCode, base_path: Option<&Path>) -> bool {
+        match self {
+            Expr::And(a, b) => a.matches(dc, base_path) && b.matches(dc, base_path),
+            Expr::Or(a, b) => a.matches(dc, base_path) || b.matches(dc, base_path),
+            Expr::Not(expr) => !expr.matches(dc, base_path),
+            Expr::Test(field, test) => {
+                let values = || field.values(dc, base_path);
+                let equal = |a: &str, b: &str| {
+                    if field.is_keyword() {
+                        a.eq_ignore_ascii_case(b)
+                    } else {
+                        a == b
+                    }
+                };
+                let any_in = |expected: &[String]| {
+                    values()
+                        .iter()
+                        .any(|value| expected.iter().any(|e| equal(value, e)))
+                };
+                match test {
+                    Test::In(expected) => any_in(expected),
+                    Test::NotIn(expected) => !any_in(expected),
+                    Test::Matches(regex) => values().iter().any(|v| regex.is_match(v)),
+                    Test::NotMatches(regex) => !values().iter().any(|v| regex.is_match(v)),
+                    Test::Compare(ordering, or_equal, rank) => {
+                        let actual = field.rank_of(dc).cmp(rank);
+                        actual == *ordering || (*or_equal && actual.is_eq())
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    /// Field name or bare value
+    Word(String),
+    /// Quoted value
+    Text(String),
+    Symbol(&'static str),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+const SYMBOLS: &[&str] = &[
+    "&&", "||", "==", "!=", "!~", ">=", "<=", "~", ">", "<", "!", "(", ")", ",",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(position, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, escaped)) => text.push(escaped),
+                        None => break,
+                    },
+                    Some((_, c)) => text.push(c),
+                    None => {
+                        return Err(FilterError {
+                            position,
+                            message: "unterminated string".to_string(),
+                        })
+                    }
+                }
+            }
+            tokens.push(Token {
+                kind: TokenKind::Text(text),
+                position,
+            });
+        } else if let Some(symbol) = SYMBOLS.iter().find(|s| source[position..].starts_with(**s)) {
+            for _ in 0..symbol.len() {
+                chars.next();
+            }
+            tokens.push(Token {
+                kind: TokenKind::Symbol(symbol),
+                position,
+            });
+        } else if is_word_char(c) {
+            let mut word = String::new();
+            while let Some(&(_, c)) = chars.peek().filter(|(_, c)| is_word_char(*c)) {
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token {
+                kind: TokenKind::Word(word),
+                position,
+            });
+        } else {
+            return Err(FilterError {
+                position,
+                message: format!("unexpected '{}'", c),
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+/// Characters of bare words: names, rule codes, paths, modules, owners
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || "_-.:/@$*".contains(c)
+}
+
+/// Recursive descent parser; each method parses one precedence level
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+    /// Position reported for errors at the end of the input
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        let found =
+            matches!(self.peek(), Some(Token { kind: TokenKind::Symbol(s), .. }) if *s == symbol);
+        if found {
+            self.next += 1;
+        }
+        found
+    }
+
+    fn position(&self) -> usize {
+        self.peek().map_or(self.end, |token| token.position)
+    }
+
+    fn error_at(&self, position: usize, message: impl Into<String>) -> FilterError {
+        FilterError {
+            position,
+            message: message.into(),
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), FilterError> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            Err(self.error_at(self.position(), format!("expected '{}'", symbol)))
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, FilterError> {
+        let mut expr = self.and()?;
+        while self.eat("||") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, FilterError> {
+        let mut expr = self.unary()?;
+        while self.eat("&&") {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, FilterError> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.or()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, FilterError> {
+        let position = self.position();
+        let field = match self.bump() {
+            Some(Token {
+                kind: TokenKind::Word(name),
+                ..
+            }) => Field::from_name(&name).ok_or_else(|| {
+                let fields: Vec<&str> = Field::NAMES.iter().map(|(name, _)| *name).collect();
+                self.error_at(
+                    position,
+                    format!(
+                        "unknown field '{}', expected one of {}",
+                        name,
+                        fields.join(", ")
+                    ),
+                )
+            })?,
+            _ => return Err(self.error_at(position, "expected a field name")),
+        };
+
+        let position = self.position();
+        let test = match self.bump().map(|token| token.kind) {
+            Some(TokenKind::Word(word)) if word == "in" => Test::In(self.list(field)?),
+            Some(TokenKind::Symbol("!")) if self.peek_word("in") => {
+                self.next += 1;
+                Test::NotIn(self.list(field)?)
+            }
+            Some(TokenKind::Symbol(op @ ("==" | "!="))) => {
+                let value = self.value(field)?;
+                if op == "==" {
+                    Test::In(vec![value])
+                } else {
+                    Test::NotIn(vec![value])
+                }
+            }
+            Some(TokenKind::Symbol(op @ ("~" | "!~"))) => {
+                let position = self.position();
+                let pattern = self.raw_value()?;
+                let regex = Regex::new(&pattern)
+                    .map_err(|e| self.error_at(position, format!("invalid pattern: {}", e)))?;
+                if op == "~" {
+                    Test::Matches(regex)
+                } else {
+                    Test::NotMatches(regex)
+                }
+            }
+            Some(TokenKind::Symbol(op @ ("<" | "<=" | ">" | ">="))) => {
+                let value_position = self.position();
+                let value = self.raw_value()?;
+                let rank = match field.rank(&value) {
+                    Some(rank) => rank.map_err(|message| self.error_at(value_position, message))?,
+                    None => {
+                        return Err(self.error_at(
+                            position,
+                            format!("'{}' only compares severity, confidence and line", op),
+                        ))
+                    }
+                };
+                let ordering = if op.starts_with('<') {
+                    std::cmp::Ordering::Less
+                } else {
+                    std::cmp::Ordering::Greater
+                };
+                Test::Compare(ordering, op.ends_with('='), rank)
+            }
+            _ => {
+                return Err(self.error_at(
+                    position,
+                    "expected an operator (==, !=, ~, !~, <, <=, >, >=, in)",
+                ))
+            }
+        };
+        Ok(Expr::Test(field, test))
+    }
+
+    fn peek_word(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token { kind: TokenKind::Word(w), .. }) if w == word)
+    }
+
+    /// `(value, ...)`
+    fn list(&mut self, field: Field) -> Result<Vec<String>, FilterError> {
+        self.expect("(")?;
+        let mut values = vec![self.value(field)?];
+        while self.eat(",") {
+            values.push(self.value(field)?);
+        }
+        self.expect(")")?;
+        Ok(values)
+    }
+
+    /// A value compared for equality, checked against the field's levels
+    fn value(&mut self, field: Field) -> Result<String, FilterError> {
+        let position = self.position();
+        let value = self.raw_value()?;
+        if let Some(Err(message)) = field.rank(&value) {
+            return Err(self.error_at(position, message));
+        }
+        Ok(value)
+    }
+
+    fn raw_value(&mut self) -> Result<String, FilterError> {
+        let position = self.position();
+        match self.bump().map(|token| token.kind) {
+            Some(TokenKind::Word(value) | TokenKind::Text(value)) => Ok(value),
+            _ => Err(self.error_at(position, "expected a value")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+
+    fn finding(file: &str, issue: DeadCodeIssue, confidence: Confidence) -> DeadCode {
+        let path = PathBuf::from("/repo").join(file);
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), 100, 200),
+            "Checkout",
+            DeclarationKind::Class,
+            Location::new(path, 12, 1, 100, 200),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, issue).with_confidence(confidence)
+    }
+
+    fn matches(filter: &str, dc: &DeadCode) -> bool {
+        FindingFilter::parse(filter)
+            .unwrap()
+            .with_base_path(PathBuf::from("/repo"))
+            .matches(dc)
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let payments = finding(
+            "feature/payments/Checkout.kt",
+            DeadCodeIssue::Unreferenced,
+            Confidence::High,
+        );
+        let mut feed = finding(
+            "feature/feed/Feed.kt",
+            DeadCodeIssue::UnusedImport,
+            Confidence::Low,
+        );
+        feed.owners = vec!["@org/feed".to_string(), "@alice".to_string()];
+
+        let example = r#"rule in (DC001,DC003) && file ~ "feature/payments" && confidence >= high"#;
+        assert!(matches(example, &payments));
+        assert!(!matches(example, &feed));
+
+        assert!(matches("rule == dc001", &payments));
+        assert!(matches("rule != DC001 || line > 100", &feed));
+        assert!(matches("!(rule in (DC001)) && confidence < medium", &feed));
+        assert!(matches("rule ! in (DC001)", &feed));
+        assert!(matches("file == feature/feed/Feed.kt", &feed));
+        assert!(matches(
+            "file !~ \"^feature/feed\" || name ~ Check",
+            &payments
+        ));
+        assert!(matches("owner == @alice", &feed));
+        assert!(!matches("owner == @alice", &payments));
+        assert!(matches("module != :app && severity == warning", &payments));
+        assert!(matches("line <= 12 && line >= 12", &payments));
+        // && binds tighter than ||
+        assert!(matches(
+            "rule == DC004 || rule == DC001 && line > 99",
+            &feed
+        ));
+    }
+
+    #[test]
+    fn test_filter_errors() {
+        let error = |filter: &str| FindingFilter::parse(filter).unwrap_err();
+
+        assert_eq!(error("rule ==").position, 7);
+        assert_eq!(error("colour == red").position, 0);
+        assert!(error("colour == red")
+            .message
+            .contains("unknown field 'colour'"));
+        assert_eq!(error("confidence >= hgh").position, 14);
+        assert_eq!(error("file > a").position, 5);
+        assert_eq!(error("rule in (DC001").position, 14);
+        assert_eq!(error("rule == DC001 line > 2").position, 14);
+        assert!(error("file ~ \"[\"").message.starts_with("invalid pattern"));
+        assert!(error("name == \"open").message.contains("unterminated"));
+        assert_eq!(error("name # x").position, 5);
+    }
+}
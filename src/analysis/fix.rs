@@ -0,0 +1,316 @@
+//! Quick fixes for findings with a mechanical edit
+//!
+//! Unused imports and redundant overrides are deleted with the lines they
+//! sit on. Unused parameters are cut from the parameter list together with a
+//! separating comma, their modifiers and any default value, found by parsing
+//! the file again; their call sites still have to drop the argument, which
+//! the fix description says.
+
+use super::{DeadCode, DeadCodeIssue};
+use crate::graph::Language;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A concrete edit that resolves a finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fix {
+    pub description: String,
+    /// Edits to the declaration's file, in order and not overlapping
+    pub edits: Vec<TextEdit>,
+}
+
+/// Replacement of a byte range of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextEdit {
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    fn delete(start_byte: usize, end_byte: usize) -> Self {
+        Self {
+            start_byte,
+            end_byte,
+            replacement: String::new(),
+        }
+    }
+}
+
+/// Whether findings of this issue get a [`Fix`]
+pub fn is_fixable(issue: &DeadCodeIssue) -> bool {
+    matches!(
+        issue,
+        DeadCodeIssue::UnusedImport
+            | DeadCodeIssue::UnusedParameter
+            | DeadCodeIssue::RedundantOverride
+    )
+}
+
+/// Compute the fixes of fixable findings, reading each of their files once
+///
+/// Findings whose file cannot be read, or whose byte range no longer fits
+/// the file, are left without a fix.
+pub fn attach_fixes(dead_code: &mut [DeadCode]) {
+    let mut sources: HashMap<PathBuf, Option<String>> = HashMap::new();
+    for dc in dead_code.iter_mut() {
+        if dc.fix.is_some() || !is_fixable(&dc.issue) {
+            continue;
+        }
+        let file = &dc.declaration.location.file;
+        let source = sources
+            .entry(file.to_path_buf())
+            .or_insert_with(|| std::fs::read_to_string(file.as_path()).ok());
+        if let Some(source) = source {
+            dc.fix = fix_for(dc, source);
+        }
+    }
+}
+
+/// The fix of a finding in `source`, the contents of its declaration's file
+pub fn fix_for(dc: &DeadCode, source: &str) -> Option<Fix> {
+    let location = &dc.declaration.location;
+    let (start, end) = (location.start_byte, location.end_byte);
+    if start >= end
+        || end > source.len()
+        || !source.is_char_boundary(start)
+        || !source.is_char_boundary(end)
+    {
+        return None;
+    }
+    let name = &dc.declaration.name;
+
+    let (description, edit) = match dc.issue {
+        DeadCodeIssue::UnusedImport => (
+            format!("Remove unused import '{}'", name),
+            whole_lines(source, start, end),
+        ),
+        DeadCodeIssue::RedundantOverride => (
+            format!("Remove redundant override '{}'", name),
+            whole_lines(source, start, end),
+        ),
+        DeadCodeIssue::UnusedParameter => (
+            format!(
+                "Remove unused parameter '{}' (call sites must drop the argument)",
+                name
+            ),
+            parameter(source, start, end, dc.declaration.language)?,
+        ),
+        _ => return None,
+    };
+    Some(Fix {
+        description,
+        edits: vec![edit],
+    })
+}
+
+/// Delete `start..end`, widened to its whole lines when nothing else is on
+/// them
+fn whole_lines(source: &str, start: usize, end: usize) -> TextEdit {
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[end..]
+        .find('\n')
+        .map_or(source.len(), |i| end + i + 1);
+    let blank = |s: &str| s.trim().is_empty();
+
+    if blank(&source[line_start..start]) && blank(&source[end..line_end]) {
+        TextEdit::delete(line_start, line_end)
+    } else {
+        TextEdit::delete(start, end)
+    }
+}
+
+/// Delete a parameter from its list, with its modifiers, its default value
+/// and the comma that separates it from a neighbour
+fn parameter(source: &str, start: usize, end: usize, language: Language) -> Option<TextEdit> {
+    let grammar = match language {
+        Language::Kotlin => tree_sitter_kotlin::language(),
+        Language::Java => tree_sitter_java::language(),
+        _ => return None,
+    };
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&grammar).ok()?;
+    let tree = parser.parse(source, None)?;
+
+    let mut param = tree.root_node().descendant_for_byte_range(start, end)?;
+    while (param.start_byte(), param.end_byte()) != (start, end)
+        || !matches!(
+            param.kind(),
+            "parameter" | "class_parameter" | "formal_parameter" | "spread_parameter"
+        )
+    {
+        param = param.parent()?;
+    }
+
+    // A Kotlin function parameter's modifiers precede the parameter node and
+    // its `= default` follows it, as siblings in the parameter list
+    let first = param
+        .prev_sibling()
+        .filter(|s| s.kind() == "parameter_modifiers")
+        .unwrap_or(param);
+    let last = match param.next_sibling() {
+        Some(eq) if eq.kind() == "=" => eq.next_sibling()?,
+        _ => param,
+    };
+
+    if let Some(comma) = last.next_sibling().filter(|s| s.kind() == ",") {
+        // Take the comma and the space up to the next parameter
+        let next = comma
+            .next_sibling()
+            .map_or(comma.end_byte(), |n| n.start_byte());
+        return Some(TextEdit::delete(first.start_byte(), next));
+    }
+
+    // Last parameter: take the comma before it, if any
+    match first.prev_sibling().filter(|s| s.kind() == ",") {
+        Some(comma) => Some(TextEdit::delete(comma.start_byte(), last.end_byte())),
+        None => Some(TextEdit::delete(first.start_byte(), last.end_byte())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Location};
+
+    fn finding(source: &str, text: &str, kind: DeclarationKind, issue: DeadCodeIssue) -> DeadCode {
+        let path = PathBuf::from("Main.kt");
+        let start = source.find(text).unwrap();
+        let end = start + text.len();
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, end),
+            "x",
+            kind,
+            Location::new(path, 1, 1, start, end),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, issue)
+    }
+
+    fn apply(source: &str, text: &str, kind: DeclarationKind, issue: DeadCodeIssue) -> String {
+        apply_fix(&finding(source, text, kind, issue), source)
+    }
+
+    fn apply_fix(dc: &DeadCode, source: &str) -> String {
+        let fix = fix_for(dc, source).unwrap();
+        let mut result = source.to_string();
+        for edit in fix.edits.iter().rev() {
+            result.replace_range(edit.start_byte..edit.end_byte, &edit.replacement);
+        }
+        result
+    }
+
+    fn without_param(source: &str, param: &str) -> String {
+        apply(
+            source,
+            param,
+            DeclarationKind::Parameter,
+            DeadCodeIssue::UnusedParameter,
+        )
+    }
+
+    fn without_java_param(source: &str, param: &str) -> String {
+        let mut dc = finding(
+            source,
+            param,
+            DeclarationKind::Parameter,
+            DeadCodeIssue::UnusedParameter,
+        );
+        dc.declaration.language = Language::Java;
+        apply_fix(&dc, source)
+    }
+
+    #[test]
+    fn test_parameter_fix() {
+        assert_eq!(
+            without_param("fun f(x: Int, y: Int) {}", "x: Int"),
+            "fun f(y: Int) {}"
+        );
+        assert_eq!(
+            without_param("fun f(y: Int, x: Int) {}", "x: Int"),
+            "fun f(y: Int) {}"
+        );
+        assert_eq!(without_param("fun f(x: Int) {}", "x: Int"), "fun f() {}");
+        assert_eq!(
+            without_param(
+                "fun f(\n    y: Int,\n    x: Map<String, Int> = mapOf(\"a,\" to 1)\n) {}",
+                "x: Map<String, Int>"
+            ),
+            "fun f(\n    y: Int\n) {}"
+        );
+        assert_eq!(
+            without_java_param("class A { void f(int x, int y) {} }", "int x"),
+            "class A { void f(int y) {} }"
+        );
+        assert_eq!(
+            without_param("fun f(y: Int, vararg x: Int) {}", "x: Int"),
+            "fun f(y: Int) {}"
+        );
+        assert_eq!(
+            without_param(
+                "class A(val x: Int = g(1, 2), val y: Int)",
+                "val x: Int = g(1, 2)"
+            ),
+            "class A(val y: Int)"
+        );
+    }
+
+    #[test]
+    fn test_parameter_fix_with_generic_and_char_defaults() {
+        assert_eq!(
+            without_param(
+                "fun f(m: Map<String, Int> = emptyMap<String, Int>(), y: Int) {}",
+                "m: Map<String, Int>"
+            ),
+            "fun f(y: Int) {}"
+        );
+        assert_eq!(
+            without_param("fun f(y: Int, c: Char = ')') {}", "c: Char"),
+            "fun f(y: Int) {}"
+        );
+        assert_eq!(
+            without_param("fun f(c: Char = ',', y: Int) {}", "c: Char"),
+            "fun f(y: Int) {}"
+        );
+    }
+
+    #[test]
+    fn test_whole_line_fixes() {
+        let source = "package app\n\nimport a.B\nimport a.C\n\nclass D : E() {\n    override fun g() = super.g()\n}\n";
+        assert_eq!(
+            apply(
+                source,
+                "import a.B",
+                DeclarationKind::Import,
+                DeadCodeIssue::UnusedImport
+            ),
+            "package app\n\nimport a.C\n\nclass D : E() {\n    override fun g() = super.g()\n}\n"
+        );
+        assert_eq!(
+            apply(
+                source,
+                "override fun g() = super.g()",
+                DeclarationKind::Method,
+                DeadCodeIssue::RedundantOverride
+            ),
+            "package app\n\nimport a.B\nimport a.C\n\nclass D : E() {\n}\n"
+        );
+
+        // A stale range gets no fix
+        let dc = finding(
+            source,
+            "import a.B",
+            DeclarationKind::Import,
+            DeadCodeIssue::UnusedImport,
+        );
+        assert_eq!(fix_for(&dc, "package app"), None);
+        let dc = finding(
+            source,
+            "class D",
+            DeclarationKind::Class,
+            DeadCodeIssue::Unreferenced,
+        );
+        assert_eq!(fix_for(&dc, source), None);
+    }
+}
@@ -66,7 +66,11 @@ impl HybridAnalyzer {
         // Check ProGuard data first (strongest signal)
         if let Some(ref proguard) = self.proguard {
             let class_name = decl.fully_qualified_name.as_deref();
-            if let Some(confidence_boost) = proguard.get_confidence_for(class_name, &decl.name) {
+            // usage.txt lists members under their JVM name (`@JvmName`)
+            let confidence_boost = std::iter::once(decl.name.as_str())
+                .chain(decl.alternate_names.iter().map(String::as_str))
+                .find_map(|name| proguard.get_confidence_for(class_name, name));
+            if let Some(confidence_boost) = confidence_boost {
                 if confidence_boost >= 1.0 {
                     dc.confidence = Confidence::Confirmed;
                     dc.message = format!("{} (confirmed by R8/ProGuard)", dc.message);
@@ -145,7 +149,7 @@ impl HybridAnalyzer {
         }
 
         // Try variations of the name
-        let simple_name = &decl.name;
+        let simple_name = decl.name.as_str();
         if coverage
             .covered_classes
             .iter()
@@ -166,7 +170,7 @@ impl HybridAnalyzer {
 
     fn check_method_coverage(&self, decl: &Declaration, coverage: &CoverageData) -> CoverageStatus {
         // Use fully qualified name if available
-        if let Some(fqn) = &decl.fully_qualified_name {
+        if let Some(fqn) = decl.fully_qualified_name.as_deref() {
             if coverage.covered_methods.contains(fqn) {
                 return CoverageStatus::Executed;
             }
@@ -176,7 +180,7 @@ impl HybridAnalyzer {
         }
 
         // Try just the method name for top-level functions or partial matches
-        let method_name = &decl.name;
+        let method_name = decl.name.as_str();
         if coverage
             .covered_methods
             .iter()
@@ -217,8 +221,9 @@ impl HybridAnalyzer {
     fn build_class_fqn(&self, decl: &Declaration) -> String {
         // Use fully qualified name if available, otherwise just the name
         decl.fully_qualified_name
-            .clone()
-            .unwrap_or_else(|| decl.name.clone())
+            .as_deref()
+            .unwrap_or(&decl.name)
+            .to_string()
     }
 
     fn estimate_confidence(&self, decl: &Declaration) -> Confidence {
@@ -356,4 +361,5 @@ mod tests {
         assert_eq!(enhanced[0].confidence, Confidence::Confirmed);
         assert!(enhanced[0].runtime_confirmed);
     }
+
 }
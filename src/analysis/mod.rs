@@ -6,25 +6,36 @@ mod deep;
 pub mod detectors;
 mod enhanced;
 mod entry_points;
+mod filter;
+mod fix;
 mod hybrid;
 mod reachability;
 pub mod resources;
 
-pub use cycles::CycleDetector;
+pub use cycles::{CycleDetector, CycleInfo};
 pub use deep::DeepAnalyzer;
 pub use enhanced::EnhancedAnalyzer;
 pub use entry_points::EntryPointDetector;
+#[allow(unused_imports)] // FilterError is only named by library consumers
+pub use filter::{FilterError, FindingFilter};
+#[allow(unused_imports)] // Some are only named by library consumers
+pub use fix::{attach_fixes, fix_for, is_fixable, Fix, TextEdit};
 pub use hybrid::HybridAnalyzer;
 pub use reachability::ReachabilityAnalyzer;
 pub use resources::ResourceDetector;
 
-use crate::graph::Declaration;
+use crate::graph::{Declaration, DeclarationId, Graph, Location};
+use detectors::DetectorMetadata;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
 
 /// Confidence level for dead code detection
 ///
 /// Combines static analysis with optional runtime coverage data
 /// to provide confidence scores for dead code findings.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Confidence {
     /// Low confidence - static analysis only, may have dynamic dispatch
     Low,
@@ -63,8 +74,26 @@ impl std::fmt::Display for Confidence {
     }
 }
 
+impl std::str::FromStr for Confidence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(Confidence::Low),
+            "medium" => Ok(Confidence::Medium),
+            "high" => Ok(Confidence::High),
+            "confirmed" => Ok(Confidence::Confirmed),
+            _ => Err(format!(
+                "unknown confidence '{}' (expected low, medium, high or confirmed)",
+                s
+            )),
+        }
+    }
+}
+
 /// Represents a piece of dead code detected by analysis
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct DeadCode {
     /// The declaration that is dead/unused
     pub declaration: Declaration,
@@ -83,12 +112,67 @@ pub struct DeadCode {
 
     /// Whether runtime coverage data confirmed this is unused
     pub runtime_confirmed: bool,
+
+    /// Gradle module the declaration belongs to (e.g., ":core:ui")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+
+    /// Whether the declaration is unused in every module or only in its
+    /// own; set by per-module analysis ([`analyze_per_module`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<ModuleScope>,
+
+    /// Owners of the declaration's file in CODEOWNERS; set with `--owners`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+
+    /// Author who last changed the declaration, from `git blame`; set with
+    /// `--blame`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_author: Option<String>,
+
+    /// Other places that explain the finding, such as the assignments of a
+    /// property that is never read
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedLocation>,
+
+    /// Edit that resolves the finding, for unused imports, unused parameters
+    /// and redundant overrides ([`attach_fixes`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+}
+
+/// A location related to a finding, with what happens there
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedLocation {
+    pub location: Location,
+    pub message: String,
+}
+
+/// Where a declaration is unused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ModuleScope {
+    /// Nothing reaches the declaration, in any module
+    AllModules,
+    /// Other modules use the declaration, but nothing in its own module does
+    OwnModule,
+}
+
+impl ModuleScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModuleScope::AllModules => "all-modules",
+            ModuleScope::OwnModule => "own-module",
+        }
+    }
 }
 
 impl DeadCode {
     pub fn new(declaration: Declaration, issue: DeadCodeIssue) -> Self {
         let severity = issue.default_severity();
         let message = issue.default_message(&declaration);
+        let module = declaration.module.as_deref().map(str::to_string);
 
         Self {
             declaration,
@@ -97,6 +181,12 @@ impl DeadCode {
             confidence: Confidence::Medium, // Default for static-only analysis
             message,
             runtime_confirmed: false,
+            module,
+            scope: None,
+            owners: Vec::new(),
+            last_author: None,
+            related: Vec::new(),
+            fix: None,
         }
     }
 
@@ -115,6 +205,24 @@ impl DeadCode {
         self
     }
 
+    pub fn with_module(mut self, module: String) -> Self {
+        self.module = Some(module);
+        self
+    }
+
+    pub fn with_related(mut self, location: Location, message: impl Into<String>) -> Self {
+        self.related.push(RelatedLocation {
+            location,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
     pub fn with_runtime_confirmed(mut self, confirmed: bool) -> Self {
         self.runtime_confirmed = confirmed;
         if confirmed {
@@ -124,8 +232,113 @@ impl DeadCode {
     }
 }
 
+/// Lower findings on declarations named in a string literal to
+/// [`Confidence::Low`]
+///
+/// `Class.forName("com.app.Plugin")` or `getMethod("load")` may load the
+/// declaration by reflection, which no reference in the graph shows. Findings
+/// runtime coverage confirmed are left alone.
+pub fn downgrade_string_referenced(graph: &Graph, dead_code: &mut [DeadCode]) {
+    for dc in dead_code {
+        if dc.runtime_confirmed || dc.confidence == Confidence::Low {
+            continue;
+        }
+        if !graph.string_references_to(&dc.declaration.id).is_empty() {
+            dc.confidence = Confidence::Low;
+            dc.message
+                .push_str(" (named in a string literal, may be loaded by reflection)");
+        }
+    }
+}
+
+/// Turn findings that only tests keep alive into
+/// [`DeadCodeIssue::TestOnlyCode`]
+///
+/// `dead_with_tests` holds the findings of the same analysis run with test
+/// declarations as additional entry points
+/// ([`EntryPointDetector::test_entry_points`]); production declarations it
+/// no longer reports are reached from tests alone.
+pub fn mark_test_only(dead_code: &mut [DeadCode], dead_with_tests: &[DeadCode]) {
+    let still_dead: HashSet<&DeclarationId> = dead_with_tests
+        .iter()
+        .map(|dc| &dc.declaration.id)
+        .collect();
+    for dc in dead_code {
+        if dc.declaration.is_test_source() || still_dead.contains(&dc.declaration.id) {
+            continue;
+        }
+        dc.issue = DeadCodeIssue::TestOnlyCode;
+        dc.severity = dc.issue.default_severity();
+        dc.message = dc.issue.default_message(&dc.declaration);
+    }
+}
+
+/// Analyze each module on its own and report the declarations only other
+/// modules use
+///
+/// `analyze` is the whole-project analysis; it is run again on each module's
+/// partition ([`Graph::module_partition`]) with the entry points the module
+/// declares. What a module leaves unreachable although the whole-project
+/// analysis reached it, and another module references, is used from other
+/// modules alone: it is added as a [`ModuleScope::OwnModule`] finding with
+/// [`Severity::Info`]. The whole-project findings are marked
+/// [`ModuleScope::AllModules`]. Modules declaring no entry point, such as
+/// libraries, are skipped: their code is there for other modules.
+pub fn analyze_per_module<F>(
+    graph: &Graph,
+    entry_points: &HashSet<DeclarationId>,
+    dead_code: &mut Vec<DeadCode>,
+    analyze: F,
+) where
+    F: Fn(&Graph, &HashSet<DeclarationId>) -> (Vec<DeadCode>, HashSet<DeclarationId>),
+{
+    let index = graph.module_index();
+    let reported: HashSet<DeclarationId> = dead_code
+        .iter()
+        .map(|dc| dc.declaration.id.clone())
+        .collect();
+    for dc in dead_code.iter_mut() {
+        if dc.declaration.module.is_some() {
+            dc.scope = Some(ModuleScope::AllModules);
+        }
+    }
+
+    for module in index.modules() {
+        let module_entry_points: HashSet<DeclarationId> = index
+            .declarations_in(module)
+            .iter()
+            .filter(|id| entry_points.contains(*id))
+            .cloned()
+            .collect();
+        if module_entry_points.is_empty() {
+            continue;
+        }
+        let (module_dead, _) = analyze(&graph.module_partition(module), &module_entry_points);
+        for mut dc in module_dead {
+            let id = &dc.declaration.id;
+            if reported.contains(id) || !index.is_referenced_from_other_modules(id) {
+                continue;
+            }
+            let users: Vec<&str> = index.referencing_modules(id).collect();
+            dc.message = format!(
+                "{} '{}' is only used from other modules ({})",
+                dc.declaration.kind.display_name(),
+                dc.declaration.name,
+                users.join(", ")
+            );
+            dc.severity = Severity::Info;
+            dc.scope = Some(ModuleScope::OwnModule);
+            dead_code.push(dc);
+        }
+    }
+}
+
 /// Types of dead code issues
+///
+/// Serializes as the rule code (`"DC001"`); custom rules serialize as their
+/// [`DetectorMetadata`] object.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum DeadCodeIssue {
     /// Declaration is never referenced
     Unreferenced,
@@ -175,6 +388,73 @@ pub enum DeadCodeIssue {
     /// Using size == 0 instead of isEmpty()
     PreferIsEmpty,
 
+    /// Dagger/Hilt binding or module whose type is never injected
+    UnusedBinding,
+
+    /// BroadcastReceiver neither declared in a manifest nor registered at runtime
+    UnregisteredReceiver,
+
+    /// `const val` that is never referenced
+    UnusedConstant,
+
+    /// Extension function or property with no call sites
+    UnusedExtension,
+
+    /// Test utility, fixture or base test class that no test reaches
+    UnusedTestHelper,
+
+    /// `@Preview` of a removed, unused or deprecated composable
+    StalePreview,
+
+    /// Hand-written Parcelable implementation on a `@Parcelize` class or a
+    /// class never sent through a Parcel
+    LegacyParcelable,
+
+    /// Room entity column that is written but never queried
+    WriteOnlyColumn,
+
+    /// Defaulted parameter that no call site overrides
+    AlwaysDefaultParam,
+
+    /// Test class whose production subject no longer exists
+    OrphanTest,
+
+    /// Callback interface whose methods are never invoked
+    DeadCallback,
+
+    /// Typealias that is never referenced
+    UnusedTypeAlias,
+
+    /// Gradle version catalog entry that no build script uses
+    UnusedCatalogEntry,
+
+    /// Production code that only tests reach
+    TestOnlyCode,
+
+    /// Custom intent action sent but never handled, or handled but never sent
+    UnmatchedIntentAction,
+
+    /// WorkManager worker that no work request schedules
+    NeverEnqueuedWorker,
+
+    /// Koin definition whose type is never injected
+    UnusedKoinDefinition,
+
+    /// Preferences DataStore key written in `edit {}` but never read
+    WriteOnlyDataStoreKey,
+
+    /// Dynamic feature module that no code installs or opens
+    UnusedDynamicFeature,
+
+    /// Consumer keep rule for a class the library module no longer declares
+    StaleConsumerRule,
+
+    /// AIDL interface method that no client calls
+    UnusedAidlMethod,
+
+    /// Protobuf message that no Kotlin or Java code builds or parses
+    UnusedProtoMessage,
+
     // ==========================================================================
     // Anti-Pattern Detectors (inspired by common Android code smells)
     // ==========================================================================
@@ -300,9 +580,96 @@ pub enum DeadCodeIssue {
 
     /// NavController passed to child composables (tight coupling)
     NavControllerPassing,
+
+    /// Finding from a detector registered outside this crate
+    Custom(DetectorMetadata),
 }
 
 impl DeadCodeIssue {
+    /// Every built-in issue kind, in rule code order
+    pub const BUILTIN: &'static [DeadCodeIssue] = &[
+        DeadCodeIssue::Unreferenced,
+        DeadCodeIssue::AssignOnly,
+        DeadCodeIssue::UnusedParameter,
+        DeadCodeIssue::UnusedImport,
+        DeadCodeIssue::UnusedEnumCase,
+        DeadCodeIssue::RedundantPublic,
+        DeadCodeIssue::DeadBranch,
+        DeadCodeIssue::UnusedSealedVariant,
+        DeadCodeIssue::RedundantOverride,
+        DeadCodeIssue::WriteOnlyPreference,
+        DeadCodeIssue::WriteOnlyDao,
+        DeadCodeIssue::DuplicateImport,
+        DeadCodeIssue::RedundantNullInit,
+        DeadCodeIssue::RedundantThis,
+        DeadCodeIssue::RedundantParentheses,
+        DeadCodeIssue::PreferIsEmpty,
+        DeadCodeIssue::UnusedBinding,
+        DeadCodeIssue::UnregisteredReceiver,
+        DeadCodeIssue::UnusedConstant,
+        DeadCodeIssue::UnusedExtension,
+        DeadCodeIssue::UnusedTestHelper,
+        DeadCodeIssue::StalePreview,
+        DeadCodeIssue::LegacyParcelable,
+        DeadCodeIssue::WriteOnlyColumn,
+        DeadCodeIssue::AlwaysDefaultParam,
+        DeadCodeIssue::OrphanTest,
+        DeadCodeIssue::DeadCallback,
+        DeadCodeIssue::UnusedTypeAlias,
+        DeadCodeIssue::UnusedCatalogEntry,
+        DeadCodeIssue::TestOnlyCode,
+        DeadCodeIssue::UnmatchedIntentAction,
+        DeadCodeIssue::NeverEnqueuedWorker,
+        DeadCodeIssue::UnusedKoinDefinition,
+        DeadCodeIssue::WriteOnlyDataStoreKey,
+        DeadCodeIssue::UnusedDynamicFeature,
+        DeadCodeIssue::StaleConsumerRule,
+        DeadCodeIssue::UnusedAidlMethod,
+        DeadCodeIssue::UnusedProtoMessage,
+        DeadCodeIssue::GlobalMutableState,
+        DeadCodeIssue::DeepInheritance,
+        DeadCodeIssue::SingleImplInterface,
+        DeadCodeIssue::EventBusPattern,
+        DeadCodeIssue::LegacyDependency,
+        DeadCodeIssue::ExcessiveFeatureToggles,
+        DeadCodeIssue::HeavyViewModel,
+        DeadCodeIssue::GlobalScopeUsage,
+        DeadCodeIssue::LateinitAbuse,
+        DeadCodeIssue::ScopeFunctionChaining,
+        DeadCodeIssue::MemoryLeakRisk,
+        DeadCodeIssue::LongMethod,
+        DeadCodeIssue::LargeClass,
+        DeadCodeIssue::CollectionWithoutSequence,
+        DeadCodeIssue::ObjectAllocationInLoop,
+        DeadCodeIssue::MutableStateExposed,
+        DeadCodeIssue::ViewLogicInViewModel,
+        DeadCodeIssue::MissingUseCase,
+        DeadCodeIssue::NestedCallback,
+        DeadCodeIssue::HardcodedDispatcher,
+        DeadCodeIssue::NullabilityOverload,
+        DeadCodeIssue::ReflectionOveruse,
+        DeadCodeIssue::LongParameterList,
+        DeadCodeIssue::ComplexCondition,
+        DeadCodeIssue::StringLiteralDuplication,
+        DeadCodeIssue::UnclosedResource,
+        DeadCodeIssue::MainThreadDatabase,
+        DeadCodeIssue::WakeLockAbuse,
+        DeadCodeIssue::AsyncTaskUsage,
+        DeadCodeIssue::InitOnDraw,
+        DeadCodeIssue::StateWithoutRemember,
+        DeadCodeIssue::LaunchedEffectWithoutKey,
+        DeadCodeIssue::BusinessLogicInComposable,
+        DeadCodeIssue::NavControllerPassing,
+    ];
+
+    /// Look up a built-in issue kind by rule code (case-insensitive)
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::BUILTIN
+            .iter()
+            .copied()
+            .find(|issue| issue.code().eq_ignore_ascii_case(code))
+    }
+
     pub fn default_severity(&self) -> Severity {
         match self {
             DeadCodeIssue::Unreferenced => Severity::Warning,
@@ -321,6 +688,28 @@ impl DeadCodeIssue {
             DeadCodeIssue::RedundantThis => Severity::Info,
             DeadCodeIssue::RedundantParentheses => Severity::Info,
             DeadCodeIssue::PreferIsEmpty => Severity::Info,
+            DeadCodeIssue::UnusedBinding => Severity::Warning,
+            DeadCodeIssue::UnregisteredReceiver => Severity::Warning,
+            DeadCodeIssue::UnusedConstant => Severity::Warning,
+            DeadCodeIssue::UnusedExtension => Severity::Warning,
+            DeadCodeIssue::UnusedTestHelper => Severity::Info,
+            DeadCodeIssue::StalePreview => Severity::Warning,
+            DeadCodeIssue::LegacyParcelable => Severity::Warning,
+            DeadCodeIssue::WriteOnlyColumn => Severity::Warning,
+            DeadCodeIssue::AlwaysDefaultParam => Severity::Info,
+            DeadCodeIssue::OrphanTest => Severity::Info,
+            DeadCodeIssue::DeadCallback => Severity::Warning,
+            DeadCodeIssue::UnusedTypeAlias => Severity::Warning,
+            DeadCodeIssue::UnusedCatalogEntry => Severity::Warning,
+            DeadCodeIssue::TestOnlyCode => Severity::Warning,
+            DeadCodeIssue::UnmatchedIntentAction => Severity::Warning,
+            DeadCodeIssue::NeverEnqueuedWorker => Severity::Warning,
+            DeadCodeIssue::UnusedKoinDefinition => Severity::Warning,
+            DeadCodeIssue::WriteOnlyDataStoreKey => Severity::Warning,
+            DeadCodeIssue::UnusedDynamicFeature => Severity::Warning,
+            DeadCodeIssue::StaleConsumerRule => Severity::Info,
+            DeadCodeIssue::UnusedAidlMethod => Severity::Warning,
+            DeadCodeIssue::UnusedProtoMessage => Severity::Warning,
             DeadCodeIssue::GlobalMutableState => Severity::Warning,
             DeadCodeIssue::DeepInheritance => Severity::Warning,
             DeadCodeIssue::SingleImplInterface => Severity::Info,
@@ -355,6 +744,7 @@ impl DeadCodeIssue {
             DeadCodeIssue::LaunchedEffectWithoutKey => Severity::Warning,
             DeadCodeIssue::BusinessLogicInComposable => Severity::Warning,
             DeadCodeIssue::NavControllerPassing => Severity::Info,
+            DeadCodeIssue::Custom(metadata) => metadata.default_severity,
         }
     }
 
@@ -433,6 +823,91 @@ impl DeadCodeIssue {
                     decl.name
                 )
             }
+            DeadCodeIssue::UnusedBinding => {
+                format!(
+                    "Binding '{}' provides a type that is never injected",
+                    decl.name
+                )
+            }
+            DeadCodeIssue::UnregisteredReceiver => {
+                format!(
+                    "Receiver '{}' is never declared in a manifest or registered",
+                    decl.name
+                )
+            }
+            DeadCodeIssue::UnusedConstant => {
+                format!("Constant '{}' is never referenced", decl.name)
+            }
+            DeadCodeIssue::UnusedExtension => {
+                format!("Extension '{}' is never used", decl.name)
+            }
+            DeadCodeIssue::UnusedTestHelper => {
+                format!("Test helper '{}' is not used by any test", decl.name)
+            }
+            DeadCodeIssue::StalePreview => {
+                format!("Preview '{}' is stale", decl.name)
+            }
+            DeadCodeIssue::LegacyParcelable => {
+                format!("Parcelable boilerplate '{}' can be removed", decl.name)
+            }
+            DeadCodeIssue::WriteOnlyColumn => {
+                format!("Column '{}' is written but never queried", decl.name)
+            }
+            DeadCodeIssue::AlwaysDefaultParam => {
+                format!("Parameter '{}' always uses its default value", decl.name)
+            }
+            DeadCodeIssue::OrphanTest => {
+                format!("Test class '{}' has no production code to test", decl.name)
+            }
+            DeadCodeIssue::DeadCallback => {
+                format!("Callback '{}' is never invoked", decl.name)
+            }
+            DeadCodeIssue::UnusedTypeAlias => {
+                format!("Type alias '{}' is never used", decl.name)
+            }
+            DeadCodeIssue::UnusedCatalogEntry => {
+                format!(
+                    "Version catalog entry '{}' is not used by any build script",
+                    decl.name
+                )
+            }
+            DeadCodeIssue::TestOnlyCode => {
+                format!(
+                    "{} '{}' is only used by tests",
+                    decl.kind.display_name(),
+                    decl.name
+                )
+            }
+            DeadCodeIssue::UnmatchedIntentAction => {
+                format!(
+                    "Intent action '{}' has no matching sender or handler",
+                    decl.name
+                )
+            }
+            DeadCodeIssue::NeverEnqueuedWorker => {
+                format!("Worker '{}' is never enqueued", decl.name)
+            }
+            DeadCodeIssue::UnusedKoinDefinition => {
+                format!("Koin definition '{}' is never injected", decl.name)
+            }
+            DeadCodeIssue::WriteOnlyDataStoreKey => {
+                format!("DataStore key '{}' is written but never read", decl.name)
+            }
+            DeadCodeIssue::UnusedDynamicFeature => {
+                format!(
+                    "Dynamic feature module '{}' is never requested or launched",
+                    decl.name
+                )
+            }
+            DeadCodeIssue::StaleConsumerRule => {
+                format!("Keep rule for '{}' matches no class in the module", decl.name)
+            }
+            DeadCodeIssue::UnusedAidlMethod => {
+                format!("AIDL method '{}' is never called by a client", decl.name)
+            }
+            DeadCodeIssue::UnusedProtoMessage => {
+                format!("Proto message '{}' is never built or parsed", decl.name)
+            }
             DeadCodeIssue::GlobalMutableState => {
                 format!(
                     "Object '{}' has mutable public properties (global mutable state is an anti-pattern)",
@@ -637,6 +1112,7 @@ impl DeadCodeIssue {
                     decl.name
                 )
             }
+            DeadCodeIssue::Custom(metadata) => format!("{}: '{}'", metadata.name, decl.name),
         }
     }
 
@@ -658,6 +1134,28 @@ impl DeadCodeIssue {
             DeadCodeIssue::RedundantThis => "DC014",
             DeadCodeIssue::RedundantParentheses => "DC015",
             DeadCodeIssue::PreferIsEmpty => "DC016",
+            DeadCodeIssue::UnusedBinding => "DC017",
+            DeadCodeIssue::UnregisteredReceiver => "DC018",
+            DeadCodeIssue::UnusedConstant => "DC019",
+            DeadCodeIssue::UnusedExtension => "DC020",
+            DeadCodeIssue::UnusedTestHelper => "DC021",
+            DeadCodeIssue::StalePreview => "DC022",
+            DeadCodeIssue::LegacyParcelable => "DC023",
+            DeadCodeIssue::WriteOnlyColumn => "DC024",
+            DeadCodeIssue::AlwaysDefaultParam => "DC025",
+            DeadCodeIssue::OrphanTest => "DC026",
+            DeadCodeIssue::DeadCallback => "DC027",
+            DeadCodeIssue::UnusedTypeAlias => "DC028",
+            DeadCodeIssue::UnusedCatalogEntry => "DC029",
+            DeadCodeIssue::TestOnlyCode => "DC030",
+            DeadCodeIssue::UnmatchedIntentAction => "DC031",
+            DeadCodeIssue::NeverEnqueuedWorker => "DC032",
+            DeadCodeIssue::UnusedKoinDefinition => "DC033",
+            DeadCodeIssue::WriteOnlyDataStoreKey => "DC034",
+            DeadCodeIssue::UnusedDynamicFeature => "DC035",
+            DeadCodeIssue::StaleConsumerRule => "DC036",
+            DeadCodeIssue::UnusedAidlMethod => "DC037",
+            DeadCodeIssue::UnusedProtoMessage => "DC038",
             DeadCodeIssue::GlobalMutableState => "AP001",
             DeadCodeIssue::DeepInheritance => "AP002",
             DeadCodeIssue::SingleImplInterface => "AP003",
@@ -692,12 +1190,250 @@ impl DeadCodeIssue {
             DeadCodeIssue::LaunchedEffectWithoutKey => "AP032",
             DeadCodeIssue::BusinessLogicInComposable => "AP033",
             DeadCodeIssue::NavControllerPassing => "AP034",
+            DeadCodeIssue::Custom(metadata) => metadata.code,
+        }
+    }
+
+    /// Short plural description used when grouping findings by rule
+    pub fn description(&self) -> &'static str {
+        match self {
+            // Dead code issues
+            DeadCodeIssue::Unreferenced => "Unreferenced declarations",
+            DeadCodeIssue::UnusedImport => "Unused imports",
+            DeadCodeIssue::UnusedParameter => "Unused parameters",
+            DeadCodeIssue::AssignOnly => "Assign-only variables",
+            DeadCodeIssue::DeadBranch => "Dead branches",
+            DeadCodeIssue::RedundantOverride => "Redundant overrides",
+            DeadCodeIssue::RedundantPublic => "Redundant public modifiers",
+            DeadCodeIssue::UnusedEnumCase => "Unused enum cases",
+            DeadCodeIssue::UnusedSealedVariant => "Unused sealed variants",
+            DeadCodeIssue::WriteOnlyPreference => "Write-only preferences",
+            DeadCodeIssue::WriteOnlyDao => "Write-only DAOs",
+            DeadCodeIssue::DuplicateImport => "Duplicate imports",
+            DeadCodeIssue::RedundantNullInit => "Redundant null init",
+            DeadCodeIssue::RedundantThis => "Redundant this",
+            DeadCodeIssue::RedundantParentheses => "Redundant parentheses",
+            DeadCodeIssue::PreferIsEmpty => "Prefer isEmpty()",
+            DeadCodeIssue::UnusedBinding => "Unused DI bindings",
+            DeadCodeIssue::UnregisteredReceiver => "Unregistered receivers",
+            DeadCodeIssue::UnusedConstant => "Unused constants",
+            DeadCodeIssue::UnusedExtension => "Unused extensions",
+            DeadCodeIssue::UnusedTestHelper => "Unused test helpers",
+            DeadCodeIssue::StalePreview => "Stale previews",
+            DeadCodeIssue::LegacyParcelable => "Legacy Parcelable boilerplate",
+            DeadCodeIssue::WriteOnlyColumn => "Write-only columns",
+            DeadCodeIssue::AlwaysDefaultParam => "Always-default parameters",
+            DeadCodeIssue::OrphanTest => "Orphan tests",
+            DeadCodeIssue::DeadCallback => "Dead callbacks",
+            DeadCodeIssue::UnusedTypeAlias => "Unused type aliases",
+            DeadCodeIssue::UnusedCatalogEntry => "Unused version catalog entries",
+            DeadCodeIssue::TestOnlyCode => "Test-only code",
+            DeadCodeIssue::UnmatchedIntentAction => "Unmatched intent actions",
+            DeadCodeIssue::NeverEnqueuedWorker => "Never-enqueued workers",
+            DeadCodeIssue::UnusedKoinDefinition => "Unused Koin definitions",
+            DeadCodeIssue::WriteOnlyDataStoreKey => "Write-only DataStore keys",
+            DeadCodeIssue::UnusedDynamicFeature => "Unused dynamic feature modules",
+            DeadCodeIssue::StaleConsumerRule => "Stale consumer rules",
+            DeadCodeIssue::UnusedAidlMethod => "Unused AIDL methods",
+            DeadCodeIssue::UnusedProtoMessage => "Unused proto messages",
+
+            // Architecture patterns
+            DeadCodeIssue::DeepInheritance => "Deep inheritance hierarchies",
+            DeadCodeIssue::EventBusPattern => "EventBus @Subscribe usage",
+            DeadCodeIssue::GlobalMutableState => "Global mutable state",
+            DeadCodeIssue::SingleImplInterface => "Single-implementation interfaces",
+            DeadCodeIssue::LegacyDependency => "Legacy dependencies",
+            DeadCodeIssue::ExcessiveFeatureToggles => "Excessive feature toggles",
+
+            // Kotlin patterns
+            DeadCodeIssue::HeavyViewModel => "Heavy ViewModels",
+            DeadCodeIssue::GlobalScopeUsage => "GlobalScope usage",
+            DeadCodeIssue::LateinitAbuse => "Excessive lateinit",
+            DeadCodeIssue::ScopeFunctionChaining => "Scope function chaining",
+            DeadCodeIssue::NullabilityOverload => "Excessive null handling",
+            DeadCodeIssue::ReflectionOveruse => "Reflection overuse",
+            DeadCodeIssue::LongParameterList => "Long parameter lists",
+            DeadCodeIssue::ComplexCondition => "Complex conditions",
+            DeadCodeIssue::StringLiteralDuplication => "Duplicated string literals",
+
+            // Performance patterns
+            DeadCodeIssue::MemoryLeakRisk => "Memory leak risks",
+            DeadCodeIssue::LongMethod => "Long methods",
+            DeadCodeIssue::LargeClass => "Large classes",
+            DeadCodeIssue::CollectionWithoutSequence => "Collections without asSequence()",
+            DeadCodeIssue::ObjectAllocationInLoop => "Object allocation in loops",
+
+            // Android patterns
+            DeadCodeIssue::MutableStateExposed => "Exposed mutable state",
+            DeadCodeIssue::ViewLogicInViewModel => "View/Context in ViewModel",
+            DeadCodeIssue::MissingUseCase => "Missing UseCase layer",
+            DeadCodeIssue::NestedCallback => "Nested callbacks",
+            DeadCodeIssue::HardcodedDispatcher => "Hardcoded Dispatchers",
+            DeadCodeIssue::UnclosedResource => "Unclosed resources",
+            DeadCodeIssue::MainThreadDatabase => "Main thread database access",
+            DeadCodeIssue::WakeLockAbuse => "WakeLock issues",
+            DeadCodeIssue::AsyncTaskUsage => "AsyncTask usage (deprecated)",
+            DeadCodeIssue::InitOnDraw => "Allocations in onDraw()",
+
+            // Compose patterns
+            DeadCodeIssue::StateWithoutRemember => "State without remember",
+            DeadCodeIssue::LaunchedEffectWithoutKey => "LaunchedEffect without key",
+            DeadCodeIssue::BusinessLogicInComposable => "Business logic in Composable",
+            DeadCodeIssue::NavControllerPassing => "NavController passing",
+
+            DeadCodeIssue::Custom(metadata) => metadata.name,
+        }
+    }
+
+    /// Report category this issue belongs to
+    pub fn category(&self) -> &'static str {
+        match self {
+            DeadCodeIssue::Unreferenced
+            | DeadCodeIssue::UnusedImport
+            | DeadCodeIssue::UnusedParameter
+            | DeadCodeIssue::AssignOnly
+            | DeadCodeIssue::DeadBranch
+            | DeadCodeIssue::RedundantOverride
+            | DeadCodeIssue::RedundantPublic
+            | DeadCodeIssue::UnusedEnumCase
+            | DeadCodeIssue::UnusedSealedVariant
+            | DeadCodeIssue::WriteOnlyPreference
+            | DeadCodeIssue::WriteOnlyDao
+            | DeadCodeIssue::DuplicateImport
+            | DeadCodeIssue::RedundantNullInit
+            | DeadCodeIssue::RedundantThis
+            | DeadCodeIssue::RedundantParentheses
+            | DeadCodeIssue::PreferIsEmpty
+            | DeadCodeIssue::UnusedBinding
+            | DeadCodeIssue::UnregisteredReceiver
+            | DeadCodeIssue::UnusedConstant
+            | DeadCodeIssue::UnusedExtension
+            | DeadCodeIssue::StalePreview
+            | DeadCodeIssue::LegacyParcelable
+            | DeadCodeIssue::WriteOnlyColumn
+            | DeadCodeIssue::AlwaysDefaultParam
+            | DeadCodeIssue::DeadCallback
+            | DeadCodeIssue::UnusedTypeAlias
+            | DeadCodeIssue::UnusedCatalogEntry
+            | DeadCodeIssue::TestOnlyCode
+            | DeadCodeIssue::UnmatchedIntentAction
+            | DeadCodeIssue::NeverEnqueuedWorker
+            | DeadCodeIssue::UnusedKoinDefinition
+            | DeadCodeIssue::WriteOnlyDataStoreKey
+            | DeadCodeIssue::UnusedDynamicFeature
+            | DeadCodeIssue::StaleConsumerRule
+            | DeadCodeIssue::UnusedAidlMethod
+            | DeadCodeIssue::UnusedProtoMessage => "Dead Code",
+
+            DeadCodeIssue::UnusedTestHelper | DeadCodeIssue::OrphanTest => "Test Code",
+
+            DeadCodeIssue::DeepInheritance
+            | DeadCodeIssue::EventBusPattern
+            | DeadCodeIssue::GlobalMutableState
+            | DeadCodeIssue::SingleImplInterface
+            | DeadCodeIssue::LegacyDependency
+            | DeadCodeIssue::ExcessiveFeatureToggles => "Architecture",
+
+            DeadCodeIssue::HeavyViewModel
+            | DeadCodeIssue::GlobalScopeUsage
+            | DeadCodeIssue::LateinitAbuse
+            | DeadCodeIssue::ScopeFunctionChaining
+            | DeadCodeIssue::NullabilityOverload
+            | DeadCodeIssue::ReflectionOveruse
+            | DeadCodeIssue::LongParameterList
+            | DeadCodeIssue::ComplexCondition
+            | DeadCodeIssue::StringLiteralDuplication => "Kotlin",
+
+            DeadCodeIssue::MemoryLeakRisk
+            | DeadCodeIssue::LongMethod
+            | DeadCodeIssue::LargeClass
+            | DeadCodeIssue::CollectionWithoutSequence
+            | DeadCodeIssue::ObjectAllocationInLoop => "Performance",
+
+            DeadCodeIssue::MutableStateExposed
+            | DeadCodeIssue::ViewLogicInViewModel
+            | DeadCodeIssue::MissingUseCase
+            | DeadCodeIssue::NestedCallback
+            | DeadCodeIssue::HardcodedDispatcher
+            | DeadCodeIssue::UnclosedResource
+            | DeadCodeIssue::MainThreadDatabase
+            | DeadCodeIssue::WakeLockAbuse
+            | DeadCodeIssue::AsyncTaskUsage
+            | DeadCodeIssue::InitOnDraw => "Android",
+
+            DeadCodeIssue::StateWithoutRemember
+            | DeadCodeIssue::LaunchedEffectWithoutKey
+            | DeadCodeIssue::BusinessLogicInComposable
+            | DeadCodeIssue::NavControllerPassing => "Compose",
+
+            DeadCodeIssue::Custom(metadata) => metadata.category,
+        }
+    }
+
+    /// Whether fixing the issue deletes its declaration, so the
+    /// declaration's size counts toward the code findings would remove
+    pub fn is_removable(&self) -> bool {
+        matches!(
+            self,
+            DeadCodeIssue::Unreferenced
+                | DeadCodeIssue::UnusedImport
+                | DeadCodeIssue::UnusedParameter
+                | DeadCodeIssue::AssignOnly
+                | DeadCodeIssue::RedundantOverride
+                | DeadCodeIssue::UnusedEnumCase
+                | DeadCodeIssue::UnusedSealedVariant
+                | DeadCodeIssue::DuplicateImport
+                | DeadCodeIssue::UnusedBinding
+                | DeadCodeIssue::UnregisteredReceiver
+                | DeadCodeIssue::UnusedConstant
+                | DeadCodeIssue::UnusedExtension
+                | DeadCodeIssue::UnusedTestHelper
+                | DeadCodeIssue::StalePreview
+                | DeadCodeIssue::OrphanTest
+                | DeadCodeIssue::DeadCallback
+                | DeadCodeIssue::UnusedTypeAlias
+                | DeadCodeIssue::UnusedCatalogEntry
+                | DeadCodeIssue::TestOnlyCode
+                | DeadCodeIssue::NeverEnqueuedWorker
+                | DeadCodeIssue::UnusedKoinDefinition
+                | DeadCodeIssue::StaleConsumerRule
+                | DeadCodeIssue::UnusedAidlMethod
+                | DeadCodeIssue::UnusedProtoMessage
+        )
+    }
+}
+
+impl Serialize for DeadCodeIssue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            DeadCodeIssue::Custom(metadata) => metadata.serialize(serializer),
+            issue => serializer.serialize_str(issue.code()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DeadCodeIssue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Code(String),
+            Custom(DetectorMetadata),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Code(code) => Self::from_code(&code)
+                .ok_or_else(|| D::Error::custom(format!("unknown rule code `{}`", code))),
+            Repr::Custom(metadata) => {
+                Ok(Self::from_code(metadata.code).unwrap_or(Self::Custom(metadata)))
+            }
         }
     }
 }
 
 /// Severity levels for dead code issues
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Info,
     Warning,
@@ -719,3 +1455,167 @@ impl std::fmt::Display for Severity {
         write!(f, "{}", self.as_str())
     }
 }
+
+impl std::str::FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(format!(
+                "unknown severity '{}' (expected info, warning or error)",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationId, DeclarationKind, Language, Location};
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    fn dead_code(issue: DeadCodeIssue) -> DeadCode {
+        let path = PathBuf::from("Test.kt");
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            "Unused".to_string(),
+            DeclarationKind::Class,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, issue).with_confidence(Confidence::High)
+    }
+
+    #[test]
+    fn test_dead_code_serde_roundtrip() {
+        let finding = dead_code(DeadCodeIssue::Unreferenced);
+        let json = serde_json::to_value(&finding).unwrap();
+
+        assert_eq!(json["issue"], "DC001");
+        assert_eq!(json["confidence"], "high");
+        assert_eq!(json["severity"], "warning");
+
+        let restored: DeadCode = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.issue, DeadCodeIssue::Unreferenced);
+        assert_eq!(restored.confidence, Confidence::High);
+        assert_eq!(restored.declaration.name, "Unused");
+    }
+
+    #[test]
+    fn test_custom_issue_roundtrip() {
+        let metadata = DetectorMetadata::new("ACME001", "Legacy helpers", "Acme", Severity::Error);
+        let json = serde_json::to_string(&DeadCodeIssue::Custom(metadata)).unwrap();
+        let restored: DeadCodeIssue = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, DeadCodeIssue::Custom(metadata));
+        assert_eq!(restored.code(), "ACME001");
+    }
+
+    #[test]
+    fn test_issue_deserialize_rejects_unknown_code() {
+        assert!(serde_json::from_str::<DeadCodeIssue>("\"XX999\"").is_err());
+        assert_eq!(
+            serde_json::from_str::<DeadCodeIssue>("\"ap001\"").unwrap(),
+            DeadCodeIssue::GlobalMutableState
+        );
+    }
+
+    #[test]
+    fn test_confidence_and_severity_from_str() {
+        assert_eq!("High".parse(), Ok(Confidence::High));
+        assert_eq!("confirmed".parse(), Ok(Confidence::Confirmed));
+        assert!("certain".parse::<Confidence>().is_err());
+        assert_eq!("ERROR".parse(), Ok(Severity::Error));
+        assert!("fatal".parse::<Severity>().is_err());
+    }
+
+    #[test]
+    fn test_builtin_codes_are_unique() {
+        let codes: HashSet<_> = DeadCodeIssue::BUILTIN.iter().map(|i| i.code()).collect();
+        assert_eq!(codes.len(), DeadCodeIssue::BUILTIN.len());
+        for issue in DeadCodeIssue::BUILTIN {
+            assert_eq!(DeadCodeIssue::from_code(issue.code()), Some(*issue));
+        }
+    }
+
+    #[test]
+    fn test_declarations_named_in_strings_are_low_confidence() {
+        use crate::discovery::{FileType, SourceFile};
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str, file_type: FileType| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, file_type)
+        };
+        let files = [
+            write(
+                "Plugins.kt",
+                r#"package com.app.plugins
+
+class Plugin {
+    fun load() {}
+    fun unload() {}
+}
+
+class Orphan
+"#,
+                FileType::Kotlin,
+            ),
+            write(
+                "Loader.kt",
+                r#"package com.app
+
+fun loadPlugin() {
+    val plugin = Class.forName("com.app.plugins.Plugin")
+    plugin.getMethod("load").invoke(plugin.newInstance())
+}
+"#,
+                FileType::Kotlin,
+            ),
+            write(
+                "Registry.java",
+                r#"package com.app;
+
+class Registry {
+    static final String ORPHAN = "com.app.plugins.Orphan";
+}
+"#,
+                FileType::Java,
+            ),
+        ];
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+
+        let named = |name: &str| graph.find_by_name(name)[0].clone();
+        // Strings are not references reachability follows
+        assert!(graph.get_references_to(&named("Plugin").id).is_empty());
+
+        let mut findings: Vec<DeadCode> = ["Plugin", "load", "Orphan", "unload"]
+            .into_iter()
+            .map(|name| DeadCode::new(named(name), DeadCodeIssue::Unreferenced))
+            .collect();
+        downgrade_string_referenced(&graph, &mut findings);
+
+        let confidences: Vec<Confidence> = findings.iter().map(|dc| dc.confidence).collect();
+        assert_eq!(
+            confidences,
+            [
+                Confidence::Low,
+                Confidence::Low,
+                Confidence::Low,
+                Confidence::Medium
+            ]
+        );
+        assert!(findings[0].message.contains("reflection"));
+    }
+}
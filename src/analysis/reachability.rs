@@ -1,15 +1,40 @@
-use super::{DeadCode, DeadCodeIssue};
-use crate::graph::{DeclarationId, DeclarationKind, Graph};
-use petgraph::visit::Dfs;
+use super::{Confidence, DeadCode, DeadCodeIssue};
+use crate::cancel::CancellationToken;
+use crate::config::DEFAULT_RETAIN_ANNOTATIONS;
+use crate::graph::{Declaration, DeclarationId, DeclarationKind, Graph, ReferenceConfidence};
+use petgraph::visit::EdgeRef;
 use std::collections::HashSet;
 use tracing::debug;
 
 /// Analyzer for finding unreachable/dead code via graph traversal
-pub struct ReachabilityAnalyzer;
+pub struct ReachabilityAnalyzer {
+    /// Annotations whose declarations are reachability roots
+    retain_annotations: Vec<String>,
+    cancel: CancellationToken,
+}
 
 impl ReachabilityAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self {
+            retain_annotations: default_retain_annotations(),
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    /// Annotations whose declarations (and the members of annotated
+    /// types) are never reported
+    pub fn with_retain_annotations(mut self, annotations: Vec<String>) -> Self {
+        self.retain_annotations = annotations;
+        self
+    }
+
+    /// Stop traversing once `cancel` fires
+    ///
+    /// The results of a cancelled run are incomplete; callers check the
+    /// token afterwards.
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
     }
 
     /// Find all unreachable declarations starting from entry points
@@ -24,20 +49,40 @@ impl ReachabilityAnalyzer {
 
     /// Find all unreachable declarations and also return the reachable set
     /// This is useful for hybrid analysis that needs to check runtime coverage
+    ///
+    /// Declarations only reachable through references matched by name among
+    /// same-named declarations, or guessed heuristically
+    /// ([`ReferenceConfidence`]), are reported with [`Confidence::Low`] and
+    /// left out of the reachable set.
     pub fn find_unreachable_with_reachable(
         &self,
         graph: &Graph,
         entry_points: &HashSet<DeclarationId>,
     ) -> (Vec<DeadCode>, HashSet<DeclarationId>) {
         // First, find all reachable nodes via DFS from entry points
-        let reachable = self.find_reachable(graph, entry_points);
+        let entry_points = &with_retained_roots(graph, entry_points, &self.retain_annotations);
+        let reachable = self.find_reachable(graph, entry_points, ReferenceConfidence::Heuristic);
+        let has_weak_references = graph
+            .inner()
+            .edge_weights()
+            .any(|reference| reference.confidence < ReferenceConfidence::Exact);
+        let confidently_reachable = if has_weak_references && !self.cancel.is_cancelled() {
+            self.find_reachable(graph, entry_points, ReferenceConfidence::Exact)
+        } else {
+            reachable.clone()
+        };
+        let weakly_reachable =
+            |id: &DeclarationId| reachable.contains(id) && !confidently_reachable.contains(id);
 
         // Collect unreachable declarations
         let mut dead_code = Vec::new();
 
-        for decl in graph.declarations() {
+        for decl in graph
+            .declarations()
+            .take_while(|_| !self.cancel.is_cancelled())
+        {
             // Skip if reachable
-            if reachable.contains(&decl.id) {
+            if confidently_reachable.contains(&decl.id) {
                 continue;
             }
 
@@ -46,9 +91,27 @@ impl ReachabilityAnalyzer {
                 continue;
             }
 
+            let issue = self.determine_issue_type(decl);
+            if weakly_reachable(&decl.id) {
+                // Reported at the outermost declaration, like unreachable members
+                if decl.parent.as_ref().is_some_and(weakly_reachable) {
+                    continue;
+                }
+                debug!(
+                    "Weakly reachable: {} ({})",
+                    decl.name,
+                    decl.kind.display_name()
+                );
+                let mut dc = DeadCode::new(decl.clone(), issue).with_confidence(Confidence::Low);
+                dc.message.push_str(
+                    " (only reachable through references matched by name or heuristically)",
+                );
+                dead_code.push(dc);
+                continue;
+            }
+
             debug!("Unreachable: {} ({})", decl.name, decl.kind.display_name());
 
-            let issue = self.determine_issue_type(decl);
             dead_code.push(DeadCode::new(decl.clone(), issue));
         }
 
@@ -68,40 +131,53 @@ impl ReachabilityAnalyzer {
                 .cmp(&b.declaration.location.line)
         });
 
-        (dead_code, reachable)
+        (dead_code, confidently_reachable)
     }
 
-    /// Find all reachable nodes from entry points using DFS
+    /// Find all reachable nodes from entry points, following the references
+    /// of at least `min_confidence`
+    ///
+    /// Following every reference, the traversal runs on the graph's
+    /// condensation, so every strongly connected component is visited once.
     fn find_reachable(
         &self,
         graph: &Graph,
         entry_points: &HashSet<DeclarationId>,
+        min_confidence: ReferenceConfidence,
     ) -> HashSet<DeclarationId> {
-        let mut reachable = HashSet::new();
         let inner_graph = graph.inner();
-
-        // Step 1: Initial DFS from entry points
-        for entry_id in entry_points {
-            if let Some(start_idx) = graph.node_index(entry_id) {
-                // Add entry point itself
-                reachable.insert(entry_id.clone());
-
-                // Perform DFS from this entry point
-                let mut dfs = Dfs::new(inner_graph, start_idx);
-
-                while let Some(node_idx) = dfs.next(inner_graph) {
-                    if let Some(node_id) = inner_graph.node_weight(node_idx) {
-                        reachable.insert(node_id.clone());
-
-                        // Also mark parent declarations as reachable
-                        if let Some(decl) = graph.get_declaration(node_id) {
-                            if let Some(parent_id) = &decl.parent {
-                                reachable.insert(parent_id.clone());
-                            }
-                        }
+        let follow_references = |from: &HashSet<DeclarationId>| {
+            let starts = from.iter().filter_map(|id| graph.node_index(id));
+            let nodes = if min_confidence == ReferenceConfidence::Heuristic {
+                graph.condensation().reachable_from(starts)
+            } else {
+                let mut visited = HashSet::new();
+                let mut stack: Vec<_> = starts.collect();
+                while let Some(node) = stack.pop() {
+                    if !visited.insert(node) {
+                        continue;
                     }
+                    stack.extend(
+                        inner_graph
+                            .edges(node)
+                            .filter(|edge| edge.weight().confidence >= min_confidence)
+                            .map(|edge| edge.target())
+                            .filter(|target| !visited.contains(target)),
+                    );
                 }
-            }
+                visited.into_iter().collect()
+            };
+            nodes
+                .into_iter()
+                .map(|node_idx| inner_graph[node_idx].clone())
+                .collect::<Vec<_>>()
+        };
+
+        // Step 1: Follow references from entry points
+        let mut reachable: HashSet<DeclarationId> =
+            follow_references(entry_points).into_iter().collect();
+        if self.cancel.is_cancelled() {
+            return reachable;
         }
 
         // Step 2: Mark all ancestors of reachable nodes as reachable
@@ -115,18 +191,12 @@ impl ReachabilityAnalyzer {
         // Use a worklist instead of iterating all declarations
         self.mark_children_reachable(graph, &mut reachable);
 
-        // Step 4: DFS from newly reachable nodes
-        let mut additional_reachable = HashSet::new();
-        for id in &reachable {
-            if let Some(start_idx) = graph.node_index(id) {
-                let mut dfs = Dfs::new(inner_graph, start_idx);
-                while let Some(node_idx) = dfs.next(inner_graph) {
-                    if let Some(node_id) = inner_graph.node_weight(node_idx) {
-                        additional_reachable.insert(node_id.clone());
-                    }
-                }
-            }
+        if self.cancel.is_cancelled() {
+            return reachable;
         }
+
+        // Step 4: Follow references from newly reachable nodes
+        let additional_reachable = follow_references(&reachable);
         reachable.extend(additional_reachable);
 
         // Step 5: Mark children again (for newly discovered reachable classes)
@@ -186,6 +256,34 @@ impl ReachabilityAnalyzer {
             return true;
         }
 
+        // Skip Kotlin const val - let the dedicated UnusedConstantDetector handle them
+        if decl.is_const() {
+            return true;
+        }
+
+        // Skip enum constants - let the dedicated UnusedEnumCaseDetector handle them,
+        // it knows when values()/entries make a constant reachable by enumeration
+        if decl.kind == DeclarationKind::EnumCase {
+            return true;
+        }
+
+        // Skip test sources - the UnusedTestHelperDetector runs reachability from
+        // the tests themselves and reports under its own category
+        if decl.is_test_source() {
+            return true;
+        }
+
+        // Skip extensions with no call sites - the dedicated UnusedExtensionDetector
+        // reports them. Extensions only called from dead code are still reported here.
+        if decl.is_extension() && !graph.is_referenced(&decl.id) {
+            return true;
+        }
+
+        // Skip type aliases with no references - the UnusedTypeAliasDetector reports them
+        if decl.kind == DeclarationKind::TypeAlias && !graph.is_referenced(&decl.id) {
+            return true;
+        }
+
         // Skip private/internal members of unreachable classes
         // (they should be reported at the class level, not individually)
         if let Some(parent_id) = &decl.parent {
@@ -230,6 +328,38 @@ impl ReachabilityAnalyzer {
     }
 }
 
+pub(crate) fn default_retain_annotations() -> Vec<String> {
+    DEFAULT_RETAIN_ANNOTATIONS
+        .iter()
+        .map(|a| a.to_string())
+        .collect()
+}
+
+/// Entry points plus the declarations a retention annotation keeps: the
+/// annotated declarations and the members of annotated types
+pub(crate) fn with_retained_roots(
+    graph: &Graph,
+    entry_points: &HashSet<DeclarationId>,
+    annotations: &[String],
+) -> HashSet<DeclarationId> {
+    let retained = |decl: &Declaration| annotations.iter().any(|a| decl.has_annotation(a));
+    let mut roots = entry_points.clone();
+    for decl in graph.declarations() {
+        let mut current = Some(decl);
+        while let Some(candidate) = current {
+            if retained(candidate) {
+                roots.insert(decl.id.clone());
+                break;
+            }
+            current = candidate
+                .parent
+                .as_ref()
+                .and_then(|parent| graph.get_declaration(parent));
+        }
+    }
+    roots
+}
+
 impl Default for ReachabilityAnalyzer {
     fn default() -> Self {
         Self::new()
@@ -249,4 +379,123 @@ mod tests {
         let dead_code = analyzer.find_unreachable(&graph, &entry_points);
         assert!(dead_code.is_empty());
     }
+
+    #[test]
+    fn test_retain_annotations_keep_declarations() {
+        use crate::discovery::{FileType, SourceFile};
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Retained.kt");
+        std::fs::write(
+            &path,
+            r#"package com.app
+
+@Keep
+class KeptModel {
+    fun serialize() = helper()
+}
+
+fun helper() = 1
+
+class Cache {
+    @VisibleForTesting
+    fun clear() {}
+}
+
+class Dumper {
+    @ExposedToScript
+    fun dump() {}
+}
+
+class Legacy {
+    fun unused() {}
+}
+"#,
+        )
+        .unwrap();
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(path, FileType::Kotlin))
+            .unwrap();
+        let graph = builder.build();
+
+        let reported = |analyzer: ReachabilityAnalyzer| {
+            let mut names: Vec<String> = analyzer
+                .find_unreachable(&graph, &HashSet::new())
+                .into_iter()
+                .map(|dc| dc.declaration.name.to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(reported(ReachabilityAnalyzer::new()), ["Dumper", "Legacy"]);
+
+        let mut annotations = default_retain_annotations();
+        annotations.push("com.app.ExposedToScript".to_string());
+        assert_eq!(
+            reported(ReachabilityAnalyzer::new().with_retain_annotations(annotations)),
+            ["Legacy"]
+        );
+    }
+    #[test]
+    fn test_name_matched_references_give_low_confidence() {
+        use crate::discovery::{FileType, SourceFile};
+        use crate::graph::GraphBuilder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Screen.kt");
+        std::fs::write(
+            &path,
+            r#"package com.app
+
+class Screen {
+    fun show(value: Int) = format(value) + trim()
+}
+
+fun trim() = ""
+
+fun format(value: Int) = "$value"
+
+fun format(value: String) = value
+
+class Legacy
+"#,
+        )
+        .unwrap();
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(path, FileType::Kotlin))
+            .unwrap();
+        let graph = builder.build();
+        let id = |name: &str| graph.find_by_name(name)[0].id.clone();
+
+        // The call may be either overload's: it matches both by name
+        let confidences: Vec<_> = graph
+            .get_references_from(&id("show"))
+            .into_iter()
+            .map(|(to, reference)| (to.name.as_str(), reference.confidence))
+            .collect();
+        assert!(confidences.contains(&("trim", ReferenceConfidence::Exact)));
+        assert!(confidences.contains(&("format", ReferenceConfidence::NameMatch)));
+
+        let entry_points = HashSet::from([id("Screen")]);
+        let (dead_code, reachable) =
+            ReachabilityAnalyzer::new().find_unreachable_with_reachable(&graph, &entry_points);
+        let mut reported: Vec<_> = dead_code
+            .iter()
+            .map(|dc| (dc.declaration.name.as_str(), dc.confidence))
+            .collect();
+        reported.sort();
+        assert_eq!(
+            reported,
+            [
+                ("Legacy", Confidence::Medium),
+                ("format", Confidence::Low),
+                ("format", Confidence::Low)
+            ]
+        );
+        assert!(reachable.contains(&id("trim")));
+        assert!(!reachable.contains(&id("format")));
+    }
 }
@@ -0,0 +1,208 @@
+//! Unused asset detection
+//!
+//! Files under `src/<source set>/assets/` have no `R` id: they are opened by
+//! path (`assets.open("config/rules.json")`, `Typeface.createFromAsset(assets,
+//! "fonts/brand.ttf")`, `"file:///android_asset/help/index.html"`,
+//! `app:lottie_fileName="confetti.json"`). An asset counts as used when any
+//! string in code, XML or another text asset names it:
+//!
+//! - its path under `assets/`, possibly behind an `android_asset` URL prefix
+//! - its file name alone, as relative links from HTML/CSS and lottie file
+//!   names in a folder do
+//! - one of its directories, which `assets.list("dir")` enumerates
+
+use super::{is_project_entry, ResourceAnalysis, ResourceDetector};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Extensions of files whose strings can name an asset
+const REFERENCING_EXTENSIONS: &[&str] = &[
+    "kt", "java", "xml", "html", "htm", "js", "css", "json", "gradle", "kts",
+];
+
+/// URL prefixes WebViews and loaders use to address assets
+const ASSET_URL_PREFIXES: &[&str] = &[
+    "file:///android_asset/",
+    "/android_asset/",
+    "asset:///",
+    "https://appassets.androidplatform.net/assets/",
+];
+
+/// A file under an `assets/` directory
+#[derive(Debug, Clone)]
+pub struct AssetFile {
+    pub file: PathBuf,
+    /// Path under `assets/` (e.g., "fonts/brand.ttf")
+    pub asset_path: String,
+    pub bytes: u64,
+}
+
+impl ResourceDetector {
+    /// Find asset files that nothing opens by path
+    pub(super) fn find_unused_assets(&self, project_root: &Path, analysis: &mut ResourceAnalysis) {
+        let mut assets = Vec::new();
+        let mut referencing_files = Vec::new();
+
+        let walker = walkdir::WalkDir::new(project_root)
+            .into_iter()
+            .filter_entry(is_project_entry);
+        for entry in walker.flatten() {
+            let path = entry.path();
+            if entry.file_type().is_dir() && is_assets_dir(path) {
+                collect_assets(path, &mut assets);
+            } else if entry.file_type().is_file()
+                && path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| REFERENCING_EXTENSIONS.contains(&e))
+            {
+                referencing_files.push(path.to_path_buf());
+            }
+        }
+        if assets.is_empty() {
+            return;
+        }
+
+        let mut names = HashSet::new();
+        for path in referencing_files {
+            if let Ok(content) = fs::read_to_string(&path) {
+                names.extend(quoted_paths(&content));
+            }
+        }
+
+        analysis.unused_assets.extend(
+            assets
+                .into_iter()
+                .filter(|asset| !asset_names(&asset.asset_path).any(|n| names.contains(n))),
+        );
+    }
+}
+
+/// An `assets` directory of a source set (`<module>/src/main/assets`)
+fn is_assets_dir(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "assets")
+        && path.parent().is_some_and(|source_set| {
+            source_set.join("AndroidManifest.xml").is_file()
+                || source_set
+                    .parent()
+                    .and_then(|dir| dir.file_name())
+                    .is_some_and(|dir| dir == "src")
+        })
+}
+
+fn collect_assets(assets_dir: &Path, assets: &mut Vec<AssetFile>) {
+    let walker = walkdir::WalkDir::new(assets_dir)
+        .into_iter()
+        .filter_entry(is_project_entry);
+    for entry in walker.flatten().filter(|e| e.file_type().is_file()) {
+        let Ok(relative) = entry.path().strip_prefix(assets_dir) else {
+            continue;
+        };
+        let asset_path = relative
+            .components()
+            .filter_map(|c| c.as_os_str().to_str())
+            .collect::<Vec<_>>()
+            .join("/");
+        assets.push(AssetFile {
+            file: entry.path().to_path_buf(),
+            asset_path,
+            bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+        });
+    }
+}
+
+/// Quoted strings and CSS `url()`s, without asset URL prefixes or `./`
+fn quoted_paths(content: &str) -> impl Iterator<Item = String> + '_ {
+    static QUOTED: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#""([^"\n]+)"|'([^'\n]+)'|url\(\s*([^)'"\s]+)\s*\)"#).unwrap()
+    });
+
+    QUOTED.captures_iter(content).filter_map(|cap| {
+        let text = cap.get(1).or(cap.get(2)).or(cap.get(3))?.as_str().trim();
+        let text = ASSET_URL_PREFIXES
+            .iter()
+            .find_map(|prefix| text.strip_prefix(prefix))
+            .unwrap_or(text);
+        let text = text.trim_start_matches("./").trim_end_matches('/');
+        (!text.is_empty()).then(|| text.to_string())
+    })
+}
+
+/// Names a reference to `asset_path` can use: the path, each shorter
+/// suffix down to the file name, and each parent directory
+fn asset_names(asset_path: &str) -> impl Iterator<Item = &str> {
+    let suffixes = std::iter::once(asset_path).chain(
+        asset_path
+            .match_indices('/')
+            .map(|(i, _)| &asset_path[i + 1..]),
+    );
+    let directories = asset_path.match_indices('/').map(|(i, _)| &asset_path[..i]);
+    suffixes.chain(directories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unused_assets_and_raw_files() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let assets = "app/src/main/assets";
+        write(root, &format!("{}/fonts/brand.ttf", assets), &[0; 30]);
+        write(root, &format!("{}/fonts/legacy.ttf", assets), &[0; 300]);
+        write(
+            root,
+            &format!("{}/help/index.html", assets),
+            br#"<link href="css/help.css">"#,
+        );
+        write(
+            root,
+            &format!("{}/help/css/help.css", assets),
+            b"body { background: url(bg.png) }",
+        );
+        write(root, &format!("{}/help/bg.png", assets), &[0; 20]);
+        write(root, &format!("{}/help/old.html", assets), &[0; 50]);
+        write(root, &format!("{}/licenses/apache.txt", assets), &[0; 10]);
+        write(root, &format!("{}/confetti.json", assets), &[0; 10]);
+        write(root, &format!("{}/seed.db", assets), &[0; 1000]);
+        write(root, "app/src/main/res/raw/chime.ogg", &[0; 40]);
+        write(root, "app/src/main/res/raw/intro_video.mp4", &[0; 2000]);
+        write(
+            root,
+            "app/src/main/java/app/Main.kt",
+            br#"val typeface = Typeface.createFromAsset(assets, "fonts/brand.ttf")
+webView.loadUrl("file:///android_asset/help/index.html")
+val licenses = assets.list("licenses")
+player.play(R.raw.chime)"#,
+        );
+        write(
+            root,
+            "app/src/main/res/layout/party.xml",
+            br#"<LottieAnimationView app:lottie_fileName="confetti.json"/>"#,
+        );
+
+        let analysis = ResourceDetector::new().analyze(root);
+
+        let unused: Vec<&str> = analysis
+            .unused_assets
+            .iter()
+            .map(|a| a.asset_path.as_str())
+            .collect();
+        assert_eq!(unused, ["seed.db", "fonts/legacy.ttf", "help/old.html"]);
+        assert_eq!(analysis.unused_asset_bytes(), 1350);
+
+        let raw: Vec<&str> = analysis
+            .unused_files
+            .iter()
+            .filter(|r| r.resource_type == "raw")
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(raw, ["intro_video"]);
+    }
+}
@@ -0,0 +1,195 @@
+//! Unused drawable, mipmap and raw file detection
+//!
+//! Drawables, mipmaps and raw files are file-backed resources:
+//! `res/drawable-hdpi/logo.png` and `res/drawable-xxhdpi/logo.webp` are both
+//! `R.drawable.logo`. Files are
+//! grouped by type and name across every qualifier directory, so a resource is
+//! only reported when no density bucket is referenced, and the finding carries
+//! the combined size of all its files.
+
+use super::{ResourceAnalysis, ResourceDetector};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A file-backed resource, grouped across qualifier directories
+#[derive(Debug, Clone)]
+pub struct FileResource {
+    /// Resource name (e.g., "ic_logo")
+    pub name: String,
+    /// Resource type ("drawable", "mipmap" or "raw")
+    pub resource_type: String,
+    /// Every file that provides this resource, sorted
+    pub files: Vec<PathBuf>,
+    /// Combined size of `files` in bytes
+    pub total_bytes: u64,
+}
+
+impl ResourceDetector {
+    /// Collect drawable/mipmap/raw files from every qualifier directory of `res_dir`
+    pub(super) fn parse_file_resources(&self, res_dir: &Path, analysis: &mut ResourceAnalysis) {
+        let Ok(entries) = fs::read_dir(res_dir) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let dir = entry.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            let Some(res_type) = dir
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(file_resource_type)
+            else {
+                continue;
+            };
+
+            let Ok(files) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for file in files.flatten() {
+                let path = file.path();
+                let Some(name) = resource_name(&path) else {
+                    continue;
+                };
+                let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+                let resource = analysis
+                    .files
+                    .entry(res_type.to_string())
+                    .or_default()
+                    .entry(name.to_string())
+                    .or_insert_with(|| FileResource {
+                        name: name.to_string(),
+                        resource_type: res_type.to_string(),
+                        files: Vec::new(),
+                        total_bytes: 0,
+                    });
+                resource.files.push(path);
+                resource.total_bytes += size;
+            }
+        }
+    }
+}
+
+/// Resource type of a `res/` subdirectory (`drawable-night-xxhdpi` -> `drawable`)
+fn file_resource_type(dir_name: &str) -> Option<&'static str> {
+    let base = dir_name.split('-').next()?;
+    match base {
+        "drawable" => Some("drawable"),
+        "mipmap" => Some("mipmap"),
+        "raw" => Some("raw"),
+        _ => None,
+    }
+}
+
+/// Resource name of a file (`btn_bg.9.png` -> `btn_bg`)
+///
+/// Resource names can't contain dots, so everything after the first one is
+/// extension (including nine-patch `.9.png`).
+pub(super) fn resource_name(path: &Path) -> Option<&str> {
+    if !path.is_file() {
+        return None;
+    }
+    let file_name = path.file_name()?.to_str()?;
+    let name = file_name.split('.').next()?;
+    (!name.is_empty()).then_some(name)
+}
+
+/// Human-readable byte count for reports (`12.3 KB`)
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unused_drawable_groups_density_buckets() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/src/main/res/drawable-hdpi/old_logo.png",
+            &[0; 100],
+        );
+        write(
+            root,
+            "app/src/main/res/drawable-xxhdpi/old_logo.png",
+            &[0; 300],
+        );
+        write(root, "app/src/main/res/drawable/bg_card.xml", b"<shape/>");
+        write(root, "app/src/main/res/drawable/btn.9.png", &[0; 10]);
+        write(
+            root,
+            "app/src/main/res/mipmap-xxhdpi/ic_launcher.webp",
+            &[0; 50],
+        );
+        write(
+            root,
+            "app/src/main/AndroidManifest.xml",
+            br#"<application android:icon="@mipmap/ic_launcher"/>"#,
+        );
+        write(
+            root,
+            "app/src/main/res/layout/card.xml",
+            br#"<View android:background="@drawable/bg_card"/>"#,
+        );
+        write(
+            root,
+            "app/src/main/java/app/Main.kt",
+            b"val id = R.drawable.btn",
+        );
+
+        let analysis = ResourceDetector::new().analyze(root);
+
+        assert_eq!(analysis.unused_files.len(), 1);
+        let unused = &analysis.unused_files[0];
+        assert_eq!(unused.name, "old_logo");
+        assert_eq!(unused.resource_type, "drawable");
+        assert_eq!(unused.files.len(), 2);
+        assert_eq!(unused.total_bytes, 400);
+        assert_eq!(analysis.unused_file_bytes(), 400);
+    }
+
+    #[test]
+    fn test_drawable_referenced_from_other_drawable_is_used() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, "res/drawable/pressed.png", &[0; 10]);
+        write(
+            root,
+            "res/drawable/selector.xml",
+            br#"<selector><item android:drawable="@drawable/pressed"/></selector>"#,
+        );
+
+        let analysis = ResourceDetector::new().analyze(root);
+        let names: Vec<_> = analysis
+            .unused_files
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(names, ["selector"]);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MB");
+    }
+}
@@ -0,0 +1,325 @@
+//! Duplicate resource detection across modules
+//!
+//! The same resource defined with the same value in several modules ends up
+//! in the APK once per module's `R` class and hides which copy a screen
+//! actually resolves. Copies are matched by qualifier directory, type, name
+//! and content: the text of a simple values resource (`string`, `color`,
+//! `dimen`, `integer`, `bool`) or the bytes of a file resource (drawables,
+//! layouts, raw files). Overlays inside one module (`main` vs `debug`) are
+//! intentional and not reported.
+//!
+//! Each finding suggests a consolidation target: the copy's module that all
+//! the other modules already depend on, or else one named like a shared
+//! module (`core`, `common`, `designsystem`, ...).
+
+use super::drawables::resource_name;
+use super::{module_dir, resource_key, ResourceAnalysis, ResourceDetector};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Directory names of modules meant to hold shared resources
+const SHARED_MODULE_NAMES: &[&str] = &[
+    "core",
+    "common",
+    "shared",
+    "base",
+    "designsystem",
+    "design-system",
+    "ui",
+    "resources",
+];
+
+/// A resource defined identically in more than one module
+#[derive(Debug, Clone)]
+pub struct DuplicateResource {
+    /// Resource name (e.g., "app_name")
+    pub name: String,
+    /// Resource type (e.g., "string", "drawable")
+    pub resource_type: String,
+    /// Qualifier directory shared by the copies (e.g., `values`, `drawable-hdpi`)
+    pub dir_name: String,
+    /// File defining each copy, one per module, sorted
+    pub files: Vec<PathBuf>,
+    /// Module directory of each copy, in the same order as `files`
+    pub modules: Vec<PathBuf>,
+    /// Bytes saved by keeping a single copy
+    pub wasted_bytes: u64,
+    /// Module the copies should be consolidated into, when one stands out
+    pub suggested_module: Option<PathBuf>,
+}
+
+/// What makes two copies the same resource
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct CopyKey {
+    dir_name: String,
+    resource_type: String,
+    name: String,
+    content_hash: u64,
+}
+
+/// One module's copy of a resource
+struct Copy {
+    module: PathBuf,
+    file: PathBuf,
+    bytes: u64,
+}
+
+impl ResourceDetector {
+    /// Find resources copied verbatim into several modules
+    pub(super) fn find_duplicate_resources(
+        &self,
+        project_root: &Path,
+        res_dirs: &[PathBuf],
+        analysis: &mut ResourceAnalysis,
+    ) {
+        let mut copies: HashMap<CopyKey, Vec<Copy>> = HashMap::new();
+
+        for res_dir in res_dirs {
+            let module = module_dir(res_dir)
+                .or_else(|| res_dir.ancestors().nth(3))
+                .unwrap_or(res_dir)
+                .to_path_buf();
+            let Ok(entries) = fs::read_dir(res_dir) else {
+                continue;
+            };
+            let mut dirs: Vec<PathBuf> = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect();
+            dirs.sort();
+
+            for dir in dirs {
+                let Some(dir_name) = dir.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                let resource_type = dir_name.split('-').next().unwrap_or(dir_name);
+                let Ok(files) = fs::read_dir(&dir) else {
+                    continue;
+                };
+                let mut files: Vec<PathBuf> = files.flatten().map(|e| e.path()).collect();
+                files.sort();
+
+                for file in files {
+                    let Some(name) = resource_name(&file) else {
+                        continue;
+                    };
+                    let found = if resource_type == "values" {
+                        values_copies(&file)
+                    } else {
+                        fs::read(&file)
+                            .map(|bytes| {
+                                vec![(
+                                    resource_type.to_string(),
+                                    name.to_string(),
+                                    hash(&bytes),
+                                    bytes.len() as u64,
+                                )]
+                            })
+                            .unwrap_or_default()
+                    };
+                    for (resource_type, name, content_hash, bytes) in found {
+                        let key = CopyKey {
+                            dir_name: dir_name.to_string(),
+                            resource_type,
+                            name,
+                            content_hash,
+                        };
+                        let module_copies = copies.entry(key).or_default();
+                        // Overlays from several source sets of one module are intentional
+                        if module_copies.iter().all(|c| c.module != module) {
+                            module_copies.push(Copy {
+                                module: module.clone(),
+                                file: file.clone(),
+                                bytes,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        for (key, mut module_copies) in copies {
+            if module_copies.len() < 2 {
+                continue;
+            }
+            module_copies.sort_by(|a, b| a.file.cmp(&b.file));
+            let modules: Vec<PathBuf> = module_copies.iter().map(|c| c.module.clone()).collect();
+            analysis.duplicate_resources.push(DuplicateResource {
+                suggested_module: consolidation_target(project_root, &modules),
+                wasted_bytes: module_copies.iter().skip(1).map(|c| c.bytes).sum(),
+                files: module_copies.into_iter().map(|c| c.file).collect(),
+                modules,
+                name: key.name,
+                resource_type: key.resource_type,
+                dir_name: key.dir_name,
+            });
+        }
+    }
+}
+
+/// Simple resources of a values file as (type, key, value hash, size)
+fn values_copies(path: &Path) -> Vec<(String, String, u64, u64)> {
+    static SIMPLE_VALUE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r#"<(string|color|dimen|integer|bool)\b[^>]*?\bname="([^"]+)"[^>]*>([^<]*)</(?:string|color|dimen|integer|bool)>"#,
+        )
+        .unwrap()
+    });
+
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    SIMPLE_VALUE
+        .captures_iter(&content)
+        .map(|cap| {
+            let (res_type, name) = resource_key(&cap[1], &cap[2]);
+            let value = cap[3].trim();
+            (res_type, name, hash(value.as_bytes()), cap[0].len() as u64)
+        })
+        .collect()
+}
+
+fn hash(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The module every other copy's module depends on, else a shared-looking one
+fn consolidation_target(project_root: &Path, modules: &[PathBuf]) -> Option<PathBuf> {
+    let depended_on = modules.iter().find(|candidate| {
+        let Some(gradle_path) = gradle_project_path(project_root, candidate) else {
+            return false;
+        };
+        let dependency = format!("project(\"{}\")", gradle_path);
+        let quoted = format!("project('{}')", gradle_path);
+        modules.iter().filter(|m| m != candidate).all(|module| {
+            ["build.gradle.kts", "build.gradle"]
+                .iter()
+                .filter_map(|name| fs::read_to_string(module.join(name)).ok())
+                .any(|script| script.contains(&dependency) || script.contains(&quoted))
+        })
+    });
+
+    depended_on
+        .or_else(|| {
+            modules.iter().find(|module| {
+                module
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|name| SHARED_MODULE_NAMES.contains(&name.to_lowercase().as_str()))
+            })
+        })
+        .cloned()
+}
+
+/// Gradle path of a module directory (`<root>/core/ui` -> `:core:ui`)
+fn gradle_project_path(project_root: &Path, module: &Path) -> Option<String> {
+    let relative = module.strip_prefix(project_root).ok()?;
+    let segments: Vec<&str> = relative
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect();
+    (!segments.is_empty()).then(|| format!(":{}", segments.join(":")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_duplicate_resources_across_modules() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, "core/ui/build.gradle.kts", b"plugins {}");
+        write(
+            root,
+            "feature/home/build.gradle.kts",
+            br#"dependencies { implementation(project(":core:ui")) }"#,
+        );
+        write(
+            root,
+            "feature/settings/build.gradle.kts",
+            br#"dependencies { implementation(project(":core:ui")) }"#,
+        );
+        for module in ["core/ui", "feature/home", "feature/settings"] {
+            write(
+                root,
+                &format!("{}/src/main/res/values/colors.xml", module),
+                br##"<resources>
+    <color name="brand_blue">#FF0055AA</color>
+</resources>"##,
+            );
+        }
+        write(
+            root,
+            "feature/home/src/main/res/values/strings.xml",
+            br#"<resources>
+    <string name="retry">Retry</string>
+    <string name="title">Home</string>
+</resources>"#,
+        );
+        write(
+            root,
+            "feature/settings/src/main/res/values/strings.xml",
+            br#"<resources>
+    <string name="retry"> Retry </string>
+    <string name="title">Settings</string>
+</resources>"#,
+        );
+        write(
+            root,
+            "feature/home/src/main/res/drawable-hdpi/hero.png",
+            &[7; 400],
+        );
+        write(
+            root,
+            "feature/settings/src/main/res/drawable-hdpi/hero.png",
+            &[7; 400],
+        );
+        write(
+            root,
+            "feature/settings/src/main/res/drawable-xhdpi/hero.png",
+            &[8; 400],
+        );
+        // Debug overlay of the same module is not a duplicate
+        write(
+            root,
+            "feature/home/src/debug/res/values/strings.xml",
+            br#"<resources><string name="title">Home</string></resources>"#,
+        );
+
+        let analysis = ResourceDetector::new().analyze(root);
+
+        let found: Vec<(&str, &str, usize, Option<PathBuf>)> = analysis
+            .duplicate_resources
+            .iter()
+            .map(|d| {
+                (
+                    d.resource_type.as_str(),
+                    d.name.as_str(),
+                    d.files.len(),
+                    d.suggested_module
+                        .as_ref()
+                        .map(|m| m.strip_prefix(root).unwrap().to_path_buf()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("drawable", "hero", 2, None),
+                ("color", "brand_blue", 3, Some(PathBuf::from("core/ui"))),
+                ("string", "retry", 2, None),
+            ]
+        );
+        assert_eq!(analysis.duplicate_resources[0].wasted_bytes, 400);
+    }
+}
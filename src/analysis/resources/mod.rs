@@ -0,0 +1,679 @@
+//! Dead Android resource detection
+//!
+//! This module detects unused Android resources like strings, colors, dimensions,
+//! drawables, etc. by cross-referencing resource definitions with code references.
+
+mod assets;
+mod drawables;
+mod duplicates;
+mod qualifiers;
+mod translations;
+
+pub use assets::AssetFile;
+pub use drawables::{format_bytes, FileResource};
+pub use duplicates::DuplicateResource;
+#[allow(unused_imports)] // `QualifierIssue` is only named by library consumers
+pub use qualifiers::{QualifierDirectory, QualifierIssue};
+#[allow(unused_imports)] // `TranslationIssue` is only named by library consumers
+pub use translations::{OrphanedTranslation, Translation, TranslationIssue};
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Represents an Android resource
+#[derive(Debug, Clone)]
+pub struct AndroidResource {
+    /// Resource name (e.g., "app_name")
+    pub name: String,
+    /// Resource type (e.g., "string", "color", "dimen")
+    pub resource_type: String,
+    /// File where resource is defined
+    pub file: PathBuf,
+    /// Line number in the file
+    pub line: usize,
+}
+
+/// Result of resource analysis
+#[derive(Debug, Default)]
+pub struct ResourceAnalysis {
+    /// All defined resources by type -> name (dots replaced by underscores, as in `R`)
+    pub defined: HashMap<String, HashMap<String, AndroidResource>>,
+    /// Resources referenced from code, layouts and manifests, or by other used resources
+    pub referenced: HashSet<(String, String)>, // (type, name)
+    /// References made inside values definitions: style parents and items, aliases
+    pub resource_refs: HashMap<(String, String), HashSet<(String, String)>>,
+    /// Unused resources (defined but not referenced)
+    pub unused: Vec<AndroidResource>,
+    /// File-backed resources (drawables, mipmaps, raw files) by type -> name
+    pub files: HashMap<String, HashMap<String, FileResource>>,
+    /// Unreferenced file-backed resources, largest first
+    pub unused_files: Vec<FileResource>,
+    /// String keys (type, name) defined in the default `values/` directories
+    pub default_strings: HashSet<(String, String)>,
+    /// Strings, plurals and string arrays from locale-qualified values directories
+    pub translations: Vec<Translation>,
+    /// Translations that can be deleted, by file and line
+    pub orphaned_translations: Vec<OrphanedTranslation>,
+    /// Qualifier directories that are never resolved differently, by path
+    pub unused_qualifier_dirs: Vec<QualifierDirectory>,
+    /// Resources defined identically in several modules, most wasted bytes first
+    pub duplicate_resources: Vec<DuplicateResource>,
+    /// Files under `assets/` that nothing opens by path, largest first
+    pub unused_assets: Vec<AssetFile>,
+}
+
+impl ResourceAnalysis {
+    /// Bytes saved by deleting every unused file-backed resource
+    pub fn unused_file_bytes(&self) -> u64 {
+        self.unused_files.iter().map(|r| r.total_bytes).sum()
+    }
+
+    /// Bytes saved by deleting every unused asset
+    pub fn unused_asset_bytes(&self) -> u64 {
+        self.unused_assets.iter().map(|a| a.bytes).sum()
+    }
+}
+
+/// Detector for unused Android resources
+pub struct ResourceDetector {
+    /// Minimum reference count to consider a resource as used
+    min_references: usize,
+}
+
+impl ResourceDetector {
+    pub fn new() -> Self {
+        Self { min_references: 1 }
+    }
+
+    /// Analyze a project for unused resources
+    pub fn analyze(&self, project_root: &Path) -> ResourceAnalysis {
+        let mut analysis = ResourceAnalysis::default();
+
+        // Find all resource directories
+        let res_dirs = self.find_resource_dirs(project_root);
+
+        // Parse all resource XML files
+        for res_dir in &res_dirs {
+            self.parse_resource_dir(res_dir, &mut analysis);
+            self.parse_file_resources(res_dir, &mut analysis);
+        }
+
+        // Collect all references from Kotlin/Java files
+        self.collect_code_references(project_root, &mut analysis);
+
+        // Resources used by used resources (style parents, color aliases, ...)
+        propagate_references(&mut analysis);
+
+        // Find unused resources
+        for (res_type, resources) in &analysis.defined {
+            for (key, resource) in resources {
+                if !analysis
+                    .referenced
+                    .contains(&(res_type.clone(), key.clone()))
+                {
+                    // Check for common false positives
+                    if !self.should_skip_resource(&resource.name, res_type) {
+                        analysis.unused.push(resource.clone());
+                    }
+                }
+            }
+        }
+
+        for (res_type, resources) in &analysis.files {
+            for (name, resource) in resources {
+                if !analysis
+                    .referenced
+                    .contains(&(res_type.clone(), name.clone()))
+                    && !self.should_skip_resource(name, res_type)
+                {
+                    let mut resource = resource.clone();
+                    resource.files.sort();
+                    analysis.unused_files.push(resource);
+                }
+            }
+        }
+
+        self.find_orphaned_translations(&mut analysis);
+        self.find_unused_qualifier_dirs(&res_dirs, &mut analysis);
+        self.find_duplicate_resources(project_root, &res_dirs, &mut analysis);
+        self.find_unused_assets(project_root, &mut analysis);
+
+        // Sort by file and line
+        analysis
+            .unused
+            .sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        analysis
+            .unused_qualifier_dirs
+            .sort_by(|a, b| a.dir.cmp(&b.dir));
+        analysis.duplicate_resources.sort_by(|a, b| {
+            b.wasted_bytes
+                .cmp(&a.wasted_bytes)
+                .then_with(|| a.resource_type.cmp(&b.resource_type))
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.dir_name.cmp(&b.dir_name))
+        });
+        analysis.unused_assets.sort_by(|a, b| {
+            b.bytes
+                .cmp(&a.bytes)
+                .then_with(|| a.file.cmp(&b.file))
+        });
+        analysis.unused_files.sort_by(|a, b| {
+            b.total_bytes
+                .cmp(&a.total_bytes)
+                .then_with(|| a.resource_type.cmp(&b.resource_type))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        analysis
+    }
+
+    /// Find all res/ directories in the project
+    fn find_resource_dirs(&self, project_root: &Path) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+
+        // Walk the project looking for res/ directories
+        let walker = walkdir::WalkDir::new(project_root)
+            .into_iter()
+            .filter_entry(is_project_entry);
+
+        for entry in walker.flatten() {
+            if entry.file_type().is_dir() {
+                let name = entry.file_name().to_string_lossy();
+                if name == "res" {
+                    dirs.push(entry.path().to_path_buf());
+                }
+            }
+        }
+
+        dirs
+    }
+
+    /// Parse all resource files in a res directory
+    ///
+    /// Locale directories (`values-fr`, `values-pt-rBR`) hold translations,
+    /// which are matched against the defaults instead of being definitions of
+    /// their own. The default `values` directory is parsed first so that its
+    /// definitions win over qualified ones (`values-night`, `values-v21`).
+    fn parse_resource_dir(&self, res_dir: &Path, analysis: &mut ResourceAnalysis) {
+        let Ok(entries) = fs::read_dir(res_dir) else {
+            return;
+        };
+        let mut values_dirs: Vec<(String, PathBuf)> = entries
+            .flatten()
+            .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+            .filter(|(name, path)| {
+                (name == "values" || name.starts_with("values-")) && path.is_dir()
+            })
+            .collect();
+        values_dirs.sort();
+
+        for (name, values_dir) in values_dirs {
+            if let Some(locale) = translations::locale_qualifier(&name) {
+                self.parse_translations(&values_dir, &locale, analysis);
+                continue;
+            }
+
+            let Ok(entries) = fs::read_dir(&values_dir) else {
+                continue;
+            };
+            let mut files: Vec<PathBuf> = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().map(|e| e == "xml").unwrap_or(false))
+                .collect();
+            files.sort();
+            for path in files {
+                self.parse_values_xml(&path, analysis);
+            }
+        }
+    }
+
+    /// Parse a values XML file for resource definitions
+    ///
+    /// References made inside a definition (style items and `parent`s, color
+    /// and dimen aliases, array items) are recorded as edges from that
+    /// resource, so they only count once the resource itself is used.
+    fn parse_values_xml(&self, file_path: &Path, analysis: &mut ResourceAnalysis) {
+        let content = match fs::read_to_string(file_path) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        let is_default = file_path
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .is_some_and(|dir| dir == "values");
+
+        let mut reader = Reader::from_str(&content);
+
+        let mut line = 1;
+        let mut buf = Vec::new();
+        let mut depth = 0usize;
+        // Resource whose definition is being read, as (type, key)
+        let mut current: Option<(String, String)> = None;
+
+        loop {
+            let event = reader.read_event_into(&mut buf);
+            match event {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    let is_start = matches!(event, Ok(Event::Start(_)));
+                    let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+
+                    let resource_type = values_resource_type(&tag_name);
+
+                    let mut defined_key = None;
+                    if let Some(res_type) = resource_type {
+                        // Get the name attribute
+                        for attr in e.attributes().flatten() {
+                            if attr.key.as_ref() == b"name" {
+                                let name = String::from_utf8_lossy(&attr.value).to_string();
+                                let key = resource_key(res_type, &name);
+
+                                let resource = AndroidResource {
+                                    name,
+                                    resource_type: res_type.to_string(),
+                                    file: file_path.to_path_buf(),
+                                    line,
+                                };
+
+                                let by_name =
+                                    analysis.defined.entry(res_type.to_string()).or_default();
+                                if is_default {
+                                    if matches!(res_type, "string" | "plurals" | "array") {
+                                        analysis.default_strings.insert(key.clone());
+                                    }
+                                    by_name.insert(key.1.clone(), resource);
+                                } else {
+                                    by_name.entry(key.1.clone()).or_insert(resource);
+                                }
+
+                                defined_key = Some(key);
+                                break;
+                            }
+                        }
+                    }
+
+                    if depth == 1 {
+                        current = defined_key;
+                        if let Some(key) = current.clone().filter(|(t, _)| t == "style") {
+                            let explicit = e
+                                .attributes()
+                                .flatten()
+                                .find(|a| a.key.as_ref() == b"parent")
+                                .map(|a| String::from_utf8_lossy(&a.value).to_string());
+                            if let Some(parent) = style_parent(&key.1, explicit.as_deref()) {
+                                analysis
+                                    .resource_refs
+                                    .entry(key)
+                                    .or_default()
+                                    .insert(resource_key("style", &parent));
+                            }
+                        }
+                    }
+                    if is_start {
+                        depth += 1;
+                    } else if depth == 1 {
+                        current = None;
+                    }
+                }
+                Ok(Event::End(_)) => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 1 {
+                        current = None;
+                    }
+                }
+                Ok(Event::Text(ref e)) => {
+                    // Count newlines in text content to track line number
+                    let bytes: &[u8] = e.as_ref();
+                    line += bytes.iter().filter(|&&b| b == b'\n').count();
+
+                    let text = String::from_utf8_lossy(bytes);
+                    for key in xml_references(&text) {
+                        match &current {
+                            Some(from) => {
+                                analysis
+                                    .resource_refs
+                                    .entry(from.clone())
+                                    .or_default()
+                                    .insert(key);
+                            }
+                            None => {
+                                analysis.referenced.insert(key);
+                            }
+                        }
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+
+    /// Collect resource references from Kotlin/Java code
+    fn collect_code_references(&self, project_root: &Path, analysis: &mut ResourceAnalysis) {
+        // Patterns for resource references:
+        // - R.string.name
+        // - R.color.name
+        // - R.dimen.name
+        // - @string/name (in XML)
+        // - getString(R.string.name)
+
+        let walker = walkdir::WalkDir::new(project_root)
+            .into_iter()
+            .filter_entry(is_project_entry);
+
+        for entry in walker.flatten() {
+            if entry.file_type().is_file() {
+                let path = entry.path();
+                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+                match ext {
+                    "kt" | "java" => self.extract_code_references(path, analysis),
+                    "xml" if !is_values_file(path) => self.extract_xml_references(path, analysis),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Extract R.type.name references from Kotlin/Java code
+    fn extract_code_references(&self, file_path: &Path, analysis: &mut ResourceAnalysis) {
+        let content = match fs::read_to_string(file_path) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        // Pattern: R.type.name
+        let r_pattern = regex::Regex::new(r"R\.(\w+)\.(\w+)").unwrap();
+
+        for cap in r_pattern.captures_iter(&content) {
+            let res_type = &cap[1];
+            let res_name = &cap[2];
+            analysis
+                .referenced
+                .insert((res_type.to_string(), res_name.to_string()));
+        }
+
+        // Raw resources played by URI: android.resource://<package>/raw/name
+        let raw_uri_pattern = regex::Regex::new(r"android\.resource://[^/]+/raw/(\w+)").unwrap();
+        for cap in raw_uri_pattern.captures_iter(&content) {
+            analysis.referenced.insert(resource_key("raw", &cap[1]));
+        }
+    }
+
+    /// Extract @type/name references from XML files
+    ///
+    /// Values files are skipped: references inside them belong to the
+    /// resource being defined and are recorded by `parse_values_xml`.
+    fn extract_xml_references(&self, file_path: &Path, analysis: &mut ResourceAnalysis) {
+        let content = match fs::read_to_string(file_path) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        analysis.referenced.extend(xml_references(&content));
+    }
+
+    /// Check if a resource should be skipped (common false positives)
+    fn should_skip_resource(&self, name: &str, res_type: &str) -> bool {
+        // Skip common Android-required resources
+        let required_strings = ["app_name", "content_description"];
+        if res_type == "string" && required_strings.contains(&name) {
+            return true;
+        }
+
+        // `res/raw/keep.xml` holds `tools:keep` / `tools:discard` rules for the shrinker
+        if res_type == "raw" && name == "keep" {
+            return true;
+        }
+
+        // Skip resources with "_" prefix (intentionally hidden)
+        if name.starts_with('_') {
+            return true;
+        }
+
+        false
+    }
+}
+
+/// Key a resource the way `R` names it (`Theme.App` -> `Theme_App`)
+fn resource_key(res_type: &str, name: &str) -> (String, String) {
+    (res_type.to_string(), name.replace('.', "_"))
+}
+
+/// Map a values XML tag to its resource type
+fn values_resource_type(tag_name: &str) -> Option<&'static str> {
+    match tag_name {
+        "string" => Some("string"),
+        "color" => Some("color"),
+        "dimen" => Some("dimen"),
+        "style" => Some("style"),
+        "string-array" => Some("array"),
+        "integer-array" => Some("array"),
+        "array" => Some("array"),
+        "plurals" => Some("plurals"),
+        "bool" => Some("bool"),
+        "integer" => Some("integer"),
+        "attr" => Some("attr"),
+        "declare-styleable" => Some("styleable"),
+        _ => None,
+    }
+}
+
+/// `@type/name` references in XML (`@style/Theme.App`, `@color/primary`)
+fn xml_references(text: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    static REF_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"@(\w+)/([\w.]+)").unwrap());
+
+    REF_PATTERN
+        .captures_iter(text)
+        .map(|cap| resource_key(&cap[1], &cap[2]))
+}
+
+/// Parent of a style: the `parent` attribute, or the name up to the last dot
+///
+/// An explicit `parent=""` means no parent. Framework parents
+/// (`@android:style/...`, `android:Theme...`) are not project resources.
+fn style_parent(name: &str, explicit: Option<&str>) -> Option<String> {
+    match explicit {
+        Some(parent) => {
+            let parent = parent.strip_prefix("@style/").unwrap_or(parent);
+            let is_framework = parent.is_empty() || parent.starts_with("@") || parent.contains(':');
+            (!is_framework).then(|| parent.to_string())
+        }
+        None => name.rsplit_once('.').map(|(parent, _)| parent.to_string()),
+    }
+}
+
+/// Mark everything reachable from a referenced resource as referenced
+fn propagate_references(analysis: &mut ResourceAnalysis) {
+    let mut pending: Vec<(String, String)> = analysis.referenced.iter().cloned().collect();
+    while let Some(key) = pending.pop() {
+        let Some(targets) = analysis.resource_refs.get(&key) else {
+            continue;
+        };
+        for target in targets {
+            if analysis.referenced.insert(target.clone()) {
+                pending.push(target.clone());
+            }
+        }
+    }
+}
+
+/// Gradle module owning `res_dir` (`<module>/src/<source set>/res`): the
+/// nearest ancestor with a build script
+fn module_dir(res_dir: &Path) -> Option<&Path> {
+    res_dir
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("build.gradle").is_file() || dir.join("build.gradle.kts").is_file())
+}
+
+/// Whether `path` is in a `values` or `values-*` directory
+fn is_values_file(path: &Path) -> bool {
+    path.parent()
+        .and_then(|dir| dir.file_name())
+        .and_then(|dir| dir.to_str())
+        .is_some_and(|dir| dir == "values" || dir.starts_with("values-"))
+}
+
+/// Skip hidden, build and generated directories below the project root
+fn is_project_entry(entry: &walkdir::DirEntry) -> bool {
+    if entry.depth() == 0 {
+        return true;
+    }
+    let name = entry.file_name().to_string_lossy();
+    !name.starts_with('.') && name != "build" && name != "generated"
+}
+
+impl Default for ResourceDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detector_creation() {
+        let detector = ResourceDetector::new();
+        assert_eq!(detector.min_references, 1);
+    }
+
+    #[test]
+    fn test_parse_strings_xml() {
+        let temp_dir = TempDir::new().unwrap();
+        let res_dir = temp_dir.path().join("res").join("values");
+        fs::create_dir_all(&res_dir).unwrap();
+
+        let strings_xml = res_dir.join("strings.xml");
+        fs::write(
+            &strings_xml,
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<resources>
+    <string name="test_string">Test</string>
+    <string name="another_string">Another</string>
+</resources>"#,
+        )
+        .unwrap();
+
+        let mut analysis = ResourceAnalysis::default();
+        let detector = ResourceDetector::new();
+        detector.parse_values_xml(&strings_xml, &mut analysis);
+
+        assert!(analysis.defined.contains_key("string"));
+        let strings = analysis.defined.get("string").unwrap();
+        assert!(strings.contains_key("test_string"));
+        assert!(strings.contains_key("another_string"));
+    }
+
+    fn unused_names(analysis: &ResourceAnalysis) -> Vec<String> {
+        let mut names: Vec<_> = analysis
+            .unused
+            .iter()
+            .map(|r| format!("{}/{}", r.resource_type, r.name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_style_parent() {
+        assert_eq!(
+            style_parent("Theme.App.Dark", None).as_deref(),
+            Some("Theme.App")
+        );
+        assert_eq!(
+            style_parent("Dark", Some("@style/Theme.App")).as_deref(),
+            Some("Theme.App")
+        );
+        assert_eq!(
+            style_parent("Theme.App", Some("Base.Theme")).as_deref(),
+            Some("Base.Theme")
+        );
+        assert_eq!(style_parent("Theme.App", Some("")), None);
+        assert_eq!(
+            style_parent("Theme.App", Some("@android:style/Theme")),
+            None
+        );
+        assert_eq!(style_parent("Theme", Some("android:Theme.Material")), None);
+        assert_eq!(style_parent("Button", None), None);
+    }
+
+    #[test]
+    fn test_style_chains_and_manifest_themes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(
+            root,
+            "res/values/themes.xml",
+            r#"<resources>
+    <style name="Base.Theme.App" parent="Theme.Material3.DayNight">
+        <item name="colorPrimary">@color/brand</item>
+    </style>
+    <style name="Theme.App" parent="Base.Theme.App" />
+    <style name="Theme.App.Splash">
+        <item name="android:windowBackground">@color/splash_bg</item>
+    </style>
+    <style name="Theme.Legacy" parent="Base.Theme.App">
+        <item name="colorPrimary">@color/legacy</item>
+    </style>
+    <style name="Widget.Button" parent="">
+        <item name="android:padding">@dimen/button_padding</item>
+    </style>
+    <style name="Widget.Button.Big">
+        <item name="android:padding">@dimen/big_padding</item>
+    </style>
+</resources>"#,
+        );
+        write(
+            root,
+            "res/values/colors.xml",
+            r#"<resources>
+    <color name="brand">#6200EE</color>
+    <color name="splash_bg">@color/brand</color>
+    <color name="legacy">#000000</color>
+</resources>"#,
+        );
+        write(
+            root,
+            "res/values/dimens.xml",
+            r#"<resources>
+    <dimen name="button_padding">8dp</dimen>
+    <dimen name="big_padding">16dp</dimen>
+</resources>"#,
+        );
+        write(
+            root,
+            "AndroidManifest.xml",
+            r#"<application android:theme="@style/Theme.App.Splash"/>"#,
+        );
+        write(
+            root,
+            "src/Main.kt",
+            "setTheme(R.style.Theme_App)\nval s = R.style.Widget_Button",
+        );
+
+        let analysis = ResourceDetector::new().analyze(root);
+
+        assert_eq!(
+            unused_names(&analysis),
+            [
+                "color/legacy",
+                "dimen/big_padding",
+                "style/Theme.Legacy",
+                "style/Widget.Button.Big",
+            ]
+        );
+    }
+}
@@ -0,0 +1,454 @@
+//! Unused resource qualifier directory detection
+//!
+//! An alternative resource directory (`layout-land/`, `values-sw600dp/`,
+//! `drawable-night/`) only matters when Android picks it over the default
+//! one. A whole directory is dead when:
+//!
+//! - none of its resources has a default any more and none is referenced,
+//!   so it overrides nothing
+//! - its configuration can't occur: `-land` / `-port` while the manifest
+//!   locks every activity to the other orientation, or `-small` / `-large` /
+//!   `-xlarge` screens the manifest doesn't support
+//! - its `-vNN` qualifier is at or below the module's `minSdk`, so it is
+//!   always picked and its contents belong in the default directory
+//!
+//! Locale directories are handled by orphaned translation detection, and
+//! density-only directories (`drawable-hdpi/`) by drawable grouping.
+
+use super::drawables::resource_name;
+use super::{
+    module_dir, resource_key, translations, values_resource_type, ResourceAnalysis,
+    ResourceDetector,
+};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Density qualifiers; a directory qualified only by density provides defaults
+const DENSITY_QUALIFIERS: &[&str] = &[
+    "ldpi", "mdpi", "tvdpi", "hdpi", "xhdpi", "xxhdpi", "xxxhdpi", "nodpi", "anydpi",
+];
+
+const PORTRAIT_ORIENTATIONS: &[&str] = &[
+    "portrait",
+    "sensorPortrait",
+    "userPortrait",
+    "reversePortrait",
+];
+
+const LANDSCAPE_ORIENTATIONS: &[&str] = &[
+    "landscape",
+    "sensorLandscape",
+    "userLandscape",
+    "reverseLandscape",
+];
+
+/// A qualifier directory that can be removed or merged
+#[derive(Debug, Clone)]
+pub struct QualifierDirectory {
+    /// The directory (e.g., `app/src/main/res/layout-land`)
+    pub dir: PathBuf,
+    /// Number of resources it provides
+    pub resource_count: usize,
+    /// Combined size of its files in bytes
+    pub total_bytes: u64,
+    pub issue: QualifierIssue,
+}
+
+/// Why a qualifier directory is never resolved differently
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QualifierIssue {
+    /// None of its resources has a default and none is referenced
+    NoDefaults,
+    /// `-land` / `-port` while every activity is locked to the other orientation
+    LockedOrientation,
+    /// A screen size the manifest's `<supports-screens>` disables
+    UnsupportedScreenSize,
+    /// `-vNN` at or below the module's `minSdk`
+    BelowMinSdk(u32),
+}
+
+impl QualifierIssue {
+    pub fn description(&self) -> String {
+        match self {
+            QualifierIssue::NoDefaults => "overrides no default resource".to_string(),
+            QualifierIssue::LockedOrientation => {
+                "every activity is locked to the other orientation".to_string()
+            }
+            QualifierIssue::UnsupportedScreenSize => {
+                "screen size disabled by <supports-screens>".to_string()
+            }
+            QualifierIssue::BelowMinSdk(min_sdk) => {
+                format!(
+                    "always selected on minSdk {}; merge into the default",
+                    min_sdk
+                )
+            }
+        }
+    }
+}
+
+/// Configuration of the module a `res/` directory belongs to
+#[derive(Debug, Default)]
+struct ModuleConfig {
+    min_sdk: Option<u32>,
+    /// `android:screenOrientation` of each activity ("" when unset)
+    orientations: Vec<String>,
+    /// Screen sizes with `android:<size>Screens="false"`
+    unsupported_screens: Vec<String>,
+}
+
+/// A resource subdirectory split into its type and qualifiers
+struct ResourceDir {
+    path: PathBuf,
+    resource_type: String,
+    qualifiers: Vec<String>,
+}
+
+impl ResourceDir {
+    fn new(path: PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_string();
+        let mut parts = name.split('-');
+        let resource_type = parts.next()?.to_string();
+        Some(Self {
+            qualifiers: parts.map(str::to_string).collect(),
+            resource_type,
+            path,
+        })
+    }
+
+    fn provides_defaults(&self) -> bool {
+        self.qualifiers
+            .iter()
+            .all(|q| DENSITY_QUALIFIERS.contains(&q.as_str()))
+    }
+
+    fn is_locale(&self) -> bool {
+        self.path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(translations::locale_qualifier)
+            .is_some()
+    }
+
+    /// Resources this directory provides, keyed as in `R`, and their total size
+    fn resources(&self) -> (Vec<(String, String)>, u64) {
+        static NAME_PATTERN: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r#"<([\w-]+)\s[^>]*?\bname="([^"]+)""#).unwrap());
+
+        let mut keys = Vec::new();
+        let mut bytes = 0;
+        let Ok(entries) = fs::read_dir(&self.path) else {
+            return (keys, bytes);
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = resource_name(&path) else {
+                continue;
+            };
+            bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if self.resource_type != "values" {
+                keys.push(resource_key(&self.resource_type, name));
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            for cap in NAME_PATTERN.captures_iter(&content) {
+                if let Some(res_type) = values_resource_type(&cap[1]) {
+                    keys.push(resource_key(res_type, &cap[2]));
+                }
+            }
+        }
+        (keys, bytes)
+    }
+}
+
+impl ResourceDetector {
+    /// Find qualifier directories that Android never resolves differently
+    pub(super) fn find_unused_qualifier_dirs(
+        &self,
+        res_dirs: &[PathBuf],
+        analysis: &mut ResourceAnalysis,
+    ) {
+        let dirs: Vec<(&PathBuf, Vec<ResourceDir>)> = res_dirs
+            .iter()
+            .map(|res_dir| {
+                let mut dirs: Vec<ResourceDir> = fs::read_dir(res_dir)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .filter_map(ResourceDir::new)
+                    .filter(|d| !d.is_locale())
+                    .collect();
+                dirs.sort_by(|a, b| a.path.cmp(&b.path));
+                (res_dir, dirs)
+            })
+            .collect();
+
+        // Defaults may come from another source set (`main` vs a flavor)
+        let defaults: HashSet<(String, String)> = dirs
+            .iter()
+            .flat_map(|(_, dirs)| dirs)
+            .filter(|d| d.provides_defaults())
+            .flat_map(|d| d.resources().0)
+            .collect();
+
+        for (res_dir, dirs) in dirs {
+            let config = module_config(res_dir);
+            for dir in dirs.iter().filter(|d| !d.provides_defaults()) {
+                let (keys, total_bytes) = dir.resources();
+                let overrides_nothing = keys
+                    .iter()
+                    .all(|k| !defaults.contains(k) && !analysis.referenced.contains(k));
+                let Some(issue) = qualifier_issue(dir, &config)
+                    .or_else(|| overrides_nothing.then_some(QualifierIssue::NoDefaults))
+                else {
+                    continue;
+                };
+                analysis.unused_qualifier_dirs.push(QualifierDirectory {
+                    dir: dir.path.clone(),
+                    resource_count: keys.len(),
+                    total_bytes,
+                    issue,
+                });
+            }
+        }
+    }
+}
+
+/// Qualifiers the module's configuration rules out or makes redundant
+fn qualifier_issue(dir: &ResourceDir, config: &ModuleConfig) -> Option<QualifierIssue> {
+    let locked_to = |orientations: &[&str]| {
+        !config.orientations.is_empty()
+            && config
+                .orientations
+                .iter()
+                .all(|o| orientations.contains(&o.as_str()))
+    };
+
+    for qualifier in &dir.qualifiers {
+        let issue = match qualifier.as_str() {
+            "land" if locked_to(PORTRAIT_ORIENTATIONS) => QualifierIssue::LockedOrientation,
+            "port" if locked_to(LANDSCAPE_ORIENTATIONS) => QualifierIssue::LockedOrientation,
+            size if config.unsupported_screens.iter().any(|s| s == size) => {
+                QualifierIssue::UnsupportedScreenSize
+            }
+            version => match (version.strip_prefix('v'), config.min_sdk) {
+                (Some(level), Some(min_sdk))
+                    if level.parse::<u32>().is_ok_and(|level| level <= min_sdk) =>
+                {
+                    QualifierIssue::BelowMinSdk(min_sdk)
+                }
+                _ => continue,
+            },
+        };
+        return Some(issue);
+    }
+    None
+}
+
+/// Read `minSdk`, activity orientations and `<supports-screens>` for the
+/// module that owns `res_dir` (`<module>/src/<source set>/res`)
+fn module_config(res_dir: &Path) -> ModuleConfig {
+    static MIN_SDK: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"minSdk(?:Version)?\s*(?:=|\()?\s*"?(\d+)"#).unwrap());
+    static ACTIVITY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<activity\s[^>]*>").unwrap());
+    static ORIENTATION: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"android:screenOrientation="(\w+)""#).unwrap());
+    static SUPPORTS_SCREENS: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"android:(small|normal|large|xlarge)Screens="false""#).unwrap()
+    });
+
+    let mut config = ModuleConfig::default();
+
+    if let Some(module_dir) = module_dir(res_dir) {
+        config.min_sdk = ["build.gradle.kts", "build.gradle"]
+            .iter()
+            .filter_map(|name| fs::read_to_string(module_dir.join(name)).ok())
+            .find_map(|gradle| MIN_SDK.captures(&gradle)?[1].parse().ok());
+    }
+
+    let manifest = res_dir
+        .parent()
+        .map(|source_set| source_set.join("AndroidManifest.xml"));
+    if let Some(manifest) = manifest.and_then(|m| fs::read_to_string(m).ok()) {
+        config.min_sdk = config.min_sdk.or_else(|| {
+            MIN_SDK
+                .captures(&manifest)
+                .and_then(|cap| cap[1].parse().ok())
+        });
+        config.orientations = ACTIVITY
+            .find_iter(&manifest)
+            .map(|tag| {
+                ORIENTATION
+                    .captures(tag.as_str())
+                    .map(|cap| cap[1].to_string())
+                    .unwrap_or_default()
+            })
+            .collect();
+        if let Some(start) = manifest.find("<supports-screens") {
+            let end = manifest[start..]
+                .find('>')
+                .map_or(manifest.len(), |i| start + i);
+            config.unsupported_screens = SUPPORTS_SCREENS
+                .captures_iter(&manifest[start..end])
+                .map(|cap| cap[1].to_string())
+                .collect();
+        }
+    }
+
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unused_qualifier_dirs() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/build.gradle.kts",
+            "android {\n    defaultConfig {\n        minSdk = 24\n    }\n}",
+        );
+        write(
+            root,
+            "app/src/main/AndroidManifest.xml",
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android">
+    <supports-screens android:smallScreens="false" android:largeScreens="true" />
+    <application>
+        <activity android:name=".MainActivity" android:screenOrientation="portrait" />
+        <activity android:name=".DetailActivity" android:screenOrientation="sensorPortrait" />
+    </application>
+</manifest>"#,
+        );
+        write(
+            root,
+            "app/src/main/res/layout/activity_main.xml",
+            "<LinearLayout/>",
+        );
+        write(
+            root,
+            "app/src/main/res/layout-land/activity_main.xml",
+            "<LinearLayout/>",
+        );
+        write(
+            root,
+            "app/src/main/res/layout-small/activity_main.xml",
+            "<LinearLayout/>",
+        );
+        write(
+            root,
+            "app/src/main/res/layout-sw600dp/activity_main.xml",
+            "<LinearLayout/>",
+        );
+        // The default `legacy_header.xml` was deleted
+        write(
+            root,
+            "app/src/main/res/layout-sw600dp-v26/legacy_header.xml",
+            "<FrameLayout/>",
+        );
+        write(
+            root,
+            "app/src/main/res/values/dimens.xml",
+            r#"<resources><dimen name="margin">8dp</dimen></resources>"#,
+        );
+        write(
+            root,
+            "app/src/main/res/values-v21/styles.xml",
+            r#"<resources><style name="Theme.App" parent="Theme.Material3.DayNight"/></resources>"#,
+        );
+        write(
+            root,
+            "app/src/main/res/values-w820dp/dimens.xml",
+            r#"<resources><dimen name="margin">24dp</dimen></resources>"#,
+        );
+        write(
+            root,
+            "app/src/main/res/values-fr/strings.xml",
+            r#"<resources><string name="title">Titre</string></resources>"#,
+        );
+        write(root, "app/src/main/res/drawable-hdpi/logo.png", "png");
+        write(
+            root,
+            "app/src/main/java/com/app/MainActivity.kt",
+            "setContentView(R.layout.activity_main); getDimension(R.dimen.margin)",
+        );
+
+        let analysis = ResourceDetector::new().analyze(root);
+
+        let found: Vec<_> = analysis
+            .unused_qualifier_dirs
+            .iter()
+            .map(|d| (d.dir.file_name().unwrap().to_str().unwrap(), d.issue))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("layout-land", QualifierIssue::LockedOrientation),
+                ("layout-small", QualifierIssue::UnsupportedScreenSize),
+                ("layout-sw600dp-v26", QualifierIssue::NoDefaults),
+                ("values-v21", QualifierIssue::BelowMinSdk(24)),
+            ]
+        );
+        assert_eq!(analysis.unused_qualifier_dirs[0].resource_count, 1);
+    }
+
+    #[test]
+    fn test_qualifier_dir_without_defaults() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "app/src/main/res/layout/screen.xml",
+            "<LinearLayout/>",
+        );
+        write(
+            root,
+            "app/src/main/res/layout-sw600dp/screen.xml",
+            "<LinearLayout/>",
+        );
+        write(
+            root,
+            "app/src/main/res/layout-sw600dp/old_pane.xml",
+            "<LinearLayout/>",
+        );
+        write(
+            root,
+            "app/src/main/res/layout-w900dp/old_pane.xml",
+            "<LinearLayout/>",
+        );
+        write(
+            root,
+            "app/src/main/res/layout-w1200dp/tablet_pane.xml",
+            "<LinearLayout/>",
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/Main.kt",
+            "inflate(R.layout.screen); inflate(R.layout.tablet_pane)",
+        );
+
+        let analysis = ResourceDetector::new().analyze(root);
+
+        let found: Vec<_> = analysis
+            .unused_qualifier_dirs
+            .iter()
+            .map(|d| (d.dir.file_name().unwrap().to_str().unwrap(), d.issue))
+            .collect();
+        assert_eq!(found, [("layout-w900dp", QualifierIssue::NoDefaults)]);
+        assert_eq!(
+            QualifierIssue::BelowMinSdk(21).description(),
+            "always selected on minSdk 21; merge into the default"
+        );
+    }
+}
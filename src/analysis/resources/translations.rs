@@ -0,0 +1,320 @@
+//! Orphaned translation detection
+//!
+//! Translations live in locale-qualified values directories (`values-fr/`,
+//! `values-pt-rBR/`, `values-b+sr+Latn/`). A translated `<string>`,
+//! `<plurals>` or `<string-array>` is orphaned when the default `values/`
+//! directory no longer defines its key, or when the default string itself is
+//! unused. Either way it can be deleted without changing the app.
+
+use super::{resource_key, ResourceAnalysis, ResourceDetector};
+use crate::parser::line_of;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Qualifiers that look like a language code but aren't one
+const NON_LOCALE_QUALIFIERS: &[&str] = &["car", "hdr"];
+
+/// A translated string resource in a locale-qualified values directory
+#[derive(Debug, Clone)]
+pub struct Translation {
+    /// Resource name (e.g., "welcome_title")
+    pub name: String,
+    /// Resource type ("string", "plurals" or "array")
+    pub resource_type: String,
+    /// Locale qualifier (e.g., "fr", "pt-rBR", "b+sr+Latn")
+    pub locale: String,
+    /// File where the translation is defined
+    pub file: PathBuf,
+    /// Line number in the file
+    pub line: usize,
+    /// Byte range of the element in the file, used for safe delete
+    pub span: (usize, usize),
+}
+
+/// Why a translation is safe to delete
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationIssue {
+    /// The default `values/` directory no longer defines the key
+    MissingDefault,
+    /// The default string exists but is never referenced
+    UnusedString,
+}
+
+impl TranslationIssue {
+    pub fn description(&self) -> &'static str {
+        match self {
+            TranslationIssue::MissingDefault => "not in default values/",
+            TranslationIssue::UnusedString => "translation of unused string",
+        }
+    }
+}
+
+/// A translation that can be removed
+#[derive(Debug, Clone)]
+pub struct OrphanedTranslation {
+    pub translation: Translation,
+    pub issue: TranslationIssue,
+}
+
+impl ResourceDetector {
+    /// Collect string translations from a locale-qualified values directory
+    pub(super) fn parse_translations(
+        &self,
+        values_dir: &Path,
+        locale: &str,
+        analysis: &mut ResourceAnalysis,
+    ) {
+        let Ok(entries) = fs::read_dir(values_dir) else {
+            return;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().map(|e| e == "xml").unwrap_or(false))
+            .collect();
+        files.sort();
+
+        for file in files {
+            if let Ok(content) = fs::read_to_string(&file) {
+                analysis
+                    .translations
+                    .extend(parse_translation_xml(&content, &file, locale));
+            }
+        }
+    }
+
+    /// Match translations against the default strings
+    pub(super) fn find_orphaned_translations(&self, analysis: &mut ResourceAnalysis) {
+        let mut orphaned = Vec::new();
+        for translation in &analysis.translations {
+            let key = resource_key(&translation.resource_type, &translation.name);
+            let issue = if !analysis.default_strings.contains(&key) {
+                TranslationIssue::MissingDefault
+            } else if !analysis.referenced.contains(&key)
+                && !self.should_skip_resource(&translation.name, &translation.resource_type)
+            {
+                TranslationIssue::UnusedString
+            } else {
+                continue;
+            };
+            orphaned.push(OrphanedTranslation {
+                translation: translation.clone(),
+                issue,
+            });
+        }
+
+        orphaned.sort_by(|a, b| {
+            a.translation
+                .file
+                .cmp(&b.translation.file)
+                .then(a.translation.line.cmp(&b.translation.line))
+        });
+        analysis.orphaned_translations = orphaned;
+    }
+}
+
+/// Locale of a values directory (`values-pt-rBR` -> `pt-rBR`), if it has one
+///
+/// MCC/MNC qualifiers may precede the language; anything else (night,
+/// v21, sw600dp, ...) means the directory is not a translation.
+pub(super) fn locale_qualifier(dir_name: &str) -> Option<String> {
+    let mut parts = dir_name
+        .strip_prefix("values-")?
+        .split('-')
+        .skip_while(|p| p.starts_with("mcc") || p.starts_with("mnc"));
+
+    let language = parts.next()?;
+    if language.starts_with("b+") {
+        return Some(language.to_string());
+    }
+    let is_language = (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_lowercase())
+        && !NON_LOCALE_QUALIFIERS.contains(&language);
+    if !is_language {
+        return None;
+    }
+
+    match parts.next() {
+        Some(region)
+            if region.len() == 3
+                && region.starts_with('r')
+                && region[1..].chars().all(|c| c.is_ascii_uppercase()) =>
+        {
+            Some(format!("{}-{}", language, region))
+        }
+        _ => Some(language.to_string()),
+    }
+}
+
+/// Extract `<string>`, `<plurals>` and `<string-array>` elements with their byte spans
+fn parse_translation_xml(content: &str, file: &Path, locale: &str) -> Vec<Translation> {
+    let mut reader = Reader::from_str(content);
+    let mut translations = Vec::new();
+    // (name, type, start byte) of the top-level element being read
+    let mut open: Option<(String, &'static str, usize)> = None;
+    let mut depth = 0usize;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = match reader.read_event() {
+            Ok(Event::Eof) | Err(_) => break,
+            Ok(event) => event,
+        };
+        let end = reader.buffer_position() as usize;
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                if depth == 1 && open.is_none() {
+                    let res_type = match e.name().as_ref() {
+                        b"string" => Some("string"),
+                        b"plurals" => Some("plurals"),
+                        b"string-array" => Some("array"),
+                        _ => None,
+                    };
+                    let name = e
+                        .attributes()
+                        .flatten()
+                        .find(|a| a.key.as_ref() == b"name")
+                        .map(|a| String::from_utf8_lossy(&a.value).to_string());
+                    if let (Some(res_type), Some(name)) = (res_type, name) {
+                        if is_empty {
+                            translations.push(translation(
+                                content, file, locale, name, res_type, start, end,
+                            ));
+                        } else {
+                            open = Some((name, res_type, start));
+                        }
+                    }
+                }
+                if !is_empty {
+                    depth += 1;
+                }
+            }
+            Event::End(_) => {
+                depth = depth.saturating_sub(1);
+                if depth == 1 {
+                    if let Some((name, res_type, start)) = open.take() {
+                        translations.push(translation(
+                            content, file, locale, name, res_type, start, end,
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    translations
+}
+
+fn translation(
+    content: &str,
+    file: &Path,
+    locale: &str,
+    name: String,
+    resource_type: &str,
+    start: usize,
+    end: usize,
+) -> Translation {
+    Translation {
+        name,
+        resource_type: resource_type.to_string(),
+        locale: locale.to_string(),
+        file: file.to_path_buf(),
+        line: line_of(content, start),
+        span: (start, end),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_locale_qualifier() {
+        assert_eq!(locale_qualifier("values-fr").as_deref(), Some("fr"));
+        assert_eq!(locale_qualifier("values-pt-rBR").as_deref(), Some("pt-rBR"));
+        assert_eq!(locale_qualifier("values-fr-night").as_deref(), Some("fr"));
+        assert_eq!(locale_qualifier("values-mcc310-es").as_deref(), Some("es"));
+        assert_eq!(
+            locale_qualifier("values-b+sr+Latn").as_deref(),
+            Some("b+sr+Latn")
+        );
+        assert_eq!(locale_qualifier("values"), None);
+        assert_eq!(locale_qualifier("values-night"), None);
+        assert_eq!(locale_qualifier("values-v21"), None);
+        assert_eq!(locale_qualifier("values-car"), None);
+    }
+
+    #[test]
+    fn test_parse_translation_spans() {
+        let content = "<resources>\n    <string name=\"a\">A</string>\n    <plurals name=\"b\">\n        <item quantity=\"one\">B</item>\n    </plurals>\n    <color name=\"c\">#fff</color>\n</resources>\n";
+        let translations = parse_translation_xml(content, Path::new("strings.xml"), "fr");
+
+        assert_eq!(translations.len(), 2);
+        let a = &translations[0];
+        assert_eq!(
+            (a.name.as_str(), a.resource_type.as_str(), a.line),
+            ("a", "string", 2)
+        );
+        assert_eq!(
+            &content[a.span.0..a.span.1],
+            "<string name=\"a\">A</string>"
+        );
+        let b = &translations[1];
+        assert_eq!((b.resource_type.as_str(), b.line), ("plurals", 3));
+        assert!(content[b.span.0..b.span.1].ends_with("</plurals>"));
+    }
+
+    #[test]
+    fn test_orphaned_translations() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "res/values/strings.xml",
+            r#"<resources>
+    <string name="title">Title</string>
+    <string name="unused">Unused</string>
+</resources>"#,
+        );
+        write(
+            root,
+            "res/values-fr/strings.xml",
+            r#"<resources>
+    <string name="title">Titre</string>
+    <string name="unused">Inutilisé</string>
+    <string name="removed">Supprimé</string>
+</resources>"#,
+        );
+        write(root, "src/Main.kt", "val t = R.string.title");
+
+        let analysis = ResourceDetector::new().analyze(root);
+
+        let orphans: Vec<_> = analysis
+            .orphaned_translations
+            .iter()
+            .map(|o| (o.translation.name.as_str(), o.issue))
+            .collect();
+        assert_eq!(
+            orphans,
+            [
+                ("unused", TranslationIssue::UnusedString),
+                ("removed", TranslationIssue::MissingDefault),
+            ]
+        );
+        assert!(analysis
+            .orphaned_translations
+            .iter()
+            .all(|o| o.translation.locale == "fr"));
+
+        // Translations are not reported as unused resources of their own
+        let unused: Vec<_> = analysis.unused.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(unused, ["unused"]);
+    }
+}
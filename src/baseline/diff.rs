@@ -0,0 +1,179 @@
+//! Comparison of two JSON reports
+//!
+//! Findings are paired by rule and [`IssueFingerprint`], the way baselines
+//! match them, so a finding whose declaration moved a few lines, or kept its
+//! qualified name, persists instead of showing up as both fixed and new.
+
+use super::{BaselineError, IssueFingerprint};
+use crate::report::JSON_SCHEMA_VERSION;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Finding read back from a JSON report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportedIssue {
+    pub code: String,
+    pub severity: String,
+    pub message: String,
+    pub file: String,
+    pub line: usize,
+    #[serde(default)]
+    pub column: usize,
+    pub declaration: ReportedDeclaration,
+}
+
+/// Declaration of a finding read back from a JSON report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportedDeclaration {
+    pub name: String,
+    pub kind: String,
+    #[serde(default)]
+    pub fully_qualified_name: Option<String>,
+}
+
+impl ReportedIssue {
+    pub fn fingerprint(&self) -> IssueFingerprint {
+        IssueFingerprint {
+            file: self.file.clone(),
+            name: self.declaration.name.clone(),
+            kind: self.declaration.kind.clone(),
+            line: self.line,
+            fqn: self.declaration.fully_qualified_name.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonReport {
+    /// Missing before schema 1.3, where `version` holds it
+    #[serde(default)]
+    schema_version: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+    issues: Vec<ReportedIssue>,
+}
+
+/// Load the findings of a report written with `--format json`, by any
+/// version with the current major schema version
+pub fn load_report(path: &Path) -> Result<Vec<ReportedIssue>, BaselineError> {
+    let file = fs::File::open(path)?;
+    let report: JsonReport = serde_json::from_reader(BufReader::new(file))?;
+    let major = |version: &str| version.split('.').next().unwrap_or_default().to_string();
+    let version = report.schema_version.or(report.version).unwrap_or_default();
+    if major(&version) != major(JSON_SCHEMA_VERSION) {
+        return Err(BaselineError::VersionMismatch);
+    }
+    Ok(report.issues)
+}
+
+/// Findings of a new report, split by whether an old report had them
+#[derive(Debug, Default, Serialize)]
+pub struct ReportDiff {
+    /// Findings only the new report has
+    pub new: Vec<ReportedIssue>,
+    /// Findings only the old report has
+    pub fixed: Vec<ReportedIssue>,
+    /// Findings both reports have, as the new report gives them
+    pub persisting: Vec<ReportedIssue>,
+}
+
+impl ReportDiff {
+    pub fn compare(old: Vec<ReportedIssue>, new: Vec<ReportedIssue>) -> Self {
+        // Old findings that can still be paired, by the parts of the
+        // fingerprint that must match exactly
+        let mut unpaired: HashMap<(String, String, String, String), Vec<ReportedIssue>> =
+            HashMap::new();
+        for issue in old {
+            unpaired.entry(Self::key(&issue)).or_default().push(issue);
+        }
+
+        let mut diff = Self::default();
+        for issue in new {
+            let fingerprint = issue.fingerprint();
+            let candidates = unpaired.entry(Self::key(&issue)).or_default();
+            // Of several matches (overloads, drifted lines), the closest
+            let paired = candidates
+                .iter()
+                .enumerate()
+                .filter(|(_, old)| old.fingerprint().matches_fingerprint(&fingerprint))
+                .min_by_key(|(_, old)| old.line.abs_diff(issue.line))
+                .map(|(index, _)| index);
+            match paired {
+                Some(index) => {
+                    candidates.swap_remove(index);
+                    diff.persisting.push(issue);
+                }
+                None => diff.new.push(issue),
+            }
+        }
+
+        diff.fixed = unpaired.into_values().flatten().collect();
+        diff.fixed
+            .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        diff
+    }
+
+    fn key(issue: &ReportedIssue) -> (String, String, String, String) {
+        (
+            issue.code.clone(),
+            issue.file.clone(),
+            issue.declaration.name.clone(),
+            issue.declaration.kind.clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(code: &str, name: &str, line: usize, fqn: Option<&str>) -> ReportedIssue {
+        ReportedIssue {
+            code: code.to_string(),
+            severity: "warning".to_string(),
+            message: format!("class '{}' is never used", name),
+            file: "src/App.kt".to_string(),
+            line,
+            column: 1,
+            declaration: ReportedDeclaration {
+                name: name.to_string(),
+                kind: "class".to_string(),
+                fully_qualified_name: fqn.map(str::to_string),
+            },
+        }
+    }
+
+    #[test]
+    fn test_report_diff() {
+        let old = vec![
+            issue("DC001", "Moved", 10, Some("com.app.Moved")),
+            issue("DC001", "Shifted", 20, None),
+            issue("DC001", "Removed", 30, None),
+            issue("DC001", "Twice", 40, None),
+        ];
+        let new = vec![
+            issue("DC001", "Moved", 90, Some("com.app.Moved")),
+            issue("DC001", "Shifted", 25, None),
+            issue("DC001", "Twice", 41, None),
+            issue("DC001", "Twice", 45, None),
+            issue("DC002", "Shifted", 25, None),
+        ];
+        let diff = ReportDiff::compare(old, new);
+        let names = |issues: &[ReportedIssue]| -> Vec<String> {
+            issues
+                .iter()
+                .map(|i| format!("{}:{}:{}", i.code, i.declaration.name, i.line))
+                .collect()
+        };
+
+        assert_eq!(
+            names(&diff.persisting),
+            ["DC001:Moved:90", "DC001:Shifted:25", "DC001:Twice:41"]
+        );
+        assert_eq!(names(&diff.new), ["DC001:Twice:45", "DC002:Shifted:25"]);
+        assert_eq!(names(&diff.fixed), ["DC001:Removed:30"]);
+    }
+}
@@ -11,6 +11,10 @@ use thiserror::Error;
 
 use crate::analysis::DeadCode;
 
+mod diff;
+
+pub use diff::{load_report, ReportDiff};
+
 /// Baseline errors
 #[derive(Error, Debug)]
 pub enum BaselineError {
@@ -54,42 +58,37 @@ impl IssueFingerprint {
 
         Self {
             file,
-            name: dc.declaration.name.clone(),
+            name: dc.declaration.name.to_string(),
             kind: dc.declaration.kind.display_name().to_string(),
             line: dc.declaration.location.line,
-            fqn: dc.declaration.fully_qualified_name.clone(),
+            fqn: dc
+                .declaration
+                .fully_qualified_name
+                .as_deref()
+                .map(str::to_string),
         }
     }
 
     /// Check if this fingerprint matches a dead code issue (with some tolerance)
     pub fn matches(&self, dc: &DeadCode, project_root: &Path) -> bool {
-        let dc_file = dc
-            .declaration
-            .location
-            .file
-            .strip_prefix(project_root)
-            .unwrap_or(&dc.declaration.location.file)
-            .to_string_lossy()
-            .to_string();
+        self.matches_fingerprint(&Self::from_dead_code(dc, project_root))
+    }
 
+    /// Check if two fingerprints identify the same declaration (with some
+    /// tolerance)
+    pub fn matches_fingerprint(&self, other: &IssueFingerprint) -> bool {
         // Must match file, name, and kind exactly
-        if self.file != dc_file || self.name != dc.declaration.name {
-            return false;
-        }
-
-        let dc_kind = dc.declaration.kind.display_name();
-        if self.kind != dc_kind {
+        if self.file != other.file || self.name != other.name || self.kind != other.kind {
             return false;
         }
 
         // If FQN is available, use it for more precise matching
-        if self.fqn.is_some() && dc.declaration.fully_qualified_name.is_some() {
-            return self.fqn == dc.declaration.fully_qualified_name;
+        if self.fqn.is_some() && other.fqn.is_some() {
+            return self.fqn == other.fqn;
         }
 
         // Allow line number to drift by up to 10 lines
-        let line_diff = (self.line as i64 - dc.declaration.location.line as i64).abs();
-        line_diff <= 10
+        self.line.abs_diff(other.line) <= 10
     }
 }
 
@@ -148,18 +147,6 @@ impl Baseline {
         Ok(())
     }
 
-    /// Filter out findings that are in the baseline
-    pub fn filter_new<'a>(
-        &self,
-        findings: &'a [DeadCode],
-        project_root: &Path,
-    ) -> Vec<&'a DeadCode> {
-        findings
-            .iter()
-            .filter(|dc| !self.is_baselined(dc, project_root))
-            .collect()
-    }
-
     /// Check if a finding is in the baseline
     pub fn is_baselined(&self, dc: &DeadCode, project_root: &Path) -> bool {
         self.issues.iter().any(|fp| fp.matches(dc, project_root))
@@ -282,14 +269,17 @@ mod tests {
     #[test]
     fn test_baseline_filter() {
         let project_root = PathBuf::from("/project");
-        let findings = vec![
+        let findings = [
             make_dead_code("ClassA", "/project/src/a.kt", 10),
             make_dead_code("ClassB", "/project/src/b.kt", 20),
         ];
 
         let baseline = Baseline::from_findings(&findings[..1], &project_root);
 
-        let new_findings = baseline.filter_new(&findings, &project_root);
+        let new_findings: Vec<_> = findings
+            .iter()
+            .filter(|dc| !baseline.is_baselined(dc, &project_root))
+            .collect();
         assert_eq!(new_findings.len(), 1);
         assert_eq!(new_findings[0].declaration.name, "ClassB");
     }
@@ -0,0 +1,128 @@
+//! Cooperative cancellation for long-running analyses
+//!
+//! A [`CancellationToken`] is checked between files during discovery and
+//! parsing, while entry points are detected and reachability is traversed,
+//! between pipeline stages, and before each detector runs. Embedders
+//! (LSP servers, daemons, IDE plugins) keep a clone and call
+//! [`CancellationToken::cancel`] from any thread; the CLI uses
+//! [`CancellationToken::with_timeout`] for `--timeout`.
+//!
+//! Cancelled runs fail with a [`Cancelled`] error, which can be told apart
+//! from other failures with `report.downcast_ref::<Cancelled>()`.
+
+use miette::Diagnostic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Why an analysis stopped early
+#[derive(Error, Diagnostic, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cancelled {
+    #[error("Analysis cancelled")]
+    #[diagnostic(code(searchdeadcode::cancelled))]
+    Requested,
+    #[error("Analysis timed out after {}s", .0.as_secs_f64())]
+    #[diagnostic(
+        code(searchdeadcode::timeout),
+        help("Raise --timeout or narrow the analyzed paths with `targets` / `exclude`")
+    )]
+    TimedOut(Duration),
+}
+
+/// Shared flag that aborts an analysis when set or when its deadline passes
+///
+/// Cloning is cheap; all clones observe the same state. The default token is
+/// never cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    deadline: Option<(Instant, Duration)>,
+}
+
+impl CancellationToken {
+    /// Token that is only cancelled by [`cancel`](Self::cancel)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Token that also cancels itself once `timeout` has elapsed
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                cancelled: AtomicBool::new(false),
+                deadline: Some((Instant::now() + timeout, timeout)),
+            }),
+        }
+    }
+
+    /// Request cancellation; running stages stop at their next check
+    #[allow(dead_code)] // the CLI only uses timeouts; embedders call this
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the analysis should stop
+    pub fn is_cancelled(&self) -> bool {
+        self.reason().is_some()
+    }
+
+    /// `Err` once the token is cancelled or timed out
+    pub fn check(&self) -> Result<(), Cancelled> {
+        match self.reason() {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+
+    fn reason(&self) -> Option<Cancelled> {
+        if self.inner.cancelled.load(Ordering::Relaxed) {
+            return Some(Cancelled::Requested);
+        }
+        match self.inner.deadline {
+            Some((deadline, timeout)) if Instant::now() >= deadline => {
+                Some(Cancelled::TimedOut(timeout))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(token.check().is_ok());
+
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(token.check(), Err(Cancelled::Requested));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let token = CancellationToken::with_timeout(Duration::ZERO);
+        assert_eq!(token.check(), Err(Cancelled::TimedOut(Duration::ZERO)));
+
+        let token = CancellationToken::with_timeout(Duration::from_secs(3600));
+        assert!(!token.is_cancelled());
+    }
+
+    #[test]
+    fn test_cancelled_survives_miette_report() {
+        let report: miette::Report = Cancelled::Requested.into();
+        assert_eq!(
+            report.downcast_ref::<Cancelled>(),
+            Some(&Cancelled::Requested)
+        );
+    }
+}
@@ -1,10 +1,16 @@
 // Configuration loader - some methods reserved for future use
 #![allow(dead_code)]
 
+use crate::discovery::SourceSetKind;
+use crate::proguard::ProguardRules;
 use miette::{IntoDiagnostic, Result, WrapErr};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+/// Annotations retained by default: shrinker keep markers, code kept
+/// visible for tests, and code called from native code
+pub const DEFAULT_RETAIN_ANNOTATIONS: &[&str] = &["Keep", "VisibleForTesting", "UsedByNative"];
+
 /// Configuration for SearchDeadCode analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -18,8 +24,13 @@ pub struct Config {
     /// Patterns to retain - never report as dead code
     pub retain_patterns: Vec<String>,
 
-    /// Explicit entry points (fully qualified class names)
-    pub entry_points: Vec<String>,
+    /// Explicit entry points: class names, or rules matching annotations,
+    /// qualified names, supertypes and files
+    pub entry_points: EntryPointConfig,
+
+    /// Annotations whose declarations are never reported (simple or
+    /// qualified names, e.g. "Keep")
+    pub retain_annotations: Vec<String>,
 
     /// Report configuration
     pub report: ReportConfig,
@@ -29,6 +40,111 @@ pub struct Config {
 
     /// Android-specific configuration
     pub android: AndroidConfig,
+
+    /// WASM detector plugins to load (relative to the project root)
+    pub plugins: Vec<PathBuf>,
+
+    /// Generated sources (KAPT/KSP output) scanned for references
+    pub generated: GeneratedConfig,
+
+    /// Protobuf/gRPC generated classes retained as entry points
+    pub protobuf: ProtobufConfig,
+
+    /// C/C++ sources scanned for JNI references
+    pub native: NativeConfig,
+
+    /// Retain what the project's ProGuard/R8 `-keep` rules keep
+    pub respect_keep_rules: bool,
+
+    /// Path patterns assigning files to source sets
+    pub source_sets: SourceSetConfig,
+
+    /// Treat every declaration in a test source set as an entry point, so
+    /// code tests use is never reported
+    pub tests_as_roots: bool,
+
+    /// Report production code that only tests reach as `DC030` instead of
+    /// as unreferenced
+    pub report_test_only: bool,
+
+    /// Also analyze each Gradle module on its own, reporting code only
+    /// other modules use
+    pub per_module: bool,
+
+    /// Conditions that fail the run, for CI
+    pub gate: GateConfig,
+}
+
+/// Entry point rules for project-specific frameworks
+///
+/// Plugin systems, routers and other reflection-driven code have roots the
+/// built-in detection cannot see. Every declaration matching one of these
+/// rules is an entry point. A plain list is read as `classes`, the format
+/// of earlier versions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "EntryPointRepr")]
+pub struct EntryPointConfig {
+    /// Classes by fully qualified or simple name
+    pub classes: Vec<String>,
+
+    /// Annotations (simple or qualified names) marking entry points
+    pub annotations: Vec<String>,
+
+    /// Regexes matched against qualified names; members match as
+    /// `<class>.<member>`
+    pub patterns: Vec<String>,
+
+    /// Supertypes (simple or qualified names) whose subtypes are entry points
+    pub supertypes: Vec<String>,
+
+    /// Path patterns of files whose declarations are all entry points
+    pub files: Vec<String>,
+}
+
+/// `entry_points` as written: a list of classes or a table of rules
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EntryPointRepr {
+    Classes(Vec<String>),
+    Rules(EntryPointRules),
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct EntryPointRules {
+    classes: Vec<String>,
+    annotations: Vec<String>,
+    patterns: Vec<String>,
+    supertypes: Vec<String>,
+    files: Vec<String>,
+}
+
+impl From<EntryPointRepr> for EntryPointConfig {
+    fn from(repr: EntryPointRepr) -> Self {
+        match repr {
+            EntryPointRepr::Classes(classes) => Self {
+                classes,
+                ..Self::default()
+            },
+            EntryPointRepr::Rules(rules) => Self {
+                classes: rules.classes,
+                annotations: rules.annotations,
+                patterns: rules.patterns,
+                supertypes: rules.supertypes,
+                files: rules.files,
+            },
+        }
+    }
+}
+
+impl EntryPointConfig {
+    /// Whether a file's declarations are all entry points
+    pub fn matches_file(&self, path: &Path) -> bool {
+        let path_str = path.to_string_lossy();
+        self.files
+            .iter()
+            .any(|pattern| glob_match(pattern, &path_str))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +158,30 @@ pub struct ReportConfig {
 
     /// Show code snippets in output
     pub show_code: bool,
+
+    /// Source lines shown around each finding in terminal, JSON and SARIF
+    /// output (same as --context); none when unset
+    pub context_lines: Option<usize>,
+}
+
+/// Quality gate: the run exits with a failure status when the reported
+/// findings break one of these
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GateConfig {
+    /// Fail on a finding of this severity or above (info, warning, error),
+    /// or on this many findings (same as --fail-on)
+    pub fail_on: Option<String>,
+
+    /// Most error findings allowed
+    pub max_errors: Option<usize>,
+
+    /// Most warning findings allowed
+    pub max_warnings: Option<usize>,
+
+    /// Most findings allowed that the baseline does not have; ignored, with
+    /// a warning, unless a --baseline is loaded
+    pub max_new_issues: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,10 +216,16 @@ pub struct DetectionConfig {
 
     /// Anti-pattern detector groups
     pub anti_patterns: AntiPatternConfig,
+
+    /// Rule codes to run regardless of their default or group (e.g. "DC009", "AP008")
+    pub enabled_rules: Vec<String>,
+
+    /// Rule codes to never run; takes precedence over `enabled_rules`
+    pub disabled_rules: Vec<String>,
 }
 
 /// Configuration for anti-pattern detectors
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AntiPatternConfig {
     /// Enable all anti-pattern detectors
@@ -126,6 +272,84 @@ pub struct AndroidConfig {
     pub component_patterns: Vec<String>,
 }
 
+/// Configuration for scanning generated sources
+///
+/// Annotation processors (Dagger/Hilt, Room, Moshi, ...) generate code that
+/// is the only caller of some project declarations. Generated files are
+/// parsed for their references alone: nothing they declare is reported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneratedConfig {
+    /// Scan generated source directories
+    pub enabled: bool,
+
+    /// Generated source directories, relative to each module
+    pub dirs: Vec<String>,
+}
+
+/// Configuration for protobuf/gRPC generated classes
+///
+/// protoc output is used through builders, parsers and reflection, so its
+/// classes never look reachable. Classes generated for the project's
+/// `.proto` files are retained automatically; these settings cover
+/// generated code checked into the tree or coming from other modules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProtobufConfig {
+    /// Retain protobuf generated classes
+    pub enabled: bool,
+
+    /// Packages holding generated classes (`com.example.proto`)
+    pub packages: Vec<String>,
+
+    /// Directories holding generated classes, relative to each module
+    pub dirs: Vec<String>,
+}
+
+/// Configuration for scanning native (JNI) sources
+///
+/// Native code reaches JVM code through exported `Java_` symbols,
+/// `FindClass` strings and member ID lookups. `native` / `external`
+/// methods are always entry points; the scan adds the classes and members
+/// only native code uses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NativeConfig {
+    /// Scan native source directories
+    pub enabled: bool,
+
+    /// Native source directories, relative to each module
+    pub dirs: Vec<String>,
+}
+
+/// Source set overrides for layouts the `src/<name>` convention misses
+///
+/// Each list holds path patterns; a file matching one belongs to that
+/// source set. Lists are checked in declaration order and files matching
+/// none are classified by their `src/<name>` directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceSetConfig {
+    /// Production sources
+    pub main: Vec<String>,
+
+    /// Local unit test sources
+    pub test: Vec<String>,
+
+    /// Instrumented test sources
+    #[serde(alias = "androidTest")]
+    pub android_test: Vec<String>,
+
+    /// Debug build type sources
+    pub debug: Vec<String>,
+
+    /// Release build type sources
+    pub release: Vec<String>,
+
+    /// Other product flavor or build type sources
+    pub flavor: Vec<String>,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -142,10 +366,24 @@ impl Default for Config {
                 "**/ui/theme/Theme.kt".to_string(),
             ],
             retain_patterns: vec![],
-            entry_points: vec![],
+            entry_points: EntryPointConfig::default(),
+            retain_annotations: DEFAULT_RETAIN_ANNOTATIONS
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
             report: ReportConfig::default(),
             detection: DetectionConfig::default(),
             android: AndroidConfig::default(),
+            plugins: vec![],
+            generated: GeneratedConfig::default(),
+            protobuf: ProtobufConfig::default(),
+            native: NativeConfig::default(),
+            respect_keep_rules: false,
+            source_sets: SourceSetConfig::default(),
+            tests_as_roots: false,
+            report_test_only: false,
+            per_module: false,
+            gate: GateConfig::default(),
         }
     }
 }
@@ -156,6 +394,7 @@ impl Default for ReportConfig {
             format: "terminal".to_string(),
             group_by: "file".to_string(),
             show_code: true,
+            context_lines: None,
         }
     }
 }
@@ -173,19 +412,8 @@ impl Default for DetectionConfig {
             dead_branch: true,
             redundant_public: true,
             anti_patterns: AntiPatternConfig::default(),
-        }
-    }
-}
-
-impl Default for AntiPatternConfig {
-    fn default() -> Self {
-        Self {
-            enabled: false,
-            architecture: false,
-            kotlin: false,
-            performance: false,
-            android: false,
-            compose: false,
+            enabled_rules: vec![],
+            disabled_rules: vec![],
         }
     }
 }
@@ -209,6 +437,40 @@ impl Default for AndroidConfig {
     }
 }
 
+impl Default for GeneratedConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dirs: vec![
+                "build/generated/ksp".to_string(),
+                "build/generated/source/kapt".to_string(),
+                "build/generated/source/kaptKotlin".to_string(),
+                "build/generated/ap_generated_sources".to_string(),
+                "build/generated/hilt".to_string(),
+            ],
+        }
+    }
+}
+
+impl Default for ProtobufConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            packages: vec![],
+            dirs: vec!["build/generated/source/proto".to_string()],
+        }
+    }
+}
+
+impl Default for NativeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dirs: vec!["src/main/cpp".to_string(), "src/main/jni".to_string()],
+        }
+    }
+}
+
 impl Config {
     /// Load configuration from a file (YAML or TOML)
     pub fn from_file(path: &Path) -> Result<Self> {
@@ -260,6 +522,38 @@ impl Config {
         Ok(Self::default())
     }
 
+    /// Add retain patterns for the `-keep` rules of the project's `.pro` files
+    pub fn add_keep_rule_patterns(&mut self, project_root: &Path) {
+        for file in ProguardRules::find_files(project_root) {
+            let Ok(rules) = ProguardRules::parse(&file) else {
+                continue;
+            };
+            for pattern in rules.retain_patterns() {
+                if !self.retain_patterns.contains(&pattern) {
+                    self.retain_patterns.push(pattern);
+                }
+            }
+        }
+    }
+
+    /// Source set of a file, from the configured overrides or its path
+    pub fn source_set_of(&self, path: &Path) -> SourceSetKind {
+        let path_str = path.to_string_lossy();
+        let overrides = [
+            (&self.source_sets.main, SourceSetKind::Main),
+            (&self.source_sets.test, SourceSetKind::Test),
+            (&self.source_sets.android_test, SourceSetKind::AndroidTest),
+            (&self.source_sets.debug, SourceSetKind::Debug),
+            (&self.source_sets.release, SourceSetKind::Release),
+            (&self.source_sets.flavor, SourceSetKind::Flavor),
+        ];
+        overrides
+            .iter()
+            .find(|(patterns, _)| patterns.iter().any(|p| glob_match(p, &path_str)))
+            .map(|(_, kind)| *kind)
+            .unwrap_or_else(|| SourceSetKind::from_path(path))
+    }
+
     /// Check if a pattern matches for exclusion
     pub fn should_exclude(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy();
@@ -372,6 +666,70 @@ mod tests {
         assert!(!glob_match("**/build/**", "/project/src/main"));
     }
 
+    #[test]
+    fn test_add_keep_rule_patterns() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("app/build/intermediates")).unwrap();
+        std::fs::write(
+            temp.path().join("app/proguard-rules.pro"),
+            "-keep class com.example.model.** { *; }\n-dontwarn okhttp3.**\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("app/build/intermediates/merged.pro"),
+            "-keep class com.example.Merged\n",
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.add_keep_rule_patterns(temp.path());
+
+        assert_eq!(
+            config.retain_patterns,
+            ["com.example.model.**", "com.example.model.**.*"]
+        );
+    }
+
+    #[test]
+    fn test_source_set_overrides() {
+        let config: Config = serde_yaml::from_str(
+            "source_sets:\n  test:\n    - \"**/integration-tests/**\"\n  androidTest:\n    - \"**/device-tests/**\"\n",
+        )
+        .unwrap();
+
+        let kind = |path: &str| config.source_set_of(Path::new(path));
+        assert_eq!(kind("app/integration-tests/A.kt"), SourceSetKind::Test);
+        assert_eq!(kind("app/device-tests/A.kt"), SourceSetKind::AndroidTest);
+        assert_eq!(kind("app/src/debug/java/A.kt"), SourceSetKind::Debug);
+        assert_eq!(kind("app/lib/A.kt"), SourceSetKind::Main);
+    }
+
+    #[test]
+    fn test_entry_point_rules() {
+        let legacy: Config = serde_yaml::from_str("entry_points:\n  - com.example.Main\n").unwrap();
+        assert_eq!(legacy.entry_points.classes, vec!["com.example.Main"]);
+
+        let config: Config = toml::from_str(
+            r#"
+[entry_points]
+annotations = ["Route"]
+patterns = ['^com\.example\.plugins\..*Plugin$']
+supertypes = ["Plugin"]
+files = ["**/routes/**"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.entry_points.annotations, vec!["Route"]);
+        assert_eq!(config.entry_points.supertypes, vec!["Plugin"]);
+        assert!(config.entry_points.classes.is_empty());
+        assert!(config
+            .entry_points
+            .matches_file(Path::new("/app/src/main/routes/Home.kt")));
+        assert!(!config
+            .entry_points
+            .matches_file(Path::new("/app/src/main/Home.kt")));
+    }
+
     #[test]
     fn test_default_config() {
         let config = Config::default();
@@ -1,3 +1,3 @@
 mod loader;
 
-pub use loader::Config;
+pub use loader::{Config, DetectionConfig, GateConfig, DEFAULT_RETAIN_ANNOTATIONS};
@@ -340,13 +340,57 @@ pub fn parse_coverage_file(path: &Path) -> Result<CoverageData> {
 }
 
 /// Parse multiple coverage files and merge results
+///
+/// Files are parsed in parallel; merging happens afterwards in input order.
 pub fn parse_coverage_files(paths: &[PathBuf]) -> Result<CoverageData> {
-    let mut merged = CoverageData::new();
+    use rayon::prelude::*;
+
+    let parsed: Vec<Result<CoverageData>> = paths
+        .par_iter()
+        .map(|path| parse_coverage_file(path))
+        .collect();
 
-    for path in paths {
-        let data = parse_coverage_file(path)?;
-        merged.merge(data);
+    let mut merged = CoverageData::new();
+    for data in parsed {
+        merged.merge(data?);
     }
 
     Ok(merged)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parse_coverage_files_merges_parallel_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.info");
+        let second = dir.path().join("second.info");
+        fs::write(
+            &first,
+            "TN:\nSF:/src/MyFile.kt\nFN:10,myFunction\nFNDA:0,myFunction\nDA:10,0\nend_of_record\n",
+        )
+        .unwrap();
+        fs::write(
+            &second,
+            "TN:\nSF:/src/MyFile.kt\nFN:10,myFunction\nFNDA:3,myFunction\nDA:10,3\nend_of_record\n",
+        )
+        .unwrap();
+
+        let merged = parse_coverage_files(&[first, second]).unwrap();
+
+        // Covered in any run means covered
+        assert_eq!(
+            merged.is_line_covered(Path::new("/src/MyFile.kt"), 10),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_parse_coverage_files_reports_errors() {
+        let missing = PathBuf::from("/nonexistent/coverage.info");
+        assert!(parse_coverage_files(&[missing]).is_err());
+    }
+}
@@ -0,0 +1,494 @@
+// Gradle version catalog discovery
+//
+// Reads the version catalogs in `gradle/*.versions.toml` and the catalog
+// accessors used by Gradle scripts:
+// ```
+// # gradle/libs.versions.toml
+// [versions]
+// kotlin = "2.0.0"
+//
+// [libraries]
+// androidx-core-ktx = { module = "androidx.core:core-ktx", version = "1.13.1" }
+//
+// [plugins]
+// kotlin-android = { id = "org.jetbrains.kotlin.android", version.ref = "kotlin" }
+//
+// // app/build.gradle.kts
+// plugins { alias(libs.plugins.kotlin.android) }
+// dependencies { implementation(libs.androidx.core.ktx) }
+// ```
+// The catalog is scanned line by line rather than fully parsed, so each
+// entry keeps the line it is declared on.
+
+#![allow(dead_code)] // API methods reserved for future use
+
+use miette::{IntoDiagnostic, Result};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::gradle::strip_comments;
+
+/// Accessor segments that call into the provider rather than name an entry
+/// (`libs.versions.kotlin.get()`, `libs.androidx.core.asProvider()`)
+const PROVIDER_METHODS: &[&str] = &["get", "asProvider", "orNull", "getOrNull", "map", "flatMap"];
+
+/// Directories holding precompiled script and convention plugins, whose
+/// Kotlin sources use catalogs too
+const BUILD_LOGIC_DIRS: &[&str] = &["buildSrc", "build-logic"];
+
+/// Catalog table an entry is declared in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CatalogSection {
+    Versions,
+    Libraries,
+    Bundles,
+    Plugins,
+}
+
+impl CatalogSection {
+    fn from_header(header: &str) -> Option<Self> {
+        match header {
+            "versions" => Some(CatalogSection::Versions),
+            "libraries" => Some(CatalogSection::Libraries),
+            "bundles" => Some(CatalogSection::Bundles),
+            "plugins" => Some(CatalogSection::Plugins),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CatalogSection::Versions => "version",
+            CatalogSection::Libraries => "library",
+            CatalogSection::Bundles => "bundle",
+            CatalogSection::Plugins => "plugin",
+        }
+    }
+
+    /// Accessor group the section's entries are reached through
+    /// (libraries sit at the top level)
+    fn accessor_prefix(&self) -> &'static str {
+        match self {
+            CatalogSection::Versions => "versions.",
+            CatalogSection::Libraries => "",
+            CatalogSection::Bundles => "bundles.",
+            CatalogSection::Plugins => "plugins.",
+        }
+    }
+}
+
+/// An alias declared in a version catalog
+#[derive(Debug, Clone)]
+pub struct CatalogEntry {
+    pub section: CatalogSection,
+    /// Alias as written (e.g., "androidx-core-ktx")
+    pub alias: String,
+    /// 1-based line the alias is declared on
+    pub line: usize,
+    /// Version alias named by `version.ref`
+    pub version_ref: Option<String>,
+    /// Library aliases of a bundle
+    pub members: Vec<String>,
+}
+
+/// A version catalog file
+#[derive(Debug, Clone)]
+pub struct VersionCatalog {
+    /// Name scripts reach the catalog by (`libs` for `libs.versions.toml`)
+    pub name: String,
+    pub file: PathBuf,
+    pub entries: Vec<CatalogEntry>,
+}
+
+/// Catalog accessors used by a project's Gradle scripts
+#[derive(Debug, Clone, Default)]
+pub struct CatalogReferences {
+    /// Normalized accessor paths by catalog (`("libs", "plugins.kotlin.android")`)
+    accessors: HashSet<(String, String)>,
+    /// Normalized aliases looked up by name (`findLibrary("androidx-core-ktx")`),
+    /// which could come from any catalog
+    lookups: HashSet<(CatalogSection, String)>,
+}
+
+impl VersionCatalog {
+    /// Find the catalogs in the project's `gradle/` directory
+    pub fn find(root: &Path) -> Vec<Self> {
+        let Ok(entries) = fs::read_dir(root.join("gradle")) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.ends_with(".versions.toml"))
+            })
+            .collect();
+        files.sort();
+        files
+            .iter()
+            .filter_map(|file| Self::parse(file).ok())
+            .collect()
+    }
+
+    /// Parse a catalog file
+    pub fn parse(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).into_diagnostic()?;
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_suffix(".versions.toml"))
+            .unwrap_or("libs");
+        Ok(Self::parse_content(name, path, &content))
+    }
+
+    /// Parse catalog content
+    pub fn parse_content(name: &str, file: &Path, content: &str) -> Self {
+        let quoted = Regex::new(r#""([^"]*)""#).unwrap();
+        let version_ref = Regex::new(r#"\bref\s*=\s*"([^"]+)""#).unwrap();
+
+        let mut entries: Vec<CatalogEntry> = Vec::new();
+        let mut section = None;
+        // Index of the entry whose value continues on the next lines
+        let mut open: Option<usize> = None;
+        let mut depth = 0usize;
+
+        for (index, line) in content.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let value = if depth > 0 {
+                line
+            } else if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = CatalogSection::from_header(header.trim());
+                open = None;
+                continue;
+            } else {
+                let Some(section) = section else {
+                    continue;
+                };
+                let Some((key, value)) = line.split_once('=') else {
+                    continue;
+                };
+                // `groovy-core.module = "..."` continues the `groovy-core` table
+                let (alias, rest) = split_key(key.trim());
+                let position = match entries
+                    .iter()
+                    .position(|e| e.section == section && e.alias == alias)
+                {
+                    Some(position) => position,
+                    None => {
+                        entries.push(CatalogEntry {
+                            section,
+                            alias: alias.to_string(),
+                            line: index + 1,
+                            version_ref: None,
+                            members: Vec::new(),
+                        });
+                        entries.len() - 1
+                    }
+                };
+                open = Some(position);
+                if rest == "version.ref" {
+                    if let Some(cap) = quoted.captures(value) {
+                        entries[position].version_ref = Some(cap[1].to_string());
+                    }
+                }
+                value
+            };
+
+            if let Some(entry) = open.map(|position| &mut entries[position]) {
+                if let Some(cap) = version_ref.captures(value) {
+                    entry.version_ref = Some(cap[1].to_string());
+                }
+                if entry.section == CatalogSection::Bundles {
+                    entry
+                        .members
+                        .extend(quoted.captures_iter(value).map(|cap| cap[1].to_string()));
+                }
+            }
+            let (opened, closed) = bracket_delta(value);
+            depth = (depth + opened).saturating_sub(closed);
+        }
+
+        Self {
+            name: name.to_string(),
+            file: file.to_path_buf(),
+            entries,
+        }
+    }
+
+    /// Accessor scripts use for an entry (e.g., "libs.plugins.kotlin.android")
+    pub fn accessor(&self, entry: &CatalogEntry) -> String {
+        format!(
+            "{}.{}{}",
+            self.name,
+            entry.section.accessor_prefix(),
+            entry.alias.replace(['-', '_'], ".")
+        )
+    }
+
+    /// Entries no script uses: libraries that are neither referenced nor in
+    /// a used bundle, and versions no entry or script refers to
+    pub fn unused_entries(&self, references: &CatalogReferences) -> Vec<&CatalogEntry> {
+        let used = |entry: &CatalogEntry| references.uses(self, entry);
+        let bundled: HashSet<String> = self
+            .entries
+            .iter()
+            .filter(|e| e.section == CatalogSection::Bundles && used(e))
+            .flat_map(|e| e.members.iter().map(|m| normalize(m)))
+            .collect();
+        let versions: HashSet<String> = self
+            .entries
+            .iter()
+            .filter_map(|e| e.version_ref.as_deref())
+            .map(normalize)
+            .collect();
+
+        self.entries
+            .iter()
+            .filter(|entry| !used(entry))
+            .filter(|entry| match entry.section {
+                CatalogSection::Libraries => !bundled.contains(&normalize(&entry.alias)),
+                CatalogSection::Versions => !versions.contains(&normalize(&entry.alias)),
+                CatalogSection::Bundles | CatalogSection::Plugins => true,
+            })
+            .collect()
+    }
+}
+
+impl CatalogReferences {
+    /// Scan every Gradle script of the project, and the Kotlin sources of
+    /// `buildSrc` / `build-logic`, for accessors of the given catalogs
+    pub fn scan(root: &Path, catalogs: &[VersionCatalog]) -> Self {
+        let mut references = Self::default();
+        if catalogs.is_empty() {
+            return references;
+        }
+        let patterns = ReferencePatterns::new(catalogs);
+
+        let walker = WalkDir::new(root).into_iter().filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0 || !(name.starts_with('.') || name == "build")
+        });
+        for entry in walker.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy();
+            let is_script = name.ends_with(".gradle") || name.ends_with(".gradle.kts");
+            let in_build_logic = name.ends_with(".kt")
+                && path
+                    .strip_prefix(root)
+                    .ok()
+                    .and_then(|p| p.components().next())
+                    .is_some_and(|c| BUILD_LOGIC_DIRS.iter().any(|d| c.as_os_str() == *d));
+            if !entry.file_type().is_file() || !(is_script || in_build_logic) {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(path) {
+                references.scan_content(&patterns, &strip_comments(&content));
+            }
+        }
+        references
+    }
+
+    fn scan_content(&mut self, patterns: &ReferencePatterns, content: &str) {
+        for cap in patterns.accessor.captures_iter(content) {
+            let mut segments: Vec<&str> = cap[2].split('.').filter(|s| !s.is_empty()).collect();
+            while segments
+                .last()
+                .is_some_and(|last| PROVIDER_METHODS.contains(last))
+            {
+                segments.pop();
+            }
+            self.accessors
+                .insert((cap[1].to_string(), normalize(&segments.join("."))));
+        }
+        for cap in patterns.lookup.captures_iter(content) {
+            let section = match &cap[1] {
+                "Library" => CatalogSection::Libraries,
+                "Bundle" => CatalogSection::Bundles,
+                "Plugin" => CatalogSection::Plugins,
+                _ => CatalogSection::Versions,
+            };
+            self.lookups.insert((section, normalize(&cap[2])));
+        }
+    }
+
+    /// Whether a script uses an entry of a catalog
+    pub fn uses(&self, catalog: &VersionCatalog, entry: &CatalogEntry) -> bool {
+        let alias = normalize(&entry.alias);
+        let path = format!("{}{}", entry.section.accessor_prefix(), alias);
+        self.accessors.contains(&(catalog.name.clone(), path))
+            || self.lookups.contains(&(entry.section, alias))
+    }
+}
+
+/// Patterns for catalog accessors in scripts
+struct ReferencePatterns {
+    // libs.androidx.core.ktx, libs.plugins.kotlin.android
+    accessor: Regex,
+    // libs.findLibrary("androidx-core-ktx"), catalog.findVersion('kotlin')
+    lookup: Regex,
+}
+
+impl ReferencePatterns {
+    fn new(catalogs: &[VersionCatalog]) -> Self {
+        let names: Vec<String> = catalogs.iter().map(|c| regex::escape(&c.name)).collect();
+        Self {
+            accessor: Regex::new(&format!(r"\b({})\.([A-Za-z_][\w.]*)", names.join("|"))).unwrap(),
+            lookup: Regex::new(r#"\bfind(Library|Bundle|Plugin|Version)\(\s*["']([^"']+)["']"#)
+                .unwrap(),
+        }
+    }
+}
+
+/// Accessor form of an alias: `-` and `_` separate segments like `.`
+fn normalize(alias: &str) -> String {
+    alias.replace(['-', '_'], ".").to_lowercase()
+}
+
+/// `"androidx-core"` / `groovy-core.version.ref` -> (alias, rest of the key)
+fn split_key(key: &str) -> (&str, &str) {
+    if let Some(quoted) = key.strip_prefix('"') {
+        let end = quoted.find('"').unwrap_or(quoted.len());
+        let rest = quoted[end..]
+            .trim_start_matches('"')
+            .trim_start_matches('.');
+        return (&quoted[..end], rest.trim());
+    }
+    match key.split_once('.') {
+        Some((alias, rest)) => (alias.trim(), rest.trim()),
+        None => (key, ""),
+    }
+}
+
+/// Drop a `#` comment that is not inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Opening and closing brackets/braces outside strings (version ranges
+/// such as `"[1.0,2.0)"` don't count)
+fn bracket_delta(text: &str) -> (usize, usize) {
+    let mut in_string = false;
+    let (mut opened, mut closed) = (0, 0);
+    for c in text.chars() {
+        match c {
+            '"' => in_string = !in_string,
+            '[' | '{' if !in_string => opened += 1,
+            ']' | '}' if !in_string => closed += 1,
+            _ => {}
+        }
+    }
+    (opened, closed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unused_catalog_entries() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "gradle/libs.versions.toml",
+            r#"[versions]
+kotlin = "2.0.0"
+compose-bom = "2024.06.00"
+okhttp = "4.12.0"   # only used by okhttp
+retrofit = "2.11.0"
+strict = { strictly = "[1.0, 2.0)" }
+
+[libraries]
+androidx-core-ktx = { module = "androidx.core:core-ktx", version = "1.13.1" }
+androidx-core = "androidx.core:core:1.13.1"
+compose-bom = { group = "androidx.compose", name = "compose-bom", version.ref = "compose-bom" }
+compose-ui = { module = "androidx.compose.ui:ui" }
+compose-tooling = { module = "androidx.compose.ui:ui-tooling" }
+okhttp = { module = "com.squareup.okhttp3:okhttp", version.ref = "okhttp" }
+groovy-core.module = "org.codehaus.groovy:groovy"
+groovy-core.version.ref = "groovy"
+legacy-support = "androidx.legacy:legacy-support-v4:1.0.0"
+
+[bundles]
+compose = [
+    "compose-ui",
+    "compose-tooling",
+]
+networking = ["okhttp"]
+
+[plugins]
+kotlin-android = { id = "org.jetbrains.kotlin.android", version.ref = "kotlin" }
+ksp = { id = "com.google.devtools.ksp", version = "2.0.0-1.0.21" }
+"#,
+        );
+        write(
+            root,
+            "build.gradle.kts",
+            "plugins {\n    alias(libs.plugins.kotlin.android) apply false\n}\n",
+        );
+        write(
+            root,
+            "app/build.gradle.kts",
+            r#"android { compileSdk = libs.versions.strict.get().toInt() }
+dependencies {
+    implementation(libs.androidx.core.ktx)
+    implementation(platform(libs.compose.bom))
+    implementation(libs.bundles.compose)
+    // implementation(libs.legacy.support)
+}"#,
+        );
+        write(
+            root,
+            "build-logic/src/main/kotlin/Conventions.kt",
+            r#"dependencies.add("implementation", libs.findLibrary("groovy-core").get())"#,
+        );
+        write(
+            root,
+            "app/build/generated/Leftover.gradle.kts",
+            "implementation(libs.bundles.networking)",
+        );
+
+        let catalogs = VersionCatalog::find(root);
+        assert_eq!(catalogs.len(), 1);
+        let catalog = &catalogs[0];
+        assert_eq!(catalog.name, "libs");
+        assert_eq!(catalog.entries.len(), 17);
+
+        let references = CatalogReferences::scan(root, &catalogs);
+        let unused: Vec<(String, usize)> = catalog
+            .unused_entries(&references)
+            .into_iter()
+            .map(|entry| (catalog.accessor(entry), entry.line))
+            .collect();
+        assert_eq!(
+            unused,
+            [
+                ("libs.versions.retrofit".to_string(), 5),
+                ("libs.androidx.core".to_string(), 10),
+                ("libs.okhttp".to_string(), 14),
+                ("libs.legacy.support".to_string(), 17),
+                ("libs.bundles.networking".to_string(), 24),
+                ("libs.plugins.ksp".to_string(), 28),
+            ]
+        );
+    }
+}
@@ -1,6 +1,9 @@
 // File discovery utilities - some reserved for future use
 #![allow(dead_code)]
 
+use super::gradle::{module_from_path, GradleProject};
+use super::source_set::SourceSetKind;
+use crate::cancel::CancellationToken;
 use crate::config::Config;
 use ignore::WalkBuilder;
 use miette::{IntoDiagnostic, Result};
@@ -18,6 +21,8 @@ pub enum FileType {
     XmlNavigation,
     XmlMenu,
     XmlOther,
+    Aidl,
+    Proto,
 }
 
 impl FileType {
@@ -29,6 +34,8 @@ impl FileType {
         match extension {
             "kt" | "kts" => Some(FileType::Kotlin),
             "java" => Some(FileType::Java),
+            "aidl" => Some(FileType::Aidl),
+            "proto" => Some(FileType::Proto),
             "xml" => {
                 // Determine XML type based on path
                 let path_str = path.to_string_lossy();
@@ -77,6 +84,15 @@ pub struct SourceFile {
     /// Type of source file
     pub file_type: FileType,
 
+    /// Generated by an annotation processor: scanned for references only
+    pub generated: bool,
+
+    /// Source set the file belongs to
+    pub source_set: SourceSetKind,
+
+    /// Gradle module the file belongs to (e.g., ":core:ui")
+    pub module: Option<String>,
+
     /// Contents of the file (loaded lazily)
     contents: Option<String>,
 }
@@ -84,12 +100,33 @@ pub struct SourceFile {
 impl SourceFile {
     pub fn new(path: PathBuf, file_type: FileType) -> Self {
         Self {
+            source_set: SourceSetKind::from_path(&path),
             path,
             file_type,
+            generated: false,
+            module: None,
             contents: None,
         }
     }
 
+    /// Mark the file as generated code
+    pub fn into_generated(mut self) -> Self {
+        self.generated = true;
+        self
+    }
+
+    /// Override the source set guessed from the path
+    pub fn with_source_set(mut self, source_set: SourceSetKind) -> Self {
+        self.source_set = source_set;
+        self
+    }
+
+    /// Attribute the file to a Gradle module
+    pub fn with_module(mut self, module: Option<String>) -> Self {
+        self.module = module;
+        self
+    }
+
     /// Load file contents
     pub fn load(&mut self) -> Result<&str> {
         if self.contents.is_none() {
@@ -113,27 +150,64 @@ impl SourceFile {
 /// File finder for discovering source files in a project
 pub struct FileFinder<'a> {
     config: &'a Config,
+    cancel: CancellationToken,
 }
 
 impl<'a> FileFinder<'a> {
     pub fn new(config: &'a Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    /// Stop walking directories once `cancel` fires
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
     }
 
     /// Find all source files in the given path
+    ///
+    /// Without explicit targets, a Gradle project is scanned module by
+    /// module (each module's source sets), skipping build output and
+    /// directories no module includes. Other projects are walked whole.
+    /// Generated sources are added when enabled in the config.
+    ///
+    /// Files are attributed to the innermost Gradle module containing them,
+    /// or, outside a Gradle project, to the directory holding their `src`
+    /// directory.
     pub fn find_files(&self, root: &Path) -> Result<Vec<SourceFile>> {
         debug!("Scanning for files in: {}", root.display());
 
-        let targets = if self.config.targets.is_empty() {
-            vec![root.to_path_buf()]
-        } else {
+        let project = GradleProject::load(root);
+        let targets = if !self.config.targets.is_empty() {
             self.config.targets.iter().map(|t| root.join(t)).collect()
+        } else {
+            match project.as_ref().map(|project| project.source_dirs()) {
+                Some(dirs) if !dirs.is_empty() => {
+                    debug!("Scanning {} Gradle source directories", dirs.len());
+                    dirs
+                }
+                _ => vec![root.to_path_buf()],
+            }
         };
 
-        let files: Vec<SourceFile> = targets
+        let mut files: Vec<SourceFile> = targets
             .par_iter()
             .flat_map(|target| self.scan_directory(target))
             .collect();
+        if self.config.generated.enabled {
+            files.extend(self.find_generated_files(root));
+        }
+        self.cancel.check()?;
+        for file in &mut files {
+            file.module = project
+                .as_ref()
+                .and_then(|project| project.module_for(&file.path))
+                .map(|module| module.path.clone())
+                .or_else(|| module_from_path(root, &file.path));
+        }
 
         debug!("Found {} files", files.len());
         Ok(files)
@@ -157,6 +231,7 @@ impl<'a> FileFinder<'a> {
             .build();
 
         walker
+            .take_while(|_| !self.cancel.is_cancelled())
             .filter_map(|entry| entry.ok())
             .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
             .filter_map(|entry| {
@@ -172,7 +247,100 @@ impl<'a> FileFinder<'a> {
                 let file_type = FileType::from_path(path)?;
 
                 trace!("Found {:?}: {}", file_type, path.display());
-                Some(SourceFile::new(path.to_path_buf(), file_type))
+                Some(
+                    SourceFile::new(path.to_path_buf(), file_type)
+                        .with_source_set(self.config.source_set_of(path)),
+                )
+            })
+            .collect()
+    }
+
+    /// Find the Kotlin and Java sources generated under each module
+    ///
+    /// Build output is gitignored and excluded by default, so these
+    /// directories are walked without either filter.
+    pub fn find_generated_files(&self, root: &Path) -> Vec<SourceFile> {
+        let modules: Vec<PathBuf> = match GradleProject::load(root) {
+            Some(project) if !project.modules.is_empty() => {
+                project.modules.into_iter().map(|m| m.dir).collect()
+            }
+            _ => vec![root.to_path_buf()],
+        };
+
+        let mut dirs: Vec<PathBuf> = modules
+            .iter()
+            .flat_map(|module| self.config.generated.dirs.iter().map(|d| module.join(d)))
+            .filter(|dir| dir.is_dir())
+            .collect();
+        dirs.sort();
+        dirs.dedup();
+        debug!("Scanning {} generated source directories", dirs.len());
+
+        dirs.par_iter()
+            .flat_map(|dir| {
+                WalkBuilder::new(dir)
+                    .hidden(true)
+                    .git_ignore(false)
+                    .git_global(false)
+                    .git_exclude(false)
+                    .ignore(false)
+                    .parents(false)
+                    .follow_links(false)
+                    .build()
+                    .take_while(|_| !self.cancel.is_cancelled())
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+                    .filter_map(|entry| {
+                        let file_type = FileType::from_path(entry.path())?;
+                        file_type.is_source().then(|| {
+                            SourceFile::new(entry.path().to_path_buf(), file_type)
+                                .with_source_set(self.config.source_set_of(entry.path()))
+                                .into_generated()
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Find the C and C++ sources under each module's native source
+    /// directories
+    pub fn find_native_files(&self, root: &Path) -> Vec<PathBuf> {
+        let modules: Vec<PathBuf> = match GradleProject::load(root) {
+            Some(project) if !project.modules.is_empty() => {
+                project.modules.into_iter().map(|m| m.dir).collect()
+            }
+            _ => vec![root.to_path_buf()],
+        };
+
+        let mut dirs: Vec<PathBuf> = modules
+            .iter()
+            .flat_map(|module| self.config.native.dirs.iter().map(|d| module.join(d)))
+            .filter(|dir| dir.is_dir())
+            .collect();
+        dirs.sort();
+        dirs.dedup();
+        debug!("Scanning {} native source directories", dirs.len());
+
+        dirs.iter()
+            .flat_map(|dir| {
+                WalkBuilder::new(dir)
+                    .hidden(true)
+                    .follow_links(false)
+                    .build()
+                    .take_while(|_| !self.cancel.is_cancelled())
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+                    .map(|entry| entry.into_path())
+                    .filter(|path| {
+                        path.extension()
+                            .and_then(|e| e.to_str())
+                            .is_some_and(|ext| {
+                                matches!(ext, "c" | "cc" | "cpp" | "cxx" | "h" | "hh" | "hpp")
+                            })
+                    })
+                    .filter(|path| !self.config.should_exclude(path))
+                    .collect::<Vec<_>>()
             })
             .collect()
     }
@@ -219,6 +387,24 @@ impl<'a> FileFinder<'a> {
             .collect())
     }
 
+    /// Find AIDL interface files
+    pub fn find_aidl(&self, root: &Path) -> Result<Vec<SourceFile>> {
+        let files = self.find_files(root)?;
+        Ok(files
+            .into_iter()
+            .filter(|f| f.file_type == FileType::Aidl)
+            .collect())
+    }
+
+    /// Find protobuf definition files
+    pub fn find_proto(&self, root: &Path) -> Result<Vec<SourceFile>> {
+        let files = self.find_files(root)?;
+        Ok(files
+            .into_iter()
+            .filter(|f| f.file_type == FileType::Proto)
+            .collect())
+    }
+
     /// Find menu XML files
     pub fn find_menus(&self, root: &Path) -> Result<Vec<SourceFile>> {
         let files = self.find_files(root)?;
@@ -239,6 +425,8 @@ pub struct FileStats {
     pub navigation_files: usize,
     pub menu_files: usize,
     pub other_xml_files: usize,
+    pub aidl_files: usize,
+    pub proto_files: usize,
 }
 
 impl FileStats {
@@ -253,6 +441,8 @@ impl FileStats {
                 FileType::XmlNavigation => stats.navigation_files += 1,
                 FileType::XmlMenu => stats.menu_files += 1,
                 FileType::XmlOther => stats.other_xml_files += 1,
+                FileType::Aidl => stats.aidl_files += 1,
+                FileType::Proto => stats.proto_files += 1,
             }
         }
         stats
@@ -266,6 +456,8 @@ impl FileStats {
             + self.navigation_files
             + self.menu_files
             + self.other_xml_files
+            + self.aidl_files
+            + self.proto_files
     }
 
     pub fn source_files(&self) -> usize {
@@ -0,0 +1,446 @@
+// Gradle project discovery
+//
+// Reads `settings.gradle(.kts)` for the included modules and each module's
+// `build.gradle(.kts)` for its plugins, source directories and project
+// dependencies:
+// ```
+// // settings.gradle.kts
+// include(":app", ":core:ui")
+// project(":legacy").projectDir = file("old/legacy")
+//
+// // core/ui/build.gradle.kts
+// plugins { id("com.android.library") }
+// android { sourceSets["main"].java.srcDirs("src/main/kotlin", "generated") }
+// dependencies { implementation(project(":core:model")) }
+// ```
+// The scripts are matched with patterns, not evaluated, so modules included
+// from loops or computed paths are not found.
+
+#![allow(dead_code)] // API methods reserved for future use
+
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Settings script names, Kotlin DSL first
+const SETTINGS_FILES: &[&str] = &["settings.gradle.kts", "settings.gradle"];
+
+/// Build script names, Kotlin DSL first
+const BUILD_FILES: &[&str] = &["build.gradle.kts", "build.gradle"];
+
+/// What a module builds, from the plugins its build script applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModuleKind {
+    /// `com.android.application`
+    AndroidApplication,
+    /// `com.android.library`
+    AndroidLibrary,
+    /// `com.android.dynamic-feature`
+    DynamicFeature,
+    /// `java-library`, `kotlin("jvm")`, ...
+    Jvm,
+    /// No recognized plugin (e.g., convention plugins only)
+    Other,
+}
+
+impl ModuleKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModuleKind::AndroidApplication => "android-application",
+            ModuleKind::AndroidLibrary => "android-library",
+            ModuleKind::DynamicFeature => "dynamic-feature",
+            ModuleKind::Jvm => "jvm",
+            ModuleKind::Other => "other",
+        }
+    }
+}
+
+/// A Gradle module
+#[derive(Debug, Clone)]
+pub struct GradleModule {
+    /// Gradle project path (e.g., ":core:ui", ":" for the root project)
+    pub path: String,
+    /// Module directory
+    pub dir: PathBuf,
+    /// Build script, if the module has one
+    pub build_file: Option<PathBuf>,
+    pub kind: ModuleKind,
+    /// Directories (and single files, such as a relocated manifest) holding
+    /// the module's sources and resources
+    pub source_dirs: Vec<PathBuf>,
+    /// Project paths named by `project(":...")` dependencies
+    pub project_dependencies: Vec<String>,
+}
+
+/// A Gradle project: the root directory and the modules its settings include
+#[derive(Debug, Clone)]
+pub struct GradleProject {
+    pub root: PathBuf,
+    /// Settings script the modules were read from
+    pub settings_file: PathBuf,
+    pub modules: Vec<GradleModule>,
+}
+
+/// Patterns for reading settings and build scripts
+struct GradlePatterns {
+    // include(":a", ":b") / include ':a', ':b'
+    include: Regex,
+    // project(":a").projectDir = file("path") / new File(settingsDir, 'path')
+    project_dir: Regex,
+    quoted: Regex,
+    // srcDirs("a", "b"), srcDir 'a', manifest.srcFile 'AndroidManifest.xml'
+    source_dir: Regex,
+    // project(":a"), project(path: ':a')
+    project_dependency: Regex,
+}
+
+impl GradlePatterns {
+    fn new() -> Self {
+        Self {
+            include: Regex::new(
+                r#"(?m)^\s*include\s*(?:\(([^)]*)\)|([^\n]*(?:,[ \t]*\n[^\n]*)*))"#,
+            )
+            .unwrap(),
+            project_dir: Regex::new(
+                r#"project\(\s*["']([^"']+)["']\s*\)\.projectDir\s*=\s*(?:new\s+)?(?:file|File)\(\s*(?:\w+\s*,\s*)?["']([^"']+)["']"#,
+            )
+            .unwrap(),
+            quoted: Regex::new(r#"["']([^"'\n]+)["']"#).unwrap(),
+            source_dir: Regex::new(r"(?m)\b(?:srcDirs?|setSrcDirs|srcFile)\b[^\n]*").unwrap(),
+            project_dependency: Regex::new(
+                r#"\bproject\(\s*(?:path\s*[:=]\s*)?["'](:[^"']*)["']\s*\)"#,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl GradleProject {
+    /// Load the project rooted at `root`, if it has a settings script
+    pub fn load(root: &Path) -> Option<Self> {
+        let settings_file = SETTINGS_FILES
+            .iter()
+            .map(|name| root.join(name))
+            .find(|path| path.is_file())?;
+        let settings = strip_comments(&fs::read_to_string(&settings_file).ok()?);
+        let patterns = GradlePatterns::new();
+
+        let mut paths: Vec<String> = Vec::new();
+        for cap in patterns.include.captures_iter(&settings) {
+            let args = cap.get(1).or_else(|| cap.get(2)).map_or("", |m| m.as_str());
+            for name in patterns.quoted.captures_iter(args) {
+                let path = project_path(&name[1]);
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        let relocated: Vec<(String, String)> = patterns
+            .project_dir
+            .captures_iter(&settings)
+            .map(|cap| (project_path(&cap[1]), cap[2].to_string()))
+            .collect();
+
+        let mut modules = Vec::new();
+        if BUILD_FILES.iter().any(|name| root.join(name).is_file()) {
+            modules.push(GradleModule::load(":", root.to_path_buf(), &patterns));
+        }
+        for path in paths {
+            let dir = match relocated.iter().find(|(p, _)| *p == path) {
+                Some((_, dir)) => root.join(dir),
+                None => root.join(path.trim_start_matches(':').replace(':', "/")),
+            };
+            if dir.is_dir() {
+                modules.push(GradleModule::load(&path, dir, &patterns));
+            } else {
+                debug!("Skipping missing module {} ({})", path, dir.display());
+            }
+        }
+
+        debug!(
+            "Loaded {} Gradle modules from {}",
+            modules.len(),
+            settings_file.display()
+        );
+        Some(Self {
+            root: root.to_path_buf(),
+            settings_file,
+            modules,
+        })
+    }
+
+    /// Module by Gradle project path (e.g., ":core:ui")
+    pub fn module(&self, path: &str) -> Option<&GradleModule> {
+        self.modules.iter().find(|m| m.path == path)
+    }
+
+    /// Innermost module containing a file
+    pub fn module_for(&self, file: &Path) -> Option<&GradleModule> {
+        self.modules
+            .iter()
+            .filter(|m| file.starts_with(&m.dir))
+            .max_by_key(|m| m.dir.components().count())
+    }
+
+    /// Source directories of every module, without ones nested in another
+    pub fn source_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = self
+            .modules
+            .iter()
+            .flat_map(|m| m.source_dirs.iter().cloned())
+            .collect();
+        dirs.sort();
+        dirs.dedup();
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for dir in dirs {
+            if !roots.iter().any(|root| dir.starts_with(root)) {
+                roots.push(dir);
+            }
+        }
+        roots
+    }
+}
+
+/// Module path a file outside any known module most likely belongs to: the
+/// directory holding its `src` or `build` directory, relative to the
+/// project root (`core/ui/src/main/...` -> `:core:ui`)
+pub fn module_from_path(root: &Path, file: &Path) -> Option<String> {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    let mut segments = Vec::new();
+    for component in relative.components() {
+        let name = component.as_os_str().to_str()?;
+        if matches!(name, "src" | "build") {
+            return Some(format!(":{}", segments.join(":")));
+        }
+        segments.push(name);
+    }
+    None
+}
+
+impl GradleModule {
+    fn load(path: &str, dir: PathBuf, patterns: &GradlePatterns) -> Self {
+        let build_file = BUILD_FILES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file());
+        let script = build_file
+            .as_ref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .map(|content| strip_comments(&content))
+            .unwrap_or_default();
+
+        let mut source_dirs = vec![dir.join("src")];
+        for line in patterns.source_dir.find_iter(&script) {
+            for cap in patterns.quoted.captures_iter(line.as_str()) {
+                let source = dir.join(&cap[1]);
+                if !source_dirs.contains(&source) {
+                    source_dirs.push(source);
+                }
+            }
+        }
+        source_dirs.retain(|source| source.exists());
+
+        let mut project_dependencies: Vec<String> = Vec::new();
+        for cap in patterns.project_dependency.captures_iter(&script) {
+            if !project_dependencies.iter().any(|d| d == &cap[1]) {
+                project_dependencies.push(cap[1].to_string());
+            }
+        }
+
+        Self {
+            path: path.to_string(),
+            dir,
+            build_file,
+            kind: module_kind(&script),
+            source_dirs,
+            project_dependencies,
+        }
+    }
+}
+
+/// Module kind from the plugin ids and version catalog aliases a build
+/// script applies
+fn module_kind(script: &str) -> ModuleKind {
+    // `apply false` only puts a plugin on the build classpath
+    let applied: Vec<&str> = script
+        .lines()
+        .filter(|line| !line.contains("apply false"))
+        .collect();
+    let applies = |ids: &[&str]| {
+        ids.iter()
+            .any(|id| applied.iter().any(|line| line.contains(id)))
+    };
+    if applies(&["com.android.dynamic-feature", "dynamic.feature"]) {
+        ModuleKind::DynamicFeature
+    } else if applies(&["com.android.application", "android.application"]) {
+        ModuleKind::AndroidApplication
+    } else if applies(&["com.android.library", "android.library"]) {
+        ModuleKind::AndroidLibrary
+    } else if applies(&[
+        "java-library",
+        "kotlin(\"jvm\")",
+        "org.jetbrains.kotlin.jvm",
+        "kotlin.jvm",
+        "id 'java'",
+        "id(\"java\")",
+        "apply plugin: 'java'",
+    ]) {
+        ModuleKind::Jvm
+    } else {
+        ModuleKind::Other
+    }
+}
+
+/// `app` / `:core:ui` -> `:app` / `:core:ui`
+fn project_path(name: &str) -> String {
+    if name.starts_with(':') {
+        name.to_string()
+    } else {
+        format!(":{}", name)
+    }
+}
+
+/// Drop `//` line comments so commented-out includes are ignored
+pub(super) fn strip_comments(script: &str) -> String {
+    script
+        .lines()
+        .map(|line| match line.find("//") {
+            // Keep URLs such as "https://..."
+            Some(i) if !line[..i].ends_with(':') => &line[..i],
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_gradle_project() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "settings.gradle.kts",
+            r#"rootProject.name = "app"
+include(":app")
+include(
+    ":core:ui",
+    ":legacy",
+)
+// include(":removed")
+include ':feature:chat', 'missing'
+project(":legacy").projectDir = file("old/legacy")
+"#,
+        );
+        write(
+            root,
+            "build.gradle.kts",
+            "plugins { alias(libs.plugins.android.application) apply false }",
+        );
+        write(
+            root,
+            "app/build.gradle.kts",
+            r#"plugins { alias(libs.plugins.android.application) }
+dependencies {
+    implementation(project(":core:ui"))
+    implementation(project(path = ":legacy"))
+}"#,
+        );
+        write(root, "app/src/main/java/com/app/App.kt", "class App");
+        write(
+            root,
+            "core/ui/build.gradle.kts",
+            r#"plugins { id("com.android.library") }
+android { sourceSets["main"].java.srcDirs("src/main/kotlin", "generated") }"#,
+        );
+        write(root, "core/ui/src/main/kotlin/Button.kt", "class Button");
+        write(root, "core/ui/generated/Theme.kt", "class Theme");
+        write(
+            root,
+            "old/legacy/build.gradle",
+            "apply plugin: 'java-library'",
+        );
+        write(
+            root,
+            "old/legacy/src/main/java/Legacy.java",
+            "class Legacy {}",
+        );
+        write(
+            root,
+            "feature/chat/build.gradle",
+            "apply plugin: 'com.android.dynamic-feature'",
+        );
+
+        let project = GradleProject::load(root).unwrap();
+
+        let modules: Vec<_> = project
+            .modules
+            .iter()
+            .map(|m| (m.path.as_str(), m.kind))
+            .collect();
+        assert_eq!(
+            modules,
+            [
+                (":", ModuleKind::Other),
+                (":app", ModuleKind::AndroidApplication),
+                (":core:ui", ModuleKind::AndroidLibrary),
+                (":legacy", ModuleKind::Jvm),
+                (":feature:chat", ModuleKind::DynamicFeature),
+            ]
+        );
+        assert_eq!(
+            project.module(":app").unwrap().project_dependencies,
+            [":core:ui", ":legacy"]
+        );
+        assert_eq!(
+            project.module(":core:ui").unwrap().source_dirs,
+            [
+                root.join("core/ui/src"),
+                root.join("core/ui/src/main/kotlin"),
+                root.join("core/ui/generated")
+            ]
+        );
+        assert_eq!(
+            project
+                .module_for(&root.join("old/legacy/src/main/java/Legacy.java"))
+                .map(|m| m.path.as_str()),
+            Some(":legacy")
+        );
+        assert_eq!(
+            project
+                .module_for(&root.join("buildSrc/Deps.kt"))
+                .map(|m| m.path.as_str()),
+            Some(":")
+        );
+        assert_eq!(
+            project.source_dirs(),
+            [
+                root.join("app/src"),
+                root.join("core/ui/generated"),
+                root.join("core/ui/src"),
+                root.join("old/legacy/src"),
+            ]
+        );
+    }
+    #[test]
+    fn test_module_from_path() {
+        let root = Path::new("/work/project");
+        let module = |rel: &str| module_from_path(root, &root.join(rel));
+        assert_eq!(
+            module("core/ui/src/main/kotlin/Button.kt").as_deref(),
+            Some(":core:ui")
+        );
+        assert_eq!(
+            module("app/build/generated/ksp/Factory.kt").as_deref(),
+            Some(":app")
+        );
+        assert_eq!(module("src/main/java/Main.java").as_deref(), Some(":"));
+        assert_eq!(module("scripts/Tool.kt"), None);
+    }
+}
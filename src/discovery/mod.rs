@@ -1,3 +1,9 @@
+pub mod catalog;
 mod file_finder;
+pub mod gradle;
+mod source_set;
 
 pub use file_finder::{FileFinder, FileType, SourceFile};
+#[allow(unused_imports)] // only named by library consumers
+pub use gradle::GradleProject;
+pub use source_set::SourceSetKind;
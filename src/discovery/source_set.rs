@@ -0,0 +1,112 @@
+//! Source set classification
+//!
+//! Gradle projects split a module's sources into source sets
+//! (`src/main`, `src/test`, `src/androidTest`, `src/debug`, ...). The kind
+//! of source set a file belongs to is worked out once, while discovering
+//! files, so detectors don't each guess from path fragments.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Kind of source set a file belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SourceSetKind {
+    /// Production code (`src/main`, or no source set at all)
+    #[default]
+    Main,
+    /// Local unit tests (`src/test`, `src/testFixtures`, `src/testDebug`, ...)
+    Test,
+    /// Instrumented tests (`src/androidTest`, `src/androidTestDebug`, ...)
+    AndroidTest,
+    /// Debug build type (`src/debug`)
+    Debug,
+    /// Release build type (`src/release`)
+    Release,
+    /// Any other product flavor or build type (`src/free`, `src/staging`, ...)
+    Flavor,
+}
+
+impl SourceSetKind {
+    /// Classify a path by the directory following its last `src` component
+    pub fn from_path(path: &Path) -> Self {
+        let components: Vec<_> = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect();
+        components
+            .windows(2)
+            .rev()
+            .find(|pair| pair[0] == "src")
+            .map(|pair| Self::from_name(&pair[1]))
+            .unwrap_or_default()
+    }
+
+    /// Classify a source set by name (`main`, `testDebug`, `androidTest`, ...)
+    pub fn from_name(name: &str) -> Self {
+        // A variant suffix starts with an uppercase letter: `testDebug`
+        // is a test source set, `testing` is not
+        let is_variant_of = |prefix: &str| {
+            name.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_uppercase))
+        };
+
+        if is_variant_of("androidTest") {
+            SourceSetKind::AndroidTest
+        } else if is_variant_of("test") || is_variant_of("sharedTest") {
+            SourceSetKind::Test
+        } else {
+            match name {
+                "main" | "commonMain" | "androidMain" | "jvmMain" => SourceSetKind::Main,
+                "debug" => SourceSetKind::Debug,
+                "release" => SourceSetKind::Release,
+                _ if name.ends_with("Test") => SourceSetKind::Test,
+                _ if name.ends_with("Main") => SourceSetKind::Main,
+                _ => SourceSetKind::Flavor,
+            }
+        }
+    }
+
+    /// Check if this source set holds test code
+    pub fn is_test(&self) -> bool {
+        matches!(self, SourceSetKind::Test | SourceSetKind::AndroidTest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(path: &str) -> SourceSetKind {
+        SourceSetKind::from_path(Path::new(path))
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(kind("app/src/main/java/A.kt"), SourceSetKind::Main);
+        assert_eq!(kind("app/src/test/java/A.kt"), SourceSetKind::Test);
+        assert_eq!(
+            kind("app/src/testFixtures/kotlin/A.kt"),
+            SourceSetKind::Test
+        );
+        assert_eq!(kind("app/src/testDebug/java/A.kt"), SourceSetKind::Test);
+        assert_eq!(
+            kind("app/src/androidTest/java/A.kt"),
+            SourceSetKind::AndroidTest
+        );
+        assert_eq!(kind("app/src/debug/java/A.kt"), SourceSetKind::Debug);
+        assert_eq!(kind("app/src/release/java/A.kt"), SourceSetKind::Release);
+        assert_eq!(kind("app/src/staging/java/A.kt"), SourceSetKind::Flavor);
+        assert_eq!(
+            kind("shared/src/commonTest/kotlin/A.kt"),
+            SourceSetKind::Test
+        );
+        assert_eq!(kind("shared/src/iosMain/kotlin/A.kt"), SourceSetKind::Main);
+        assert_eq!(
+            kind("app/src/main/java/com/app/test/A.kt"),
+            SourceSetKind::Main
+        );
+        assert_eq!(kind("app/src/testing/A.kt"), SourceSetKind::Flavor);
+        assert_eq!(kind("lib/Foo.kt"), SourceSetKind::Main);
+    }
+}
@@ -0,0 +1,202 @@
+//! C API for in-process embedding
+//!
+//! A small `extern "C"` surface so JVM tooling (a Gradle plugin via JNI or
+//! JNA) can run an analysis without spawning the CLI and scraping stdout.
+//! Build the shared library with:
+//!
+//! ```text
+//! cargo rustc --profile release-ffi --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! The `release-ffi` profile unwinds on panic, unlike `release`, so that
+//! [`sdc_analyze`] can turn a panic into an error result.
+//!
+//! All strings are NUL-terminated UTF-8. Strings returned by this module are
+//! owned by the caller and must be released with [`sdc_string_free`].
+//! See `include/searchdeadcode.h` and `docs/ffi.md`.
+
+use crate::config::Config;
+use crate::session::AnalysisSession;
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use serde_json::{json, Value};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+
+/// NUL-terminated crate version, e.g. `"0.4.0"`
+static VERSION: &CStr =
+    match CStr::from_bytes_with_nul(concat!(env!("CARGO_PKG_VERSION"), "\0").as_bytes()) {
+        Ok(version) => version,
+        Err(_) => panic!("version contains a NUL byte"),
+    };
+
+/// Analyze the project at `path` and return the results as JSON
+///
+/// `config_json` is a [`Config`] serialized as JSON, or null to load the
+/// project's `.deadcode.yml` / `.deadcode.toml` like the CLI does.
+///
+/// Always returns a string. On success it is
+/// `{"version", "files_analyzed", "dead_code": [...]}`; on failure it is
+/// `{"version", "error": "..."}`. Returns null only if the result can't be
+/// represented as a C string. A panic in the analysis is reported as an
+/// error when the library is built with `panic = "unwind"` (the
+/// `release-ffi` profile); under `panic = "abort"` it aborts the process.
+///
+/// # Safety
+///
+/// `path` must be a valid NUL-terminated string. `config_json` must be null
+/// or a valid NUL-terminated string. Both only need to live for the call.
+#[no_mangle]
+pub unsafe extern "C" fn sdc_analyze(
+    path: *const c_char,
+    config_json: *const c_char,
+) -> *mut c_char {
+    let path = borrow_str(path);
+    let config_json = borrow_str(config_json);
+
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| analyze(path, config_json)))
+        .unwrap_or_else(|_| Err(miette!("analysis panicked")));
+
+    let response = match outcome {
+        Ok(mut response) => {
+            response["version"] = json!(env!("CARGO_PKG_VERSION"));
+            response
+        }
+        Err(e) => json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "error": e.chain().map(ToString::to_string).collect::<Vec<_>>().join(": "),
+        }),
+    };
+
+    into_c_string(response.to_string())
+}
+
+/// Release a string returned by this library
+///
+/// # Safety
+///
+/// `s` must be null or a pointer returned by [`sdc_analyze`] that hasn't
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn sdc_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Crate version as a static string; must not be freed
+#[no_mangle]
+pub extern "C" fn sdc_version() -> *const c_char {
+    VERSION.as_ptr()
+}
+
+/// Borrow a C string, treating null as absent
+///
+/// Invalid UTF-8 is passed on as `Err` so the caller gets a proper error
+/// response instead of a crash.
+unsafe fn borrow_str<'a>(s: *const c_char) -> Option<std::result::Result<&'a str, String>> {
+    if s.is_null() {
+        return None;
+    }
+    Some(
+        CStr::from_ptr(s)
+            .to_str()
+            .map_err(|e| format!("argument is not valid UTF-8: {e}")),
+    )
+}
+
+fn analyze(
+    path: Option<std::result::Result<&str, String>>,
+    config_json: Option<std::result::Result<&str, String>>,
+) -> Result<Value> {
+    let path = match path {
+        Some(path) => PathBuf::from(path.map_err(|e| miette!("path: {e}"))?),
+        None => return Err(miette!("path must not be null")),
+    };
+    if !path.is_dir() {
+        return Err(miette!("not a directory: {}", path.display()));
+    }
+
+    let config = match config_json {
+        Some(json) => {
+            let json = json.map_err(|e| miette!("config_json: {e}"))?;
+            serde_json::from_str::<Config>(json)
+                .into_diagnostic()
+                .wrap_err("Invalid config_json")?
+        }
+        None => Config::from_default_locations(&path)?,
+    };
+
+    let results = AnalysisSession::builder().path(path).config(config).run()?;
+
+    Ok(json!({
+        "files_analyzed": results.files_analyzed,
+        "dead_code": results.dead_code,
+    }))
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    CString::new(s).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn call(path: Option<&str>, config_json: Option<&str>) -> Value {
+        let path = path.map(|p| CString::new(p).unwrap());
+        let config_json = config_json.map(|c| CString::new(c).unwrap());
+        unsafe {
+            let raw = sdc_analyze(
+                path.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                config_json
+                    .as_ref()
+                    .map_or(std::ptr::null(), |c| c.as_ptr()),
+            );
+            assert!(!raw.is_null());
+            let value = serde_json::from_str(CStr::from_ptr(raw).to_str().unwrap()).unwrap();
+            sdc_string_free(raw);
+            value
+        }
+    }
+
+    #[test]
+    fn test_analyze_reports_dead_code() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Main.kt"),
+            "package app\n\nfun main() {}\n\nclass NeverUsed\n",
+        )
+        .unwrap();
+
+        let response = call(dir.path().to_str(), None);
+        assert!(response.get("error").is_none(), "{response}");
+        assert_eq!(response["files_analyzed"], 1);
+        let dead_code = response["dead_code"].as_array().unwrap();
+        assert!(dead_code
+            .iter()
+            .any(|d| d["declaration"]["name"] == "NeverUsed"));
+    }
+
+    #[test]
+    fn test_analyze_returns_errors_as_json() {
+        let response = call(None, None);
+        assert_eq!(response["error"], "path must not be null");
+
+        let dir = TempDir::new().unwrap();
+        let response = call(dir.path().to_str(), Some("{not json"));
+        assert!(response["error"]
+            .as_str()
+            .unwrap()
+            .contains("Invalid config_json"));
+    }
+
+    #[test]
+    fn test_version() {
+        let version = unsafe { CStr::from_ptr(sdc_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+        unsafe { sdc_string_free(std::ptr::null_mut()) };
+    }
+}
@@ -0,0 +1,227 @@
+//! Quality gate for CI
+//!
+//! `--fail-on` and the `gate` configuration keys decide whether the reported
+//! findings fail the run. A failing run exits with 2 when errors are among
+//! the findings and with 1 otherwise, so pipelines can tell a broken budget
+//! from findings that must be fixed.
+
+use crate::analysis::{DeadCode, Severity};
+use crate::config::GateConfig;
+use miette::Diagnostic;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Exit code of a failed gate with error findings
+pub const EXIT_ERRORS: u8 = 2;
+/// Exit code of a failed gate without error findings
+pub const EXIT_FINDINGS: u8 = 1;
+
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid fail-on '{0}': expected info, warning, error or a number of findings (1 or more)")]
+pub struct InvalidFailOn(String);
+
+/// When `--fail-on` fails the run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailOn {
+    /// Any finding of this severity or above
+    Severity(Severity),
+    /// At least this many findings
+    Count(usize),
+}
+
+impl FromStr for FailOn {
+    type Err = InvalidFailOn;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(FailOn::Severity(Severity::Info)),
+            "warning" => Ok(FailOn::Severity(Severity::Warning)),
+            "error" => Ok(FailOn::Severity(Severity::Error)),
+            count => match count.parse() {
+                Ok(count) if count > 0 => Ok(FailOn::Count(count)),
+                _ => Err(InvalidFailOn(s.to_string())),
+            },
+        }
+    }
+}
+
+/// Conditions the findings of a run must meet
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Gate {
+    pub fail_on: Option<FailOn>,
+    pub max_errors: Option<usize>,
+    pub max_warnings: Option<usize>,
+    pub max_new_issues: Option<usize>,
+}
+
+/// Why a gate failed, and the exit code to report it with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateFailure {
+    pub reasons: Vec<String>,
+    pub exit_code: u8,
+}
+
+impl Gate {
+    pub fn from_config(config: &GateConfig) -> Result<Self, InvalidFailOn> {
+        Ok(Self {
+            fail_on: config.fail_on.as_deref().map(str::parse).transpose()?,
+            max_errors: config.max_errors,
+            max_warnings: config.max_warnings,
+            max_new_issues: config.max_new_issues,
+        })
+    }
+
+    /// Check the reported findings; with a baseline they are the new ones
+    ///
+    /// `max_new_issues` only applies when `baseline_loaded`: without a
+    /// baseline no finding is known to be new.
+    pub fn check(&self, dead_code: &[DeadCode], baseline_loaded: bool) -> Option<GateFailure> {
+        let count = |severity| {
+            dead_code
+                .iter()
+                .filter(|dc| dc.severity == severity)
+                .count()
+        };
+        let errors = count(Severity::Error);
+        let mut reasons = Vec::new();
+
+        match self.fail_on {
+            Some(FailOn::Severity(severity)) => {
+                let found = dead_code
+                    .iter()
+                    .filter(|dc| dc.severity >= severity)
+                    .count();
+                if found > 0 {
+                    reasons.push(format!(
+                        "{} findings at {} severity or above (fail-on {})",
+                        found, severity, severity
+                    ));
+                }
+            }
+            Some(FailOn::Count(limit)) if dead_code.len() >= limit => {
+                reasons.push(format!("{} findings (fail-on {})", dead_code.len(), limit));
+            }
+            _ => {}
+        }
+
+        let budgets = [
+            ("errors", errors, self.max_errors, "max_errors"),
+            (
+                "warnings",
+                count(Severity::Warning),
+                self.max_warnings,
+                "max_warnings",
+            ),
+            (
+                "new issues",
+                dead_code.len(),
+                self.max_new_issues.filter(|_| baseline_loaded),
+                "max_new_issues",
+            ),
+        ];
+        for (what, found, max, key) in budgets {
+            if let Some(max) = max.filter(|max| found > *max) {
+                reasons.push(format!("{} {}, more than {} ({})", found, what, key, max));
+            }
+        }
+
+        if reasons.is_empty() {
+            return None;
+        }
+        Some(GateFailure {
+            reasons,
+            exit_code: if errors > 0 {
+                EXIT_ERRORS
+            } else {
+                EXIT_FINDINGS
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+    use std::path::PathBuf;
+
+    fn finding(severity: Severity) -> DeadCode {
+        let path = PathBuf::from("Main.kt");
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), 0, 10),
+            "unused",
+            DeclarationKind::Function,
+            Location::new(path, 1, 1, 0, 10),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, DeadCodeIssue::Unreferenced).with_severity(severity)
+    }
+
+    #[test]
+    fn test_parse_fail_on() {
+        assert_eq!(
+            "Error".parse::<FailOn>(),
+            Ok(FailOn::Severity(Severity::Error))
+        );
+        assert_eq!("25".parse::<FailOn>(), Ok(FailOn::Count(25)));
+        assert!("0".parse::<FailOn>().is_err());
+        assert!("fatal".parse::<FailOn>().is_err());
+    }
+
+    #[test]
+    fn test_gate_exit_codes() {
+        let warnings = vec![finding(Severity::Warning), finding(Severity::Warning)];
+        let mut with_error = warnings.clone();
+        with_error.push(finding(Severity::Error));
+
+        assert_eq!(Gate::default().check(&with_error, true), None);
+
+        let fail_on_error = Gate {
+            fail_on: Some(FailOn::Severity(Severity::Error)),
+            ..Gate::default()
+        };
+        assert_eq!(fail_on_error.check(&warnings, true), None);
+        let failure = fail_on_error.check(&with_error, true).unwrap();
+        assert_eq!(failure.exit_code, EXIT_ERRORS);
+        assert_eq!(
+            failure.reasons,
+            ["1 findings at error severity or above (fail-on error)"]
+        );
+
+        let budget = Gate {
+            max_warnings: Some(1),
+            max_new_issues: Some(2),
+            ..Gate::default()
+        };
+        let failure = budget.check(&warnings, true).unwrap();
+        assert_eq!(failure.exit_code, EXIT_FINDINGS);
+        assert_eq!(failure.reasons, ["2 warnings, more than max_warnings (1)"]);
+        assert_eq!(budget.check(&with_error, true).unwrap().reasons.len(), 2);
+
+        let count = Gate {
+            fail_on: Some(FailOn::Count(3)),
+            ..Gate::default()
+        };
+        assert_eq!(count.check(&warnings, true), None);
+        assert_eq!(
+            count.check(&with_error, true).unwrap().exit_code,
+            EXIT_ERRORS
+        );
+    }
+
+    #[test]
+    fn test_max_new_issues_needs_baseline() {
+        let warnings = vec![finding(Severity::Warning), finding(Severity::Warning)];
+        let gate = Gate {
+            max_new_issues: Some(1),
+            ..Gate::default()
+        };
+
+        assert_eq!(
+            gate.check(&warnings, true).unwrap().reasons,
+            ["2 new issues, more than max_new_issues (1)"]
+        );
+        assert_eq!(gate.check(&warnings, false), None);
+    }
+}
@@ -1,7 +1,16 @@
-use super::{Declaration, DeclarationId, Graph, Reference, ReferenceKind};
+use super::cache::{CachedFile, GraphCache};
+use super::{
+    CallArguments, CallReceiver, Declaration, DeclarationId, DeclarationKind, FileScope, Graph,
+    Language, Reference, ReferenceConfidence, ReferenceKind, Symbol,
+};
 use crate::discovery::{FileType, SourceFile};
-use crate::parser::{JavaParser, KotlinParser, Parser as SourceParser};
+use crate::parser::xml::{LayoutParser, XmlParseResult};
+use crate::parser::{JavaParser, KotlinParser, Parser as SourceParser, TreeCache};
 use miette::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 use tracing::debug;
 
 /// Builder for constructing the reference graph
@@ -17,14 +26,31 @@ pub struct GraphBuilder {
 
     /// Unresolved references to be resolved after all files are parsed
     unresolved_references: Vec<UnresolvedRef>,
+
+    /// Parsed layouts (path, length), whose references are added last
+    layouts: Vec<(PathBuf, usize, XmlParseResult)>,
+
+    /// Typealiases and the declarations they stand for
+    type_aliases: HashMap<DeclarationId, Vec<DeclarationId>>,
+
+    /// Syntax trees of earlier builds, for incremental re-parsing
+    trees: Option<Arc<TreeCache>>,
+
+    /// Files derived by earlier builds, which are not parsed again
+    graph_cache: Option<Arc<GraphCache>>,
 }
 
-struct UnresolvedRef {
-    from: DeclarationId,
-    name: String,
-    qualified_name: Option<String>,
-    kind: ReferenceKind,
-    imports: Vec<String>,
+/// A reference found by the parser, resolved once every file is parsed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct UnresolvedRef {
+    pub(super) from: DeclarationId,
+    pub(super) name: String,
+    pub(super) qualified_name: Option<String>,
+    pub(super) kind: ReferenceKind,
+    pub(super) package: Option<String>,
+    pub(super) imports: Vec<String>,
+    pub(super) arguments: Option<CallArguments>,
+    pub(super) receiver: Option<CallReceiver>,
 }
 
 impl GraphBuilder {
@@ -34,38 +60,132 @@ impl GraphBuilder {
             kotlin_parser: KotlinParser::new(),
             java_parser: JavaParser::new(),
             unresolved_references: Vec::new(),
+            layouts: Vec::new(),
+            type_aliases: HashMap::new(),
+            trees: None,
+            graph_cache: None,
         }
     }
 
+    /// Re-parse files incrementally against the trees kept in `trees`
+    pub fn with_tree_cache(mut self, trees: Arc<TreeCache>) -> Self {
+        self.trees = Some(trees);
+        self
+    }
+
+    /// Take the declarations and references of source files whose contents
+    /// are unchanged from `cache`, and record those of the files parsed
+    pub fn with_graph_cache(mut self, cache: Arc<GraphCache>) -> Self {
+        self.graph_cache = Some(cache);
+        self
+    }
+
     /// Process a source file and add its declarations to the graph
     pub fn process_file(&mut self, file: &SourceFile) -> Result<()> {
         let contents = file.read_contents()?;
 
+        if file.file_type.is_source() {
+            if let Some(cached) = self
+                .graph_cache
+                .as_ref()
+                .and_then(|cache| cache.get(&file.path, &contents, file.generated))
+            {
+                self.add_cached_file(file, cached);
+                return Ok(());
+            }
+        }
+
+        let first_reference = self.unresolved_references.len();
         match file.file_type {
             FileType::Kotlin => {
-                self.process_kotlin_file(&file.path, &contents)?;
+                let declarations = self.process_kotlin_file(file, &contents)?;
+                self.cache_file(file, &contents, &declarations, first_reference);
             }
             FileType::Java => {
-                self.process_java_file(&file.path, &contents)?;
+                let declarations = self.process_java_file(file, &contents)?;
+                self.cache_file(file, &contents, &declarations, first_reference);
+            }
+            FileType::XmlLayout => {
+                // Data binding and onClick references are added once every
+                // declaration is known
+                let layout = LayoutParser::new().parse(&file.path, &contents)?;
+                self.layouts
+                    .push((file.path.clone(), contents.len(), layout));
             }
-            FileType::XmlManifest
-            | FileType::XmlLayout
-            | FileType::XmlNavigation
-            | FileType::XmlMenu => {
+            FileType::XmlManifest | FileType::XmlNavigation | FileType::XmlMenu => {
                 // XML files are processed separately for entry point detection
             }
-            FileType::XmlOther => {
-                // Ignore other XML files
+            FileType::XmlOther | FileType::Aidl | FileType::Proto => {
+                // Ignore other XML files; AIDL is read for entry points
             }
         }
 
         Ok(())
     }
 
-    fn process_kotlin_file(&mut self, path: &std::path::Path, contents: &str) -> Result<()> {
+    /// Record what was derived from a parsed source file in the graph cache
+    fn cache_file(
+        &self,
+        file: &SourceFile,
+        contents: &str,
+        declarations: &[Declaration],
+        first_reference: usize,
+    ) {
+        if let (Some(cache), Some(scope)) = (&self.graph_cache, self.graph.file_scope(&file.path)) {
+            cache.insert(
+                &file.path,
+                contents,
+                file.generated,
+                declarations,
+                &self.unresolved_references[first_reference..],
+                scope,
+            );
+        }
+    }
+
+    /// Add what an earlier build derived from a file
+    fn add_cached_file(&mut self, file: &SourceFile, cached: CachedFile) {
+        debug!("Using cached graph of {}", file.path.display());
+        self.graph.set_file_scope(file.path.clone(), cached.scope);
+        for mut decl in cached.declarations {
+            // Modules may have been moved or renamed since
+            decl.module = file.module.as_deref().map(Symbol::new);
+            self.graph.add_declaration(decl);
+        }
+        self.unresolved_references.extend(cached.unresolved_refs);
+    }
+
+    fn process_kotlin_file(
+        &mut self,
+        file: &SourceFile,
+        contents: &str,
+    ) -> Result<Vec<Declaration>> {
+        let path = file.path.as_path();
         debug!("Parsing Kotlin file: {}", path.display());
 
-        let parse_result = self.kotlin_parser.parse(path, contents)?;
+        let mut parse_result = match &self.trees {
+            Some(trees) => self
+                .kotlin_parser
+                .parse_incremental(path, contents, trees)?,
+            None => self.kotlin_parser.parse(path, contents)?,
+        };
+        // Generated code only contributes its references
+        if file.generated {
+            parse_result.declarations = vec![Declaration::generated_file(
+                path,
+                contents.len(),
+                Language::Kotlin,
+            )];
+        }
+        for decl in &mut parse_result.declarations {
+            decl.source_set = file.source_set;
+            decl.module = file.module.as_deref().map(Symbol::new);
+        }
+
+        self.graph.set_file_scope(
+            path.to_path_buf(),
+            FileScope::new(parse_result.package.clone(), parse_result.imports.clone()),
+        );
 
         // Add declarations to graph (clone since we need to reference them later)
         let declarations = parse_result.declarations.clone();
@@ -74,15 +194,42 @@ impl GraphBuilder {
         }
 
         // Store unresolved references for later resolution
-        self.store_unresolved_references(&declarations, parse_result.references);
-
-        Ok(())
+        self.store_unresolved_references(
+            &declarations,
+            parse_result.package,
+            parse_result.references,
+            parse_result.call_arguments,
+            parse_result.call_receivers,
+        );
+
+        Ok(declarations)
     }
 
-    fn process_java_file(&mut self, path: &std::path::Path, contents: &str) -> Result<()> {
+    fn process_java_file(&mut self, file: &SourceFile, contents: &str) -> Result<Vec<Declaration>> {
+        let path = file.path.as_path();
         debug!("Parsing Java file: {}", path.display());
 
-        let parse_result = self.java_parser.parse(path, contents)?;
+        let mut parse_result = match &self.trees {
+            Some(trees) => self.java_parser.parse_incremental(path, contents, trees)?,
+            None => self.java_parser.parse(path, contents)?,
+        };
+        // Generated code only contributes its references
+        if file.generated {
+            parse_result.declarations = vec![Declaration::generated_file(
+                path,
+                contents.len(),
+                Language::Java,
+            )];
+        }
+        for decl in &mut parse_result.declarations {
+            decl.source_set = file.source_set;
+            decl.module = file.module.as_deref().map(Symbol::new);
+        }
+
+        self.graph.set_file_scope(
+            path.to_path_buf(),
+            FileScope::new(parse_result.package.clone(), parse_result.imports.clone()),
+        );
 
         // Add declarations to graph (clone since we need to reference them later)
         let declarations = parse_result.declarations.clone();
@@ -91,16 +238,25 @@ impl GraphBuilder {
         }
 
         // Store unresolved references for later resolution
-        self.store_unresolved_references(&declarations, parse_result.references);
-
-        Ok(())
+        self.store_unresolved_references(
+            &declarations,
+            parse_result.package,
+            parse_result.references,
+            parse_result.call_arguments,
+            parse_result.call_receivers,
+        );
+
+        Ok(declarations)
     }
 
     /// Store unresolved references, attributing each to the correct enclosing declaration
     fn store_unresolved_references(
         &mut self,
         declarations: &[Declaration],
+        package: Option<String>,
         references: Vec<crate::graph::UnresolvedReference>,
+        mut call_arguments: HashMap<usize, CallArguments>,
+        mut call_receivers: HashMap<usize, CallReceiver>,
     ) {
         for unresolved in references {
             // Find the declaration that CONTAINS this reference (by byte range)
@@ -125,13 +281,38 @@ impl GraphBuilder {
                     .find(|d| d.location.file == unresolved.location.file)
             });
 
+            // A parameter's type and annotations are used whenever its
+            // function is
+            let from_decl = from_decl.map(|d| match &d.parent {
+                Some(parent)
+                    if d.kind == DeclarationKind::Parameter && unresolved.kind.is_signature() =>
+                {
+                    declarations.iter().find(|p| &p.id == parent).unwrap_or(d)
+                }
+                _ => d,
+            });
+
             if let Some(from_decl) = from_decl {
+                let (arguments, receiver) = match unresolved.kind {
+                    ReferenceKind::Call => (
+                        call_arguments.remove(&ref_byte),
+                        call_receivers.remove(&ref_byte),
+                    ),
+                    // `Foo.CONSTANT` is qualified like `Foo.create()`
+                    ReferenceKind::Read | ReferenceKind::Write => {
+                        (None, call_receivers.remove(&ref_byte))
+                    }
+                    _ => (None, None),
+                };
                 self.unresolved_references.push(UnresolvedRef {
                     from: from_decl.id.clone(),
                     name: unresolved.name,
                     qualified_name: unresolved.qualified_name,
                     kind: unresolved.kind,
+                    package: package.clone(),
                     imports: unresolved.imports,
+                    arguments,
+                    receiver,
                 });
             }
         }
@@ -140,15 +321,27 @@ impl GraphBuilder {
     /// Build the final graph, resolving all references
     pub fn build(mut self) -> Graph {
         self.resolve_references();
+        for (path, len, layout) in &self.layouts {
+            self.graph.add_layout_references(path, *len, layout);
+        }
+        self.graph.link_expect_actual();
+        self.graph.link_class_delegation();
+        // Resolved here so analyzers sharing the graph find it ready
+        self.graph.hierarchy();
         self.graph
     }
 
     /// Resolve all unresolved references
     fn resolve_references(&mut self) {
         let references = std::mem::take(&mut self.unresolved_references);
+        self.type_aliases = self.graph.type_alias_targets();
 
         for unresolved in references {
-            let resolved_ids = self.resolve_reference(&unresolved);
+            let (mut resolved_ids, confidence) = self.resolve_reference(&unresolved);
+            // A reference through a typealias also uses what it aliases
+            for id in resolved_ids.clone() {
+                resolved_ids.extend(self.type_aliases.get(&id).into_iter().flatten().cloned());
+            }
             for to_id in resolved_ids {
                 // Skip self-references (e.g., property referencing itself in initialization)
                 // These are artifacts of parsing and don't represent actual code usage
@@ -186,19 +379,54 @@ impl GraphBuilder {
                         unresolved.from.end,
                     ),
                     unresolved.name.clone(),
-                );
+                )
+                .with_arguments(unresolved.arguments.clone())
+                .with_confidence(confidence);
                 self.graph
                     .add_reference(&unresolved.from, &to_id, reference);
             }
         }
     }
 
-    /// Try to resolve a reference to declarations (may return multiple for overloaded functions)
-    fn resolve_reference(&self, unresolved: &UnresolvedRef) -> Vec<DeclarationId> {
+    /// Try to resolve a reference to declarations (may return multiple for
+    /// overloaded functions), and how surely it points at them
+    fn resolve_reference(
+        &self,
+        unresolved: &UnresolvedRef,
+    ) -> (Vec<DeclarationId>, ReferenceConfidence) {
+        // Extensions and qualified members are narrowed by the call's
+        // receiver, overloads by its arguments
+        let narrow = |found: Vec<&Declaration>| -> Vec<DeclarationId> {
+            let found = self.graph.narrow_to_receiver(
+                found,
+                unresolved.receiver.as_ref(),
+                &unresolved.from,
+            );
+            let found = self
+                .graph
+                .narrow_to_qualifier(found, unresolved.receiver.as_ref());
+            self.graph
+                .narrow_to_arity(found, unresolved.arguments.as_ref())
+                .iter()
+                .map(|d| d.id.clone())
+                .collect()
+        };
+
+        // A string names a declaration exactly or not at all
+        if unresolved.kind == ReferenceKind::ReflectionString {
+            let found = self
+                .graph
+                .find_named_in_string(&unresolved.name, unresolved.qualified_name.as_deref())
+                .iter()
+                .map(|d| d.id.clone())
+                .collect();
+            return (found, ReferenceConfidence::Heuristic);
+        }
+
         // Try fully qualified name first
         if let Some(fqn) = &unresolved.qualified_name {
             if let Some(decl) = self.graph.find_by_fqn(fqn) {
-                return vec![decl.id.clone()];
+                return (vec![decl.id.clone()], ReferenceConfidence::Exact);
             }
         }
 
@@ -209,13 +437,18 @@ impl GraphBuilder {
                 let package = &import[..import.len() - 2];
                 let fqn = format!("{}.{}", package, unresolved.name);
                 if let Some(decl) = self.graph.find_by_fqn(&fqn) {
-                    return vec![decl.id.clone()];
+                    return (narrow(vec![decl]), ReferenceConfidence::Exact);
                 }
             }
             // Specific import
             else if import.ends_with(&format!(".{}", unresolved.name)) {
                 if let Some(decl) = self.graph.find_by_fqn(import) {
-                    return vec![decl.id.clone()];
+                    return (narrow(vec![decl]), ReferenceConfidence::Exact);
+                }
+                // `import com.app.Foo.Companion.create`
+                let members = self.graph.find_qualified_member(import);
+                if unresolved.receiver.is_none() && !members.is_empty() {
+                    return (narrow(members), ReferenceConfidence::Exact);
                 }
             }
             // Aliased import (Kotlin)
@@ -224,21 +457,32 @@ impl GraphBuilder {
                 if alias == unresolved.name {
                     let original = &import[..alias_start];
                     if let Some(decl) = self.graph.find_by_fqn(original) {
-                        return vec![decl.id.clone()];
+                        return (narrow(vec![decl]), ReferenceConfidence::Exact);
+                    }
+                    let members = self.graph.find_qualified_member(original);
+                    if !members.is_empty() {
+                        return (narrow(members), ReferenceConfidence::Exact);
                     }
                 }
             }
         }
 
         // Try simple name match - return ALL candidates for overloaded functions
-        let candidates = self.graph.find_by_name(&unresolved.name);
-        if !candidates.is_empty() {
-            // For ambiguous references (overloaded functions), mark all as referenced
-            // This is conservative but avoids false positives
-            return candidates.iter().map(|c| c.id.clone()).collect();
-        }
-
-        Vec::new()
+        // For ambiguous references (overloaded functions), mark all as referenced
+        // This is conservative but avoids false positives. Same-named types in
+        // different packages are told apart by the file's package and imports.
+        let found = narrow(self.graph.narrow_to_imports(
+            self.graph.find_in_scope(&unresolved.name, &unresolved.from),
+            &unresolved.name,
+            unresolved.package.as_deref(),
+            &unresolved.imports,
+        ));
+        let confidence = if found.len() > 1 {
+            ReferenceConfidence::NameMatch
+        } else {
+            ReferenceConfidence::Exact
+        };
+        (found, confidence)
     }
 }
 
@@ -251,6 +495,7 @@ impl Default for GraphBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::write;
 
     #[test]
     fn test_graph_builder_creation() {
@@ -258,4 +503,458 @@ mod tests {
         let graph = builder.build();
         assert_eq!(graph.declaration_count(), 0);
     }
+
+    #[test]
+    fn test_extension_calls_resolve_by_receiver() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, FileType::Kotlin)
+        };
+        let ext = write(
+            "SlugExt.kt",
+            r#"package com.app.util
+
+fun String.toSlug(): String = lowercase()
+
+fun Uri.toSlug(): String = path.orEmpty()
+
+fun Int.toSlug(): String = toString()
+"#,
+        );
+        let screen = write(
+            "Screen.kt",
+            r#"package com.app.ui
+
+import com.app.util.toSlug
+
+class Screen {
+    private val uri: Uri = Uri.EMPTY
+
+    fun title(name: String?) = name?.toSlug() + uri.toSlug() + "a b".toSlug()
+}
+"#,
+        );
+
+        let mut builder = GraphBuilder::new();
+        builder.process_file(&ext).unwrap();
+        builder.process_file(&screen).unwrap();
+        let graph = builder.build();
+
+        let mut called: Vec<&str> = graph
+            .find_by_name("toSlug")
+            .into_iter()
+            .filter(|d| !graph.get_references_to(&d.id).is_empty())
+            .filter_map(|d| d.receiver_type.as_deref())
+            .collect();
+        called.sort_unstable();
+        assert_eq!(called, ["String", "Uri"]);
+        assert_eq!(graph.find_extensions("Int").len(), 1);
+    }
+
+    #[test]
+    fn test_references_through_type_aliases() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Aliases.kt");
+        std::fs::write(
+            &path,
+            r#"package com.app
+
+class UserRepository
+class User
+class Result
+
+typealias Repo = UserRepository
+typealias Users = Map<String, User>
+typealias Callback = (Result) -> Unit
+
+class Screen(private val onDone: Callback) {
+    private val repo = Repo()
+}
+"#,
+        )
+        .unwrap();
+
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(path, FileType::Kotlin))
+            .unwrap();
+        let graph = builder.build();
+
+        let callers = |name: &str| -> Vec<String> {
+            let decl = &graph.find_by_name(name)[0];
+            graph
+                .get_references_to(&decl.id)
+                .into_iter()
+                .map(|(from, _)| from.name.to_string())
+                .collect()
+        };
+        // `Repo()` constructs the aliased class directly
+        assert!(callers("UserRepository").contains(&"repo".to_string()));
+        assert!(callers("User").contains(&"Users".to_string()));
+        assert!(callers("Result").contains(&"Callback".to_string()));
+        assert!(callers("Users").is_empty());
+
+        let targets = graph.type_alias_targets();
+        let callback = &graph.find_by_name("Callback")[0];
+        assert!(!targets.contains_key(&callback.id));
+    }
+
+    #[test]
+    fn test_simple_names_resolve_through_package_and_imports() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, FileType::Kotlin)
+        };
+        let files = [
+            write("DataResult.kt", "package com.app.data\n\nclass Result\n"),
+            write("NetResult.kt", "package com.app.net\n\nclass Result\n"),
+            write(
+                "Client.kt",
+                "package com.app.net\n\nclass Client {\n    fun get() = Result()\n}\n",
+            ),
+            write(
+                "Screen.kt",
+                r#"package com.app.ui
+
+import com.app.data.*
+
+class Screen {
+    fun show(result: Result) {}
+}
+"#,
+            ),
+            write(
+                "Worker.kt",
+                r#"package com.app.ui
+
+import kotlin.Result
+
+class Worker {
+    fun run(): Result<Unit> = Result.success(Unit)
+}
+"#,
+            ),
+        ];
+
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+
+        let users = |fqn: &str| -> Vec<String> {
+            let decl = graph.find_by_fqn(fqn).unwrap();
+            let mut files: Vec<String> = graph
+                .get_references_to(&decl.id)
+                .into_iter()
+                .filter_map(|(from, _)| from.location.file.file_name())
+                .map(|name| name.to_string_lossy().into())
+                .collect();
+            files.sort();
+            files.dedup();
+            files
+        };
+        assert_eq!(users("com.app.net.Result"), ["Client.kt"]);
+        assert_eq!(users("com.app.data.Result"), ["Screen.kt"]);
+    }
+
+    #[test]
+    fn test_overloads_resolve_by_arity() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str, file_type: FileType| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, file_type)
+        };
+        let kotlin = write(
+            "Loader.kt",
+            r#"package com.app
+
+class Loader {
+    fun load() {}
+    fun load(id: Int) {}
+    fun load(id: Int, force: Boolean) {}
+
+    fun fetch(id: Int, cache: Boolean = true) {}
+    fun fetch(id: Int, cache: Boolean, retries: Int) {}
+
+    fun tag(vararg names: String) {}
+    fun tag(name: String, color: Int) {}
+
+    fun start() {
+        load(1)
+        fetch(id = 2)
+        tag("a", "b", "c")
+    }
+}
+"#,
+            FileType::Kotlin,
+        );
+        let java = write(
+            "Store.java",
+            r#"package com.app;
+
+class Store {
+    void save(String key) {}
+    void save(String key, String value) {}
+
+    void run() {
+        save("k", "v");
+    }
+}
+"#,
+            FileType::Java,
+        );
+
+        let mut builder = GraphBuilder::new();
+        builder.process_file(&kotlin).unwrap();
+        builder.process_file(&java).unwrap();
+        let graph = builder.build();
+
+        let called = |name: &str| -> Vec<usize> {
+            let mut arities: Vec<usize> = graph
+                .find_by_name(name)
+                .into_iter()
+                .filter(|d| graph.is_referenced(&d.id))
+                .map(|d| graph.get_children(&d.id).len())
+                .collect();
+            arities.sort_unstable();
+            arities
+        };
+        assert_eq!(called("load"), [1]);
+        assert_eq!(called("fetch"), [2]);
+        assert_eq!(called("tag"), [1]);
+        assert_eq!(called("save"), [2]);
+    }
+
+    #[test]
+    fn test_qualified_access_resolves_through_companions() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str, file_type: FileType| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, file_type)
+        };
+        let files = [
+            write(
+                "Models.kt",
+                r#"package com.app.model
+
+class User {
+    companion object {
+        const val TABLE = "users"
+        fun create(): User = User()
+    }
+}
+
+class Order {
+    companion object Factory {
+        const val TABLE = "orders"
+        fun create(): Order = Order()
+    }
+}
+
+object Registry {
+    fun create() {}
+}
+"#,
+                FileType::Kotlin,
+            ),
+            write(
+                "Screen.kt",
+                r#"package com.app.ui
+
+import com.app.model.User
+import com.app.model.Order.Factory.TABLE
+
+fun show() {
+    println(User.TABLE)
+    println(TABLE)
+    User.create()
+}
+"#,
+                FileType::Kotlin,
+            ),
+            write(
+                "Sync.java",
+                r#"package com.app.sync;
+
+class Sync {
+    void run() {
+        Order.Factory.create();
+        Registry.INSTANCE.create();
+    }
+}
+"#,
+                FileType::Java,
+            ),
+        ];
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+
+        // Who refers to each `name`, by the declaration holding it
+        let referrers = |name: &str| -> Vec<(String, Vec<String>)> {
+            let mut found: Vec<(String, Vec<String>)> = graph
+                .find_by_name(name)
+                .into_iter()
+                .map(|decl| {
+                    let holder = graph
+                        .get_declaration(decl.parent.as_ref().unwrap())
+                        .unwrap();
+                    let mut from: Vec<String> = graph
+                        .get_references_to(&decl.id)
+                        .iter()
+                        .map(|(from, _)| from.name.to_string())
+                        .filter(|from| from != name)
+                        .collect();
+                    from.sort();
+                    from.dedup();
+                    (holder.name.to_string(), from)
+                })
+                .collect();
+            found.sort();
+            found
+        };
+        assert_eq!(
+            referrers("TABLE"),
+            [
+                ("Companion".to_string(), vec!["show".to_string()]),
+                ("Factory".to_string(), vec!["show".to_string()]),
+            ]
+        );
+        assert_eq!(
+            referrers("create"),
+            [
+                ("Companion".to_string(), vec!["show".to_string()]),
+                ("Factory".to_string(), vec!["run".to_string()]),
+                ("Registry".to_string(), vec!["run".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generated_sources_only_add_references() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, "settings.gradle.kts", r#"include(":app")"#);
+        write(
+            root,
+            "app/build.gradle.kts",
+            "plugins { id(\"com.android.application\") }",
+        );
+        write(
+            root,
+            "app/src/main/java/com/app/UserRepository.kt",
+            "package com.app\n\nclass UserRepository @Inject constructor()\n",
+        );
+        write(
+            root,
+            "app/build/generated/ksp/debug/kotlin/com/app/UserRepository_Factory.kt",
+            r#"package com.app
+
+class UserRepository_Factory {
+    fun get(): UserRepository = UserRepository()
+}
+"#,
+        );
+
+        let mut config = crate::config::Config::default();
+        let finder = crate::discovery::FileFinder::new(&config);
+        assert_eq!(finder.find_files(root).unwrap().len(), 1);
+
+        config.generated.enabled = true;
+        let files = crate::discovery::FileFinder::new(&config)
+            .find_files(root)
+            .unwrap();
+        assert_eq!(files.iter().filter(|f| f.generated).count(), 1);
+
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+
+        assert!(graph.find_by_name("UserRepository_Factory").is_empty());
+        let repository = &graph.find_by_name("UserRepository")[0];
+        let callers = graph.get_references_to(&repository.id);
+        assert!(callers.iter().any(|(from, _)| from.is_generated()));
+    }
+
+    #[test]
+    fn test_parameter_signatures_belong_to_their_function() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str, file_type: FileType| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, file_type)
+        };
+        let screen = write(
+            "Screen.kt",
+            r#"package com.app
+
+annotation class Param
+
+class Widget
+
+class Screen {
+    fun show(@Param widget: Widget, label: Label) = Unit
+}
+"#,
+            FileType::Kotlin,
+        );
+        let label = write(
+            "Label.java",
+            r#"package com.app;
+
+@interface Nullable {}
+
+public class Label {
+    public Label copy(@Nullable Label other) { return this; }
+}
+"#,
+            FileType::Java,
+        );
+
+        let mut builder = GraphBuilder::new();
+        builder.process_file(&screen).unwrap();
+        builder.process_file(&label).unwrap();
+        let graph = builder.build();
+
+        let uses = |name: &str| -> Vec<(String, ReferenceKind)> {
+            let decl = graph.find_by_name(name)[0];
+            let mut uses: Vec<(String, ReferenceKind)> = graph
+                .get_references_to(&decl.id)
+                .into_iter()
+                .map(|(from, r)| (from.name.to_string(), r.kind))
+                .collect();
+            uses.sort();
+            uses
+        };
+        let used_by = |from: &str, kind: ReferenceKind| vec![(from.to_string(), kind)];
+        assert_eq!(uses("Param"), used_by("show", ReferenceKind::AnnotationUse));
+        assert_eq!(
+            uses("Widget"),
+            used_by("show", ReferenceKind::TypeReference)
+        );
+        assert_eq!(
+            uses("Nullable"),
+            used_by("copy", ReferenceKind::AnnotationUse)
+        );
+        assert_eq!(
+            uses("Label"),
+            // Return and parameter type of `copy`
+            [
+                ("copy".to_string(), ReferenceKind::TypeReference),
+                ("copy".to_string(), ReferenceKind::TypeReference),
+                ("show".to_string(), ReferenceKind::TypeReference),
+            ]
+        );
+    }
 }
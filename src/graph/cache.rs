@@ -0,0 +1,281 @@
+// Persistent graph cache
+//
+// Parsing is most of the cost of building the graph. The cache keeps what
+// the builders derive from each Kotlin and Java file (its declarations,
+// unresolved references and scope) keyed by path and content hash, so a
+// later build only parses files whose contents changed and re-runs
+// reference resolution, which depends on every file, over the result. It is
+// saved between runs and is invalidated as a whole by a new release, whose
+// parsers may derive different declarations, and by a change to the
+// configuration that decides what a file's declarations are marked with
+// (generated sources, source sets).
+//
+// Hashes are saved, so they use xxHash rather than `DefaultHasher`, whose
+// output may change between Rust releases.
+
+use super::builder::UnresolvedRef;
+use super::{Declaration, FileScope};
+use crate::config::Config;
+use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::debug;
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Version of the cache file format
+const GRAPH_CACHE_VERSION: u32 = 4;
+
+/// What the graph builders derived from the source files of earlier builds
+///
+/// Safe to share between parser threads; watch mode keeps one cache across
+/// runs.
+#[derive(Default)]
+pub struct GraphCache {
+    /// Fingerprint of the configuration the files were built with
+    config: u64,
+    files: Mutex<HashMap<PathBuf, CachedFile>>,
+}
+
+/// Declarations and references derived from one source file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct CachedFile {
+    content_hash: u64,
+    /// Whether the file was built as generated code, which only contributes
+    /// its references
+    generated: bool,
+    pub(super) declarations: Vec<Declaration>,
+    pub(super) unresolved_refs: Vec<UnresolvedRef>,
+    pub(super) scope: FileScope,
+}
+
+/// On-disk form of the cache
+#[derive(Serialize, Deserialize)]
+struct CacheContents {
+    version: u32,
+    release: String,
+    config: u64,
+    files: HashMap<PathBuf, CachedFile>,
+}
+
+impl GraphCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empty cache for files discovered with `config`
+    pub fn for_config(config: &Config) -> Self {
+        Self {
+            config: config_fingerprint(config),
+            ..Self::default()
+        }
+    }
+
+    /// Load a cache saved by `save`; a missing, unreadable or outdated cache
+    /// file, or one built with a different configuration, gives an empty
+    /// cache
+    pub fn load(path: &Path, config: &Config) -> Self {
+        let fingerprint = config_fingerprint(config);
+        let contents = std::fs::File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader::<_, CacheContents>(BufReader::new(file)).ok())
+            .filter(|contents| {
+                contents.version == GRAPH_CACHE_VERSION
+                    && contents.release == env!("CARGO_PKG_VERSION")
+                    && contents.config == fingerprint
+            });
+        match contents {
+            Some(contents) => {
+                debug!(
+                    "Loaded graph cache of {} files from {}",
+                    contents.files.len(),
+                    path.display()
+                );
+                Self {
+                    config: fingerprint,
+                    files: Mutex::new(contents.files),
+                }
+            }
+            None => Self::for_config(config),
+        }
+    }
+
+    /// Write the cache to `path`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let files = self.files.lock().unwrap();
+        let contents = CacheContents {
+            version: GRAPH_CACHE_VERSION,
+            release: env!("CARGO_PKG_VERSION").to_string(),
+            config: self.config,
+            files: files.clone(),
+        };
+        let file = std::fs::File::create(path).into_diagnostic()?;
+        serde_json::to_writer(BufWriter::new(file), &contents).into_diagnostic()
+    }
+
+    /// Keep only the files `keep` accepts, dropping deleted files
+    pub fn retain(&self, mut keep: impl FnMut(&Path) -> bool) {
+        self.files.lock().unwrap().retain(|path, _| keep(path));
+    }
+
+    /// Number of cached files
+    pub fn len(&self) -> usize {
+        self.files.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// What an earlier build derived from `path`, if its contents are unchanged
+    pub(super) fn get(&self, path: &Path, contents: &str, generated: bool) -> Option<CachedFile> {
+        let hash = content_hash(contents);
+        self.files
+            .lock()
+            .unwrap()
+            .get(path)
+            .filter(|cached| cached.content_hash == hash && cached.generated == generated)
+            .cloned()
+    }
+
+    /// Remember what a build derived from `path`
+    pub(super) fn insert(
+        &self,
+        path: &Path,
+        contents: &str,
+        generated: bool,
+        declarations: &[Declaration],
+        unresolved_refs: &[UnresolvedRef],
+        scope: &FileScope,
+    ) {
+        let cached = CachedFile {
+            content_hash: content_hash(contents),
+            generated,
+            declarations: declarations.to_vec(),
+            unresolved_refs: unresolved_refs.to_vec(),
+            scope: scope.clone(),
+        };
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), cached);
+    }
+}
+
+fn content_hash(contents: &str) -> u64 {
+    xxh3_64(contents.as_bytes())
+}
+
+/// Hash of the settings that change what the builders derive from a file
+fn config_fingerprint(config: &Config) -> u64 {
+    let settings = serde_json::to_vec(&(&config.generated, &config.source_sets))
+        .expect("config serializes to JSON");
+    xxh3_64(&settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::{Graph, GraphBuilder, ParallelGraphBuilder};
+    use std::sync::Arc;
+
+    fn sources(root: &Path) -> Vec<SourceFile> {
+        ["Screen.kt", "Repository.kt"]
+            .iter()
+            .map(|name| SourceFile::new(root.join(name), FileType::Kotlin))
+            .collect()
+    }
+
+    fn build_sequential(files: &[SourceFile], cache: &Arc<GraphCache>) -> Graph {
+        let mut builder = GraphBuilder::new().with_graph_cache(cache.clone());
+        for file in files {
+            builder.process_file(file).unwrap();
+        }
+        builder.build()
+    }
+
+    fn calls(graph: &Graph, name: &str) -> Vec<String> {
+        let decl = graph.find_by_name(name)[0];
+        let mut called: Vec<String> = graph
+            .get_references_from(&decl.id)
+            .iter()
+            .map(|(to, _)| to.name.to_string())
+            .collect();
+        called.sort();
+        called
+    }
+
+    #[test]
+    fn test_graph_cache() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str| {
+            std::fs::write(temp.path().join(name), contents).unwrap();
+        };
+        write(
+            "Screen.kt",
+            "package com.app\n\nclass Screen {\n    fun show() = Repository().load()\n}\n",
+        );
+        write(
+            "Repository.kt",
+            "package com.app\n\nclass Repository {\n    fun load() = Unit\n\n    fun clear() = Unit\n}\n",
+        );
+        let files = sources(temp.path());
+        let config = Config::default();
+
+        let cache = Arc::new(GraphCache::for_config(&config));
+        let graph = ParallelGraphBuilder::new()
+            .with_graph_cache(cache.clone())
+            .build_from_files(&files)
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(calls(&graph, "show"), ["Repository", "load"]);
+
+        let path = temp.path().join("cache.json");
+        cache.save(&path).unwrap();
+        let cache = Arc::new(GraphCache::load(&path, &config));
+        assert_eq!(cache.len(), 2);
+        let screen = std::fs::read_to_string(&files[0].path).unwrap();
+        assert!(cache.get(&files[0].path, &screen, false).is_some());
+        assert!(cache.get(&files[0].path, &screen, true).is_none());
+
+        // Only the edited file is parsed again, and its new call resolves
+        // to the cached declarations of the other
+        write(
+            "Screen.kt",
+            "package com.app\n\nclass Screen {\n    fun show() = Repository().clear()\n}\n",
+        );
+        assert!(cache.get(&files[0].path, &screen, false).is_some());
+        let graph = ParallelGraphBuilder::new()
+            .with_graph_cache(cache.clone())
+            .build_from_files(&files)
+            .unwrap();
+        assert_eq!(calls(&graph, "show"), ["Repository", "clear"]);
+        let screen = std::fs::read_to_string(&files[0].path).unwrap();
+        assert!(cache.get(&files[0].path, &screen, false).is_some());
+
+        // The sequential builder shares the cache
+        let sequential = build_sequential(&files, &cache);
+        assert_eq!(sequential.declaration_count(), graph.declaration_count());
+        assert_eq!(sequential.reference_count(), graph.reference_count());
+
+        // Caches built with other source set or generated code settings are
+        // not loaded
+        cache.save(&path).unwrap();
+        let mut other = config.clone();
+        other.source_sets.test.push("**/integration/**".to_string());
+        assert!(GraphCache::load(&path, &other).is_empty());
+        assert_eq!(GraphCache::load(&path, &config).len(), 2);
+
+        // Caches of other releases or formats are not loaded
+        std::fs::write(
+            &path,
+            "{\"version\": 0, \"release\": \"0.0.0\", \"config\": 0, \"files\": {}}",
+        )
+        .unwrap();
+        assert!(GraphCache::load(&path, &config).is_empty());
+        assert!(GraphCache::load(&temp.path().join("missing.json"), &config).is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+// Strongly connected components
+//
+// Code that references itself in a cycle forms a strongly connected
+// component: reaching one declaration of it reaches all of them. Collapsing
+// each component to a single node gives a DAG, the condensation, on which
+// reachability visits every component once however many cycles run through
+// it. The condensation is computed once per graph (Tarjan's algorithm) and
+// shared by reachability and cycle detection until the graph changes.
+
+use super::Graph;
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use std::collections::HashSet;
+
+/// The graph's strongly connected components and the references between them
+#[derive(Debug, Clone)]
+pub struct Condensation {
+    /// Nodes of each component, in reverse topological order: a component
+    /// only references components before it
+    components: Vec<Vec<NodeIndex>>,
+    /// Component of each node, by node index
+    component_of: Vec<usize>,
+    /// Components each component references, without duplicates
+    successors: Vec<Vec<usize>>,
+    /// Number of other components referencing each component
+    in_degree: Vec<usize>,
+}
+
+impl Condensation {
+    /// Condense a graph
+    pub fn new(graph: &Graph) -> Self {
+        let inner = graph.inner();
+        let components = tarjan_scc(inner);
+
+        let mut component_of = vec![0; inner.node_count()];
+        for (i, component) in components.iter().enumerate() {
+            for node in component {
+                component_of[node.index()] = i;
+            }
+        }
+
+        let mut successors = vec![Vec::new(); components.len()];
+        let mut in_degree = vec![0; components.len()];
+        for (i, component) in components.iter().enumerate() {
+            let targets: HashSet<usize> = component
+                .iter()
+                .flat_map(|&node| inner.edges(node))
+                .map(|edge| component_of[edge.target().index()])
+                .filter(|&target| target != i)
+                .collect();
+            let mut targets: Vec<usize> = targets.into_iter().collect();
+            targets.sort_unstable();
+            for &target in &targets {
+                in_degree[target] += 1;
+            }
+            successors[i] = targets;
+        }
+
+        Self {
+            components,
+            component_of,
+            successors,
+            in_degree,
+        }
+    }
+
+    /// Number of components
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Nodes of each component, in reverse topological order
+    pub fn components(&self) -> impl Iterator<Item = &[NodeIndex]> {
+        self.components.iter().map(Vec::as_slice)
+    }
+
+    /// Nodes of a component
+    pub fn component(&self, component: usize) -> &[NodeIndex] {
+        &self.components[component]
+    }
+
+    /// Component a node belongs to
+    pub fn component_of(&self, node: NodeIndex) -> Option<usize> {
+        self.component_of.get(node.index()).copied()
+    }
+
+    /// Components a component references
+    pub fn successors(&self, component: usize) -> &[usize] {
+        &self.successors[component]
+    }
+
+    /// Whether a declaration outside a component references it
+    pub fn is_referenced_externally(&self, component: usize) -> bool {
+        self.in_degree[component] > 0
+    }
+
+    /// Nodes reachable from `starts` by following references, the starts
+    /// included
+    ///
+    /// Each component is visited once, so the cost is linear in the size of
+    /// the condensation however many starts there are.
+    pub fn reachable_from(&self, starts: impl IntoIterator<Item = NodeIndex>) -> Vec<NodeIndex> {
+        let mut visited = vec![false; self.components.len()];
+        let mut stack: Vec<usize> = starts
+            .into_iter()
+            .filter_map(|node| self.component_of(node))
+            .collect();
+        let mut reachable = Vec::new();
+
+        while let Some(component) = stack.pop() {
+            if std::mem::replace(&mut visited[component], true) {
+                continue;
+            }
+            reachable.extend_from_slice(&self.components[component]);
+            stack.extend(
+                self.successors[component]
+                    .iter()
+                    .filter(|&&next| !visited[next]),
+            );
+        }
+        reachable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        Declaration, DeclarationId, DeclarationKind, Language, Location, Reference, ReferenceKind,
+    };
+    use std::path::PathBuf;
+
+    fn add(graph: &mut Graph, name: &str, start: usize) -> DeclarationId {
+        let path = PathBuf::from("Test.kt");
+        graph.add_declaration(Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 10),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(path, 1, 1, start, start + 10),
+            Language::Kotlin,
+        ))
+    }
+
+    fn call(graph: &mut Graph, from: &DeclarationId, to: &DeclarationId) {
+        let location = Location::new(PathBuf::from("Test.kt"), 1, 1, 0, 1);
+        graph.add_reference(
+            from,
+            to,
+            Reference::new(ReferenceKind::Call, location, String::new()),
+        );
+    }
+
+    #[test]
+    fn test_condensation() {
+        // main -> a <-> b -> c, and d <-> e on their own
+        let mut graph = Graph::new();
+        let mut start = 0;
+        let [main, a, b, c, d, e] = ["main", "a", "b", "c", "d", "e"].map(|name| {
+            start += 20;
+            add(&mut graph, name, start)
+        });
+        call(&mut graph, &main, &a);
+        call(&mut graph, &a, &b);
+        call(&mut graph, &b, &a);
+        call(&mut graph, &b, &c);
+        call(&mut graph, &d, &e);
+        call(&mut graph, &e, &d);
+
+        let condensation = graph.condensation();
+        let component = |id: &DeclarationId| {
+            condensation
+                .component_of(graph.node_index(id).unwrap())
+                .unwrap()
+        };
+        assert_eq!(component(&a), component(&b));
+        assert_ne!(component(&a), component(&c));
+        assert_eq!(condensation.component(component(&d)).len(), 2);
+        assert!(condensation.is_referenced_externally(component(&a)));
+        assert!(!condensation.is_referenced_externally(component(&d)));
+        assert_eq!(condensation.successors(component(&a)), [component(&c)]);
+
+        let reachable: HashSet<NodeIndex> = condensation
+            .reachable_from([graph.node_index(&main).unwrap()])
+            .into_iter()
+            .collect();
+        let names: HashSet<&str> = reachable
+            .iter()
+            .filter_map(|&node| graph.get_declaration(&graph.inner()[node]))
+            .map(|decl| decl.name.as_str())
+            .collect();
+        assert_eq!(names, HashSet::from(["main", "a", "b", "c"]));
+
+        // The condensation is kept until the graph changes
+        assert!(std::ptr::eq(condensation, graph.condensation()));
+        call(&mut graph, &c, &d);
+        let reachable = graph
+            .condensation()
+            .reachable_from([graph.node_index(&main).unwrap()]);
+        assert_eq!(reachable.len(), 6);
+    }
+}
@@ -1,14 +1,16 @@
 // Declaration types - some fields and methods reserved for future use
 #![allow(dead_code)]
 
+use super::intern::{FilePath, Symbol};
+use crate::discovery::SourceSetKind;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::Path;
 
 /// Unique identifier for a declaration
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DeclarationId {
     /// File path
-    pub file: PathBuf,
+    pub file: FilePath,
     /// Starting byte offset in file
     pub start: usize,
     /// Ending byte offset in file
@@ -16,8 +18,12 @@ pub struct DeclarationId {
 }
 
 impl DeclarationId {
-    pub fn new(file: PathBuf, start: usize, end: usize) -> Self {
-        Self { file, start, end }
+    pub fn new(file: impl Into<FilePath>, start: usize, end: usize) -> Self {
+        Self {
+            file: file.into(),
+            start,
+            end,
+        }
     }
 }
 
@@ -29,6 +35,7 @@ impl std::fmt::Display for DeclarationId {
 
 /// Kind of declaration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum DeclarationKind {
     // Classes and types
     Class,
@@ -140,13 +147,23 @@ impl Visibility {
             Visibility::PackagePrivate // Java default
         }
     }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Visibility::Public => "public",
+            Visibility::Private => "private",
+            Visibility::Protected => "protected",
+            Visibility::Internal => "internal",
+            Visibility::PackagePrivate => "package-private",
+        }
+    }
 }
 
 /// Location in source code
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Location {
     /// File path
-    pub file: PathBuf,
+    pub file: FilePath,
     /// Line number (1-indexed)
     pub line: usize,
     /// Column number (1-indexed)
@@ -159,14 +176,14 @@ pub struct Location {
 
 impl Location {
     pub fn new(
-        file: PathBuf,
+        file: impl Into<FilePath>,
         line: usize,
         column: usize,
         start_byte: usize,
         end_byte: usize,
     ) -> Self {
         Self {
-            file,
+            file: file.into(),
             line,
             column,
             start_byte,
@@ -181,17 +198,33 @@ impl std::fmt::Display for Location {
     }
 }
 
+/// Size and complexity of a declaration's source, measured when parsing
+///
+/// Zero for declarations not parsed from Kotlin or Java source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metrics {
+    /// Lines the declaration spans, from its first line to its last
+    pub lines: usize,
+    /// Lines of code: the lines left after blank and comment-only ones
+    pub loc: usize,
+    /// Cyclomatic complexity: 1 plus the branches (`if`, `when` and
+    /// `switch` cases, loops, `catch`, `&&`, `||`) in the declaration,
+    /// members included
+    pub complexity: usize,
+}
+
 /// A declaration in the source code
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Declaration {
     /// Unique identifier
     pub id: DeclarationId,
 
     /// Simple name (e.g., "MainActivity")
-    pub name: String,
+    pub name: Symbol,
 
     /// Fully qualified name (e.g., "com.example.app.MainActivity")
-    pub fully_qualified_name: Option<String>,
+    pub fully_qualified_name: Option<Symbol>,
 
     /// Kind of declaration
     pub kind: DeclarationKind,
@@ -223,30 +256,91 @@ pub struct Declaration {
     /// Language (Kotlin or Java)
     pub language: Language,
 
-    /// Type name for properties/fields (e.g., "String", "RecyclerView", "MutableStateFlow<User>")
+    /// Type name for properties/fields/parameters (e.g., "String", "RecyclerView",
+    /// "MutableStateFlow<User>"), or the return type of functions and methods
     pub type_name: Option<String>,
+
+    /// Receiver type of an extension function or property, as a simple
+    /// name (e.g., "String" for `fun String?.toSlug()`)
+    #[serde(default)]
+    pub receiver_type: Option<String>,
+
+    /// Interfaces a Kotlin class implements by delegation
+    /// (e.g., "Repository" for `class Cache(d: Repository) : Repository by d`)
+    #[serde(default)]
+    pub delegated_types: Vec<String>,
+
+    /// Names the declaration goes by outside the source, from renaming
+    /// annotations (e.g., "legacyApi" for `@JvmName("legacyApi")`,
+    /// "json_key" for `@SerializedName("json_key")`)
+    #[serde(default)]
+    pub alternate_names: Vec<String>,
+
+    /// Source set the declaring file belongs to
+    #[serde(default)]
+    pub source_set: SourceSetKind,
+
+    /// Gradle module the declaring file belongs to (e.g., ":core:ui")
+    #[serde(default)]
+    pub module: Option<Symbol>,
+
+    /// Lines of code and cyclomatic complexity
+    #[serde(default)]
+    pub metrics: Metrics,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Language {
     Kotlin,
     Java,
+    /// Android resource XML (layouts referencing code)
+    Xml,
+    /// Gradle version catalog (`libs.versions.toml`)
+    Toml,
+    /// Groovy Gradle build script (`build.gradle`)
+    Gradle,
+    /// ProGuard / R8 rule file (`proguard-rules.pro`)
+    Proguard,
+    /// Android interface definition (`.aidl`)
+    Aidl,
+    /// Protocol Buffers definition (`.proto`)
+    Proto,
+}
+
+impl Language {
+    /// Language of a file, from its extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "kt" | "kts" => Some(Language::Kotlin),
+            "java" => Some(Language::Java),
+            "xml" => Some(Language::Xml),
+            "toml" => Some(Language::Toml),
+            "gradle" => Some(Language::Gradle),
+            "pro" => Some(Language::Proguard),
+            "aidl" => Some(Language::Aidl),
+            "proto" => Some(Language::Proto),
+            _ => None,
+        }
+    }
 }
 
 impl Declaration {
     pub fn new(
         id: DeclarationId,
-        name: String,
+        name: impl Into<Symbol>,
         kind: DeclarationKind,
         location: Location,
         language: Language,
     ) -> Self {
         Self {
             id,
-            name,
+            name: name.into(),
             fully_qualified_name: None,
             kind,
             visibility: Visibility::default(),
+            source_set: SourceSetKind::from_path(&location.file),
+            module: None,
+            metrics: Metrics::default(),
             location,
             parent: None,
             is_static: false,
@@ -256,9 +350,120 @@ impl Declaration {
             modifiers: Vec::new(),
             language,
             type_name: None,
+            receiver_type: None,
+            delegated_types: Vec::new(),
+            alternate_names: Vec::new(),
         }
     }
 
+    /// Declaration standing for a line of a file the graph doesn't cover
+    /// (a manifest entry, a build script, ...), to report findings on
+    pub fn at_line(
+        path: &Path,
+        line: usize,
+        name: impl Into<Symbol>,
+        kind: DeclarationKind,
+        language: Language,
+    ) -> Self {
+        Self::new(
+            DeclarationId::new(path.to_path_buf(), line, line),
+            name,
+            kind,
+            Location::new(path.to_path_buf(), line, 1, 0, 0),
+            language,
+        )
+    }
+
+    /// References-only node standing for a whole generated source file
+    pub fn generated_file(path: &Path, len: usize, language: Language) -> Self {
+        Self::file_node(path, len, language, "generated")
+    }
+
+    /// References-only node standing for a layout XML file
+    pub fn layout_file(path: &Path, len: usize) -> Self {
+        Self::file_node(path, len, Language::Xml, "layout")
+    }
+
+    fn file_node(path: &Path, len: usize, language: Language, origin: &str) -> Self {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut decl = Self::new(
+            DeclarationId::new(path.to_path_buf(), 0, len),
+            name,
+            DeclarationKind::File,
+            Location::new(path.to_path_buf(), 1, 1, 0, len),
+            language,
+        );
+        decl.modifiers.push(origin.to_string());
+        decl
+    }
+
+    /// Number of bytes the declaration spans
+    pub fn byte_span(&self) -> usize {
+        self.location
+            .end_byte
+            .saturating_sub(self.location.start_byte)
+    }
+
+    /// Check if this node stands for a generated source file
+    pub fn is_generated(&self) -> bool {
+        self.kind == DeclarationKind::File && self.modifiers.iter().any(|m| m == "generated")
+    }
+
+    /// Check if this is a Kotlin Multiplatform `expect` declaration
+    pub fn is_expect(&self) -> bool {
+        self.modifiers.iter().any(|m| m == "expect")
+    }
+
+    /// Check if this is a Kotlin Multiplatform `actual` declaration
+    pub fn is_actual(&self) -> bool {
+        self.modifiers.iter().any(|m| m == "actual")
+    }
+
+    /// Check if the declaration carries an annotation, compared by simple
+    /// name (`Keep` matches `@Keep` and `@androidx.annotation.Keep`)
+    pub fn has_annotation(&self, name: &str) -> bool {
+        let name = annotation_name(name);
+        self.annotation_names().any(|a| a == name)
+    }
+
+    /// Simple names of the declaration's annotations, without arguments or
+    /// use-site targets (`@get:org.junit.Rule` -> `Rule`)
+    pub fn annotation_names(&self) -> impl Iterator<Item = &str> {
+        self.annotations.iter().map(|a| annotation_name(a))
+    }
+
+    /// Check if this is a method implemented in native code
+    /// (Kotlin `external`, Java `native`)
+    pub fn is_native(&self) -> bool {
+        self.kind.is_callable() && self.modifiers.iter().any(|m| m == "external" || m == "native")
+    }
+
+    /// Check if this is a Kotlin `const val`
+    pub fn is_const(&self) -> bool {
+        self.kind == DeclarationKind::Property
+            && self.language == Language::Kotlin
+            && self.modifiers.iter().any(|m| m == "const")
+    }
+
+    /// Check if this declaration lives in a test source set
+    /// (`src/test`, `src/androidTest`, `src/testFixtures`, `src/testDebug`, ...)
+    pub fn is_test_source(&self) -> bool {
+        self.source_set.is_test()
+    }
+
+    /// Check if this is a Kotlin companion object
+    pub fn is_companion(&self) -> bool {
+        self.kind == DeclarationKind::Object && self.modifiers.iter().any(|m| m == "companion")
+    }
+
+    /// Check if this is a Kotlin extension function or property
+    pub fn is_extension(&self) -> bool {
+        self.language == Language::Kotlin && self.modifiers.iter().any(|m| m == "extension")
+    }
+
     /// Check if this declaration is an Android entry point
     pub fn is_android_entry_point(&self) -> bool {
         // Check super types for Android components
@@ -294,7 +499,6 @@ impl Declaration {
 
         // Check annotations
         let entry_annotations = [
-            "Composable",
             "Test",
             "Before",
             "After",
@@ -302,8 +506,6 @@ impl Declaration {
             "AfterEach",
             "JvmStatic",
             "BindingAdapter",
-            "Provides",
-            "Binds",
             "Inject",
             "HiltAndroidApp",
             "AndroidEntryPoint",
@@ -328,6 +530,14 @@ impl Declaration {
 
     /// Check if this declaration should be retained based on patterns
     pub fn matches_pattern(&self, pattern: &str) -> bool {
+        // Qualified patterns (`com.example.model.**`) use ProGuard class name syntax
+        if pattern.contains('.') {
+            return self
+                .fully_qualified_name
+                .as_deref()
+                .is_some_and(|fqn| crate::proguard::class_name_matches(pattern, fqn));
+        }
+
         // Simple wildcard matching
         if let Some(suffix) = pattern.strip_prefix('*') {
             self.name.ends_with(suffix)
@@ -354,9 +564,19 @@ impl Declaration {
     }
 }
 
+/// `@get:androidx.compose.ui.tooling.preview.Preview(showBackground = true)`
+/// -> `Preview`
+fn annotation_name(annotation: &str) -> &str {
+    let name = annotation.trim().trim_start_matches('@');
+    let name = &name[..name.find('(').unwrap_or(name.len())];
+    let name = name.rsplit(':').next().unwrap_or(name);
+    name.rsplit('.').next().unwrap_or(name).trim()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
 
     #[test]
     fn test_declaration_kind_display() {
@@ -419,4 +639,27 @@ mod tests {
         assert!(decl.matches_pattern("MainActivity"));
         assert!(!decl.matches_pattern("*Fragment"));
     }
+
+    #[test]
+    fn test_has_annotation() {
+        let mut decl = Declaration::new(
+            DeclarationId::new(PathBuf::from("test.kt"), 0, 100),
+            "rule".to_string(),
+            DeclarationKind::Property,
+            Location::new(PathBuf::from("test.kt"), 1, 1, 0, 100),
+            Language::Kotlin,
+        );
+        decl.annotations = vec![
+            "@get:org.junit.Rule".to_string(),
+            "@Preview(showBackground = true)".to_string(),
+        ];
+
+        assert_eq!(
+            decl.annotation_names().collect::<Vec<_>>(),
+            ["Rule", "Preview"]
+        );
+        assert!(decl.has_annotation("Rule"));
+        assert!(decl.has_annotation("@androidx.compose.ui.tooling.preview.Preview"));
+        assert!(!decl.has_annotation("Test"));
+    }
 }
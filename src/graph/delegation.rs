@@ -0,0 +1,155 @@
+// Kotlin class delegation
+//
+// `class CachingRepository(d: Repository) : Repository by d` implements every
+// member of `Repository` it does not override by forwarding to `d`. Callers
+// name the delegating class, never the forwarded members, so each delegating
+// class is linked to the interface members it forwards and, when the delegate
+// is a class or object (`by LoggingTracker()`), to that class's
+// implementations of them.
+
+use super::{DeclarationId, DeclarationKind, Graph, Reference, ReferenceKind};
+use std::collections::HashSet;
+
+impl Graph {
+    /// Add delegation references from classes implementing interfaces
+    /// `by` a delegate to the members they forward
+    pub fn link_class_delegation(&mut self) {
+        let mut links: Vec<(DeclarationId, DeclarationId, Reference)> = Vec::new();
+        for class in self.declarations.values() {
+            if class.delegated_types.is_empty() {
+                continue;
+            }
+            let overridden: HashSet<&str> = self
+                .get_children(&class.id)
+                .into_iter()
+                .filter_map(|id| self.declarations.get(id))
+                .map(|member| member.name.as_str())
+                .collect();
+            // Classes and objects delegated to (`by LoggingTracker()`)
+            let delegates: Vec<&DeclarationId> = self
+                .get_references_from(&class.id)
+                .into_iter()
+                .filter(|(to, r)| r.kind == ReferenceKind::Delegation && to.kind.is_type())
+                .map(|(to, _)| &to.id)
+                .collect();
+
+            for interface in class
+                .delegated_types
+                .iter()
+                .filter_map(|name| self.find_interface(name))
+            {
+                let members = |id: &DeclarationId| {
+                    self.get_children(id)
+                        .into_iter()
+                        .filter_map(|id| self.declarations.get(id))
+                        .filter(|member| member.kind != DeclarationKind::Parameter)
+                };
+                let forwarded: HashSet<&str> = members(interface)
+                    .map(|member| member.name.as_str())
+                    .filter(|name| !overridden.contains(name))
+                    .collect();
+                let targets = members(interface)
+                    .chain(delegates.iter().flat_map(|d| members(d)))
+                    .filter(|member| forwarded.contains(member.name.as_str()));
+                for member in targets {
+                    let reference = Reference::new(
+                        ReferenceKind::Delegation,
+                        class.location.clone(),
+                        member.name.clone(),
+                    );
+                    links.push((class.id.clone(), member.id.clone(), reference));
+                }
+            }
+        }
+        // Stable order, so delegation references are added deterministically
+        links.sort_by(|a, b| {
+            (&a.0.file, a.0.start, &a.1.file, a.1.start)
+                .cmp(&(&b.0.file, b.0.start, &b.1.file, b.1.start))
+        });
+        for (from, to, reference) in links {
+            self.add_reference(&from, &to, reference);
+        }
+    }
+
+    /// The interface a delegation specifier names (`Repository<User>` -> `Repository`)
+    fn find_interface(&self, type_name: &str) -> Option<&DeclarationId> {
+        let name = type_name.split('<').next().unwrap_or(type_name).trim();
+        if let Some(decl) = self.find_by_fqn(name) {
+            return Some(&decl.id);
+        }
+        let simple = name.rsplit('.').next().unwrap_or(name);
+        self.find_by_name(simple)
+            .into_iter()
+            .find(|decl| decl.kind == DeclarationKind::Interface)
+            .map(|decl| &decl.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::{GraphBuilder, ReferenceKind};
+
+    #[test]
+    fn test_delegated_members_are_linked() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Repository.kt");
+        std::fs::write(
+            &path,
+            r#"package com.app
+
+interface Repository {
+    fun load(): String
+    fun save(value: String)
+}
+
+class CachingRepository(private val delegate: Repository) : Repository by delegate {
+    override fun save(value: String) {
+        delegate.save(value)
+    }
+}
+
+interface Tracker {
+    fun track(event: String)
+}
+
+class LoggingTracker : Tracker {
+    override fun track(event: String) = println(event)
+
+    fun flush() {}
+}
+
+object Analytics : Tracker by LoggingTracker()
+"#,
+        )
+        .unwrap();
+
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(path, FileType::Kotlin))
+            .unwrap();
+        let graph = builder.build();
+
+        let delegated_to = |class: &str| -> Vec<String> {
+            let class = &graph.find_by_name(class)[0];
+            assert!(!class.delegated_types.is_empty());
+            let mut names: Vec<String> = graph
+                .get_references_from(&class.id)
+                .into_iter()
+                .filter(|(to, r)| r.kind == ReferenceKind::Delegation && to.kind.is_member())
+                .map(|(to, _)| {
+                    let parent = graph.get_declaration(to.parent.as_ref().unwrap()).unwrap();
+                    format!("{}.{}", parent.name, to.name)
+                })
+                .collect();
+            names.sort();
+            names
+        };
+        // `save` is overridden, so only `load` is forwarded
+        assert_eq!(delegated_to("CachingRepository"), ["Repository.load"]);
+        assert_eq!(
+            delegated_to("Analytics"),
+            ["LoggingTracker.track", "Tracker.track"]
+        );
+    }
+}
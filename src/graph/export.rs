@@ -0,0 +1,325 @@
+// Graph export
+//
+// Renders the reference graph for visualization tools. An export covers the
+// declarations `ExportOptions` selects (all of them, the dead ones, or one
+// package) and the references between them, in a stable order so that
+// exports of the same project can be diffed.
+
+use super::{Declaration, DeclarationId, DeclarationKind, Graph, ReferenceKind};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+/// Which declarations a graph export includes and highlights
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Declarations reported as dead; they and their members are highlighted
+    pub dead: HashSet<DeclarationId>,
+    /// Leave out declarations that are not dead
+    pub dead_only: bool,
+    /// Only export declarations in this package or its subpackages
+    pub package: Option<String>,
+}
+
+impl ExportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Highlight these declarations, and their members, as dead
+    pub fn with_dead(mut self, dead: impl IntoIterator<Item = DeclarationId>) -> Self {
+        self.dead.extend(dead);
+        self
+    }
+
+    /// Only export dead declarations
+    pub fn with_dead_only(mut self, dead_only: bool) -> Self {
+        self.dead_only = dead_only;
+        self
+    }
+
+    /// Only export declarations in a package (`com.app.feed`) or its subpackages
+    pub fn with_package(mut self, package: Option<String>) -> Self {
+        self.package = package;
+        self
+    }
+}
+
+/// A declaration selected for export
+#[derive(Debug, Clone)]
+pub struct ExportNode<'g> {
+    pub declaration: &'g Declaration,
+    /// Whether the declaration, or one enclosing it, is dead
+    pub dead: bool,
+    /// Index of the declaring type's node, when that is exported too
+    pub parent: Option<usize>,
+}
+
+/// A reference between exported declarations, as indexes into the nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExportEdge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: ReferenceKind,
+}
+
+/// The declarations and references an export covers
+#[derive(Debug, Clone)]
+pub struct ExportSelection<'g> {
+    /// Declarations ordered by file and position
+    pub nodes: Vec<ExportNode<'g>>,
+    /// References ordered by source and target, one per pair and kind
+    pub edges: Vec<ExportEdge>,
+}
+
+impl Graph {
+    /// Select the declarations an export covers and the references between
+    /// them; parameters are left out
+    pub fn export_selection(&self, options: &ExportOptions) -> ExportSelection<'_> {
+        let mut nodes: Vec<ExportNode> = self
+            .declarations()
+            .filter(|decl| decl.kind != DeclarationKind::Parameter)
+            .filter(|decl| {
+                options
+                    .package
+                    .as_deref()
+                    .map_or(true, |prefix| self.in_package(decl, prefix))
+            })
+            .map(|declaration| ExportNode {
+                declaration,
+                dead: self.is_dead(declaration, &options.dead),
+                parent: None,
+            })
+            .filter(|node| node.dead || !options.dead_only)
+            .collect();
+        nodes.sort_by(|a, b| {
+            let (a, b) = (&a.declaration, &b.declaration);
+            (&a.location.file, a.location.start_byte, &a.name).cmp(&(
+                &b.location.file,
+                b.location.start_byte,
+                &b.name,
+            ))
+        });
+
+        let index: HashMap<&DeclarationId, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (&node.declaration.id, i))
+            .collect();
+        let mut edges = BTreeSet::new();
+        for (id, &from) in &index {
+            for (to, reference) in self.get_references_from(id) {
+                if let Some(&to) = index.get(&to.id) {
+                    edges.insert(ExportEdge {
+                        from,
+                        to,
+                        kind: reference.kind,
+                    });
+                }
+            }
+        }
+        for node in &mut nodes {
+            node.parent = node
+                .declaration
+                .parent
+                .as_ref()
+                .and_then(|parent| index.get(parent))
+                .copied();
+        }
+
+        ExportSelection {
+            nodes,
+            edges: edges.into_iter().collect(),
+        }
+    }
+
+    /// Render the selected declarations and the references between them as
+    /// a Graphviz DOT digraph (`dot -Tsvg graph.dot -o graph.svg`)
+    ///
+    /// Declarations are grouped in one cluster per file and dead ones are
+    /// filled red. Membership is drawn as dotted edges from a type to its
+    /// members, inheritance and overrides with hollow arrowheads, and type
+    /// references dashed. Parameters are left out.
+    pub fn to_dot(&self, options: &ExportOptions) -> String {
+        let ExportSelection { nodes, edges } = self.export_selection(options);
+
+        let mut dot = String::from("digraph references {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box, style=rounded, fontname=\"Helvetica\"];\n");
+        dot.push_str("    edge [color=\"#555555\"];\n");
+
+        let mut cluster = 0;
+        let mut i = 0;
+        while i < nodes.len() {
+            let file = &nodes[i].declaration.location.file;
+            let _ = writeln!(dot, "    subgraph cluster_{} {{", cluster);
+            let _ = writeln!(
+                dot,
+                "        label=\"{}\";",
+                escape(&file.to_string_lossy())
+            );
+            while i < nodes.len() && &nodes[i].declaration.location.file == file {
+                let node = &nodes[i];
+                let decl = node.declaration;
+                let label = format!("{}\n{}", decl.name, decl.kind.display_name());
+                let style = if node.dead {
+                    ", style=\"rounded,filled\", fillcolor=\"#f8d7da\", color=\"#c82333\""
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    dot,
+                    "        n{} [label=\"{}\"{}];",
+                    i,
+                    escape(&label),
+                    style
+                );
+                i += 1;
+            }
+            dot.push_str("    }\n");
+            cluster += 1;
+        }
+
+        for (i, node) in nodes.iter().enumerate() {
+            if let Some(parent) = node.parent {
+                let _ = writeln!(
+                    dot,
+                    "    n{} -> n{} [style=dotted, arrowhead=none];",
+                    parent, i
+                );
+            }
+        }
+        for edge in edges {
+            let style = match edge.kind {
+                ReferenceKind::Inheritance
+                | ReferenceKind::Override
+                | ReferenceKind::SealedSubtype => " [arrowhead=empty]",
+                ReferenceKind::Call
+                | ReferenceKind::Read
+                | ReferenceKind::Write
+                | ReferenceKind::Instantiation => "",
+                _ => " [style=dashed]",
+            };
+            let _ = writeln!(dot, "    n{} -> n{}{};", edge.from, edge.to, style);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Whether a declaration, or a declaration enclosing it, is dead
+    fn is_dead(&self, decl: &Declaration, dead: &HashSet<DeclarationId>) -> bool {
+        let mut current = Some(decl);
+        while let Some(decl) = current {
+            if dead.contains(&decl.id) {
+                return true;
+            }
+            current = decl.parent.as_ref().and_then(|p| self.get_declaration(p));
+        }
+        false
+    }
+
+    /// Whether a declaration's file is in a package or one of its subpackages
+    fn in_package(&self, decl: &Declaration, prefix: &str) -> bool {
+        self.file_scope(&decl.location.file)
+            .and_then(|scope| scope.package.as_deref())
+            .is_some_and(|package| {
+                package
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            })
+    }
+}
+
+/// Escape a string for a quoted DOT attribute
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    fn build(files: &[(&str, &str)]) -> (tempfile::TempDir, Graph) {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut builder = GraphBuilder::new();
+        for (name, contents) in files {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            builder
+                .process_file(&SourceFile::new(path, FileType::Kotlin))
+                .unwrap();
+        }
+        (temp, builder.build())
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let (_temp, graph) = build(&[
+            (
+                "Feed.kt",
+                r#"package com.app.feed
+
+class FeedScreen {
+    fun show() = FeedRepository().load()
+}
+
+class FeedRepository {
+    fun load() = Unit
+}
+
+class Legacy {
+    fun render(size: Int) = FeedRepository()
+}
+"#,
+            ),
+            (
+                "Settings.kt",
+                r#"package com.app.settings
+
+class SettingsScreen
+"#,
+            ),
+        ]);
+        let dead: Vec<DeclarationId> = graph
+            .find_by_name("Legacy")
+            .iter()
+            .map(|d| d.id.clone())
+            .collect();
+        assert_eq!(dead.len(), 1);
+
+        let dot = graph.to_dot(&ExportOptions::new().with_dead(dead.clone()));
+        assert!(dot.starts_with("digraph references {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches("subgraph cluster_").count(), 2);
+        assert!(dot.contains("n0 [label=\"FeedScreen\\nclass\"];"));
+        assert!(dot.contains("n1 [label=\"show\\nmethod\"];"));
+        assert!(dot.contains("n0 -> n1 [style=dotted, arrowhead=none];"));
+        assert!(dot.contains("\"render\\nmethod\", style=\"rounded,filled\""));
+        assert!(!dot.contains("size"));
+
+        // Only dead declarations, their members included
+        let dot = graph.to_dot(&ExportOptions::new().with_dead(dead).with_dead_only(true));
+        assert_eq!(dot.matches("[label=").count(), 2);
+        assert!(!dot.contains("FeedScreen"));
+
+        // Packages match whole segments
+        let options = ExportOptions::new().with_package(Some("com.app.settings".to_string()));
+        let dot = graph.to_dot(&options);
+        assert!(dot.contains("SettingsScreen"));
+        assert!(!dot.contains("FeedScreen"));
+        let options = ExportOptions::new().with_package(Some("com.app.set".to_string()));
+        assert!(!graph.to_dot(&options).contains("SettingsScreen"));
+
+        assert_eq!(escape("say \"hi\"\n"), "say \\\"hi\\\"\\n");
+
+        // Same graph, same export
+        assert_eq!(
+            graph.to_dot(&ExportOptions::new()),
+            graph.to_dot(&ExportOptions::new())
+        );
+    }
+}
@@ -0,0 +1,161 @@
+// Inheritance hierarchy
+//
+// Sealed subtypes, interface implementations, overrides and inheritance
+// depth all walk the type hierarchy. Resolving every type's super types
+// means resolving written names through packages and imports, so the
+// hierarchy is resolved once per graph, when the builders finish, and
+// shared by the analyzers and detectors until the graph changes.
+
+use super::{Declaration, DeclarationId, Graph, TypeResolver};
+use std::collections::HashMap;
+
+/// Direct super and subtypes of every project type
+#[derive(Debug, Clone, Default)]
+pub struct Hierarchy {
+    /// Project types each type extends or implements, in declaration order
+    supertypes: HashMap<DeclarationId, Vec<DeclarationId>>,
+    /// Project types extending or implementing each type, ordered by file
+    /// and position
+    subtypes: HashMap<DeclarationId, Vec<DeclarationId>>,
+}
+
+impl Hierarchy {
+    /// Resolve the super types of every type declared in `graph`
+    pub fn new(graph: &Graph) -> Self {
+        let types = TypeResolver::new(graph);
+        let mut declarations: Vec<&Declaration> =
+            graph.declarations().filter(|d| d.kind.is_type()).collect();
+        declarations.sort_by(|a, b| (&a.id.file, a.id.start).cmp(&(&b.id.file, b.id.start)));
+
+        let mut hierarchy = Self::default();
+        for decl in declarations {
+            let mut supertypes: Vec<DeclarationId> = Vec::new();
+            for super_type in types.super_types(decl) {
+                if !supertypes.contains(&super_type.id) {
+                    supertypes.push(super_type.id.clone());
+                }
+            }
+            for super_type in &supertypes {
+                hierarchy
+                    .subtypes
+                    .entry(super_type.clone())
+                    .or_default()
+                    .push(decl.id.clone());
+            }
+            if !supertypes.is_empty() {
+                hierarchy.supertypes.insert(decl.id.clone(), supertypes);
+            }
+        }
+        hierarchy
+    }
+
+    /// Project types `id` directly extends or implements
+    pub fn supertypes(&self, id: &DeclarationId) -> &[DeclarationId] {
+        self.supertypes.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Project types directly extending or implementing `id`
+    pub fn subtypes(&self, id: &DeclarationId) -> &[DeclarationId] {
+        self.subtypes.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether `id` extends or implements `ancestor`, directly or through
+    /// other project types
+    pub fn is_subtype_of(&self, id: &DeclarationId, ancestor: &DeclarationId) -> bool {
+        let mut seen = vec![id];
+        let mut pending: Vec<&DeclarationId> = self.supertypes(id).iter().collect();
+        while let Some(current) = pending.pop() {
+            if current == ancestor {
+                return true;
+            }
+            if !seen.contains(&current) {
+                seen.push(current);
+                pending.extend(self.supertypes(current));
+            }
+        }
+        false
+    }
+}
+
+impl Graph {
+    /// Project types directly extending or implementing `id`
+    pub fn subtypes(&self, id: &DeclarationId) -> Vec<&Declaration> {
+        self.resolve_ids(self.hierarchy().subtypes(id))
+    }
+
+    /// Project types `id` directly extends or implements
+    pub fn supertypes(&self, id: &DeclarationId) -> Vec<&Declaration> {
+        self.resolve_ids(self.hierarchy().supertypes(id))
+    }
+
+    fn resolve_ids(&self, ids: &[DeclarationId]) -> Vec<&Declaration> {
+        ids.iter()
+            .filter_map(|id| self.declarations.get(id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn test_hierarchy() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, FileType::Kotlin)
+        };
+        let files = [
+            write(
+                "Shapes.kt",
+                r#"package com.app.shapes
+
+sealed interface Shape
+
+open class Circle : Shape
+
+class Ring : Circle(), Comparable<Ring>
+"#,
+            ),
+            write(
+                "Other.kt",
+                r#"package com.app.other
+
+interface Shape
+
+class Square : Shape
+"#,
+            ),
+        ];
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+        let id = |fqn: &str| graph.find_by_fqn(fqn).unwrap().id.clone();
+        let names = |decls: Vec<&crate::graph::Declaration>| -> Vec<String> {
+            decls.iter().map(|d| d.name.to_string()).collect()
+        };
+
+        // Super types resolve through the package, not by simple name
+        let shape = id("com.app.shapes.Shape");
+        assert_eq!(names(graph.subtypes(&shape)), ["Circle"]);
+        assert_eq!(
+            names(graph.subtypes(&id("com.app.other.Shape"))),
+            ["Square"]
+        );
+        // Library types are left out
+        assert_eq!(
+            names(graph.supertypes(&id("com.app.shapes.Ring"))),
+            ["Circle"]
+        );
+
+        let hierarchy = graph.hierarchy();
+        assert!(hierarchy.is_subtype_of(&id("com.app.shapes.Ring"), &shape));
+        assert!(!hierarchy.is_subtype_of(&id("com.app.other.Square"), &shape));
+        assert!(!hierarchy.is_subtype_of(&shape, &shape));
+    }
+}
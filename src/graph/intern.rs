@@ -0,0 +1,410 @@
+// String interning
+//
+// Large projects repeat the same names, qualified names, module names and
+// file paths across millions of declarations, ids, locations and references.
+// Each distinct string is stored once, in a process-wide table, and the
+// graph holds pointer-sized handles to it: a `Symbol` for names, a
+// `FilePath` for paths. Both dereference to the string or path, so they
+// read like the owned values they replace. Interned strings live for the
+// rest of the process; a long-running server only adds the names its edits
+// introduce.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+/// Number of independently locked parts of a table, so parallel parsing
+/// rarely waits on another thread
+const SHARDS: usize = 32;
+
+/// Table of interned values of one type
+struct Interner<T: ?Sized + 'static> {
+    shards: Vec<RwLock<HashSet<&'static T>>>,
+    hasher: RandomState,
+}
+
+impl<T: ?Sized + Hash + Eq + 'static> Interner<T>
+where
+    Box<T>: for<'a> From<&'a T>,
+{
+    fn new() -> Self {
+        Self {
+            shards: (0..SHARDS).map(|_| RwLock::default()).collect(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// The table's copy of `value`, added on first use
+    fn intern(&self, value: &T) -> &'static T {
+        let shard = &self.shards[self.hasher.hash_one(value) as usize % SHARDS];
+        if let Some(&interned) = shard
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(value)
+        {
+            return interned;
+        }
+        let mut table = shard.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(&interned) = table.get(value) {
+            return interned;
+        }
+        let interned: &'static T = Box::leak(Box::from(value));
+        table.insert(interned);
+        interned
+    }
+}
+
+fn strings() -> &'static Interner<str> {
+    static STRINGS: OnceLock<Interner<str>> = OnceLock::new();
+    STRINGS.get_or_init(Interner::new)
+}
+
+fn paths() -> &'static Interner<Path> {
+    static PATHS: OnceLock<Interner<Path>> = OnceLock::new();
+    PATHS.get_or_init(Interner::new)
+}
+
+/// Interned string: a name, qualified name or module name
+///
+/// Equal strings share one allocation, so comparing symbols compares
+/// pointers. Dereferences to `str`.
+#[derive(Clone)]
+pub struct Symbol(&'static str);
+
+impl Symbol {
+    pub fn new(value: &str) -> Self {
+        Self(strings().intern(value))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl Default for Symbol {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        self.0
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    // Hashed like the string, as `Borrow<str>` requires
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(other.0)
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<Symbol> for str {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == other.0
+    }
+}
+
+impl PartialEq<Symbol> for &str {
+    fn eq(&self, other: &Symbol) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialEq<Symbol> for String {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == other.0
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.0, f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&String> for Symbol {
+    fn from(value: &String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(value: String) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(value: Symbol) -> Self {
+        value.0.to_string()
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+        Ok(Self::new(&value))
+    }
+}
+
+/// Interned file path
+///
+/// Equal paths share one allocation, so comparing them compares pointers.
+/// Dereferences to `Path`.
+#[derive(Clone)]
+pub struct FilePath(&'static Path);
+
+impl FilePath {
+    pub fn new(path: &Path) -> Self {
+        Self(paths().intern(path))
+    }
+
+    pub fn as_path(&self) -> &'static Path {
+        self.0
+    }
+}
+
+impl Default for FilePath {
+    fn default() -> Self {
+        Self::new(Path::new(""))
+    }
+}
+
+impl Deref for FilePath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        self.0
+    }
+}
+
+impl AsRef<Path> for FilePath {
+    fn as_ref(&self) -> &Path {
+        self.0
+    }
+}
+
+impl Borrow<Path> for FilePath {
+    fn borrow(&self) -> &Path {
+        self.0
+    }
+}
+
+impl PartialEq for FilePath {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for FilePath {}
+
+impl Hash for FilePath {
+    // Hashed like the path, as `Borrow<Path>` requires
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl PartialOrd for FilePath {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FilePath {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(other.0)
+    }
+}
+
+impl PartialEq<Path> for FilePath {
+    fn eq(&self, other: &Path) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&Path> for FilePath {
+    fn eq(&self, other: &&Path) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<PathBuf> for FilePath {
+    fn eq(&self, other: &PathBuf) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<FilePath> for Path {
+    fn eq(&self, other: &FilePath) -> bool {
+        self == other.0
+    }
+}
+
+impl PartialEq<FilePath> for &Path {
+    fn eq(&self, other: &FilePath) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialEq<FilePath> for PathBuf {
+    fn eq(&self, other: &FilePath) -> bool {
+        self == other.0
+    }
+}
+
+impl fmt::Debug for FilePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.0, f)
+    }
+}
+
+impl From<&Path> for FilePath {
+    fn from(path: &Path) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<&PathBuf> for FilePath {
+    fn from(path: &PathBuf) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<PathBuf> for FilePath {
+    fn from(path: PathBuf) -> Self {
+        Self::new(&path)
+    }
+}
+
+impl From<FilePath> for PathBuf {
+    fn from(path: FilePath) -> Self {
+        path.0.to_path_buf()
+    }
+}
+
+impl Serialize for FilePath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FilePath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = PathBuf::deserialize(deserializer)?;
+        Ok(Self::new(&path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_interned_values_share_storage() {
+        let a = Symbol::new("UserRepository");
+        let b = Symbol::from("UserRepository".to_string());
+        assert_eq!(a, b);
+        assert!(std::ptr::eq(a.as_str(), b.as_str()));
+        assert_ne!(a, Symbol::new("UserRepositoryImpl"));
+        assert_eq!(a, "UserRepository");
+        assert!(a.starts_with("User"));
+        assert!(Symbol::new("a") < Symbol::new("b"));
+
+        let names: HashMap<Symbol, usize> = HashMap::from([(a.clone(), 1)]);
+        assert_eq!(names.get("UserRepository"), Some(&1));
+
+        let file = FilePath::new(Path::new("src/Main.kt"));
+        assert_eq!(file, FilePath::from(PathBuf::from("src/Main.kt")));
+        assert_eq!(file, PathBuf::from("src/Main.kt"));
+        assert!(std::ptr::eq(
+            file.as_path(),
+            FilePath::new(Path::new("src/Main.kt")).as_path()
+        ));
+        assert_eq!(file.extension().unwrap(), "kt");
+
+        let json = serde_json::to_string(&(&a, &file)).unwrap();
+        assert_eq!(json, r#"["UserRepository","src/Main.kt"]"#);
+        let (name, path): (Symbol, FilePath) = serde_json::from_str(&json).unwrap();
+        assert_eq!((name, path), (a, file));
+    }
+}
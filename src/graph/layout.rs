@@ -0,0 +1,378 @@
+// Layout XML references
+//
+// Data binding expressions and `android:onClick` handlers use ViewModel
+// properties and Activity methods that no Kotlin or Java code may touch,
+// and custom views, layout managers and behaviors are instantiated from
+// their class names. Each layout becomes a file node with a reference to
+// every class and member it uses, so those are reachable and counted as
+// used like any other call site.
+
+use super::{
+    Declaration, DeclarationId, DeclarationKind, Graph, Location, Reference, ReferenceConfidence,
+    ReferenceKind,
+};
+use crate::parser::xml::{MemberChain, XmlParseResult};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Observable wrappers data binding unwraps (`viewModel.user.name` reads
+/// `name` of the `User` inside a `LiveData<User>`)
+const OBSERVABLE_WRAPPERS: &[&str] = &[
+    "LiveData",
+    "MutableLiveData",
+    "StateFlow",
+    "MutableStateFlow",
+    "ObservableField",
+];
+
+impl Graph {
+    /// Add a layout's node and its references to the classes and members it
+    /// uses
+    pub fn add_layout_references(&mut self, path: &Path, len: usize, layout: &XmlParseResult) {
+        let mut targets: Vec<(DeclarationId, ReferenceKind, String, ReferenceConfidence)> =
+            Vec::new();
+
+        // View tags, `<fragment>`/`<view class>`, `tools:context`,
+        // `app:layoutManager`, `app:layout_behavior` and binding variable types
+        for class_ref in &layout.class_references {
+            if let Some(class) = self.find_class(class_ref.trim_start_matches('.')) {
+                targets.push((
+                    class.id.clone(),
+                    ReferenceKind::Type,
+                    class.name.to_string(),
+                    ReferenceConfidence::Exact,
+                ));
+            }
+        }
+
+        for chain in &layout.member_chains {
+            self.resolve_member_chain(chain, &mut targets);
+        }
+
+        // Without tools:context, any method of that name may be the handler
+        let context = layout
+            .context_class
+            .as_deref()
+            .and_then(|class| self.find_class(class.trim_start_matches('.')));
+        for handler in &layout.click_handlers {
+            let (methods, confidence): (Vec<&Declaration>, _) = match context {
+                Some(class) => (
+                    self.find_members(class, std::slice::from_ref(handler)),
+                    ReferenceConfidence::Exact,
+                ),
+                None => (
+                    self.find_by_name(handler)
+                        .into_iter()
+                        .filter(|d| d.kind.is_callable() && d.parent.is_some())
+                        .collect(),
+                    ReferenceConfidence::Heuristic,
+                ),
+            };
+            for method in methods {
+                targets.push((
+                    method.id.clone(),
+                    ReferenceKind::Call,
+                    handler.clone(),
+                    confidence,
+                ));
+            }
+        }
+
+        if targets.is_empty() {
+            return;
+        }
+        let node = self.add_declaration(Declaration::layout_file(path, len));
+        for (target, kind, name, confidence) in targets {
+            let location = Location::new(path.to_path_buf(), 0, 0, 0, len);
+            let reference = Reference::new(kind, location, name).with_confidence(confidence);
+            self.add_reference(&node, &target, reference);
+        }
+    }
+
+    /// Follow a member chain through member types, collecting each member
+    fn resolve_member_chain(
+        &self,
+        chain: &MemberChain,
+        targets: &mut Vec<(DeclarationId, ReferenceKind, String, ReferenceConfidence)>,
+    ) {
+        let Some(mut class) = self.find_class(&chain.class_fqn) else {
+            return;
+        };
+        for (index, member) in chain.members.iter().enumerate() {
+            let last = index + 1 == chain.members.len();
+            let mut names = vec![
+                member.clone(),
+                accessor("get", member),
+                accessor("is", member),
+            ];
+            if last && chain.two_way {
+                names.push(accessor("set", member));
+            }
+
+            let found = self.find_members(class, &names);
+            for decl in &found {
+                let kind = if decl.kind.is_callable() {
+                    ReferenceKind::Call
+                } else {
+                    ReferenceKind::Read
+                };
+                targets.push((
+                    decl.id.clone(),
+                    kind,
+                    decl.name.to_string(),
+                    ReferenceConfidence::Exact,
+                ));
+                if last && chain.two_way && !decl.kind.is_callable() {
+                    targets.push((
+                        decl.id.clone(),
+                        ReferenceKind::Write,
+                        decl.name.to_string(),
+                        ReferenceConfidence::Exact,
+                    ));
+                }
+            }
+
+            let next = found
+                .iter()
+                .find_map(|decl| decl.type_name.as_deref())
+                .and_then(|type_name| self.find_class(value_type(type_name)));
+            match next {
+                Some(next) => class = next,
+                None => return,
+            }
+        }
+    }
+
+    /// A class by fully qualified name, or by simple name if unique enough
+    fn find_class(&self, name: &str) -> Option<&Declaration> {
+        if let Some(decl) = self.find_by_fqn(name) {
+            return Some(decl);
+        }
+        let simple_name = name.rsplit('.').next().unwrap_or(name);
+        self.find_by_name(simple_name)
+            .into_iter()
+            .find(|d| d.kind.is_type())
+    }
+
+    /// Members of a class or its project supertypes with one of the names
+    fn find_members(&self, class: &Declaration, names: &[String]) -> Vec<&Declaration> {
+        let mut classes = vec![class];
+        let mut seen = HashSet::new();
+        let mut found = Vec::new();
+        while let Some(class) = classes.pop() {
+            if !seen.insert(&class.id) {
+                continue;
+            }
+            found.extend(
+                self.get_children(&class.id)
+                    .into_iter()
+                    .filter_map(|id| self.get_declaration(id))
+                    .filter(|d| names.iter().any(|name| d.name == *name))
+                    .filter(|d| d.kind != DeclarationKind::Parameter),
+            );
+            classes.extend(
+                class
+                    .super_types
+                    .iter()
+                    .filter_map(|super_type| self.find_class(value_type(super_type))),
+            );
+        }
+        found
+    }
+}
+
+/// `title` -> `getTitle`
+fn accessor(prefix: &str, member: &str) -> String {
+    let mut chars = member.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}{}", prefix, first.to_uppercase(), chars.as_str()),
+        None => prefix.to_string(),
+    }
+}
+
+/// Simple name of the type a binding expression sees: `LiveData<User?>`
+/// -> `User`, `BaseViewModel()` -> `BaseViewModel`
+fn value_type(type_name: &str) -> &str {
+    let type_name = type_name
+        .trim_start_matches("extends ")
+        .trim_start_matches("implements ")
+        .trim()
+        .trim_end_matches('?');
+    let (outer, argument) = match type_name.find('<') {
+        Some(open) => (
+            &type_name[..open],
+            Some(type_name[open + 1..].trim_end_matches('>').trim()),
+        ),
+        None => (type_name, None),
+    };
+    let outer = outer.trim_end_matches("()");
+    let outer = &outer[..outer.find('(').unwrap_or(outer.len())];
+    let simple = outer.rsplit('.').next().unwrap_or(outer);
+    match argument {
+        Some(argument) if OBSERVABLE_WRAPPERS.contains(&simple) => value_type(argument),
+        _ => simple,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn test_layout_references() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str, file_type: FileType| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, file_type)
+        };
+        let files = [
+            write(
+                "ProfileViewModel.kt",
+                r#"package com.app
+
+class User {
+    val name: String = ""
+    val nickname: String = ""
+}
+
+class ProfileViewModel : BaseViewModel() {
+    val user: LiveData<User> = MutableLiveData()
+    var query: String = ""
+    fun onSave() {}
+    fun onUnused() {}
+}
+
+open class BaseViewModel {
+    val isLoading: Boolean = false
+}
+
+class ProfileActivity {
+    fun onHelpClicked(view: View) {}
+}
+"#,
+                FileType::Kotlin,
+            ),
+            write(
+                "Settings.java",
+                r#"package com.app;
+
+public class Settings {
+    public String getTitle() { return ""; }
+}
+"#,
+                FileType::Java,
+            ),
+            write(
+                "activity_profile.xml",
+                r#"<layout xmlns:android="http://schemas.android.com/apk/res/android"
+    xmlns:tools="http://schemas.android.com/tools">
+    <data>
+        <variable name="vm" type="com.app.ProfileViewModel" />
+        <variable name="settings" type="com.app.Settings" />
+    </data>
+    <LinearLayout tools:context=".ProfileActivity">
+        <TextView android:text="@{vm.user.name}" />
+        <TextView android:text="@{settings.title + 'a.b'}" />
+        <EditText android:text="@={vm.query}" />
+        <ProgressBar android:visibility="@{vm.isLoading ? View.VISIBLE : View.GONE}" />
+        <Button android:onClick="@{() -> vm.onSave()}" />
+        <Button android:onClick="onHelpClicked" />
+    </LinearLayout>
+</layout>
+"#,
+                FileType::XmlLayout,
+            ),
+        ];
+
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+
+        let used = |name: &str| {
+            graph.find_by_name(name).iter().any(|d| {
+                graph
+                    .get_references_to(&d.id)
+                    .iter()
+                    .any(|(from, _)| from.name == "activity_profile.xml")
+            })
+        };
+        for name in [
+            "user",
+            "name",
+            "getTitle",
+            "query",
+            "isLoading",
+            "onSave",
+            "onHelpClicked",
+        ] {
+            assert!(used(name), "{name} should be used by the layout");
+        }
+        assert!(!used("nickname"));
+        assert!(!used("onUnused"));
+    }
+
+    #[test]
+    fn test_layout_class_references() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str, file_type: FileType| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, file_type)
+        };
+        let files = [
+            write(
+                "Views.kt",
+                r#"package com.app.ui
+
+class FancyButton(context: Context) : AppCompatButton(context)
+
+class GridManager(context: Context, attrs: AttributeSet) : GridLayoutManager(context, attrs)
+
+class FabBehavior : CoordinatorLayout.Behavior<View>()
+
+class HomeActivity : AppCompatActivity()
+
+class UnusedView(context: Context) : View(context)
+"#,
+                FileType::Kotlin,
+            ),
+            write(
+                "activity_home.xml",
+                r#"<androidx.coordinatorlayout.widget.CoordinatorLayout
+    xmlns:android="http://schemas.android.com/apk/res/android"
+    xmlns:app="http://schemas.android.com/apk/res-auto"
+    xmlns:tools="http://schemas.android.com/tools"
+    tools:context=".ui.HomeActivity">
+    <com.app.ui.FancyButton android:layout_width="wrap_content" />
+    <androidx.recyclerview.widget.RecyclerView app:layoutManager=".ui.GridManager" />
+    <View app:layout_behavior="com.app.ui.FabBehavior" />
+</androidx.coordinatorlayout.widget.CoordinatorLayout>
+"#,
+                FileType::XmlLayout,
+            ),
+        ];
+
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+
+        let used = |name: &str| {
+            graph.find_by_name(name).iter().any(|d| {
+                graph
+                    .get_references_to(&d.id)
+                    .iter()
+                    .any(|(from, _)| from.name == "activity_home.xml")
+            })
+        };
+        for name in ["FancyButton", "GridManager", "FabBehavior", "HomeActivity"] {
+            assert!(used(name), "{name} should be used by the layout");
+        }
+        assert!(!used("UnusedView"));
+    }
+}
@@ -2,20 +2,77 @@
 #![allow(dead_code)]
 
 mod builder;
+mod cache;
+mod condensation;
 mod declaration;
+mod delegation;
+mod export;
+mod hierarchy;
+mod intern;
+mod layout;
+mod modules;
+mod multiplatform;
 mod parallel_builder;
+mod path;
+#[cfg(feature = "graph-api")]
+pub mod query;
+mod reach;
 pub mod reference;
+pub mod resolver;
+mod stats;
 
 pub use builder::GraphBuilder;
+pub use cache::GraphCache;
+pub use condensation::Condensation;
 pub use declaration::{
-    Declaration, DeclarationId, DeclarationKind, Language, Location, Visibility,
+    Declaration, DeclarationId, DeclarationKind, Language, Location, Metrics, Visibility,
 };
+#[allow(unused_imports)] // ExportEdge and ExportNode are only named by library consumers
+pub use export::{ExportEdge, ExportNode, ExportOptions, ExportSelection};
+pub use hierarchy::Hierarchy;
+#[allow(unused_imports)] // FilePath is only named by library consumers
+pub use intern::{FilePath, Symbol};
+pub use modules::ModuleIndex;
 pub use parallel_builder::ParallelGraphBuilder;
-pub use reference::{Reference, ReferenceKind, UnresolvedReference};
+#[allow(unused_imports)] // PathStep is only named by library consumers
+pub use path::{PathLink, PathStep};
+#[cfg(feature = "graph-api")]
+#[allow(unused_imports)] // only used by library consumers, not the CLI
+pub use query::{GraphQuery, ReferenceView};
+pub use reach::Reach;
+pub use reference::{
+    CallArgument, CallArguments, CallReceiver, Reference, ReferenceConfidence, ReferenceKind,
+    UnresolvedReference,
+};
+pub use resolver::{FileScope, TypeResolver};
+#[allow(unused_imports)] // Bucket and Distribution are only named by library consumers
+pub use stats::{Bucket, Distribution, GraphStats, RankedDeclaration};
 
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Serializable slice of a [`Graph`]: declarations and the references between them
+///
+/// Declarations and references are listed in insertion order, so the same
+/// graph always serializes the same way.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct GraphFragment {
+    pub declarations: Vec<Declaration>,
+    pub references: Vec<ReferenceEdge>,
+}
+
+/// A reference between two declarations of a [`GraphFragment`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceEdge {
+    pub from: DeclarationId,
+    pub to: DeclarationId,
+    pub reference: Reference,
+}
 
 /// The reference graph containing all declarations and their relationships
 #[derive(Debug)]
@@ -31,13 +88,41 @@ pub struct Graph {
     declarations: HashMap<DeclarationId, Declaration>,
 
     /// Map from simple name to possible declarations (for resolution)
-    name_index: HashMap<String, Vec<DeclarationId>>,
+    ///
+    /// Parameters are left out: they are only visible inside their function
+    /// and are found through `children_index` instead.
+    name_index: HashMap<Symbol, Vec<DeclarationId>>,
 
     /// Map from fully qualified name to declaration
-    fqn_index: HashMap<String, DeclarationId>,
+    fqn_index: HashMap<Symbol, DeclarationId>,
 
     /// Map from parent to children (for fast member lookup)
     children_index: HashMap<DeclarationId, Vec<DeclarationId>>,
+
+    /// Map from receiver type to the extensions declared on it
+    extension_index: HashMap<Symbol, Vec<DeclarationId>>,
+
+    /// Package and imports of each parsed source file
+    file_scopes: HashMap<PathBuf, FileScope>,
+
+    /// Declarations named in string literals, by the declaration named
+    ///
+    /// Kept out of `inner` so reachability does not follow them: a string
+    /// only suggests the declaration is loaded by reflection.
+    string_references: HashMap<DeclarationId, Vec<(DeclarationId, Reference)>>,
+
+    /// Strongly connected components, computed on first use and dropped
+    /// when nodes or edges change
+    condensation: OnceLock<Condensation>,
+
+    /// Direct super and subtypes of every type, resolved on first use (the
+    /// builders resolve it before returning the graph) and dropped when
+    /// declarations or file scopes change
+    hierarchy: OnceLock<Hierarchy>,
+
+    /// Declarations of each module and the references between modules,
+    /// computed on first use and dropped when nodes or edges change
+    module_index: OnceLock<ModuleIndex>,
 }
 
 impl Graph {
@@ -50,22 +135,33 @@ impl Graph {
             name_index: HashMap::new(),
             fqn_index: HashMap::new(),
             children_index: HashMap::new(),
+            extension_index: HashMap::new(),
+            file_scopes: HashMap::new(),
+            string_references: HashMap::new(),
+            condensation: OnceLock::new(),
+            hierarchy: OnceLock::new(),
+            module_index: OnceLock::new(),
         }
     }
 
     /// Add a declaration to the graph
     pub fn add_declaration(&mut self, decl: Declaration) -> DeclarationId {
         let id = decl.id.clone();
+        self.condensation.take();
+        self.hierarchy.take();
+        self.module_index.take();
 
         // Add to graph
         let node_idx = self.inner.add_node(id.clone());
         self.node_map.insert(id.clone(), node_idx);
 
         // Index by simple name
-        self.name_index
-            .entry(decl.name.clone())
-            .or_default()
-            .push(id.clone());
+        if decl.kind != DeclarationKind::Parameter {
+            self.name_index
+                .entry(decl.name.clone())
+                .or_default()
+                .push(id.clone());
+        }
 
         // Index by fully qualified name
         if let Some(fqn) = &decl.fully_qualified_name {
@@ -80,6 +176,14 @@ impl Graph {
                 .push(id.clone());
         }
 
+        // Index extensions by receiver type
+        if let Some(receiver) = &decl.receiver_type {
+            self.extension_index
+                .entry(Symbol::new(receiver))
+                .or_default()
+                .push(id.clone());
+        }
+
         // Store declaration details
         self.declarations.insert(id.clone(), decl);
 
@@ -94,8 +198,74 @@ impl Graph {
         reference: Reference,
     ) {
         if let (Some(&from_idx), Some(&to_idx)) = (self.node_map.get(from), self.node_map.get(to)) {
-            self.inner.add_edge(from_idx, to_idx, reference);
+            if reference.kind == ReferenceKind::ReflectionString {
+                self.string_references
+                    .entry(to.clone())
+                    .or_default()
+                    .push((from.clone(), reference));
+            } else {
+                self.inner.add_edge(from_idx, to_idx, reference);
+                self.condensation.take();
+                self.module_index.take();
+            }
+        }
+    }
+
+    /// Declarations naming `id` in a string literal, and those references
+    pub fn string_references_to(&self, id: &DeclarationId) -> Vec<(&Declaration, &Reference)> {
+        self.string_references
+            .get(id)
+            .into_iter()
+            .flatten()
+            .filter_map(|(from, reference)| Some((self.declarations.get(from)?, reference)))
+            .collect()
+    }
+
+    /// Declarations a string literal names exactly: types by fully qualified
+    /// name (`"com.app.Outer$Inner"`), functions, properties and fields by
+    /// name or alternate name (`@JvmName`) when the string is passed to a
+    /// reflective lookup
+    pub fn find_named_in_string(
+        &self,
+        name: &str,
+        qualified_name: Option<&str>,
+    ) -> Vec<&Declaration> {
+        let mut found = self.find_by_name(name);
+        if qualified_name.is_none() {
+            found.extend(
+                self.declarations
+                    .values()
+                    .filter(|d| d.name != name && d.alternate_names.iter().any(|n| n == name)),
+            );
         }
+        found
+            .into_iter()
+            .filter(|d| match qualified_name {
+                Some(fqn) => {
+                    d.kind.is_type()
+                        && (d.fully_qualified_name.as_deref() == Some(fqn)
+                            || self.nested_fqn(d) == fqn)
+                }
+                None => matches!(
+                    d.kind,
+                    DeclarationKind::Function
+                        | DeclarationKind::Method
+                        | DeclarationKind::Property
+                        | DeclarationKind::Field
+                ),
+            })
+            .collect()
+    }
+
+    /// Record the package and imports of a parsed source file
+    pub fn set_file_scope(&mut self, path: PathBuf, scope: FileScope) {
+        self.file_scopes.insert(path, scope);
+        self.hierarchy.take();
+    }
+
+    /// Package and imports of a parsed source file
+    pub fn file_scope(&self, path: &Path) -> Option<&FileScope> {
+        self.file_scopes.get(path)
     }
 
     /// Get a declaration by ID
@@ -113,7 +283,7 @@ impl Graph {
         self.declarations.keys()
     }
 
-    /// Find declarations by simple name
+    /// Find declarations by simple name (parameters excluded)
     pub fn find_by_name(&self, name: &str) -> Vec<&Declaration> {
         self.name_index
             .get(name)
@@ -125,6 +295,434 @@ impl Graph {
             .unwrap_or_default()
     }
 
+    /// Declarations a simple name used inside `from` can refer to
+    ///
+    /// Parameters are only in scope inside their own function (including
+    /// declarations nested in it); everything else is resolved globally.
+    pub fn find_in_scope(&self, name: &str, from: &DeclarationId) -> Vec<&Declaration> {
+        let mut found = self.find_by_name(name);
+
+        let mut scope = self.declarations.get(from);
+        while let Some(decl) = scope {
+            if decl.kind.is_callable() {
+                found.extend(
+                    self.get_children(&decl.id)
+                        .into_iter()
+                        .filter_map(|id| self.declarations.get(id))
+                        .filter(|child| child.kind == DeclarationKind::Parameter)
+                        .filter(|child| child.name == name),
+                );
+            }
+            scope = decl.parent.as_ref().and_then(|p| self.declarations.get(p));
+        }
+        found
+    }
+
+    /// Extension functions and properties declared on a receiver type
+    pub fn find_extensions(&self, receiver_type: &str) -> Vec<&Declaration> {
+        self.extension_index
+            .get(receiver_type)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| self.declarations.get(id))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Declarations each typealias stands for: the project types named by
+    /// the aliased type, following aliases of aliases
+    /// (`typealias Repo = UserRepository`). Function types and library
+    /// types have none.
+    pub fn type_alias_targets(&self) -> HashMap<DeclarationId, Vec<DeclarationId>> {
+        let mut table = HashMap::new();
+        for alias in self.declarations.values() {
+            if alias.kind != DeclarationKind::TypeAlias {
+                continue;
+            }
+            let mut targets = Vec::new();
+            let mut seen = HashSet::from([alias.id.clone()]);
+            let mut pending = vec![alias];
+            while let Some(current) = pending.pop() {
+                let Some(name) = current.type_name.as_deref().and_then(aliased_type_name) else {
+                    continue;
+                };
+                let found: Vec<&Declaration> = match self.find_by_fqn(name) {
+                    Some(decl) => vec![decl],
+                    None => self
+                        .find_by_name(name.rsplit('.').next().unwrap_or(name))
+                        .into_iter()
+                        .filter(|d| d.kind.is_type())
+                        .collect(),
+                };
+                for decl in found {
+                    if !seen.insert(decl.id.clone()) {
+                        continue;
+                    }
+                    if decl.kind == DeclarationKind::TypeAlias {
+                        pending.push(decl);
+                    } else {
+                        targets.push(decl.id.clone());
+                    }
+                }
+            }
+            if !targets.is_empty() {
+                table.insert(alias.id.clone(), targets);
+            }
+        }
+        table
+    }
+
+    /// Narrow the declarations a call resolved to by the call's receiver
+    ///
+    /// An extension stands for every overload sharing its fully qualified
+    /// name (`fun String.toSlug()` and `fun Uri.toSlug()` in one file). When
+    /// the receiver's type is known and one of the extensions is declared on
+    /// exactly that type, Kotlin picks it over the others, so the others are
+    /// dropped. Members and unknown receivers are left alone.
+    pub fn narrow_to_receiver<'a>(
+        &'a self,
+        mut found: Vec<&'a Declaration>,
+        receiver: Option<&CallReceiver>,
+        from: &DeclarationId,
+    ) -> Vec<&'a Declaration> {
+        let overloads: Vec<&Declaration> = found
+            .iter()
+            .filter(|d| d.receiver_type.is_some())
+            .filter_map(|d| d.fully_qualified_name.as_deref().map(|fqn| (d, fqn)))
+            .flat_map(|(d, fqn)| {
+                self.find_by_name(&d.name).into_iter().filter(move |other| {
+                    other.receiver_type.is_some()
+                        && other.fully_qualified_name.as_deref() == Some(fqn)
+                })
+            })
+            .collect();
+        for overload in overloads {
+            if !found.iter().any(|d| d.id == overload.id) {
+                found.push(overload);
+            }
+        }
+
+        let Some(receiver_type) = receiver.and_then(|r| self.receiver_type(r, from)) else {
+            return found;
+        };
+        let exact = found
+            .iter()
+            .any(|d| d.receiver_type.as_deref() == Some(receiver_type));
+        if exact {
+            found.retain(|d| {
+                d.receiver_type
+                    .as_deref()
+                    .map_or(true, |r| r == receiver_type)
+            });
+        }
+        found
+    }
+
+    /// Narrow the declarations a qualified access resolved to by the type
+    /// qualifying it
+    ///
+    /// `Foo.create()`, `Foo.CONSTANT` and `Foo.Companion.create()` reach
+    /// into the member scope of `Foo`: its own members and those of its
+    /// companion objects. When some candidates are in that scope the others
+    /// are dropped, extensions aside; inherited members and qualifiers that
+    /// are not project types leave every candidate.
+    pub fn narrow_to_qualifier<'a>(
+        &'a self,
+        found: Vec<&'a Declaration>,
+        receiver: Option<&CallReceiver>,
+    ) -> Vec<&'a Declaration> {
+        let Some(CallReceiver::Type(qualifier)) = receiver else {
+            return found;
+        };
+        let scope: HashSet<&DeclarationId> = self
+            .qualifier_types(qualifier)
+            .into_iter()
+            .flat_map(|decl| self.member_scope(decl))
+            .map(|member| &member.id)
+            .collect();
+        if !found.iter().any(|d| scope.contains(&d.id)) {
+            return found;
+        }
+        found
+            .into_iter()
+            .filter(|d| scope.contains(&d.id) || d.receiver_type.is_some())
+            .collect()
+    }
+
+    /// Members named by a qualified path (`com.app.Foo.Companion.create`,
+    /// as in an import), walking nested types and companion objects
+    pub fn find_qualified_member(&self, path: &str) -> Vec<&Declaration> {
+        let Some((qualifier, name)) = path.rsplit_once('.') else {
+            return Vec::new();
+        };
+        self.qualifier_types(qualifier)
+            .into_iter()
+            .flat_map(|decl| self.member_scope(decl))
+            .filter(|member| member.name == name)
+            .collect()
+    }
+
+    /// Types a qualifier written before a member stands for (`Foo`,
+    /// `Foo.Companion`, `com.app.Foo`): the longest fully qualified prefix,
+    /// or else the types with the first segment's simple name, then nested
+    /// types down the remaining segments
+    fn qualifier_types(&self, qualifier: &str) -> Vec<&Declaration> {
+        let segments: Vec<&str> = qualifier.split('.').collect();
+        let (mut types, rest) = (1..=segments.len())
+            .rev()
+            .find_map(|n| {
+                let decl = self.find_by_fqn(&segments[..n].join("."))?;
+                Some((vec![decl], &segments[n..]))
+            })
+            .unwrap_or_else(|| {
+                let types = self
+                    .find_by_name(segments[0])
+                    .into_iter()
+                    .filter(|d| d.kind.is_type())
+                    .collect();
+                (types, &segments[1..])
+            });
+        for segment in rest {
+            types = types
+                .into_iter()
+                .flat_map(|decl| self.member_scope(decl))
+                .filter(|member| member.kind.is_type() && member.name == *segment)
+                .collect();
+        }
+        types.retain(|decl| decl.kind.is_type());
+        types
+    }
+
+    /// Members a type qualifies: its own and those of its companion objects
+    fn member_scope(&self, decl: &Declaration) -> Vec<&Declaration> {
+        let members: Vec<&Declaration> = self
+            .get_children(&decl.id)
+            .into_iter()
+            .filter_map(|id| self.declarations.get(id))
+            .collect();
+        let companion_members: Vec<&Declaration> = members
+            .iter()
+            .filter(|member| member.is_companion())
+            .flat_map(|companion| self.get_children(&companion.id))
+            .filter_map(|id| self.declarations.get(id))
+            .collect();
+        members.into_iter().chain(companion_members).collect()
+    }
+
+    /// Narrow the declarations a simple name resolved to by the package
+    /// and imports of the file using it
+    ///
+    /// Two `Result` classes in different packages share a simple name, so
+    /// the one the file can see wins, in Kotlin and Java order: explicitly
+    /// imported, then declared in the file's own package, then wildcard
+    /// imported. A name explicitly imported from outside the project (say
+    /// `kotlin.Result`) refers to none of them. Members are left alone, and
+    /// when the imports decide nothing every candidate is kept.
+    pub fn narrow_to_imports<'a>(
+        &'a self,
+        found: Vec<&'a Declaration>,
+        name: &str,
+        package: Option<&str>,
+        imports: &[String],
+    ) -> Vec<&'a Declaration> {
+        let (scoped, members): (Vec<&Declaration>, Vec<&Declaration>) = found
+            .into_iter()
+            .partition(|d| d.fully_qualified_name.is_some());
+        let explicit: Vec<&str> = imports
+            .iter()
+            .filter(|import| !import.ends_with(".*") && !import.contains(" as "))
+            .map(String::as_str)
+            .collect();
+        let wildcards: Vec<&str> = imports
+            .iter()
+            .filter_map(|import| import.strip_suffix(".*"))
+            .collect();
+        let imported_elsewhere = explicit
+            .iter()
+            .any(|import| import.rsplit('.').next() == Some(name));
+        if scoped.is_empty() || (explicit.is_empty() && wildcards.is_empty() && scoped.len() < 2) {
+            return scoped.into_iter().chain(members).collect();
+        }
+
+        // (declaration, its package, its FQN including outer classes)
+        let candidates: Vec<(&Declaration, &str, String)> = scoped
+            .iter()
+            .map(|d| {
+                let fqn = d.fully_qualified_name.as_deref().unwrap_or(&d.name);
+                let package = fqn
+                    .strip_suffix(d.name.as_str())
+                    .and_then(|p| p.strip_suffix('.'))
+                    .unwrap_or("");
+                (*d, package, self.nested_fqn(d))
+            })
+            .collect();
+        let within = |fqn: &str, prefix: &str| {
+            fqn.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        };
+        let pick = |visible: &dyn Fn(&str, &str) -> bool| -> Vec<&'a Declaration> {
+            candidates
+                .iter()
+                .filter(|(_, pkg, fqn)| visible(pkg, fqn))
+                .map(|(d, _, _)| *d)
+                .collect()
+        };
+
+        let imported = pick(&|_, fqn| explicit.iter().any(|import| within(fqn, import)));
+        if !imported.is_empty() {
+            return imported.into_iter().chain(members).collect();
+        }
+        // An explicit import of a library type shadows the project's
+        if imported_elsewhere {
+            return members;
+        }
+        let same_package = pick(&|pkg, _| pkg == package.unwrap_or(""));
+        if !same_package.is_empty() {
+            return same_package.into_iter().chain(members).collect();
+        }
+        // `import com.app.*` or, for nested classes, `import com.app.Outer.*`
+        let wildcard = pick(&|pkg, fqn| {
+            let outer = fqn.strip_suffix(name).and_then(|o| o.strip_suffix('.'));
+            wildcards.iter().any(|w| *w == pkg || Some(*w) == outer)
+        });
+        if !wildcard.is_empty() {
+            return wildcard.into_iter().chain(members).collect();
+        }
+        scoped.into_iter().chain(members).collect()
+    }
+
+    /// Fully qualified name of a declaration including its outer classes
+    /// (`com.app.Outer.Inner`); the indexed FQN leaves them out
+    fn nested_fqn(&self, decl: &Declaration) -> String {
+        match decl
+            .parent
+            .as_ref()
+            .and_then(|p| self.declarations.get(p))
+            .filter(|parent| parent.kind.is_type())
+        {
+            Some(parent) => format!("{}.{}", self.nested_fqn(parent), decl.name),
+            None => decl
+                .fully_qualified_name
+                .as_deref()
+                .unwrap_or(&decl.name)
+                .to_string(),
+        }
+    }
+
+    /// Narrow the overloads a call resolved to by the number of arguments
+    ///
+    /// `load(id)` can only call an overload that takes one argument: one
+    /// parameter, more with defaults, or a vararg. Overloads that cannot take
+    /// the call's arguments are dropped when another one can; when none fits
+    /// (an argument the parser missed), or the callee is not a function,
+    /// every candidate is kept.
+    pub fn narrow_to_arity<'a>(
+        &'a self,
+        found: Vec<&'a Declaration>,
+        arguments: Option<&CallArguments>,
+    ) -> Vec<&'a Declaration> {
+        let Some(arguments) = arguments else {
+            return found;
+        };
+        let fits = |d: &&Declaration| !d.kind.is_callable() || self.accepts(d, arguments);
+        if found.iter().filter(|d| d.kind.is_callable()).count() < 2
+            || !found.iter().any(|d| d.kind.is_callable() && fits(d))
+        {
+            return found;
+        }
+        found.into_iter().filter(fits).collect()
+    }
+
+    /// Whether a function's parameters can take a call's arguments
+    fn accepts(&self, function: &Declaration, call: &CallArguments) -> bool {
+        let params: Vec<&Declaration> = self
+            .get_children(&function.id)
+            .into_iter()
+            .filter_map(|id| self.declarations.get(id))
+            .filter(|child| child.kind == DeclarationKind::Parameter)
+            .collect();
+        let has =
+            |param: &Declaration, modifier: &str| param.modifiers.iter().any(|m| m == modifier);
+        let vararg = params.iter().any(|p| has(p, "vararg"));
+        let named: Vec<&str> = call
+            .arguments
+            .iter()
+            .filter_map(|a| a.name.as_deref())
+            .collect();
+        if !named
+            .iter()
+            .all(|name| params.iter().any(|p| p.name == *name))
+        {
+            return false;
+        }
+
+        let given = call.arguments.len() + usize::from(call.trailing_lambda);
+        // Overrides inherit the defaults of the function they override
+        let required = if function.modifiers.iter().any(|m| m == "override") {
+            0
+        } else {
+            params
+                .iter()
+                .filter(|p| !has(p, "default") && !has(p, "vararg"))
+                .count()
+        };
+        given >= required && (vararg || given <= params.len())
+    }
+
+    /// Simple type name of a call receiver, from the expression or the
+    /// declared type of the variable it names
+    fn receiver_type<'a>(
+        &'a self,
+        receiver: &'a CallReceiver,
+        from: &DeclarationId,
+    ) -> Option<&'a str> {
+        let type_name = match receiver {
+            CallReceiver::Type(name) => name.as_str(),
+            CallReceiver::Name(name) => self.variable_type(name, from)?,
+        };
+        let type_name = type_name.trim_end_matches('?');
+        let type_name = &type_name[..type_name.find('<').unwrap_or(type_name.len())];
+        type_name.rsplit('.').next()
+    }
+
+    /// Declared type of the variable a name refers to inside `from`: the
+    /// nearest parameter or property of an enclosing scope (including the
+    /// primary constructor's `val` / `var` parameters of a class)
+    fn variable_type(&self, name: &str, from: &DeclarationId) -> Option<&str> {
+        let mut scope = self.declarations.get(from);
+        while let Some(decl) = scope {
+            let members: Vec<&Declaration> = self
+                .get_children(&decl.id)
+                .into_iter()
+                .filter_map(|id| self.declarations.get(id))
+                .collect();
+            let constructor_parameters = members
+                .iter()
+                .filter(|member| member.kind == DeclarationKind::Constructor)
+                .flat_map(|constructor| self.get_children(&constructor.id))
+                .filter_map(|id| self.declarations.get(id));
+            let variable = members
+                .iter()
+                .copied()
+                .chain(constructor_parameters)
+                .find(|child| {
+                    child.name == name
+                        && matches!(
+                            child.kind,
+                            DeclarationKind::Parameter
+                                | DeclarationKind::Property
+                                | DeclarationKind::Field
+                        )
+                });
+            if let Some(variable) = variable {
+                return variable.type_name.as_deref();
+            }
+            scope = decl.parent.as_ref().and_then(|p| self.declarations.get(p));
+        }
+        None
+    }
+
     /// Find declaration by fully qualified name
     pub fn find_by_fqn(&self, fqn: &str) -> Option<&Declaration> {
         self.fqn_index
@@ -164,6 +762,38 @@ impl Graph {
             .collect()
     }
 
+    /// Declarations calling `id`, or instantiating it when it is a type,
+    /// each listed once
+    pub fn callers_of(&self, id: &DeclarationId) -> Vec<&Declaration> {
+        let mut seen = HashSet::new();
+        self.get_references_to(id)
+            .into_iter()
+            .filter(|(_, reference)| is_call(reference.kind))
+            .map(|(caller, _)| caller)
+            .filter(|caller| seen.insert(&caller.id))
+            .collect()
+    }
+
+    /// Declarations `id` calls or instantiates, each listed once
+    pub fn callees_of(&self, id: &DeclarationId) -> Vec<&Declaration> {
+        let mut seen = HashSet::new();
+        self.get_references_from(id)
+            .into_iter()
+            .filter(|(_, reference)| is_call(reference.kind))
+            .map(|(callee, _)| callee)
+            .filter(|callee| seen.insert(&callee.id))
+            .collect()
+    }
+
+    /// Project types directly extending or implementing the type `id`
+    ///
+    /// Super types are resolved the way the compiler does, through each
+    /// declaring file's package and imports, so a subtype of another type
+    /// with the same simple name is not listed.
+    pub fn subtypes_of(&self, id: &DeclarationId) -> Vec<&Declaration> {
+        self.subtypes(id)
+    }
+
     /// Check if a declaration is referenced by anything
     pub fn is_referenced(&self, id: &DeclarationId) -> bool {
         let Some(&node_idx) = self.node_map.get(id) else {
@@ -248,10 +878,270 @@ impl Graph {
         &self.inner
     }
 
+    /// Strongly connected components of the reference graph, computed once
+    /// until the graph changes
+    pub fn condensation(&self) -> &Condensation {
+        self.condensation.get_or_init(|| Condensation::new(self))
+    }
+
+    /// Inheritance hierarchy of the project's types, resolved once until
+    /// declarations or file scopes change
+    pub fn hierarchy(&self) -> &Hierarchy {
+        self.hierarchy.get_or_init(|| Hierarchy::new(self))
+    }
+
+    /// Declarations of each module and the references between modules,
+    /// computed once until the graph changes
+    pub fn module_index(&self) -> &ModuleIndex {
+        self.module_index.get_or_init(|| ModuleIndex::new(self))
+    }
+
     /// Get node index for a declaration ID
     pub fn node_index(&self, id: &DeclarationId) -> Option<NodeIndex> {
         self.node_map.get(id).copied()
     }
+
+    /// Snapshot the whole graph
+    pub fn to_fragment(&self) -> GraphFragment {
+        self.collect_fragment(|_| true)
+    }
+
+    /// Snapshot the given declarations and the references between them
+    pub fn fragment<'a>(&self, ids: impl IntoIterator<Item = &'a DeclarationId>) -> GraphFragment {
+        let ids: HashSet<&DeclarationId> = ids.into_iter().collect();
+        self.collect_fragment(|id| ids.contains(id))
+    }
+
+    fn collect_fragment(&self, include: impl Fn(&DeclarationId) -> bool) -> GraphFragment {
+        let declarations = self
+            .inner
+            .node_weights()
+            .filter(|id| include(id))
+            .filter_map(|id| self.declarations.get(id))
+            .cloned()
+            .collect();
+
+        let edges = self
+            .inner
+            .edge_references()
+            .map(|edge| {
+                let from = &self.inner[edge.source()];
+                let to = &self.inner[edge.target()];
+                (from, to, edge.weight())
+            })
+            .chain(self.string_references.iter().flat_map(|(to, references)| {
+                references
+                    .iter()
+                    .map(move |(from, reference)| (from, to, reference))
+            }));
+        let references = edges
+            .filter(|(from, to, _)| include(from) && include(to))
+            .map(|(from, to, reference)| ReferenceEdge {
+                from: from.clone(),
+                to: to.clone(),
+                reference: reference.clone(),
+            })
+            .collect();
+
+        GraphFragment {
+            declarations,
+            references,
+        }
+    }
+
+    /// Add a fragment's declarations and references
+    ///
+    /// References to declarations that are in neither the graph nor the
+    /// fragment are dropped.
+    pub fn extend_from_fragment(&mut self, fragment: GraphFragment) {
+        for decl in fragment.declarations {
+            self.add_declaration(decl);
+        }
+        for edge in fragment.references {
+            self.add_reference(&edge.from, &edge.to, edge.reference);
+        }
+    }
+
+    /// Remove a file's declarations, every reference from or to them, and
+    /// its file scope
+    ///
+    /// Returns the removed declarations.
+    pub fn remove_file(&mut self, path: &Path) -> Vec<Declaration> {
+        self.file_scopes.remove(path);
+        self.hierarchy.take();
+        self.remove_declarations_in(path)
+    }
+
+    /// Replace a file's declarations and the references from them, keeping
+    /// the rest of the graph
+    ///
+    /// `references` are the file's resolved references; those to
+    /// declarations that are not in the graph are dropped. Declaration ids
+    /// are byte offsets, which an edit above a declaration shifts, so
+    /// references from other files follow their target by fully qualified
+    /// name (or name) and kind, overloads in source order, and are dropped
+    /// when the new declarations no longer include it. The file scope is
+    /// kept; set it again when the file's package or imports changed.
+    pub fn update_file(
+        &mut self,
+        path: &Path,
+        declarations: Vec<Declaration>,
+        references: Vec<ReferenceEdge>,
+    ) {
+        let old_identities = identities(self.declarations.values().filter(|d| d.id.file == path));
+        let incoming: Vec<(DeclarationId, Identity, Reference)> = old_identities
+            .into_iter()
+            .flat_map(|(id, identity)| {
+                let graph_edges = self
+                    .get_references_to(&id)
+                    .into_iter()
+                    .map(|(from, reference)| (&from.id, reference));
+                let string_edges = self
+                    .string_references
+                    .get(&id)
+                    .into_iter()
+                    .flatten()
+                    .map(|(from, reference)| (from, reference));
+                graph_edges
+                    .chain(string_edges)
+                    .filter(|(from, _)| from.file != path)
+                    .map(|(from, reference)| (from.clone(), identity.clone(), reference.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let targets: HashMap<Identity, DeclarationId> = identities(declarations.iter())
+            .into_iter()
+            .map(|(id, identity)| (identity, id))
+            .collect();
+
+        self.remove_declarations_in(path);
+        for decl in declarations {
+            self.add_declaration(decl);
+        }
+        for edge in references {
+            self.add_reference(&edge.from, &edge.to, edge.reference);
+        }
+        for (from, identity, reference) in incoming {
+            if let Some(to) = targets.get(&identity) {
+                self.add_reference(&from, to, reference);
+            }
+        }
+    }
+
+    /// Remove the declarations of a file from the graph and its indexes
+    fn remove_declarations_in(&mut self, path: &Path) -> Vec<Declaration> {
+        let ids: HashSet<DeclarationId> = self
+            .declarations
+            .keys()
+            .filter(|id| id.file == path)
+            .cloned()
+            .collect();
+        if ids.is_empty() {
+            return Vec::new();
+        }
+        self.condensation.take();
+        self.hierarchy.take();
+        self.module_index.take();
+
+        for id in &ids {
+            // Removing a node moves the last node into its index
+            let Some(idx) = self.node_map.remove(id) else {
+                continue;
+            };
+            self.inner.remove_node(idx);
+            if let Some(moved) = self.inner.node_weight(idx) {
+                self.node_map.insert(moved.clone(), idx);
+            }
+        }
+
+        let removed: Vec<Declaration> = ids
+            .iter()
+            .filter_map(|id| self.declarations.remove(id))
+            .collect();
+        for decl in &removed {
+            if let Some(fqn) = &decl.fully_qualified_name {
+                if self.fqn_index.get(fqn) == Some(&decl.id) {
+                    self.fqn_index.remove(fqn);
+                }
+            }
+        }
+        for index in [&mut self.name_index, &mut self.extension_index] {
+            index.retain(|_, members| {
+                members.retain(|id| !ids.contains(id));
+                !members.is_empty()
+            });
+        }
+        self.children_index.retain(|parent, children| {
+            children.retain(|id| !ids.contains(id));
+            !ids.contains(parent) && !children.is_empty()
+        });
+        self.string_references.retain(|to, references| {
+            references.retain(|(from, _)| !ids.contains(from));
+            !ids.contains(to) && !references.is_empty()
+        });
+
+        removed
+    }
+}
+
+impl From<GraphFragment> for Graph {
+    fn from(fragment: GraphFragment) -> Self {
+        let mut graph = Graph::new();
+        graph.extend_from_fragment(fragment);
+        graph
+    }
+}
+
+/// What identifies a declaration across edits of its file, unlike its
+/// offset-based id: fully qualified name (or name), kind, and the position
+/// among the file's declarations sharing both, for overloads
+type Identity = (Symbol, DeclarationKind, usize);
+
+/// Identities of one file's declarations
+fn identities<'a>(
+    declarations: impl Iterator<Item = &'a Declaration>,
+) -> Vec<(DeclarationId, Identity)> {
+    let mut declarations: Vec<&Declaration> = declarations.collect();
+    declarations.sort_by_key(|decl| decl.id.start);
+    let mut seen: HashMap<(Symbol, DeclarationKind), usize> = HashMap::new();
+    declarations
+        .into_iter()
+        .map(|decl| {
+            let name = decl.fully_qualified_name.as_ref().unwrap_or(&decl.name);
+            let nth = seen.entry((name.clone(), decl.kind)).or_default();
+            let identity = (name.clone(), decl.kind, *nth);
+            *nth += 1;
+            (decl.id.clone(), identity)
+        })
+        .collect()
+}
+
+/// Whether a reference runs the code it names
+fn is_call(kind: ReferenceKind) -> bool {
+    matches!(kind, ReferenceKind::Call | ReferenceKind::Instantiation)
+}
+
+/// Class named by an aliased type: `Map<String, User>?` -> `Map`; none for
+/// function types (`(Int) -> Unit`, `suspend () -> Unit`)
+fn aliased_type_name(aliased: &str) -> Option<&str> {
+    let aliased = aliased.trim_start_matches('@').trim();
+    if aliased.starts_with('(') || aliased.starts_with("suspend") || aliased.contains("->") {
+        return None;
+    }
+    let end = aliased.find(['<', '?', ' ']).unwrap_or(aliased.len());
+    Some(&aliased[..end]).filter(|name| !name.is_empty())
+}
+
+impl Serialize for Graph {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_fragment().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Graph {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        GraphFragment::deserialize(deserializer).map(Graph::from)
+    }
 }
 
 impl Default for Graph {
@@ -259,3 +1149,225 @@ impl Default for Graph {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn decl(name: &str, start: usize) -> Declaration {
+        let path = PathBuf::from("Test.kt");
+        Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 10),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(path, start + 1, 1, start, start + 10),
+            Language::Kotlin,
+        )
+    }
+
+    fn sample_graph() -> Graph {
+        let mut graph = Graph::new();
+        let a = graph.add_declaration(decl("A", 0));
+        let b = graph.add_declaration(decl("B", 20));
+        let c = graph.add_declaration(decl("C", 40));
+        let location = Location::new(PathBuf::from("Test.kt"), 1, 1, 0, 1);
+        graph.add_reference(
+            &a,
+            &b,
+            Reference::new(ReferenceKind::Call, location.clone(), "B".to_string()),
+        );
+        graph.add_reference(
+            &b,
+            &c,
+            Reference::new(ReferenceKind::Type, location, "C".to_string()),
+        );
+        graph
+    }
+
+    #[test]
+    fn test_graph_serde_roundtrip() {
+        let graph = sample_graph();
+        let json = serde_json::to_string(&graph).unwrap();
+        let restored: Graph = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.declaration_count(), 3);
+        assert_eq!(restored.reference_count(), 2);
+        let b = restored.find_by_name("B")[0].id.clone();
+        assert_eq!(restored.get_references_to(&b).len(), 1);
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+    }
+
+    #[test]
+    fn test_callers_and_callees() {
+        let mut graph = sample_graph();
+        let id = |graph: &Graph, name: &str| graph.find_by_name(name)[0].id.clone();
+        let (a, b, c) = (id(&graph, "A"), id(&graph, "B"), id(&graph, "C"));
+        let location = Location::new(PathBuf::from("Test.kt"), 1, 1, 0, 1);
+        graph.add_reference(
+            &a,
+            &b,
+            Reference::new(ReferenceKind::Call, location.clone(), "B".to_string()),
+        );
+        graph.add_reference(
+            &a,
+            &c,
+            Reference::new(ReferenceKind::Instantiation, location, "C".to_string()),
+        );
+
+        let names = |decls: Vec<&Declaration>| -> Vec<String> {
+            let mut names: Vec<_> = decls.iter().map(|d| d.name.to_string()).collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(graph.callers_of(&b)), ["A"]);
+        assert_eq!(names(graph.callees_of(&a)), ["B", "C"]);
+        // A type reference is not a call
+        assert!(graph.callees_of(&b).is_empty());
+        assert!(graph.subtypes_of(&c).is_empty());
+    }
+
+    #[test]
+    fn test_fragment_keeps_only_internal_references() {
+        let graph = sample_graph();
+        let ids: Vec<_> = ["A", "B"]
+            .iter()
+            .map(|name| graph.find_by_name(name)[0].id.clone())
+            .collect();
+
+        let fragment = graph.fragment(&ids);
+        assert_eq!(fragment.declarations.len(), 2);
+        assert_eq!(fragment.references.len(), 1);
+        assert_eq!(fragment.references[0].reference.name, "B");
+    }
+
+    #[test]
+    fn test_remove_and_update_file() {
+        let in_file = |file: &str, name: &str, start: usize| {
+            let mut decl = decl(name, start);
+            let path = PathBuf::from(file);
+            decl.id.file = path.clone().into();
+            decl.location.file = path.into();
+            decl
+        };
+        let reference = |kind: ReferenceKind, name: &str| {
+            Reference::new(
+                kind,
+                Location::new(PathBuf::from("Screen.kt"), 1, 1, 0, 1),
+                name.to_string(),
+            )
+        };
+        let mut graph = Graph::new();
+        let screen = graph.add_declaration(in_file("Screen.kt", "Screen", 0));
+        let repository = graph.add_declaration(in_file("Repository.kt", "Repository", 0));
+        let cache = graph.add_declaration(in_file("Repository.kt", "Cache", 20));
+        let other = graph.add_declaration(in_file("Other.kt", "Other", 0));
+        graph.add_reference(
+            &screen,
+            &repository,
+            reference(ReferenceKind::Call, "Repository"),
+        );
+        graph.add_reference(&repository, &cache, reference(ReferenceKind::Type, "Cache"));
+        graph.add_reference(
+            &screen,
+            &cache,
+            reference(ReferenceKind::ReflectionString, "Cache"),
+        );
+        graph.add_reference(&other, &screen, reference(ReferenceKind::Call, "Screen"));
+        graph.set_file_scope(PathBuf::from("Repository.kt"), FileScope::default());
+
+        let consistent = |graph: &Graph| {
+            graph
+                .declaration_ids()
+                .all(|id| &graph.inner()[graph.node_index(id).unwrap()] == id)
+        };
+
+        // Replace the file: Cache is gone, Store is new, and the reference
+        // from Screen to the unchanged Repository survives
+        let mut updated = Graph::from(graph.to_fragment());
+        let store = in_file("Repository.kt", "Store", 40);
+        let edge = ReferenceEdge {
+            from: repository.clone(),
+            to: store.id.clone(),
+            reference: reference(ReferenceKind::Type, "Store"),
+        };
+        updated.update_file(
+            Path::new("Repository.kt"),
+            vec![in_file("Repository.kt", "Repository", 0), store],
+            vec![edge],
+        );
+        assert!(consistent(&updated));
+        assert_eq!(updated.declaration_count(), 4);
+        assert!(updated.find_by_name("Cache").is_empty());
+        assert!(updated.string_references_to(&cache).is_empty());
+        assert_eq!(updated.get_references_to(&repository).len(), 1);
+        assert_eq!(updated.get_references_from(&repository)[0].0.name, "Store");
+        assert_eq!(updated.reference_count(), 3);
+
+        // Remove the file altogether
+        let removed = graph.remove_file(Path::new("Repository.kt"));
+        assert_eq!(removed.len(), 2);
+        assert!(consistent(&graph));
+        assert_eq!(graph.declaration_count(), 2);
+        assert_eq!(graph.reference_count(), 1);
+        assert!(graph.find_by_name("Repository").is_empty());
+        assert!(graph.get_references_from(&screen).is_empty());
+        assert_eq!(graph.get_references_to(&screen)[0].0.name, "Other");
+        assert!(graph.file_scope(Path::new("Repository.kt")).is_none());
+        assert!(graph.remove_file(Path::new("Repository.kt")).is_empty());
+    }
+
+    #[test]
+    fn test_update_file_follows_shifted_declarations() {
+        let member = |name: &str, start: usize| {
+            let path = PathBuf::from("Repository.kt");
+            let mut decl = Declaration::new(
+                DeclarationId::new(path.clone(), start, start + 10),
+                name.to_string(),
+                DeclarationKind::Method,
+                Location::new(path, 1, 1, start, start + 10),
+                Language::Kotlin,
+            );
+            decl.fully_qualified_name = Some(format!("com.app.Repository.{}", name).into());
+            decl
+        };
+        let call = |name: &str| {
+            Reference::new(
+                ReferenceKind::Call,
+                Location::new(PathBuf::from("Test.kt"), 1, 1, 0, 1),
+                name.to_string(),
+            )
+        };
+        let mut graph = Graph::new();
+        let screen = graph.add_declaration(decl("Screen", 0));
+        graph.add_declaration(member("load", 20));
+        let load_by_id = graph.add_declaration(member("load", 40));
+        let clear = graph.add_declaration(member("clear", 60));
+        graph.add_reference(&screen, &load_by_id, call("load"));
+        graph.add_reference(&screen, &clear, call("clear"));
+
+        // A comment added above every declaration shifts their offsets; the
+        // calls follow the second `load` overload and `clear`, which is now
+        // declared before them
+        graph.update_file(
+            Path::new("Repository.kt"),
+            vec![member("clear", 50), member("load", 70), member("load", 90)],
+            Vec::new(),
+        );
+        let callees = graph.get_references_from(&screen);
+        let mut callees: Vec<(&str, usize)> = callees
+            .iter()
+            .map(|(to, _)| (to.name.as_str(), to.id.start))
+            .collect();
+        callees.sort_unstable();
+        assert_eq!(callees, [("clear", 50), ("load", 90)]);
+
+        // A removed declaration loses its references
+        graph.update_file(
+            Path::new("Repository.kt"),
+            vec![member("load", 70)],
+            Vec::new(),
+        );
+        assert!(graph.get_references_from(&screen).is_empty());
+    }
+}
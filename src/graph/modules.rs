@@ -0,0 +1,203 @@
+// Module partitions
+//
+// Declarations carry the Gradle module declaring them. The module index
+// groups them by module and records, for each declaration, the other modules
+// referencing it: the references crossing module boundaries. A module's
+// partition is the part of the graph inside the module, so an analysis run on
+// it sees what the module uses by itself, without its dependents.
+
+use super::{DeclarationId, Graph};
+use petgraph::visit::EdgeRef;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Declarations of each module and the references between modules
+#[derive(Debug, Clone, Default)]
+pub struct ModuleIndex {
+    /// Declarations of each module, ordered by file and position
+    declarations: BTreeMap<String, Vec<DeclarationId>>,
+    /// Modules other than its own referencing each declaration
+    referencing_modules: HashMap<DeclarationId, BTreeSet<String>>,
+    /// Number of references from a declaration of one module to another's
+    cross_module_references: usize,
+}
+
+impl ModuleIndex {
+    /// Index the modules of every declaration in `graph`; declarations
+    /// without a module are left out
+    pub fn new(graph: &Graph) -> Self {
+        let mut index = Self::default();
+        for decl in graph.declarations() {
+            if let Some(module) = &decl.module {
+                index
+                    .declarations
+                    .entry(module.to_string())
+                    .or_default()
+                    .push(decl.id.clone());
+            }
+        }
+        for ids in index.declarations.values_mut() {
+            ids.sort_by(|a, b| (&a.file, a.start).cmp(&(&b.file, b.start)));
+        }
+
+        let module = |id: &DeclarationId| graph.get_declaration(id)?.module.as_deref();
+        for edge in graph.inner.edge_references() {
+            let (from, to) = (&graph.inner[edge.source()], &graph.inner[edge.target()]);
+            let (Some(from_module), Some(to_module)) = (module(from), module(to)) else {
+                continue;
+            };
+            if from_module != to_module {
+                index.cross_module_references += 1;
+                index
+                    .referencing_modules
+                    .entry(to.clone())
+                    .or_default()
+                    .insert(from_module.to_string());
+            }
+        }
+        index
+    }
+
+    /// Modules declaring something, in order
+    pub fn modules(&self) -> impl Iterator<Item = &str> {
+        self.declarations.keys().map(String::as_str)
+    }
+
+    /// Declarations of a module
+    pub fn declarations_in(&self, module: &str) -> &[DeclarationId] {
+        self.declarations.get(module).map_or(&[], Vec::as_slice)
+    }
+
+    /// Modules other than its own referencing a declaration, in order
+    pub fn referencing_modules(&self, id: &DeclarationId) -> impl Iterator<Item = &str> {
+        self.referencing_modules
+            .get(id)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Whether a declaration of another module references `id`
+    pub fn is_referenced_from_other_modules(&self, id: &DeclarationId) -> bool {
+        self.referencing_modules.contains_key(id)
+    }
+
+    /// Number of references from a declaration of one module to another's
+    pub fn cross_module_references(&self) -> usize {
+        self.cross_module_references
+    }
+}
+
+impl Graph {
+    /// The part of the graph inside a module: its declarations, the
+    /// references between them and the scopes of its files
+    ///
+    /// References from and to other modules are left out, so what the
+    /// partition reaches is what the module reaches by itself.
+    pub fn module_partition(&self, module: &str) -> Graph {
+        let ids = self.module_index().declarations_in(module);
+        let members: HashSet<&DeclarationId> = ids.iter().collect();
+
+        let mut partition = Graph::new();
+        for id in ids {
+            if let Some(decl) = self.declarations.get(id) {
+                partition.add_declaration(decl.clone());
+            }
+        }
+        let files: HashSet<_> = ids.iter().map(|id| &id.file).collect();
+        for file in files {
+            if let Some(scope) = self.file_scopes.get(file.as_path()) {
+                partition.set_file_scope(file.to_path_buf(), scope.clone());
+            }
+        }
+        for edge in self.inner.edge_references() {
+            let (from, to) = (&self.inner[edge.source()], &self.inner[edge.target()]);
+            if members.contains(from) && members.contains(to) {
+                partition.add_reference(from, to, edge.weight().clone());
+            }
+        }
+        for (to, references) in &self.string_references {
+            if !members.contains(to) {
+                continue;
+            }
+            for (from, reference) in references {
+                if members.contains(from) {
+                    partition.add_reference(from, to, reference.clone());
+                }
+            }
+        }
+        partition
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn test_module_partitions() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, module: &str, contents: &str| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, FileType::Kotlin).with_module(Some(module.to_string()))
+        };
+        let files = [
+            write(
+                "Screen.kt",
+                ":app",
+                r#"package com.app
+
+import com.app.core.Formatter
+
+class Screen {
+    fun show() = Formatter().format()
+}
+"#,
+            ),
+            write(
+                "Formatter.kt",
+                ":core",
+                r#"package com.app.core
+
+class Formatter {
+    fun format() = trim()
+    private fun trim() = Unit
+}
+"#,
+            ),
+        ];
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+        let id = |name: &str| graph.find_by_name(name)[0].id.clone();
+        assert_eq!(
+            graph
+                .get_declaration(&id("format"))
+                .unwrap()
+                .module
+                .as_deref(),
+            Some(":core")
+        );
+
+        let index = graph.module_index();
+        assert_eq!(index.modules().collect::<Vec<_>>(), [":app", ":core"]);
+        assert_eq!(index.declarations_in(":core").len(), 3);
+        assert_eq!(
+            index.referencing_modules(&id("format")).collect::<Vec<_>>(),
+            [":app"]
+        );
+        assert!(index.is_referenced_from_other_modules(&id("Formatter")));
+        assert!(!index.is_referenced_from_other_modules(&id("trim")));
+        assert!(!index.is_referenced_from_other_modules(&id("show")));
+        assert_eq!(index.cross_module_references(), 2);
+
+        let core = graph.module_partition(":core");
+        assert_eq!(core.declaration_count(), 3);
+        assert_eq!(core.get_references_to(&id("trim")).len(), 1);
+        assert!(core.get_references_to(&id("format")).is_empty());
+        assert!(graph.module_partition(":missing").declaration_count() == 0);
+    }
+}
@@ -0,0 +1,184 @@
+// Kotlin Multiplatform expect/actual linking
+//
+// Common code declares `expect` declarations and each platform source set
+// provides the matching `actual` one. Callers only ever name one of them
+// (common code resolves to the `expect`, platform code to its `actual`),
+// yet the build needs every side. Each pair shares its fully qualified
+// name, so they are grouped by it and every reference to one is copied to
+// the others.
+
+use super::{Declaration, DeclarationId, Graph, Reference};
+use std::collections::HashMap;
+
+impl Graph {
+    /// Make references to an `expect` declaration or any of its `actual`s
+    /// count as references to all of them
+    pub fn link_expect_actual(&mut self) {
+        let mut copies: Vec<(DeclarationId, DeclarationId, Reference)> = Vec::new();
+        for group in self.expect_actual_groups() {
+            for member in &group {
+                for (from, reference) in self.get_references_to(member) {
+                    if group.contains(&from.id) {
+                        continue;
+                    }
+                    for other in group.iter().filter(|other| *other != member) {
+                        copies.push((from.id.clone(), other.clone(), reference.clone()));
+                    }
+                }
+            }
+        }
+        for (from, to, reference) in copies {
+            self.add_reference(&from, &to, reference);
+        }
+    }
+
+    /// The other sides of an `expect`/`actual` declaration
+    pub fn platform_counterparts(&self, id: &DeclarationId) -> Vec<&Declaration> {
+        self.expect_actual_groups()
+            .into_iter()
+            .find(|group| group.contains(id))
+            .into_iter()
+            .flatten()
+            .filter(|other| other != id)
+            .filter_map(|other| self.declarations.get(&other))
+            .collect()
+    }
+
+    /// `expect` declarations (with the members of `expect` classes) grouped
+    /// with their `actual`s, keeping only groups with both sides
+    fn expect_actual_groups(&self) -> Vec<Vec<DeclarationId>> {
+        let mut groups: HashMap<String, (Vec<DeclarationId>, Vec<DeclarationId>)> = HashMap::new();
+        for decl in self.declarations.values() {
+            let side = if decl.is_actual() {
+                &mut groups.entry(self.platform_key(decl)).or_default().1
+            } else if self.in_expect(decl) {
+                &mut groups.entry(self.platform_key(decl)).or_default().0
+            } else {
+                continue;
+            };
+            side.push(decl.id.clone());
+        }
+
+        let mut groups: Vec<Vec<DeclarationId>> = groups
+            .into_values()
+            .filter(|(expects, actuals)| !expects.is_empty() && !actuals.is_empty())
+            .map(|(mut expects, actuals)| {
+                expects.extend(actuals);
+                expects
+            })
+            .collect();
+        // Stable order, so copied references are added deterministically
+        for group in &mut groups {
+            group.sort_by(|a, b| (&a.file, a.start).cmp(&(&b.file, b.start)));
+        }
+        groups.sort_by(|a, b| (&a[0].file, a[0].start).cmp(&(&b[0].file, b[0].start)));
+        groups
+    }
+
+    /// Whether a declaration is `expect`, itself or through an enclosing
+    /// `expect class`
+    fn in_expect(&self, decl: &Declaration) -> bool {
+        let mut current = Some(decl);
+        while let Some(decl) = current {
+            if decl.is_expect() {
+                return true;
+            }
+            current = decl.parent.as_ref().and_then(|p| self.declarations.get(p));
+        }
+        false
+    }
+
+    /// Name shared by both sides: the fully qualified name of the top-level
+    /// declaration followed by the member names
+    fn platform_key(&self, decl: &Declaration) -> String {
+        match decl.parent.as_ref().and_then(|p| self.declarations.get(p)) {
+            Some(parent) => format!("{}.{}", self.platform_key(parent), decl.name),
+            None => decl
+                .fully_qualified_name
+                .as_deref()
+                .unwrap_or(&decl.name)
+                .to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_expect_actual_references() {
+        let temp = tempfile::TempDir::new().unwrap();
+        // Platform sources come first, so imports resolve to the `expect`s
+        let files = [
+            (
+                "src/androidMain/kotlin/com/app/Platform.android.kt",
+                r#"package com.app
+
+actual class Platform actual constructor() {
+    actual val name: String = "Android"
+}
+
+actual fun randomUuid(): String = java.util.UUID.randomUUID().toString()
+
+actual fun currentTimeMillis(): Long = System.currentTimeMillis()
+"#,
+            ),
+            (
+                "src/commonMain/kotlin/com/app/Platform.kt",
+                r#"package com.app
+
+expect class Platform() {
+    val name: String
+}
+
+expect fun randomUuid(): String
+
+expect fun currentTimeMillis(): Long
+"#,
+            ),
+            (
+                "src/commonMain/kotlin/com/app/ui/Greeting.kt",
+                r#"package com.app.ui
+
+import com.app.Platform
+import com.app.randomUuid
+
+class Greeting {
+    fun greet(): String = "Hello, ${Platform().name} ${randomUuid()}"
+}
+"#,
+            ),
+        ];
+
+        let mut builder = GraphBuilder::new();
+        for (rel, contents) in files {
+            let path: PathBuf = temp.path().join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, contents).unwrap();
+            builder
+                .process_file(&SourceFile::new(path, FileType::Kotlin))
+                .unwrap();
+        }
+        let graph = builder.build();
+
+        let actual = |name: &str| {
+            graph
+                .find_by_name(name)
+                .into_iter()
+                .find(|d| d.is_actual())
+                .unwrap()
+        };
+        let referenced = |name: &str| !graph.get_references_to(&actual(name).id).is_empty();
+        assert!(referenced("Platform"));
+        assert!(referenced("name"));
+        assert!(referenced("randomUuid"));
+        assert!(!referenced("currentTimeMillis"));
+
+        let counterparts = graph.platform_counterparts(&actual("currentTimeMillis").id);
+        assert_eq!(counterparts.len(), 1);
+        assert!(counterparts[0].is_expect());
+    }
+}
@@ -1,32 +1,81 @@
 // Parallel graph builder using rayon
 
-use super::{Declaration, DeclarationId, Graph, Location, Reference, ReferenceKind};
+use super::builder::UnresolvedRef;
+use super::cache::{CachedFile, GraphCache};
+use super::{
+    CallArguments, CallReceiver, Declaration, DeclarationId, DeclarationKind, FileScope, Graph,
+    Language, Location, Reference, ReferenceConfidence, ReferenceKind, Symbol,
+};
+use crate::cancel::CancellationToken;
 use crate::discovery::{FileType, SourceFile};
-use crate::parser::{JavaParser, KotlinParser, Parser as SourceParser};
+use crate::parser::xml::{LayoutParser, XmlParseResult};
+use crate::parser::{JavaParser, KotlinParser, Parser as SourceParser, TreeCache};
 use miette::Result;
 use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
 use tracing::{debug, info};
 
 /// Parsed file result
 struct ParsedFile {
     declarations: Vec<Declaration>,
     unresolved_refs: Vec<UnresolvedRef>,
+    /// Layout (path, length), whose references are added last
+    layout: Option<(PathBuf, usize, XmlParseResult)>,
+    /// Package and imports of a source file
+    scope: Option<(PathBuf, FileScope)>,
 }
 
-struct UnresolvedRef {
-    from: DeclarationId,
-    name: String,
-    qualified_name: Option<String>,
-    kind: ReferenceKind,
-    imports: Vec<String>,
+impl ParsedFile {
+    /// A source file as an earlier build derived it
+    fn from_cache(file: &SourceFile, mut cached: CachedFile) -> Self {
+        // Modules may have been moved or renamed since
+        for decl in &mut cached.declarations {
+            decl.module = file.module.as_deref().map(Symbol::new);
+        }
+        Self {
+            declarations: cached.declarations,
+            unresolved_refs: cached.unresolved_refs,
+            layout: None,
+            scope: Some((file.path.clone(), cached.scope)),
+        }
+    }
 }
 
 /// Parallel graph builder for faster processing
-pub struct ParallelGraphBuilder;
+pub struct ParallelGraphBuilder {
+    cancel: CancellationToken,
+    trees: Option<Arc<TreeCache>>,
+    graph_cache: Option<Arc<GraphCache>>,
+}
 
 impl ParallelGraphBuilder {
     pub fn new() -> Self {
-        Self
+        Self {
+            cancel: CancellationToken::new(),
+            trees: None,
+            graph_cache: None,
+        }
+    }
+
+    /// Skip the remaining files and fail once `cancel` fires
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Re-parse files incrementally against the trees kept in `trees`
+    pub fn with_tree_cache(mut self, trees: Arc<TreeCache>) -> Self {
+        self.trees = Some(trees);
+        self
+    }
+
+    /// Take the declarations and references of source files whose contents
+    /// are unchanged from `cache`, and record those of the files parsed
+    pub fn with_graph_cache(mut self, cache: Arc<GraphCache>) -> Self {
+        self.graph_cache = Some(cache);
+        self
     }
 
     /// Build graph from source files using parallel processing
@@ -34,18 +83,26 @@ impl ParallelGraphBuilder {
         info!("Parsing {} files in parallel...", files.len());
 
         // Parse files in parallel
-        let results: Vec<Result<ParsedFile>> =
-            files.par_iter().map(|file| self.parse_file(file)).collect();
+        let results: Vec<Result<ParsedFile>> = files
+            .par_iter()
+            .filter(|_| !self.cancel.is_cancelled())
+            .map(|file| self.parse_file(file))
+            .collect();
+        self.cancel.check()?;
 
         // Collect results
         let mut all_declarations = Vec::new();
         let mut all_unresolved = Vec::new();
+        let mut layouts = Vec::new();
+        let mut scopes = Vec::new();
 
         for result in results {
             match result {
                 Ok(parsed) => {
                     all_declarations.extend(parsed.declarations);
                     all_unresolved.extend(parsed.unresolved_refs);
+                    layouts.extend(parsed.layout);
+                    scopes.extend(parsed.scope);
                 }
                 Err(e) => {
                     debug!("Parse error (continuing): {}", e);
@@ -64,58 +121,224 @@ impl ParallelGraphBuilder {
         for decl in all_declarations {
             graph.add_declaration(decl);
         }
+        for (path, scope) in scopes {
+            graph.set_file_scope(path, scope);
+        }
 
         // Resolve references
         info!("Resolving references...");
         self.resolve_references(&mut graph, all_unresolved);
+        for (path, len, layout) in &layouts {
+            graph.add_layout_references(path, *len, layout);
+        }
+        graph.link_expect_actual();
+        graph.link_class_delegation();
+        // Resolved here so analyzers sharing the graph find it ready
+        graph.hierarchy();
 
         Ok(graph)
     }
 
+    /// Bring a graph built from the same project up to date with edited
+    /// and deleted files, parsing and resolving only the edited ones
+    ///
+    /// References from the other files are kept (see
+    /// [`Graph::update_file`]) but not resolved again, so an edit that
+    /// declares something new, and changes to what the builder links across
+    /// files (layouts, generated code, `expect` / `actual`, class
+    /// delegation), need a full build: `graph` is then left as it was and
+    /// `false` is returned.
+    pub fn update_graph(
+        &self,
+        graph: &mut Graph,
+        changed: &[SourceFile],
+        removed: &[PathBuf],
+    ) -> Result<bool> {
+        if changed
+            .iter()
+            .any(|file| !file.file_type.is_source() || file.generated)
+            || removed
+                .iter()
+                .any(|path| !FileType::from_path(path).is_some_and(|t| t.is_source()))
+            || graph.declarations().any(is_linked)
+        {
+            return Ok(false);
+        }
+
+        let parsed = changed
+            .par_iter()
+            .map(|file| Ok((file, self.parse_file(file)?)))
+            .collect::<Result<Vec<_>>>()?;
+        self.cancel.check()?;
+        for (file, parsed) in &parsed {
+            let declared: HashSet<_> = graph
+                .declarations()
+                .filter(|decl| decl.id.file == file.path)
+                .map(declared_as)
+                .collect();
+            let declares_new = parsed
+                .declarations
+                .iter()
+                .any(|decl| is_linked(decl) || !declared.contains(&declared_as(decl)));
+            if declares_new {
+                debug!("{} declares something new, rebuilding", file.path.display());
+                return Ok(false);
+            }
+        }
+
+        info!(
+            "Updating the graph for {} changed and {} removed files",
+            changed.len(),
+            removed.len()
+        );
+        for path in removed {
+            graph.remove_file(path);
+        }
+        let mut unresolved = Vec::new();
+        for (file, parsed) in parsed {
+            graph.update_file(&file.path, parsed.declarations, Vec::new());
+            if let Some((path, scope)) = parsed.scope {
+                graph.set_file_scope(path, scope);
+            }
+            unresolved.extend(parsed.unresolved_refs);
+        }
+        self.resolve_references(graph, unresolved);
+        graph.hierarchy();
+        Ok(true)
+    }
+
     /// Parse a single file
     fn parse_file(&self, file: &SourceFile) -> Result<ParsedFile> {
         let contents = file.read_contents()?;
 
-        match file.file_type {
-            FileType::Kotlin => self.parse_kotlin_file(&file.path, &contents),
-            FileType::Java => self.parse_java_file(&file.path, &contents),
-            _ => Ok(ParsedFile {
+        if file.file_type.is_source() {
+            if let Some(cached) = self
+                .graph_cache
+                .as_ref()
+                .and_then(|cache| cache.get(&file.path, &contents, file.generated))
+            {
+                debug!("Using cached graph of {}", file.path.display());
+                return Ok(ParsedFile::from_cache(file, cached));
+            }
+        }
+
+        let parsed = match file.file_type {
+            FileType::Kotlin => self.parse_kotlin_file(file, &contents)?,
+            FileType::Java => self.parse_java_file(file, &contents)?,
+            FileType::XmlLayout => ParsedFile {
+                declarations: Vec::new(),
+                unresolved_refs: Vec::new(),
+                layout: Some((
+                    file.path.clone(),
+                    contents.len(),
+                    LayoutParser::new().parse(&file.path, &contents)?,
+                )),
+                scope: None,
+            },
+            _ => ParsedFile {
                 declarations: Vec::new(),
                 unresolved_refs: Vec::new(),
-            }),
+                layout: None,
+                scope: None,
+            },
+        };
+
+        if let (Some(cache), Some((_, scope))) = (&self.graph_cache, &parsed.scope) {
+            cache.insert(
+                &file.path,
+                &contents,
+                file.generated,
+                &parsed.declarations,
+                &parsed.unresolved_refs,
+                scope,
+            );
         }
+        Ok(parsed)
     }
 
-    fn parse_kotlin_file(&self, path: &std::path::Path, contents: &str) -> Result<ParsedFile> {
+    fn parse_kotlin_file(&self, file: &SourceFile, contents: &str) -> Result<ParsedFile> {
+        let path = file.path.as_path();
         let parser = KotlinParser::new();
-        let result = parser.parse(path, contents)?;
+        let mut result = match &self.trees {
+            Some(trees) => parser.parse_incremental(path, contents, trees)?,
+            None => parser.parse(path, contents)?,
+        };
+        // Generated code only contributes its references
+        if file.generated {
+            result.declarations = vec![Declaration::generated_file(
+                path,
+                contents.len(),
+                Language::Kotlin,
+            )];
+        }
+        for decl in &mut result.declarations {
+            decl.source_set = file.source_set;
+            decl.module = file.module.as_deref().map(Symbol::new);
+        }
 
+        let scope = FileScope::new(result.package.clone(), result.imports.clone());
         let declarations = result.declarations.clone();
-        let unresolved = self.extract_unresolved(&declarations, result.references);
+        let unresolved = self.extract_unresolved(
+            &declarations,
+            result.package,
+            result.references,
+            result.call_arguments,
+            result.call_receivers,
+        );
 
         Ok(ParsedFile {
             declarations: result.declarations,
             unresolved_refs: unresolved,
+            layout: None,
+            scope: Some((path.to_path_buf(), scope)),
         })
     }
 
-    fn parse_java_file(&self, path: &std::path::Path, contents: &str) -> Result<ParsedFile> {
+    fn parse_java_file(&self, file: &SourceFile, contents: &str) -> Result<ParsedFile> {
+        let path = file.path.as_path();
         let parser = JavaParser::new();
-        let result = parser.parse(path, contents)?;
+        let mut result = match &self.trees {
+            Some(trees) => parser.parse_incremental(path, contents, trees)?,
+            None => parser.parse(path, contents)?,
+        };
+        // Generated code only contributes its references
+        if file.generated {
+            result.declarations = vec![Declaration::generated_file(
+                path,
+                contents.len(),
+                Language::Java,
+            )];
+        }
+        for decl in &mut result.declarations {
+            decl.source_set = file.source_set;
+            decl.module = file.module.as_deref().map(Symbol::new);
+        }
 
+        let scope = FileScope::new(result.package.clone(), result.imports.clone());
         let declarations = result.declarations.clone();
-        let unresolved = self.extract_unresolved(&declarations, result.references);
+        let unresolved = self.extract_unresolved(
+            &declarations,
+            result.package,
+            result.references,
+            result.call_arguments,
+            result.call_receivers,
+        );
 
         Ok(ParsedFile {
             declarations: result.declarations,
             unresolved_refs: unresolved,
+            layout: None,
+            scope: Some((path.to_path_buf(), scope)),
         })
     }
 
     fn extract_unresolved(
         &self,
         declarations: &[Declaration],
+        package: Option<String>,
         references: Vec<crate::graph::UnresolvedReference>,
+        mut call_arguments: HashMap<usize, CallArguments>,
+        mut call_receivers: HashMap<usize, CallReceiver>,
     ) -> Vec<UnresolvedRef> {
         let mut result = Vec::new();
 
@@ -138,13 +361,38 @@ impl ParallelGraphBuilder {
                     .find(|d| d.location.file == unresolved.location.file)
             });
 
+            // A parameter's type and annotations are used whenever its
+            // function is
+            let from_decl = from_decl.map(|d| match &d.parent {
+                Some(parent)
+                    if d.kind == DeclarationKind::Parameter && unresolved.kind.is_signature() =>
+                {
+                    declarations.iter().find(|p| &p.id == parent).unwrap_or(d)
+                }
+                _ => d,
+            });
+
             if let Some(from_decl) = from_decl {
+                let (arguments, receiver) = match unresolved.kind {
+                    ReferenceKind::Call => (
+                        call_arguments.remove(&ref_byte),
+                        call_receivers.remove(&ref_byte),
+                    ),
+                    // `Foo.CONSTANT` is qualified like `Foo.create()`
+                    ReferenceKind::Read | ReferenceKind::Write => {
+                        (None, call_receivers.remove(&ref_byte))
+                    }
+                    _ => (None, None),
+                };
                 result.push(UnresolvedRef {
                     from: from_decl.id.clone(),
                     name: unresolved.name,
                     qualified_name: unresolved.qualified_name,
                     kind: unresolved.kind,
+                    package: package.clone(),
                     imports: unresolved.imports,
+                    arguments,
+                    receiver,
                 });
             }
         }
@@ -153,8 +401,13 @@ impl ParallelGraphBuilder {
     }
 
     fn resolve_references(&self, graph: &mut Graph, unresolved: Vec<UnresolvedRef>) {
+        let type_aliases = graph.type_alias_targets();
         for unresolved in unresolved {
-            let resolved_ids = self.resolve_reference(graph, &unresolved);
+            let (mut resolved_ids, confidence) = self.resolve_reference(graph, &unresolved);
+            // A reference through a typealias also uses what it aliases
+            for id in resolved_ids.clone() {
+                resolved_ids.extend(type_aliases.get(&id).into_iter().flatten().cloned());
+            }
 
             for to_id in resolved_ids {
                 // Skip self-references
@@ -172,17 +425,46 @@ impl ParallelGraphBuilder {
                         unresolved.from.end,
                     ),
                     unresolved.name.clone(),
-                );
+                )
+                .with_arguments(unresolved.arguments.clone())
+                .with_confidence(confidence);
                 graph.add_reference(&unresolved.from, &to_id, reference);
             }
         }
     }
 
-    fn resolve_reference(&self, graph: &Graph, unresolved: &UnresolvedRef) -> Vec<DeclarationId> {
+    fn resolve_reference(
+        &self,
+        graph: &Graph,
+        unresolved: &UnresolvedRef,
+    ) -> (Vec<DeclarationId>, ReferenceConfidence) {
+        // Extensions and qualified members are narrowed by the call's
+        // receiver, overloads by its arguments
+        let narrow = |found: Vec<&Declaration>| -> Vec<DeclarationId> {
+            let found =
+                graph.narrow_to_receiver(found, unresolved.receiver.as_ref(), &unresolved.from);
+            let found = graph.narrow_to_qualifier(found, unresolved.receiver.as_ref());
+            graph
+                .narrow_to_arity(found, unresolved.arguments.as_ref())
+                .iter()
+                .map(|d| d.id.clone())
+                .collect()
+        };
+
+        // A string names a declaration exactly or not at all
+        if unresolved.kind == ReferenceKind::ReflectionString {
+            let found = graph
+                .find_named_in_string(&unresolved.name, unresolved.qualified_name.as_deref())
+                .iter()
+                .map(|d| d.id.clone())
+                .collect();
+            return (found, ReferenceConfidence::Heuristic);
+        }
+
         // Try fully qualified name first
         if let Some(fqn) = &unresolved.qualified_name {
             if let Some(decl) = graph.find_by_fqn(fqn) {
-                return vec![decl.id.clone()];
+                return (vec![decl.id.clone()], ReferenceConfidence::Exact);
             }
         }
 
@@ -192,30 +474,44 @@ impl ParallelGraphBuilder {
                 let package = &import[..import.len() - 2];
                 let fqn = format!("{}.{}", package, unresolved.name);
                 if let Some(decl) = graph.find_by_fqn(&fqn) {
-                    return vec![decl.id.clone()];
+                    return (narrow(vec![decl]), ReferenceConfidence::Exact);
                 }
             } else if import.ends_with(&format!(".{}", unresolved.name)) {
                 if let Some(decl) = graph.find_by_fqn(import) {
-                    return vec![decl.id.clone()];
+                    return (narrow(vec![decl]), ReferenceConfidence::Exact);
+                }
+                let members = graph.find_qualified_member(import);
+                if unresolved.receiver.is_none() && !members.is_empty() {
+                    return (narrow(members), ReferenceConfidence::Exact);
                 }
             } else if let Some(alias_start) = import.find(" as ") {
                 let alias = &import[alias_start + 4..];
                 if alias == unresolved.name {
                     let original = &import[..alias_start];
                     if let Some(decl) = graph.find_by_fqn(original) {
-                        return vec![decl.id.clone()];
+                        return (narrow(vec![decl]), ReferenceConfidence::Exact);
+                    }
+                    let members = graph.find_qualified_member(original);
+                    if !members.is_empty() {
+                        return (narrow(members), ReferenceConfidence::Exact);
                     }
                 }
             }
         }
 
-        // Try simple name match
-        let candidates = graph.find_by_name(&unresolved.name);
-        if !candidates.is_empty() {
-            return candidates.iter().map(|c| c.id.clone()).collect();
-        }
-
-        Vec::new()
+        // Try simple name match, told apart by the file's package and imports
+        let found = narrow(graph.narrow_to_imports(
+            graph.find_in_scope(&unresolved.name, &unresolved.from),
+            &unresolved.name,
+            unresolved.package.as_deref(),
+            &unresolved.imports,
+        ));
+        let confidence = if found.len() > 1 {
+            ReferenceConfidence::NameMatch
+        } else {
+            ReferenceConfidence::Exact
+        };
+        (found, confidence)
     }
 }
 
@@ -224,3 +520,15 @@ impl Default for ParallelGraphBuilder {
         Self::new()
     }
 }
+
+/// Whether the builder links a declaration to others after resolving
+/// references: `expect` / `actual` pairs and classes delegating an interface
+fn is_linked(decl: &Declaration) -> bool {
+    decl.is_actual() || decl.is_expect() || !decl.delegated_types.is_empty()
+}
+
+/// What other files can refer to a declaration by
+fn declared_as(decl: &Declaration) -> (Symbol, DeclarationKind) {
+    let name = decl.fully_qualified_name.as_ref().unwrap_or(&decl.name);
+    (name.clone(), decl.kind)
+}
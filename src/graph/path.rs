@@ -0,0 +1,197 @@
+// Reference paths
+//
+// A declaration is reachable when a chain of references leads to it from an
+// entry point, with two implicit links reachability also follows: the
+// members of a reachable type are reachable, and so is the type declaring a
+// reachable member. The shortest such chain explains why a declaration is
+// kept alive.
+
+use super::{Declaration, DeclarationId, Graph, ReferenceKind};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// How a step of a reference path is reached from the step before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathLink {
+    /// The previous declaration references this one
+    Reference(ReferenceKind),
+    /// This declaration is a member of the previous one
+    Member,
+    /// This declaration declares the previous one
+    Parent,
+}
+
+/// A declaration on a reference path
+#[derive(Debug, Clone)]
+pub struct PathStep<'g> {
+    pub declaration: &'g Declaration,
+    /// How the declaration is reached; `None` for the root the path starts at
+    pub link: Option<PathLink>,
+}
+
+impl Graph {
+    /// Declarations a symbol written by a user names: a fully qualified
+    /// name (`com.app.Foo`), a member path (`Foo.bar`,
+    /// `com.app.Foo.Companion.create`) or a simple name
+    pub fn find_symbol(&self, symbol: &str) -> Vec<&Declaration> {
+        if let Some(decl) = self.find_by_fqn(symbol) {
+            return vec![decl];
+        }
+        let members = self.find_qualified_member(symbol);
+        if !members.is_empty() {
+            return members;
+        }
+        self.find_by_name(symbol)
+    }
+
+    /// Shortest path from one of `roots` to `target`, following references,
+    /// type members and declaring types, or `None` when `target` cannot be
+    /// reached
+    ///
+    /// Roots are tried in a stable order, so ties between equally short
+    /// paths always resolve the same way.
+    pub fn shortest_path(
+        &self,
+        roots: &HashSet<DeclarationId>,
+        target: &DeclarationId,
+    ) -> Option<Vec<PathStep<'_>>> {
+        let mut starts: Vec<&Declaration> = roots
+            .iter()
+            .filter_map(|id| self.get_declaration(id))
+            .collect();
+        starts.sort_by(|a, b| {
+            (&a.location.file, a.location.line, &a.name).cmp(&(
+                &b.location.file,
+                b.location.line,
+                &b.name,
+            ))
+        });
+
+        let mut previous: HashMap<&DeclarationId, Option<(&DeclarationId, PathLink)>> =
+            HashMap::new();
+        let mut queue = VecDeque::new();
+        for start in starts {
+            previous.insert(&start.id, None);
+            queue.push_back(&start.id);
+        }
+
+        while let Some(id) = queue.pop_front() {
+            if id == target {
+                return Some(self.collect_path(id, &previous));
+            }
+            let Some(decl) = self.get_declaration(id) else {
+                continue;
+            };
+
+            let mut next: Vec<(&DeclarationId, PathLink)> = self
+                .get_references_from(id)
+                .into_iter()
+                .map(|(to, reference)| (&to.id, PathLink::Reference(reference.kind)))
+                .collect();
+            next.extend(
+                self.get_children(id)
+                    .into_iter()
+                    .map(|child| (child, PathLink::Member)),
+            );
+            next.extend(
+                decl.parent
+                    .as_ref()
+                    .map(|parent| (parent, PathLink::Parent)),
+            );
+
+            for (to, link) in next {
+                if !previous.contains_key(to) && self.get_declaration(to).is_some() {
+                    previous.insert(to, Some((id, link)));
+                    queue.push_back(to);
+                }
+            }
+        }
+        None
+    }
+
+    /// Walk the breadth-first search predecessors back from `end`
+    fn collect_path<'g>(
+        &'g self,
+        end: &'g DeclarationId,
+        previous: &HashMap<&'g DeclarationId, Option<(&'g DeclarationId, PathLink)>>,
+    ) -> Vec<PathStep<'g>> {
+        let mut steps = Vec::new();
+        let mut current = Some(end);
+        while let Some(id) = current {
+            let link = previous.get(id).copied().flatten();
+            if let Some(declaration) = self.get_declaration(id) {
+                steps.push(PathStep {
+                    declaration,
+                    link: link.map(|(_, link)| link),
+                });
+            }
+            current = link.map(|(from, _)| from);
+        }
+        steps.reverse();
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn test_shortest_path() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("App.kt");
+        std::fs::write(
+            &path,
+            r#"package com.app
+
+class MainActivity {
+    fun onCreate() {
+        Repository().load()
+    }
+}
+
+class Repository {
+    fun load() = parse()
+
+    fun parse() = Unit
+
+    fun clear() = Unit
+}
+
+class Orphan {
+    fun run() = Unit
+}
+"#,
+        )
+        .unwrap();
+
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(path, FileType::Kotlin))
+            .unwrap();
+        let graph = builder.build();
+
+        let main = graph.find_symbol("com.app.MainActivity");
+        assert_eq!(main.len(), 1);
+        let roots: HashSet<DeclarationId> = main.iter().map(|d| d.id.clone()).collect();
+
+        let parse = graph.find_symbol("Repository.parse");
+        assert_eq!(parse.len(), 1);
+        let path = graph.shortest_path(&roots, &parse[0].id).unwrap();
+        let names: Vec<&str> = path.iter().map(|s| s.declaration.name.as_str()).collect();
+        assert_eq!(names, ["MainActivity", "onCreate", "load", "parse"]);
+        assert_eq!(path[0].link, None);
+        assert_eq!(path[1].link, Some(PathLink::Member));
+        assert_eq!(path[3].link, Some(PathLink::Reference(ReferenceKind::Call)));
+
+        // Members of a reached type are reachable through it
+        let clear = graph.find_symbol("clear");
+        let path = graph.shortest_path(&roots, &clear[0].id).unwrap();
+        assert_eq!(path.last().unwrap().link, Some(PathLink::Member));
+
+        let orphan = graph.find_symbol("Orphan");
+        assert!(graph.shortest_path(&roots, &orphan[0].id).is_none());
+        assert!(graph.find_symbol("Missing").is_empty());
+    }
+}
@@ -0,0 +1,363 @@
+//! Stable read-only query surface for the reference graph
+//!
+//! Enabled with the `graph-api` feature. Everything reachable from
+//! [`GraphQuery`] follows semver: it only grows in minor releases, and changes
+//! in behavior are called out in the changelog. So do the `Graph` methods it
+//! delegates to (`find_by_fqn`, `callers_of`, `callees_of`, `subtypes_of`),
+//! which are available without the feature. Other `Graph` methods (`inner`,
+//! `node_index`, the builders, ...) are implementation details for the
+//! analyzer and may change in any release.
+//!
+//! ```
+//! use searchdeadcode::graph::{Graph, ReferenceKind};
+//!
+//! fn callers_of(graph: &Graph, fqn: &str) -> Vec<String> {
+//!     let query = graph.query();
+//!     let Some(target) = query.by_fqn(fqn) else {
+//!         return Vec::new();
+//!     };
+//!     query
+//!         .callers_of(&target.id)
+//!         .map(|caller| caller.name.to_string())
+//!         .collect()
+//! }
+//!
+//! fn type_references(graph: &Graph) -> usize {
+//!     graph
+//!         .query()
+//!         .references()
+//!         .filter(|r| r.kind() == ReferenceKind::TypeReference)
+//!         .count()
+//! }
+//! ```
+
+use super::{
+    Declaration, DeclarationId, Graph, GraphFragment, Location, Reference, ReferenceConfidence,
+    ReferenceKind,
+};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+
+impl Graph {
+    /// Read-only query view of this graph
+    pub fn query(&self) -> GraphQuery<'_> {
+        GraphQuery { graph: self }
+    }
+}
+
+/// Read-only view of a [`Graph`], created by [`Graph::query`]
+#[derive(Debug, Clone, Copy)]
+pub struct GraphQuery<'g> {
+    graph: &'g Graph,
+}
+
+/// A reference between two declarations, borrowed from the graph
+#[derive(Debug, Clone, Copy)]
+pub struct ReferenceView<'g> {
+    from: &'g Declaration,
+    to: &'g Declaration,
+    reference: &'g Reference,
+}
+
+impl<'g> ReferenceView<'g> {
+    /// The referencing declaration
+    pub fn from(&self) -> &'g Declaration {
+        self.from
+    }
+
+    /// The referenced declaration
+    pub fn to(&self) -> &'g Declaration {
+        self.to
+    }
+
+    /// How `to` is used by `from`
+    pub fn kind(&self) -> ReferenceKind {
+        self.reference.kind
+    }
+
+    /// How surely the reference points at `to`
+    pub fn confidence(&self) -> ReferenceConfidence {
+        self.reference.confidence
+    }
+
+    /// Where the reference appears in the source
+    pub fn location(&self) -> &'g Location {
+        &self.reference.location
+    }
+
+    /// Full reference details
+    pub fn reference(&self) -> &'g Reference {
+        self.reference
+    }
+}
+
+impl<'g> GraphQuery<'g> {
+    /// Number of declarations
+    pub fn len(&self) -> usize {
+        self.graph.declarations.len()
+    }
+
+    /// Whether the graph has no declarations
+    pub fn is_empty(&self) -> bool {
+        self.graph.declarations.is_empty()
+    }
+
+    /// Number of references
+    pub fn reference_count(&self) -> usize {
+        self.graph.inner.edge_count()
+    }
+
+    /// All declarations, in the order they were added to the graph
+    pub fn declarations(&self) -> impl Iterator<Item = &'g Declaration> + 'g {
+        let graph = self.graph;
+        graph
+            .inner
+            .node_weights()
+            .filter_map(|id| graph.declarations.get(id))
+    }
+
+    /// All references, in the order they were added to the graph
+    pub fn references(&self) -> impl Iterator<Item = ReferenceView<'g>> + 'g {
+        let graph = self.graph;
+        graph
+            .inner
+            .edge_references()
+            .filter_map(move |edge| view(graph, edge.source(), edge.target(), edge.weight()))
+    }
+
+    /// Declaration with the given ID
+    pub fn get(&self, id: &DeclarationId) -> Option<&'g Declaration> {
+        self.graph.declarations.get(id)
+    }
+
+    /// Declaration with the given fully qualified name
+    pub fn by_fqn(&self, fqn: &str) -> Option<&'g Declaration> {
+        self.graph.find_by_fqn(fqn)
+    }
+
+    /// Declarations with the given simple name
+    pub fn by_name(&self, name: &str) -> impl Iterator<Item = &'g Declaration> + 'g {
+        let graph = self.graph;
+        graph
+            .name_index
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| graph.declarations.get(id))
+    }
+
+    /// Enclosing declaration (e.g. the class of a method)
+    pub fn parent(&self, id: &DeclarationId) -> Option<&'g Declaration> {
+        self.get(id)?.parent.as_ref().and_then(|p| self.get(p))
+    }
+
+    /// Declarations nested directly inside `id`
+    pub fn children(&self, id: &DeclarationId) -> impl Iterator<Item = &'g Declaration> + 'g {
+        let graph = self.graph;
+        graph
+            .children_index
+            .get(id)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| graph.declarations.get(child))
+    }
+
+    /// References pointing at `id`
+    pub fn incoming(&self, id: &DeclarationId) -> impl Iterator<Item = ReferenceView<'g>> + 'g {
+        self.edges(id, Direction::Incoming)
+    }
+
+    /// References made by `id`
+    pub fn outgoing(&self, id: &DeclarationId) -> impl Iterator<Item = ReferenceView<'g>> + 'g {
+        self.edges(id, Direction::Outgoing)
+    }
+
+    /// References of one kind pointing at `id`
+    pub fn incoming_of_kind(
+        &self,
+        id: &DeclarationId,
+        kind: ReferenceKind,
+    ) -> impl Iterator<Item = ReferenceView<'g>> + 'g {
+        self.incoming(id).filter(move |r| r.kind() == kind)
+    }
+
+    /// References of one kind made by `id`
+    pub fn outgoing_of_kind(
+        &self,
+        id: &DeclarationId,
+        kind: ReferenceKind,
+    ) -> impl Iterator<Item = ReferenceView<'g>> + 'g {
+        self.outgoing(id).filter(move |r| r.kind() == kind)
+    }
+
+    /// Declarations calling `id`, or instantiating it when it is a type,
+    /// each listed once (see [`Graph::callers_of`])
+    pub fn callers_of(&self, id: &DeclarationId) -> impl Iterator<Item = &'g Declaration> + 'g {
+        self.graph.callers_of(id).into_iter()
+    }
+
+    /// Declarations `id` calls or instantiates, each listed once (see
+    /// [`Graph::callees_of`])
+    pub fn callees_of(&self, id: &DeclarationId) -> impl Iterator<Item = &'g Declaration> + 'g {
+        self.graph.callees_of(id).into_iter()
+    }
+
+    /// Project types directly extending or implementing the type `id` (see
+    /// [`Graph::subtypes_of`])
+    pub fn subtypes_of(&self, id: &DeclarationId) -> impl Iterator<Item = &'g Declaration> + 'g {
+        self.graph.subtypes_of(id).into_iter()
+    }
+
+    /// Copy of the given declarations and the references between them
+    pub fn subgraph<'a>(&self, ids: impl IntoIterator<Item = &'a DeclarationId>) -> GraphFragment {
+        self.graph.fragment(ids)
+    }
+
+    fn edges(
+        &self,
+        id: &DeclarationId,
+        direction: Direction,
+    ) -> impl Iterator<Item = ReferenceView<'g>> + 'g {
+        let graph = self.graph;
+        graph
+            .node_map
+            .get(id)
+            .into_iter()
+            .flat_map(move |&node| graph.inner.edges_directed(node, direction))
+            .filter_map(move |edge| view(graph, edge.source(), edge.target(), edge.weight()))
+    }
+}
+
+fn view<'g>(
+    graph: &'g Graph,
+    source: petgraph::graph::NodeIndex,
+    target: petgraph::graph::NodeIndex,
+    reference: &'g Reference,
+) -> Option<ReferenceView<'g>> {
+    Some(ReferenceView {
+        from: graph.declarations.get(&graph.inner[source])?,
+        to: graph.declarations.get(&graph.inner[target])?,
+        reference,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DeclarationKind, Language};
+    use std::path::PathBuf;
+
+    fn decl(name: &str, start: usize, parent: Option<&DeclarationId>) -> Declaration {
+        let path = PathBuf::from("Test.kt");
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 10),
+            name.to_string(),
+            DeclarationKind::Class,
+            Location::new(path, start + 1, 1, start, start + 10),
+            Language::Kotlin,
+        );
+        decl.fully_qualified_name = Some(format!("app.{name}").into());
+        decl.parent = parent.cloned();
+        decl
+    }
+
+    fn reference(kind: ReferenceKind, name: &str) -> Reference {
+        let location = Location::new(PathBuf::from("Test.kt"), 1, 1, 0, 1);
+        Reference::new(kind, location, name.to_string())
+    }
+
+    #[test]
+    fn test_query_declarations_and_lookup() {
+        let mut graph = Graph::new();
+        let outer = graph.add_declaration(decl("Outer", 0, None));
+        let inner = graph.add_declaration(decl("Inner", 20, Some(&outer)));
+        graph.add_declaration(decl("Other", 40, None));
+
+        let query = graph.query();
+        let names: Vec<_> = query.declarations().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["Outer", "Inner", "Other"]);
+        assert_eq!(query.len(), 3);
+        assert_eq!(query.by_fqn("app.Inner").unwrap().id, inner);
+        assert_eq!(query.by_name("Other").count(), 1);
+        assert_eq!(query.parent(&inner).unwrap().id, outer);
+        assert_eq!(query.children(&outer).next().unwrap().id, inner);
+    }
+
+    #[test]
+    fn test_query_references_by_kind() {
+        let mut graph = Graph::new();
+        let a = graph.add_declaration(decl("A", 0, None));
+        let b = graph.add_declaration(decl("B", 20, None));
+        let c = graph.add_declaration(decl("C", 40, None));
+        graph.add_reference(&a, &b, reference(ReferenceKind::Call, "B"));
+        graph.add_reference(&c, &b, reference(ReferenceKind::Type, "B"));
+
+        let query = graph.query();
+        assert_eq!(query.incoming(&b).count(), 2);
+        let calls: Vec<_> = query.incoming_of_kind(&b, ReferenceKind::Call).collect();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].from().name, "A");
+        assert_eq!(calls[0].to().name, "B");
+        assert_eq!(query.outgoing_of_kind(&c, ReferenceKind::Call).count(), 0);
+        assert_eq!(query.references().count(), 2);
+
+        let missing = DeclarationId::new(PathBuf::from("Missing.kt"), 0, 1);
+        assert_eq!(query.incoming(&missing).count(), 0);
+
+        let fragment = query.subgraph([&a, &b]);
+        assert_eq!(fragment.declarations.len(), 2);
+        assert_eq!(fragment.references.len(), 1);
+    }
+
+    #[test]
+    fn test_query_callers_callees_and_subtypes() {
+        let mut graph = Graph::new();
+        let a = graph.add_declaration(decl("A", 0, None));
+        let b = graph.add_declaration(decl("B", 20, None));
+        let c = graph.add_declaration(decl("C", 40, None));
+        graph.add_reference(&a, &b, reference(ReferenceKind::Call, "B"));
+        graph.add_reference(&a, &b, reference(ReferenceKind::Call, "B"));
+        graph.add_reference(&a, &c, reference(ReferenceKind::Instantiation, "C"));
+        graph.add_reference(&c, &b, reference(ReferenceKind::Type, "B"));
+
+        let query = graph.query();
+        let names = |decls: Vec<&Declaration>| -> Vec<String> {
+            decls.iter().map(|d| d.name.to_string()).collect()
+        };
+        assert_eq!(names(query.callers_of(&b).collect()), ["A"]);
+        let mut callees = names(query.callees_of(&a).collect());
+        callees.sort();
+        assert_eq!(callees, ["B", "C"]);
+        assert_eq!(query.callees_of(&c).count(), 0);
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Shapes.kt");
+        std::fs::write(
+            &path,
+            r#"package com.app
+
+interface Shape
+
+open class Circle : Shape
+
+class Ring : Circle()
+
+class Square : Shape
+"#,
+        )
+        .unwrap();
+        let mut builder = crate::graph::GraphBuilder::new();
+        builder
+            .process_file(&crate::discovery::SourceFile::new(
+                path,
+                crate::discovery::FileType::Kotlin,
+            ))
+            .unwrap();
+        let graph = builder.build();
+        let query = graph.query();
+        let shape = query.by_fqn("com.app.Shape").unwrap();
+        let mut subtypes = names(query.subtypes_of(&shape.id).collect());
+        subtypes.sort();
+        assert_eq!(subtypes, ["Circle", "Square"]);
+    }
+}
@@ -0,0 +1,137 @@
+// Reference reach
+//
+// A reference either stays in the file of the declaration it references or
+// crosses a boundary: to another file of the package, another package of
+// the module, or another module. The widest boundary a declaration's
+// references cross bounds the visibility the declaration needs.
+
+use super::{DeclarationId, Graph};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::Serialize;
+
+/// Widest boundary a reference crosses, from its referencing declaration to
+/// the declaration it references
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Reach {
+    /// Both are declared in the same file
+    File,
+    /// They are declared in different files of the same package
+    Package,
+    /// They are declared in different packages of the same module
+    Module,
+    /// They are declared in different modules
+    Project,
+}
+
+impl Reach {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Reach::File => "file",
+            Reach::Package => "package",
+            Reach::Module => "module",
+            Reach::Project => "project",
+        }
+    }
+}
+
+impl Graph {
+    /// Widest boundary a reference from `from` to `to` crosses
+    ///
+    /// Declarations without a module count as one module.
+    pub fn reach(&self, from: &DeclarationId, to: &DeclarationId) -> Reach {
+        if from.file == to.file {
+            return Reach::File;
+        }
+        let module = |id: &DeclarationId| self.get_declaration(id).and_then(|d| d.module.as_ref());
+        if module(from) != module(to) {
+            return Reach::Project;
+        }
+        let package = |id: &DeclarationId| {
+            self.file_scope(&id.file)
+                .and_then(|scope| scope.package.as_deref())
+        };
+        if package(from) == package(to) {
+            Reach::Package
+        } else {
+            Reach::Module
+        }
+    }
+
+    /// Widest boundary the references to `id` cross; `None` when nothing
+    /// references it
+    pub fn widest_reach(&self, id: &DeclarationId) -> Option<Reach> {
+        let node = self.node_index(id)?;
+        self.inner
+            .edges_directed(node, Direction::Incoming)
+            .map(|edge| self.reach(&self.inner[edge.source()], id))
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn test_reach() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, module: &str, contents: &str| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, FileType::Kotlin).with_module(Some(module.to_string()))
+        };
+        let files = [
+            write(
+                "Screen.kt",
+                ":app",
+                r#"package com.app
+
+import com.app.core.format
+
+class Screen {
+    fun show() = format()
+}
+"#,
+            ),
+            write(
+                "Format.kt",
+                ":core",
+                r#"package com.app.core
+
+import com.app.core.text.trim
+
+fun format() = trim() + pad()
+fun pad() = Unit
+fun unused() = Unit
+"#,
+            ),
+            write(
+                "Trim.kt",
+                ":core",
+                r#"package com.app.core.text
+
+fun trim() = Unit
+"#,
+            ),
+        ];
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+        let id = |name: &str| graph.find_by_name(name)[0].id.clone();
+
+        assert_eq!(graph.reach(&id("show"), &id("format")), Reach::Project);
+        assert_eq!(graph.reach(&id("format"), &id("trim")), Reach::Module);
+        assert_eq!(graph.reach(&id("format"), &id("pad")), Reach::File);
+        assert_eq!(graph.widest_reach(&id("format")), Some(Reach::Project));
+        assert_eq!(graph.widest_reach(&id("trim")), Some(Reach::Module));
+        assert_eq!(graph.widest_reach(&id("pad")), Some(Reach::File));
+        assert_eq!(graph.widest_reach(&id("unused")), None);
+        assert!(Reach::File < Reach::Package && Reach::Module < Reach::Project);
+    }
+}
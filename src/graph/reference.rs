@@ -1,11 +1,12 @@
 // Reference types - some variants and methods reserved for future use
 #![allow(dead_code)]
 
-use super::Location;
+use super::{Location, Symbol};
 use serde::{Deserialize, Serialize};
 
 /// Kind of reference between declarations
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ReferenceKind {
     /// Calling a function/method
     Call,
@@ -28,15 +29,22 @@ pub enum ReferenceKind {
     /// Instantiation (new/constructor call)
     Instantiation,
 
-    /// Annotation usage
+    /// Used in the arguments of an annotation (`@Named(KEY)`)
     Annotation,
 
+    /// Annotation class applied to a declaration or type (`@Tracked fun`)
+    AnnotationUse,
+
     /// Cast expression
     Cast,
 
     /// Generic type argument
     TypeArgument,
 
+    /// Type named in a declaration's signature: parameter, return,
+    /// property, field, receiver, bound and thrown types
+    TypeReference,
+
     /// Return type
     ReturnType,
 
@@ -60,6 +68,14 @@ pub enum ReferenceKind {
 
     /// Generic type argument (e.g., List<MyClass>)
     GenericArgument,
+
+    /// Enumerating every constant of an enum (values(), entries, valueOf)
+    EnumValues,
+
+    /// Class or member named in a string literal, possibly loaded by
+    /// reflection (`Class.forName("com.app.Foo")`, `getMethod("load")`).
+    /// Not followed by reachability; see `Graph::string_references_to`
+    ReflectionString,
 }
 
 impl ReferenceKind {
@@ -70,6 +86,7 @@ impl ReferenceKind {
             ReferenceKind::Read
                 | ReferenceKind::Call
                 | ReferenceKind::Type
+                | ReferenceKind::TypeReference
                 | ReferenceKind::TypeArgument
         )
     }
@@ -79,6 +96,44 @@ impl ReferenceKind {
         matches!(self, ReferenceKind::Write)
     }
 
+    /// Name of the reference kind in exports and reports
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ReferenceKind::Call => "call",
+            ReferenceKind::Read => "read",
+            ReferenceKind::Write => "write",
+            ReferenceKind::Type => "type",
+            ReferenceKind::Inheritance => "inheritance",
+            ReferenceKind::Import => "import",
+            ReferenceKind::Instantiation => "instantiation",
+            ReferenceKind::Annotation => "annotation",
+            ReferenceKind::AnnotationUse => "annotation use",
+            ReferenceKind::Cast => "cast",
+            ReferenceKind::TypeArgument => "type argument",
+            ReferenceKind::TypeReference => "type reference",
+            ReferenceKind::ReturnType => "return type",
+            ReferenceKind::ParameterType => "parameter type",
+            ReferenceKind::Override => "override",
+            ReferenceKind::Reflection => "reflection",
+            ReferenceKind::ExtensionReceiver => "extension receiver",
+            ReferenceKind::SealedSubtype => "sealed subtype",
+            ReferenceKind::Delegation => "delegation",
+            ReferenceKind::GenericArgument => "generic argument",
+            ReferenceKind::EnumValues => "enum values",
+            ReferenceKind::ReflectionString => "reflection string",
+        }
+    }
+
+    /// Check if this reference is part of the signature of the declaration
+    /// it is made from, so a parameter's type and annotations are used by
+    /// the parameter's function
+    pub fn is_signature(&self) -> bool {
+        matches!(
+            self,
+            ReferenceKind::AnnotationUse | ReferenceKind::TypeReference
+        )
+    }
+
     /// Check if this reference counts as "usage" for dead code detection
     pub fn counts_as_usage(&self) -> bool {
         // All references count as usage for now
@@ -86,6 +141,40 @@ impl ReferenceKind {
     }
 }
 
+/// How surely a reference points at the declaration it was resolved to
+///
+/// Ordered from the weakest to the surest, so `confidence >= NameMatch`
+/// leaves out heuristics.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReferenceConfidence {
+    /// Guessed from something other than the code's names: a handler named
+    /// in a layout without `tools:context`, a string literal
+    Heuristic,
+
+    /// Matched by simple name among several same-named declarations, all of
+    /// which are referenced since the parser cannot tell which one is meant
+    NameMatch,
+
+    /// Resolved through a qualified name, an import or the scope, or the
+    /// only declaration of its name
+    #[default]
+    Exact,
+}
+
+impl ReferenceConfidence {
+    /// Name of the confidence in exports and reports
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ReferenceConfidence::Heuristic => "heuristic",
+            ReferenceConfidence::NameMatch => "name match",
+            ReferenceConfidence::Exact => "exact",
+        }
+    }
+}
+
 /// A reference from one declaration to another
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Reference {
@@ -96,19 +185,29 @@ pub struct Reference {
     pub location: Location,
 
     /// The name/identifier used in the reference
-    pub name: String,
+    pub name: Symbol,
 
     /// Whether this is a qualified reference (e.g., com.example.Foo)
     pub is_qualified: bool,
+
+    /// Arguments passed, for Kotlin calls whose argument list was parsed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<CallArguments>,
+
+    /// How surely the reference points at its target
+    #[serde(default)]
+    pub confidence: ReferenceConfidence,
 }
 
 impl Reference {
-    pub fn new(kind: ReferenceKind, location: Location, name: String) -> Self {
+    pub fn new(kind: ReferenceKind, location: Location, name: impl Into<Symbol>) -> Self {
         Self {
             kind,
             location,
-            name,
+            name: name.into(),
             is_qualified: false,
+            arguments: None,
+            confidence: ReferenceConfidence::Exact,
         }
     }
 
@@ -116,6 +215,58 @@ impl Reference {
         self.is_qualified = qualified;
         self
     }
+
+    pub fn with_arguments(mut self, arguments: Option<CallArguments>) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    pub fn with_confidence(mut self, confidence: ReferenceConfidence) -> Self {
+        self.confidence = confidence;
+        self
+    }
+}
+
+/// Arguments passed at a call site
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallArguments {
+    /// Arguments inside the parentheses, in source order
+    pub arguments: Vec<CallArgument>,
+
+    /// Whether a trailing lambda follows the parentheses (`run(x) { ... }`)
+    pub trailing_lambda: bool,
+}
+
+impl CallArguments {
+    /// Number of arguments without a parameter name
+    pub fn positional_count(&self) -> usize {
+        self.arguments.iter().filter(|a| a.name.is_none()).count()
+    }
+}
+
+/// A single argument at a call site
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallArgument {
+    /// Parameter name of a named argument (`retries = 3`)
+    pub name: Option<String>,
+
+    /// The argument expression as written
+    pub value: String,
+
+    /// Whether the argument is spread into a vararg (`*items`)
+    pub spread: bool,
+}
+
+/// Receiver of a qualified call (`receiver.callee()`), as far as the parser
+/// can tell without type inference
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CallReceiver {
+    /// Type evident from the expression: `"a".f()` is a `String`,
+    /// `Foo().f()` a `Foo`
+    Type(String),
+
+    /// Identifier whose declared type is the receiver type: `user.f()`
+    Name(String),
 }
 
 /// Builder for tracking references during parsing
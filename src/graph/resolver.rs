@@ -0,0 +1,296 @@
+// Fully qualified type resolution
+//
+// Declarations name their super types, parameter types and return types as
+// written in the source (`extends BaseRepository<User>`, `Result?`,
+// `com.app.net.Result`). The resolver turns those names into declarations
+// the way the compiler does: qualified names through a table of every
+// project type's fully qualified name, simple names through the package and
+// imports of the file naming them. Library types resolve to nothing.
+
+use super::{Declaration, DeclarationId, DeclarationKind, Graph};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Package and imports of a source file, the context simple type names
+/// written in it are resolved in
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileScope {
+    /// Package declared by the file (`None` for the default package)
+    pub package: Option<String>,
+    /// Imports as written (`com.app.User`, `com.app.*`, `com.app.User as Person`)
+    pub imports: Vec<String>,
+}
+
+impl FileScope {
+    pub fn new(package: Option<String>, imports: Vec<String>) -> Self {
+        Self { package, imports }
+    }
+}
+
+/// Symbol table of the project's types, resolving type names written in
+/// declarations to the declarations they stand for
+pub struct TypeResolver<'g> {
+    graph: &'g Graph,
+
+    /// Type declarations by fully qualified name, both the indexed form
+    /// (`com.app.Inner`) and the form with outer classes (`com.app.Outer.Inner`)
+    types: HashMap<String, Vec<DeclarationId>>,
+
+    /// Typealiases and the declarations they stand for
+    aliases: HashMap<DeclarationId, Vec<DeclarationId>>,
+}
+
+impl<'g> TypeResolver<'g> {
+    /// Build the symbol table for every type declared in `graph`
+    pub fn new(graph: &'g Graph) -> Self {
+        let mut resolver = Self {
+            graph,
+            types: HashMap::new(),
+            aliases: graph.type_alias_targets(),
+        };
+
+        let mut types: Vec<&Declaration> =
+            graph.declarations().filter(|d| d.kind.is_type()).collect();
+        // Stable order, so ambiguous names resolve the same way every run
+        types.sort_by(|a, b| (&a.id.file, a.id.start).cmp(&(&b.id.file, b.id.start)));
+        for decl in &types {
+            let mut names = vec![graph.nested_fqn(decl)];
+            names.extend(decl.fully_qualified_name.as_deref().map(str::to_string));
+            names.dedup();
+            for name in names {
+                resolver
+                    .types
+                    .entry(name)
+                    .or_default()
+                    .push(decl.id.clone());
+            }
+        }
+        resolver
+    }
+
+    /// Declarations a type name written inside `from` stands for
+    ///
+    /// Generic arguments, nullability, array brackets and constructor calls
+    /// are ignored (`BaseRepository<User>()` names `BaseRepository`), and
+    /// typealiases resolve to what they alias.
+    pub fn resolve(&self, type_name: &str, from: &Declaration) -> Vec<&'g Declaration> {
+        let Some(name) = base_type_name(type_name) else {
+            return Vec::new();
+        };
+
+        let mut found: Vec<&'g Declaration> = match self.types.get(name) {
+            Some(ids) if name.contains('.') => ids
+                .iter()
+                .filter_map(|id| self.graph.get_declaration(id))
+                .collect(),
+            // A package-qualified name missing from the table is a library type
+            _ if name.contains('.') && name.starts_with(|c: char| c.is_lowercase()) => {
+                return Vec::new();
+            }
+            _ => {
+                let simple = name.rsplit('.').next().unwrap_or(name);
+                let candidates = self
+                    .graph
+                    .find_by_name(simple)
+                    .into_iter()
+                    .filter(|d| d.kind.is_type())
+                    .collect();
+                match self.graph.file_scope(&from.location.file) {
+                    Some(scope) => self.graph.narrow_to_imports(
+                        candidates,
+                        simple,
+                        scope.package.as_deref(),
+                        &scope.imports,
+                    ),
+                    None => candidates,
+                }
+            }
+        };
+
+        let aliased: Vec<&'g Declaration> = found
+            .iter()
+            .filter(|d| d.kind == DeclarationKind::TypeAlias)
+            .flat_map(|d| self.aliases.get(&d.id).into_iter().flatten())
+            .filter_map(|id| self.graph.get_declaration(id))
+            .collect();
+        found.retain(|d| d.kind != DeclarationKind::TypeAlias);
+        for decl in aliased {
+            if !found.iter().any(|d| d.id == decl.id) {
+                found.push(decl);
+            }
+        }
+        found
+    }
+
+    /// Project types a class, object or interface extends or implements
+    pub fn super_types(&self, decl: &Declaration) -> Vec<&'g Declaration> {
+        decl.super_types
+            .iter()
+            .flat_map(|super_type| self.resolve(super_type, decl))
+            .filter(|super_type| super_type.id != decl.id)
+            .collect()
+    }
+
+    /// Whether `decl` extends or implements `ancestor`, directly or through
+    /// other project types
+    pub fn is_subtype_of(&self, decl: &Declaration, ancestor: &DeclarationId) -> bool {
+        self.graph.hierarchy().is_subtype_of(&decl.id, ancestor)
+    }
+
+    /// Project types directly extending or implementing `id`
+    pub fn subtypes(&self, id: &DeclarationId) -> Vec<&'g Declaration> {
+        self.graph.subtypes(id)
+    }
+
+    /// Project types named by the declared type of a property, field or
+    /// parameter, or by the return type of a function, generic arguments
+    /// included (`Map<UserId, User>` names both)
+    pub fn declared_types(&self, decl: &Declaration) -> Vec<&'g Declaration> {
+        let Some(type_name) = decl.type_name.as_deref() else {
+            return Vec::new();
+        };
+        let mut found: Vec<&'g Declaration> = Vec::new();
+        for name in type_name
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+            .filter(|name| !name.is_empty())
+        {
+            for decl in self.resolve(name, decl) {
+                if !found.iter().any(|d| d.id == decl.id) {
+                    found.push(decl);
+                }
+            }
+        }
+        found
+    }
+
+    /// Project types named by the parameters of a function or constructor
+    pub fn parameter_types(&self, decl: &Declaration) -> Vec<&'g Declaration> {
+        let mut found: Vec<&'g Declaration> = Vec::new();
+        for parameter in self
+            .graph
+            .get_children(&decl.id)
+            .into_iter()
+            .filter_map(|id| self.graph.get_declaration(id))
+            .filter(|child| child.kind == DeclarationKind::Parameter)
+        {
+            for decl in self.declared_types(parameter) {
+                if !found.iter().any(|d| d.id == decl.id) {
+                    found.push(decl);
+                }
+            }
+        }
+        found
+    }
+}
+
+/// The type a written type name stands for, without generic arguments,
+/// nullability, arrays or constructor calls
+/// (`extends BaseRepository<User>` -> `BaseRepository`)
+fn base_type_name(type_name: &str) -> Option<&str> {
+    let name = type_name.trim();
+    let name = name
+        .strip_prefix("extends ")
+        .or_else(|| name.strip_prefix("implements "))
+        .unwrap_or(name);
+    let end = name.find(['<', '(', '?', '[', ' ']).unwrap_or(name.len());
+    let name = name[..end].trim_end_matches('.');
+    let simple = name.rsplit('.').next().unwrap_or(name);
+    // Function types and lowercase names (`(Int) -> Unit`, `int`) are not types
+    simple
+        .starts_with(|c: char| c.is_uppercase())
+        .then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn test_resolves_types_through_packages_and_imports() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, FileType::Kotlin)
+        };
+        let files = [
+            write(
+                "Data.kt",
+                r#"package com.app.data
+
+open class Result
+class User
+typealias Member = User
+"#,
+            ),
+            write(
+                "Net.kt",
+                r#"package com.app.net
+
+open class Result {
+    class Error : Result()
+}
+"#,
+            ),
+            write(
+                "Repository.kt",
+                r#"package com.app.data
+
+import com.app.net.Result.Error
+
+class Cached : Result()
+
+class Failed : com.app.net.Result()
+
+class Repository {
+    fun load(id: String, members: List<Member>): Error? = null
+}
+"#,
+            ),
+        ];
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+        let resolver = TypeResolver::new(&graph);
+
+        let fqns = |decls: Vec<&Declaration>| -> Vec<String> {
+            decls.iter().map(|d| graph.nested_fqn(d)).collect()
+        };
+        let named = |name: &str| graph.find_by_name(name)[0];
+
+        assert_eq!(
+            fqns(resolver.super_types(named("Cached"))),
+            ["com.app.data.Result"]
+        );
+        assert_eq!(
+            fqns(resolver.super_types(named("Failed"))),
+            ["com.app.net.Result"]
+        );
+        assert_eq!(
+            fqns(resolver.super_types(named("Error"))),
+            ["com.app.net.Result"]
+        );
+
+        let load = named("load");
+        assert_eq!(
+            fqns(resolver.declared_types(load)),
+            ["com.app.net.Result.Error"]
+        );
+        assert_eq!(fqns(resolver.parameter_types(load)), ["com.app.data.User"]);
+
+        let net_result = graph.find_by_fqn("com.app.net.Result").unwrap();
+        let mut subtypes: Vec<&str> = resolver
+            .subtypes(&net_result.id)
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        subtypes.sort_unstable();
+        assert_eq!(subtypes, ["Error", "Failed"]);
+        assert!(resolver.is_subtype_of(named("Error"), &net_result.id));
+        assert!(!resolver.is_subtype_of(named("Cached"), &net_result.id));
+    }
+}
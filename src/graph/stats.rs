@@ -0,0 +1,372 @@
+// Graph statistics
+//
+// Summarizes the shape of the reference graph for architecture reviews:
+// how many declarations and references there are, how references spread
+// over declarations (fan-in: how many declarations reference one, fan-out:
+// how many it references), which declarations everything depends on, and
+// which packages nothing outside them references. Parameters are left out,
+// like in exports: their references belong to their function.
+
+use super::{Declaration, DeclarationId, DeclarationKind, Graph};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Upper bounds of the fan-in and fan-out histogram buckets; the last bucket
+/// is open-ended
+const BUCKET_BOUNDS: [usize; 5] = [0, 1, 4, 9, 49];
+
+/// Size and shape of a reference graph
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphStats {
+    /// Source files declaring something
+    pub files: usize,
+    /// Packages declared by the source files
+    pub packages: usize,
+    pub declarations: usize,
+    pub references: usize,
+    /// Declarations by kind (`class`, `method`, ...)
+    pub declarations_by_kind: BTreeMap<&'static str, usize>,
+    /// References by kind (`call`, `type reference`, ...)
+    pub references_by_kind: BTreeMap<&'static str, usize>,
+    /// Groups of declarations that reference each other in a cycle
+    pub cycles: usize,
+    /// Number of other declarations referencing each declaration
+    pub fan_in: Distribution,
+    /// Number of other declarations each declaration references
+    pub fan_out: Distribution,
+    /// Declarations with the highest fan-in
+    pub most_referenced: Vec<RankedDeclaration>,
+    /// Declarations with the highest fan-out
+    pub most_dependent: Vec<RankedDeclaration>,
+    /// Packages no declaration of another package references
+    pub orphaned_packages: Vec<String>,
+}
+
+/// Distribution of a per-declaration count
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Distribution {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    pub median: usize,
+    /// 90th percentile
+    pub p90: usize,
+    /// Declarations per range of counts
+    pub histogram: Vec<Bucket>,
+}
+
+/// Number of declarations whose count falls in a range
+#[derive(Debug, Clone, Serialize)]
+pub struct Bucket {
+    /// The range (`0`, `2-4`, `50+`)
+    pub range: String,
+    pub declarations: usize,
+}
+
+/// A declaration and the count it is ranked by
+#[derive(Debug, Clone, Serialize)]
+pub struct RankedDeclaration {
+    pub name: String,
+    pub fully_qualified_name: Option<String>,
+    pub kind: &'static str,
+    pub file: PathBuf,
+    pub line: usize,
+    pub count: usize,
+}
+
+impl Graph {
+    /// Compute the graph's statistics, ranking the `top` declarations with
+    /// the highest fan-in and fan-out
+    pub fn stats(&self, top: usize) -> GraphStats {
+        let declarations: Vec<&Declaration> = self
+            .declarations()
+            .filter(|decl| decl.kind != DeclarationKind::Parameter)
+            .collect();
+
+        let mut declarations_by_kind = BTreeMap::new();
+        for decl in &declarations {
+            *declarations_by_kind
+                .entry(decl.kind.display_name())
+                .or_default() += 1;
+        }
+        let mut references_by_kind = BTreeMap::new();
+        for edge in self.inner.edge_references() {
+            *references_by_kind
+                .entry(edge.weight().kind.display_name())
+                .or_default() += 1;
+        }
+
+        let fan_in: Vec<usize> = declarations
+            .iter()
+            .map(|decl| self.neighbors(&decl.id, Direction::Incoming).len())
+            .collect();
+        let fan_out: Vec<usize> = declarations
+            .iter()
+            .map(|decl| self.neighbors(&decl.id, Direction::Outgoing).len())
+            .collect();
+
+        let files: HashSet<&Path> = declarations
+            .iter()
+            .map(|d| d.location.file.as_path())
+            .collect();
+        let packages: BTreeSet<&str> = files
+            .iter()
+            .filter_map(|file| self.file_scope(file))
+            .filter_map(|scope| scope.package.as_deref())
+            .collect();
+        let cycles = self
+            .condensation()
+            .components()
+            .filter(|component| component.len() > 1)
+            .count();
+
+        GraphStats {
+            files: files.len(),
+            packages: packages.len(),
+            declarations: declarations.len(),
+            references: self.reference_count(),
+            declarations_by_kind,
+            references_by_kind,
+            cycles,
+            fan_in: Distribution::new(&fan_in),
+            fan_out: Distribution::new(&fan_out),
+            most_referenced: rank(&declarations, &fan_in, top),
+            most_dependent: rank(&declarations, &fan_out, top),
+            orphaned_packages: if packages.len() > 1 {
+                self.orphaned_packages(&declarations)
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /// Declarations referencing, or referenced by, a declaration, leaving
+    /// out the declaration itself, what it encloses and what encloses it
+    fn neighbors(&self, id: &DeclarationId, direction: Direction) -> HashSet<&DeclarationId> {
+        let Some(node) = self.node_index(id) else {
+            return HashSet::new();
+        };
+        self.inner
+            .edges_directed(node, direction)
+            .map(|edge| match direction {
+                Direction::Incoming => &self.inner[edge.source()],
+                Direction::Outgoing => &self.inner[edge.target()],
+            })
+            .filter(|other| !self.encloses(id, other) && !self.encloses(other, id))
+            .collect()
+    }
+
+    /// Whether `outer` is `inner` or one of the declarations enclosing it
+    fn encloses(&self, outer: &DeclarationId, inner: &DeclarationId) -> bool {
+        let mut current = Some(inner);
+        while let Some(id) = current {
+            if id == outer {
+                return true;
+            }
+            current = self.get_declaration(id).and_then(|d| d.parent.as_ref());
+        }
+        false
+    }
+
+    /// Packages none of whose declarations another package references
+    fn orphaned_packages(&self, declarations: &[&Declaration]) -> Vec<String> {
+        let package = |id: &DeclarationId| {
+            self.file_scope(&id.file)
+                .and_then(|scope| scope.package.as_deref())
+        };
+        let mut packages: BTreeSet<&str> = BTreeSet::new();
+        let mut referenced: HashSet<&str> = HashSet::new();
+        for decl in declarations {
+            let Some(to) = package(&decl.id) else {
+                continue;
+            };
+            packages.insert(to);
+            if self
+                .neighbors(&decl.id, Direction::Incoming)
+                .into_iter()
+                .any(|from| package(from) != Some(to))
+            {
+                referenced.insert(to);
+            }
+        }
+        packages
+            .into_iter()
+            .filter(|package| !referenced.contains(package))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl Distribution {
+    fn new(counts: &[usize]) -> Self {
+        if counts.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = counts.to_vec();
+        sorted.sort_unstable();
+        // Nearest-rank percentile
+        let percentile = |p: usize| sorted[(sorted.len() * p).div_ceil(100).max(1) - 1];
+
+        let mut histogram = Vec::new();
+        let mut low = 0;
+        for high in BUCKET_BOUNDS.iter().copied().map(Some).chain([None]) {
+            let declarations = sorted
+                .iter()
+                .filter(|&&count| count >= low && high.map_or(true, |high| count <= high))
+                .count();
+            let range = match high {
+                Some(high) if high == low => low.to_string(),
+                Some(high) => format!("{}-{}", low, high),
+                None => format!("{}+", low),
+            };
+            histogram.push(Bucket {
+                range,
+                declarations,
+            });
+            low = high.map_or(low, |high| high + 1);
+        }
+
+        Self {
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            mean: sorted.iter().sum::<usize>() as f64 / sorted.len() as f64,
+            median: percentile(50),
+            p90: percentile(90),
+            histogram,
+        }
+    }
+}
+
+/// The `top` declarations with the highest counts, ties broken by name and
+/// position so the ranking is stable
+fn rank(declarations: &[&Declaration], counts: &[usize], top: usize) -> Vec<RankedDeclaration> {
+    let mut ranked: Vec<(&Declaration, usize)> = declarations
+        .iter()
+        .copied()
+        .zip(counts.iter().copied())
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    ranked.sort_by(|(a, a_count), (b, b_count)| {
+        b_count
+            .cmp(a_count)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| {
+                (&a.location.file, a.location.start_byte)
+                    .cmp(&(&b.location.file, b.location.start_byte))
+            })
+    });
+    ranked
+        .into_iter()
+        .take(top)
+        .map(|(decl, count)| RankedDeclaration {
+            name: decl.name.to_string(),
+            fully_qualified_name: decl.fully_qualified_name.as_deref().map(str::to_string),
+            kind: decl.kind.display_name(),
+            file: decl.location.file.to_path_buf(),
+            line: decl.location.line,
+            count,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn test_graph_stats() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, contents: &str| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            SourceFile::new(path, FileType::Kotlin)
+        };
+        let files = [
+            write(
+                "Feed.kt",
+                r#"package com.app.feed
+
+import com.app.data.Repository
+
+class FeedScreen {
+    fun show() = Repository().load()
+    fun refresh() = Repository().load()
+}
+"#,
+            ),
+            write(
+                "Repository.kt",
+                r#"package com.app.data
+
+class Repository {
+    fun load() = helper()
+    private fun helper() = Unit
+}
+"#,
+            ),
+        ];
+        let mut builder = GraphBuilder::new();
+        for file in &files {
+            builder.process_file(file).unwrap();
+        }
+        let graph = builder.build();
+        let stats = graph.stats(2);
+
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.packages, 2);
+        assert_eq!(stats.declarations, 6);
+        assert_eq!(stats.declarations_by_kind["class"], 2);
+        assert_eq!(stats.declarations_by_kind["method"], 4);
+        assert!(stats.references_by_kind["call"] >= 3);
+        assert_eq!(stats.cycles, 0);
+
+        // `load` is called by `show` and `refresh`, `Repository` too
+        let ranked: Vec<(&str, usize)> = stats
+            .most_referenced
+            .iter()
+            .map(|r| (r.name.as_str(), r.count))
+            .collect();
+        assert_eq!(ranked, [("Repository", 2), ("load", 2)]);
+        assert_eq!(stats.fan_in.max, 2);
+        assert_eq!(stats.fan_in.min, 0);
+        let buckets: usize = stats.fan_in.histogram.iter().map(|b| b.declarations).sum();
+        assert_eq!(buckets, stats.declarations);
+        assert_eq!(stats.fan_in.histogram[0].range, "0");
+        assert_eq!(stats.fan_in.histogram[2].range, "2-4");
+        assert_eq!(stats.fan_in.histogram[5].range, "50+");
+
+        // Nothing outside the feed package references it
+        assert_eq!(stats.orphaned_packages, ["com.app.feed"]);
+    }
+
+    #[test]
+    fn test_distribution() {
+        let distribution = Distribution::new(&[0, 0, 1, 2, 3, 5, 8, 13, 21, 60]);
+        assert_eq!(distribution.min, 0);
+        assert_eq!(distribution.max, 60);
+        assert_eq!(distribution.median, 3);
+        assert_eq!(distribution.p90, 21);
+        assert!((distribution.mean - 11.3).abs() < 1e-9);
+        let counts: Vec<(&str, usize)> = distribution
+            .histogram
+            .iter()
+            .map(|b| (b.range.as_str(), b.declarations))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                ("0", 2),
+                ("1", 1),
+                ("2-4", 2),
+                ("5-9", 2),
+                ("10-49", 2),
+                ("50+", 1)
+            ]
+        );
+    }
+}
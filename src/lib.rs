@@ -12,20 +12,36 @@
 //! 4. **Entry Point Detection** - Identify Android entry points
 //! 5. **Reachability Analysis** - Find unreachable code
 //! 6. **Reporting** - Output results in various formats
+//!
+//! [`AnalysisSession`] runs the whole pipeline for embedders. Tools that build
+//! on the reference graph should enable the `graph-api` feature and go through
+//! `Graph::query`, the semver-guarded read-only surface.
 
 pub mod analysis;
+pub mod cancel;
 pub mod config;
 pub mod coverage;
 pub mod discovery;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod graph;
 pub mod parser;
 pub mod proguard;
+#[cfg(feature = "python")]
+mod python;
 pub mod refactor;
 pub mod report;
+pub mod session;
+#[cfg(test)]
+mod test_util;
 
+pub use analysis::detectors::{
+    Detector, DetectorMetadata, DetectorRegistry, ProjectContext, ProjectDetector,
+};
 pub use analysis::{
     Confidence, DeadCode, EntryPointDetector, HybridAnalyzer, ReachabilityAnalyzer,
 };
+pub use cancel::{CancellationToken, Cancelled};
 pub use config::Config;
 pub use coverage::{parse_coverage_file, parse_coverage_files, CoverageData, CoverageParser};
 pub use discovery::FileFinder;
@@ -33,3 +49,7 @@ pub use graph::{Declaration, DeclarationKind, Graph, Reference};
 pub use proguard::{ProguardUsage, UsageEntryKind};
 pub use refactor::SafeDeleter;
 pub use report::{ReportFormat, Reporter};
+pub use session::{
+    AnalysisMode, AnalysisResults, AnalysisSession, AnalysisSessionBuilder, FindingSource,
+    FindingsBatch,
+};
@@ -1,66 +1,50 @@
-use clap::{CommandFactory, Parser};
+use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
 use colored::Colorize;
-use miette::Result;
+use miette::{IntoDiagnostic, Result};
+use std::collections::HashSet;
 use std::path::PathBuf;
+use std::process::ExitCode;
 use tracing::info;
 
 mod analysis;
 mod baseline;
 mod cache;
+mod cancel;
 mod config;
 mod coverage;
 mod discovery;
+mod gate;
 mod graph;
+mod owners;
 mod parser;
 mod proguard;
 mod refactor;
 mod report;
+mod session;
+#[cfg(test)]
+mod test_util;
 mod watch;
 
-use proguard::{ProguardUsage, ReportGenerator};
-
-use analysis::detectors::{
-    // Core detectors
-    Detector, RedundantOverrideDetector, UnusedIntentExtraDetector, UnusedParamDetector,
-    UnusedSealedVariantDetector, WriteOnlyDetector,
-    // Anti-pattern detectors (AP001-AP006)
-    DeepInheritanceDetector, EventBusPatternDetector, GlobalMutableStateDetector,
-    SingleImplInterfaceDetector,
-    // Phase 1: Kotlin patterns (AP007-AP010)
-    GlobalScopeUsageDetector, HeavyViewModelDetector, LateinitAbuseDetector,
-    ScopeFunctionChainingDetector,
-    // Phase 2: Performance & Memory (AP011-AP015)
-    CollectionWithoutSequenceDetector, LargeClassDetector, LongMethodDetector,
-    MemoryLeakRiskDetector, ObjectAllocationInLoopDetector,
-    // Phase 3: Architecture & Design (AP016-AP020)
-    HardcodedDispatcherDetector, MissingUseCaseDetector, MutableStateExposedDetector,
-    NestedCallbackDetector, ViewLogicInViewModelDetector,
-    // Phase 4: Kotlin-Specific (AP021-AP025)
-    ComplexConditionDetector, LongParameterListDetector, NullabilityOverloadDetector,
-    ReflectionOveruseDetector, StringLiteralDuplicationDetector,
-    // Phase 5: Android-Specific (AP026-AP030)
-    AsyncTaskUsageDetector, InitOnDrawDetector, MainThreadDatabaseDetector,
-    UnclosedResourceDetector, WakeLockAbuseDetector,
-    // Phase 6: Compose-Specific (AP031-AP034)
-    BusinessLogicInComposableDetector, LaunchedEffectWithoutKeyDetector,
-    NavControllerPassingDetector, StateWithoutRememberDetector,
-};
-use analysis::{
-    Confidence, CycleDetector, DeepAnalyzer, EnhancedAnalyzer, EntryPointDetector, HybridAnalyzer,
-    ReachabilityAnalyzer, ResourceDetector,
-};
-use config::Config;
-use coverage::parse_coverage_files;
+use proguard::ReportGenerator;
+
+use analysis::detectors::{DetectorRegistry, UnusedIntentExtraDetector};
+use analysis::{Confidence, CycleDetector, FindingFilter, ResourceDetector};
+use cancel::CancellationToken;
+use config::{Config, DetectionConfig};
 use discovery::FileFinder;
-use graph::{GraphBuilder, ParallelGraphBuilder};
+use graph::{Graph, GraphBuilder, ParallelGraphBuilder, PathLink, ReferenceKind};
 use report::Reporter;
+use session::{AnalysisMode, AnalysisResults, AnalysisSession, AnalysisSessionBuilder};
 
 /// SearchDeadCode - Fast dead code detection for Android (Kotlin/Java)
 #[derive(Parser, Debug)]
 #[command(name = "searchdeadcode")]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to the project directory to analyze
     #[arg(default_value = ".")]
     path: PathBuf,
@@ -81,14 +65,52 @@ struct Cli {
     #[arg(short, long)]
     retain: Vec<String>,
 
+    /// Retain classes and members kept by the project's ProGuard/R8 `-keep` rules
+    /// Reads every .pro file (proguard-rules.pro, consumer-rules.pro, ...)
+    #[arg(long)]
+    respect_keep_rules: bool,
+
+    /// Scan KAPT/KSP generated sources for references to project code
+    /// Nothing declared in generated code is reported
+    #[arg(long)]
+    generated_sources: bool,
+
+    /// Scan C/C++ sources (src/main/cpp, src/main/jni) for classes and
+    /// members reached through JNI
+    #[arg(long)]
+    native_sources: bool,
+
+    /// Treat declarations in test source sets as entry points
+    /// Code only tests use is no longer reported
+    #[arg(long)]
+    tests_as_roots: bool,
+
+    /// Report production code that only tests reach as DC030
+    /// instead of as unreferenced
+    #[arg(long)]
+    report_test_only: bool,
+
+    /// Also analyze each module on its own and report code only other
+    /// modules use (info severity)
+    #[arg(long)]
+    per_module: bool,
+
     /// Output format
     #[arg(short, long, value_enum, default_value = "terminal")]
     format: OutputFormat,
 
-    /// Output file (for json/sarif formats)
+    /// Output file (for json/sarif/markdown/rdjson formats)
     #[arg(short, long)]
     output: Option<PathBuf>,
 
+    /// Attribute findings to the owners of their files in CODEOWNERS
+    #[arg(long)]
+    owners: bool,
+
+    /// Attribute findings to the author who last changed them (git blame)
+    #[arg(long)]
+    blame: bool,
+
     /// Enable safe delete mode
     #[arg(long)]
     delete: bool,
@@ -116,7 +138,12 @@ struct Cli {
 
     /// Minimum confidence level to report (low, medium, high, confirmed)
     #[arg(long, default_value = "medium")]
-    min_confidence: String,
+    min_confidence: Confidence,
+
+    /// Only report findings matching an expression, such as
+    /// `rule in (DC001,DC003) && file ~ "feature/payments" && confidence >= high`
+    #[arg(long, value_name = "EXPR")]
+    filter: Option<FindingFilter>,
 
     /// Only show findings confirmed by runtime coverage
     #[arg(long)]
@@ -130,6 +157,41 @@ struct Cli {
     #[arg(long)]
     detect_cycles: bool,
 
+    /// Also draw the dead cycles as a diagram (mermaid, plantuml)
+    #[arg(long, value_enum, value_name = "FORMAT", requires = "detect_cycles")]
+    cycles_format: Option<CyclesFormat>,
+
+    /// File to write the cycle diagram to (default: stdout)
+    #[arg(long, value_name = "FILE", requires = "cycles_format")]
+    cycles_output: Option<PathBuf>,
+
+    /// Export the reference graph in this format (dot, graphml, json)
+    /// Dead declarations are highlighted
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    export_graph: Option<GraphFormat>,
+
+    /// File to write the exported graph to (default: stdout)
+    #[arg(long, value_name = "FILE")]
+    graph_output: Option<PathBuf>,
+
+    /// Only export dead declarations
+    #[arg(long)]
+    graph_dead_only: bool,
+
+    /// Only export declarations in this package or its subpackages (e.g., "com.example.feed")
+    #[arg(long, value_name = "PREFIX")]
+    graph_package: Option<String>,
+
+    /// Add the declarations, references and findings to a SQLite database
+    /// Requires a build with the `sqlite` feature
+    #[arg(long, value_name = "FILE")]
+    export_db: Option<PathBuf>,
+
+    /// Write an SVG treemap of the packages, sized by lines of code and
+    /// colored by their share of dead code
+    #[arg(long, value_name = "FILE")]
+    treemap: Option<PathBuf>,
+
     /// ProGuard/R8 usage.txt file for enhanced detection
     /// This file lists code that R8 determined is unused
     #[arg(long, value_name = "FILE")]
@@ -165,7 +227,7 @@ struct Cli {
     unused_params: bool,
 
     /// Enable unused resource detection (off by default - slower)
-    /// Finds Android resources (strings, colors, etc.) that are never referenced
+    /// Finds Android resources (strings, colors, drawables, etc.) that are never referenced
     #[arg(long)]
     unused_resources: bool,
 
@@ -179,26 +241,142 @@ struct Cli {
     #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
     sealed_variants: bool,
 
+    /// Enable unused enum case detection (enabled by default)
+    /// Finds enum constants never referenced by name; enumerated enums are reported with low confidence
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_enum_cases: bool,
+
+    /// Enable unused constant detection (enabled by default)
+    /// Finds const val declarations that are never referenced
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_constants: bool,
+
+    /// Enable unused extension detection (enabled by default)
+    /// Finds extension functions and properties with no call sites
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_extensions: bool,
+
+    /// Enable unused type alias detection (enabled by default)
+    /// Finds typealias declarations that are never referenced
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_typealiases: bool,
+
+    /// Enable unused test helper detection (enabled by default)
+    /// Runs reachability inside test source sets from test methods and reports
+    /// unused fixtures, fakes and base test classes under a separate category
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_test_helpers: bool,
+
     /// Enable redundant override detection (off by default - can be intentional)
     /// Finds method overrides that only call super
     #[arg(long)]
     redundant_overrides: bool,
 
+    /// Enable redundant public detection (off by default)
+    /// Finds public and internal declarations only used within their own module
+    #[arg(long)]
+    redundant_public: bool,
+
+    /// Enable stale @Preview detection (off by default)
+    /// Finds previews of removed, preview-only or deprecated composables
+    #[arg(long)]
+    stale_previews: bool,
+
+    /// Enable always-default parameter detection (off by default)
+    /// Finds defaulted parameters that no call site ever passes
+    #[arg(long)]
+    always_default_params: bool,
+
+    /// Enable orphan test class detection (off by default)
+    /// Finds test classes whose production subject no longer exists
+    #[arg(long)]
+    orphan_tests: bool,
+
+    /// Enable dead callback detection (off by default)
+    /// Finds listener/callback interfaces whose methods are never invoked
+    #[arg(long)]
+    dead_callbacks: bool,
+
     /// Enable unused Intent extra detection (enabled by default)
     /// Finds putExtra() keys that are never retrieved via getXxxExtra()
     #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
     unused_extras: bool,
 
+    /// Enable unmatched intent action detection (enabled by default)
+    /// Finds custom ACTION_* strings that are sent but never handled, or handled but never sent
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unmatched_actions: bool,
+
+    /// Enable unused dynamic feature module detection (enabled by default)
+    /// Finds dynamic feature modules that no SplitInstall request or navigation reference targets
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_dynamic_features: bool,
+
+    /// Enable stale consumer ProGuard rule detection (enabled by default)
+    /// Finds consumer-rules.pro keep rules of classes the library module no longer declares
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    stale_consumer_rules: bool,
+
+    /// Enable unused version catalog entry detection (enabled by default)
+    /// Finds gradle/*.versions.toml entries that no build script uses
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_catalog_entries: bool,
+
+    /// Enable unused AIDL method detection (enabled by default)
+    /// Finds methods of AIDL interfaces the project binds to that no client calls
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_aidl_methods: bool,
+
+    /// Enable unused protobuf message detection
+    /// Finds messages in .proto files that no Kotlin or Java code builds or parses
+    #[arg(long)]
+    unused_proto_messages: bool,
+
+    /// Enable unregistered BroadcastReceiver detection (enabled by default)
+    /// Finds receivers missing from the manifest and never passed to registerReceiver()
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unregistered_receivers: bool,
+
+    /// Enable legacy Parcelable boilerplate detection (enabled by default)
+    /// Finds hand-written CREATOR/writeToParcel on @Parcelize classes or classes never parceled
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    legacy_parcelables: bool,
+
+    /// Enable never-enqueued Worker detection (enabled by default)
+    /// Finds WorkManager workers that no work request or worker factory names
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_workers: bool,
+
+    /// Enable unused Koin definition detection (enabled by default)
+    /// Finds single/factory/viewModel definitions whose type is never injected
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_koin: bool,
+
+    /// Enable unused Dagger/Hilt binding detection (enabled by default)
+    /// Finds @Provides/@Binds methods and @Module classes whose type is never injected
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    unused_bindings: bool,
+
     /// Enable write-only SharedPreferences detection (enabled by default)
     /// Finds SharedPreferences keys that are written but never read
     #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
     write_only_prefs: bool,
 
+    /// Enable write-only DataStore detection (enabled by default)
+    /// Finds Preferences DataStore keys that are written in edit {} but never read
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    write_only_datastore: bool,
+
     /// Enable write-only Room DAO detection (enabled by default)
     /// Finds Room DAOs that have @Insert but no @Query methods
     #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
     write_only_dao: bool,
 
+    /// Enable write-only Room column detection (enabled by default)
+    /// Finds @Entity columns that are inserted but never named by any @Query
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    write_only_columns: bool,
+
     /// Enable all anti-pattern detectors (AP001-AP034)
     /// Includes: architecture, performance, Kotlin, Android, and Compose patterns
     #[arg(long)]
@@ -233,8 +411,18 @@ struct Cli {
     #[arg(long)]
     compose_patterns: bool,
 
+    /// Load a detector plugin compiled to WebAssembly (can be repeated)
+    /// Requires a build with the `wasm-plugins` feature
+    #[arg(long, value_name = "FILE")]
+    plugin: Vec<PathBuf>,
+
+    /// Abort the analysis if it runs longer than this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
+
     /// Enable incremental analysis with caching (enabled by default)
-    /// Skips re-parsing unchanged files for faster subsequent runs
+    /// Skips re-parsing unchanged files for faster subsequent runs; in watch
+    /// mode, changed files only have their edited ranges re-parsed
     #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
     incremental: bool,
 
@@ -255,6 +443,12 @@ struct Cli {
     #[arg(long, value_name = "FILE")]
     generate_baseline: Option<PathBuf>,
 
+    /// Exit with a failure status on a finding of this severity or above
+    /// (info, warning, error), or on this many findings
+    /// Exits with 2 when errors are among the findings, 1 otherwise
+    #[arg(long, value_name = "SEVERITY|COUNT")]
+    fail_on: Option<gate::FailOn>,
+
     /// Watch mode - continuously monitor for changes
     #[arg(long)]
     watch: bool,
@@ -271,6 +465,11 @@ struct Cli {
     #[arg(long, value_name = "SHELL")]
     completions: Option<Shell>,
 
+    /// Print the JSON Schema of `--format json` reports, or write it to
+    /// --output
+    #[arg(long)]
+    schema: bool,
+
     /// Summary output - show statistics and top issues only
     #[arg(long)]
     summary: bool,
@@ -279,10 +478,15 @@ struct Cli {
     #[arg(long)]
     compact: bool,
 
-    /// Group results by: rule, category, severity, file
+    /// Group results by: rule, category, severity, file, module, owner
     #[arg(long, value_name = "MODE")]
     group_by: Option<String>,
 
+    /// Show each finding's source line with this many lines before and
+    /// after it (terminal, JSON and SARIF output)
+    #[arg(long, value_name = "LINES")]
+    context: Option<usize>,
+
     /// Expand all collapsed groups (show every issue)
     #[arg(long)]
     expand: bool,
@@ -296,6 +500,44 @@ struct Cli {
     top: usize,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Explain why a declaration is reachable, or why it is reported
+    ///
+    /// Prints the shortest reference path from an entry point to it, or the
+    /// findings on it when nothing reaches it
+    Trace {
+        /// Declaration to trace: fully qualified name (com.example.Foo),
+        /// member path (Foo.bar) or simple name
+        symbol: String,
+    },
+    /// Summarize the reference graph for architecture reviews
+    ///
+    /// Prints its size, fan-in and fan-out distributions, the most
+    /// referenced declarations and the packages nothing else references;
+    /// `--format json` prints them as JSON
+    Stats {
+        /// Number of declarations to list in each ranking
+        #[arg(long, default_value = "10")]
+        top: usize,
+    },
+    /// Compare two JSON reports
+    ///
+    /// Prints the findings the new report adds, the ones it no longer has
+    /// and the ones both have, paired like baselines pair findings;
+    /// `--format json` prints them as JSON
+    Diff {
+        /// Report of the earlier run, written with `--format json`
+        old: PathBuf,
+        /// Report of the later run
+        new: PathBuf,
+        /// Exit with an error when the new report has findings the old one
+        /// does not
+        #[arg(long)]
+        fail_on_new: bool,
+    },
+}
+
 #[derive(clap::ValueEnum, Clone, Debug, Default)]
 enum OutputFormat {
     #[default]
@@ -303,6 +545,22 @@ enum OutputFormat {
     Compact,
     Json,
     Sarif,
+    Markdown,
+    Rdjson,
+    Csv,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum CyclesFormat {
+    Mermaid,
+    Plantuml,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum GraphFormat {
+    Dot,
+    Graphml,
+    Json,
 }
 
 /// Determine the report format from CLI options
@@ -327,10 +585,13 @@ fn determine_report_format(cli: &Cli) -> report::ReportFormat {
         OutputFormat::Compact => report::ReportFormat::Compact,
         OutputFormat::Json => report::ReportFormat::Json,
         OutputFormat::Sarif => report::ReportFormat::Sarif,
+        OutputFormat::Markdown => report::ReportFormat::Markdown,
+        OutputFormat::Rdjson => report::ReportFormat::Rdjson,
+        OutputFormat::Csv => report::ReportFormat::Csv,
     }
 }
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
     let cli = Cli::parse();
 
     // Handle shell completions
@@ -338,26 +599,49 @@ fn main() -> Result<()> {
         let mut cmd = Cli::command();
         let name = cmd.get_name().to_string();
         generate(shell, &mut cmd, name, &mut std::io::stdout());
-        return Ok(());
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if cli.schema {
+        match &cli.output {
+            Some(path) => std::fs::write(path, report::JSON_SCHEMA).into_diagnostic()?,
+            None => print!("{}", report::JSON_SCHEMA),
+        }
+        return Ok(ExitCode::SUCCESS);
     }
 
     // Initialize logging
     init_logging(cli.verbose, cli.quiet);
 
+    if let Some(Command::Diff {
+        old,
+        new,
+        fail_on_new,
+    }) = &cli.command
+    {
+        return run_diff(&cli, old, new, *fail_on_new).map(|_| ExitCode::SUCCESS);
+    }
+
     info!("SearchDeadCode v{}", env!("CARGO_PKG_VERSION"));
 
     // Load configuration
     let config = load_config(&cli)?;
 
+    if let Some(Command::Trace { symbol }) = &cli.command {
+        return run_trace(&config, &cli, symbol).map(|_| ExitCode::SUCCESS);
+    }
+    if let Some(Command::Stats { top }) = &cli.command {
+        return run_stats(&config, &cli, *top).map(|_| ExitCode::SUCCESS);
+    }
+
     // Watch mode
     if cli.watch {
         run_watch_mode(&config, &cli)?;
+        Ok(ExitCode::SUCCESS)
     } else {
         // Run analysis once
-        run_analysis(&config, &cli)?;
+        run_analysis(&config, &cli)
     }
-
-    Ok(())
 }
 
 fn run_watch_mode(config: &Config, cli: &Cli) -> Result<()> {
@@ -365,46 +649,25 @@ fn run_watch_mode(config: &Config, cli: &Cli) -> Result<()> {
 
     let watcher = FileWatcher::new();
 
-    // Clone what we need for the closure
-    let config = config.clone();
-    let cli_path = cli.path.clone();
-    let cli_format = cli.format.clone();
-    let cli_output = cli.output.clone();
-    let cli_verbose = cli.verbose;
-    let cli_quiet = cli.quiet;
-    let cli_deep = cli.deep;
-    let cli_parallel = cli.parallel;
-    let cli_enhanced = cli.enhanced;
-    let cli_detect_cycles = cli.detect_cycles;
-    let cli_min_confidence = cli.min_confidence.clone();
-    let cli_baseline = cli.baseline.clone();
-    let cli_coverage = cli.coverage.clone();
-    let cli_proguard_usage = cli.proguard_usage.clone();
+    // Trees from the previous run, so changed files are re-parsed incrementally
+    let trees = cli
+        .incremental
+        .then(|| std::sync::Arc::new(parser::TreeCache::new()));
+    // Parsed files, loaded once and saved after every run
+    let graph_cache = load_graph_cache(config, cli);
+    // Graph of the previous run, updated with the changed files only
+    let mut last_graph = None;
 
     watcher
-        .watch(&cli.path, move || {
-            // Suppress output for repeated runs except results
-            if !cli_verbose {
-                // Temporarily change log level
-            }
-
-            // Re-run analysis
-            match run_analysis_internal(
-                &config,
-                &cli_path,
-                cli_format.clone(),
-                cli_output.clone(),
-                cli_deep,
-                cli_parallel,
-                cli_enhanced,
-                cli_detect_cycles,
-                &cli_min_confidence,
-                &cli_baseline,
-                &cli_coverage,
-                &cli_proguard_usage,
-                cli_quiet,
-            ) {
-                Ok(_) => {
+        .watch(&cli.path, |changed| {
+            // Re-run analysis; the quality gate only decides the exit code of
+            // a single run
+            let base = last_graph.take().map(|graph| (graph, changed.to_vec()));
+            match analyze(config, cli, graph_cache.as_ref(), trees.clone(), base) {
+                Ok((_, graph)) => {
+                    if cli.incremental {
+                        last_graph = Some(graph);
+                    }
                     println!();
                     println!("{}", "✓ Analysis complete. Waiting for changes...".green());
                     true
@@ -420,167 +683,353 @@ fn run_watch_mode(config: &Config, cli: &Cli) -> Result<()> {
     Ok(())
 }
 
-/// Internal analysis function for watch mode
-#[allow(clippy::too_many_arguments)]
-fn run_analysis_internal(
+/// Session running the analysis the command line asks for
+fn analysis_session(
     config: &Config,
-    path: &std::path::Path,
-    format: OutputFormat,
-    output: Option<PathBuf>,
-    deep: bool,
-    parallel: bool,
-    enhanced: bool,
-    detect_cycles: bool,
-    min_confidence: &str,
-    baseline_path: &Option<PathBuf>,
-    coverage_files: &[PathBuf],
-    proguard_usage: &Option<PathBuf>,
-    quiet: bool,
-) -> Result<()> {
-    use colored::Colorize;
-    use std::time::Instant;
+    cli: &Cli,
+    cancel: CancellationToken,
+) -> Result<AnalysisSessionBuilder> {
+    let mut config = config.clone();
+    config.detection = detection_config(&config, cli);
+    // `load_config` resolved the plugins against the project root already,
+    // so they go into the registry here rather than through the session
+    let mut registry = DetectorRegistry::with_builtins();
+    registry.load_plugins(&std::mem::take(&mut config.plugins))?;
+    let mode = if cli.deep {
+        AnalysisMode::Deep
+    } else if cli.enhanced {
+        AnalysisMode::Enhanced
+    } else {
+        AnalysisMode::Standard
+    };
 
-    let start_time = Instant::now();
+    let mut session = AnalysisSession::builder()
+        .path(&cli.path)
+        .config(config)
+        .registry(registry)
+        .mode(mode)
+        .parallel(cli.parallel)
+        .min_confidence(cli.min_confidence)
+        .runtime_only(cli.runtime_only)
+        .with_coverage(cli.coverage.iter())
+        .cancellation(cancel);
+    if let Some(usage) = &cli.proguard_usage {
+        session = session.with_proguard_usage(usage);
+    }
+    Ok(session)
+}
 
-    // Discover files
-    let finder = FileFinder::new(config);
-    let files = finder.find_files(path)?;
+/// Explain each declaration `symbol` names: the shortest reference path
+/// from an entry point when it is reachable, and the findings on it
+fn run_trace(config: &Config, cli: &Cli, symbol: &str) -> Result<()> {
+    // Every finding explains something, whatever its confidence
+    let results = analysis_session(config, cli, cancellation(cli.timeout))?
+        .min_confidence(Confidence::Low)
+        .runtime_only(false)
+        .run()?;
+    let graph = &results.graph;
+
+    let declarations = graph.find_symbol(symbol);
+    if declarations.is_empty() {
+        return Err(miette::miette!("No declaration named '{}'", symbol));
+    }
 
-    if files.is_empty() {
-        if !quiet {
-            println!("{}", "No Kotlin or Java files found.".yellow());
+    let relative = |file: &std::path::Path| {
+        file.strip_prefix(&cli.path)
+            .unwrap_or(file)
+            .display()
+            .to_string()
+    };
+    for decl in declarations {
+        println!();
+        println!(
+            "{} {} '{}'  {}",
+            "🔎".bold(),
+            decl.kind.display_name(),
+            decl.fully_qualified_name.as_deref().unwrap_or(&decl.name).bold(),
+            format!("{}:{}", relative(&decl.location.file), decl.location.line).dimmed()
+        );
+
+        let path = results
+            .reachable
+            .contains(&decl.id)
+            .then(|| graph.shortest_path(&results.entry_points, &decl.id))
+            .flatten();
+        match path {
+            Some(path) => {
+                println!("  {}", "Reachable from an entry point:".green());
+                for step in path {
+                    let label = match step.link {
+                        None => "entry point",
+                        Some(link) => path_link_label(link),
+                    };
+                    println!(
+                        "    {:<14} {} '{}'  {}",
+                        label.cyan(),
+                        step.declaration.kind.display_name(),
+                        step.declaration.name,
+                        format!(
+                            "{}:{}",
+                            relative(&step.declaration.location.file),
+                            step.declaration.location.line
+                        )
+                        .dimmed()
+                    );
+                }
+            }
+            None if results.reachable.contains(&decl.id) => {
+                println!("  {}", "Reachable (retained by the analysis)".green());
+            }
+            None => {
+                println!("  {}", "Not reachable from any entry point".yellow());
+            }
         }
-        return Ok(());
-    }
 
-    // Parse and build graph
-    let graph = if parallel {
-        let parallel_builder = ParallelGraphBuilder::new();
-        parallel_builder.build_from_files(&files)?
-    } else {
-        let mut graph_builder = GraphBuilder::new();
-        for file in &files {
-            graph_builder.process_file(file)?;
+        let findings: Vec<_> = results
+            .dead_code
+            .iter()
+            .filter(|dc| dc.declaration.id == decl.id)
+            .collect();
+        let parent_reported = decl.parent.as_ref().is_some_and(|parent| {
+            results
+                .dead_code
+                .iter()
+                .any(|dc| &dc.declaration.id == parent)
+        });
+        if findings.is_empty() && parent_reported && !results.reachable.contains(&decl.id) {
+            println!("  {}", "Reported with its declaring type".dimmed());
+        } else if findings.is_empty() {
+            println!("  {}", "No findings".dimmed());
+        } else {
+            println!("  {}", "Reported as:".yellow());
+            for finding in findings {
+                println!(
+                    "    {} {} ({} confidence)",
+                    finding.issue.code().bold(),
+                    finding.message,
+                    finding.confidence
+                );
+            }
         }
-        graph_builder.build()
-    };
+    }
 
-    // Detect entry points
-    let entry_detector = EntryPointDetector::new(config);
-    let entry_points = entry_detector.detect(&graph, path)?;
+    Ok(())
+}
 
-    // Load ProGuard data if available
-    let proguard_data = if let Some(ref usage_path) = proguard_usage {
-        ProguardUsage::parse(usage_path).ok()
-    } else {
-        None
+fn run_diff(
+    cli: &Cli,
+    old: &std::path::Path,
+    new: &std::path::Path,
+    fail_on_new: bool,
+) -> Result<()> {
+    let load = |path: &std::path::Path| {
+        baseline::load_report(path)
+            .map_err(|e| miette::miette!("Failed to load report {}: {}", path.display(), e))
     };
+    let diff = baseline::ReportDiff::compare(load(old)?, load(new)?);
 
-    // Run reachability analysis
-    let (dead_code, reachable) = if deep {
-        let analyzer = DeepAnalyzer::new()
-            .with_parallel(parallel)
-            .with_unused_members(true);
-        analyzer.analyze(&graph, &entry_points)
-    } else if enhanced && proguard_data.is_some() {
-        let mut analyzer = EnhancedAnalyzer::new();
-        if let Some(pg) = proguard_data.clone() {
-            analyzer = analyzer.with_proguard(pg);
+    if matches!(cli.format, OutputFormat::Json) {
+        let json = serde_json::to_string_pretty(&diff).into_diagnostic()?;
+        match &cli.output {
+            Some(path) => std::fs::write(path, json + "\n").into_diagnostic()?,
+            None => println!("{}", json),
         }
-        analyzer.analyze(&graph, &entry_points)
     } else {
-        let analyzer = ReachabilityAnalyzer::new();
-        analyzer.find_unreachable_with_reachable(&graph, &entry_points)
-    };
-
-    // Load coverage data
-    let coverage_data = if !coverage_files.is_empty() {
-        parse_coverage_files(coverage_files).ok()
-    } else {
-        None
-    };
-
-    // Enhance findings
-    let mut hybrid = HybridAnalyzer::new();
-    if let Some(coverage) = coverage_data {
-        hybrid = hybrid.with_coverage(coverage);
-    }
-    if let Some(proguard) = proguard_data {
-        hybrid = hybrid.with_proguard(proguard);
+        println!();
+        println!(
+            "{} {} new, {} fixed, {} persisting",
+            "📋 Report diff:".bold(),
+            diff.new.len(),
+            diff.fixed.len(),
+            diff.persisting.len()
+        );
+        for (title, issues) in [
+            ("New".red().bold(), &diff.new),
+            ("Fixed".green().bold(), &diff.fixed),
+            ("Persisting".dimmed().bold(), &diff.persisting),
+        ] {
+            if issues.is_empty() {
+                continue;
+            }
+            println!();
+            println!("{}", title);
+            for issue in issues {
+                println!(
+                    "  {}  {}  {}",
+                    format!("{}:{}", issue.file, issue.line).dimmed(),
+                    issue.code.bold(),
+                    issue.message
+                );
+            }
+        }
     }
 
-    let dead_code = hybrid.enhance_findings(dead_code);
+    if fail_on_new && !diff.new.is_empty() {
+        miette::bail!("{} new findings", diff.new.len());
+    }
+    Ok(())
+}
 
-    // Filter by confidence
-    let min_conf = parse_confidence(min_confidence);
-    let dead_code: Vec<_> = dead_code
-        .into_iter()
-        .filter(|dc| dc.confidence >= min_conf)
-        .collect();
+fn run_stats(config: &Config, cli: &Cli, top: usize) -> Result<()> {
+    let cancel = cancellation(cli.timeout);
+    let files = FileFinder::new(config)
+        .with_cancellation(cancel.clone())
+        .find_files(&cli.path)?;
 
-    // Apply baseline filter
-    let dead_code = if let Some(ref bp) = baseline_path {
-        match baseline::Baseline::load(bp) {
-            Ok(baseline) => {
-                let stats = baseline.stats(&dead_code, path);
-                if !quiet {
-                    println!("{}", format!("📋 Baseline: {}", stats).cyan());
-                }
-                baseline
-                    .filter_new(&dead_code, path)
-                    .into_iter()
-                    .cloned()
-                    .collect()
-            }
-            Err(_) => dead_code,
+    let graph_cache = load_graph_cache(config, cli);
+    let graph = if cli.parallel {
+        let mut builder = ParallelGraphBuilder::new().with_cancellation(cancel);
+        if let Some((cache, _)) = &graph_cache {
+            builder = builder.with_graph_cache(cache.clone());
         }
+        builder.build_from_files(&files)?
     } else {
-        dead_code
+        let mut builder = GraphBuilder::new();
+        if let Some((cache, _)) = &graph_cache {
+            builder = builder.with_graph_cache(cache.clone());
+        }
+        for file in &files {
+            cancel.check()?;
+            builder.process_file(file)?;
+        }
+        builder.build()
     };
+    if let Some(graph_cache) = &graph_cache {
+        save_graph_cache(graph_cache, &files);
+    }
 
-    // Detect cycles if requested
-    if detect_cycles {
-        let cycle_detector = CycleDetector::new();
-        let cycle_stats = cycle_detector.get_cycle_stats(&graph, &reachable);
-        if cycle_stats.has_cycles() && !quiet {
-            println!(
-                "{}",
-                format!(
-                    "🧟 {} dead cycles ({} declarations)",
-                    cycle_stats.num_dead_cycles, cycle_stats.total_declarations_in_cycles
-                )
-                .yellow()
-            );
+    let mut stats = graph.stats(top);
+    for ranked in stats
+        .most_referenced
+        .iter_mut()
+        .chain(stats.most_dependent.iter_mut())
+    {
+        if let Ok(relative) = ranked.file.strip_prefix(&cli.path) {
+            ranked.file = relative.to_path_buf();
         }
     }
 
-    // Report results
-    let report_format = match format {
-        OutputFormat::Terminal => report::ReportFormat::Terminal,
-        OutputFormat::Compact => report::ReportFormat::Compact,
-        OutputFormat::Json => report::ReportFormat::Json,
-        OutputFormat::Sarif => report::ReportFormat::Sarif,
+    if matches!(cli.format, OutputFormat::Json) {
+        let json = serde_json::to_string_pretty(&stats).into_diagnostic()?;
+        return match &cli.output {
+            Some(path) => std::fs::write(path, json + "\n").into_diagnostic(),
+            None => {
+                println!("{}", json);
+                Ok(())
+            }
+        };
+    }
+
+    println!();
+    println!("{}", "📊 Graph statistics".bold());
+    println!(
+        "  {} files, {} packages, {} declarations, {} references, {} cycles",
+        stats.files, stats.packages, stats.declarations, stats.references, stats.cycles
+    );
+    let by_kind = |counts: &std::collections::BTreeMap<&str, usize>| {
+        let mut counts: Vec<_> = counts.iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        counts
+            .iter()
+            .map(|(kind, count)| format!("{} {}", count, kind))
+            .collect::<Vec<_>>()
+            .join(", ")
     };
-    let reporter = Reporter::new(report_format, output);
-    reporter.report(&dead_code)?;
+    println!(
+        "  {} {}",
+        "Declarations:".dimmed(),
+        by_kind(&stats.declarations_by_kind)
+    );
+    println!(
+        "  {} {}",
+        "References:".dimmed(),
+        by_kind(&stats.references_by_kind)
+    );
 
-    // Print timing
-    let elapsed = start_time.elapsed();
-    if !quiet {
+    for (title, distribution, ranking) in [
+        ("Fan-in", &stats.fan_in, &stats.most_referenced),
+        ("Fan-out", &stats.fan_out, &stats.most_dependent),
+    ] {
+        println!();
         println!(
-            "{}",
-            format!(
-                "⏱  Analyzed {} files in {:.2}s",
-                files.len(),
-                elapsed.as_secs_f64()
-            )
-            .dimmed()
+            "{}  min {}, median {}, mean {:.1}, p90 {}, max {}",
+            title.bold(),
+            distribution.min,
+            distribution.median,
+            distribution.mean,
+            distribution.p90,
+            distribution.max
         );
+        let widest = distribution
+            .histogram
+            .iter()
+            .map(|bucket| bucket.declarations)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for bucket in &distribution.histogram {
+            let bar = "█".repeat((bucket.declarations * 30).div_ceil(widest));
+            println!(
+                "  {:>6}  {:<30} {}",
+                bucket.range,
+                bar.cyan(),
+                bucket.declarations
+            );
+        }
+        if !ranking.is_empty() {
+            let heading = format!("Highest {}:", title.to_lowercase());
+            println!("  {}", heading.dimmed());
+        }
+        for ranked in ranking {
+            println!(
+                "  {:>6}  {} '{}'  {}",
+                ranked.count,
+                ranked.kind,
+                ranked
+                    .fully_qualified_name
+                    .as_deref()
+                    .unwrap_or(&ranked.name),
+                format!("{}:{}", ranked.file.display(), ranked.line).dimmed()
+            );
+        }
+    }
+
+    println!();
+    if stats.orphaned_packages.is_empty() {
+        println!("{}", "No orphaned packages".dimmed());
+    } else {
+        println!("{}", "Packages nothing else references:".yellow());
+        for package in &stats.orphaned_packages {
+            println!("  {}", package);
+        }
     }
 
     Ok(())
 }
 
+/// How a trace step is reached from the previous one, for display
+fn path_link_label(link: PathLink) -> &'static str {
+    match link {
+        PathLink::Member => "member",
+        PathLink::Parent => "declared in",
+        PathLink::Reference(kind) => match kind {
+            ReferenceKind::Call => "calls",
+            ReferenceKind::Read => "reads",
+            ReferenceKind::Write => "writes",
+            ReferenceKind::Inheritance => "extends",
+            ReferenceKind::Override => "overrides",
+            ReferenceKind::Import => "imports",
+            ReferenceKind::Instantiation => "instantiates",
+            ReferenceKind::Annotation | ReferenceKind::AnnotationUse => "annotated with",
+            ReferenceKind::Reflection => "class literal",
+            ReferenceKind::Delegation => "delegates to",
+            ReferenceKind::SealedSubtype => "sealed subtype",
+            ReferenceKind::EnumValues => "enumerates",
+            _ => "uses type",
+        },
+    }
+}
+
 fn init_logging(verbose: bool, quiet: bool) {
     use tracing_subscriber::{fmt, EnvFilter};
 
@@ -613,177 +1062,172 @@ fn load_config(cli: &Cli) -> Result<Config> {
     if !cli.retain.is_empty() {
         config.retain_patterns.extend(cli.retain.clone());
     }
+    if cli.generated_sources {
+        config.generated.enabled = true;
+    }
+    if cli.native_sources {
+        config.native.enabled = true;
+    }
+    if cli.respect_keep_rules {
+        config.respect_keep_rules = true;
+    }
+    if cli.tests_as_roots {
+        config.tests_as_roots = true;
+    }
+    if cli.report_test_only {
+        config.report_test_only = true;
+    }
+    if cli.per_module {
+        config.per_module = true;
+    }
+    if config.respect_keep_rules {
+        config.add_keep_rule_patterns(&cli.path);
+    }
+
+    // Plugins from the config file are relative to the project root
+    config.plugins = config.plugins.iter().map(|p| cli.path.join(p)).collect();
+    config.plugins.extend(cli.plugin.clone());
 
     Ok(config)
 }
 
-fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
+/// Analyze once and report; the exit code is the quality gate's
+fn run_analysis(config: &Config, cli: &Cli) -> Result<ExitCode> {
+    let graph_cache = load_graph_cache(config, cli);
+    let (code, _) = analyze(config, cli, graph_cache.as_ref(), None, None)?;
+    Ok(code)
+}
+
+/// Analyze and report, with the parse caches and the graph of earlier runs
+/// in watch mode
+///
+/// `base` is the previous run's graph and the files changed since. The graph
+/// of this run is returned with the exit code.
+fn analyze(
+    config: &Config,
+    cli: &Cli,
+    graph_cache: Option<&GraphCacheFile>,
+    trees: Option<std::sync::Arc<parser::TreeCache>>,
+    base: Option<(Graph, Vec<PathBuf>)>,
+) -> Result<(ExitCode, Graph)> {
     use colored::Colorize;
-    use indicatif::{ProgressBar, ProgressStyle};
+    use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
     use std::time::Instant;
 
     let start_time = Instant::now();
+    let cancel = cancellation(cli.timeout);
 
-    // Step 1: Discover files
-    info!("Discovering files...");
-    let finder = FileFinder::new(config);
-    let files = finder.find_files(&cli.path)?;
-
-    info!("Found {} files to analyze", files.len());
-
-    if files.is_empty() {
-        println!("{}", "No Kotlin or Java files found.".yellow());
-        return Ok(());
+    // Checked before the analysis, so a bad `fail_on` fails fast
+    let mut gate = gate::Gate::from_config(&config.gate)?;
+    if let Some(fail_on) = cli.fail_on {
+        gate.fail_on = Some(fail_on);
     }
 
-    // Step 2: Parse files and build graph
-    let graph = if cli.parallel {
-        // Parallel parsing mode
-        if !cli.quiet {
-            eprintln!(
-                "{}",
-                format!("⚡ Parallel mode: parsing {} files...", files.len()).cyan()
-            );
-        }
-        let parallel_builder = ParallelGraphBuilder::new();
-        parallel_builder.build_from_files(&files)?
-    } else {
-        // Sequential parsing mode
-        let pb = ProgressBar::new(files.len() as u64);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template(
-                    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})",
-                )
-                .unwrap()
-                .progress_chars("#>-"),
+    // Steps 1-9b run in the analysis session: discover and parse the files
+    // (ProGuard usage.txt and coverage reports are parsed alongside), detect
+    // entry points, run reachability, enhance the findings with coverage and
+    // ProGuard data and run the registered detectors (unused params,
+    // write-only variables, sealed variants, redundant overrides, the rules
+    // reading manifests, build scripts and other project files, the enabled
+    // anti-pattern groups and any WASM plugins)
+    if cli.deep {
+        eprintln!(
+            "{}",
+            "🔬 Deep mode: aggressive dead code detection...".cyan()
         );
-
-        info!("Parsing files...");
-        let mut graph_builder = GraphBuilder::new();
-
-        for file in &files {
-            graph_builder.process_file(file)?;
-            pb.inc(1);
-        }
-        pb.finish_with_message("Parsing complete");
-
-        graph_builder.build()
-    };
-
-    let parse_time = start_time.elapsed();
-    if cli.parallel && !cli.quiet {
+    } else if cli.enhanced && cli.proguard_usage.is_some() {
         eprintln!(
             "{}",
-            format!(
-                "⚡ Parsed {} files in {:.2}s",
-                files.len(),
-                parse_time.as_secs_f64()
-            )
-            .green()
+            "🔍 Enhanced mode: cross-validating with ProGuard data...".cyan()
         );
     }
+    let mut session =
+        analysis_session(config, cli, cancel)?.include_runtime_dead(cli.include_runtime_dead);
+    if let Some((cache, _)) = graph_cache {
+        session = session.graph_cache(cache.clone());
+    }
+    if let Some(trees) = trees {
+        session = session.tree_cache(trees);
+    }
+    if let Some((graph, changed)) = base {
+        session = session.base_graph(graph, changed);
+    }
 
-    // Step 3: Detect entry points
-    info!("Detecting entry points...");
-    let entry_detector = EntryPointDetector::new(config);
-    let entry_points = entry_detector.detect(&graph, &cli.path)?;
-
-    info!("Found {} entry points", entry_points.len());
-
-    // Step 4: Load ProGuard data early if available (needed for enhanced mode)
-    let proguard_data = if let Some(ref usage_path) = cli.proguard_usage {
-        info!("Loading ProGuard usage.txt from {:?}...", usage_path);
-        match ProguardUsage::parse(usage_path) {
-            Ok(data) => {
-                let stats = data.stats();
-                info!("ProGuard usage: {}", stats);
-                println!(
+    // Parsing shows a progress bar, or the file count and time in parallel mode
+    let (parallel, quiet) = (cli.parallel, cli.quiet);
+    let pb = ProgressBar::hidden();
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template(
+                "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})",
+            )
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+    let bar = pb.clone();
+    let session = session
+        .on_parse_progress(move |parsed, total| {
+            if parallel && quiet {
+                return;
+            }
+            if parallel && parsed == 0 {
+                eprintln!(
+                    "{}",
+                    format!("⚡ Parallel mode: parsing {} files...", total).cyan()
+                );
+                return;
+            }
+            if parallel {
+                eprintln!(
                     "{}",
                     format!(
-                        "📋 ProGuard usage.txt: {} unused items ({} classes, {} methods)",
-                        stats.total, stats.classes, stats.methods
+                        "⚡ Parsed {} files in {:.2}s",
+                        total,
+                        start_time.elapsed().as_secs_f64()
                     )
-                    .cyan()
+                    .green()
                 );
-                Some(data)
+                return;
             }
-            Err(e) => {
-                eprintln!("{}: Failed to load usage.txt: {}", "Warning".yellow(), e);
-                None
+            if parsed == 0 {
+                info!("Parsing files...");
+                bar.set_draw_target(ProgressDrawTarget::stderr());
+                bar.set_length(total as u64);
             }
-        }
-    } else {
-        None
-    };
+            bar.set_position(parsed as u64);
+            if parsed == total {
+                bar.finish_with_message("Parsing complete");
+            }
+        })
+        .build()?;
+    let AnalysisResults {
+        dead_code,
+        graph,
+        reachable,
+        files,
+        proguard: proguard_data,
+        ..
+    } = session.run()?;
 
-    // Step 5: Run reachability analysis (deep, enhanced, or standard)
-    info!("Running reachability analysis...");
+    if files.is_empty() {
+        println!("{}", "No Kotlin or Java files found.".yellow());
+        return Ok((ExitCode::SUCCESS, graph));
+    }
+    if let Some(graph_cache) = graph_cache {
+        save_graph_cache(graph_cache, &files);
+    }
 
-    let (dead_code, reachable) = if cli.deep {
-        // Deep analysis mode - most aggressive
-        eprintln!(
-            "{}",
-            "🔬 Deep mode: aggressive dead code detection...".cyan()
-        );
-        let deep = DeepAnalyzer::new()
-            .with_parallel(cli.parallel)
-            .with_unused_members(true);
-        deep.analyze(&graph, &entry_points)
-    } else if cli.enhanced && proguard_data.is_some() {
-        // Enhanced mode with ProGuard cross-validation
-        eprintln!(
+    if let Some(proguard) = &proguard_data {
+        let stats = proguard.stats();
+        println!(
             "{}",
-            "🔍 Enhanced mode: cross-validating with ProGuard data...".cyan()
-        );
-        let mut enhanced = EnhancedAnalyzer::new();
-        if let Some(pg) = proguard_data.clone() {
-            enhanced = enhanced.with_proguard(pg);
-        }
-        enhanced.analyze(&graph, &entry_points)
-    } else if cli.parallel {
-        // Standard analysis with parallel analyzer
-        let enhanced = EnhancedAnalyzer::new();
-        enhanced.analyze(&graph, &entry_points)
-    } else {
-        // Standard sequential analysis
-        let analyzer = ReachabilityAnalyzer::new();
-        analyzer.find_unreachable_with_reachable(&graph, &entry_points)
-    };
-
-    info!(
-        "Reachability: {} reachable, {} total",
-        reachable.len(),
-        graph.declarations().count()
-    );
-
-    // Step 6: Load coverage data if provided
-    let coverage_data = if !cli.coverage.is_empty() {
-        info!(
-            "Loading coverage data from {} file(s)...",
-            cli.coverage.len()
+            format!(
+                "📋 ProGuard usage.txt: {} unused items ({} classes, {} methods)",
+                stats.total, stats.classes, stats.methods
+            )
+            .cyan()
         );
-        match parse_coverage_files(&cli.coverage) {
-            Ok(data) => {
-                let stats = data.stats();
-                info!(
-                    "Coverage: {} files, {} classes ({:.1}% covered), {} methods ({:.1}% covered)",
-                    stats.total_files,
-                    stats.total_classes,
-                    stats.class_coverage_percent(),
-                    stats.total_methods,
-                    stats.method_coverage_percent()
-                );
-                Some(data)
-            }
-            Err(e) => {
-                eprintln!("{}: Failed to load coverage: {}", "Warning".yellow(), e);
-                None
-            }
-        }
-    } else {
-        None
-    };
+    }
 
     // Step 7: Generate filtered report if requested
     if let Some(ref report_path) = cli.generate_report {
@@ -816,73 +1260,11 @@ fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
         }
     }
 
-    // Step 8: Enhance findings with hybrid analysis
-    let mut hybrid = HybridAnalyzer::new();
-    if let Some(coverage) = coverage_data {
-        hybrid = hybrid.with_coverage(coverage);
-    }
-    if let Some(proguard) = proguard_data.clone() {
-        hybrid = hybrid.with_proguard(proguard);
-    }
-
-    let mut dead_code = hybrid.enhance_findings(dead_code);
-
-    // Step 9: Find runtime-dead code (reachable but never executed)
-    if cli.include_runtime_dead {
-        let runtime_dead = hybrid.find_runtime_dead_code(&graph, &reachable);
-        if !runtime_dead.is_empty() {
-            info!(
-                "Found {} additional runtime-dead code items",
-                runtime_dead.len()
-            );
-            dead_code.extend(runtime_dead);
-        }
-    }
-
-    // Step 9b: Detect unused parameters
-    if cli.unused_params {
-        let param_detector = UnusedParamDetector::new();
-        let unused_params = param_detector.detect(&graph);
-        if !unused_params.is_empty() {
-            info!("Found {} unused parameters", unused_params.len());
-            dead_code.extend(unused_params);
-        }
-    }
-
-    // Step 9c: Detect write-only variables (Phase 9)
-    if cli.write_only {
-        let write_only_detector = WriteOnlyDetector::new();
-        let write_only_vars = write_only_detector.detect(&graph);
-        if !write_only_vars.is_empty() {
-            info!("Found {} write-only variables", write_only_vars.len());
-            dead_code.extend(write_only_vars);
-        }
-    }
-
-    // Step 9d: Detect unused sealed variants (Phase 10)
-    if cli.sealed_variants {
-        let sealed_detector = UnusedSealedVariantDetector::new();
-        let sealed_issues = sealed_detector.detect(&graph);
-        if !sealed_issues.is_empty() {
-            info!("Found {} unused sealed variants", sealed_issues.len());
-            dead_code.extend(sealed_issues);
-        }
-    }
-
-    // Step 9e: Detect redundant overrides (Phase 10)
-    if cli.redundant_overrides {
-        let override_detector = RedundantOverrideDetector::new();
-        let override_issues = override_detector.detect(&graph);
-        if !override_issues.is_empty() {
-            info!("Found {} redundant overrides", override_issues.len());
-            dead_code.extend(override_issues);
-        }
-    }
-
     // Step 9f: Detect unused Android resources
-    if cli.unused_resources {
-        let resource_detector = ResourceDetector::new();
-        let resource_analysis = resource_detector.analyze(&cli.path);
+    let resource_analysis = cli
+        .unused_resources
+        .then(|| ResourceDetector::new().analyze(&cli.path));
+    if let Some(resource_analysis) = &resource_analysis {
         if !resource_analysis.unused.is_empty() {
             info!(
                 "Found {} unused resources ({} total defined, {} referenced)",
@@ -916,6 +1298,149 @@ fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
                 println!();
             }
         }
+        if !resource_analysis.unused_files.is_empty() {
+            let savings = analysis::resources::format_bytes(resource_analysis.unused_file_bytes());
+            info!(
+                "Found {} unused drawables/mipmaps/raw files ({})",
+                resource_analysis.unused_files.len(),
+                savings
+            );
+            if !cli.quiet {
+                use colored::Colorize;
+                println!(
+                    "{}",
+                    "🖼  Unused Drawables, Mipmaps & Raw Files:".yellow().bold()
+                );
+                for resource in &resource_analysis.unused_files {
+                    println!(
+                        "  {} @{}/{} - {} file(s), {}",
+                        "○".dimmed(),
+                        resource.resource_type,
+                        resource.name,
+                        resource.files.len(),
+                        analysis::resources::format_bytes(resource.total_bytes)
+                    );
+                    for file in &resource.files {
+                        let rel_path = file.strip_prefix(&cli.path).unwrap_or(file);
+                        println!("      {}", rel_path.display().to_string().dimmed());
+                    }
+                }
+                println!("  {}", format!("Removing them saves {}", savings).green());
+                println!();
+            }
+        }
+        if !resource_analysis.unused_assets.is_empty() {
+            let savings = analysis::resources::format_bytes(resource_analysis.unused_asset_bytes());
+            info!(
+                "Found {} unused assets ({})",
+                resource_analysis.unused_assets.len(),
+                savings
+            );
+            if !cli.quiet {
+                use colored::Colorize;
+                println!("{}", "📦 Unused Assets:".yellow().bold());
+                for asset in &resource_analysis.unused_assets {
+                    let rel_path = asset.file.strip_prefix(&cli.path).unwrap_or(&asset.file);
+                    println!(
+                        "  {} {} - {}",
+                        "○".dimmed(),
+                        rel_path.display(),
+                        analysis::resources::format_bytes(asset.bytes)
+                    );
+                }
+                println!("  {}", format!("Removing them saves {}", savings).green());
+                println!();
+            }
+        }
+        if !resource_analysis.orphaned_translations.is_empty() {
+            info!(
+                "Found {} orphaned translations ({} total)",
+                resource_analysis.orphaned_translations.len(),
+                resource_analysis.translations.len()
+            );
+            if !cli.quiet {
+                use colored::Colorize;
+                println!("{}", "🌐 Orphaned Translations:".yellow().bold());
+                for orphan in &resource_analysis.orphaned_translations {
+                    let translation = &orphan.translation;
+                    let rel_path = translation
+                        .file
+                        .strip_prefix(&cli.path)
+                        .unwrap_or(&translation.file);
+                    println!(
+                        "  {} {}:{} - {} '{}' ({})",
+                        "○".dimmed(),
+                        rel_path.display(),
+                        translation.line,
+                        translation.resource_type,
+                        translation.name,
+                        orphan.issue.description().dimmed()
+                    );
+                }
+                println!();
+            }
+        }
+        if !resource_analysis.unused_qualifier_dirs.is_empty() {
+            info!(
+                "Found {} unused resource qualifier directories",
+                resource_analysis.unused_qualifier_dirs.len()
+            );
+            if !cli.quiet {
+                use colored::Colorize;
+                println!("{}", "📂 Unused Qualifier Directories:".yellow().bold());
+                for qualifier_dir in &resource_analysis.unused_qualifier_dirs {
+                    let rel_path = qualifier_dir
+                        .dir
+                        .strip_prefix(&cli.path)
+                        .unwrap_or(&qualifier_dir.dir);
+                    println!(
+                        "  {} {}/ - {} resource(s), {} ({})",
+                        "○".dimmed(),
+                        rel_path.display(),
+                        qualifier_dir.resource_count,
+                        analysis::resources::format_bytes(qualifier_dir.total_bytes),
+                        qualifier_dir.issue.description().dimmed()
+                    );
+                }
+                println!();
+            }
+        }
+
+        if !resource_analysis.duplicate_resources.is_empty() {
+            info!(
+                "Found {} resources duplicated across modules",
+                resource_analysis.duplicate_resources.len()
+            );
+            if !cli.quiet {
+                use colored::Colorize;
+                println!("{}", "📑 Duplicate Resources:".yellow().bold());
+                for duplicate in &resource_analysis.duplicate_resources {
+                    let modules: Vec<String> = duplicate
+                        .modules
+                        .iter()
+                        .map(|m| m.strip_prefix(&cli.path).unwrap_or(m).display().to_string())
+                        .collect();
+                    let target = match &duplicate.suggested_module {
+                        Some(module) => format!(
+                            "consolidate into {}",
+                            module.strip_prefix(&cli.path).unwrap_or(module).display()
+                        ),
+                        None => "consolidate into a shared module".to_string(),
+                    };
+                    println!(
+                        "  {} {}/{} in {}/ of {} - {} wasted ({})",
+                        "○".dimmed(),
+                        duplicate.resource_type,
+                        duplicate.name,
+                        duplicate.dir_name,
+                        modules.join(", "),
+                        analysis::resources::format_bytes(duplicate.wasted_bytes),
+                        target.dimmed()
+                    );
+                }
+                println!();
+            }
+        }
     }
 
     // Step 9g: Detect unused Intent extras (Phase 11)
@@ -958,7 +1483,7 @@ fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
         // Analyze all Kotlin files for SharedPreferences usage
         let mut prefs_analysis = analysis::detectors::SharedPrefsAnalysis::new();
         for file in &files {
-            if file.file_type == FileType::Kotlin {
+            if file.file_type == FileType::Kotlin && !file.generated {
                 if let Ok(content) = std::fs::read_to_string(&file.path) {
                     let file_analysis = prefs_detector.analyze_source(&content, &file.path);
                     // Merge results
@@ -1014,7 +1539,7 @@ fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
         // Analyze all Kotlin files for DAO definitions
         let mut dao_analysis = analysis::detectors::DaoCollectionAnalysis::new();
         for file in &files {
-            if file.file_type == FileType::Kotlin {
+            if file.file_type == FileType::Kotlin && !file.generated {
                 if let Ok(content) = std::fs::read_to_string(&file.path) {
                     let file_analysis = dao_detector.analyze_source(&content, &file.path);
                     dao_analysis.daos.extend(file_analysis.daos);
@@ -1057,122 +1582,6 @@ fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
         }
     }
 
-    // Step 9j: Anti-pattern detectors
-    let run_architecture = cli.anti_patterns || cli.architecture_patterns;
-    let run_kotlin = cli.anti_patterns || cli.kotlin_patterns;
-    let run_performance = cli.anti_patterns || cli.performance_patterns;
-    let run_android = cli.anti_patterns || cli.android_patterns;
-    let run_compose = cli.anti_patterns || cli.compose_patterns;
-
-    // Architecture patterns (AP001-AP006)
-    if run_architecture {
-        let detectors: Vec<Box<dyn Detector>> = vec![
-            Box::new(DeepInheritanceDetector::new()),
-            Box::new(EventBusPatternDetector::new()),
-            Box::new(GlobalMutableStateDetector::new()),
-            Box::new(SingleImplInterfaceDetector::new()),
-        ];
-        for detector in detectors {
-            let issues = detector.detect(&graph);
-            if !issues.is_empty() {
-                dead_code.extend(issues);
-            }
-        }
-        info!("Architecture pattern analysis complete");
-    }
-
-    // Kotlin patterns (AP007-AP010, AP021-AP025)
-    if run_kotlin {
-        let detectors: Vec<Box<dyn Detector>> = vec![
-            // Phase 1
-            Box::new(GlobalScopeUsageDetector::new()),
-            Box::new(HeavyViewModelDetector::new()),
-            Box::new(LateinitAbuseDetector::new()),
-            Box::new(ScopeFunctionChainingDetector::new()),
-            // Phase 4
-            Box::new(ComplexConditionDetector::new()),
-            Box::new(LongParameterListDetector::new()),
-            Box::new(NullabilityOverloadDetector::new()),
-            Box::new(ReflectionOveruseDetector::new()),
-            Box::new(StringLiteralDuplicationDetector::new()),
-        ];
-        for detector in detectors {
-            let issues = detector.detect(&graph);
-            if !issues.is_empty() {
-                dead_code.extend(issues);
-            }
-        }
-        info!("Kotlin pattern analysis complete");
-    }
-
-    // Performance patterns (AP011-AP015)
-    if run_performance {
-        let detectors: Vec<Box<dyn Detector>> = vec![
-            Box::new(MemoryLeakRiskDetector::new()),
-            Box::new(LongMethodDetector::new()),
-            Box::new(LargeClassDetector::new()),
-            Box::new(CollectionWithoutSequenceDetector::new()),
-            Box::new(ObjectAllocationInLoopDetector::new()),
-        ];
-        for detector in detectors {
-            let issues = detector.detect(&graph);
-            if !issues.is_empty() {
-                dead_code.extend(issues);
-            }
-        }
-        info!("Performance pattern analysis complete");
-    }
-
-    // Android patterns (AP016-AP020, AP026-AP030)
-    if run_android {
-        let detectors: Vec<Box<dyn Detector>> = vec![
-            // Phase 3
-            Box::new(MutableStateExposedDetector::new()),
-            Box::new(ViewLogicInViewModelDetector::new()),
-            Box::new(MissingUseCaseDetector::new()),
-            Box::new(NestedCallbackDetector::new()),
-            Box::new(HardcodedDispatcherDetector::new()),
-            // Phase 5
-            Box::new(UnclosedResourceDetector::new()),
-            Box::new(MainThreadDatabaseDetector::new()),
-            Box::new(WakeLockAbuseDetector::new()),
-            Box::new(AsyncTaskUsageDetector::new()),
-            Box::new(InitOnDrawDetector::new()),
-        ];
-        for detector in detectors {
-            let issues = detector.detect(&graph);
-            if !issues.is_empty() {
-                dead_code.extend(issues);
-            }
-        }
-        info!("Android pattern analysis complete");
-    }
-
-    // Compose patterns (AP031-AP034)
-    if run_compose {
-        let detectors: Vec<Box<dyn Detector>> = vec![
-            Box::new(StateWithoutRememberDetector::new()),
-            Box::new(LaunchedEffectWithoutKeyDetector::new()),
-            Box::new(BusinessLogicInComposableDetector::new()),
-            Box::new(NavControllerPassingDetector::new()),
-        ];
-        for detector in detectors {
-            let issues = detector.detect(&graph);
-            if !issues.is_empty() {
-                dead_code.extend(issues);
-            }
-        }
-        info!("Compose pattern analysis complete");
-    }
-
-    // Step 10: Filter by confidence level
-    let min_confidence = parse_confidence(&cli.min_confidence);
-    let dead_code: Vec<_> = dead_code
-        .into_iter()
-        .filter(|dc| dc.confidence >= min_confidence)
-        .filter(|dc| !cli.runtime_only || dc.runtime_confirmed)
-        .collect();
-
     info!("Found {} dead code candidates", dead_code.len());
 
     // Step 11: Detect zombie code cycles if requested
@@ -1220,6 +1629,85 @@ fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
                 println!("  ... and {} more cycles", dead_cycles.len() - 5);
             }
             println!();
+
+            if let Some(format) = cli.cycles_format.filter(|_| !dead_cycles.is_empty()) {
+                let format = match format {
+                    CyclesFormat::Mermaid => report::CycleDiagramFormat::Mermaid,
+                    CyclesFormat::Plantuml => report::CycleDiagramFormat::PlantUml,
+                };
+                let diagram = report::CycleDiagram::new(format).render(&graph, &dead_cycles);
+                match &cli.cycles_output {
+                    Some(path) => match std::fs::write(path, diagram) {
+                        Ok(_) => {
+                            println!(
+                                "{}",
+                                format!("🧟 Cycle diagram written: {}", path.display()).green()
+                            );
+                        }
+                        Err(e) => {
+                            eprintln!("{}: Failed to write cycle diagram: {}", "Error".red(), e);
+                        }
+                    },
+                    None => println!("{}", diagram),
+                }
+            }
+        }
+    }
+
+    // Step 11b: Export the reference graph if requested
+    if let Some(format) = cli.export_graph {
+        let options = graph::ExportOptions::new()
+            .with_dead(dead_code.iter().map(|dc| dc.declaration.id.clone()))
+            .with_dead_only(cli.graph_dead_only)
+            .with_package(cli.graph_package.clone());
+        let format = match format {
+            GraphFormat::Dot => report::GraphExportFormat::Dot,
+            GraphFormat::Graphml => report::GraphExportFormat::GraphMl,
+            GraphFormat::Json => report::GraphExportFormat::Json,
+        };
+        let exported = report::GraphExporter::new(format)
+            .with_base_path(cli.path.clone())
+            .export(&graph, &options)?;
+        match &cli.graph_output {
+            Some(path) => match std::fs::write(path, exported) {
+                Ok(_) => {
+                    println!(
+                        "{}",
+                        format!("🕸️  Graph exported: {}", path.display()).green()
+                    );
+                }
+                Err(e) => {
+                    eprintln!("{}: Failed to export graph: {}", "Error".red(), e);
+                }
+            },
+            None => print!("{}", exported),
+        }
+    }
+
+    // Step 11c: Export to SQLite if requested
+    if let Some(ref db_path) = cli.export_db {
+        report::SqliteExporter::new()
+            .with_base_path(cli.path.clone())
+            .export(db_path, &graph, &dead_code)?;
+        println!(
+            "{}",
+            format!("🗄️  Findings exported: {}", db_path.display()).green()
+        );
+    }
+
+    // Step 11d: Render the dead code treemap if requested
+    if let Some(ref treemap_path) = cli.treemap {
+        let svg = report::TreemapExporter::new().render(&graph, &dead_code);
+        match std::fs::write(treemap_path, svg) {
+            Ok(_) => {
+                println!(
+                    "{}",
+                    format!("🗺️  Treemap written: {}", treemap_path.display()).green()
+                );
+            }
+            Err(e) => {
+                eprintln!("{}: Failed to write treemap: {}", "Error".red(), e);
+            }
         }
     }
 
@@ -1246,33 +1734,84 @@ fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
     }
 
     // Step 13: Filter by baseline if provided
-    let dead_code = if let Some(ref baseline_path) = cli.baseline {
+    // Baselined findings are kept aside for SARIF, which reports them as
+    // suppressed
+    let (dead_code, mut baselined) = if let Some(ref baseline_path) = cli.baseline {
         match baseline::Baseline::load(baseline_path) {
             Ok(baseline) => {
                 let stats = baseline.stats(&dead_code, &cli.path);
                 println!("{}", format!("📋 Baseline: {}", stats).cyan());
 
                 // Only report new issues not in baseline
-                let new_issues: Vec<_> = baseline
-                    .filter_new(&dead_code, &cli.path)
+                let (baselined, new_issues): (Vec<_>, Vec<_>) = dead_code
                     .into_iter()
-                    .cloned()
-                    .collect();
+                    .partition(|dc| baseline.is_baselined(dc, &cli.path));
 
                 if new_issues.is_empty() && stats.baselined_found > 0 {
                     println!("{}", "✓ No new dead code issues found!".green());
                 }
 
-                new_issues
+                (new_issues, Some(baselined))
             }
             Err(e) => {
                 eprintln!("{}: Failed to load baseline: {}", "Warning".yellow(), e);
-                dead_code
+                (dead_code, None)
             }
         }
     } else {
-        dead_code
+        (dead_code, None)
     };
+    let baseline_loaded = baselined.is_some();
+    if gate.max_new_issues.is_some() && !baseline_loaded {
+        eprintln!(
+            "{}: gate.max_new_issues needs a loaded --baseline; ignoring it",
+            "Warning".yellow()
+        );
+    }
+
+    // Step 13b: Attribute findings to owners if requested
+    let mut dead_code = dead_code;
+    if cli.owners || cli.blame {
+        // Outside a git repository, CODEOWNERS is looked up in the analyzed
+        // directory and there is nothing to blame
+        let repository = owners::repository_root(&cli.path);
+        let blame = cli.blame && repository.is_some();
+        if cli.blame && !blame {
+            eprintln!(
+                "{}: {} is not in a git repository, findings have no last authors",
+                "Warning".yellow(),
+                cli.path.display()
+            );
+        }
+        if let Some(root) = repository.or_else(|| cli.path.canonicalize().ok()) {
+            let codeowners = if cli.owners {
+                let found = owners::CodeOwners::find(&root);
+                match &found {
+                    Some((path, _)) => info!("Using owners from {}", path.display()),
+                    None => eprintln!(
+                        "{}: No CODEOWNERS file in {}",
+                        "Warning".yellow(),
+                        root.display()
+                    ),
+                }
+                found.map(|(_, codeowners)| codeowners)
+            } else {
+                None
+            };
+            owners::assign_owners(&mut dead_code, &root, codeowners.as_ref(), blame);
+        }
+    }
+
+    // Step 13c: Keep the findings matching --filter
+    if let Some(filter) = &cli.filter {
+        let filter = filter.clone().with_base_path(cli.path.clone());
+        let before = dead_code.len();
+        dead_code.retain(|dc| filter.matches(dc));
+        if let Some(baselined) = &mut baselined {
+            baselined.retain(|dc| filter.matches(dc));
+        }
+        info!("Filter kept {} of {} findings", dead_code.len(), before);
+    }
 
     // Step 14: Report results
     let report_format = determine_report_format(cli);
@@ -1284,6 +1823,8 @@ fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
     report_options.top_n = cli.top;
     report_options.files_count = Some(files.len());
     report_options.declarations_count = Some(graph.declarations().count());
+    report_options.baselined = baselined;
+    report_options.context_lines = cli.context.or(config.report.context_lines);
 
     let reporter = Reporter::with_options(report_format, report_options);
     reporter.report(&dead_code)?;
@@ -1293,21 +1834,134 @@ fn run_analysis(config: &Config, cli: &Cli) -> Result<()> {
     info!("Analysis completed in {:.2}s", elapsed.as_secs_f64());
 
     // Step 15: Safe delete if requested
-    if cli.delete && !dead_code.is_empty() {
+    if cli.delete {
         let deleter =
             refactor::SafeDeleter::new(cli.interactive, cli.dry_run, cli.undo_script.clone());
-        deleter.delete(&dead_code)?;
+        if !dead_code.is_empty() {
+            deleter.delete(&dead_code)?;
+        }
+        if let Some(resource_analysis) = &resource_analysis {
+            deleter.delete_translations(&resource_analysis.orphaned_translations)?;
+        }
     }
 
-    Ok(())
+    // Step 16: Quality gate
+    if let Some(failure) = gate.check(&dead_code, baseline_loaded) {
+        eprintln!("{}: Quality gate failed", "Error".red());
+        for reason in &failure.reasons {
+            eprintln!("  - {}", reason);
+        }
+        return Ok((ExitCode::from(failure.exit_code), graph));
+    }
+
+    Ok((ExitCode::SUCCESS, graph))
 }
 
-fn parse_confidence(s: &str) -> Confidence {
-    match s.to_lowercase().as_str() {
-        "low" => Confidence::Low,
-        "medium" => Confidence::Medium,
-        "high" => Confidence::High,
-        "confirmed" => Confidence::Confirmed,
-        _ => Confidence::Low,
+/// Graph cache and the file it is saved to
+type GraphCacheFile = (std::sync::Arc<graph::GraphCache>, PathBuf);
+
+/// Files parsed by earlier runs, from `--cache-path`; none with
+/// `--incremental false`, and an empty cache with `--clear-cache`
+fn load_graph_cache(config: &Config, cli: &Cli) -> Option<GraphCacheFile> {
+    if !cli.incremental {
+        return None;
+    }
+    let path = cli
+        .cache_path
+        .clone()
+        .unwrap_or_else(|| cache::AnalysisCache::default_cache_path(&cli.path));
+    let cache = if cli.clear_cache {
+        graph::GraphCache::for_config(config)
+    } else {
+        graph::GraphCache::load(&path, config)
+    };
+    if !cache.is_empty() {
+        info!("Reusing {} cached files from {}", cache.len(), path.display());
     }
+    Some((std::sync::Arc::new(cache), path))
+}
+
+/// Save the graph cache for the next run, without files no longer analyzed
+fn save_graph_cache((cache, path): &GraphCacheFile, files: &[discovery::SourceFile]) {
+    let analyzed: HashSet<&std::path::Path> = files.iter().map(|f| f.path.as_path()).collect();
+    cache.retain(|p| analyzed.contains(p));
+    if let Err(e) = cache.save(path) {
+        eprintln!(
+            "{}: Failed to save the cache to {}: {}",
+            "Warning".yellow(),
+            path.display(),
+            e
+        );
+    }
+}
+
+/// Token for `--timeout`; never fires without one
+fn cancellation(timeout: Option<u64>) -> CancellationToken {
+    timeout
+        .map(|secs| CancellationToken::with_timeout(std::time::Duration::from_secs(secs)))
+        .unwrap_or_default()
+}
+
+/// Fold the detector flags from the command line into the configured rule set
+fn detection_config(config: &Config, cli: &Cli) -> DetectionConfig {
+    let mut detection = config.detection.clone();
+
+    let defaults_on = [
+        (cli.unused_params, "DC003"),
+        (cli.write_only, "DC002"),
+        (cli.sealed_variants, "DC008"),
+        (cli.unused_enum_cases, "DC005"),
+        (cli.unused_constants, "DC019"),
+        (cli.unused_extensions, "DC020"),
+        (cli.unused_typealiases, "DC028"),
+        (cli.unused_test_helpers, "DC021"),
+        (cli.unused_bindings, "DC017"),
+        (cli.unregistered_receivers, "DC018"),
+        (cli.legacy_parcelables, "DC023"),
+        (cli.write_only_columns, "DC024"),
+        (cli.unused_catalog_entries, "DC029"),
+        (cli.unmatched_actions, "DC031"),
+        (cli.unused_workers, "DC032"),
+        (cli.unused_koin, "DC033"),
+        (cli.write_only_datastore, "DC034"),
+        (cli.unused_dynamic_features, "DC035"),
+        (cli.stale_consumer_rules, "DC036"),
+        (cli.unused_aidl_methods, "DC037"),
+    ];
+    for (enabled, code) in defaults_on {
+        if !enabled {
+            detection.disabled_rules.push(code.to_string());
+        }
+    }
+    if cli.redundant_overrides {
+        detection.enabled_rules.push("DC009".to_string());
+    }
+    if cli.redundant_public {
+        detection.enabled_rules.push("DC006".to_string());
+    }
+    if cli.stale_previews {
+        detection.enabled_rules.push("DC022".to_string());
+    }
+    if cli.always_default_params {
+        detection.enabled_rules.push("DC025".to_string());
+    }
+    if cli.orphan_tests {
+        detection.enabled_rules.push("DC026".to_string());
+    }
+    if cli.dead_callbacks {
+        detection.enabled_rules.push("DC027".to_string());
+    }
+    if cli.unused_proto_messages {
+        detection.enabled_rules.push("DC038".to_string());
+    }
+
+    let groups = &mut detection.anti_patterns;
+    groups.enabled |= cli.anti_patterns;
+    groups.architecture |= cli.architecture_patterns;
+    groups.kotlin |= cli.kotlin_patterns;
+    groups.performance |= cli.performance_patterns;
+    groups.android |= cli.android_patterns;
+    groups.compose |= cli.compose_patterns;
+
+    detection
 }
@@ -0,0 +1,82 @@
+//! Last-change authors from `git blame`
+
+use std::path::Path;
+use std::process::Command;
+
+/// Author and time of the commit that last changed each line of a file
+pub struct Blame {
+    lines: Vec<(String, i64)>,
+}
+
+impl Blame {
+    /// Blame a file, given relative to the repository root; `None` when git
+    /// fails, for example on a file it does not track
+    pub fn of(root: &Path, file: &Path) -> Option<Self> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["blame", "--line-porcelain", "--"])
+            .arg(file)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(Self::parse(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn parse(porcelain: &str) -> Self {
+        let mut lines = Vec::new();
+        let mut author = String::new();
+        let mut time = 0;
+        for line in porcelain.lines() {
+            if line.starts_with('\t') {
+                // The line's content ends its entry
+                lines.push((std::mem::take(&mut author), time));
+            } else if let Some(name) = line.strip_prefix("author ") {
+                author = name.to_string();
+            } else if let Some(seconds) = line.strip_prefix("author-time ") {
+                time = seconds.parse().unwrap_or(0);
+            }
+        }
+        Self { lines }
+    }
+
+    /// Author of the latest change to lines `first..=last`, numbered from 1
+    pub fn last_author(&self, first: usize, last: usize) -> Option<&str> {
+        self.lines
+            .get(first.saturating_sub(1)..last.min(self.lines.len()))?
+            .iter()
+            .max_by_key(|(_, time)| *time)
+            .map(|(author, _)| author.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_author() {
+        let entry = |sha: &str, author: &str, time: i64, content: &str| {
+            format!(
+                "{sha} 1 1 1\nauthor {author}\nauthor-mail <{author}@example.com>\n\
+                 author-time {time}\nauthor-tz +0000\nsummary change\nfilename App.kt\n\t{content}\n"
+            )
+        };
+        let blame = Blame::parse(
+            &[
+                entry("a1", "Alice", 100, "class App {"),
+                entry("b2", "Bob", 300, "    fun run() = Unit"),
+                entry("a1", "Alice", 100, "}"),
+                entry("c3", "Carol", 200, "class Other"),
+            ]
+            .concat(),
+        );
+
+        assert_eq!(blame.last_author(1, 3), Some("Bob"));
+        assert_eq!(blame.last_author(3, 3), Some("Alice"));
+        assert_eq!(blame.last_author(4, 9), Some("Carol"));
+        assert_eq!(blame.last_author(7, 9), None);
+    }
+}
@@ -0,0 +1,89 @@
+//! CODEOWNERS parsing
+//!
+//! Follows GitHub's rules: patterns use gitignore syntax relative to the
+//! repository root, and the last pattern matching a file decides its
+//! owners. A pattern without owners leaves the files it matches unowned.
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+/// Where GitHub and GitLab look for the file, in order
+const LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Owner rules of a repository
+pub struct CodeOwners {
+    rules: Vec<(Gitignore, Vec<String>)>,
+}
+
+impl CodeOwners {
+    /// Load the CODEOWNERS file of the repository at `root`, if it has one
+    pub fn find(root: &Path) -> Option<(PathBuf, Self)> {
+        LOCATIONS.iter().find_map(|location| {
+            let path = root.join(location);
+            let content = std::fs::read_to_string(&path).ok()?;
+            Some((path, Self::parse(root, &content)))
+        })
+    }
+
+    pub fn parse(root: &Path, content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?;
+                let owners = parts
+                    .take_while(|part| !part.starts_with('#'))
+                    .map(str::to_string)
+                    .collect();
+                let mut builder = GitignoreBuilder::new(root);
+                builder.add_line(None, pattern).ok()?;
+                Some((builder.build().ok()?, owners))
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Owners of a file, given relative to the repository root
+    pub fn owners_of(&self, file: &Path) -> &[String] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|(matcher, _)| matcher.matched_path_or_any_parents(file, false).is_ignore())
+            .map_or(&[], |(_, owners)| owners.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codeowners() {
+        let owners = CodeOwners::parse(
+            Path::new("/repo"),
+            r#"
+# Default owners
+*                       @org/android
+*.java                  @org/legacy
+/feature/payments/      @org/payments @alice  # billing
+docs/
+**/generated/**
+"#,
+        );
+        let of = |file: &str| owners.owners_of(Path::new(file)).join(" ");
+
+        assert_eq!(of("app/src/Main.kt"), "@org/android");
+        assert_eq!(of("app/src/Legacy.java"), "@org/legacy");
+        assert_eq!(
+            of("feature/payments/src/Checkout.java"),
+            "@org/payments @alice"
+        );
+        assert_eq!(of("app/feature/payments/Checkout.kt"), "@org/android");
+        assert_eq!(of("docs/Guide.kt"), "");
+        assert_eq!(of("app/build/generated/R.kt"), "");
+    }
+}
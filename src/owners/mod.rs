@@ -0,0 +1,69 @@
+//! Ownership attribution for findings
+//!
+//! Maps each finding's file to its owners in the repository's CODEOWNERS
+//! file and, optionally, its declaration to the author who last changed it,
+//! so cleanup can be routed to the teams that own the code.
+
+mod blame;
+mod codeowners;
+
+pub use blame::Blame;
+pub use codeowners::CodeOwners;
+
+use crate::analysis::DeadCode;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Nearest directory at or above `path` that holds a `.git` entry
+pub fn repository_root(path: &Path) -> Option<PathBuf> {
+    let path = path.canonicalize().ok()?;
+    path.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Set the owners of findings from `codeowners`, and their last authors
+/// from `git blame` when `blame` is set
+pub fn assign_owners(
+    dead_code: &mut [DeadCode],
+    root: &Path,
+    codeowners: Option<&CodeOwners>,
+    blame: bool,
+) {
+    // Paths relative to the repository root, as both sources expect
+    let mut relative: HashMap<PathBuf, Option<PathBuf>> = HashMap::new();
+    for dc in dead_code.iter() {
+        let file = dc.declaration.location.file.as_path();
+        relative.entry(file.to_path_buf()).or_insert_with(|| {
+            let file = file.canonicalize().ok()?;
+            file.strip_prefix(root).ok().map(Path::to_path_buf)
+        });
+    }
+
+    let blames: HashMap<&Path, Blame> = if blame {
+        relative
+            .par_iter()
+            .filter_map(|(file, relative)| {
+                Some((file.as_path(), Blame::of(root, relative.as_deref()?)?))
+            })
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    for dc in dead_code.iter_mut() {
+        let file = dc.declaration.location.file.as_path();
+        let Some(relative) = relative[file].as_deref() else {
+            continue;
+        };
+        if let Some(codeowners) = codeowners {
+            dc.owners = codeowners.owners_of(relative).to_vec();
+        }
+        if let Some(blame) = blames.get(file) {
+            let first = dc.declaration.location.line;
+            let last = first + dc.declaration.metrics.lines.max(1) - 1;
+            dc.last_author = blame.last_author(first, last).map(str::to_string);
+        }
+    }
+}
@@ -0,0 +1,276 @@
+// AIDL parser
+//
+// Reads Android Interface Definition Language files (src/main/aidl/**.aidl):
+// ```
+// package com.example;
+//
+// interface IRemoteService {
+//     int getPid();
+//     oneway void basicTypes(int anInt, in String aString);
+// }
+//
+// parcelable Rect;
+// ```
+// The build generates `IRemoteService`, its `Stub` and `Default` classes;
+// the project implements `IRemoteService.Stub` and hand-writes the classes
+// of parcelables declared without a body.
+
+#![allow(dead_code)] // API methods reserved for future use
+
+use super::common::line_of;
+use miette::Result;
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static PACKAGE_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^\s*package\s+([\w.]+)\s*;").expect("Invalid package regex"));
+
+// `interface IFoo {`, `oneway interface IFoo {`, `parcelable Foo;`, `parcelable Foo {`
+static DECLARATION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(interface|parcelable)\s+([A-Za-z_][\w.]*)\s*(?:<[^>]*>\s*)?([{;])")
+        .expect("Invalid declaration regex")
+});
+
+// `int getPid(` inside an interface body
+static METHOD_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"([A-Za-z_]\w*)\s*\($").expect("Invalid method regex"));
+
+/// Parsed AIDL file
+#[derive(Debug, Clone, Default)]
+pub struct AidlFile {
+    pub package: Option<String>,
+    pub interfaces: Vec<AidlInterface>,
+    /// Parcelables declared without a body, whose classes are hand-written
+    pub parcelables: Vec<String>,
+    /// Structured parcelables (`parcelable Foo { ... }`), generated by the build
+    pub structured_parcelables: Vec<String>,
+}
+
+/// An AIDL interface
+#[derive(Debug, Clone)]
+pub struct AidlInterface {
+    pub name: String,
+    /// 1-based line of the declaration
+    pub line: usize,
+    pub methods: Vec<AidlMethod>,
+}
+
+/// A method of an AIDL interface
+#[derive(Debug, Clone)]
+pub struct AidlMethod {
+    pub name: String,
+    /// 1-based line of the declaration
+    pub line: usize,
+}
+
+impl AidlFile {
+    /// Fully qualified name of a type declared in the file
+    pub fn qualified_name(&self, name: &str) -> String {
+        match &self.package {
+            Some(package) => format!("{}.{}", package, name),
+            None => name.to_string(),
+        }
+    }
+}
+
+impl AidlInterface {
+    /// Generated classes a project implementation extends
+    /// (`IFoo.Stub`, `IFoo.Default`)
+    pub fn implementation_bases(&self) -> [String; 2] {
+        [
+            format!("{}.Stub", self.name),
+            format!("{}.Default", self.name),
+        ]
+    }
+}
+
+/// Parser for AIDL files
+pub struct AidlParser;
+
+impl AidlParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse an AIDL file
+    pub fn parse(&self, _path: &Path, contents: &str) -> Result<AidlFile> {
+        let source = strip_comments(contents);
+        let mut file = AidlFile {
+            package: PACKAGE_PATTERN
+                .captures(&source)
+                .map(|cap| cap[1].to_string()),
+            ..Default::default()
+        };
+
+        let mut offset = 0;
+        while let Some(cap) = DECLARATION_PATTERN.captures_at(&source, offset) {
+            let whole = cap.get(0).map_or(0..0, |m| m.range());
+            let name = cap[2].to_string();
+            let line = line_of(&source, whole.start);
+            offset = whole.end;
+
+            let body = (&cap[3] == "{").then(|| {
+                let end = matching_brace(&source, whole.end - 1);
+                let body = whole.end..end;
+                offset = end;
+                body
+            });
+            match (&cap[1], body) {
+                ("interface", Some(body)) => file.interfaces.push(AidlInterface {
+                    name,
+                    line,
+                    methods: parse_methods(&source, body),
+                }),
+                ("parcelable", Some(_)) => file.structured_parcelables.push(name),
+                ("parcelable", None) => file.parcelables.push(name),
+                _ => {}
+            }
+        }
+
+        Ok(file)
+    }
+}
+
+impl Default for AidlParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Methods declared in an interface body: every statement with a parameter
+/// list, skipping constants and nested types
+fn parse_methods(source: &str, body: std::ops::Range<usize>) -> Vec<AidlMethod> {
+    let mut methods = Vec::new();
+    let mut start = body.start;
+    let mut depth = 0usize;
+    for (index, c) in source[body.clone()].char_indices() {
+        let index = body.start + index;
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                let statement = &source[start..index];
+                if let Some(open) = statement.find('(') {
+                    let head = &statement[..=open];
+                    if !head.trim_start().starts_with("const ") {
+                        if let Some(cap) = METHOD_PATTERN.captures(head) {
+                            let name = cap.get(1).unwrap();
+                            methods.push(AidlMethod {
+                                name: name.as_str().to_string(),
+                                line: line_of(source, start + name.start()),
+                            });
+                        }
+                    }
+                }
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    methods
+}
+
+/// Byte offset of the brace closing the one at `open`
+fn matching_brace(source: &str, open: usize) -> usize {
+    let mut depth = 0usize;
+    for (index, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + index;
+                }
+            }
+            _ => {}
+        }
+    }
+    source.len()
+}
+
+/// Blank out comments, keeping byte offsets and line breaks
+fn strip_comments(contents: &str) -> String {
+    let mut out = String::with_capacity(contents.len());
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                out.push(' ');
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                    out.push(' ');
+                }
+            }
+            ('/', Some('*')) => {
+                out.push(' ');
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    out.push(if c == '\n' { '\n' } else { ' ' });
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_aidl() {
+        let aidl = r#"// Remote service API
+package com.example.remote;
+
+import com.example.remote.Track;
+
+/** Playback service */
+oneway interface IPlayerService {
+    const int VERSION = 2;
+
+    /* Starts playback */
+    void play(in Track track);
+    int getPosition();
+    List<Track> queue(
+        int limit);
+}
+
+parcelable Track;
+
+parcelable Position {
+    long millis;
+}
+"#;
+        let file = AidlParser::new()
+            .parse(Path::new("IPlayerService.aidl"), aidl)
+            .unwrap();
+
+        assert_eq!(file.package.as_deref(), Some("com.example.remote"));
+        assert_eq!(file.interfaces.len(), 1);
+        let interface = &file.interfaces[0];
+        assert_eq!(interface.name, "IPlayerService");
+        assert_eq!(interface.line, 7);
+        let methods: Vec<_> = interface
+            .methods
+            .iter()
+            .map(|m| (m.name.as_str(), m.line))
+            .collect();
+        assert_eq!(methods, [("play", 11), ("getPosition", 12), ("queue", 13)]);
+        assert_eq!(
+            interface.implementation_bases(),
+            ["IPlayerService.Stub", "IPlayerService.Default"]
+        );
+        assert_eq!(file.parcelables, ["Track"]);
+        assert_eq!(file.structured_parcelables, ["Position"]);
+        assert_eq!(file.qualified_name("Track"), "com.example.remote.Track");
+    }
+}
@@ -0,0 +1,169 @@
+// Baseline and startup profile parser
+//
+// Baseline profiles (`src/main/baseline-prof.txt`, or the
+// `src/<variant>/generated/baselineProfiles/*.txt` files the Baseline
+// Profile Gradle plugin writes) list the classes and methods ART compiles
+// ahead of time, in human-readable ART profile rules:
+// ```
+// HSPLcom/app/MainActivity;-><init>()V
+// PLcom/app/feed/FeedViewModel;->load(Ljava/lang/String;)V
+// Lcom/app/feed/FeedItem;
+// Lcom/app/ui/**;
+// ```
+// Each rule starts with optional method flags (`H`ot, `S`tartup, `P`ost
+// startup), then a class descriptor with `*` / `**` wildcards, then
+// optionally `->` and a method.
+
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Parsed baseline or startup profile
+#[derive(Debug, Clone, Default)]
+pub struct BaselineProfile {
+    pub rules: Vec<ProfileRule>,
+}
+
+/// A class or method rule of a profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileRule {
+    /// Class name pattern in source form (`com.app.Outer.Inner`,
+    /// `com.app.ui.**`)
+    pub class_pattern: String,
+    /// Method name pattern (`load`, `<init>`, `*`) for method rules
+    pub method: Option<String>,
+}
+
+impl BaselineProfile {
+    /// Parse the rules of a profile; lines that are not rules are skipped
+    pub fn parse(contents: &str) -> Self {
+        let rules = contents.lines().filter_map(ProfileRule::parse).collect();
+        Self { rules }
+    }
+
+    /// Find the project's baseline and startup profiles, skipping hidden
+    /// and `build` directories
+    pub fn find_files(root: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|entry| {
+                let name = entry.file_name().to_string_lossy();
+                entry.depth() == 0 || !(name.starts_with('.') || name == "build")
+            })
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| is_profile(path))
+            .collect();
+        files.sort();
+        files
+    }
+}
+
+impl ProfileRule {
+    /// Parse one rule (`HSPLcom/app/Foo;->bar()V`)
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let descriptor = line.trim_start_matches(['H', 'S', 'P']);
+        let descriptor = descriptor.strip_prefix('L')?;
+        let (class, rest) = descriptor.split_once(';')?;
+        if class.is_empty() {
+            return None;
+        }
+        let method = rest.strip_prefix("->").map(|method| {
+            let end = method.find('(').unwrap_or(method.len());
+            method[..end].to_string()
+        });
+        Some(Self {
+            class_pattern: class.replace(['/', '$'], "."),
+            method,
+        })
+    }
+}
+
+/// Whether a file is a baseline or startup profile
+fn is_profile(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    if matches!(name, "baseline-prof.txt" | "startup-prof.txt") {
+        return true;
+    }
+    let in_profile_dir = path
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .and_then(|dir| dir.to_str())
+        .is_some_and(|dir| matches!(dir, "baselineProfiles" | "startupProfiles"));
+    in_profile_dir && name.ends_with(".txt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write;
+
+    #[test]
+    fn test_parse_profile() {
+        let profile = BaselineProfile::parse(
+            r#"# Startup path
+HSPLcom/app/MainActivity;-><init>()V
+PLcom/app/feed/FeedViewModel;->load(Ljava/lang/String;)V
+Lcom/app/feed/FeedItem$Author;
+Lcom/app/ui/**;
+SPLcom/app/Foo;->**(**)**
+
+not a rule
+"#,
+        );
+
+        let rule = |class: &str, method: Option<&str>| ProfileRule {
+            class_pattern: class.to_string(),
+            method: method.map(str::to_string),
+        };
+        assert_eq!(
+            profile.rules,
+            vec![
+                rule("com.app.MainActivity", Some("<init>")),
+                rule("com.app.feed.FeedViewModel", Some("load")),
+                rule("com.app.feed.FeedItem.Author", None),
+                rule("com.app.ui.**", None),
+                rule("com.app.Foo", Some("**")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_profiles() {
+        let temp = tempfile::TempDir::new().unwrap();
+        for rel in [
+            "app/src/main/baseline-prof.txt",
+            "app/src/release/generated/baselineProfiles/baseline-prof.txt",
+            "app/src/release/generated/baselineProfiles/startup-prof.txt",
+            "app/src/main/assets/notes.txt",
+            "app/build/outputs/baseline-prof.txt",
+        ] {
+            write(temp.path(), rel, "");
+        }
+
+        let files = BaselineProfile::find_files(temp.path());
+        let names: Vec<String> = files
+            .iter()
+            .map(|f| {
+                f.strip_prefix(temp.path())
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "app/src/main/baseline-prof.txt",
+                "app/src/release/generated/baselineProfiles/baseline-prof.txt",
+                "app/src/release/generated/baselineProfiles/startup-prof.txt",
+            ]
+        );
+    }
+}
@@ -1,9 +1,14 @@
 // Parser utilities - some reserved for future use
 #![allow(dead_code)]
 
-use crate::graph::{Declaration, Location, UnresolvedReference};
-use miette::Result;
-use std::path::Path;
+use crate::graph::{
+    CallArguments, CallReceiver, Declaration, Location, Metrics, UnresolvedReference,
+};
+use miette::{IntoDiagnostic, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tree_sitter::{InputEdit, Point, Tree};
 
 /// Result of parsing a source file
 #[derive(Debug)]
@@ -19,6 +24,12 @@ pub struct ParseResult {
 
     /// Import statements
     pub imports: Vec<String>,
+
+    /// Arguments of call references, by the start byte of the reference
+    pub call_arguments: HashMap<usize, CallArguments>,
+
+    /// Receivers of qualified call references, by the start byte of the reference
+    pub call_receivers: HashMap<usize, CallReceiver>,
 }
 
 impl ParseResult {
@@ -28,6 +39,8 @@ impl ParseResult {
             references: Vec::new(),
             package: None,
             imports: Vec::new(),
+            call_arguments: HashMap::new(),
+            call_receivers: HashMap::new(),
         }
     }
 }
@@ -42,6 +55,138 @@ impl Default for ParseResult {
 pub trait Parser {
     /// Parse a source file and extract declarations and references
     fn parse(&self, path: &Path, contents: &str) -> Result<ParseResult>;
+
+    /// Parse a source file, reusing the syntax tree of its previous parse
+    /// in `trees` so only the edited ranges are re-parsed
+    ///
+    /// Parsers that don't build tree-sitter trees parse from scratch.
+    fn parse_incremental(
+        &self,
+        path: &Path,
+        contents: &str,
+        trees: &TreeCache,
+    ) -> Result<ParseResult> {
+        let _ = trees;
+        self.parse(path, contents)
+    }
+}
+
+/// Syntax trees of earlier parses, by file
+///
+/// When a file is parsed again, the difference between its old and new
+/// source is applied to the old tree as a tree-sitter edit, and tree-sitter
+/// re-parses only the nodes that edit touches. Watch mode keeps one cache
+/// across runs; it is safe to share between parser threads.
+#[derive(Default)]
+pub struct TreeCache {
+    trees: Mutex<HashMap<PathBuf, (String, Tree)>>,
+}
+
+impl TreeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse `contents` as `language`, incrementally when `path` was parsed before
+    pub fn parse(
+        &self,
+        path: &Path,
+        contents: &str,
+        language: &tree_sitter::Language,
+    ) -> Result<Tree> {
+        // Taken out while parsing, so other threads aren't blocked
+        let previous = self.trees.lock().unwrap().remove(path);
+        let old_tree = match previous {
+            Some((old_source, tree)) if old_source == contents => {
+                self.insert(path, contents, tree.clone());
+                return Ok(tree);
+            }
+            Some((old_source, mut tree)) => {
+                if let Some(edit) = source_edit(&old_source, contents) {
+                    tree.edit(&edit);
+                }
+                Some(tree)
+            }
+            None => None,
+        };
+
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(language).into_diagnostic()?;
+        let tree = parser
+            .parse(contents, old_tree.as_ref())
+            .ok_or_else(|| miette::miette!("Failed to parse {}", path.display()))?;
+        self.insert(path, contents, tree.clone());
+        Ok(tree)
+    }
+
+    /// Forget files that are no longer analyzed
+    pub fn retain(&self, mut keep: impl FnMut(&Path) -> bool) {
+        self.trees.lock().unwrap().retain(|path, _| keep(path));
+    }
+
+    /// Number of files with a cached tree
+    pub fn len(&self) -> usize {
+        self.trees.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert(&self, path: &Path, contents: &str, tree: Tree) {
+        self.trees
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), (contents.to_string(), tree));
+    }
+}
+
+/// The single edit turning `old` into `new`: everything between their common
+/// prefix and common suffix. `None` when the sources are identical.
+fn source_edit(old: &str, new: &str) -> Option<InputEdit> {
+    let mut start = old
+        .bytes()
+        .zip(new.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !old.is_char_boundary(start) || !new.is_char_boundary(start) {
+        start -= 1;
+    }
+
+    let max_suffix = old.len().min(new.len()) - start;
+    let suffix = old
+        .bytes()
+        .rev()
+        .zip(new.bytes().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (mut old_end, mut new_end) = (old.len() - suffix, new.len() - suffix);
+    // Both ends are followed by the same bytes, so they move together
+    while !old.is_char_boundary(old_end) || !new.is_char_boundary(new_end) {
+        old_end += 1;
+        new_end += 1;
+    }
+
+    if start == old_end && start == new_end {
+        return None;
+    }
+    Some(InputEdit {
+        start_byte: start,
+        old_end_byte: old_end,
+        new_end_byte: new_end,
+        start_position: point_at(old, start),
+        old_end_position: point_at(old, old_end),
+        new_end_position: point_at(new, new_end),
+    })
+}
+
+/// Row and byte column of a byte offset
+fn point_at(source: &str, byte: usize) -> Point {
+    let before = &source[..byte];
+    let row = before.matches('\n').count();
+    let column = byte - before.rfind('\n').map_or(0, |i| i + 1);
+    Point::new(row, column)
 }
 
 /// Helper to convert tree-sitter Point to Location
@@ -61,6 +206,107 @@ pub fn point_to_location(
     )
 }
 
+/// Measure the lines of code and cyclomatic complexity of declarations
+/// parsed from `root`
+///
+/// One walk over the tree records which lines hold code, as opposed to
+/// blanks and comments, and where the branches `is_branch` recognizes
+/// start; each declaration's metrics then count those inside its range.
+pub fn measure_declarations(
+    root: tree_sitter::Node,
+    source: &str,
+    declarations: &mut [Declaration],
+    is_branch: impl Fn(tree_sitter::Node) -> bool,
+) {
+    let mut code_lines = vec![false; root.end_position().row + 1];
+    let mut branches = Vec::new();
+    for node in descendants(root) {
+        if is_branch(node) {
+            branches.push(node.start_byte());
+        }
+        if node.child_count() == 0 && !node.kind().ends_with("comment") {
+            code_lines[node.start_position().row..=node.end_position().row].fill(true);
+        }
+    }
+    branches.sort_unstable();
+
+    for decl in declarations {
+        let (start, end) = (decl.location.start_byte, decl.location.end_byte);
+        let Some(text) = source.get(start..end) else {
+            continue;
+        };
+        let first = decl.location.line.saturating_sub(1);
+        let last = first + text.matches('\n').count();
+        let branches_inside =
+            branches.partition_point(|&b| b < end) - branches.partition_point(|&b| b < start);
+        decl.metrics = Metrics {
+            lines: last - first + 1,
+            loc: code_lines
+                .get(first..=last.min(code_lines.len().saturating_sub(1)))
+                .map_or(0, |rows| rows.iter().filter(|&&code| code).count()),
+            complexity: 1 + branches_inside,
+        };
+    }
+}
+
+/// Annotations naming a declaration as it is seen outside the source: by the
+/// JVM (`@JvmName`) or by a serialization library (`@SerializedName`,
+/// `@SerialName`, `@Json`, `@JsonProperty`)
+pub const RENAMING_ANNOTATIONS: &[&str] = &[
+    "JvmName",
+    "SerializedName",
+    "SerialName",
+    "Json",
+    "JsonProperty",
+];
+
+/// Named arguments of renaming annotations that hold a name (`value`, Moshi's
+/// `name`, Gson's `alternate`); positional arguments always do
+pub const NAME_ARGUMENTS: &[&str] = &["value", "name", "alternate"];
+
+/// Reflective member lookups whose first argument names the member
+/// (`getDeclaredMethod("load")`)
+pub const REFLECTIVE_LOOKUPS: &[&str] = &[
+    "getMethod",
+    "getDeclaredMethod",
+    "getField",
+    "getDeclaredField",
+];
+
+/// Class a string literal names, if it is written like a fully qualified
+/// class name (`"com.app.Foo"`, `"com.app.Outer$Inner"` -> `com.app.Outer.Inner`)
+pub fn class_name_in_string(text: &str) -> Option<String> {
+    let name = text.trim().replace('$', ".");
+    let segments: Vec<&str> = name.split('.').collect();
+    let is_identifier = |segment: &&str| {
+        segment
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    if !segments.iter().all(is_identifier) {
+        return None;
+    }
+    // A package first, then classes: `com.app.Foo`, not `Foo.bar` or `app.name`
+    let is_class = |segment: &&str| segment.starts_with(|c: char| c.is_uppercase());
+    let first_class = segments.iter().position(is_class)?;
+    (first_class > 0 && segments[first_class..].iter().all(is_class)).then_some(name)
+}
+
+/// 1-based line of a byte offset into `source`
+pub fn line_of(source: &str, offset: usize) -> usize {
+    source[..offset].matches('\n').count() + 1
+}
+
+/// Simple name of a type as written in source or metadata:
+/// `com.app.Repo<Foo>?` -> `Repo`, `com.app.Outer$Inner` -> `Inner`
+pub fn simple_name(name: &str) -> String {
+    let name = name.split('<').next().unwrap_or(name).trim();
+    let name = name.trim_end_matches('?');
+    name.rsplit(['.', '$']).next().unwrap_or(name).to_string()
+}
+
 /// Extract text from a node
 pub fn node_text<'a>(node: tree_sitter::Node<'a>, source: &'a str) -> &'a str {
     &source[node.start_byte()..node.end_byte()]
@@ -130,3 +376,117 @@ impl<'a> Iterator for DescendantIterator<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incremental_parse_matches_fresh_parse() {
+        let language = tree_sitter_kotlin::language();
+        let path = Path::new("Screen.kt");
+        let before = "class Screen {\n    fun title() = \"Home\"\n}\n";
+        let after = "class Screen {\n    fun title() = \"Hömé\"\n\n    fun load() {}\n}\n";
+
+        let edit = source_edit(before, after).unwrap();
+        assert_eq!(edit.start_position, Point::new(1, 20));
+        assert_eq!(edit.old_end_position, Point::new(1, 24));
+        assert_eq!(edit.new_end_position, Point::new(3, 17));
+        assert!(source_edit(after, after).is_none());
+
+        let trees = TreeCache::new();
+        trees.parse(path, before, &language).unwrap();
+        let incremental = trees.parse(path, after, &language).unwrap();
+
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&language).unwrap();
+        let fresh = parser.parse(after, None).unwrap();
+        assert_eq!(
+            incremental.root_node().to_sexp(),
+            fresh.root_node().to_sexp()
+        );
+        assert_eq!(trees.len(), 1);
+
+        trees.retain(|p| p != path);
+        assert!(trees.is_empty());
+    }
+
+    #[test]
+    fn test_simple_name() {
+        assert_eq!(simple_name("google.protobuf.Timestamp"), "Timestamp");
+        assert_eq!(simple_name("com.app.Outer$InnerReceiver"), "InnerReceiver");
+        assert_eq!(simple_name("com.app.Repo<Foo>?"), "Repo");
+        assert_eq!(simple_name("Api? "), "Api");
+    }
+    #[test]
+    fn test_declaration_metrics() {
+        use crate::parser::{JavaParser, KotlinParser};
+
+        let kotlin = r#"class Rules {
+    // Validates input
+    fun check(x: Int): Int {
+        if (x > 1 && x < 3 || x == 0) return 1
+
+        when (x) {
+            4, 5 -> return 2
+            else -> return 3
+        }
+    }
+}
+"#;
+        let result = KotlinParser::new()
+            .parse(Path::new("Rules.kt"), kotlin)
+            .unwrap();
+        let metrics = |name: &str| {
+            result
+                .declarations
+                .iter()
+                .find(|d| d.name == name)
+                .unwrap()
+                .metrics
+        };
+        // if, &&, ||, and the two `when` conditions
+        assert_eq!(
+            metrics("check"),
+            Metrics {
+                lines: 8,
+                loc: 7,
+                complexity: 6
+            }
+        );
+        assert_eq!(metrics("Rules").lines, 11);
+        assert_eq!(metrics("Rules").loc, 9);
+
+        let java = r#"class Rules {
+    int check(int x) {
+        /* Validates
+           input */
+        for (int i : xs) {
+            if (x > i) continue;
+        }
+        switch (x) {
+            case 1: return 1;
+            default: return x > 0 ? 2 : 3;
+        }
+    }
+}
+"#;
+        let result = JavaParser::new()
+            .parse(Path::new("Rules.java"), java)
+            .unwrap();
+        let check = result
+            .declarations
+            .iter()
+            .find(|d| d.name == "check")
+            .unwrap();
+        // for, if, case 1 and the ternary
+        assert_eq!(
+            check.metrics,
+            Metrics {
+                lines: 11,
+                loc: 9,
+                complexity: 5
+            }
+        );
+    }
+}
@@ -1,15 +1,18 @@
 // Java parser - some internal methods reserved for future use
 #![allow(dead_code)]
 
-use super::common::{node_text, point_to_location, ParseResult, Parser};
+use super::common::{
+    class_name_in_string, measure_declarations, node_text, point_to_location, ParseResult, Parser,
+    TreeCache, NAME_ARGUMENTS, REFLECTIVE_LOOKUPS, RENAMING_ANNOTATIONS,
+};
 use crate::graph::{
-    Declaration, DeclarationId, DeclarationKind, Language, ReferenceKind, UnresolvedReference,
-    Visibility,
+    CallArgument, CallArguments, CallReceiver, Declaration, DeclarationId, DeclarationKind,
+    Language, ReferenceKind, UnresolvedReference, Visibility,
 };
 use miette::{IntoDiagnostic, Result};
 use std::path::Path;
 use tracing::debug;
-use tree_sitter::{Node, Parser as TsParser};
+use tree_sitter::{Node, Parser as TsParser, Tree};
 
 /// Java source code parser using tree-sitter
 pub struct JavaParser {
@@ -54,7 +57,11 @@ impl JavaParser {
                     {
                         let import_text = node_text(import_child, source);
                         // Check for wildcard import
-                        if let Some(_asterisk) = child.child_by_field_name("asterisk") {
+                        let mut asterisk_cursor = child.walk();
+                        if child
+                            .children(&mut asterisk_cursor)
+                            .any(|c| c.kind() == "asterisk")
+                        {
                             imports.push(format!("{}.*", import_text));
                         } else {
                             imports.push(import_text.to_string());
@@ -89,6 +96,9 @@ impl JavaParser {
                 "enum_declaration" => {
                     self.extract_enum(path, child, source, package, None, result)?;
                 }
+                "record_declaration" => {
+                    self.extract_record(path, child, source, package, None, result)?;
+                }
                 "annotation_type_declaration" => {
                     self.extract_annotation_type(path, child, source, package, result)?;
                 }
@@ -134,10 +144,11 @@ impl JavaParser {
             Language::Java,
         );
 
-        decl.fully_qualified_name = Some(self.build_fqn(package, &name));
+        decl.fully_qualified_name = Some(self.build_fqn(package, &name).into());
         self.extract_modifiers(node, source, &mut decl);
         decl.super_types = self.extract_super_types(node, source);
         decl.annotations = self.extract_annotations(node, source);
+        decl.alternate_names = self.extract_alternate_names(node, source);
         decl.parent = parent.clone();
 
         result.declarations.push(decl);
@@ -150,6 +161,97 @@ impl JavaParser {
         Ok(())
     }
 
+    /// Extract a record: a class whose components are both fields and
+    /// accessor methods (`record Point(int x, int y)` has `x()` and `y()`)
+    fn extract_record(
+        &self,
+        path: &Path,
+        node: Node,
+        source: &str,
+        package: &Option<String>,
+        parent: Option<DeclarationId>,
+        result: &mut ParseResult,
+    ) -> Result<()> {
+        let name = node
+            .child_by_field_name("name")
+            .map(|n| node_text(n, source).to_string())
+            .unwrap_or_else(|| "<anonymous>".to_string());
+
+        let location = point_to_location(
+            path,
+            node.start_position(),
+            node.end_position(),
+            node.start_byte(),
+            node.end_byte(),
+        );
+
+        let id = DeclarationId::new(path.to_path_buf(), node.start_byte(), node.end_byte());
+
+        let mut decl = Declaration::new(
+            id.clone(),
+            name.clone(),
+            DeclarationKind::Class,
+            location,
+            Language::Java,
+        );
+
+        decl.fully_qualified_name = Some(self.build_fqn(package, &name).into());
+        self.extract_modifiers(node, source, &mut decl);
+        decl.modifiers.push("record".to_string());
+        decl.super_types = self.extract_super_types(node, source);
+        decl.annotations = self.extract_annotations(node, source);
+        decl.alternate_names = self.extract_alternate_names(node, source);
+        decl.parent = parent;
+
+        result.declarations.push(decl);
+
+        // Record components become properties, read through their accessors
+        if let Some(params) = node.child_by_field_name("parameters") {
+            let mut cursor = params.walk();
+            for component in params.children(&mut cursor) {
+                if component.kind() != "formal_parameter" {
+                    continue;
+                }
+                let Some(name_node) = component.child_by_field_name("name") else {
+                    continue;
+                };
+                let location = point_to_location(
+                    path,
+                    component.start_position(),
+                    component.end_position(),
+                    component.start_byte(),
+                    component.end_byte(),
+                );
+                let mut property = Declaration::new(
+                    DeclarationId::new(
+                        path.to_path_buf(),
+                        component.start_byte(),
+                        component.end_byte(),
+                    ),
+                    node_text(name_node, source).to_string(),
+                    DeclarationKind::Property,
+                    location,
+                    Language::Java,
+                );
+                property.visibility = Visibility::Public;
+                property.modifiers.push("final".to_string());
+                property.annotations = self.extract_annotations(component, source);
+                property.alternate_names = self.extract_alternate_names(component, source);
+                property.type_name = component
+                    .child_by_field_name("type")
+                    .map(|t| node_text(t, source).to_string());
+                property.parent = Some(id.clone());
+                result.declarations.push(property);
+            }
+        }
+
+        if let Some(body) = node.child_by_field_name("body") {
+            self.extract_class_members(path, body, source, package, id, result)?;
+        }
+
+        Ok(())
+    }
+
     fn extract_interface(
         &self,
         path: &Path,
@@ -182,10 +284,11 @@ impl JavaParser {
             Language::Java,
         );
 
-        decl.fully_qualified_name = Some(self.build_fqn(package, &name));
+        decl.fully_qualified_name = Some(self.build_fqn(package, &name).into());
         self.extract_modifiers(node, source, &mut decl);
         decl.super_types = self.extract_super_types(node, source);
         decl.annotations = self.extract_annotations(node, source);
+        decl.alternate_names = self.extract_alternate_names(node, source);
         decl.parent = parent.clone();
         decl.is_abstract = true; // Interfaces are implicitly abstract
 
@@ -231,9 +334,10 @@ impl JavaParser {
             Language::Java,
         );
 
-        decl.fully_qualified_name = Some(self.build_fqn(package, &name));
+        decl.fully_qualified_name = Some(self.build_fqn(package, &name).into());
         self.extract_modifiers(node, source, &mut decl);
         decl.annotations = self.extract_annotations(node, source);
+        decl.alternate_names = self.extract_alternate_names(node, source);
         decl.parent = parent.clone();
 
         result.declarations.push(decl);
@@ -352,7 +456,7 @@ impl JavaParser {
             Language::Java,
         );
 
-        decl.fully_qualified_name = Some(self.build_fqn(package, &name));
+        decl.fully_qualified_name = Some(self.build_fqn(package, &name).into());
         self.extract_modifiers(node, source, &mut decl);
 
         result.declarations.push(decl);
@@ -389,6 +493,16 @@ impl JavaParser {
                 "enum_declaration" => {
                     self.extract_enum(path, child, source, package, Some(parent.clone()), result)?;
                 }
+                "record_declaration" => {
+                    self.extract_record(
+                        path,
+                        child,
+                        source,
+                        package,
+                        Some(parent.clone()),
+                        result,
+                    )?;
+                }
                 "method_declaration" => {
                     self.extract_method(
                         path,
@@ -399,7 +513,7 @@ impl JavaParser {
                         result,
                     )?;
                 }
-                "constructor_declaration" => {
+                "constructor_declaration" | "compact_constructor_declaration" => {
                     self.extract_constructor(path, child, source, parent.clone(), result)?;
                 }
                 "field_declaration" => {
@@ -446,12 +560,16 @@ impl JavaParser {
 
         self.extract_modifiers(node, source, &mut decl);
         decl.annotations = self.extract_annotations(node, source);
+        decl.alternate_names = self.extract_alternate_names(node, source);
         decl.parent = parent;
 
         // Extract parameters
         if let Some(params) = node.child_by_field_name("parameters") {
             self.extract_parameters(path, params, source, id, result)?;
         }
+        decl.type_name = node
+            .child_by_field_name("type")
+            .map(|t| node_text(t, source).to_string());
 
         result.declarations.push(decl);
 
@@ -491,6 +609,7 @@ impl JavaParser {
 
         self.extract_modifiers(node, source, &mut decl);
         decl.annotations = self.extract_annotations(node, source);
+        decl.alternate_names = self.extract_alternate_names(node, source);
         decl.parent = Some(parent);
 
         // Extract parameters
@@ -544,6 +663,7 @@ impl JavaParser {
 
                     self.extract_modifiers(node, source, &mut decl);
                     decl.annotations = self.extract_annotations(node, source);
+                    decl.alternate_names = self.extract_alternate_names(node, source);
                     decl.parent = parent.clone();
                     decl.type_name = field_type.clone();
 
@@ -624,6 +744,12 @@ impl JavaParser {
                     );
 
                     decl.parent = Some(parent.clone());
+                    decl.type_name = child
+                        .child_by_field_name("type")
+                        .map(|t| node_text(t, source).to_string());
+                    if child.kind() == "spread_parameter" {
+                        decl.modifiers.push("vararg".to_string());
+                    }
 
                     result.declarations.push(decl);
                 }
@@ -633,6 +759,25 @@ impl JavaParser {
         Ok(())
     }
 
+    /// Extract the enum whose constants a call enumerates: `Color.values()`,
+    /// `Color.valueOf(name)` and `EnumSet.allOf(Color.class)`
+    fn extract_enum_enumeration(&self, node: Node, source: &str) -> Option<String> {
+        let object = node_text(node.child_by_field_name("object")?, source);
+        let method = node_text(node.child_by_field_name("name")?, source);
+        let name = match method {
+            "values" | "valueOf" => object.rsplit('.').next()?,
+            "allOf" if object == "EnumSet" => {
+                let arguments = node_text(node.child_by_field_name("arguments")?, source);
+                let class_literal = arguments.trim_matches(['(', ')']).trim();
+                class_literal.strip_suffix(".class")?.rsplit('.').next()?
+            }
+            _ => return None,
+        };
+        let is_type_name = name.starts_with(|c: char| c.is_ascii_uppercase())
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        is_type_name.then(|| name.to_string())
+    }
+
     fn extract_references(
         &self,
         path: &Path,
@@ -649,7 +794,12 @@ impl JavaParser {
             match current.kind() {
                 "identifier" => {
                     if let Some(parent) = current.parent() {
-                        if let Some(kind) = self.determine_reference_kind(parent) {
+                        let kind = if parent.kind() == "method_reference" {
+                            Some(self.method_reference_kind(parent, current, source))
+                        } else {
+                            self.determine_reference_kind(parent)
+                        };
+                        if let Some(kind) = kind {
                             let name = node_text(current, source).to_string();
                             let location = point_to_location(
                                 path,
@@ -674,7 +824,8 @@ impl JavaParser {
                         }
                     }
                 }
-                "type_identifier" => {
+                // `permits` lists subclasses without using them
+                "type_identifier" if !self.in_permits_clause(current) => {
                     let name = node_text(current, source).to_string();
                     let location = point_to_location(
                         path,
@@ -687,12 +838,14 @@ impl JavaParser {
                     result.references.push(UnresolvedReference {
                         name,
                         qualified_name: None,
-                        kind: ReferenceKind::Type,
+                        kind: self.type_reference_kind(current),
                         location,
                         imports: imports.to_vec(),
                     });
                 }
-                "scoped_identifier" | "scoped_type_identifier" => {
+                "scoped_identifier" | "scoped_type_identifier"
+                    if !self.in_permits_clause(current) =>
+                {
                     let name = node_text(current, source).to_string();
                     let location = point_to_location(
                         path,
@@ -705,11 +858,66 @@ impl JavaParser {
                     result.references.push(UnresolvedReference {
                         name: name.split('.').next_back().unwrap_or(&name).to_string(),
                         qualified_name: Some(name),
-                        kind: ReferenceKind::Type,
+                        kind: self.type_reference_kind(current),
                         location,
                         imports: imports.to_vec(),
                     });
                 }
+                // "com.app.Foo" / getMethod("load") may load code by reflection
+                "string_fragment" | "multiline_string_fragment" => {
+                    if let Some((name, qualified_name)) =
+                        self.extract_string_reference(current, source)
+                    {
+                        let location = point_to_location(
+                            path,
+                            current.start_position(),
+                            current.end_position(),
+                            current.start_byte(),
+                            current.end_byte(),
+                        );
+
+                        result.references.push(UnresolvedReference {
+                            name,
+                            qualified_name,
+                            kind: ReferenceKind::ReflectionString,
+                            location,
+                            imports: imports.to_vec(),
+                        });
+                    }
+                }
+                // Color.values() / Color.valueOf(name) / EnumSet.allOf(Color.class)
+                // Foo.CONSTANT / Foo.Companion.create() qualify the member by a type
+                "field_access" => {
+                    if let Some((member, qualifier)) = self.extract_qualifier(current, source) {
+                        result.call_receivers.insert(member.start_byte(), qualifier);
+                    }
+                }
+                "method_invocation" => {
+                    if let Some((callee, arguments)) = self.extract_call_arguments(current, source)
+                    {
+                        result.call_arguments.insert(callee.start_byte(), arguments);
+                    }
+                    if let Some((callee, qualifier)) = self.extract_qualifier(current, source) {
+                        result.call_receivers.insert(callee.start_byte(), qualifier);
+                    }
+                    if let Some(enum_name) = self.extract_enum_enumeration(current, source) {
+                        let location = point_to_location(
+                            path,
+                            current.start_position(),
+                            current.end_position(),
+                            current.start_byte(),
+                            current.end_byte(),
+                        );
+
+                        result.references.push(UnresolvedReference {
+                            name: enum_name,
+                            qualified_name: None,
+                            kind: ReferenceKind::EnumValues,
+                            location,
+                            imports: imports.to_vec(),
+                        });
+                    }
+                }
                 _ => {}
             }
 
@@ -726,6 +934,134 @@ impl JavaParser {
 
     // Helper methods
 
+    /// The class a string names (`"com.app.Foo"`) or the member a reflective
+    /// lookup names (`getMethod("load")`), as the simple name and the
+    /// qualified name if any
+    fn extract_string_reference(
+        &self,
+        node: Node,
+        source: &str,
+    ) -> Option<(String, Option<String>)> {
+        let text = node_text(node, source);
+        if let Some(class) = class_name_in_string(text) {
+            let name = class.rsplit('.').next().unwrap_or(&class).to_string();
+            return Some((name, Some(class)));
+        }
+
+        let literal = node
+            .parent()
+            .filter(|p| p.kind() == "string_literal" && p.named_child_count() == 1)?;
+        let call = literal
+            .parent()
+            .filter(|p| p.kind() == "argument_list" && p.named_child(0) == Some(literal))
+            .and_then(|arguments| arguments.parent())
+            .filter(|c| c.kind() == "method_invocation")?;
+        let lookup = node_text(call.child_by_field_name("name")?, source);
+        let is_identifier = text.chars().all(|c| c.is_alphanumeric() || c == '_');
+        (REFLECTIVE_LOOKUPS.contains(&lookup) && !text.is_empty() && is_identifier)
+            .then(|| (text.to_string(), None))
+    }
+
+    /// The member a qualified access names and the type qualifying it
+    /// (`Foo.CONSTANT`, `Foo.Companion.create()`); qualifiers that are not
+    /// type names (`user.getName()`) are left unknown
+    fn extract_qualifier<'a>(
+        &self,
+        node: Node<'a>,
+        source: &str,
+    ) -> Option<(Node<'a>, CallReceiver)> {
+        let (object, member) = match node.kind() {
+            "method_invocation" => (
+                node.child_by_field_name("object")?,
+                node.child_by_field_name("name")?,
+            ),
+            "field_access" => (
+                node.child_by_field_name("object")?,
+                node.child_by_field_name("field")?,
+            ),
+            _ => return None,
+        };
+        if !matches!(object.kind(), "identifier" | "field_access") {
+            return None;
+        }
+        let qualifier = node_text(object, source);
+        // `Registry.INSTANCE.create()` calls a member of a Kotlin object
+        let qualifier = qualifier.strip_suffix(".INSTANCE").unwrap_or(qualifier);
+        let is_type = qualifier
+            .split('.')
+            .all(|segment| segment.chars().all(|c| c.is_alphanumeric() || c == '_'))
+            && qualifier
+                .rsplit('.')
+                .next()
+                .is_some_and(|name| name.starts_with(|c: char| c.is_ascii_uppercase()));
+        is_type.then(|| (member, CallReceiver::Type(qualifier.to_string())))
+    }
+
+    /// The method name of a call and the arguments passed to it
+    fn extract_call_arguments<'a>(
+        &self,
+        node: Node<'a>,
+        source: &str,
+    ) -> Option<(Node<'a>, CallArguments)> {
+        let name = node.child_by_field_name("name")?;
+        let list = node.child_by_field_name("arguments")?;
+        let mut cursor = list.walk();
+        let arguments = list
+            .named_children(&mut cursor)
+            .filter(|c| c.kind() != "comment")
+            .map(|argument| CallArgument {
+                name: None,
+                value: node_text(argument, source).to_string(),
+                spread: false,
+            })
+            .collect();
+        Some((
+            name,
+            CallArguments {
+                arguments,
+                trailing_lambda: false,
+            },
+        ))
+    }
+
+    /// Whether a type name is listed in a `sealed ... permits A, B` clause
+    /// Kind of a reference to a type: an annotation applied to a declaration
+    /// or type, a type in a declaration's signature (parameter, return,
+    /// field, bound and thrown types), or another use of the type
+    fn type_reference_kind(&self, node: Node) -> ReferenceKind {
+        let mut current = node;
+        while let Some(parent) = current.parent() {
+            match parent.kind() {
+                // The type is part of a larger type: `List<Foo>[]`
+                "generic_type"
+                | "type_arguments"
+                | "array_type"
+                | "scoped_type_identifier"
+                | "wildcard"
+                | "annotated_type" => current = parent,
+                "annotation" | "marker_annotation" => return ReferenceKind::AnnotationUse,
+                "formal_parameter"
+                | "spread_parameter"
+                | "receiver_parameter"
+                | "method_declaration"
+                | "field_declaration"
+                | "constant_declaration"
+                | "annotation_type_element_declaration"
+                | "type_bound"
+                | "throws" => return ReferenceKind::TypeReference,
+                _ => return ReferenceKind::Type,
+            }
+        }
+        ReferenceKind::Type
+    }
+
+    fn in_permits_clause(&self, node: Node) -> bool {
+        node.parent()
+            .filter(|p| p.kind() == "type_list")
+            .and_then(|p| p.parent())
+            .is_some_and(|p| p.kind() == "permits")
+    }
+
     fn extract_modifiers(&self, node: Node, source: &str, decl: &mut Declaration) {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
@@ -801,6 +1137,96 @@ impl JavaParser {
         annotations
     }
 
+    /// Names given by the arguments of renaming annotations
+    /// (`@JsonProperty("x")`,
+    /// `@SerializedName(value = "id", alternate = {"user_id"})`)
+    fn extract_alternate_names(&self, node: Node, source: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut cursor = node.walk();
+        let annotations = node
+            .children(&mut cursor)
+            .filter(|child| child.kind() == "modifiers")
+            .flat_map(|modifiers| {
+                let mut cursor = modifiers.walk();
+                modifiers.children(&mut cursor).collect::<Vec<_>>()
+            })
+            .filter(|modifier| modifier.kind() == "annotation");
+
+        for annotation in annotations {
+            let Some(annotation_name) = annotation.child_by_field_name("name") else {
+                continue;
+            };
+            let annotation_name = node_text(annotation_name, source);
+            let annotation_name = annotation_name.rsplit('.').next().unwrap_or_default();
+            let Some(arguments) = annotation.child_by_field_name("arguments") else {
+                continue;
+            };
+            if !RENAMING_ANNOTATIONS.contains(&annotation_name) {
+                continue;
+            }
+
+            let mut cursor = arguments.walk();
+            for argument in arguments.named_children(&mut cursor) {
+                // `value = "id"`
+                let value = if argument.kind() == "element_value_pair" {
+                    let key = argument.child_by_field_name("key");
+                    if !key.is_some_and(|key| NAME_ARGUMENTS.contains(&node_text(key, source))) {
+                        continue;
+                    }
+                    let Some(value) = argument.child_by_field_name("value") else {
+                        continue;
+                    };
+                    value
+                } else {
+                    argument
+                };
+                // `alternate = {"user_id", "uid"}`
+                let literals = if value.kind() == "element_value_array_initializer" {
+                    let mut cursor = value.walk();
+                    value.named_children(&mut cursor).collect()
+                } else {
+                    vec![value]
+                };
+                for literal in literals {
+                    if literal.kind() == "string_literal" && literal.named_child_count() == 1 {
+                        let content = literal
+                            .named_child(0)
+                            .filter(|c| c.kind() == "string_fragment");
+                        if let Some(content) = content {
+                            names.push(node_text(content, source).to_string());
+                        }
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    /// How an identifier in a method reference is used: the method after
+    /// `::` is called (`vm::onClick`, `this::onTap`), a type before it is
+    /// named or instantiated (`User::getName`, `Foo::new`) and a variable
+    /// before it is read
+    fn method_reference_kind(
+        &self,
+        reference: Node,
+        identifier: Node,
+        source: &str,
+    ) -> ReferenceKind {
+        if identifier.prev_sibling().is_some_and(|s| s.kind() == "::") {
+            return ReferenceKind::Call;
+        }
+        let is_constructor = reference
+            .child(reference.child_count().saturating_sub(1))
+            .is_some_and(|c| c.kind() == "new");
+        if is_constructor {
+            ReferenceKind::Instantiation
+        } else if node_text(identifier, source).starts_with(|c: char| c.is_uppercase()) {
+            ReferenceKind::Type
+        } else {
+            ReferenceKind::Read
+        }
+    }
+
     fn determine_reference_kind(&self, parent: Node) -> Option<ReferenceKind> {
         match parent.kind() {
             // Method/function calls
@@ -825,10 +1251,9 @@ impl JavaParser {
             // Object instantiation
             "object_creation_expression" => Some(ReferenceKind::Instantiation),
 
-            // Annotations
-            "annotation" | "marker_annotation" | "annotation_argument_list" => {
-                Some(ReferenceKind::Annotation)
-            }
+            // Annotations, and constants in their arguments
+            "annotation" | "marker_annotation" => Some(ReferenceKind::AnnotationUse),
+            "annotation_argument_list" => Some(ReferenceKind::Annotation),
 
             // Cast expression
             "cast_expression" => Some(ReferenceKind::Read),
@@ -888,6 +1313,9 @@ impl JavaParser {
             // Instanceof check
             "instanceof_expression" => Some(ReferenceKind::Read),
 
+            // Record deconstruction patterns (`case Point(var x, var y)`)
+            "record_pattern" => Some(ReferenceKind::Type),
+
             // Class literal (SomeClass.class)
             "class_literal" => Some(ReferenceKind::Type),
 
@@ -935,6 +1363,23 @@ impl Parser for JavaParser {
             .parse(contents, None)
             .ok_or_else(|| miette::miette!("Failed to parse Java file"))?;
 
+        self.parse_tree(path, contents, &tree)
+    }
+
+    fn parse_incremental(
+        &self,
+        path: &Path,
+        contents: &str,
+        trees: &TreeCache,
+    ) -> Result<ParseResult> {
+        let tree = trees.parse(path, contents, &tree_sitter_java::language())?;
+        self.parse_tree(path, contents, &tree)
+    }
+}
+
+impl JavaParser {
+    /// Extract declarations and references from a parsed tree
+    fn parse_tree(&self, path: &Path, contents: &str, tree: &Tree) -> Result<ParseResult> {
         let root = tree.root_node();
         let mut result = ParseResult::new();
 
@@ -947,6 +1392,7 @@ impl Parser for JavaParser {
         result.imports = imports.clone();
         temp_parser.extract_declarations(path, root, contents, &package, &mut result)?;
         temp_parser.extract_references(path, root, contents, &imports, &mut result)?;
+        measure_declarations(root, contents, &mut result.declarations, Self::is_branch);
 
         debug!(
             "Parsed {}: {} declarations, {} references",
@@ -957,6 +1403,24 @@ impl Parser for JavaParser {
 
         Ok(result)
     }
+
+    /// Whether a node adds a path through the code, for cyclomatic complexity
+    fn is_branch(node: Node) -> bool {
+        match node.kind() {
+            "if_statement"
+            | "for_statement"
+            | "enhanced_for_statement"
+            | "while_statement"
+            | "do_statement"
+            | "catch_clause"
+            | "ternary_expression"
+            | "&&"
+            | "||" => true,
+            // `case` labels; `default` is the path taken when none matches
+            "switch_label" => node.child(0).is_some_and(|label| label.kind() == "case"),
+            _ => false,
+        }
+    }
 }
 
 impl Default for JavaParser {
@@ -1001,4 +1465,187 @@ mod tests {
 
         assert_eq!(result.imports.len(), 2);
     }
+
+    #[test]
+    fn test_enum_enumeration_references() {
+        let parser = JavaParser::new();
+        let source = r#"
+            class Modes {
+                void all() {
+                    for (Mode mode : Mode.values()) {}
+                    Status.valueOf("ACTIVE");
+                    EnumSet.allOf(Level.class);
+                    list.valueOf(1);
+                }
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Modes.java"), source).unwrap();
+
+        let enumerated: Vec<_> = result
+            .references
+            .iter()
+            .filter(|r| r.kind == ReferenceKind::EnumValues)
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(enumerated, ["Mode", "Status", "Level"]);
+    }
+
+    #[test]
+    fn test_records_and_sealed_types() {
+        let parser = JavaParser::new();
+        let source = r#"
+            sealed interface Shape permits Circle, Square {}
+
+            record Circle(@JsonProperty("r") double radius) implements Shape {
+                Circle {
+                    if (radius < 0) throw new IllegalArgumentException();
+                }
+            }
+
+            final class Square implements Shape {}
+        "#;
+
+        let result = parser.parse(Path::new("Shapes.java"), source).unwrap();
+
+        let shape = &result.declarations[0];
+        assert!(shape.modifiers.contains(&"sealed".to_string()));
+        let circle = result
+            .declarations
+            .iter()
+            .find(|d| d.name == "Circle" && d.kind == DeclarationKind::Class)
+            .unwrap();
+        assert!(circle.modifiers.contains(&"record".to_string()));
+        assert_eq!(circle.super_types, ["Shape"]);
+
+        let radius = result
+            .declarations
+            .iter()
+            .find(|d| d.name == "radius")
+            .unwrap();
+        assert_eq!(radius.kind, DeclarationKind::Property);
+        assert_eq!(radius.parent.as_ref(), Some(&circle.id));
+        assert_eq!(radius.type_name.as_deref(), Some("double"));
+        assert_eq!(radius.annotations.len(), 1);
+        assert!(result
+            .declarations
+            .iter()
+            .any(|d| d.kind == DeclarationKind::Constructor && d.name == "Circle"));
+
+        // The `permits` clause lists the variants without using them
+        let type_refs: Vec<_> = result
+            .references
+            .iter()
+            .filter(|r| r.name == "Circle" || r.name == "Square")
+            .collect();
+        assert!(type_refs.is_empty());
+    }
+
+    #[test]
+    fn test_renaming_annotation_arguments() {
+        let parser = JavaParser::new();
+        let source = r#"
+            public class User {
+                @SerializedName(value = "id", alternate = {"user_id", "uid"})
+                private String id;
+
+                @com.fasterxml.jackson.annotation.JsonProperty("mail")
+                private String email;
+
+                @Deprecated
+                private String legacy;
+            }
+        "#;
+
+        let result = parser.parse(Path::new("User.java"), source).unwrap();
+
+        let alternate_names = |name: &str| {
+            result
+                .declarations
+                .iter()
+                .find(|d| d.name == name)
+                .unwrap()
+                .alternate_names
+                .clone()
+        };
+        assert_eq!(alternate_names("id"), ["id", "user_id", "uid"]);
+        assert_eq!(alternate_names("email"), ["mail"]);
+        assert!(alternate_names("legacy").is_empty());
+    }
+
+    #[test]
+    fn test_method_references() {
+        let parser = JavaParser::new();
+        let source = r#"
+            public class Screen {
+                void bind(ScreenViewModel viewModel) {
+                    button.setOnClickListener(viewModel::onRefresh);
+                    items.forEach(this::render);
+                    names.stream().map(User::getName).map(Label::new);
+                }
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Screen.java"), source).unwrap();
+
+        let kinds = |name: &str| -> Vec<ReferenceKind> {
+            result
+                .references
+                .iter()
+                .filter(|r| r.name == name)
+                .map(|r| r.kind)
+                .collect()
+        };
+        assert_eq!(kinds("onRefresh"), [ReferenceKind::Call]);
+        assert_eq!(kinds("render"), [ReferenceKind::Call]);
+        assert_eq!(kinds("getName"), [ReferenceKind::Call]);
+        assert_eq!(kinds("viewModel"), [ReferenceKind::Read]);
+        assert_eq!(kinds("User"), [ReferenceKind::Type]);
+        assert_eq!(kinds("Label"), [ReferenceKind::Instantiation]);
+    }
+    #[test]
+    fn test_signature_reference_kinds() {
+        let parser = JavaParser::new();
+        let source = r#"
+            public class Screen<T extends Listener> {
+                @Tracked @com.app.Logged(Keys.SCREEN)
+                public Result show(@Param Widget widget, List<Item> items) throws Failure {
+                    Local local = new Local();
+                    return null;
+                }
+
+                private Config config;
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Screen.java"), source).unwrap();
+
+        let kinds = |name: &str| -> Vec<ReferenceKind> {
+            result
+                .references
+                .iter()
+                .filter(|r| r.name == name)
+                .map(|r| r.kind)
+                .collect()
+        };
+        for annotation in ["Tracked", "Logged", "Param"] {
+            assert_eq!(
+                kinds(annotation),
+                [ReferenceKind::AnnotationUse],
+                "{}",
+                annotation
+            );
+        }
+        for signature_type in [
+            "Listener", "Result", "Widget", "List", "Item", "Failure", "Config",
+        ] {
+            assert_eq!(
+                kinds(signature_type),
+                [ReferenceKind::TypeReference],
+                "{}",
+                signature_type
+            );
+        }
+        assert!(!kinds("Local").contains(&ReferenceKind::TypeReference));
+    }
 }
@@ -0,0 +1,198 @@
+// JNI parser
+//
+// Reads C and C++ sources (src/main/cpp, src/main/jni) for the JVM code
+// they reach through JNI:
+// ```
+// JNIEXPORT jstring JNICALL
+// Java_com_example_app_MainActivity_stringFromJNI(JNIEnv* env, jobject thiz) { ... }
+//
+// jclass cls = env->FindClass("com/example/app/NativeBridge");
+// jmethodID cb = env->GetMethodID(cls, "onFrame", "(I)V");
+//
+// static JNINativeMethod methods[] = {{"nativeInit", "()V", (void*) init}};
+// ```
+// Exported `Java_` symbols implement `native` / `external` methods, while
+// `FindClass` strings, member ID lookups and `RegisterNatives` tables name
+// classes and members only native code uses.
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+// `Java_com_example_MainActivity_stringFromJNI`
+static SYMBOL_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bJava_(\w+)").expect("Invalid symbol regex"));
+
+// `env->FindClass("com/example/Foo")`, `(*env)->FindClass(env, "com/example/Foo")`
+static FIND_CLASS_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bFindClass\s*\(\s*(?:\w+\s*,\s*)?"([^"]+)""#).expect("Invalid FindClass regex")
+});
+
+// `env->GetMethodID(cls, "onFrame", "(I)V")`, `GetStaticFieldID(env, cls, "sInstance", ...)`
+static MEMBER_ID_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bGet(?:Static)?(?:Method|Field)ID\s*\((?:[^,"()]*,\s*){1,2}"(\w+)""#)
+        .expect("Invalid member ID regex")
+});
+
+// `{"nativeInit", "()V", (void*) init}` in a `JNINativeMethod` table
+static NATIVE_METHOD_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\{\s*"(\w+)"\s*,\s*"\("#).expect("Invalid native method regex"));
+
+/// JVM code a native source reaches
+#[derive(Debug, Clone, Default)]
+pub struct JniReferences {
+    /// Native methods implemented by exported symbols, as
+    /// (class fully qualified name, method name)
+    pub native_methods: HashSet<(String, String)>,
+    /// Classes named by `FindClass` (fully qualified, nested classes
+    /// joined with `.`)
+    pub classes: HashSet<String>,
+    /// Members named by `Get*MethodID` / `Get*FieldID` and registered
+    /// through `RegisterNatives` tables
+    pub members: HashSet<String>,
+}
+
+impl JniReferences {
+    pub fn merge(&mut self, other: JniReferences) {
+        self.native_methods.extend(other.native_methods);
+        self.classes.extend(other.classes);
+        self.members.extend(other.members);
+    }
+}
+
+/// Parser for JNI references in C and C++ sources
+pub struct JniParser;
+
+impl JniParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Collect the JNI references of a C or C++ source
+    pub fn parse(&self, contents: &str) -> JniReferences {
+        let mut references = JniReferences::default();
+
+        for cap in SYMBOL_PATTERN.captures_iter(contents) {
+            if let Some((class, method)) = demangle_symbol(&cap[1]) {
+                references.classes.insert(class.clone());
+                references.native_methods.insert((class, method));
+            }
+        }
+        for cap in FIND_CLASS_PATTERN.captures_iter(contents) {
+            // Array descriptors name their element class: `[Lcom/example/Foo;`
+            let class = cap[1].trim_start_matches('[');
+            let class = match class.strip_suffix(';') {
+                Some(descriptor) => descriptor.strip_prefix('L').unwrap_or(descriptor),
+                None => class,
+            };
+            references.classes.insert(class.replace(['/', '$'], "."));
+        }
+        references.members.extend(
+            MEMBER_ID_PATTERN
+                .captures_iter(contents)
+                .chain(NATIVE_METHOD_PATTERN.captures_iter(contents))
+                .map(|cap| cap[1].to_string()),
+        );
+
+        references
+    }
+}
+
+impl Default for JniParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Split a mangled `Java_` symbol (without the prefix) into class and
+/// method: `com_example_Foo_do_1work` -> ("com.example.Foo", "do_work").
+/// `_1` escapes `_`, `_0xxxx` a Unicode character, and the argument
+/// signature of an overloaded method follows `__`
+fn demangle_symbol(mangled: &str) -> Option<(String, String)> {
+    let mut segments = vec![String::new()];
+    let mut chars = mangled.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '_' {
+            segments.last_mut()?.push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some('1') => {
+                chars.next();
+                segments.last_mut()?.push('_');
+            }
+            Some('0') => {
+                chars.next();
+                let hex: String = chars.by_ref().take(4).collect();
+                let decoded = u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)?;
+                segments.last_mut()?.push(decoded);
+            }
+            Some('2') | Some('3') => {
+                chars.next();
+            }
+            // Argument signature of an overloaded method
+            Some('_') => break,
+            _ => segments.push(String::new()),
+        }
+    }
+
+    let method = segments.pop().filter(|m| !m.is_empty())?;
+    if segments.is_empty() || segments.iter().any(|s| s.is_empty()) {
+        return None;
+    }
+    Some((segments.join(".").replace('$', "."), method))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jni_references() {
+        let source = r#"
+#include <jni.h>
+
+extern "C" JNIEXPORT jstring JNICALL
+Java_com_example_app_MainActivity_stringFromJNI(JNIEnv* env, jobject) {
+    return env->NewStringUTF("hello");
+}
+
+extern "C" JNIEXPORT void JNICALL
+Java_com_example_app_Native_00024Codec_decode_1frame__I(JNIEnv* env, jclass, jint) {}
+
+static void attach(JNIEnv* env) {
+    jclass bridge = env->FindClass("com/example/app/NativeBridge");
+    jmethodID onFrame = env->GetMethodID(bridge, "onFrame", "(I)V");
+    jfieldID handle = (*env)->GetStaticFieldID(env, bridge, "sHandle", "J");
+}
+
+static JNINativeMethod methods[] = {
+    {"nativeInit", "()V", (void*) init},
+};
+"#;
+
+        let references = JniParser::new().parse(source);
+
+        let mut native_methods: Vec<_> = references.native_methods.iter().cloned().collect();
+        native_methods.sort();
+        assert_eq!(
+            native_methods,
+            [
+                (
+                    "com.example.app.MainActivity".to_string(),
+                    "stringFromJNI".to_string()
+                ),
+                (
+                    "com.example.app.Native.Codec".to_string(),
+                    "decode_frame".to_string()
+                ),
+            ]
+        );
+        assert!(references.classes.contains("com.example.app.NativeBridge"));
+        let mut members: Vec<_> = references.members.iter().map(String::as_str).collect();
+        members.sort();
+        assert_eq!(members, ["nativeInit", "onFrame", "sHandle"]);
+    }
+}
@@ -1,15 +1,31 @@
 // Kotlin parser - some internal methods reserved for future use
 #![allow(dead_code)]
 
-use super::common::{node_text, point_to_location, ParseResult, Parser};
+use super::common::{
+    class_name_in_string, measure_declarations, node_text, point_to_location, ParseResult, Parser,
+    TreeCache, NAME_ARGUMENTS, REFLECTIVE_LOOKUPS, RENAMING_ANNOTATIONS,
+};
 use crate::graph::{
-    Declaration, DeclarationId, DeclarationKind, Language, Location, ReferenceKind,
-    UnresolvedReference, Visibility,
+    CallArgument, CallArguments, CallReceiver, Declaration, DeclarationId, DeclarationKind,
+    Language, Location, ReferenceKind, UnresolvedReference, Visibility,
 };
 use miette::{IntoDiagnostic, Result};
 use std::path::Path;
+use std::sync::LazyLock;
 use tracing::debug;
-use tree_sitter::{Node, Parser as TsParser};
+use tree_sitter::{Node, Parser as TsParser, Tree};
+
+/// Matches zero-argument calls like `isEnabled()` in misparsed source text
+static NO_ARG_CALL_PATTERN: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"([a-z][a-zA-Z0-9]*)\s*\(\s*\)").expect("Invalid no-arg call regex")
+});
+
+/// Matches the text before a function name in a declaration, such as
+/// `fun ` or `override fun <T> List<T>.`
+static FUN_DECLARATION_PREFIX: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"\bfun\s+(?:<[^>]*>\s*)?(?:[\w.<>?, *]+\.)?$")
+        .expect("Invalid declaration prefix regex")
+});
 
 /// Kotlin source code parser using tree-sitter
 pub struct KotlinParser {
@@ -80,15 +96,27 @@ impl KotlinParser {
                 for import in child.children(&mut import_cursor) {
                     if import.kind() == "import_header" {
                         // Find identifier by kind (not field name) since tree-sitter-kotlin
-                        // doesn't use field names for import identifiers
+                        // doesn't use field names for import identifiers. Wildcards are
+                        // kept as `pkg.*` and aliases as `pkg.Name as Alias`.
                         let mut header_cursor = import.walk();
+                        let mut import_text = String::new();
                         for header_child in import.children(&mut header_cursor) {
-                            if header_child.kind() == "identifier" {
-                                let import_text = node_text(header_child, source);
-                                imports.push(import_text.to_string());
-                                break;
+                            match header_child.kind() {
+                                "identifier" => {
+                                    import_text = node_text(header_child, source).to_string()
+                                }
+                                "wildcard_import" => import_text.push_str(".*"),
+                                "import_alias" => {
+                                    let alias = node_text(header_child, source);
+                                    let alias = alias.trim_start_matches("as").trim();
+                                    import_text = format!("{} as {}", import_text, alias);
+                                }
+                                _ => {}
                             }
                         }
+                        if !import_text.is_empty() {
+                            imports.push(import_text);
+                        }
                     }
                 }
             }
@@ -165,7 +193,7 @@ impl KotlinParser {
         let mut decl = Declaration::new(id.clone(), name.clone(), kind, location, Language::Kotlin);
 
         // Set fully qualified name
-        decl.fully_qualified_name = Some(self.build_fqn(package, &name));
+        decl.fully_qualified_name = Some(self.build_fqn(package, &name).into());
 
         // Extract modifiers and visibility
         self.extract_modifiers(node, source, &mut decl);
@@ -174,11 +202,13 @@ impl KotlinParser {
         decl.super_types = self.extract_super_types(node, source);
 
         // Extract class delegation (e.g., class Foo : Bar by delegate)
+        decl.delegated_types = self.extract_delegated_types(node, source);
         let imports_clone = result.imports.clone();
         self.extract_class_delegates(node, source, path, &imports_clone, result);
 
         // Extract annotations
         decl.annotations = self.extract_annotations(node, source);
+        decl.alternate_names = self.extract_alternate_names(node, source);
 
         decl.parent = parent.clone();
 
@@ -189,7 +219,7 @@ impl KotlinParser {
         let mut cursor = node.walk();
         let mut found_class_body = false;
         for child in node.children(&mut cursor) {
-            if child.kind() == "class_body" {
+            if child.kind() == "class_body" || child.kind() == "enum_class_body" {
                 self.extract_class_members(path, child, source, package, id.clone(), result)?;
                 found_class_body = true;
                 break;
@@ -313,10 +343,14 @@ impl KotlinParser {
             Language::Kotlin,
         );
 
-        decl.fully_qualified_name = Some(self.build_fqn(package, &name));
+        decl.fully_qualified_name = Some(self.build_fqn(package, &name).into());
         self.extract_modifiers(node, source, &mut decl);
         decl.super_types = self.extract_super_types(node, source);
+        decl.delegated_types = self.extract_delegated_types(node, source);
+        let imports_clone = result.imports.clone();
+        self.extract_class_delegates(node, source, path, &imports_clone, result);
         decl.annotations = self.extract_annotations(node, source);
+        decl.alternate_names = self.extract_alternate_names(node, source);
         decl.parent = parent.clone();
 
         result.declarations.push(decl);
@@ -434,15 +468,17 @@ impl KotlinParser {
         let mut decl = Declaration::new(id, name.clone(), kind, location.clone(), Language::Kotlin);
 
         if parent.is_none() {
-            decl.fully_qualified_name = Some(self.build_fqn(package, &name));
+            decl.fully_qualified_name = Some(self.build_fqn(package, &name).into());
         }
 
         self.extract_modifiers(node, source, &mut decl);
         decl.annotations = self.extract_annotations(node, source);
+        decl.alternate_names = self.extract_alternate_names(node, source);
         decl.parent = parent;
 
         // Extract extension receiver type (e.g., fun String.myExtension())
         if let Some(receiver_type) = self.extract_extension_receiver(node, source) {
+            decl.receiver_type = Some(receiver_type.clone());
             // Add a reference to the receiver type so it's not marked as dead code
             result.references.push(UnresolvedReference {
                 name: receiver_type,
@@ -451,18 +487,41 @@ impl KotlinParser {
                 location: location.clone(),
                 imports: result.imports.clone(),
             });
+            decl.modifiers.push("extension".to_string());
         }
 
         // Extract parameters
-        if let Some(params) = node.child_by_field_name("function_value_parameters") {
+        let mut params_cursor = node.walk();
+        let params = node
+            .children(&mut params_cursor)
+            .find(|c| c.kind() == "function_value_parameters");
+        if let Some(params) = params {
             self.extract_parameters(path, params, source, decl.id.clone(), result)?;
         }
+        decl.type_name = self.extract_return_type(node, source);
 
         result.declarations.push(decl);
 
         Ok(())
     }
 
+    /// Extract the declared return type of a function (`fun load(): User?` -> "User?")
+    fn extract_return_type(&self, node: Node, source: &str) -> Option<String> {
+        let mut cursor = node.walk();
+        let return_type = node
+            .children(&mut cursor)
+            .skip_while(|c| c.kind() != "function_value_parameters")
+            .skip_while(|c| c.kind() != ":")
+            .nth(1)
+            .filter(|c| {
+                matches!(
+                    c.kind(),
+                    "user_type" | "nullable_type" | "type_reference" | "function_type"
+                )
+            });
+        return_type.map(|c| node_text(c, source).to_string())
+    }
+
     /// Extract the receiver type from an extension function (e.g., "String" from "fun String.myExtension()")
     fn extract_extension_receiver(&self, node: Node, source: &str) -> Option<String> {
         let mut cursor = node.walk();
@@ -480,7 +539,7 @@ impl KotlinParser {
             // After 'fun', look for receiver_type or user_type before the dot
             if found_fun {
                 if kind == "receiver_type" || kind == "type_reference" {
-                    let type_text = node_text(child, source);
+                    let type_text = node_text(child, source).trim_end_matches('?');
                     // Strip generic parameters if present
                     let name = type_text.split('<').next().unwrap_or(type_text);
                     // Take the last component of qualified names
@@ -550,11 +609,12 @@ impl KotlinParser {
                     );
 
                     if parent.is_none() {
-                        decl.fully_qualified_name = Some(self.build_fqn(package, &name));
+                        decl.fully_qualified_name = Some(self.build_fqn(package, &name).into());
                     }
 
                     self.extract_modifiers(node, source, &mut decl);
                     decl.annotations = self.extract_annotations(node, source);
+                    decl.alternate_names = self.extract_alternate_names(node, source);
                     decl.parent = parent.clone();
 
                     // Check for val/var keyword - in tree-sitter-kotlin grammar,
@@ -580,6 +640,19 @@ impl KotlinParser {
                     // Extract property type (e.g., "val name: String" -> "String")
                     decl.type_name = self.extract_property_type(node, source);
 
+                    // Extension property (e.g., val String.wordCount: Int get() = ...)
+                    if let Some(receiver_type) = self.extract_property_receiver(node, source) {
+                        decl.receiver_type = Some(receiver_type.clone());
+                        result.references.push(UnresolvedReference {
+                            name: receiver_type,
+                            qualified_name: None,
+                            kind: ReferenceKind::ExtensionReceiver,
+                            location: location.clone(),
+                            imports: result.imports.clone(),
+                        });
+                        decl.modifiers.push("extension".to_string());
+                    }
+
                     // Check for property delegation (by lazy, by Delegates, etc.)
                     if let Some(delegate_type) = self.extract_property_delegate(node, source) {
                         // Add delegation reference
@@ -607,6 +680,64 @@ impl KotlinParser {
         Ok(())
     }
 
+    /// Extract the receiver type from an extension property (e.g., "String" from
+    /// "val String.wordCount: Int"). The receiver is the type that precedes the
+    /// variable declaration.
+    fn extract_property_receiver(&self, node: Node, source: &str) -> Option<String> {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "variable_declaration" => break,
+                "user_type" | "nullable_type" | "type_reference" => {
+                    let type_text = node_text(child, source).trim_end_matches('?');
+                    let name = type_text.split('<').next().unwrap_or(type_text);
+                    let simple_name = name.split('.').next_back().unwrap_or(name);
+                    return Some(simple_name.to_string());
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Extract the enum whose constants an expression enumerates:
+    /// `Color.values()`, `Color.entries`, `Color.valueOf(name)`, `enumValues<Color>()`,
+    /// `enumValueOf<Color>(name)` and `enumEntries<Color>()`
+    fn extract_enum_enumeration(&self, node: Node, source: &str) -> Option<String> {
+        let first = node.child(0)?;
+        let last = node.child(node.child_count().checked_sub(1)?)?;
+
+        match node.kind() {
+            "navigation_expression" if last.kind() == "navigation_suffix" => {
+                let member = node_text(last, source).trim_start_matches(['.', '?']).trim();
+                if !matches!(member, "values" | "entries" | "valueOf") {
+                    return None;
+                }
+                let receiver = node_text(first, source);
+                let name = receiver.rsplit('.').next()?.trim();
+                let is_type_name = name.starts_with(|c: char| c.is_ascii_uppercase())
+                    && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+                is_type_name.then(|| name.to_string())
+            }
+            "call_expression" if first.kind() == "simple_identifier" => {
+                let callee = node_text(first, source);
+                if !matches!(callee, "enumValues" | "enumValueOf" | "enumEntries") {
+                    return None;
+                }
+                let mut cursor = last.walk();
+                let type_arguments = last
+                    .children(&mut cursor)
+                    .find(|c| c.kind() == "type_arguments")?;
+                let type_text = node_text(type_arguments, source)
+                    .trim_start_matches('<')
+                    .trim_end_matches('>');
+                let name = type_text.split('<').next()?.rsplit('.').next()?.trim();
+                (!name.is_empty()).then(|| name.to_string())
+            }
+            _ => None,
+        }
+    }
+
     /// Extract delegation type from a property (e.g., "lazy" from "by lazy { }")
     fn extract_property_delegate(&self, node: Node, source: &str) -> Option<String> {
         let mut cursor = node.walk();
@@ -664,6 +795,16 @@ impl KotlinParser {
 
     /// Extract the type of a property declaration (e.g., "val name: String" -> "String")
     fn extract_property_type(&self, node: Node, source: &str) -> Option<String> {
+        // A property's type is inside its variable declaration; a type before
+        // it is an extension receiver
+        let mut cursor = node.walk();
+        let variable = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "variable_declaration");
+        if let Some(variable) = variable {
+            return self.extract_property_type(variable, source);
+        }
+
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
             match child.kind() {
@@ -835,7 +976,11 @@ impl KotlinParser {
         decl.parent = Some(parent);
 
         // Extract parameters
-        if let Some(params) = node.child_by_field_name("class_parameters") {
+        let mut params_cursor = node.walk();
+        let params = node
+            .children(&mut params_cursor)
+            .find(|c| c.kind() == "class_parameters");
+        if let Some(params) = params {
             self.extract_parameters(path, params, source, id, result)?;
         }
 
@@ -855,7 +1000,12 @@ impl KotlinParser {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
             if child.kind() == "parameter" || child.kind() == "class_parameter" {
-                if let Some(name_node) = child.child_by_field_name("simple_identifier") {
+                // Kotlin grammar nodes have no field names; the name is the first identifier
+                let mut name_cursor = child.walk();
+                let name_node = child
+                    .named_children(&mut name_cursor)
+                    .find(|c| c.kind() == "simple_identifier");
+                if let Some(name_node) = name_node {
                     let name = node_text(name_node, source).to_string();
                     let location = point_to_location(
                         path,
@@ -880,6 +1030,24 @@ impl KotlinParser {
                     );
 
                     decl.parent = Some(parent.clone());
+                    decl.type_name = self.extract_property_type(child, source);
+                    decl.alternate_names = self.extract_alternate_names(child, source);
+
+                    // A function parameter's `= default` follows the parameter
+                    // node; a class parameter's is inside it
+                    let mut parameter_cursor = child.walk();
+                    let has_default = child.next_sibling().is_some_and(|s| s.kind() == "=")
+                        || child
+                            .children(&mut parameter_cursor)
+                            .any(|c| c.kind() == "=");
+                    if has_default {
+                        decl.modifiers.push("default".to_string());
+                    }
+                    if child.prev_sibling().is_some_and(|s| {
+                        s.kind() == "parameter_modifiers" && node_text(s, source).contains("vararg")
+                    }) {
+                        decl.modifiers.push("vararg".to_string());
+                    }
 
                     result.declarations.push(decl);
                 }
@@ -957,7 +1125,12 @@ impl KotlinParser {
         parent: DeclarationId,
         result: &mut ParseResult,
     ) -> Result<()> {
-        if let Some(name_node) = node.child_by_field_name("simple_identifier") {
+        let mut cursor = node.walk();
+        let name_node = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "simple_identifier");
+
+        if let Some(name_node) = name_node {
             let name = node_text(name_node, source).to_string();
             let location = point_to_location(
                 path,
@@ -977,6 +1150,9 @@ impl KotlinParser {
                 Language::Kotlin,
             );
 
+            decl.annotations = self.extract_annotations(node, source);
+
+            decl.alternate_names = self.extract_alternate_names(node, source);
             decl.parent = Some(parent);
 
             result.declarations.push(decl);
@@ -993,7 +1169,13 @@ impl KotlinParser {
         package: &Option<String>,
         result: &mut ParseResult,
     ) -> Result<()> {
-        if let Some(name_node) = node.child_by_field_name("simple_identifier") {
+        // typealias Name<T> = Type
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        let name_node = children
+            .iter()
+            .find(|c| matches!(c.kind(), "type_identifier" | "simple_identifier"));
+        if let Some(&name_node) = name_node {
             let name = node_text(name_node, source).to_string();
             let location = point_to_location(
                 path,
@@ -1013,7 +1195,13 @@ impl KotlinParser {
                 Language::Kotlin,
             );
 
-            decl.fully_qualified_name = Some(self.build_fqn(package, &name));
+            decl.fully_qualified_name = Some(self.build_fqn(package, &name).into());
+            // The aliased type, everything after `=`
+            decl.type_name = children
+                .iter()
+                .skip_while(|c| c.kind() != "=")
+                .nth(1)
+                .map(|t| node_text(*t, source).trim().to_string());
             self.extract_modifiers(node, source, &mut decl);
 
             result.declarations.push(decl);
@@ -1097,6 +1285,73 @@ impl KotlinParser {
                         }
                     }
                 }
+                // Color.values() / Color.entries / enumValues<Color>() enumerate every constant
+                "navigation_expression" | "call_expression" => {
+                    if let Some((callee, arguments)) = self.extract_call_arguments(current, source)
+                    {
+                        result.call_arguments.insert(callee.start_byte(), arguments);
+                    }
+                    if let Some((callee, receiver)) = self.extract_call_receiver(current, source) {
+                        result.call_receivers.insert(callee.start_byte(), receiver);
+                    }
+                    if let Some(enum_name) = self.extract_enum_enumeration(current, source) {
+                        let location = point_to_location(
+                            path,
+                            current.start_position(),
+                            current.end_position(),
+                            current.start_byte(),
+                            current.end_byte(),
+                        );
+
+                        result.references.push(UnresolvedReference {
+                            name: enum_name,
+                            qualified_name: None,
+                            kind: ReferenceKind::EnumValues,
+                            location,
+                            imports: imports.to_vec(),
+                        });
+                    }
+                }
+                // "com.app.Foo" / getMethod("load") may load code by reflection
+                "string_content" => {
+                    if let Some((name, qualified_name)) =
+                        self.extract_string_reference(current, source)
+                    {
+                        let location = point_to_location(
+                            path,
+                            current.start_position(),
+                            current.end_position(),
+                            current.start_byte(),
+                            current.end_byte(),
+                        );
+
+                        result.references.push(UnresolvedReference {
+                            name,
+                            qualified_name,
+                            kind: ReferenceKind::ReflectionString,
+                            location,
+                            imports: imports.to_vec(),
+                        });
+                    }
+                }
+                // "$TAG" in a string template reads TAG
+                "interpolated_identifier" => {
+                    let location = point_to_location(
+                        path,
+                        current.start_position(),
+                        current.end_position(),
+                        current.start_byte(),
+                        current.end_byte(),
+                    );
+
+                    result.references.push(UnresolvedReference {
+                        name: node_text(current, source).to_string(),
+                        qualified_name: None,
+                        kind: ReferenceKind::Read,
+                        location,
+                        imports: imports.to_vec(),
+                    });
+                }
                 "user_type" => {
                     // Extract just the base type name, stripping generic arguments
                     let full_name = node_text(current, source).to_string();
@@ -1118,7 +1373,7 @@ impl KotlinParser {
                     result.references.push(UnresolvedReference {
                         name: name.clone(),
                         qualified_name: None,
-                        kind: ReferenceKind::Type,
+                        kind: Self::user_type_kind(current),
                         location: location.clone(),
                         imports: imports.to_vec(),
                     });
@@ -1147,8 +1402,11 @@ impl KotlinParser {
                         );
 
                         // Use Reflection kind for ::class references (more important for dead code detection)
+                        // `viewModel::onRefresh` reads the variable on the left
                         let ref_kind = if is_class_literal {
                             ReferenceKind::Reflection
+                        } else if type_ref.starts_with(|c: char| c.is_lowercase()) {
+                            ReferenceKind::Read
                         } else {
                             ReferenceKind::Type
                         };
@@ -1171,6 +1429,18 @@ impl KotlinParser {
                                 let method_name = node_text(child, source).to_string();
                                 // Skip "class" which is a keyword, not a method reference
                                 if method_name != "class" {
+                                    // `String::toSlug` picks the extension on String
+                                    if let Some(type_ref) =
+                                        self.extract_callable_reference_type(current, source)
+                                    {
+                                        let receiver =
+                                            if type_ref.starts_with(|c: char| c.is_lowercase()) {
+                                                CallReceiver::Name(type_ref)
+                                            } else {
+                                                CallReceiver::Type(type_ref)
+                                            };
+                                        result.call_receivers.insert(child.start_byte(), receiver);
+                                    }
                                     let location = point_to_location(
                                         path,
                                         child.start_position(),
@@ -1234,9 +1504,10 @@ impl KotlinParser {
                     }
 
                     // If we detected the bug pattern, extract the function call
-                    if has_not_is && error_identifier.is_some() && has_function_type {
+                    if let (true, Some(ident), true) =
+                        (has_not_is, error_identifier, has_function_type)
+                    {
                         // Reconstruct the function name: "is" + error_identifier
-                        let ident = error_identifier.unwrap();
                         let func_name = format!("is{}", ident);
                         let (row, col, start, end) = error_location.unwrap();
 
@@ -1260,14 +1531,48 @@ impl KotlinParser {
                     // Also scan the entire type_test text for additional misparsed function calls
                     // Since the parse error can cascade and absorb multiple when entries
                     let type_test_text = node_text(current, source);
-                    let re_pattern = regex::Regex::new(r"([a-z][a-zA-Z0-9]*)\s*\(\s*\)").ok();
-                    if let Some(re) = re_pattern {
-                        for cap in re.captures_iter(type_test_text) {
+                    let re = &*NO_ARG_CALL_PATTERN;
+                    for cap in re.captures_iter(type_test_text) {
+                        if let Some(m) = cap.get(1) {
+                            let func_name = m.as_str().to_string();
+                            // Skip keywords and already-handled isXxx patterns
+                            if func_name != "if" && func_name != "when" && func_name != "for"
+                                && !func_name.starts_with("is") {
+                                let offset = current.start_byte() + m.start();
+                                let end = current.start_byte() + m.end();
+
+                                let location = point_to_location(
+                                    path,
+                                    current.start_position(),
+                                    current.start_position(),
+                                    offset,
+                                    end,
+                                );
+
+                                result.references.push(UnresolvedReference {
+                                    name: func_name,
+                                    qualified_name: None,
+                                    kind: ReferenceKind::Call,
+                                    location,
+                                    imports: imports.to_vec(),
+                                });
+                            }
+                        }
+                    }
+                }
+                // Also scan when_entry nodes that might have absorbed misparsed content
+                "when_entry" => {
+                    // Check if this when_entry has parse errors by looking for unusual content
+                    // (when entries with errors often contain multiple "-> " patterns)
+                    let entry_text = node_text(current, source);
+                    if entry_text.matches("->").count() > 1 {
+                        // This entry likely contains absorbed misparsed entries
+                        let re = &*NO_ARG_CALL_PATTERN;
+                        for cap in re.captures_iter(entry_text) {
                             if let Some(m) = cap.get(1) {
                                 let func_name = m.as_str().to_string();
-                                // Skip keywords and already-handled isXxx patterns
-                                if func_name != "if" && func_name != "when" && func_name != "for"
-                                    && !func_name.starts_with("is") {
+                                // Skip keywords
+                                if func_name != "if" && func_name != "when" && func_name != "for" {
                                     let offset = current.start_byte() + m.start();
                                     let end = current.start_byte() + m.end();
 
@@ -1291,44 +1596,6 @@ impl KotlinParser {
                         }
                     }
                 }
-                // Also scan when_entry nodes that might have absorbed misparsed content
-                "when_entry" => {
-                    // Check if this when_entry has parse errors by looking for unusual content
-                    // (when entries with errors often contain multiple "-> " patterns)
-                    let entry_text = node_text(current, source);
-                    if entry_text.matches("->").count() > 1 {
-                        // This entry likely contains absorbed misparsed entries
-                        let re_pattern = regex::Regex::new(r"([a-z][a-zA-Z0-9]*)\s*\(\s*\)").ok();
-                        if let Some(re) = re_pattern {
-                            for cap in re.captures_iter(entry_text) {
-                                if let Some(m) = cap.get(1) {
-                                    let func_name = m.as_str().to_string();
-                                    // Skip keywords
-                                    if func_name != "if" && func_name != "when" && func_name != "for" {
-                                        let offset = current.start_byte() + m.start();
-                                        let end = current.start_byte() + m.end();
-
-                                        let location = point_to_location(
-                                            path,
-                                            current.start_position(),
-                                            current.start_position(),
-                                            offset,
-                                            end,
-                                        );
-
-                                        result.references.push(UnresolvedReference {
-                                            name: func_name,
-                                            qualified_name: None,
-                                            kind: ReferenceKind::Call,
-                                            location,
-                                            imports: imports.to_vec(),
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
                 // Workaround for tree-sitter-kotlin grammar bug (continued):
                 // After `!isXxx()` parse errors, the when conditions get misparsed.
                 // Look for ERROR nodes that contain identifiers followed by () in source.
@@ -1339,32 +1606,30 @@ impl KotlinParser {
 
                     // Look for patterns like "identifier()" in the error text
                     // These are likely misparsed function calls
-                    let re_pattern = regex::Regex::new(r"([a-z][a-zA-Z0-9]*)\s*\(\s*\)").ok();
-                    if let Some(re) = re_pattern {
-                        for cap in re.captures_iter(error_text) {
-                            if let Some(m) = cap.get(1) {
-                                let func_name = m.as_str().to_string();
-                                // Skip common keywords
-                                if func_name != "if" && func_name != "when" && func_name != "for" {
-                                    let offset = current.start_byte() + m.start();
-                                    let end = current.start_byte() + m.end();
+                    let re = &*NO_ARG_CALL_PATTERN;
+                    for cap in re.captures_iter(error_text) {
+                        if let Some(m) = cap.get(1) {
+                            let func_name = m.as_str().to_string();
+                            // Skip common keywords
+                            if func_name != "if" && func_name != "when" && func_name != "for" {
+                                let offset = current.start_byte() + m.start();
+                                let end = current.start_byte() + m.end();
 
-                                    let location = point_to_location(
-                                        path,
-                                        current.start_position(),
-                                        current.start_position(),
-                                        offset,
-                                        end,
-                                    );
+                                let location = point_to_location(
+                                    path,
+                                    current.start_position(),
+                                    current.start_position(),
+                                    offset,
+                                    end,
+                                );
 
-                                    result.references.push(UnresolvedReference {
-                                        name: func_name,
-                                        qualified_name: None,
-                                        kind: ReferenceKind::Call,
-                                        location,
-                                        imports: imports.to_vec(),
-                                    });
-                                }
+                                result.references.push(UnresolvedReference {
+                                    name: func_name,
+                                    qualified_name: None,
+                                    kind: ReferenceKind::Call,
+                                    location,
+                                    imports: imports.to_vec(),
+                                });
                             }
                         }
                     }
@@ -1550,6 +1815,14 @@ impl KotlinParser {
     fn determine_class_kind(&self, node: Node, source: &str) -> DeclarationKind {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
+            // `enum class` - the `enum` keyword is a token of the declaration itself
+            if child.kind() == "enum" {
+                return DeclarationKind::Enum;
+            }
+            // `interface` and `fun interface` are tokens too
+            if child.kind() == "interface" {
+                return DeclarationKind::Interface;
+            }
             if child.kind() == "modifiers" {
                 let modifiers_text = node_text(child, source);
                 if modifiers_text.contains("interface") {
@@ -1666,6 +1939,31 @@ impl KotlinParser {
         super_types
     }
 
+    /// Delegation specifiers of a class, whether or not the grammar put them
+    /// under the `delegation_specifiers` field
+    fn delegation_specifiers<'a>(&self, node: Node<'a>) -> Vec<Node<'a>> {
+        let container = node
+            .child_by_field_name("delegation_specifiers")
+            .unwrap_or(node);
+        let mut cursor = container.walk();
+        container
+            .children(&mut cursor)
+            .filter(|child| child.kind() == "delegation_specifier")
+            .collect()
+    }
+
+    /// Interfaces implemented by delegation (e.g., "Bar" from "class Foo : Bar by delegate")
+    fn extract_delegated_types(&self, node: Node, source: &str) -> Vec<String> {
+        self.delegation_specifiers(node)
+            .into_iter()
+            .filter_map(|child| {
+                let text = node_text(child, source);
+                let (type_part, _) = text.split_once(" by ")?;
+                Some(type_part.trim().to_string())
+            })
+            .collect()
+    }
+
     /// Extract class delegation references (e.g., "delegate" from "class Foo : Bar by delegate")
     fn extract_class_delegates(
         &self,
@@ -1675,37 +1973,32 @@ impl KotlinParser {
         imports: &[String],
         result: &mut ParseResult,
     ) {
-        if let Some(delegation) = node.child_by_field_name("delegation_specifiers") {
-            let mut cursor = delegation.walk();
-            for child in delegation.children(&mut cursor) {
-                if child.kind() == "delegation_specifier" {
-                    let text = node_text(child, source);
-                    // Check if this has "by" delegation
-                    if let Some(by_pos) = text.find(" by ") {
-                        let delegate_expr = &text[by_pos + 4..].trim();
-                        // Extract the delegate identifier (first word)
-                        if let Some(delegate_name) = delegate_expr
-                            .split(|c: char| !c.is_alphanumeric() && c != '_')
-                            .next()
-                        {
-                            if !delegate_name.is_empty() {
-                                let location = point_to_location(
-                                    path,
-                                    child.start_position(),
-                                    child.end_position(),
-                                    child.start_byte(),
-                                    child.end_byte(),
-                                );
+        for child in self.delegation_specifiers(node) {
+            let text = node_text(child, source);
+            // Check if this has "by" delegation
+            if let Some(by_pos) = text.find(" by ") {
+                let delegate_expr = &text[by_pos + 4..].trim();
+                // Extract the delegate identifier (first word)
+                if let Some(delegate_name) = delegate_expr
+                    .split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .next()
+                {
+                    if !delegate_name.is_empty() {
+                        let location = point_to_location(
+                            path,
+                            child.start_position(),
+                            child.end_position(),
+                            child.start_byte(),
+                            child.end_byte(),
+                        );
 
-                                result.references.push(UnresolvedReference {
-                                    name: delegate_name.to_string(),
-                                    qualified_name: None,
-                                    kind: ReferenceKind::Delegation,
-                                    location,
-                                    imports: imports.to_vec(),
-                                });
-                            }
-                        }
+                        result.references.push(UnresolvedReference {
+                            name: delegate_name.to_string(),
+                            qualified_name: None,
+                            kind: ReferenceKind::Delegation,
+                            location,
+                            imports: imports.to_vec(),
+                        });
                     }
                 }
             }
@@ -1713,6 +2006,13 @@ impl KotlinParser {
     }
 
     fn extract_annotations(&self, node: Node, source: &str) -> Vec<String> {
+        self.annotation_nodes(node)
+            .into_iter()
+            .map(|annotation| node_text(annotation, source).to_string())
+            .collect()
+    }
+
+    fn annotation_nodes<'t>(&self, node: Node<'t>) -> Vec<Node<'t>> {
         let mut annotations = Vec::new();
         let mut cursor = node.walk();
 
@@ -1721,7 +2021,7 @@ impl KotlinParser {
                 let mut mod_cursor = child.walk();
                 for modifier in child.children(&mut mod_cursor) {
                     if modifier.kind() == "annotation" {
-                        annotations.push(node_text(modifier, source).to_string());
+                        annotations.push(modifier);
                     }
                 }
             }
@@ -1734,7 +2034,7 @@ impl KotlinParser {
                 let mut prefix_cursor = prev.walk();
                 for child in prev.children(&mut prefix_cursor) {
                     if child.kind() == "annotation" {
-                        annotations.push(node_text(child, source).to_string());
+                        annotations.push(child);
                     }
                 }
             }
@@ -1743,6 +2043,74 @@ impl KotlinParser {
         annotations
     }
 
+    /// Names given by the arguments of renaming annotations
+    /// (`@JvmName("legacyApi")`, `@get:JvmName("isOn")`, `@Json(name = "n")`,
+    /// `@SerializedName("id", alternate = ["user_id"])`)
+    fn extract_alternate_names(&self, node: Node, source: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        for annotation in self.annotation_nodes(node) {
+            let mut cursor = annotation.walk();
+            let Some(invocation) = annotation
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "constructor_invocation")
+            else {
+                continue;
+            };
+            let Some(annotation_type) = invocation.named_child(0) else {
+                continue;
+            };
+            let annotation_name = node_text(annotation_type, source);
+            let annotation_name = annotation_name.rsplit('.').next().unwrap_or_default();
+            if !RENAMING_ANNOTATIONS.contains(&annotation_name) {
+                continue;
+            }
+
+            let mut cursor = invocation.walk();
+            let arguments = invocation
+                .named_children(&mut cursor)
+                .filter(|child| child.kind() == "value_arguments")
+                .flat_map(|arguments| {
+                    let mut cursor = arguments.walk();
+                    arguments.named_children(&mut cursor).collect::<Vec<_>>()
+                });
+            for argument in arguments {
+                let mut cursor = argument.walk();
+                let mut children = argument.named_children(&mut cursor);
+                let Some(mut value) = children.next() else {
+                    continue;
+                };
+                // `name = "n"`
+                if value.kind() == "simple_identifier" {
+                    if !NAME_ARGUMENTS.contains(&node_text(value, source)) {
+                        continue;
+                    }
+                    let Some(named_value) = children.next() else {
+                        continue;
+                    };
+                    value = named_value;
+                }
+                // `alternate = ["user_id", "uid"]`
+                let literals = if value.kind() == "collection_literal" {
+                    let mut cursor = value.walk();
+                    value.named_children(&mut cursor).collect()
+                } else {
+                    vec![value]
+                };
+                for literal in literals {
+                    if literal.kind() == "string_literal" && literal.named_child_count() == 1 {
+                        let content = literal
+                            .named_child(0)
+                            .filter(|c| c.kind() == "string_content");
+                        if let Some(content) = content {
+                            names.push(node_text(content, source).to_string());
+                        }
+                    }
+                }
+            }
+        }
+        names
+    }
+
     fn determine_reference_kind(&self, parent: Node) -> Option<ReferenceKind> {
         match parent.kind() {
             "call_expression" => Some(ReferenceKind::Call),
@@ -1754,7 +2122,10 @@ impl KotlinParser {
             // - this.method() → method is in navigation_suffix, parent has call_suffix → Call
             // - DEFAULT_HEIGHT.dpToPx() → DEFAULT_HEIGHT is direct child → Read, dpToPx is Call
             "navigation_suffix" => {
-                if self.is_navigation_method_call(parent) {
+                // `this::handle` / `vm.state::update` reference the function
+                if self.is_navigation_method_call(parent)
+                    || self.is_callable_reference_suffix(parent)
+                {
                     Some(ReferenceKind::Call)
                 } else {
                     // Check if this navigation_suffix is part of an assignment target
@@ -1895,6 +2266,191 @@ impl KotlinParser {
         false
     }
 
+    /// The class a piece of string names (`"com.app.Foo"`) or the member a
+    /// reflective lookup names (`getMethod("load")`), as the simple name and
+    /// the qualified name if any
+    fn extract_string_reference(
+        &self,
+        node: Node,
+        source: &str,
+    ) -> Option<(String, Option<String>)> {
+        let text = node_text(node, source);
+        if let Some(class) = class_name_in_string(text) {
+            let name = class.rsplit('.').next().unwrap_or(&class).to_string();
+            return Some((name, Some(class)));
+        }
+
+        let literal = node
+            .parent()
+            .filter(|p| p.kind() == "string_literal" && p.named_child_count() == 1)?;
+        let argument = literal.parent().filter(|p| p.kind() == "value_argument")?;
+        let call = argument
+            .parent()
+            .filter(|p| p.named_child(0) == Some(argument))
+            .and_then(|arguments| arguments.parent())
+            .and_then(|suffix| suffix.parent())
+            .filter(|c| c.kind() == "call_expression")?;
+        let callee = call.child(0)?;
+        let callee = match callee.kind() {
+            "navigation_expression" => callee.child(callee.child_count().checked_sub(1)?)?,
+            _ => callee,
+        };
+        let lookup = node_text(callee, source).trim_start_matches(['.', '?']);
+        let is_identifier = text.chars().all(|c| c.is_alphanumeric() || c == '_');
+        (REFLECTIVE_LOOKUPS.contains(&lookup) && !text.is_empty() && is_identifier)
+            .then(|| (text.to_string(), None))
+    }
+
+    /// The callee identifier of a call and the arguments passed to it
+    ///
+    /// `f(1) { }` parses as a call wrapping `f(1)` with the trailing lambda;
+    /// the inner call reports it and the wrapper is skipped.
+    fn extract_call_arguments<'a>(
+        &self,
+        node: Node<'a>,
+        source: &str,
+    ) -> Option<(Node<'a>, CallArguments)> {
+        if node.kind() != "call_expression" {
+            return None;
+        }
+        let callee = node.child(0)?;
+        let suffix = node.child(1).filter(|s| s.kind() == "call_suffix")?;
+        let identifier = match callee.kind() {
+            "simple_identifier" => callee,
+            "navigation_expression" => {
+                let member = callee.child(callee.child_count().checked_sub(1)?)?;
+                let mut cursor = member.walk();
+                let identifier = member
+                    .children(&mut cursor)
+                    .find(|c| c.kind() == "simple_identifier")?;
+                identifier
+            }
+            _ => return None,
+        };
+
+        let has_lambda = |suffix: Node| {
+            let mut cursor = suffix.walk();
+            let found = suffix
+                .children(&mut cursor)
+                .any(|c| c.kind() == "annotated_lambda");
+            found
+        };
+        let wrapper_suffix = node
+            .parent()
+            .filter(|p| p.kind() == "call_expression" && p.child(0) == Some(node))
+            .and_then(|p| p.child(1));
+        let mut arguments = CallArguments {
+            arguments: Vec::new(),
+            trailing_lambda: has_lambda(suffix) || wrapper_suffix.is_some_and(has_lambda),
+        };
+
+        let mut cursor = suffix.walk();
+        let value_arguments = suffix
+            .children(&mut cursor)
+            .find(|c| c.kind() == "value_arguments");
+        if let Some(value_arguments) = value_arguments {
+            let mut cursor = value_arguments.walk();
+            for argument in value_arguments
+                .children(&mut cursor)
+                .filter(|c| c.kind() == "value_argument")
+            {
+                let value = argument.named_child(argument.named_child_count().checked_sub(1)?)?;
+                let name = argument
+                    .named_child(0)
+                    .filter(|first| first.kind() == "simple_identifier")
+                    .filter(|first| self.is_named_argument_param_name(argument, *first))
+                    .map(|first| node_text(first, source).to_string());
+                arguments.arguments.push(CallArgument {
+                    name,
+                    value: node_text(value, source).to_string(),
+                    spread: value.kind() == "spread_expression",
+                });
+            }
+        }
+
+        Some((identifier, arguments))
+    }
+
+    /// Whether a navigation_suffix is the `::name` of a callable reference
+    /// (`this::handle`, `vm.state::update`)
+    fn is_callable_reference_suffix(&self, node: Node) -> bool {
+        node.kind() == "navigation_suffix" && node.child(0).is_some_and(|c| c.kind() == "::")
+    }
+
+    /// The callee identifier of a qualified call, the property of a
+    /// qualified access (`Foo.CONSTANT`) or the function of a bound callable
+    /// reference (`name::toSlug`), and what its receiver is
+    ///
+    /// `name.toSlug()` names its receiver, `"a b".toSlug()`,
+    /// `Slugger().slug()` and `Foo.Companion.create()` show its type. Other
+    /// receivers (`this`, chained calls, ...) are left unknown.
+    fn extract_call_receiver<'a>(
+        &self,
+        node: Node<'a>,
+        source: &str,
+    ) -> Option<(Node<'a>, CallReceiver)> {
+        let callee = match node.kind() {
+            "call_expression" => node
+                .child(0)
+                .filter(|c| c.kind() == "navigation_expression")?,
+            "navigation_expression" => node,
+            _ => return None,
+        };
+        let suffix = callee.child(callee.child_count().checked_sub(1)?)?;
+        let mut cursor = suffix.walk();
+        let identifier = suffix
+            .children(&mut cursor)
+            .find(|c| c.kind() == "simple_identifier")?;
+
+        let receiver = callee.child(0)?;
+        let receiver = match receiver.kind() {
+            "string_literal" | "multiline_string_literal" => CallReceiver::Type("String".into()),
+            "integer_literal" => CallReceiver::Type("Int".into()),
+            "long_literal" => CallReceiver::Type("Long".into()),
+            "real_literal" => CallReceiver::Type("Double".into()),
+            "boolean_literal" => CallReceiver::Type("Boolean".into()),
+            "character_literal" => CallReceiver::Type("Char".into()),
+            "simple_identifier" => {
+                let name = node_text(receiver, source);
+                if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+                    // `Slugger.slug()`: an object or companion
+                    CallReceiver::Type(name.to_string())
+                } else {
+                    CallReceiver::Name(name.to_string())
+                }
+            }
+            // `Slugger().slug()`
+            "call_expression" => {
+                let constructor = receiver
+                    .child(0)
+                    .filter(|c| c.kind() == "simple_identifier")?;
+                let name = node_text(constructor, source);
+                if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+                    return None;
+                }
+                CallReceiver::Type(name.to_string())
+            }
+            // `Foo.Companion.create()` / `Outer.Inner.CONSTANT`
+            "navigation_expression" => {
+                let qualifier = node_text(receiver, source);
+                let is_type = qualifier
+                    .split('.')
+                    .all(|segment| segment.chars().all(|c| c.is_alphanumeric() || c == '_'))
+                    && qualifier
+                        .rsplit('.')
+                        .next()
+                        .is_some_and(|name| name.starts_with(|c: char| c.is_ascii_uppercase()));
+                if !is_type {
+                    return None;
+                }
+                CallReceiver::Type(qualifier.to_string())
+            }
+            _ => return None,
+        };
+
+        Some((identifier, receiver))
+    }
+
     /// Check if an identifier in a value_argument is the parameter name (left of =)
     /// vs the value (right of =). Returns true if it's the parameter name.
     ///
@@ -1916,6 +2472,57 @@ impl KotlinParser {
         false
     }
 
+    /// Kind of a reference to a `user_type`: an annotation applied to a
+    /// declaration or type, a type in a declaration's signature, or another
+    /// use of the type
+    ///
+    /// Signatures are parameter, return, receiver and bound types, and the
+    /// types of properties that are not local variables.
+    fn user_type_kind(node: Node) -> ReferenceKind {
+        let mut current = node;
+        while let Some(parent) = current.parent() {
+            match parent.kind() {
+                // The type is part of a larger type: `List<Foo>?`, `(Foo) -> Unit`
+                "user_type"
+                | "nullable_type"
+                | "non_nullable_type"
+                | "parenthesized_type"
+                | "type_arguments"
+                | "type_projection"
+                | "function_type"
+                | "function_type_parameters"
+                | "constructor_invocation" => current = parent,
+                "annotation" => return ReferenceKind::AnnotationUse,
+                "parameter"
+                | "class_parameter"
+                | "function_declaration"
+                | "type_parameter"
+                | "type_constraint"
+                | "getter"
+                | "setter" => return ReferenceKind::TypeReference,
+                "variable_declaration" | "property_declaration" => {
+                    let property = if parent.kind() == "variable_declaration" {
+                        parent
+                            .parent()
+                            .filter(|p| p.kind() == "property_declaration")
+                    } else {
+                        Some(parent)
+                    };
+                    let is_member = property
+                        .and_then(|p| p.parent())
+                        .is_some_and(|p| p.kind() != "statements");
+                    return if is_member {
+                        ReferenceKind::TypeReference
+                    } else {
+                        ReferenceKind::Type
+                    };
+                }
+                _ => return ReferenceKind::Type,
+            }
+        }
+        ReferenceKind::Type
+    }
+
     /// Check if a callable_reference is a class literal (::class)
     /// as opposed to a method reference (::method)
     fn is_class_literal(&self, node: Node, source: &str) -> bool {
@@ -1952,6 +2559,8 @@ impl KotlinParser {
         // Look for the type on the left side of ::
         for child in node.children(&mut cursor) {
             match child.kind() {
+                // `::top` has no left side
+                "::" => break,
                 // Type identifier (e.g., MyProvider in MyProvider::class)
                 // This is the most common case for class literals
                 "type_identifier" => {
@@ -2141,6 +2750,12 @@ impl KotlinParser {
                         continue;
                     }
 
+                    // Skip the names of function declarations (`fun String.toSlug(`)
+                    let line_start = source[..match_start].rfind('\n').map_or(0, |i| i + 1);
+                    if FUN_DECLARATION_PREFIX.is_match(&source[line_start..match_start]) {
+                        continue;
+                    }
+
                     // Create location
                     let (line, col) = self.byte_to_line_col(source, match_start);
                     let location = Location::new(
@@ -2250,6 +2865,23 @@ impl Parser for KotlinParser {
             .parse(contents, None)
             .ok_or_else(|| miette::miette!("Failed to parse Kotlin file"))?;
 
+        self.parse_tree(path, contents, &tree)
+    }
+
+    fn parse_incremental(
+        &self,
+        path: &Path,
+        contents: &str,
+        trees: &TreeCache,
+    ) -> Result<ParseResult> {
+        let tree = trees.parse(path, contents, &tree_sitter_kotlin::language())?;
+        self.parse_tree(path, contents, &tree)
+    }
+}
+
+impl KotlinParser {
+    /// Extract declarations and references from a parsed tree
+    fn parse_tree(&self, path: &Path, contents: &str, tree: &Tree) -> Result<ParseResult> {
         let root = tree.root_node();
         let mut result = ParseResult::new();
 
@@ -2280,6 +2912,8 @@ impl Parser for KotlinParser {
         // Scan the source text for function call patterns that weren't captured.
         temp_parser.scan_missed_function_calls(path, contents, &imports, &mut result);
 
+        measure_declarations(root, contents, &mut result.declarations, Self::is_branch);
+
         debug!(
             "Parsed {}: {} declarations, {} references",
             path.display(),
@@ -2289,6 +2923,21 @@ impl Parser for KotlinParser {
 
         Ok(result)
     }
+
+    /// Whether a node adds a path through the code, for cyclomatic complexity
+    fn is_branch(node: Node) -> bool {
+        matches!(
+            node.kind(),
+            "if_expression"
+                | "when_condition"
+                | "for_statement"
+                | "while_statement"
+                | "do_while_statement"
+                | "catch_block"
+                | "&&"
+                | "||"
+        )
+    }
 }
 
 impl Default for KotlinParser {
@@ -2333,4 +2982,300 @@ mod tests {
 
         assert_eq!(result.imports.len(), 2);
     }
+
+    #[test]
+    fn test_string_template_references() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            const val TAG = "Main"
+
+            fun log(message: String) {
+                println("$TAG: ${Keys.PREFIX} $message")
+            }
+        "#;
+
+        let result = parser.parse(Path::new("test.kt"), source).unwrap();
+
+        for name in ["TAG", "PREFIX", "message"] {
+            assert!(
+                result
+                    .references
+                    .iter()
+                    .any(|r| r.name == name && r.kind == ReferenceKind::Read),
+                "missing read of {}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_enum_entries_and_enumeration() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            enum class Level(val code: Int) {
+                @SerialName("low") LOW(1),
+                HIGH(2);
+
+                fun isHigh() = this == HIGH
+            }
+
+            fun all() {
+                Level.entries.forEach { println(it) }
+                enumValues<Status>().forEach { println(it) }
+                println(Mode.valueOf("FAST"))
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Level.kt"), source).unwrap();
+
+        let level = result
+            .declarations
+            .iter()
+            .find(|d| d.name == "Level")
+            .unwrap();
+        assert_eq!(level.kind, DeclarationKind::Enum);
+
+        let cases: Vec<_> = result
+            .declarations
+            .iter()
+            .filter(|d| d.kind == DeclarationKind::EnumCase)
+            .collect();
+        assert_eq!(cases.len(), 2);
+        assert!(cases.iter().all(|c| c.parent.as_ref() == Some(&level.id)));
+        assert!(cases[0].annotations[0].contains("SerialName"));
+        assert!(result.declarations.iter().any(|d| d.name == "isHigh"));
+
+        let enumerated: Vec<_> = result
+            .references
+            .iter()
+            .filter(|r| r.kind == ReferenceKind::EnumValues)
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(enumerated, ["Level", "Status", "Mode"]);
+    }
+
+    #[test]
+    fn test_extension_declarations() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            fun List<Int>.total(): Int = sum()
+
+            val String?.wordCount: Int
+                get() = this?.split(" ")?.size ?: 0
+
+            fun plain() {}
+        "#;
+
+        let result = parser.parse(Path::new("StringExt.kt"), source).unwrap();
+
+        let extensions: Vec<_> = result
+            .declarations
+            .iter()
+            .filter(|d| d.is_extension())
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(extensions, ["total", "wordCount"]);
+        let receivers: Vec<_> = result
+            .declarations
+            .iter()
+            .filter_map(|d| d.receiver_type.as_deref())
+            .collect();
+        assert_eq!(receivers, ["List", "String"]);
+        for receiver in ["List", "String"] {
+            assert!(result
+                .references
+                .iter()
+                .any(|r| r.name == receiver && r.kind == ReferenceKind::ExtensionReceiver));
+        }
+    }
+
+    #[test]
+    fn test_call_arguments_and_parameter_defaults() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            fun load(id: Int, retries: Int = 3, vararg tags: String, onDone: () -> Unit = {}) {}
+
+            fun main() {
+                load(1, retries = 5)
+                repo.load(2, *tags) { println() }
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Loader.kt"), source).unwrap();
+
+        let parameters: Vec<_> = result
+            .declarations
+            .iter()
+            .filter(|d| d.kind == DeclarationKind::Parameter)
+            .map(|d| (d.name.as_str(), d.modifiers.join(" ")))
+            .collect();
+        assert_eq!(
+            parameters,
+            [
+                ("id", String::new()),
+                ("retries", "default".to_string()),
+                ("tags", "vararg".to_string()),
+                ("onDone", "default".to_string()),
+            ]
+        );
+
+        let mut calls: Vec<_> = result
+            .references
+            .iter()
+            .filter(|r| r.name == "load" && r.kind == ReferenceKind::Call)
+            .filter_map(|r| result.call_arguments.get(&r.location.start_byte))
+            .collect();
+        calls.sort_by_key(|args| args.trailing_lambda);
+        assert_eq!(calls.len(), 2);
+        assert_eq!(
+            calls[0].arguments,
+            [
+                CallArgument {
+                    name: None,
+                    value: "1".to_string(),
+                    spread: false,
+                },
+                CallArgument {
+                    name: Some("retries".to_string()),
+                    value: "5".to_string(),
+                    spread: false,
+                },
+            ]
+        );
+        assert!(calls[1].trailing_lambda);
+        assert_eq!(calls[1].positional_count(), 2);
+        assert!(calls[1].arguments[1].spread);
+    }
+
+    #[test]
+    fn test_renaming_annotation_arguments() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            class User {
+                @SerializedName("id", alternate = ["user_id", "uid"])
+                val id: String = ""
+
+                @field:Json(name = "display_name")
+                val name: String = ""
+
+                @JsonProperty(value = "mail", defaultValue = "none")
+                val email: String = ""
+
+                @JvmName("legacyApi")
+                fun api() {}
+
+                @get:JvmName("isActive")
+                val active: Boolean = true
+
+                @Deprecated("use api") fun old() {}
+            }
+        "#;
+
+        let result = parser.parse(Path::new("User.kt"), source).unwrap();
+
+        let alternate_names = |name: &str| {
+            result
+                .declarations
+                .iter()
+                .find(|d| d.name == name)
+                .unwrap()
+                .alternate_names
+                .clone()
+        };
+        assert_eq!(alternate_names("id"), ["id", "user_id", "uid"]);
+        assert_eq!(alternate_names("name"), ["display_name"]);
+        assert_eq!(alternate_names("email"), ["mail"]);
+        assert_eq!(alternate_names("api"), ["legacyApi"]);
+        assert_eq!(alternate_names("active"), ["isActive"]);
+        assert!(alternate_names("old").is_empty());
+    }
+
+    #[test]
+    fn test_callable_references() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            class Screen(private val viewModel: ScreenViewModel) {
+                fun bind() {
+                    button.setOnClickListener(viewModel::onRefresh)
+                    items.forEach(this::render)
+                    names.map(String::toSlug)
+                    run(::log)
+                }
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Screen.kt"), source).unwrap();
+
+        let kinds = |name: &str| -> Vec<ReferenceKind> {
+            result
+                .references
+                .iter()
+                .filter(|r| r.name == name)
+                .map(|r| r.kind)
+                .collect()
+        };
+        assert_eq!(kinds("onRefresh"), [ReferenceKind::Call]);
+        assert_eq!(kinds("render"), [ReferenceKind::Call]);
+        assert_eq!(kinds("toSlug"), [ReferenceKind::Call]);
+        assert_eq!(kinds("log"), [ReferenceKind::Call]);
+        assert!(kinds("viewModel").contains(&ReferenceKind::Read));
+        assert!(!kinds("viewModel").contains(&ReferenceKind::Type));
+
+        let to_slug = result
+            .references
+            .iter()
+            .find(|r| r.name == "toSlug")
+            .unwrap();
+        assert_eq!(
+            result.call_receivers.get(&to_slug.location.start_byte),
+            Some(&CallReceiver::Type("String".to_string()))
+        );
+    }
+    #[test]
+    fn test_signature_reference_kinds() {
+        let parser = KotlinParser::new();
+        let source = r#"
+            class Screen<T : Listener> {
+                @Tracked("screen")
+                fun show(@Param widget: Widget, onEvent: (Event) -> Unit): Result? {
+                    val local: Local = Local()
+                    return null
+                }
+
+                @Inject lateinit var config: Config
+
+                fun Receiver.labels(): List<@Marker String> = emptyList()
+            }
+        "#;
+
+        let result = parser.parse(Path::new("Screen.kt"), source).unwrap();
+
+        let kinds = |name: &str| -> Vec<ReferenceKind> {
+            result
+                .references
+                .iter()
+                .filter(|r| r.name == name)
+                .map(|r| r.kind)
+                .collect()
+        };
+        for annotation in ["Tracked", "Param", "Inject", "Marker"] {
+            assert_eq!(
+                kinds(annotation),
+                [ReferenceKind::AnnotationUse],
+                "{}",
+                annotation
+            );
+        }
+        for signature_type in [
+            "Listener", "Widget", "Event", "Result", "Config", "Receiver",
+        ] {
+            assert!(
+                kinds(signature_type).contains(&ReferenceKind::TypeReference),
+                "{}",
+                signature_type
+            );
+        }
+        assert!(kinds("Local").contains(&ReferenceKind::Type));
+        assert!(!kinds("Local").contains(&ReferenceKind::TypeReference));
+    }
 }
@@ -1,8 +1,12 @@
+pub mod aidl;
+pub mod baseline_profile;
 mod common;
 mod java;
+pub mod jni;
 mod kotlin;
+pub mod proto;
 pub mod xml;
 
-pub use common::Parser;
+pub use common::{line_of, simple_name, Parser, TreeCache};
 pub use java::JavaParser;
 pub use kotlin::KotlinParser;
@@ -0,0 +1,415 @@
+// Protocol Buffers parser
+//
+// Reads the declarations of `.proto` files (src/main/proto/**.proto):
+// ```
+// syntax = "proto3";
+// package app.feed;
+// option java_package = "com.app.feed.proto";
+// option java_multiple_files = true;
+//
+// message FeedItem {
+//     string id = 1;
+//     Author author = 2;
+//     message Author { string name = 1; }
+// }
+//
+// service FeedService {
+//     rpc GetFeed (FeedRequest) returns (stream FeedItem);
+// }
+// ```
+// and the Java/Kotlin classes protoc and the gRPC plugins generate for them.
+
+#![allow(dead_code)] // API methods reserved for future use
+
+use super::common::{line_of, simple_name};
+use miette::Result;
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static PACKAGE_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^\s*package\s+([\w.]+)\s*;").expect("Invalid package regex"));
+
+// option java_package = "com.app.proto";
+static OPTION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^\s*option\s+(java_package|java_outer_classname|java_multiple_files)\s*=\s*"?([\w.]+)"?\s*;"#)
+        .expect("Invalid option regex")
+});
+
+// message Foo {, enum Foo {, service Foo {
+static BLOCK_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(message|enum|service|oneof|extend)\s+([\w.]+)\s*\{")
+        .expect("Invalid block regex")
+});
+
+// rpc GetFeed (FeedRequest) returns (stream FeedItem)
+static RPC_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\brpc\s+(\w+)\s*\(\s*(?:stream\s+)?([\w.]+)\s*\)\s*returns\s*\(\s*(?:stream\s+)?([\w.]+)\s*\)")
+        .expect("Invalid rpc regex")
+});
+
+// repeated Author author = 2; / map<string, Author> authors = 3;
+static FIELD_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\s*(?:repeated\s+|optional\s+|required\s+)?(?:map\s*<\s*[\w.]+\s*,\s*([\w.]+)\s*>|([\w.]+))\s+\w+\s*=\s*\d+")
+        .expect("Invalid field regex")
+});
+
+/// Parsed `.proto` file
+#[derive(Debug, Clone, Default)]
+pub struct ProtoFile {
+    /// Proto package (`package app.feed;`)
+    pub package: Option<String>,
+    /// Java package of the generated classes, defaulting to the proto package
+    pub java_package: Option<String>,
+    /// Outer class holding the messages, unless `java_multiple_files` is set
+    pub outer_class: String,
+    pub multiple_files: bool,
+    /// Messages, including nested ones
+    pub messages: Vec<ProtoMessage>,
+    /// Enums, including nested ones
+    pub enums: Vec<String>,
+    pub services: Vec<ProtoService>,
+}
+
+/// A message declaration
+#[derive(Debug, Clone)]
+pub struct ProtoMessage {
+    pub name: String,
+    /// Enclosing messages, outermost first
+    pub parents: Vec<String>,
+    /// 1-based line of the declaration
+    pub line: usize,
+    /// Simple names of the message types of its fields
+    pub field_types: Vec<String>,
+}
+
+/// A gRPC service declaration
+#[derive(Debug, Clone)]
+pub struct ProtoService {
+    pub name: String,
+    /// 1-based line of the declaration
+    pub line: usize,
+    pub rpcs: Vec<ProtoRpc>,
+}
+
+/// An rpc of a service
+#[derive(Debug, Clone)]
+pub struct ProtoRpc {
+    pub name: String,
+    pub request: String,
+    pub response: String,
+}
+
+impl ProtoFile {
+    /// Fully qualified names of the top-level classes generated for the
+    /// file: the outer class (or each message and enum), gRPC stubs and
+    /// Kotlin DSL files
+    pub fn generated_classes(&self) -> Vec<String> {
+        let package = self.java_package.as_deref().or(self.package.as_deref());
+        let qualify = |name: &str| match package {
+            Some(package) => format!("{}.{}", package, name),
+            None => name.to_string(),
+        };
+
+        let mut classes = vec![qualify(&self.outer_class)];
+        if self.multiple_files {
+            let top_level = self
+                .messages
+                .iter()
+                .filter(|m| m.parents.is_empty())
+                .map(|m| m.name.as_str())
+                .chain(self.enums.iter().map(String::as_str));
+            for name in top_level {
+                classes.push(qualify(name));
+                classes.push(qualify(&format!("{}Kt", name)));
+                classes.push(qualify(&format!("{}OrBuilder", name)));
+            }
+        }
+        for service in &self.services {
+            classes.push(qualify(&format!("{}Grpc", service.name)));
+            classes.push(qualify(&format!("{}GrpcKt", service.name)));
+        }
+        classes
+    }
+}
+
+impl ProtoService {
+    /// Generated base classes a server implementation extends, nested in
+    /// `FooGrpc` and `FooGrpcKt`
+    pub fn implementation_bases(&self) -> [String; 2] {
+        [
+            format!("{}ImplBase", self.name),
+            format!("{}CoroutineImplBase", self.name),
+        ]
+    }
+}
+
+impl ProtoRpc {
+    /// Name of the generated stub method (`GetFeed` -> `getFeed`)
+    pub fn method_name(&self) -> String {
+        let mut chars = self.name.chars();
+        match chars.next() {
+            Some(first) => first.to_lowercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+}
+
+/// Parser for `.proto` files
+pub struct ProtoParser;
+
+impl ProtoParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse a `.proto` file
+    pub fn parse(&self, path: &Path, contents: &str) -> Result<ProtoFile> {
+        let source = strip_comments(contents);
+        let mut file = ProtoFile {
+            package: PACKAGE_PATTERN
+                .captures(&source)
+                .map(|cap| cap[1].to_string()),
+            outer_class: outer_class_name(path),
+            ..Default::default()
+        };
+        for cap in OPTION_PATTERN.captures_iter(&source) {
+            match &cap[1] {
+                "java_package" => file.java_package = Some(cap[2].to_string()),
+                "java_outer_classname" => file.outer_class = cap[2].to_string(),
+                _ => file.multiple_files = &cap[2] == "true",
+            }
+        }
+
+        // Open blocks: (kind, name, end offset)
+        let mut open: Vec<(String, String, usize)> = Vec::new();
+        for cap in BLOCK_PATTERN.captures_iter(&source) {
+            let whole = cap.get(0).unwrap();
+            open.retain(|(_, _, end)| *end > whole.start());
+            let end = matching_brace(&source, whole.end() - 1);
+            let kind = cap[1].to_string();
+            let name = cap[2].to_string();
+            let line = line_of(&source, whole.start());
+            let body = &source[whole.end()..end];
+
+            match kind.as_str() {
+                "message" => file.messages.push(ProtoMessage {
+                    name: name.clone(),
+                    parents: open
+                        .iter()
+                        .filter(|(kind, _, _)| kind == "message")
+                        .map(|(_, name, _)| name.clone())
+                        .collect(),
+                    line,
+                    field_types: own_fields(body).iter().map(|t| simple_name(t)).collect(),
+                }),
+                "enum" => file.enums.push(name.clone()),
+                "service" => file.services.push(ProtoService {
+                    name: name.clone(),
+                    line,
+                    rpcs: RPC_PATTERN
+                        .captures_iter(body)
+                        .map(|rpc| ProtoRpc {
+                            name: rpc[1].to_string(),
+                            request: simple_name(&rpc[2]),
+                            response: simple_name(&rpc[3]),
+                        })
+                        .collect(),
+                }),
+                _ => {}
+            }
+            open.push((kind, name, end));
+        }
+
+        Ok(file)
+    }
+}
+
+impl Default for ProtoParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Types of the fields declared directly in a message body, skipping nested
+/// messages and enums but keeping `oneof` members
+fn own_fields(body: &str) -> Vec<String> {
+    let mut own = String::with_capacity(body.len());
+    let mut depth = 0usize;
+    let mut in_oneof = Vec::new();
+    let mut rest = body;
+    while let Some(c) = rest.chars().next() {
+        if c == '{' {
+            let head = own.trim_end();
+            in_oneof.push(
+                head.rsplit_once(char::is_whitespace)
+                    .map_or(head, |(h, _)| h)
+                    .ends_with("oneof"),
+            );
+            depth += 1;
+        } else if c == '}' {
+            depth = depth.saturating_sub(1);
+            in_oneof.pop();
+        } else if depth == 0 || in_oneof.last() == Some(&true) && depth == 1 {
+            own.push(c);
+        } else if c == '\n' {
+            own.push('\n');
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    FIELD_PATTERN
+        .captures_iter(&own)
+        .filter_map(|cap| cap.get(1).or(cap.get(2)))
+        .map(|m| m.as_str().to_string())
+        .filter(|t| t.starts_with(char::is_uppercase) || t.contains('.'))
+        .collect()
+}
+
+/// Default outer class: the file name in UpperCamelCase (`feed_item.proto`
+/// -> `FeedItem`)
+fn outer_class_name(path: &Path) -> String {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let mut name = String::with_capacity(stem.len());
+    let mut upper = true;
+    for c in stem.chars() {
+        if c.is_ascii_alphanumeric() {
+            if upper {
+                name.extend(c.to_uppercase());
+            } else {
+                name.push(c);
+            }
+            upper = c.is_ascii_digit();
+        } else {
+            upper = true;
+        }
+    }
+    name
+}
+
+/// Byte offset of the brace closing the one at `open`
+fn matching_brace(source: &str, open: usize) -> usize {
+    let mut depth = 0usize;
+    for (index, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + index;
+                }
+            }
+            _ => {}
+        }
+    }
+    source.len()
+}
+
+/// Blank out comments, keeping line breaks
+fn strip_comments(contents: &str) -> String {
+    let mut out = String::with_capacity(contents.len());
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proto() {
+        let proto = r#"// Feed API
+syntax = "proto3";
+package app.feed;
+
+option java_package = "com.app.feed.proto";
+option java_multiple_files = true;
+
+/* A feed entry */
+message FeedItem {
+    string id = 1;
+    Author author = 2;
+    repeated google.protobuf.Timestamp edits = 3;
+    map<string, Reaction> reactions = 4;
+    oneof media {
+        Photo photo = 5;
+    }
+
+    message Author {
+        string name = 1;
+        Avatar avatar = 2;
+    }
+}
+
+enum Kind { KIND_UNSPECIFIED = 0; }
+
+service FeedService {
+    rpc GetFeed (FeedRequest) returns (stream FeedItem);
+}
+"#;
+        let file = ProtoParser::new()
+            .parse(Path::new("feed_api.proto"), proto)
+            .unwrap();
+
+        assert_eq!(file.package.as_deref(), Some("app.feed"));
+        assert_eq!(file.java_package.as_deref(), Some("com.app.feed.proto"));
+        assert_eq!(file.outer_class, "FeedApi");
+        assert!(file.multiple_files);
+
+        let messages: Vec<_> = file
+            .messages
+            .iter()
+            .map(|m| (m.name.as_str(), m.parents.clone(), m.line))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                ("FeedItem", vec![], 9),
+                ("Author", vec!["FeedItem".to_string()], 18)
+            ]
+        );
+        assert_eq!(
+            file.messages[0].field_types,
+            ["Author", "Timestamp", "Reaction", "Photo"]
+        );
+        assert_eq!(file.messages[1].field_types, ["Avatar"]);
+        assert_eq!(file.enums, ["Kind"]);
+
+        let service = &file.services[0];
+        assert_eq!(service.name, "FeedService");
+        assert_eq!(service.rpcs[0].method_name(), "getFeed");
+        assert_eq!(service.rpcs[0].request, "FeedRequest");
+        assert_eq!(service.rpcs[0].response, "FeedItem");
+
+        let generated = file.generated_classes();
+        assert!(generated.contains(&"com.app.feed.proto.FeedApi".to_string()));
+        assert!(generated.contains(&"com.app.feed.proto.FeedItem".to_string()));
+        assert!(generated.contains(&"com.app.feed.proto.FeedItemKt".to_string()));
+        assert!(generated.contains(&"com.app.feed.proto.FeedServiceGrpcKt".to_string()));
+        assert!(!generated.contains(&"com.app.feed.proto.Author".to_string()));
+    }
+}
@@ -1,4 +1,4 @@
-use super::{MethodReference, XmlParseResult};
+use super::{MemberChain, MethodReference, XmlParseResult};
 use miette::Result;
 use quick_xml::events::Event;
 use quick_xml::Reader;
@@ -22,6 +22,21 @@ static LAMBDA_METHOD_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
         .expect("Invalid lambda method regex")
 });
 
+static MEMBER_CHAIN_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: variable followed by member accesses
+    // Examples: viewModel.user.name, vm::onSave, Converters.format(price)
+    Regex::new(r"([A-Za-z_][A-Za-z0-9_]*)((?:\s*(?:::|\.)\s*[A-Za-z_][A-Za-z0-9_]*)+)")
+        .expect("Invalid member chain regex")
+});
+
+static STRING_LITERAL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"'[^']*'|"[^"]*"|`[^`]*`|&quot;.*?&quot;"#).expect("Invalid string literal regex")
+});
+
+/// Attributes naming a class the framework instantiates by reflection
+/// (`app:layoutManager`, `app:layout_behavior`)
+const CLASS_ATTRIBUTES: &[&str] = &["layoutManager", "layout_behavior"];
+
 /// Parser for Android layout XML files
 pub struct LayoutParser;
 
@@ -64,7 +79,8 @@ impl LayoutParser {
                         for attr in e.attributes().filter_map(|a| a.ok()) {
                             let key = String::from_utf8_lossy(attr.key.as_ref());
                             if key == "class" {
-                                let value = String::from_utf8_lossy(&attr.value).to_string();
+                                // Nested classes are written `Outer$Inner`
+                                let value = String::from_utf8_lossy(&attr.value).replace('$', ".");
                                 result.class_references.insert(value);
                             }
                         }
@@ -79,23 +95,34 @@ impl LayoutParser {
                             let value = String::from_utf8_lossy(&attr.value).to_string();
                             if value.contains('.') || value.starts_with('.') {
                                 // Need package context to resolve relative names
+                                result.class_references.insert(value.clone());
+                                result.context_class.get_or_insert(value);
+                            }
+                        }
+
+                        // app:layoutManager=".ui.GridManager" (relative to the app
+                        // package), app:layout_behavior="com.example.FabBehavior"
+                        let local_name = key.rsplit(':').next().unwrap_or(&key);
+                        if CLASS_ATTRIBUTES.contains(&local_name) {
+                            let value = String::from_utf8_lossy(&attr.value).replace('$', ".");
+                            if value.contains('.') && !value.starts_with('@') {
                                 result.class_references.insert(value);
                             }
                         }
 
-                        // Any attribute with binding expression @{...}
+                        // Any attribute with binding expression @{...} or @={...}
                         let value = String::from_utf8_lossy(&attr.value).to_string();
-                        if value.starts_with("@{") {
+                        if value.starts_with("@{") || value.starts_with("@={") {
                             self.extract_binding_references(&value, &mut result);
                         }
 
                         // android:onClick="onButtonClick" (non-binding method references)
+                        // Resolved against the tools:context activity when there is one
                         if (key == "android:onClick" || key.ends_with(":onClick"))
                             && !value.starts_with('@')
                             && !value.is_empty()
                         {
-                            // Legacy onClick - these are activity methods, harder to track
-                            // but we could add them if we knew the activity from tools:context
+                            result.click_handlers.insert(value);
                         }
                     }
 
@@ -226,15 +253,19 @@ impl LayoutParser {
     /// Extract class and method references from data binding expressions
     fn extract_binding_references(&self, expression: &str, result: &mut XmlParseResult) {
         // Data binding expressions like "@{viewModel.field}" or "@{com.example.Util.method()}"
-        if !expression.starts_with("@{") || !expression.ends_with('}') {
+        let (inner, two_way) = match expression.strip_prefix("@=") {
+            Some(rest) => (rest, true),
+            None => (&expression[1..], false),
+        };
+        if !inner.starts_with('{') || !inner.ends_with('}') {
             return;
         }
-
-        let inner = &expression[2..expression.len() - 1];
+        let inner = &inner[1..inner.len() - 1];
 
         // Extract method calls like: viewModel.onClicked() or viewModel::onClicked
         // Also handles lambdas: (_) -> viewModel.onClicked()
         self.extract_method_calls(inner, result);
+        self.extract_member_chains(inner, two_way, result);
 
         // Look for fully qualified class names (e.g., com.example.Util.method())
         for word in inner.split(|c: char| !c.is_alphanumeric() && c != '.') {
@@ -294,6 +325,31 @@ impl LayoutParser {
         }
     }
 
+    /// Extract member chains read from binding variables, such as
+    /// `viewModel.user.name`. Chains are stored against the variable name
+    /// until the variables are known.
+    fn extract_member_chains(&self, expression: &str, two_way: bool, result: &mut XmlParseResult) {
+        let expression = STRING_LITERAL_PATTERN.replace_all(expression, "");
+        for cap in MEMBER_CHAIN_PATTERN.captures_iter(&expression) {
+            let start = cap.get(0).map_or(0, |m| m.start());
+            // Part of a longer chain, e.g. `android.view.View`
+            if expression[..start].trim_end().ends_with(['.', ':']) {
+                continue;
+            }
+            let members: Vec<String> = cap[2]
+                .split(['.', ':'])
+                .map(str::trim)
+                .filter(|m| !m.is_empty())
+                .map(str::to_string)
+                .collect();
+            result.member_chains.insert(MemberChain {
+                class_fqn: format!("__var__{}", &cap[1]),
+                members,
+                two_way,
+            });
+        }
+    }
+
     /// Resolve method references using binding variable types
     fn resolve_method_references(&self, result: &mut XmlParseResult) {
         let resolved: Vec<MethodReference> = result
@@ -323,6 +379,19 @@ impl LayoutParser {
 
         // Replace with resolved references
         result.method_references = resolved.into_iter().collect();
+
+        // Chains from variables that aren't declared can't be resolved
+        let variables = &result.binding_variables;
+        result.member_chains = std::mem::take(&mut result.member_chains)
+            .into_iter()
+            .filter_map(|chain| {
+                let var_name = chain.class_fqn.strip_prefix("__var__")?;
+                Some(MemberChain {
+                    class_fqn: variables.get(var_name)?.clone(),
+                    ..chain
+                })
+            })
+            .collect();
     }
 }
 
@@ -452,6 +521,37 @@ mod tests {
         assert!(result.class_references.contains(".MainActivity"));
     }
 
+    #[test]
+    fn test_parse_reflective_class_attributes() {
+        let parser = LayoutParser::new();
+        let layout = r#"
+            <?xml version="1.0" encoding="utf-8"?>
+            <androidx.coordinatorlayout.widget.CoordinatorLayout
+                xmlns:android="http://schemas.android.com/apk/res/android"
+                xmlns:app="http://schemas.android.com/apk/res-auto">
+                <androidx.recyclerview.widget.RecyclerView
+                    app:layoutManager=".ui.GridManager" />
+                <view class="com.example.Outer$InnerView" />
+                <com.google.android.material.floatingactionbutton.FloatingActionButton
+                    app:layout_behavior="com.example.FabBehavior" />
+                <androidx.core.widget.NestedScrollView
+                    app:layout_behavior="@string/appbar_scrolling_view_behavior" />
+                <androidx.recyclerview.widget.RecyclerView
+                    app:layoutManager="LinearLayoutManager" />
+            </androidx.coordinatorlayout.widget.CoordinatorLayout>
+        "#;
+
+        let result = parser.parse(Path::new("layout.xml"), layout).unwrap();
+
+        assert!(result.class_references.contains(".ui.GridManager"));
+        assert!(result.class_references.contains("com.example.Outer.InnerView"));
+        assert!(result.class_references.contains("com.example.FabBehavior"));
+        assert!(!result
+            .class_references
+            .iter()
+            .any(|c| c.starts_with('@') || c == "LinearLayoutManager"));
+    }
+
     #[test]
     fn test_parse_data_binding_variable() {
         let parser = LayoutParser::new();
@@ -673,4 +773,38 @@ mod tests {
             result.method_references
         );
     }
+
+    #[test]
+    fn test_parse_member_chains_and_click_handlers() {
+        let parser = LayoutParser::new();
+        let layout = r#"
+            <layout xmlns:android="http://schemas.android.com/apk/res/android"
+                xmlns:tools="http://schemas.android.com/tools">
+                <data>
+                    <variable name="vm" type="com.example.ProfileViewModel" />
+                </data>
+                <LinearLayout tools:context=".ProfileActivity">
+                    <TextView android:text="@{vm.user.name ?? 'a.b'}" />
+                    <EditText android:text="@={vm.query}" />
+                    <TextView android:text="@{other.value}" />
+                    <Button android:onClick="onHelpClicked" />
+                </LinearLayout>
+            </layout>
+        "#;
+
+        let result = parser.parse(Path::new("layout.xml"), layout).unwrap();
+
+        let chain = |members: &[&str], two_way: bool| MemberChain {
+            class_fqn: "com.example.ProfileViewModel".to_string(),
+            members: members.iter().map(|m| m.to_string()).collect(),
+            two_way,
+        };
+        assert_eq!(result.member_chains.len(), 2);
+        assert!(result
+            .member_chains
+            .contains(&chain(&["user", "name"], false)));
+        assert!(result.member_chains.contains(&chain(&["query"], true)));
+        assert!(result.click_handlers.contains("onHelpClicked"));
+        assert_eq!(result.context_class.as_deref(), Some(".ProfileActivity"));
+    }
 }
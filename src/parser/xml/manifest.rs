@@ -20,11 +20,22 @@ impl ManifestParser {
         reader.config_mut().trim_text(true);
 
         let mut buf = Vec::new();
+        // Component whose children are being read, for its intent filters
+        let mut component: Option<String> = None;
 
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                Ok(Event::End(ref e)) => {
+                    if matches!(
+                        e.name().as_ref(),
+                        b"activity" | b"activity-alias" | b"service" | b"receiver" | b"provider"
+                    ) {
+                        component = None;
+                    }
+                }
+                Ok(ref event @ Event::Start(ref e)) | Ok(ref event @ Event::Empty(ref e)) => {
                     let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    let has_children = matches!(event, Event::Start(_));
 
                     // Extract package from manifest tag
                     if tag_name == "manifest" {
@@ -46,25 +57,59 @@ impl ManifestParser {
                             if key == "android:name" || key.ends_with(":name") {
                                 let value = String::from_utf8_lossy(&attr.value).to_string();
                                 let class_name = self.resolve_class_name(&value, &result.package);
+                                if has_children && tag_name != "application" {
+                                    component = Some(class_name.clone());
+                                }
+                                result.class_references.insert(class_name);
+                            }
+                        }
+                    }
+
+                    // An alias starts its target activity
+                    if tag_name == "activity-alias" {
+                        for attr in e.attributes().filter_map(|a| a.ok()) {
+                            if attr.key.as_ref().ends_with(b":targetActivity") {
+                                let value = String::from_utf8_lossy(&attr.value).to_string();
+                                let class_name = self.resolve_class_name(&value, &result.package);
+                                if has_children {
+                                    component = Some(class_name.clone());
+                                }
                                 result.class_references.insert(class_name);
                             }
                         }
                     }
 
+                    if tag_name == "intent-filter" {
+                        if let Some(component) = &component {
+                            result.intent_filter_components.insert(component.clone());
+                        }
+                    }
+
                     // Extract meta-data values that might be class names
                     if tag_name == "meta-data" {
+                        let mut name_value = None;
                         let mut value_value = None;
 
                         for attr in e.attributes().filter_map(|a| a.ok()) {
                             let key = String::from_utf8_lossy(attr.key.as_ref());
-                            if key == "android:value" || key.ends_with(":value") {
+                            if key == "android:name" || key.ends_with(":name") {
+                                name_value = Some(String::from_utf8_lossy(&attr.value).to_string());
+                            } else if key == "android:value" || key.ends_with(":value") {
                                 value_value =
                                     Some(String::from_utf8_lossy(&attr.value).to_string());
                             }
                         }
 
-                        // Check if value looks like a class name
-                        if let Some(value) = value_value {
+                        // androidx.startup names each initializer in the
+                        // meta-data of its InitializationProvider
+                        if let (Some(name), Some("androidx.startup")) =
+                            (&name_value, value_value.as_deref())
+                        {
+                            let class_name = self.resolve_class_name(name, &result.package);
+                            result.class_references.insert(class_name.clone());
+                            result.startup_initializers.insert(class_name);
+                        } else if let Some(value) = value_value {
+                            // Check if value looks like a class name
                             if value.contains('.') && !value.contains(' ') {
                                 result.class_references.insert(value);
                             }
@@ -153,6 +198,83 @@ mod tests {
             .contains("com.example.app.MyApplication"));
     }
 
+    #[test]
+    fn test_parse_startup_initializers() {
+        let parser = ManifestParser::new();
+        let manifest = r#"
+            <manifest xmlns:android="http://schemas.android.com/apk/res/android"
+                package="com.example.app">
+                <application>
+                    <provider
+                        android:name="androidx.startup.InitializationProvider"
+                        android:authorities="${applicationId}.androidx-startup">
+                        <meta-data
+                            android:name="com.example.app.init.LoggerInitializer"
+                            android:value="androidx.startup" />
+                        <meta-data
+                            android:name="com.google.android.geo.API_KEY"
+                            android:value="key" />
+                    </provider>
+                </application>
+            </manifest>
+        "#;
+
+        let result = parser
+            .parse(Path::new("AndroidManifest.xml"), manifest)
+            .unwrap();
+
+        let initializers: Vec<_> = result.startup_initializers.iter().collect();
+        assert_eq!(initializers, ["com.example.app.init.LoggerInitializer"]);
+        assert!(result
+            .class_references
+            .contains("com.example.app.init.LoggerInitializer"));
+    }
+
+    #[test]
+    fn test_parse_intent_filter_components() {
+        let parser = ManifestParser::new();
+        let manifest = r#"
+            <manifest xmlns:android="http://schemas.android.com/apk/res/android"
+                package="com.example.app">
+                <application>
+                    <activity android:name=".MainActivity" />
+                    <activity android:name=".DeepLinkActivity" android:exported="true">
+                        <intent-filter android:autoVerify="true">
+                            <action android:name="android.intent.action.VIEW" />
+                            <category android:name="android.intent.category.BROWSABLE" />
+                            <data android:scheme="https" android:host="example.com" />
+                        </intent-filter>
+                    </activity>
+                    <activity android:name=".ShareActivity" />
+                    <activity-alias
+                        android:name=".ShareTarget"
+                        android:targetActivity=".ShareActivity">
+                        <intent-filter>
+                            <action android:name="android.intent.action.SEND" />
+                        </intent-filter>
+                    </activity-alias>
+                </application>
+            </manifest>
+        "#;
+
+        let result = parser
+            .parse(Path::new("AndroidManifest.xml"), manifest)
+            .unwrap();
+
+        let mut components: Vec<_> = result.intent_filter_components.iter().collect();
+        components.sort();
+        assert_eq!(
+            components,
+            [
+                "com.example.app.DeepLinkActivity",
+                "com.example.app.ShareActivity"
+            ]
+        );
+        assert!(result
+            .class_references
+            .contains("com.example.app.ShareActivity"));
+    }
+
     #[test]
     fn test_resolve_class_name() {
         let parser = ManifestParser::new();
@@ -22,6 +22,18 @@ pub struct MethodReference {
     pub method_name: String,
 }
 
+/// Members read or called through a data binding variable
+/// (`@{viewModel.user.name}`, `@{vm::onSave}`)
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct MemberChain {
+    /// Fully qualified type of the binding variable
+    pub class_fqn: String,
+    /// Members in access order (e.g., ["user", "name"])
+    pub members: Vec<String>,
+    /// Two-way binding (`@={...}`), which also writes the last member
+    pub two_way: bool,
+}
+
 /// Result of parsing Android XML files
 #[derive(Debug, Default)]
 pub struct XmlParseResult {
@@ -35,6 +47,24 @@ pub struct XmlParseResult {
     /// Data binding variable declarations: variable_name -> type_fqn
     pub binding_variables: HashMap<String, String>,
 
+    /// Member chains from data binding expressions, resolved to the
+    /// variable's type
+    pub member_chains: HashSet<MemberChain>,
+
+    /// Legacy `android:onClick="handler"` method names
+    pub click_handlers: HashSet<String>,
+
+    /// Class named by the root's `tools:context`, which hosts click handlers
+    pub context_class: Option<String>,
+
+    /// `androidx.startup` initializers registered in the manifest
+    /// (`<meta-data android:name="..." android:value="androidx.startup" />`)
+    pub startup_initializers: HashSet<String>,
+
+    /// Components declaring an `<intent-filter>`, directly or through an
+    /// `<activity-alias>`; implicit intents and deep links start them
+    pub intent_filter_components: HashSet<String>,
+
     /// Package name from manifest
     pub package: Option<String>,
 }
@@ -48,8 +78,16 @@ impl XmlParseResult {
         self.class_references.extend(other.class_references);
         self.method_references.extend(other.method_references);
         self.binding_variables.extend(other.binding_variables);
+        self.member_chains.extend(other.member_chains);
+        self.click_handlers.extend(other.click_handlers);
+        self.startup_initializers.extend(other.startup_initializers);
+        self.intent_filter_components
+            .extend(other.intent_filter_components);
         if self.package.is_none() {
             self.package = other.package;
         }
+        if self.context_class.is_none() {
+            self.context_class = other.context_class;
+        }
     }
 }
@@ -10,6 +10,15 @@ use quick_xml::Reader;
 use std::path::Path;
 use tracing::debug;
 
+/// Elements of a graph that aren't destinations themselves
+const NON_DESTINATION_TAGS: &[&str] = &[
+    "argument",
+    "action",
+    "deepLink",
+    "include",
+    "include-dynamic",
+];
+
 /// Parser for Android Navigation XML files
 pub struct NavigationParser;
 
@@ -31,12 +40,9 @@ impl NavigationParser {
                 Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                     let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
 
-                    // Handle <fragment>, <dialog>, <activity> destinations
-                    if tag_name == "fragment"
-                        || tag_name == "dialog"
-                        || tag_name == "activity"
-                        || tag_name == "navigation"
-                    {
+                    // Handle <fragment>, <dialog>, <activity> destinations, and
+                    // those of custom navigators (<keep_state_fragment>, ...)
+                    if !NON_DESTINATION_TAGS.contains(&tag_name.as_str()) {
                         for attr in e.attributes().filter_map(|a| a.ok()) {
                             let key = String::from_utf8_lossy(attr.key.as_ref());
 
@@ -75,7 +81,9 @@ impl NavigationParser {
 
                             // app:argType="com.example.MyParcelable"
                             if key == "app:argType" || key.ends_with(":argType") {
-                                let value = String::from_utf8_lossy(&attr.value).to_string();
+                                let value = String::from_utf8_lossy(&attr.value);
+                                // "com.example.Item[]", "com.example.Outer$Inner"
+                                let value = value.trim_end_matches("[]").replace('$', ".");
                                 // Skip primitive types
                                 if value.contains('.') && !value.starts_with("android.") {
                                     result.class_references.insert(value);
@@ -186,4 +194,40 @@ mod tests {
             .contains("com.example.DetailFragment"));
         assert!(result.class_references.contains("com.example.model.Item"));
     }
+
+    #[test]
+    fn test_parse_navigation_custom_destinations() {
+        let parser = NavigationParser::new();
+        let nav = r#"
+            <navigation xmlns:android="http://schemas.android.com/apk/res/android"
+                xmlns:app="http://schemas.android.com/apk/res-auto">
+                <keep_state_fragment
+                    android:id="@+id/feed"
+                    android:name=".feed.FeedFragment">
+                    <argument android:name="user.id" app:argType="string" />
+                    <argument
+                        android:name="items"
+                        app:argType="com.example.model.Item[]" />
+                    <argument
+                        android:name="mode"
+                        app:argType="com.example.FeedFragment$Mode" />
+                </keep_state_fragment>
+                <include app:graph="@navigation/settings" />
+            </navigation>
+        "#;
+
+        let result = parser.parse(Path::new("nav_main.xml"), nav).unwrap();
+
+        let mut references: Vec<&str> =
+            result.class_references.iter().map(String::as_str).collect();
+        references.sort_unstable();
+        assert_eq!(
+            references,
+            [
+                ".feed.FeedFragment",
+                "com.example.FeedFragment.Mode",
+                "com.example.model.Item",
+            ]
+        );
+    }
 }
@@ -4,9 +4,13 @@
 // - usage.txt: Lists code that ProGuard determined is unused
 // - seeds.txt: Lists code that matched -keep rules
 // - mapping.txt: Obfuscation mapping (for reverse lookups)
+// - *.pro: Keep rules (consumer rules shipped by library modules)
 
 mod report_generator;
+mod rules;
 mod usage;
 
 pub use report_generator::ReportGenerator;
+#[allow(unused_imports)] // KeepRule is only named by library consumers
+pub use rules::{class_name_matches, KeepRule, ProguardRules};
 pub use usage::{ProguardUsage, UsageEntryKind};
@@ -0,0 +1,379 @@
+// ProGuard/R8 keep rule parser
+//
+// Reads the class specifications of rule files (proguard-rules.pro,
+// consumer-rules.pro, ...):
+// ```
+// -keep,allowobfuscation @com.example.Keep class com.example.model.** {
+//     <fields>;
+// }
+// -keepclassmembers class * extends com.example.Base { *; }
+// ```
+// Rules without a class specification (`-dontobfuscate`, `-dontwarn ...`,
+// `-include ...`) are skipped.
+//
+// Keep rules that stop shrinking can be turned into retain patterns
+// (`--respect-keep-rules`): `-keep class com.foo.** { *; }` retains
+// `com.foo.**` and `com.foo.**.*` (the members of the matched classes).
+
+#![allow(dead_code)] // API methods reserved for future use
+
+use miette::{IntoDiagnostic, Result};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Directives followed by a class specification
+const CLASS_SPEC_DIRECTIVES: &[&str] = &[
+    "-keep",
+    "-keepclassmembers",
+    "-keepclasseswithmembers",
+    "-keepnames",
+    "-keepclassmembernames",
+    "-keepclasseswithmembernames",
+    "-if",
+    "-assumenosideeffects",
+    "-assumevalues",
+    "-whyareyoukeeping",
+    "-checkdiscard",
+];
+
+/// Directives that keep the matched classes from being shrunk
+const KEEP_CLASS_DIRECTIVES: &[&str] = &["-keep", "-keepclasseswithmembers"];
+
+/// Directives that keep the specified members from being shrunk
+const KEEP_MEMBER_DIRECTIVES: &[&str] = &["-keep", "-keepclassmembers", "-keepclasseswithmembers"];
+
+/// Parsed ProGuard/R8 rule file
+#[derive(Debug, Clone, Default)]
+pub struct ProguardRules {
+    pub rules: Vec<KeepRule>,
+}
+
+/// A rule with a class specification
+#[derive(Debug, Clone)]
+pub struct KeepRule {
+    /// Directive without its options (e.g., "-keep")
+    pub directive: String,
+    /// Options after the directive (e.g., ["allowobfuscation"])
+    pub options: Vec<String>,
+    /// Class name pattern, possibly a comma-separated list (e.g., "com.example.**")
+    pub class_pattern: String,
+    /// Annotation the class must carry, without `@`
+    pub annotation: Option<String>,
+    /// Type named by `extends` / `implements`
+    pub super_type: Option<String>,
+    /// Member specifications between the braces, as written
+    pub members: Option<String>,
+    /// The rule on a single line
+    pub text: String,
+    /// 1-based line the rule starts on
+    pub line: usize,
+}
+
+impl ProguardRules {
+    /// Parse a rule file
+    pub fn parse(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).into_diagnostic()?;
+        Ok(Self::parse_content(&content))
+    }
+
+    /// Parse rule file content
+    pub fn parse_content(content: &str) -> Self {
+        let mut rules = Vec::new();
+        let mut current: Option<(usize, String)> = None;
+        let mut depth = 0usize;
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            if depth == 0 && line.starts_with('-') {
+                if let Some((start, text)) = current.take() {
+                    rules.extend(KeepRule::parse(&text, start));
+                }
+                current = Some((index + 1, String::new()));
+            }
+            if let Some((_, text)) = current.as_mut() {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(line);
+            }
+            depth += line.matches('{').count();
+            depth = depth.saturating_sub(line.matches('}').count());
+        }
+        if let Some((start, text)) = current {
+            rules.extend(KeepRule::parse(&text, start));
+        }
+
+        Self { rules }
+    }
+
+    /// Find the rule files of a project (`proguard-rules.pro`,
+    /// `consumer-rules.pro`, ...), skipping build output
+    pub fn find_files(root: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|entry| {
+                let name = entry.file_name().to_string_lossy();
+                entry.depth() == 0 || !(name.starts_with('.') || name == "build")
+            })
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "pro"))
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Retain patterns for every rule that keeps classes or members from
+    /// being shrunk
+    pub fn retain_patterns(&self) -> Vec<String> {
+        let mut patterns = Vec::new();
+        for pattern in self.rules.iter().flat_map(KeepRule::retain_patterns) {
+            if !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+        }
+        patterns
+    }
+}
+
+impl KeepRule {
+    /// Parse one rule, if it has a class specification
+    fn parse(text: &str, line: usize) -> Option<Self> {
+        let (head, members) = match text.find('{') {
+            Some(open) => {
+                let close = text.rfind('}').filter(|&c| c > open).unwrap_or(text.len());
+                (
+                    &text[..open],
+                    Some(text[open + 1..close].trim().to_string()),
+                )
+            }
+            None => (text, None),
+        };
+
+        let mut tokens = head.split_whitespace();
+        let mut options: Vec<String> = tokens.next()?.split(',').map(str::to_string).collect();
+        let directive = options.remove(0);
+        if !CLASS_SPEC_DIRECTIVES.contains(&directive.as_str()) {
+            return None;
+        }
+        // `-keep,allowshrinking , allowobfuscation class ...`
+        let mut tokens = tokens.peekable();
+        while let Some(option) = tokens.peek().and_then(|t| t.strip_prefix(',')) {
+            options.extend(
+                option
+                    .split(',')
+                    .filter(|o| !o.is_empty())
+                    .map(str::to_string),
+            );
+            tokens.next();
+        }
+
+        let mut annotation = None;
+        let mut class_pattern = None;
+        let mut super_type = None;
+        let mut expect_name = false;
+        let mut expect_super = false;
+        for token in tokens {
+            match token {
+                "class" | "interface" | "enum" | "@interface" | "!class" | "!interface"
+                | "!enum" => expect_name = true,
+                "extends" | "implements" => expect_super = true,
+                _ if expect_name => {
+                    class_pattern = Some(token.to_string());
+                    expect_name = false;
+                }
+                _ if token.starts_with('@') && class_pattern.is_none() => {
+                    annotation = Some(token[1..].to_string());
+                }
+                // Annotations of the supertype are not tracked
+                _ if expect_super && !token.starts_with('@') => {
+                    super_type = Some(token.to_string());
+                    expect_super = false;
+                }
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            directive,
+            options,
+            class_pattern: class_pattern?,
+            annotation,
+            super_type,
+            members,
+            text: text.to_string(),
+            line,
+        })
+    }
+
+    /// Class names and patterns the rule refers to: its class pattern,
+    /// annotation and supertype
+    pub fn referenced_types(&self) -> impl Iterator<Item = &str> {
+        self.class_pattern
+            .split(',')
+            .chain(self.annotation.as_deref())
+            .chain(self.super_type.as_deref())
+            .map(|name| name.trim().trim_start_matches('!'))
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Retain patterns equivalent to the rule: its class patterns when it
+    /// keeps classes, `<pattern>.*` when it keeps members
+    ///
+    /// Only unconditional name patterns can be expressed, so rules that
+    /// allow shrinking, negate names, match every class or depend on an
+    /// annotation or supertype yield nothing. Any member specification
+    /// retains all members of the matched classes.
+    pub fn retain_patterns(&self) -> Vec<String> {
+        let allows_shrinking = self.options.iter().any(|o| o == "allowshrinking");
+        if allows_shrinking || self.annotation.is_some() || self.super_type.is_some() {
+            return Vec::new();
+        }
+        let classes: Vec<&str> = self.class_pattern.split(',').map(str::trim).collect();
+        if classes
+            .iter()
+            .any(|c| c.is_empty() || c.starts_with('!') || c.trim_start_matches('*').is_empty())
+        {
+            return Vec::new();
+        }
+
+        let directive = self.directive.as_str();
+        let keeps_members = KEEP_MEMBER_DIRECTIVES.contains(&directive)
+            && self.members.as_deref().is_some_and(|m| !m.is_empty());
+        let mut patterns = Vec::new();
+        for class in classes {
+            if KEEP_CLASS_DIRECTIVES.contains(&directive) {
+                patterns.push(class.to_string());
+            }
+            if keeps_members {
+                patterns.push(format!("{}.*", class));
+            }
+        }
+        patterns
+    }
+
+    /// Whether the rule's class pattern matches a fully qualified class name
+    pub fn matches_class(&self, class_name: &str) -> bool {
+        let mut matched = false;
+        for pattern in self.class_pattern.split(',').map(str::trim) {
+            match pattern.strip_prefix('!') {
+                Some(negated) if class_name_matches(negated, class_name) => return false,
+                Some(_) => {}
+                None => matched |= class_name_matches(pattern, class_name),
+            }
+        }
+        matched
+    }
+}
+
+/// Match a class name against a ProGuard name pattern: `?` is one
+/// character and `*` any part of a name, `**` also spans packages. A lone
+/// `*` matches every class, as in ProGuard.
+pub fn class_name_matches(pattern: &str, class_name: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    if !pattern.contains(['*', '?', '<']) {
+        return pattern == class_name;
+    }
+
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^.]*"),
+            '?' => regex.push_str("[^.]"),
+            // Back references (`<1>`) to wildcards of an `-if` rule
+            '<' => {
+                for c in chars.by_ref() {
+                    if c == '>' {
+                        break;
+                    }
+                }
+                regex.push_str("[^.]*");
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).is_ok_and(|re| re.is_match(class_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keep_rules() {
+        let content = r#"
+# Models are read reflectively
+-keep,allowobfuscation @com.example.Keep class com.example.model.** {
+    <fields>;
+}
+-keepclassmembers class * extends com.example.BaseViewModel { *; }
+-dontwarn okhttp3.**
+-keepnames class com.example.Api$Service,!com.example.Internal
+"#;
+        let rules = ProguardRules::parse_content(content).rules;
+
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].directive, "-keep");
+        assert_eq!(rules[0].options, ["allowobfuscation"]);
+        assert_eq!(rules[0].annotation.as_deref(), Some("com.example.Keep"));
+        assert_eq!(rules[0].members.as_deref(), Some("<fields>;"));
+        assert_eq!(rules[0].line, 3);
+        assert_eq!(
+            rules[1].super_type.as_deref(),
+            Some("com.example.BaseViewModel")
+        );
+        assert_eq!(rules[2].line, 8);
+
+        assert!(rules[0].matches_class("com.example.model.user.User"));
+        assert!(!rules[0].matches_class("com.example.Other"));
+        assert!(rules[1].matches_class("com.example.LoginViewModel"));
+        assert!(rules[1].matches_class("com.example.ui.LoginViewModel"));
+        assert!(class_name_matches("com.example.*", "com.example.Login"));
+        assert!(!class_name_matches("com.example.*", "com.example.ui.Login"));
+        assert!(rules[2].matches_class("com.example.Api$Service"));
+        assert!(!rules[2].matches_class("com.example.Internal"));
+
+        let types: Vec<&str> = rules[2].referenced_types().collect();
+        assert_eq!(types, ["com.example.Api$Service", "com.example.Internal"]);
+    }
+
+    #[test]
+    fn test_retain_patterns() {
+        let content = r#"
+-keep class com.example.model.** { *; }
+-keep class com.example.Api
+-keepclassmembers class com.example.Bridge { public <methods>; }
+-keepnames class com.example.Named
+-keep,allowshrinking class com.example.Shrinkable
+-keep @com.example.Keep class *
+-keepclassmembers class * { @com.google.gson.annotations.SerializedName <fields>; }
+-keep class com.example.*,!com.example.Internal
+-keep class com.example.model.** { *; }
+"#;
+        let patterns = ProguardRules::parse_content(content).retain_patterns();
+
+        assert_eq!(
+            patterns,
+            [
+                "com.example.model.**",
+                "com.example.model.**.*",
+                "com.example.Api",
+                "com.example.Bridge.*",
+            ]
+        );
+    }
+}
@@ -0,0 +1,452 @@
+//! Python bindings
+//!
+//! Exposes the analysis pipeline, its findings and the reference graph as
+//! Python objects, so results can be joined with other data in notebooks:
+//!
+//! ```python
+//! import pandas as pd
+//! import searchdeadcode
+//!
+//! results = searchdeadcode.analyze("path/to/app", min_confidence="medium")
+//! df = pd.DataFrame(results.to_records())
+//! ```
+//!
+//! Build the extension module with `maturin develop --features python`.
+//! See `docs/python.md`.
+
+use crate::analysis::{Confidence, DeadCode};
+use crate::config::Config;
+use crate::graph::Declaration;
+use crate::session::{AnalysisMode, AnalysisResults, AnalysisSession};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Run a full analysis of the project at `path`
+///
+/// `config` is the configuration as a JSON string (same keys as
+/// `.deadcode.yml`); without it the project's config file is used. The GIL is
+/// released while the analysis runs.
+#[pyfunction]
+#[pyo3(signature = (
+    path,
+    config = None,
+    mode = "deep",
+    min_confidence = "low",
+    coverage = Vec::new(),
+    proguard_usage = None,
+    runtime_only = false,
+))]
+#[allow(clippy::too_many_arguments)]
+fn analyze(
+    py: Python<'_>,
+    path: PathBuf,
+    config: Option<&str>,
+    mode: &str,
+    min_confidence: &str,
+    coverage: Vec<PathBuf>,
+    proguard_usage: Option<PathBuf>,
+    runtime_only: bool,
+) -> PyResult<PyResults> {
+    let mode: AnalysisMode = mode.parse().map_err(PyValueError::new_err)?;
+    let min_confidence: Confidence = min_confidence.parse().map_err(PyValueError::new_err)?;
+    let config = config
+        .map(serde_json::from_str::<Config>)
+        .transpose()
+        .map_err(|e| PyValueError::new_err(format!("invalid config: {e}")))?;
+
+    let results = py.allow_threads(|| {
+        let mut builder = AnalysisSession::builder()
+            .path(path)
+            .mode(mode)
+            .min_confidence(min_confidence)
+            .runtime_only(runtime_only)
+            .with_coverage(coverage);
+        if let Some(config) = config {
+            builder = builder.config(config);
+        }
+        if let Some(usage) = proguard_usage {
+            builder = builder.with_proguard_usage(usage);
+        }
+        builder.run()
+    });
+
+    let results = results.map_err(|e| {
+        let chain: Vec<String> = e.chain().map(ToString::to_string).collect();
+        PyRuntimeError::new_err(chain.join(": "))
+    })?;
+    Ok(PyResults(Arc::new(results)))
+}
+
+/// Results of [`analyze`]
+#[pyclass(name = "Results", module = "searchdeadcode", frozen)]
+struct PyResults(Arc<AnalysisResults>);
+
+#[pymethods]
+impl PyResults {
+    /// Number of source files discovered
+    #[getter]
+    fn files_analyzed(&self) -> usize {
+        self.0.files_analyzed
+    }
+
+    /// Findings as `Finding` objects
+    #[getter]
+    fn findings(&self) -> Vec<PyFinding> {
+        self.0.dead_code.iter().map(PyFinding::from).collect()
+    }
+
+    /// The reference graph the findings were computed from
+    #[getter]
+    fn graph(&self) -> PyGraph {
+        PyGraph(Arc::clone(&self.0))
+    }
+
+    /// One flat dict per finding, ready for `pandas.DataFrame`
+    fn to_records<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.0
+            .dead_code
+            .iter()
+            .map(|finding| {
+                let decl = &finding.declaration;
+                let record = PyDict::new(py);
+                record.set_item("code", finding.issue.code())?;
+                record.set_item("rule", finding.issue.description())?;
+                record.set_item("category", finding.issue.category())?;
+                record.set_item("severity", finding.severity.as_str())?;
+                record.set_item("confidence", finding.confidence.as_str())?;
+                record.set_item("runtime_confirmed", finding.runtime_confirmed)?;
+                record.set_item("message", &finding.message)?;
+                record.set_item("name", decl.name.as_str())?;
+                record.set_item("fqn", decl.fully_qualified_name.as_deref())?;
+                record.set_item("kind", format!("{:?}", decl.kind))?;
+                record.set_item("language", format!("{:?}", decl.language))?;
+                record.set_item("file", decl.location.file.as_path())?;
+                record.set_item("line", decl.location.line)?;
+                record.set_item("column", decl.location.column)?;
+                Ok(record)
+            })
+            .collect()
+    }
+
+    /// Findings serialized as a JSON array
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.0.dead_code).map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.dead_code.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<Results findings={} files_analyzed={}>",
+            self.0.dead_code.len(),
+            self.0.files_analyzed
+        )
+    }
+}
+
+/// A single dead code finding
+#[pyclass(name = "Finding", module = "searchdeadcode", frozen, get_all)]
+struct PyFinding {
+    /// Rule code, e.g. `DC001`
+    code: String,
+    /// Rule description
+    rule: String,
+    category: String,
+    severity: String,
+    confidence: String,
+    message: String,
+    runtime_confirmed: bool,
+    declaration: PyDeclaration,
+}
+
+impl From<&DeadCode> for PyFinding {
+    fn from(finding: &DeadCode) -> Self {
+        Self {
+            code: finding.issue.code().to_string(),
+            rule: finding.issue.description().to_string(),
+            category: finding.issue.category().to_string(),
+            severity: finding.severity.as_str().to_string(),
+            confidence: finding.confidence.as_str().to_string(),
+            message: finding.message.clone(),
+            runtime_confirmed: finding.runtime_confirmed,
+            declaration: PyDeclaration(finding.declaration.clone()),
+        }
+    }
+}
+
+#[pymethods]
+impl PyFinding {
+    fn __repr__(&self) -> String {
+        format!(
+            "<Finding {} {} at {}:{}>",
+            self.code,
+            self.declaration.0.name,
+            self.declaration.0.location.file.display(),
+            self.declaration.0.location.line
+        )
+    }
+}
+
+/// A declaration in the reference graph
+#[pyclass(name = "Declaration", module = "searchdeadcode", frozen)]
+#[derive(Clone)]
+struct PyDeclaration(Declaration);
+
+#[pymethods]
+impl PyDeclaration {
+    #[getter]
+    fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    #[getter]
+    fn fqn(&self) -> Option<&str> {
+        self.0.fully_qualified_name.as_deref()
+    }
+
+    #[getter]
+    fn kind(&self) -> String {
+        format!("{:?}", self.0.kind)
+    }
+
+    #[getter]
+    fn visibility(&self) -> String {
+        format!("{:?}", self.0.visibility)
+    }
+
+    #[getter]
+    fn language(&self) -> String {
+        format!("{:?}", self.0.language)
+    }
+
+    #[getter]
+    fn file(&self) -> PathBuf {
+        self.0.location.file.to_path_buf()
+    }
+
+    #[getter]
+    fn line(&self) -> usize {
+        self.0.location.line
+    }
+
+    #[getter]
+    fn column(&self) -> usize {
+        self.0.location.column
+    }
+
+    #[getter]
+    fn annotations(&self) -> Vec<String> {
+        self.0.annotations.clone()
+    }
+
+    #[getter]
+    fn super_types(&self) -> Vec<String> {
+        self.0.super_types.clone()
+    }
+
+    #[getter]
+    fn modifiers(&self) -> Vec<String> {
+        self.0.modifiers.clone()
+    }
+
+    #[getter]
+    fn type_name(&self) -> Option<&str> {
+        self.0.type_name.as_deref()
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.0.id == other.0.id
+    }
+
+    fn __hash__(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.0.id.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<Declaration {} {} at {}>",
+            self.kind(),
+            self.0
+                .fully_qualified_name
+                .as_deref()
+                .unwrap_or(&self.0.name),
+            self.0.location
+        )
+    }
+}
+
+/// Read-only view of the reference graph
+#[pyclass(name = "Graph", module = "searchdeadcode", frozen)]
+struct PyGraph(Arc<AnalysisResults>);
+
+impl PyGraph {
+    fn references(&self, decl: &PyDeclaration, incoming: bool) -> Vec<(PyDeclaration, String)> {
+        let graph = &self.0.graph;
+        let refs = if incoming {
+            graph.get_references_to(&decl.0.id)
+        } else {
+            graph.get_references_from(&decl.0.id)
+        };
+        refs.into_iter()
+            .map(|(other, reference)| {
+                (
+                    PyDeclaration(other.clone()),
+                    format!("{:?}", reference.kind),
+                )
+            })
+            .collect()
+    }
+}
+
+#[pymethods]
+impl PyGraph {
+    /// All declarations
+    fn declarations(&self) -> Vec<PyDeclaration> {
+        self.0
+            .graph
+            .declarations()
+            .cloned()
+            .map(PyDeclaration)
+            .collect()
+    }
+
+    /// Declarations with the given simple name
+    fn find(&self, name: &str) -> Vec<PyDeclaration> {
+        self.0
+            .graph
+            .find_by_name(name)
+            .into_iter()
+            .cloned()
+            .map(PyDeclaration)
+            .collect()
+    }
+
+    /// Declaration with the given fully qualified name
+    fn find_by_fqn(&self, fqn: &str) -> Option<PyDeclaration> {
+        self.0.graph.find_by_fqn(fqn).cloned().map(PyDeclaration)
+    }
+
+    /// `(declaration, kind)` pairs for everything referencing `decl`
+    fn references_to(&self, decl: &PyDeclaration) -> Vec<(PyDeclaration, String)> {
+        self.references(decl, true)
+    }
+
+    /// `(declaration, kind)` pairs for everything `decl` references
+    fn references_from(&self, decl: &PyDeclaration) -> Vec<(PyDeclaration, String)> {
+        self.references(decl, false)
+    }
+
+    fn is_reachable(&self, decl: &PyDeclaration) -> bool {
+        self.0.reachable.contains(&decl.0.id)
+    }
+
+    fn is_entry_point(&self, decl: &PyDeclaration) -> bool {
+        self.0.entry_points.contains(&decl.0.id)
+    }
+
+    #[getter]
+    fn reference_count(&self) -> usize {
+        self.0.graph.reference_count()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.graph.declaration_count()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<Graph declarations={} references={}>",
+            self.0.graph.declaration_count(),
+            self.0.graph.reference_count()
+        )
+    }
+}
+
+#[pymodule]
+fn searchdeadcode(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add_function(wrap_pyfunction!(analyze, m)?)?;
+    m.add_class::<PyResults>()?;
+    m.add_class::<PyFinding>()?;
+    m.add_class::<PyDeclaration>()?;
+    m.add_class::<PyGraph>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_analyze_from_python() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Main.kt"),
+            "package app\n\nfun main() {}\n\nclass NeverUsed\n",
+        )
+        .unwrap();
+
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "searchdeadcode").unwrap();
+            searchdeadcode(&module).unwrap();
+            let results = module
+                .getattr("analyze")
+                .unwrap()
+                .call1((dir.path(),))
+                .unwrap();
+
+            let results = results.downcast::<PyResults>().unwrap().get();
+            assert_eq!(results.files_analyzed(), 1);
+            let finding = results
+                .findings()
+                .into_iter()
+                .find(|f| f.declaration.0.name == "NeverUsed")
+                .unwrap();
+            assert_eq!(finding.code, "DC001");
+
+            let graph = results.graph();
+            assert!(!graph.is_reachable(&finding.declaration));
+            assert!(graph
+                .find("main")
+                .iter()
+                .any(|main| graph.is_entry_point(main)));
+
+            let records = results.to_records(py).unwrap();
+            assert_eq!(records.len(), results.findings().len());
+        });
+    }
+
+    #[test]
+    fn test_invalid_arguments_raise_value_error() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let run = |mode: &str, confidence: &str| {
+                analyze(
+                    py,
+                    PathBuf::from("."),
+                    None,
+                    mode,
+                    confidence,
+                    Vec::new(),
+                    None,
+                    false,
+                )
+            };
+            let err = run("fast", "medium").err().unwrap();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            let err = run("deep", "certain").err().unwrap();
+            assert!(err.is_instance_of::<PyValueError>(py));
+        });
+    }
+}
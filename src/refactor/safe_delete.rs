@@ -1,16 +1,20 @@
+use crate::analysis::resources::OrphanedTranslation;
 use crate::analysis::DeadCode;
 use crate::refactor::undo::UndoScript;
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm, MultiSelect};
 use miette::{IntoDiagnostic, Result};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 /// Safe delete functionality with user confirmation
 pub struct SafeDeleter {
     interactive: bool,
     dry_run: bool,
     undo_script_path: Option<PathBuf>,
+    /// File states recorded across calls, so one undo script restores every deletion
+    undo_script: RefCell<UndoScript>,
 }
 
 impl SafeDeleter {
@@ -19,6 +23,7 @@ impl SafeDeleter {
             interactive,
             dry_run,
             undo_script_path,
+            undo_script: RefCell::new(UndoScript::new()),
         }
     }
 
@@ -33,7 +38,7 @@ impl SafeDeleter {
         let mut by_file: HashMap<PathBuf, Vec<&DeadCode>> = HashMap::new();
         for item in dead_code {
             by_file
-                .entry(item.declaration.location.file.clone())
+                .entry(item.declaration.location.file.to_path_buf())
                 .or_default()
                 .push(item);
         }
@@ -60,34 +65,28 @@ impl SafeDeleter {
         }
 
         // Get user selection (only in non-dry-run mode)
-        let selected = if self.interactive {
-            self.interactive_select(dead_code)?
-        } else {
-            self.batch_confirm(dead_code)?
-        };
+        let labels: Vec<String> = dead_code.iter().map(dead_code_label).collect();
+        let selected: Vec<&DeadCode> = self
+            .select(&labels)?
+            .into_iter()
+            .map(|i| &dead_code[i])
+            .collect();
 
         if selected.is_empty() {
             println!("{}", "No items selected for deletion.".yellow());
             return Ok(());
         }
 
-        // Generate undo script if requested
-        let mut undo_script = if self.undo_script_path.is_some() {
-            Some(UndoScript::new())
-        } else {
-            None
-        };
-
         // Perform deletions
         println!();
         println!("{}", "Deleting dead code...".cyan().bold());
 
         for item in &selected {
-            if let Some(ref mut script) = undo_script {
-                // Record for undo
-                if let Ok(contents) = std::fs::read_to_string(&item.declaration.location.file) {
-                    script.record_file_state(&item.declaration.location.file, &contents);
-                }
+            // Record for undo
+            if let Ok(contents) = std::fs::read_to_string(&item.declaration.location.file) {
+                self.undo_script
+                    .borrow_mut()
+                    .record_file_state(&item.declaration.location.file, &contents);
             }
 
             // Perform deletion
@@ -111,18 +110,20 @@ impl SafeDeleter {
             }
         }
 
-        // Write undo script
-        if let (Some(script), Some(path)) = (undo_script, &self.undo_script_path) {
-            script.write(path)?;
-            println!();
-            println!("{} Undo script saved to: {}", "→".dimmed(), path.display());
-        }
+        self.write_undo_script()
+    }
 
-        Ok(())
+    /// Let the user pick which of the labelled items to delete
+    fn select(&self, labels: &[String]) -> Result<Vec<usize>> {
+        if self.interactive {
+            self.interactive_select(labels)
+        } else {
+            self.batch_confirm(labels)
+        }
     }
 
     /// Interactive selection mode - confirm each item
-    fn interactive_select<'a>(&self, dead_code: &'a [DeadCode]) -> Result<Vec<&'a DeadCode>> {
+    fn interactive_select(&self, labels: &[String]) -> Result<Vec<usize>> {
         let mut selected = Vec::new();
 
         println!();
@@ -132,14 +133,8 @@ impl SafeDeleter {
         );
         println!();
 
-        for item in dead_code {
-            let prompt = format!(
-                "Delete {} '{}' at {}:{}?",
-                item.declaration.kind.display_name(),
-                item.declaration.name,
-                item.declaration.location.file.display(),
-                item.declaration.location.line
-            );
+        for (i, label) in labels.iter().enumerate() {
+            let prompt = format!("Delete {}?", label);
 
             if Confirm::with_theme(&ColorfulTheme::default())
                 .with_prompt(&prompt)
@@ -147,7 +142,7 @@ impl SafeDeleter {
                 .interact()
                 .into_diagnostic()?
             {
-                selected.push(item);
+                selected.push(i);
             }
         }
 
@@ -155,32 +150,17 @@ impl SafeDeleter {
     }
 
     /// Batch confirmation - select multiple at once
-    fn batch_confirm<'a>(&self, dead_code: &'a [DeadCode]) -> Result<Vec<&'a DeadCode>> {
-        let items: Vec<String> = dead_code
-            .iter()
-            .map(|dc| {
-                format!(
-                    "{} '{}' at {}:{}",
-                    dc.declaration.kind.display_name(),
-                    dc.declaration.name,
-                    dc.declaration.location.file.display(),
-                    dc.declaration.location.line
-                )
-            })
-            .collect();
-
+    fn batch_confirm(&self, labels: &[String]) -> Result<Vec<usize>> {
         println!();
         println!("{}", "Select items to delete:".cyan().bold());
         println!("{}", "(Space to toggle, Enter to confirm)".dimmed());
         println!();
 
-        let selections = MultiSelect::with_theme(&ColorfulTheme::default())
-            .items(&items)
+        let selected = MultiSelect::with_theme(&ColorfulTheme::default())
+            .items(labels)
             .interact()
             .into_diagnostic()?;
 
-        let selected: Vec<&DeadCode> = selections.into_iter().map(|i| &dead_code[i]).collect();
-
         // Confirm final selection
         if !selected.is_empty() {
             println!();
@@ -198,6 +178,90 @@ impl SafeDeleter {
         Ok(selected)
     }
 
+    /// Delete orphaned translations from their locale values files
+    pub fn delete_translations(&self, orphans: &[OrphanedTranslation]) -> Result<()> {
+        if orphans.is_empty() {
+            return Ok(());
+        }
+
+        let labels: Vec<String> = orphans.iter().map(translation_label).collect();
+
+        if self.dry_run {
+            println!();
+            println!("{}", "Dry run - would delete translations:".yellow().bold());
+            for label in &labels {
+                println!("  {}", label);
+            }
+            println!();
+            println!(
+                "{}",
+                format!("Total: {} translations would be deleted", orphans.len()).dimmed()
+            );
+            return Ok(());
+        }
+
+        let selected = self.select(&labels)?;
+        if selected.is_empty() {
+            println!("{}", "No translations selected for deletion.".yellow());
+            return Ok(());
+        }
+
+        let mut by_file: BTreeMap<&Path, Vec<&OrphanedTranslation>> = BTreeMap::new();
+        for i in selected {
+            by_file
+                .entry(orphans[i].translation.file.as_path())
+                .or_default()
+                .push(&orphans[i]);
+        }
+
+        println!();
+        println!("{}", "Deleting orphaned translations...".cyan().bold());
+
+        for (file, items) in by_file {
+            let contents = match std::fs::read_to_string(file) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    println!("  {} Failed to read {}: {}", "✗".red(), file.display(), e);
+                    continue;
+                }
+            };
+            self.undo_script
+                .borrow_mut()
+                .record_file_state(file, &contents);
+
+            let spans: Vec<(usize, usize)> = items.iter().map(|o| o.translation.span).collect();
+            match std::fs::write(file, remove_spans(&contents, &spans)) {
+                Ok(_) => {
+                    for item in items {
+                        println!(
+                            "  {} Deleted {} '{}' ({})",
+                            "✓".green(),
+                            item.translation.resource_type,
+                            item.translation.name,
+                            item.translation.locale
+                        );
+                    }
+                }
+                Err(e) => {
+                    println!("  {} Failed to write {}: {}", "✗".red(), file.display(), e);
+                }
+            }
+        }
+
+        self.write_undo_script()
+    }
+
+    /// Write the undo script, if requested, covering everything deleted so far
+    fn write_undo_script(&self) -> Result<()> {
+        if let Some(path) = &self.undo_script_path {
+            self.undo_script.borrow().write(path)?;
+            println!();
+            println!("{} Undo script saved to: {}", "→".dimmed(), path.display());
+        }
+
+        Ok(())
+    }
+
     /// Delete a single declaration from its file
     fn delete_declaration(&self, dead_code: &DeadCode) -> Result<()> {
         let file_path = &dead_code.declaration.location.file;
@@ -256,3 +320,74 @@ impl SafeDeleter {
         start_line
     }
 }
+
+fn dead_code_label(dc: &DeadCode) -> String {
+    format!(
+        "{} '{}' at {}:{}",
+        dc.declaration.kind.display_name(),
+        dc.declaration.name,
+        dc.declaration.location.file.display(),
+        dc.declaration.location.line
+    )
+}
+
+fn translation_label(orphan: &OrphanedTranslation) -> String {
+    let t = &orphan.translation;
+    format!(
+        "{} '{}' ({}) at {}:{} - {}",
+        t.resource_type,
+        t.name,
+        t.locale,
+        t.file.display(),
+        t.line,
+        orphan.issue.description()
+    )
+}
+
+/// Remove byte ranges from `contents`, taking the whole line when a range is
+/// the only thing on it
+fn remove_spans(contents: &str, spans: &[(usize, usize)]) -> String {
+    let mut spans = spans.to_vec();
+    spans.sort_unstable();
+
+    let mut result = String::with_capacity(contents.len());
+    let mut cursor = 0;
+    for (mut start, mut end) in spans {
+        let line_start = contents[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = contents[end..]
+            .find('\n')
+            .map_or(contents.len(), |i| end + i + 1);
+        if contents[line_start..start].trim().is_empty()
+            && contents[end..line_end].trim().is_empty()
+        {
+            start = line_start;
+            end = line_end;
+        }
+        if start < cursor {
+            continue;
+        }
+        result.push_str(&contents[cursor..start]);
+        cursor = end;
+    }
+    result.push_str(&contents[cursor..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_spans_drops_whole_lines() {
+        let contents = "<resources>\n    <string name=\"a\">A</string>\n    <string name=\"b\">B</string> <!-- keep -->\n</resources>\n";
+        let a = contents.find("<string name=\"a\"").unwrap();
+        let b = contents.find("<string name=\"b\"").unwrap();
+        let b_end = b + "<string name=\"b\">B</string>".len();
+        let spans = [(b, b_end), (a, a + "<string name=\"a\">A</string>".len())];
+
+        assert_eq!(
+            remove_spans(contents, &spans),
+            "<resources>\n     <!-- keep -->\n</resources>\n"
+        );
+    }
+}
@@ -3,8 +3,10 @@
 //! Groups similar issues to reduce noise in output
 
 use crate::analysis::{DeadCode, DeadCodeIssue, Severity};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use crate::graph::DeclarationKind;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 /// Aggregated group of similar issues
 #[derive(Debug, Clone)]
@@ -28,7 +30,7 @@ impl IssueGroup {
     pub fn by_file(&self) -> HashMap<PathBuf, Vec<&DeadCode>> {
         let mut map: HashMap<PathBuf, Vec<&DeadCode>> = HashMap::new();
         for item in &self.items {
-            map.entry(item.declaration.location.file.clone())
+            map.entry(item.declaration.location.file.to_path_buf())
                 .or_default()
                 .push(item);
         }
@@ -66,12 +68,12 @@ impl Aggregator {
 
         // Convert to IssueGroups
         let mut by_rule: Vec<IssueGroup> = rule_map
-            .into_iter()
-            .map(|(_code, items)| {
+            .into_values()
+            .map(|items| {
                 let first = items.first().unwrap();
                 IssueGroup {
-                    issue: first.issue.clone(),
-                    severity: first.severity.clone(),
+                    issue: first.issue,
+                    severity: first.severity,
                     description: Self::group_description(&first.issue),
                     items,
                 }
@@ -79,7 +81,7 @@ impl Aggregator {
             .collect();
 
         // Sort by count descending
-        by_rule.sort_by(|a, b| b.count().cmp(&a.count()));
+        by_rule.sort_by_key(|g| std::cmp::Reverse(g.count()));
 
         // Group by category
         let by_category = self.group_by_category(&by_rule);
@@ -92,130 +94,12 @@ impl Aggregator {
 
     /// Get a short description for a rule
     fn group_description(issue: &DeadCodeIssue) -> String {
-        match issue {
-            // Dead code issues
-            DeadCodeIssue::Unreferenced => "Unreferenced declarations".to_string(),
-            DeadCodeIssue::UnusedImport => "Unused imports".to_string(),
-            DeadCodeIssue::UnusedParameter => "Unused parameters".to_string(),
-            DeadCodeIssue::AssignOnly => "Assign-only variables".to_string(),
-            DeadCodeIssue::DeadBranch => "Dead branches".to_string(),
-            DeadCodeIssue::RedundantOverride => "Redundant overrides".to_string(),
-            DeadCodeIssue::RedundantPublic => "Redundant public modifiers".to_string(),
-            DeadCodeIssue::UnusedEnumCase => "Unused enum cases".to_string(),
-            DeadCodeIssue::UnusedSealedVariant => "Unused sealed variants".to_string(),
-            DeadCodeIssue::WriteOnlyPreference => "Write-only preferences".to_string(),
-            DeadCodeIssue::WriteOnlyDao => "Write-only DAOs".to_string(),
-            DeadCodeIssue::DuplicateImport => "Duplicate imports".to_string(),
-            DeadCodeIssue::RedundantNullInit => "Redundant null init".to_string(),
-            DeadCodeIssue::RedundantThis => "Redundant this".to_string(),
-            DeadCodeIssue::RedundantParentheses => "Redundant parentheses".to_string(),
-            DeadCodeIssue::PreferIsEmpty => "Prefer isEmpty()".to_string(),
-
-            // Architecture patterns
-            DeadCodeIssue::DeepInheritance => "Deep inheritance hierarchies".to_string(),
-            DeadCodeIssue::EventBusPattern => "EventBus @Subscribe usage".to_string(),
-            DeadCodeIssue::GlobalMutableState => "Global mutable state".to_string(),
-            DeadCodeIssue::SingleImplInterface => "Single-implementation interfaces".to_string(),
-            DeadCodeIssue::LegacyDependency => "Legacy dependencies".to_string(),
-            DeadCodeIssue::ExcessiveFeatureToggles => "Excessive feature toggles".to_string(),
-
-            // Kotlin patterns
-            DeadCodeIssue::HeavyViewModel => "Heavy ViewModels".to_string(),
-            DeadCodeIssue::GlobalScopeUsage => "GlobalScope usage".to_string(),
-            DeadCodeIssue::LateinitAbuse => "Excessive lateinit".to_string(),
-            DeadCodeIssue::ScopeFunctionChaining => "Scope function chaining".to_string(),
-            DeadCodeIssue::NullabilityOverload => "Excessive null handling".to_string(),
-            DeadCodeIssue::ReflectionOveruse => "Reflection overuse".to_string(),
-            DeadCodeIssue::LongParameterList => "Long parameter lists".to_string(),
-            DeadCodeIssue::ComplexCondition => "Complex conditions".to_string(),
-            DeadCodeIssue::StringLiteralDuplication => "Duplicated string literals".to_string(),
-
-            // Performance patterns
-            DeadCodeIssue::MemoryLeakRisk => "Memory leak risks".to_string(),
-            DeadCodeIssue::LongMethod => "Long methods".to_string(),
-            DeadCodeIssue::LargeClass => "Large classes".to_string(),
-            DeadCodeIssue::CollectionWithoutSequence => "Collections without asSequence()".to_string(),
-            DeadCodeIssue::ObjectAllocationInLoop => "Object allocation in loops".to_string(),
-
-            // Android patterns
-            DeadCodeIssue::MutableStateExposed => "Exposed mutable state".to_string(),
-            DeadCodeIssue::ViewLogicInViewModel => "View/Context in ViewModel".to_string(),
-            DeadCodeIssue::MissingUseCase => "Missing UseCase layer".to_string(),
-            DeadCodeIssue::NestedCallback => "Nested callbacks".to_string(),
-            DeadCodeIssue::HardcodedDispatcher => "Hardcoded Dispatchers".to_string(),
-            DeadCodeIssue::UnclosedResource => "Unclosed resources".to_string(),
-            DeadCodeIssue::MainThreadDatabase => "Main thread database access".to_string(),
-            DeadCodeIssue::WakeLockAbuse => "WakeLock issues".to_string(),
-            DeadCodeIssue::AsyncTaskUsage => "AsyncTask usage (deprecated)".to_string(),
-            DeadCodeIssue::InitOnDraw => "Allocations in onDraw()".to_string(),
-
-            // Compose patterns
-            DeadCodeIssue::StateWithoutRemember => "State without remember".to_string(),
-            DeadCodeIssue::LaunchedEffectWithoutKey => "LaunchedEffect without key".to_string(),
-            DeadCodeIssue::BusinessLogicInComposable => "Business logic in Composable".to_string(),
-            DeadCodeIssue::NavControllerPassing => "NavController passing".to_string(),
-        }
+        issue.description().to_string()
     }
 
     /// Get category for a rule
     pub fn category_for_issue(issue: &DeadCodeIssue) -> &'static str {
-        match issue {
-            DeadCodeIssue::Unreferenced
-            | DeadCodeIssue::UnusedImport
-            | DeadCodeIssue::UnusedParameter
-            | DeadCodeIssue::AssignOnly
-            | DeadCodeIssue::DeadBranch
-            | DeadCodeIssue::RedundantOverride
-            | DeadCodeIssue::RedundantPublic
-            | DeadCodeIssue::UnusedEnumCase
-            | DeadCodeIssue::UnusedSealedVariant
-            | DeadCodeIssue::WriteOnlyPreference
-            | DeadCodeIssue::WriteOnlyDao
-            | DeadCodeIssue::DuplicateImport
-            | DeadCodeIssue::RedundantNullInit
-            | DeadCodeIssue::RedundantThis
-            | DeadCodeIssue::RedundantParentheses
-            | DeadCodeIssue::PreferIsEmpty => "Dead Code",
-
-            DeadCodeIssue::DeepInheritance
-            | DeadCodeIssue::EventBusPattern
-            | DeadCodeIssue::GlobalMutableState
-            | DeadCodeIssue::SingleImplInterface
-            | DeadCodeIssue::LegacyDependency
-            | DeadCodeIssue::ExcessiveFeatureToggles => "Architecture",
-
-            DeadCodeIssue::HeavyViewModel
-            | DeadCodeIssue::GlobalScopeUsage
-            | DeadCodeIssue::LateinitAbuse
-            | DeadCodeIssue::ScopeFunctionChaining
-            | DeadCodeIssue::NullabilityOverload
-            | DeadCodeIssue::ReflectionOveruse
-            | DeadCodeIssue::LongParameterList
-            | DeadCodeIssue::ComplexCondition
-            | DeadCodeIssue::StringLiteralDuplication => "Kotlin",
-
-            DeadCodeIssue::MemoryLeakRisk
-            | DeadCodeIssue::LongMethod
-            | DeadCodeIssue::LargeClass
-            | DeadCodeIssue::CollectionWithoutSequence
-            | DeadCodeIssue::ObjectAllocationInLoop => "Performance",
-
-            DeadCodeIssue::MutableStateExposed
-            | DeadCodeIssue::ViewLogicInViewModel
-            | DeadCodeIssue::MissingUseCase
-            | DeadCodeIssue::NestedCallback
-            | DeadCodeIssue::HardcodedDispatcher
-            | DeadCodeIssue::UnclosedResource
-            | DeadCodeIssue::MainThreadDatabase
-            | DeadCodeIssue::WakeLockAbuse
-            | DeadCodeIssue::AsyncTaskUsage
-            | DeadCodeIssue::InitOnDraw => "Android",
-
-            DeadCodeIssue::StateWithoutRemember
-            | DeadCodeIssue::LaunchedEffectWithoutKey
-            | DeadCodeIssue::BusinessLogicInComposable
-            | DeadCodeIssue::NavControllerPassing => "Compose",
-        }
+        issue.category()
     }
 
     fn group_by_category(&self, by_rule: &[IssueGroup]) -> HashMap<String, Vec<IssueGroup>> {
@@ -236,6 +120,102 @@ impl Default for Aggregator {
     }
 }
 
+/// Estimated size of the code removing findings would delete
+///
+/// Lines of code leave out blank and comment-only lines; bytes are the
+/// source span of the declarations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Savings {
+    pub loc: usize,
+    pub bytes: usize,
+}
+
+impl Savings {
+    /// Savings of one finding; zero when fixing it keeps its declaration
+    pub fn of(dc: &DeadCode) -> Self {
+        if !dc.issue.is_removable() {
+            return Self::default();
+        }
+        let location = &dc.declaration.location;
+        Self {
+            // A parameter shares its line with the rest of the signature
+            loc: match dc.declaration.kind {
+                DeclarationKind::Parameter => 0,
+                _ => dc.declaration.metrics.loc,
+            },
+            bytes: location.end_byte.saturating_sub(location.start_byte),
+        }
+    }
+
+    /// Findings whose savings add up to the total: the removable ones, except
+    /// those inside the declaration of another, which removing it deletes
+    pub fn counted(dead_code: &[DeadCode]) -> Vec<&DeadCode> {
+        let mut removable: Vec<&DeadCode> = dead_code
+            .iter()
+            .filter(|dc| dc.issue.is_removable())
+            .collect();
+        removable.sort_by(|a, b| {
+            let (a, b) = (&a.declaration.location, &b.declaration.location);
+            (&a.file, a.start_byte, std::cmp::Reverse(a.end_byte)).cmp(&(
+                &b.file,
+                b.start_byte,
+                std::cmp::Reverse(b.end_byte),
+            ))
+        });
+
+        let mut counted = Vec::new();
+        let mut enclosing: Option<(&Path, usize)> = None;
+        for dc in removable {
+            let location = &dc.declaration.location;
+            if enclosing.is_some_and(|(file, end)| {
+                file == location.file.as_path() && location.end_byte <= end
+            }) {
+                continue;
+            }
+            enclosing = Some((location.file.as_path(), location.end_byte));
+            counted.push(dc);
+        }
+        counted
+    }
+
+    /// Total savings of the findings
+    pub fn total(dead_code: &[DeadCode]) -> Self {
+        Self::counted(dead_code).into_iter().map(Self::of).sum()
+    }
+
+    /// Savings of the findings, totaled per key; findings without a key
+    /// are left out
+    pub fn by<K: Ord>(
+        dead_code: &[DeadCode],
+        key: impl Fn(&DeadCode) -> Option<K>,
+    ) -> BTreeMap<K, Self> {
+        let mut totals: BTreeMap<K, Self> = BTreeMap::new();
+        for dc in Self::counted(dead_code) {
+            if let Some(key) = key(dc) {
+                *totals.entry(key).or_default() += Self::of(dc);
+            }
+        }
+        totals
+    }
+}
+
+impl std::ops::AddAssign for Savings {
+    fn add_assign(&mut self, other: Self) {
+        self.loc += other.loc;
+        self.bytes += other.bytes;
+    }
+}
+
+impl std::iter::Sum for Savings {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut total = Self::default();
+        for savings in iter {
+            total += savings;
+        }
+        total
+    }
+}
+
 /// Statistics about the analysis results
 #[derive(Debug, Default)]
 pub struct ResultStats {
@@ -250,14 +230,18 @@ pub struct ResultStats {
     pub by_category: HashMap<String, usize>,
     pub by_rule: HashMap<String, usize>,
     pub files_affected: usize,
+    /// Code removing the findings would delete
+    pub savings: Savings,
 }
 
 impl ResultStats {
     pub fn from_dead_code(dead_code: &[DeadCode]) -> Self {
         use crate::analysis::{Confidence, Severity};
 
-        let mut stats = Self::default();
-        stats.total_issues = dead_code.len();
+        let mut stats = Self {
+            total_issues: dead_code.len(),
+            ..Self::default()
+        };
 
         let mut files = std::collections::HashSet::new();
 
@@ -292,6 +276,7 @@ impl ResultStats {
         }
 
         stats.files_affected = files.len();
+        stats.savings = Savings::total(dead_code);
         stats
     }
 }
@@ -315,4 +300,49 @@ mod tests {
             "Android"
         );
     }
+    #[test]
+    fn test_savings() {
+        use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+
+        let finding = |file: &str, start: usize, end: usize, loc: usize, issue: DeadCodeIssue| {
+            let mut decl = Declaration::new(
+                DeclarationId::new(PathBuf::from(file), start, end),
+                "decl",
+                DeclarationKind::Class,
+                Location::new(PathBuf::from(file), 1, 1, start, end),
+                Language::Kotlin,
+            );
+            decl.metrics.loc = loc;
+            DeadCode::new(decl, issue)
+        };
+        let dead_code = vec![
+            finding("A.kt", 0, 500, 20, DeadCodeIssue::Unreferenced),
+            // Inside the unused class: deleted with it
+            finding("A.kt", 100, 200, 5, DeadCodeIssue::UnusedConstant),
+            finding("A.kt", 600, 700, 4, DeadCodeIssue::Unreferenced),
+            // Fixing it keeps the declaration
+            finding("B.kt", 0, 900, 30, DeadCodeIssue::LongMethod),
+            finding("B.kt", 950, 1000, 2, DeadCodeIssue::UnusedImport),
+        ];
+
+        assert_eq!(Savings::of(&dead_code[3]), Savings::default());
+        assert_eq!(
+            Savings::total(&dead_code),
+            Savings {
+                loc: 26,
+                bytes: 650
+            }
+        );
+        let by_file = Savings::by(&dead_code, |dc| {
+            Some(dc.declaration.location.file.to_path_buf())
+        });
+        assert_eq!(
+            by_file[Path::new("A.kt")],
+            Savings {
+                loc: 24,
+                bytes: 600
+            }
+        );
+        assert_eq!(by_file[Path::new("B.kt")], Savings { loc: 2, bytes: 50 });
+    }
 }
@@ -66,7 +66,7 @@ impl CompactReporter {
         let mut by_file: HashMap<PathBuf, Vec<&DeadCode>> = HashMap::new();
         for item in dead_code {
             by_file
-                .entry(item.declaration.location.file.clone())
+                .entry(item.declaration.location.file.to_path_buf())
                 .or_default()
                 .push(item);
         }
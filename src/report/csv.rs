@@ -0,0 +1,142 @@
+//! CSV reporter - one row per finding, for spreadsheets and issue trackers
+//!
+//! Includes the owner columns filled in by `--owners`, so findings can be
+//! filtered or pivoted by team
+
+use crate::analysis::DeadCode;
+use miette::{IntoDiagnostic, Result};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const HEADER: &[&str] = &[
+    "file",
+    "line",
+    "column",
+    "code",
+    "severity",
+    "confidence",
+    "name",
+    "kind",
+    "module",
+    "owners",
+    "last_author",
+    "message",
+];
+
+/// CSV reporter
+pub struct CsvReporter {
+    output_path: Option<PathBuf>,
+    /// Base path to strip from file paths
+    base_path: Option<PathBuf>,
+}
+
+impl CsvReporter {
+    pub fn new(output_path: Option<PathBuf>) -> Self {
+        Self {
+            output_path,
+            base_path: None,
+        }
+    }
+
+    pub fn with_base_path(mut self, path: PathBuf) -> Self {
+        self.base_path = Some(path);
+        self
+    }
+
+    pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
+        let csv = self.render(dead_code);
+
+        if let Some(path) = &self.output_path {
+            std::fs::write(path, &csv).into_diagnostic()?;
+            println!("CSV report written to: {}", path.display());
+        } else {
+            print!("{}", csv);
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, dead_code: &[DeadCode]) -> String {
+        let mut out = HEADER.join(",");
+        out.push('\n');
+
+        for dc in dead_code {
+            let location = &dc.declaration.location;
+            let row = [
+                self.format_path(&location.file),
+                location.line.to_string(),
+                location.column.to_string(),
+                dc.issue.code().to_string(),
+                dc.severity.as_str().to_string(),
+                dc.confidence.as_str().to_string(),
+                dc.declaration.name.to_string(),
+                dc.declaration.kind.display_name().to_string(),
+                dc.module.clone().unwrap_or_default(),
+                dc.owners.join(" "),
+                dc.last_author.clone().unwrap_or_default(),
+                dc.message.clone(),
+            ];
+            let row: Vec<String> = row.iter().map(|field| escape_field(field)).collect();
+            let _ = writeln!(out, "{}", row.join(","));
+        }
+
+        out
+    }
+
+    /// Format a path relative to base path if set
+    fn format_path(&self, path: &Path) -> String {
+        match &self.base_path {
+            Some(base) => path.strip_prefix(base).unwrap_or(path),
+            None => path,
+        }
+        .display()
+        .to_string()
+    }
+}
+
+/// Quote a field when it holds a separator, quote or line break (RFC 4180)
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+
+    #[test]
+    fn test_csv_report() {
+        let path = PathBuf::from("/project/src/Main.kt");
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), 40, 60),
+            "Unused",
+            DeclarationKind::Class,
+            Location::new(path, 4, 1, 40, 60),
+            Language::Kotlin,
+        );
+        let mut dc = DeadCode::new(decl, DeadCodeIssue::Unreferenced)
+            .with_message("class \"Unused\" is never used, anywhere".to_string());
+        dc.owners = vec!["@org/android".to_string(), "@alice".to_string()];
+        dc.last_author = Some("Alice".to_string());
+
+        let csv = CsvReporter::new(None)
+            .with_base_path(PathBuf::from("/project"))
+            .render(&[dc]);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "file,line,column,code,severity,confidence,name,kind,module,owners,last_author,message"
+        );
+        assert_eq!(
+            lines[1],
+            "src/Main.kt,4,1,DC001,warning,medium,Unused,class,,@org/android @alice,Alice,\
+             \"class \"\"Unused\"\" is never used, anywhere\""
+        );
+    }
+}
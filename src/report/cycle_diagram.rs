@@ -0,0 +1,227 @@
+//! Diagrams of zombie code cycles
+//!
+//! Renders the dead cycles `--detect-cycles` finds as one Mermaid flowchart
+//! or PlantUML diagram: a group per cycle with its declarations, and the
+//! references between them that keep the cycle alive.
+
+use crate::analysis::CycleInfo;
+use crate::graph::{DeclarationId, Graph};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// Diagram language for dead cycles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CycleDiagramFormat {
+    /// Mermaid flowchart, rendered by GitHub, GitLab and `mmdc`
+    Mermaid,
+    /// PlantUML component diagram
+    PlantUml,
+}
+
+/// Renderer of dead cycles as a diagram
+pub struct CycleDiagram {
+    format: CycleDiagramFormat,
+}
+
+/// A cycle's declarations, by node number, and its references
+struct CycleNodes {
+    title: String,
+    nodes: Vec<(usize, String)>,
+    edges: BTreeSet<(usize, usize, &'static str)>,
+}
+
+impl CycleDiagram {
+    pub fn new(format: CycleDiagramFormat) -> Self {
+        Self { format }
+    }
+
+    /// One diagram with every cycle
+    pub fn render(&self, graph: &Graph, cycles: &[CycleInfo]) -> String {
+        let cycles = Self::collect(graph, cycles);
+        match self.format {
+            CycleDiagramFormat::Mermaid => Self::to_mermaid(&cycles),
+            CycleDiagramFormat::PlantUml => Self::to_plantuml(&cycles),
+        }
+    }
+
+    /// Number the declarations of all cycles, in source order within each,
+    /// and keep the references between declarations of the same cycle
+    fn collect(graph: &Graph, cycles: &[CycleInfo]) -> Vec<CycleNodes> {
+        let mut next = 0;
+        cycles
+            .iter()
+            .enumerate()
+            .map(|(i, cycle)| {
+                let mut members: Vec<_> = cycle
+                    .members
+                    .iter()
+                    .filter_map(|id| graph.get_declaration(id))
+                    .collect();
+                members.sort_by(|a, b| {
+                    (&a.location.file, a.location.start_byte)
+                        .cmp(&(&b.location.file, b.location.start_byte))
+                });
+
+                let mut numbers: HashMap<&DeclarationId, usize> = HashMap::new();
+                let nodes = members
+                    .iter()
+                    .map(|decl| {
+                        next += 1;
+                        numbers.insert(&decl.id, next);
+                        // Members are prefixed with their type, to tell apart
+                        // same-named methods of different classes
+                        let parent = decl
+                            .parent
+                            .as_ref()
+                            .and_then(|id| graph.get_declaration(id));
+                        let name = match parent {
+                            Some(parent) => format!("{}.{}", parent.name, decl.name),
+                            None => decl.name.to_string(),
+                        };
+                        (next, format!("{} {}", decl.kind.display_name(), name))
+                    })
+                    .collect();
+                let edges = members
+                    .iter()
+                    .flat_map(|decl| {
+                        let from = numbers[&decl.id];
+                        graph
+                            .get_references_from(&decl.id)
+                            .into_iter()
+                            .filter_map(|(target, reference)| {
+                                let to = *numbers.get(&target.id)?;
+                                Some((from, to, reference.kind.display_name()))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+
+                CycleNodes {
+                    title: format!("Cycle #{} ({} declarations)", i + 1, cycle.size),
+                    nodes,
+                    edges,
+                }
+            })
+            .collect()
+    }
+
+    fn to_mermaid(cycles: &[CycleNodes]) -> String {
+        let mut out = String::from("flowchart LR\n");
+        for (i, cycle) in cycles.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "  subgraph cycle{}[\"{}\"]",
+                i + 1,
+                mermaid_text(&cycle.title)
+            );
+            for (number, label) in &cycle.nodes {
+                let _ = writeln!(out, "    n{}[\"{}\"]", number, mermaid_text(label));
+            }
+            out.push_str("  end\n");
+            for (from, to, kind) in &cycle.edges {
+                let _ = writeln!(out, "  n{} -->|{}| n{}", from, kind, to);
+            }
+        }
+        out
+    }
+
+    fn to_plantuml(cycles: &[CycleNodes]) -> String {
+        let mut out = String::from("@startuml\nleft to right direction\n");
+        for cycle in cycles {
+            let _ = writeln!(out, "package \"{}\" {{", plantuml_text(&cycle.title));
+            for (number, label) in &cycle.nodes {
+                let _ = writeln!(
+                    out,
+                    "  rectangle \"{}\" as n{}",
+                    plantuml_text(label),
+                    number
+                );
+            }
+            out.push_str("}\n");
+            for (from, to, kind) in &cycle.edges {
+                let _ = writeln!(out, "n{} --> n{} : {}", from, to, kind);
+            }
+        }
+        out.push_str("@enduml\n");
+        out
+    }
+}
+
+/// Mermaid labels are quoted; quotes inside are written as entity codes
+fn mermaid_text(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+fn plantuml_text(text: &str) -> String {
+    text.replace('"', "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{CycleDetector, ReachabilityAnalyzer};
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+    use std::collections::HashSet;
+
+    fn zombie_cycles() -> (Graph, Vec<CycleInfo>) {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Zombie.kt");
+        std::fs::write(
+            &path,
+            r#"package com.app
+
+class Alpha {
+    fun run() = Beta().run()
+}
+
+class Beta {
+    fun run() = Alpha().run()
+}
+"#,
+        )
+        .unwrap();
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(path, FileType::Kotlin))
+            .unwrap();
+        let graph = builder.build();
+        let (_, reachable) =
+            ReachabilityAnalyzer::new().find_unreachable_with_reachable(&graph, &HashSet::new());
+        let cycles = CycleDetector::new().find_dead_cycles(&graph, &reachable);
+        (graph, cycles)
+    }
+
+    #[test]
+    fn test_mermaid_cycles() {
+        let (graph, cycles) = zombie_cycles();
+        assert_eq!(cycles.len(), 1);
+        let mermaid = CycleDiagram::new(CycleDiagramFormat::Mermaid).render(&graph, &cycles);
+
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("  subgraph cycle1[\"Cycle #1 ("));
+        assert!(
+            mermaid.contains("    n1[\"method Alpha.run\"]"),
+            "{}",
+            mermaid
+        );
+        assert!(
+            mermaid.contains("    n2[\"method Beta.run\"]"),
+            "{}",
+            mermaid
+        );
+        assert!(mermaid.contains("  n1 -->|call| n2\n  n2 -->|call| n1\n"));
+    }
+
+    #[test]
+    fn test_plantuml_cycles() {
+        let (graph, cycles) = zombie_cycles();
+        let plantuml = CycleDiagram::new(CycleDiagramFormat::PlantUml).render(&graph, &cycles);
+
+        assert!(plantuml.starts_with("@startuml\n"));
+        assert!(plantuml.contains("package \"Cycle #1 ("));
+        assert!(plantuml.contains("  rectangle \"method Alpha.run\" as n1"));
+        assert!(plantuml.contains("n1 --> n2 : call\nn2 --> n1 : call\n"));
+        assert!(plantuml.ends_with("@enduml\n"));
+    }
+}
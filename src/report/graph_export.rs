@@ -0,0 +1,286 @@
+use crate::graph::{ExportOptions, ExportSelection, Graph};
+use miette::{IntoDiagnostic, Result};
+use quick_xml::escape::escape;
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Version of the JSON graph format, bumped on incompatible changes
+const GRAPH_FORMAT_VERSION: &str = "1.0";
+
+/// Format of an exported reference graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphExportFormat {
+    /// Graphviz DOT
+    Dot,
+    /// GraphML, for Gephi, yEd and Cytoscape
+    GraphMl,
+    /// JSON in the format documented in docs/cli-reference.md
+    Json,
+}
+
+/// Exporter of the reference graph for external tools
+pub struct GraphExporter {
+    format: GraphExportFormat,
+    base_path: Option<PathBuf>,
+}
+
+impl GraphExporter {
+    pub fn new(format: GraphExportFormat) -> Self {
+        Self {
+            format,
+            base_path: None,
+        }
+    }
+
+    /// Write file paths relative to this directory
+    pub fn with_base_path(mut self, path: PathBuf) -> Self {
+        self.base_path = Some(path);
+        self
+    }
+
+    /// Render the declarations `options` selects and the references between them
+    pub fn export(&self, graph: &Graph, options: &ExportOptions) -> Result<String> {
+        match self.format {
+            GraphExportFormat::Dot => Ok(graph.to_dot(options)),
+            GraphExportFormat::GraphMl => Ok(self.to_graphml(&graph.export_selection(options))),
+            GraphExportFormat::Json => {
+                let export = JsonGraph::new(&graph.export_selection(options), |path| {
+                    self.display_path(path)
+                });
+                serde_json::to_string_pretty(&export).into_diagnostic()
+            }
+        }
+    }
+
+    /// GraphML document with one node per declaration and one edge per
+    /// reference; membership is exported as `member` edges from a type to
+    /// its members
+    fn to_graphml(&self, selection: &ExportSelection) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        for (id, target, name, kind) in [
+            ("name", "node", "name", "string"),
+            ("kind", "node", "kind", "string"),
+            ("fqn", "node", "fully_qualified_name", "string"),
+            ("file", "node", "file", "string"),
+            ("line", "node", "line", "int"),
+            ("column", "node", "column", "int"),
+            ("dead", "node", "dead", "boolean"),
+            ("reference", "edge", "kind", "string"),
+        ] {
+            let _ = writeln!(
+                xml,
+                "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>",
+                id, target, name, kind
+            );
+        }
+        xml.push_str("  <graph id=\"references\" edgedefault=\"directed\">\n");
+
+        for (i, node) in selection.nodes.iter().enumerate() {
+            let decl = node.declaration;
+            let _ = writeln!(xml, "    <node id=\"n{}\">", i);
+            let mut data = |key: &str, value: &str| {
+                let _ = writeln!(xml, "      <data key=\"{}\">{}</data>", key, escape(value));
+            };
+            data("name", &decl.name);
+            data("kind", decl.kind.display_name());
+            if let Some(fqn) = &decl.fully_qualified_name {
+                data("fqn", fqn);
+            }
+            data("file", &self.display_path(&decl.location.file));
+            data("line", &decl.location.line.to_string());
+            data("column", &decl.location.column.to_string());
+            data("dead", if node.dead { "true" } else { "false" });
+            xml.push_str("    </node>\n");
+        }
+
+        let members = selection
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, node)| node.parent.map(|parent| (parent, i, "member")));
+        let references = selection
+            .edges
+            .iter()
+            .map(|edge| (edge.from, edge.to, edge.kind.display_name()));
+        for (i, (from, to, kind)) in members.chain(references).enumerate() {
+            let _ = writeln!(
+                xml,
+                "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\"><data key=\"reference\">{}</data></edge>",
+                i, from, to, kind
+            );
+        }
+
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+
+    fn display_path(&self, path: &Path) -> String {
+        let path = match &self.base_path {
+            Some(base) => path.strip_prefix(base).unwrap_or(path),
+            None => path,
+        };
+        path.display().to_string()
+    }
+}
+
+#[derive(Serialize)]
+struct JsonGraph {
+    version: &'static str,
+    declarations: Vec<JsonGraphDeclaration>,
+    references: Vec<JsonGraphReference>,
+}
+
+#[derive(Serialize)]
+struct JsonGraphDeclaration {
+    id: usize,
+    name: String,
+    kind: &'static str,
+    fully_qualified_name: Option<String>,
+    file: String,
+    line: usize,
+    column: usize,
+    parent: Option<usize>,
+    dead: bool,
+}
+
+#[derive(Serialize)]
+struct JsonGraphReference {
+    from: usize,
+    to: usize,
+    kind: &'static str,
+}
+
+impl JsonGraph {
+    fn new(selection: &ExportSelection, display_path: impl Fn(&Path) -> String) -> Self {
+        let declarations = selection
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(id, node)| {
+                let decl = node.declaration;
+                JsonGraphDeclaration {
+                    id,
+                    name: decl.name.to_string(),
+                    kind: decl.kind.display_name(),
+                    fully_qualified_name: decl.fully_qualified_name.as_deref().map(str::to_string),
+                    file: display_path(&decl.location.file),
+                    line: decl.location.line,
+                    column: decl.location.column,
+                    parent: node.parent,
+                    dead: node.dead,
+                }
+            })
+            .collect();
+        let references = selection
+            .edges
+            .iter()
+            .map(|edge| JsonGraphReference {
+                from: edge.from,
+                to: edge.to,
+                kind: edge.kind.display_name(),
+            })
+            .collect();
+
+        Self {
+            version: GRAPH_FORMAT_VERSION,
+            declarations,
+            references,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    fn sample_graph(root: &Path) -> Graph {
+        let path = root.join("Feed.kt");
+        std::fs::write(
+            &path,
+            r#"package com.app
+
+class FeedScreen {
+    fun show() = FeedRepository()
+}
+
+class FeedRepository
+"#,
+        )
+        .unwrap();
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(path, FileType::Kotlin))
+            .unwrap();
+        builder.build()
+    }
+
+    #[test]
+    fn test_json_graph_export() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let graph = sample_graph(temp.path());
+        let dead = graph.find_by_name("FeedRepository")[0].id.clone();
+        let options = ExportOptions::new().with_dead([dead]);
+
+        let json = GraphExporter::new(GraphExportFormat::Json)
+            .with_base_path(temp.path().to_path_buf())
+            .export(&graph, &options)
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["version"], GRAPH_FORMAT_VERSION);
+        let declarations = value["declarations"].as_array().unwrap();
+        let find = |name: &str| {
+            declarations
+                .iter()
+                .find(|d| d["name"] == name)
+                .unwrap_or_else(|| panic!("{} should be exported", name))
+        };
+        let screen = find("FeedScreen");
+        assert_eq!(screen["kind"], "class");
+        assert_eq!(screen["fully_qualified_name"], "com.app.FeedScreen");
+        assert_eq!(screen["file"], "Feed.kt");
+        assert_eq!(screen["line"], 3);
+        assert_eq!(screen["dead"], false);
+        assert_eq!(find("show")["parent"], screen["id"]);
+        assert_eq!(find("FeedRepository")["dead"], true);
+
+        let references = value["references"].as_array().unwrap();
+        assert!(references.iter().any(|r| {
+            r["from"] == find("show")["id"] && r["to"] == find("FeedRepository")["id"]
+        }));
+    }
+
+    #[test]
+    fn test_graphml_export() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let graph = sample_graph(temp.path());
+
+        let xml = GraphExporter::new(GraphExportFormat::GraphMl)
+            .export(&graph, &ExportOptions::new())
+            .unwrap();
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml"));
+        assert!(xml.ends_with("</graphml>\n"));
+        assert!(xml.contains("<data key=\"name\">FeedScreen</data>"));
+        assert!(xml.contains("<data key=\"kind\">method</data>"));
+        assert!(xml.contains("<data key=\"dead\">false</data>"));
+        assert!(xml.contains("<data key=\"reference\">member</data>"));
+        assert_eq!(
+            xml.matches("<node ").count(),
+            graph.export_selection(&ExportOptions::new()).nodes.len()
+        );
+        // Everything is well-formed XML
+        let mut reader = quick_xml::Reader::from_str(&xml);
+        loop {
+            match reader.read_event() {
+                Ok(quick_xml::events::Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => panic!("Invalid GraphML: {}", e),
+            }
+        }
+    }
+}
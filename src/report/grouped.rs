@@ -19,6 +19,10 @@ pub enum GroupBy {
     Severity,
     /// Group by file (default behavior)
     File,
+    /// Group by Gradle module
+    Module,
+    /// Group by owners in CODEOWNERS (with `--owners`)
+    Owner,
 }
 
 impl std::str::FromStr for GroupBy {
@@ -30,7 +34,12 @@ impl std::str::FromStr for GroupBy {
             "category" | "cat" => Ok(GroupBy::Category),
             "severity" | "sev" => Ok(GroupBy::Severity),
             "file" => Ok(GroupBy::File),
-            _ => Err(format!("Unknown grouping: {}. Use: rule, category, severity, file", s)),
+            "module" => Ok(GroupBy::Module),
+            "owner" | "owners" => Ok(GroupBy::Owner),
+            _ => Err(format!(
+                "Unknown grouping: {}. Use: rule, category, severity, file, module, owner",
+                s
+            )),
         }
     }
 }
@@ -106,6 +115,16 @@ impl GroupedReporter {
             GroupBy::Category => self.report_by_category(&results.by_category, &results.by_rule),
             GroupBy::Severity => self.report_by_severity(&results.by_rule),
             GroupBy::File => self.report_by_file_grouped(&results.by_rule),
+            GroupBy::Module => self.report_by_label("Module", &results.by_rule, |item| {
+                item.module.as_deref().unwrap_or("(no module)").to_string()
+            }),
+            GroupBy::Owner => self.report_by_label("Owner", &results.by_rule, |item| {
+                if item.owners.is_empty() {
+                    "(no owner)".to_string()
+                } else {
+                    item.owners.join(" ")
+                }
+            }),
         }
         // Summary is printed by Reporter (full summary at the end)
     }
@@ -237,7 +256,7 @@ impl GroupedReporter {
         for group in groups {
             for item in &group.items {
                 by_file
-                    .entry(item.declaration.location.file.clone())
+                    .entry(item.declaration.location.file.to_path_buf())
                     .or_default()
                     .push(item);
             }
@@ -294,6 +313,54 @@ impl GroupedReporter {
         }
     }
 
+    /// Rule counts per label (module, owners), for issues without one too
+    fn report_by_label(
+        &self,
+        title: &str,
+        groups: &[IssueGroup],
+        label: impl Fn(&DeadCode) -> String,
+    ) {
+        let mut by_label: std::collections::BTreeMap<String, Vec<(&IssueGroup, usize)>> =
+            std::collections::BTreeMap::new();
+
+        for group in groups {
+            let mut counts: std::collections::BTreeMap<String, usize> =
+                std::collections::BTreeMap::new();
+            for item in &group.items {
+                *counts.entry(label(item)).or_default() += 1;
+            }
+            for (label, count) in counts {
+                by_label.entry(label).or_default().push((group, count));
+            }
+        }
+
+        println!();
+        println!("{}", format!("Issues Grouped by {}", title).cyan().bold());
+        println!("{}", BoxChars::heavy_line(50).dimmed());
+        println!();
+
+        for (label, rules) in by_label {
+            let total: usize = rules.iter().map(|(_, count)| count).sum();
+
+            println!(
+                "{} ({} issues)",
+                StructureColors::category(&label),
+                StructureColors::count(&total.to_string())
+            );
+            println!("{}", BoxChars::light_line(40).dimmed());
+
+            for (group, count) in rules {
+                println!(
+                    "  {}  {:>4}  {}",
+                    StructureColors::rule_code(group.issue.code()),
+                    count.to_string().dimmed(),
+                    &group.description
+                );
+            }
+            println!();
+        }
+    }
+
     fn print_rule_group(&self, group: &IssueGroup) {
         let rule = group.issue.code();
         let count = group.count();
@@ -1,20 +1,61 @@
-use crate::analysis::{Confidence, DeadCode, Severity};
+//! JSON reporter
+//!
+//! The report's structure is the serde structs below, described by the JSON
+//! Schema in `report.schema.json` (`--schema`). Within a major
+//! [`JSON_SCHEMA_VERSION`] fields are only added, with a minor version bump,
+//! never removed, renamed or retyped.
+
+use crate::analysis::{Confidence, DeadCode, Fix, Severity};
+use crate::report::aggregator::Savings;
+use crate::report::snippet::{Snippet, SnippetReader};
 use miette::{IntoDiagnostic, Result};
 use serde::Serialize;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Version of the report structure, MAJOR.MINOR
+pub const JSON_SCHEMA_VERSION: &str = "1.5";
+
+/// JSON Schema of the report, printed by `--schema`
+pub const JSON_SCHEMA: &str = include_str!("report.schema.json");
 
 /// JSON reporter for programmatic output
 pub struct JsonReporter {
     output_path: Option<PathBuf>,
+    /// Base path to strip from file paths
+    base_path: Option<PathBuf>,
+    /// Source lines included around each finding
+    snippets: Option<SnippetReader>,
 }
 
 impl JsonReporter {
     pub fn new(output_path: Option<PathBuf>) -> Self {
-        Self { output_path }
+        Self {
+            output_path,
+            base_path: None,
+            snippets: None,
+        }
+    }
+
+    /// Write file paths relative to this directory, so reports of
+    /// different checkouts compare
+    pub fn with_base_path(mut self, path: PathBuf) -> Self {
+        self.base_path = Some(path);
+        self
+    }
+
+    /// Include each finding's line with `context` lines before and after it
+    pub fn with_context(mut self, context: usize) -> Self {
+        self.snippets = Some(SnippetReader::new(context));
+        self
     }
 
     pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
-        let report = JsonReport::from_dead_code(dead_code);
+        let report = JsonReport::from_dead_code(
+            dead_code,
+            self.base_path.as_deref(),
+            self.snippets.as_ref(),
+        );
         let json = serde_json::to_string_pretty(&report).into_diagnostic()?;
 
         if let Some(path) = &self.output_path {
@@ -30,6 +71,8 @@ impl JsonReporter {
 
 #[derive(Serialize)]
 struct JsonReport {
+    schema_version: &'static str,
+    /// Same as `schema_version`, for tools written before it
     version: &'static str,
     total_issues: usize,
     issues: Vec<JsonIssue>,
@@ -47,6 +90,20 @@ struct JsonIssue {
     file: String,
     line: usize,
     column: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    module: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_savings: Option<Savings>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    owners: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snippet: Option<Snippet>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<Fix>,
     declaration: JsonDeclaration,
 }
 
@@ -64,6 +121,15 @@ struct JsonSummary {
     infos: usize,
     by_confidence: JsonConfidenceSummary,
     runtime_confirmed_count: usize,
+    estimated_savings: JsonSavings,
+}
+
+#[derive(Serialize)]
+struct JsonSavings {
+    loc: usize,
+    bytes: usize,
+    by_file: BTreeMap<String, Savings>,
+    by_module: BTreeMap<String, Savings>,
 }
 
 #[derive(Serialize)]
@@ -75,7 +141,18 @@ struct JsonConfidenceSummary {
 }
 
 impl JsonReport {
-    fn from_dead_code(dead_code: &[DeadCode]) -> Self {
+    fn from_dead_code(
+        dead_code: &[DeadCode],
+        base_path: Option<&Path>,
+        snippets: Option<&SnippetReader>,
+    ) -> Self {
+        let relative = |file: &Path| {
+            base_path
+                .and_then(|base| file.strip_prefix(base).ok())
+                .unwrap_or(file)
+                .to_string_lossy()
+                .to_string()
+        };
         let mut errors = 0;
         let mut warnings = 0;
         let mut infos = 0;
@@ -110,20 +187,33 @@ impl JsonReport {
                     confidence_score: dc.confidence.score(),
                     runtime_confirmed: dc.runtime_confirmed,
                     message: dc.message.clone(),
-                    file: dc.declaration.location.file.to_string_lossy().to_string(),
+                    file: relative(&dc.declaration.location.file),
                     line: dc.declaration.location.line,
                     column: dc.declaration.location.column,
+                    module: dc.module.clone(),
+                    scope: dc.scope.map(|scope| scope.as_str()),
+                    estimated_savings: dc.issue.is_removable().then(|| Savings::of(dc)),
+                    owners: dc.owners.clone(),
+                    last_author: dc.last_author.clone(),
+                    snippet: snippets.and_then(|s| s.around(&dc.declaration.location)),
+                    fix: dc.fix.clone(),
                     declaration: JsonDeclaration {
-                        name: dc.declaration.name.clone(),
+                        name: dc.declaration.name.to_string(),
                         kind: dc.declaration.kind.display_name(),
-                        fully_qualified_name: dc.declaration.fully_qualified_name.clone(),
+                        fully_qualified_name: dc
+                            .declaration
+                            .fully_qualified_name
+                            .as_deref()
+                            .map(str::to_string),
                     },
                 }
             })
             .collect();
 
+        let savings = Savings::total(dead_code);
         Self {
-            version: "1.1",
+            schema_version: JSON_SCHEMA_VERSION,
+            version: JSON_SCHEMA_VERSION,
             total_issues: dead_code.len(),
             issues,
             summary: JsonSummary {
@@ -137,7 +227,103 @@ impl JsonReport {
                     low,
                 },
                 runtime_confirmed_count,
+                estimated_savings: JsonSavings {
+                    loc: savings.loc,
+                    bytes: savings.bytes,
+                    by_file: Savings::by(dead_code, |dc| {
+                        Some(relative(&dc.declaration.location.file))
+                    }),
+                    by_module: Savings::by(dead_code, |dc| dc.module.clone()),
+                },
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{DeadCodeIssue, ModuleScope, TextEdit};
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+    use serde_json::Value;
+
+    /// Check that `value` has the schema's required fields and no field the
+    /// schema does not describe
+    fn check(value: &Value, schema: &Value, root: &Value, path: &str) {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/$defs/");
+            return check(value, &root["$defs"][name], root, path);
+        }
+        match value {
+            Value::Object(object) => {
+                for required in schema["required"].as_array().into_iter().flatten() {
+                    let required = required.as_str().unwrap();
+                    assert!(object.contains_key(required), "{path} lacks {required}");
+                }
+                for (key, field) in object {
+                    let field_schema = schema["properties"]
+                        .get(key)
+                        .or_else(|| schema.get("additionalProperties"))
+                        .unwrap_or_else(|| panic!("{path}.{key} is not in the schema"));
+                    check(field, field_schema, root, &format!("{path}.{key}"));
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    check(item, &schema["items"], root, &format!("{path}[]"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_report_matches_schema() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("app/src/Main.kt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "package app\n\n\nclass Unused\n").unwrap();
+        let mut decl = Declaration::new(
+            DeclarationId::new(path.clone(), 40, 90),
+            "Unused",
+            DeclarationKind::Class,
+            Location::new(path, 4, 1, 40, 90),
+            Language::Kotlin,
+        );
+        decl.module = Some(":app".into());
+        let mut dc = DeadCode::new(decl, DeadCodeIssue::Unreferenced);
+        dc.scope = Some(ModuleScope::OwnModule);
+        dc.owners = vec!["@org/android".to_string()];
+        dc.last_author = Some("Alice".to_string());
+        dc.fix = Some(Fix {
+            description: "Remove class 'Unused'".to_string(),
+            edits: vec![TextEdit {
+                start_byte: 14,
+                end_byte: 27,
+                replacement: String::new(),
+            }],
+        });
+
+        let snippets = SnippetReader::new(1);
+        let report = JsonReport::from_dead_code(&[dc], Some(temp.path()), Some(&snippets));
+        let value = serde_json::to_value(&report).unwrap();
+        let schema: Value = serde_json::from_str(JSON_SCHEMA).unwrap();
+
+        assert_eq!(value["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(value["issues"][0]["file"], "app/src/Main.kt");
+        assert_eq!(
+            value["issues"][0]["snippet"],
+            serde_json::json!({ "start_line": 3, "lines": ["", "class Unused"] })
+        );
+        assert_eq!(value["issues"][0]["fix"]["edits"][0]["end_byte"], 27);
+        assert_eq!(
+            schema["properties"]["schema_version"]["const"],
+            JSON_SCHEMA_VERSION
+        );
+        assert!(schema["$id"]
+            .as_str()
+            .unwrap()
+            .ends_with(&format!("report-{}.json", JSON_SCHEMA_VERSION)));
+        check(&value, &schema, &schema, "report");
+    }
+}
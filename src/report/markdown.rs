@@ -0,0 +1,209 @@
+//! Markdown reporter - GitHub-flavored summary for pull requests
+//!
+//! A table of findings per rule, then one collapsible section per file, so
+//! the output can be posted as a PR comment or appended to a CI job summary
+
+use crate::analysis::{DeadCode, Severity};
+use miette::{IntoDiagnostic, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Markdown reporter for PR descriptions and CI job summaries
+pub struct MarkdownReporter {
+    output_path: Option<PathBuf>,
+    /// Base path to strip from file paths
+    base_path: Option<PathBuf>,
+}
+
+impl MarkdownReporter {
+    pub fn new(output_path: Option<PathBuf>) -> Self {
+        Self {
+            output_path,
+            base_path: None,
+        }
+    }
+
+    pub fn with_base_path(mut self, path: PathBuf) -> Self {
+        self.base_path = Some(path);
+        self
+    }
+
+    pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
+        let markdown = self.render(dead_code);
+
+        if let Some(path) = &self.output_path {
+            std::fs::write(path, &markdown).into_diagnostic()?;
+            println!("Markdown report written to: {}", path.display());
+        } else {
+            print!("{}", markdown);
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, dead_code: &[DeadCode]) -> String {
+        let mut out = String::from("## SearchDeadCode report\n\n");
+        if dead_code.is_empty() {
+            out.push_str("No issues found.\n");
+            return out;
+        }
+
+        let mut by_file: BTreeMap<String, Vec<&DeadCode>> = BTreeMap::new();
+        for item in dead_code {
+            by_file
+                .entry(self.format_path(&item.declaration.location.file))
+                .or_default()
+                .push(item);
+        }
+
+        let count = |severity: Severity| {
+            dead_code
+                .iter()
+                .filter(|item| item.severity == severity)
+                .count()
+        };
+        let _ = writeln!(
+            out,
+            "**{} {}** in {} {}: {} errors, {} warnings, {} infos\n",
+            dead_code.len(),
+            plural(dead_code.len(), "issue"),
+            by_file.len(),
+            plural(by_file.len(), "file"),
+            count(Severity::Error),
+            count(Severity::Warning),
+            count(Severity::Info)
+        );
+
+        // Rules with the most findings first
+        let mut by_rule: HashMap<&'static str, (&DeadCode, usize)> = HashMap::new();
+        for item in dead_code {
+            by_rule.entry(item.issue.code()).or_insert((item, 0)).1 += 1;
+        }
+        let mut rules: Vec<_> = by_rule.into_iter().collect();
+        rules.sort_by(|(a_code, (_, a)), (b_code, (_, b))| b.cmp(a).then(a_code.cmp(b_code)));
+
+        out.push_str("| Rule | Description | Severity | Count |\n");
+        out.push_str("|------|-------------|----------|------:|\n");
+        for (code, (first, count)) in rules {
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} |",
+                code,
+                first.issue.description(),
+                first.severity.as_str(),
+                count
+            );
+        }
+
+        for (file, mut items) in by_file {
+            items.sort_by_key(|item| {
+                (
+                    item.declaration.location.line,
+                    item.declaration.location.column,
+                )
+            });
+            let _ = write!(
+                out,
+                "\n<details>\n<summary><code>{}</code> ({})</summary>\n\n",
+                escape_html(&file),
+                items.len()
+            );
+            out.push_str("| Line | Rule | Severity | Confidence | Message |\n");
+            out.push_str("|-----:|------|----------|------------|---------|\n");
+            for item in items {
+                let _ = writeln!(
+                    out,
+                    "| {} | `{}` | {} | {} | {} |",
+                    item.declaration.location.line,
+                    item.issue.code(),
+                    item.severity.as_str(),
+                    item.confidence.as_str(),
+                    escape_cell(&item.message)
+                );
+            }
+            out.push_str("\n</details>\n");
+        }
+
+        out
+    }
+
+    /// Format a path relative to base path if set
+    fn format_path(&self, path: &Path) -> String {
+        match &self.base_path {
+            Some(base) => path.strip_prefix(base).unwrap_or(path),
+            None => path,
+        }
+        .display()
+        .to_string()
+    }
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        word.to_string()
+    } else {
+        format!("{}s", word)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Keep a message on one table row: pipes would end the cell and line
+/// breaks the row
+fn escape_cell(text: &str) -> String {
+    escape_html(text).replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language, Location};
+
+    fn finding(file: &str, line: usize, name: &str, issue: DeadCodeIssue) -> DeadCode {
+        let path = PathBuf::from("/project").join(file);
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), line * 10, line * 10 + 5),
+            name,
+            DeclarationKind::Class,
+            Location::new(path, line, 1, line * 10, line * 10 + 5),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, issue)
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let dead_code = vec![
+            finding("src/Main.kt", 20, "Unused", DeadCodeIssue::Unreferenced),
+            finding("src/Main.kt", 4, "Old", DeadCodeIssue::Unreferenced)
+                .with_message("Map<A|B> is never used".to_string()),
+            finding("src/Util.kt", 7, "Helper", DeadCodeIssue::AssignOnly),
+        ];
+        let markdown = MarkdownReporter::new(None)
+            .with_base_path(PathBuf::from("/project"))
+            .render(&dead_code);
+
+        assert!(markdown.contains("**3 issues** in 2 files"));
+        assert!(markdown.contains("| `DC001` | Unreferenced declarations | warning | 2 |"));
+        let main = markdown
+            .find("<summary><code>src/Main.kt</code> (2)</summary>")
+            .unwrap();
+        let util = markdown.find("<code>src/Util.kt</code> (1)").unwrap();
+        assert!(main < util);
+        let old = markdown.find("Map&lt;A\\|B&gt; is never used").unwrap();
+        let unused = markdown.find("| 20 | `DC001`").unwrap();
+        assert!(main < old && old < unused && unused < util);
+        assert_eq!(markdown.matches("</details>").count(), 2);
+
+        assert_eq!(
+            MarkdownReporter::new(None).render(&[]),
+            "## SearchDeadCode report\n\nNo issues found.\n"
+        );
+    }
+}
@@ -1,18 +1,35 @@
 mod aggregator;
 mod colors;
 mod compact;
+mod csv;
+mod cycle_diagram;
+mod graph_export;
 mod grouped;
 mod json;
+mod markdown;
+mod rdjson;
 mod sarif;
+mod snippet;
+mod sqlite;
 mod summary;
 mod terminal;
+mod treemap;
 
 pub use compact::CompactReporter;
+pub use csv::CsvReporter;
+pub use cycle_diagram::{CycleDiagram, CycleDiagramFormat};
+pub use graph_export::{GraphExportFormat, GraphExporter};
 pub use grouped::{GroupBy, GroupedReporter};
-pub use json::JsonReporter;
+pub use json::{JsonReporter, JSON_SCHEMA, JSON_SCHEMA_VERSION};
+pub use markdown::MarkdownReporter;
+pub use rdjson::RdjsonReporter;
 pub use sarif::SarifReporter;
+#[allow(unused_imports)] // only named by library consumers
+pub use snippet::{Snippet, SnippetReader};
+pub use sqlite::SqliteExporter;
 pub use summary::SummaryReporter;
 pub use terminal::TerminalReporter;
+pub use treemap::TreemapExporter;
 
 use crate::analysis::DeadCode;
 use miette::Result;
@@ -34,6 +51,12 @@ pub enum ReportFormat {
     Json,
     /// SARIF format for IDE integration
     Sarif,
+    /// GitHub-flavored markdown for PR descriptions and job summaries
+    Markdown,
+    /// Reviewdog Diagnostic Format, for PR comments through reviewdog
+    Rdjson,
+    /// Comma-separated values, one row per finding
+    Csv,
 }
 
 /// Options for report generation
@@ -57,6 +80,12 @@ pub struct ReportOptions {
     pub files_count: Option<usize>,
     /// Declarations count (for summary)
     pub declarations_count: Option<usize>,
+    /// With `--baseline`, the findings it holds, which SARIF reports as
+    /// suppressed instead of leaving out
+    pub baselined: Option<Vec<DeadCode>>,
+    /// Source lines around each finding in terminal, JSON and SARIF
+    /// output, or `None` for no snippets
+    pub context_lines: Option<usize>,
 }
 
 impl ReportOptions {
@@ -71,6 +100,8 @@ impl ReportOptions {
             top_n: 10,
             files_count: None,
             declarations_count: None,
+            baselined: None,
+            context_lines: None,
         }
     }
 
@@ -83,6 +114,7 @@ pub struct Reporter {
 }
 
 impl Reporter {
+    #[allow(dead_code)] // the CLI passes full options; library consumers use this
     pub fn new(format: ReportFormat, output_path: Option<PathBuf>) -> Self {
         Self {
             format,
@@ -101,8 +133,11 @@ impl Reporter {
     pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
         match &self.format {
             ReportFormat::Terminal => {
-                let reporter = TerminalReporter::new()
+                let mut reporter = TerminalReporter::new()
                     .with_confidence(self.options.show_confidence);
+                if let Some(context) = self.options.context_lines {
+                    reporter = reporter.with_context(context);
+                }
                 reporter.report(dead_code)?;
                 // Always show full summary at the end
                 self.print_final_summary(dead_code);
@@ -148,11 +183,44 @@ impl Reporter {
                 Ok(())
             }
             ReportFormat::Json => {
-                let reporter = JsonReporter::new(self.options.output_path.clone());
+                let mut reporter = JsonReporter::new(self.options.output_path.clone());
+                if let Some(base) = &self.options.base_path {
+                    reporter = reporter.with_base_path(base.clone());
+                }
+                if let Some(context) = self.options.context_lines {
+                    reporter = reporter.with_context(context);
+                }
                 reporter.report(dead_code)
             }
             ReportFormat::Sarif => {
-                let reporter = SarifReporter::new(self.options.output_path.clone());
+                let mut reporter = SarifReporter::new(self.options.output_path.clone());
+                if let Some(base) = &self.options.base_path {
+                    reporter = reporter.with_base_path(base.clone());
+                }
+                if let Some(baselined) = &self.options.baselined {
+                    reporter = reporter.with_baselined(baselined.clone());
+                }
+                if let Some(context) = self.options.context_lines {
+                    reporter = reporter.with_context(context);
+                }
+                reporter.report(dead_code)
+            }
+            ReportFormat::Markdown => {
+                let mut reporter = MarkdownReporter::new(self.options.output_path.clone());
+                if let Some(base) = &self.options.base_path {
+                    reporter = reporter.with_base_path(base.clone());
+                }
+                reporter.report(dead_code)
+            }
+            ReportFormat::Rdjson => {
+                let reporter = RdjsonReporter::new(self.options.output_path.clone());
+                reporter.report(dead_code)
+            }
+            ReportFormat::Csv => {
+                let mut reporter = CsvReporter::new(self.options.output_path.clone());
+                if let Some(base) = &self.options.base_path {
+                    reporter = reporter.with_base_path(base.clone());
+                }
                 reporter.report(dead_code)
             }
         }
@@ -0,0 +1,113 @@
+use crate::analysis::{DeadCode, Severity};
+use miette::{IntoDiagnostic, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Reviewdog Diagnostic Format reporter, for `reviewdog -f=rdjson`
+///
+/// Paths are written as the analysis found them, relative to the working
+/// directory when the analyzed path is, which is where reviewdog looks them
+/// up.
+pub struct RdjsonReporter {
+    output_path: Option<PathBuf>,
+}
+
+impl RdjsonReporter {
+    pub fn new(output_path: Option<PathBuf>) -> Self {
+        Self { output_path }
+    }
+
+    pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
+        let report = RdjsonReport::from_dead_code(dead_code);
+        let json = serde_json::to_string_pretty(&report).into_diagnostic()?;
+
+        if let Some(path) = &self.output_path {
+            std::fs::write(path, &json).into_diagnostic()?;
+            println!("Reviewdog report written to: {}", path.display());
+        } else {
+            println!("{}", json);
+        }
+
+        Ok(())
+    }
+}
+
+/// Diagnostic result, as defined by reviewdog's `rdf/proto/rdf.proto`
+#[derive(Serialize)]
+struct RdjsonReport {
+    source: RdjsonSource,
+    diagnostics: Vec<RdjsonDiagnostic>,
+}
+
+#[derive(Serialize)]
+struct RdjsonSource {
+    name: &'static str,
+    url: &'static str,
+}
+
+#[derive(Serialize)]
+struct RdjsonDiagnostic {
+    message: String,
+    location: RdjsonLocation,
+    severity: &'static str,
+    code: RdjsonCode,
+}
+
+#[derive(Serialize)]
+struct RdjsonLocation {
+    path: String,
+    range: RdjsonRange,
+}
+
+#[derive(Serialize)]
+struct RdjsonRange {
+    start: RdjsonPosition,
+}
+
+/// 1-based line, and 1-based column counted in UTF-8 bytes
+#[derive(Serialize)]
+struct RdjsonPosition {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Serialize)]
+struct RdjsonCode {
+    value: &'static str,
+}
+
+impl RdjsonReport {
+    fn from_dead_code(dead_code: &[DeadCode]) -> Self {
+        let diagnostics = dead_code
+            .iter()
+            .map(|dc| RdjsonDiagnostic {
+                message: dc.message.clone(),
+                location: RdjsonLocation {
+                    path: dc.declaration.location.file.to_string_lossy().to_string(),
+                    range: RdjsonRange {
+                        start: RdjsonPosition {
+                            line: dc.declaration.location.line,
+                            column: dc.declaration.location.column,
+                        },
+                    },
+                },
+                severity: match dc.severity {
+                    Severity::Error => "ERROR",
+                    Severity::Warning => "WARNING",
+                    Severity::Info => "INFO",
+                },
+                code: RdjsonCode {
+                    value: dc.issue.code(),
+                },
+            })
+            .collect();
+
+        Self {
+            source: RdjsonSource {
+                name: "searchdeadcode",
+                url: env!("CARGO_PKG_REPOSITORY"),
+            },
+            diagnostics,
+        }
+    }
+}
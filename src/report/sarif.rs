@@ -1,20 +1,64 @@
-use crate::analysis::{DeadCode, Severity};
+use crate::analysis::{DeadCode, DeadCodeIssue, Severity};
+use crate::graph::Location;
+use crate::report::aggregator::Savings;
+use crate::report::snippet::SnippetReader;
 use miette::{IntoDiagnostic, Result};
 use serde::Serialize;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Key of the fingerprint in `partialFingerprints`, versioned so a change in
+/// how it is computed does not reopen every alert under the same key
+const FINGERPRINT_KEY: &str = "searchDeadCode/v1";
 
 /// SARIF reporter for CI/CD integration (GitHub, Azure DevOps, etc.)
 pub struct SarifReporter {
     output_path: Option<PathBuf>,
+    /// Base path stripped from file paths in fingerprints, so they do not
+    /// depend on where the project is checked out
+    base_path: Option<PathBuf>,
+    /// With a baseline, the findings it holds, reported as suppressed
+    baselined: Option<Vec<DeadCode>>,
+    /// Source lines included around each finding
+    snippets: Option<SnippetReader>,
 }
 
 impl SarifReporter {
     pub fn new(output_path: Option<PathBuf>) -> Self {
-        Self { output_path }
+        Self {
+            output_path,
+            base_path: None,
+            baselined: None,
+            snippets: None,
+        }
+    }
+
+    pub fn with_base_path(mut self, path: PathBuf) -> Self {
+        self.base_path = Some(path);
+        self
+    }
+
+    /// Report the findings a baseline holds as suppressed results, and the
+    /// others with the `new` baseline state
+    pub fn with_baselined(mut self, baselined: Vec<DeadCode>) -> Self {
+        self.baselined = Some(baselined);
+        self
+    }
+
+    /// Include each finding's line with `context` lines before and after it,
+    /// as the `contextRegion` of its location
+    pub fn with_context(mut self, context: usize) -> Self {
+        self.snippets = Some(SnippetReader::new(context));
+        self
     }
 
     pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
-        let sarif = SarifReport::from_dead_code(dead_code);
+        let sarif = SarifReport::from_dead_code(
+            dead_code,
+            self.baselined.as_deref(),
+            self.base_path.as_deref(),
+            self.snippets.as_ref(),
+        );
         let json = serde_json::to_string_pretty(&sarif).into_diagnostic()?;
 
         if let Some(path) = &self.output_path {
@@ -41,6 +85,24 @@ struct SarifReport {
 struct SarifRun {
     tool: SarifTool,
     results: Vec<SarifResult>,
+    properties: SarifSavings,
+}
+
+/// Property bag with the code removing findings would delete
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifSavings {
+    estimated_removable_loc: usize,
+    estimated_removable_bytes: usize,
+}
+
+impl From<Savings> for SarifSavings {
+    fn from(savings: Savings) -> Self {
+        Self {
+            estimated_removable_loc: savings.loc,
+            estimated_removable_bytes: savings.bytes,
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -79,6 +141,66 @@ struct SarifResult {
     level: &'static str,
     message: SarifMessage,
     locations: Vec<SarifLocation>,
+    #[serde(rename = "partialFingerprints")]
+    partial_fingerprints: BTreeMap<&'static str, String>,
+    #[serde(rename = "relatedLocations", skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<SarifRelatedLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<SarifFix>,
+    /// `new` or `unchanged`, when a baseline was used
+    #[serde(rename = "baselineState", skip_serializing_if = "Option::is_none")]
+    baseline_state: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressions: Vec<SarifSuppression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<SarifSavings>,
+}
+
+/// Why a result is not alerted on: for baselined findings, the baseline
+#[derive(Serialize)]
+struct SarifSuppression {
+    kind: &'static str,
+    status: &'static str,
+    justification: &'static str,
+}
+
+#[derive(Serialize)]
+struct SarifRelatedLocation {
+    id: usize,
+    #[serde(rename = "physicalLocation")]
+    physical_location: SarifPhysicalLocation,
+    message: SarifMessage,
+}
+
+/// Proposed fix: deleting the declaration
+#[derive(Serialize)]
+struct SarifFix {
+    description: SarifMessage,
+    #[serde(rename = "artifactChanges")]
+    artifact_changes: Vec<SarifArtifactChange>,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactChange {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+    replacements: Vec<SarifReplacement>,
+}
+
+#[derive(Serialize)]
+struct SarifReplacement {
+    #[serde(rename = "deletedRegion")]
+    deleted_region: SarifByteRegion,
+    #[serde(rename = "insertedContent", skip_serializing_if = "Option::is_none")]
+    inserted_content: Option<SarifArtifactContent>,
+}
+
+#[derive(Serialize)]
+struct SarifByteRegion {
+    #[serde(rename = "byteOffset")]
+    byte_offset: usize,
+    #[serde(rename = "byteLength")]
+    byte_length: usize,
 }
 
 #[derive(Serialize)]
@@ -97,6 +219,23 @@ struct SarifPhysicalLocation {
     #[serde(rename = "artifactLocation")]
     artifact_location: SarifArtifactLocation,
     region: SarifRegion,
+    #[serde(rename = "contextRegion", skip_serializing_if = "Option::is_none")]
+    context_region: Option<SarifContextRegion>,
+}
+
+/// Lines around the region, with their text
+#[derive(Serialize)]
+struct SarifContextRegion {
+    #[serde(rename = "startLine")]
+    start_line: usize,
+    #[serde(rename = "endLine")]
+    end_line: usize,
+    snippet: SarifArtifactContent,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactContent {
+    text: String,
 }
 
 #[derive(Serialize)]
@@ -112,8 +251,34 @@ struct SarifRegion {
     start_column: usize,
 }
 
+impl SarifPhysicalLocation {
+    fn new(location: &Location) -> Self {
+        Self {
+            artifact_location: SarifArtifactLocation::new(location),
+            region: SarifRegion {
+                start_line: location.line,
+                start_column: location.column,
+            },
+            context_region: None,
+        }
+    }
+}
+
+impl SarifArtifactLocation {
+    fn new(location: &Location) -> Self {
+        Self {
+            uri: location.file.to_string_lossy().to_string(),
+        }
+    }
+}
+
 impl SarifReport {
-    fn from_dead_code(dead_code: &[DeadCode]) -> Self {
+    fn from_dead_code(
+        dead_code: &[DeadCode],
+        baselined: Option<&[DeadCode]>,
+        base_path: Option<&Path>,
+        snippets: Option<&SnippetReader>,
+    ) -> Self {
         let rules = vec![
             SarifRule {
                 id: "DC001",
@@ -173,35 +338,75 @@ impl SarifReport {
             },
         ];
 
-        let results: Vec<SarifResult> = dead_code
-            .iter()
-            .map(|dc| {
-                let level = match dc.severity {
-                    Severity::Error => "error",
-                    Severity::Warning => "warning",
-                    Severity::Info => "note",
-                };
-
-                SarifResult {
-                    rule_id: dc.issue.code(),
-                    level,
-                    message: SarifMessage {
-                        text: dc.message.clone(),
+        // Findings with the same fingerprint (overloads) are told apart by
+        // their order, the way GitHub numbers `primaryLocationLineHash`
+        let mut occurrences: HashMap<u64, usize> = HashMap::new();
+
+        // Baselined findings follow the new ones, as suppressed results
+        let suppressed = baselined.unwrap_or_default().iter().map(|dc| (dc, true));
+        let findings = dead_code.iter().map(|dc| (dc, false)).chain(suppressed);
+        let mut results = Vec::new();
+        for (dc, is_baselined) in findings {
+            let level = match dc.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "note",
+            };
+
+            let hash = fingerprint(dc, base_path);
+            let occurrence = occurrences.entry(hash).or_default();
+            *occurrence += 1;
+
+            let mut location = SarifPhysicalLocation::new(&dc.declaration.location);
+            location.context_region = snippets
+                .and_then(|s| s.around(&dc.declaration.location))
+                .map(|snippet| SarifContextRegion {
+                    start_line: snippet.start_line,
+                    end_line: snippet.end_line(),
+                    snippet: SarifArtifactContent {
+                        text: snippet.text(),
                     },
-                    locations: vec![SarifLocation {
-                        physical_location: SarifPhysicalLocation {
-                            artifact_location: SarifArtifactLocation {
-                                uri: dc.declaration.location.file.to_string_lossy().to_string(),
-                            },
-                            region: SarifRegion {
-                                start_line: dc.declaration.location.line,
-                                start_column: dc.declaration.location.column,
-                            },
+                });
+
+            results.push(SarifResult {
+                rule_id: dc.issue.code(),
+                level,
+                message: SarifMessage {
+                    text: dc.message.clone(),
+                },
+                locations: vec![SarifLocation {
+                    physical_location: location,
+                }],
+                partial_fingerprints: BTreeMap::from([(
+                    FINGERPRINT_KEY,
+                    format!("{:016x}:{}", hash, occurrence),
+                )]),
+                related_locations: dc
+                    .related
+                    .iter()
+                    .enumerate()
+                    .map(|(index, related)| SarifRelatedLocation {
+                        id: index + 1,
+                        physical_location: SarifPhysicalLocation::new(&related.location),
+                        message: SarifMessage {
+                            text: related.message.clone(),
                         },
-                    }],
-                }
-            })
-            .collect();
+                    })
+                    .collect(),
+                fixes: fix(dc).into_iter().collect(),
+                baseline_state: baselined.map(|_| if is_baselined { "unchanged" } else { "new" }),
+                suppressions: if is_baselined {
+                    vec![SarifSuppression {
+                        kind: "external",
+                        status: "accepted",
+                        justification: "In the baseline",
+                    }]
+                } else {
+                    Vec::new()
+                },
+                properties: dc.issue.is_removable().then(|| Savings::of(dc).into()),
+            });
+        }
 
         SarifReport {
             schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
@@ -216,7 +421,249 @@ impl SarifReport {
                     },
                 },
                 results,
+                properties: Savings::total(dead_code).into(),
             }],
         }
     }
 }
+
+/// Hash of what identifies a finding across runs: its rule, file and
+/// declaration, but not its line, so unrelated edits above it keep the alert
+fn fingerprint(dc: &DeadCode, base_path: Option<&Path>) -> u64 {
+    let file = dc.declaration.location.file.as_path();
+    let file = base_path
+        .and_then(|base| file.strip_prefix(base).ok())
+        .unwrap_or(file);
+    let name = dc
+        .declaration
+        .fully_qualified_name
+        .as_deref()
+        .unwrap_or(&dc.declaration.name);
+    let key = format!(
+        "{}|{}|{}|{}",
+        dc.issue.code(),
+        file.to_string_lossy().replace('\\', "/"),
+        dc.declaration.kind.display_name(),
+        name
+    );
+
+    // FNV-1a rather than `DefaultHasher`, whose output may change between
+    // Rust releases
+    key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The finding's quick fix, or else a fix deleting the declaration of a
+/// finding whose fix is deleting it. Parameters without a quick fix are left
+/// out: call sites have to change with them
+fn fix(dc: &DeadCode) -> Option<SarifFix> {
+    let location = &dc.declaration.location;
+    if let Some(fix) = &dc.fix {
+        return Some(SarifFix {
+            description: SarifMessage {
+                text: fix.description.clone(),
+            },
+            artifact_changes: vec![SarifArtifactChange {
+                artifact_location: SarifArtifactLocation::new(location),
+                replacements: fix
+                    .edits
+                    .iter()
+                    .map(|edit| SarifReplacement {
+                        deleted_region: SarifByteRegion {
+                            byte_offset: edit.start_byte,
+                            byte_length: edit.end_byte - edit.start_byte,
+                        },
+                        inserted_content: (!edit.replacement.is_empty()).then(|| {
+                            SarifArtifactContent {
+                                text: edit.replacement.clone(),
+                            }
+                        }),
+                    })
+                    .collect(),
+            }],
+        });
+    }
+    if !dc.issue.is_removable()
+        || dc.issue == DeadCodeIssue::UnusedParameter
+        || location.end_byte <= location.start_byte
+    {
+        return None;
+    }
+
+    Some(SarifFix {
+        description: SarifMessage {
+            text: format!(
+                "Delete {} '{}'",
+                dc.declaration.kind.display_name(),
+                dc.declaration.name
+            ),
+        },
+        artifact_changes: vec![SarifArtifactChange {
+            artifact_location: SarifArtifactLocation::new(location),
+            replacements: vec![SarifReplacement {
+                deleted_region: SarifByteRegion {
+                    byte_offset: location.start_byte,
+                    byte_length: location.end_byte - location.start_byte,
+                },
+                inserted_content: None,
+            }],
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{Fix, TextEdit};
+    use crate::graph::{Declaration, DeclarationId, DeclarationKind, Language};
+
+    fn finding(root: &str, line: usize, issue: DeadCodeIssue) -> DeadCode {
+        let path = PathBuf::from(root).join("src/Main.kt");
+        let start = line * 10;
+        let decl = Declaration::new(
+            DeclarationId::new(path.clone(), start, start + 25),
+            "Unused",
+            DeclarationKind::Class,
+            Location::new(path, line, 1, start, start + 25),
+            Language::Kotlin,
+        );
+        DeadCode::new(decl, issue)
+    }
+
+    fn result(dead_code: &[DeadCode], root: &str) -> serde_json::Value {
+        let report = SarifReport::from_dead_code(dead_code, None, Some(Path::new(root)), None);
+        serde_json::to_value(&report).unwrap()["runs"][0]["results"].clone()
+    }
+
+    #[test]
+    fn test_partial_fingerprints() {
+        let before = result(&[finding("/ci/a", 4, DeadCodeIssue::Unreferenced)], "/ci/a");
+        let after = result(
+            &[
+                finding("/ci/b", 9, DeadCodeIssue::Unreferenced),
+                finding("/ci/b", 30, DeadCodeIssue::Unreferenced),
+                finding("/ci/b", 9, DeadCodeIssue::AssignOnly),
+            ],
+            "/ci/b",
+        );
+        let fingerprint = |results: &serde_json::Value, index: usize| {
+            results[index]["partialFingerprints"][FINGERPRINT_KEY]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+
+        // Moved and checked out elsewhere, still the same alert
+        assert_eq!(fingerprint(&before, 0), fingerprint(&after, 0));
+        assert!(fingerprint(&after, 0).ends_with(":1"));
+        assert!(fingerprint(&after, 1).ends_with(":2"));
+        assert_ne!(
+            fingerprint(&after, 0).split(':').next(),
+            fingerprint(&after, 2).split(':').next()
+        );
+    }
+
+    #[test]
+    fn test_related_locations_and_fixes() {
+        let assigned = Location::new(PathBuf::from("/p/src/Other.kt"), 12, 5, 0, 0);
+        let dead_code = [
+            finding("/p", 4, DeadCodeIssue::AssignOnly).with_related(assigned, "Assigned here"),
+            finding("/p", 8, DeadCodeIssue::UnusedParameter),
+            finding("/p", 9, DeadCodeIssue::UnusedParameter).with_fix(Fix {
+                description: "Remove unused parameter 'Unused'".to_string(),
+                edits: vec![TextEdit {
+                    start_byte: 90,
+                    end_byte: 117,
+                    replacement: String::new(),
+                }],
+            }),
+        ];
+        let results = result(&dead_code, "/p");
+
+        let related = &results[0]["relatedLocations"][0];
+        assert_eq!(related["id"], 1);
+        assert_eq!(related["message"]["text"], "Assigned here");
+        assert_eq!(
+            related["physicalLocation"]["artifactLocation"]["uri"],
+            "/p/src/Other.kt"
+        );
+        assert_eq!(related["physicalLocation"]["region"]["startLine"], 12);
+
+        let change = &results[0]["fixes"][0]["artifactChanges"][0];
+        assert_eq!(
+            results[0]["fixes"][0]["description"]["text"],
+            "Delete class 'Unused'"
+        );
+        assert_eq!(change["artifactLocation"]["uri"], "/p/src/Main.kt");
+        assert_eq!(
+            change["replacements"][0]["deletedRegion"],
+            serde_json::json!({ "byteOffset": 40, "byteLength": 25 })
+        );
+        assert!(results[1].get("fixes").is_none());
+        assert!(results[1].get("relatedLocations").is_none());
+        assert_eq!(
+            results[2]["fixes"][0]["artifactChanges"][0]["replacements"],
+            serde_json::json!([{ "deletedRegion": { "byteOffset": 90, "byteLength": 27 } }])
+        );
+    }
+
+    #[test]
+    fn test_baselined_suppressions() {
+        let new = [finding("/p", 4, DeadCodeIssue::Unreferenced)];
+        let baselined = [finding("/p", 9, DeadCodeIssue::AssignOnly)];
+        let report =
+            SarifReport::from_dead_code(&new, Some(&baselined), Some(Path::new("/p")), None);
+        let results = serde_json::to_value(&report).unwrap()["runs"][0]["results"].clone();
+
+        assert_eq!(results.as_array().unwrap().len(), 2);
+        assert_eq!(results[0]["baselineState"], "new");
+        assert!(results[0].get("suppressions").is_none());
+        assert_eq!(results[1]["ruleId"], "DC002");
+        assert_eq!(results[1]["baselineState"], "unchanged");
+        assert_eq!(
+            results[1]["suppressions"],
+            serde_json::json!([{
+                "kind": "external",
+                "status": "accepted",
+                "justification": "In the baseline"
+            }])
+        );
+
+        // Without a baseline, results have no baseline state
+        assert!(result(&new, "/p")[0].get("baselineState").is_none());
+    }
+
+    #[test]
+    fn test_context_region() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().to_str().unwrap();
+        let dc = finding(root, 2, DeadCodeIssue::Unreferenced);
+        std::fs::create_dir_all(temp.path().join("src")).unwrap();
+        std::fs::write(
+            temp.path().join("src/Main.kt"),
+            "package app\nclass Unused\n",
+        )
+        .unwrap();
+
+        let snippets = SnippetReader::new(2);
+        let report = SarifReport::from_dead_code(&[dc], None, Some(temp.path()), Some(&snippets));
+        let value = serde_json::to_value(&report).unwrap();
+        let location = &value["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+
+        assert_eq!(
+            location["contextRegion"],
+            serde_json::json!({
+                "startLine": 1,
+                "endLine": 2,
+                "snippet": { "text": "package app\nclass Unused" }
+            })
+        );
+        assert!(
+            result(&[finding(root, 2, DeadCodeIssue::Unreferenced)], root)[0]["locations"][0]
+                ["physicalLocation"]
+                .get("contextRegion")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Source lines around findings (`--context`)
+//!
+//! Files are read the first time a finding in them asks for its snippet and
+//! kept for the next ones, so only files with findings are read, once.
+
+use crate::graph::Location;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Lines of a file around a finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Snippet {
+    /// Line number of the first line (1-indexed)
+    pub start_line: usize,
+    pub lines: Vec<String>,
+}
+
+impl Snippet {
+    pub fn end_line(&self) -> usize {
+        self.start_line + self.lines.len() - 1
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+/// Reader of snippets with a number of context lines on each side
+pub struct SnippetReader {
+    context: usize,
+    /// Lines of the files read so far; `None` for unreadable files
+    files: RefCell<HashMap<PathBuf, Option<Rc<Vec<String>>>>>,
+}
+
+impl SnippetReader {
+    pub fn new(context: usize) -> Self {
+        Self {
+            context,
+            files: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// The location's line with `context` lines before and after it, or
+    /// `None` when the file cannot be read or is shorter than the line
+    pub fn around(&self, location: &Location) -> Option<Snippet> {
+        let lines = self
+            .files
+            .borrow_mut()
+            .entry(location.file.to_path_buf())
+            .or_insert_with(|| {
+                let bytes = std::fs::read(location.file.as_path()).ok()?;
+                let text = String::from_utf8_lossy(&bytes);
+                Some(Rc::new(text.lines().map(str::to_string).collect()))
+            })
+            .clone()?;
+
+        if location.line == 0 || location.line > lines.len() {
+            return None;
+        }
+        let start = location.line.saturating_sub(self.context).max(1);
+        let end = (location.line + self.context).min(lines.len());
+        Some(Snippet {
+            start_line: start,
+            lines: lines[start - 1..end].to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_around() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Main.kt");
+        std::fs::write(
+            &path,
+            "package app\r\n\nclass Unused {\n}\n\nfun main() {}\n",
+        )
+        .unwrap();
+        let at = |line| Location::new(path.clone(), line, 1, 0, 0);
+
+        let reader = SnippetReader::new(1);
+        let snippet = reader.around(&at(3)).unwrap();
+        assert_eq!(snippet.start_line, 2);
+        assert_eq!(snippet.end_line(), 4);
+        assert_eq!(snippet.text(), "\nclass Unused {\n}");
+
+        // Clamped to the file
+        assert_eq!(reader.around(&at(1)).unwrap().lines, ["package app", ""]);
+        assert_eq!(reader.around(&at(6)).unwrap().start_line, 5);
+        assert_eq!(reader.around(&at(7)), None);
+
+        assert_eq!(
+            SnippetReader::new(0).around(&at(3)).unwrap().lines,
+            ["class Unused {"]
+        );
+        assert_eq!(
+            reader.around(&Location::new(temp.path().join("Gone.kt"), 1, 1, 0, 0)),
+            None
+        );
+    }
+}
@@ -0,0 +1,347 @@
+use crate::analysis::DeadCode;
+use crate::graph::Graph;
+use miette::Result;
+use std::path::{Path, PathBuf};
+
+/// Version of the database schema, stored as `PRAGMA user_version` and
+/// bumped on incompatible changes
+#[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+const SCHEMA_VERSION: i64 = 1;
+
+/// Tables of the database, documented in docs/cli-reference.md
+#[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    created_at INTEGER NOT NULL,
+    tool_version TEXT NOT NULL,
+    root TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS declarations (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    name TEXT NOT NULL,
+    fully_qualified_name TEXT,
+    kind TEXT NOT NULL,
+    visibility TEXT NOT NULL,
+    file TEXT NOT NULL,
+    line INTEGER NOT NULL,
+    column INTEGER NOT NULL,
+    parent_id INTEGER REFERENCES declarations(id),
+    module TEXT,
+    lines INTEGER NOT NULL,
+    loc INTEGER NOT NULL,
+    complexity INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS refs (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    from_id INTEGER NOT NULL REFERENCES declarations(id),
+    to_id INTEGER NOT NULL REFERENCES declarations(id),
+    kind TEXT NOT NULL,
+    file TEXT NOT NULL,
+    line INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS findings (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    declaration_id INTEGER REFERENCES declarations(id),
+    code TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    confidence TEXT NOT NULL,
+    runtime_confirmed INTEGER NOT NULL,
+    message TEXT NOT NULL,
+    file TEXT NOT NULL,
+    line INTEGER NOT NULL,
+    column INTEGER NOT NULL,
+    module TEXT,
+    scope TEXT
+);
+CREATE INDEX IF NOT EXISTS declarations_run ON declarations(run_id, file);
+CREATE INDEX IF NOT EXISTS refs_from ON refs(from_id);
+CREATE INDEX IF NOT EXISTS refs_to ON refs(to_id);
+CREATE INDEX IF NOT EXISTS findings_run ON findings(run_id, code);
+";
+
+/// Exporter of declarations, references and findings to a SQLite database
+///
+/// Each export adds a run to the database, so exporting every build into
+/// the same file keeps the history of the findings.
+pub struct SqliteExporter {
+    base_path: Option<PathBuf>,
+}
+
+impl SqliteExporter {
+    pub fn new() -> Self {
+        Self { base_path: None }
+    }
+
+    /// Write file paths relative to this directory
+    pub fn with_base_path(mut self, path: PathBuf) -> Self {
+        self.base_path = Some(path);
+        self
+    }
+
+    /// Add a run with the graph's declarations and references and the
+    /// findings to the database at `path`, creating it if needed; returns
+    /// the run's id
+    #[cfg(feature = "sqlite")]
+    pub fn export(&self, path: &Path, graph: &Graph, dead_code: &[DeadCode]) -> Result<i64> {
+        use crate::graph::{DeclarationId, DeclarationKind};
+        use miette::IntoDiagnostic;
+        use rusqlite::{params, Connection};
+        use std::collections::HashMap;
+
+        let mut conn = Connection::open(path).into_diagnostic()?;
+        let version: i64 = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .into_diagnostic()?;
+        if version != 0 && version != SCHEMA_VERSION {
+            miette::bail!(
+                "Cannot export to {}: its schema version is {}, expected {}",
+                path.display(),
+                version,
+                SCHEMA_VERSION
+            );
+        }
+        conn.execute_batch(SCHEMA).into_diagnostic()?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+            .into_diagnostic()?;
+
+        let tx = conn.transaction().into_diagnostic()?;
+        let created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        tx.execute(
+            "INSERT INTO runs (created_at, tool_version, root) VALUES (?1, ?2, ?3)",
+            params![
+                created_at,
+                env!("CARGO_PKG_VERSION"),
+                self.base_path
+                    .as_deref()
+                    .map(|base| base.display().to_string())
+                    .unwrap_or_default()
+            ],
+        )
+        .into_diagnostic()?;
+        let run_id = tx.last_insert_rowid();
+
+        // Parents before their members, so parent ids are known when a
+        // member is inserted; parameters are left out, like in graph exports
+        let mut declarations: Vec<_> = graph
+            .declarations()
+            .filter(|decl| decl.kind != DeclarationKind::Parameter)
+            .collect();
+        declarations.sort_by(|a, b| {
+            (&a.location.file, a.location.start_byte, &a.name).cmp(&(
+                &b.location.file,
+                b.location.start_byte,
+                &b.name,
+            ))
+        });
+        let mut ids: HashMap<&DeclarationId, i64> = HashMap::new();
+        {
+            let mut insert = tx
+                .prepare(
+                    "INSERT INTO declarations (run_id, name, fully_qualified_name, kind, \
+                     visibility, file, line, column, parent_id, module, lines, loc, complexity) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                )
+                .into_diagnostic()?;
+            for decl in &declarations {
+                let parent = decl.parent.as_ref().and_then(|parent| ids.get(parent));
+                insert
+                    .execute(params![
+                        run_id,
+                        decl.name.as_str(),
+                        decl.fully_qualified_name.as_deref(),
+                        decl.kind.display_name(),
+                        decl.visibility.display_name(),
+                        self.display_path(&decl.location.file),
+                        decl.location.line as i64,
+                        decl.location.column as i64,
+                        parent,
+                        decl.module.as_deref(),
+                        decl.metrics.lines as i64,
+                        decl.metrics.loc as i64,
+                        decl.metrics.complexity as i64,
+                    ])
+                    .into_diagnostic()?;
+                ids.insert(&decl.id, tx.last_insert_rowid());
+            }
+        }
+
+        {
+            let mut insert = tx
+                .prepare(
+                    "INSERT INTO refs (run_id, from_id, to_id, kind, file, line) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                )
+                .into_diagnostic()?;
+            for decl in &declarations {
+                let from = ids[&decl.id];
+                for (target, reference) in graph.get_references_from(&decl.id) {
+                    let Some(&to) = ids.get(&target.id) else {
+                        continue;
+                    };
+                    insert
+                        .execute(params![
+                            run_id,
+                            from,
+                            to,
+                            reference.kind.display_name(),
+                            self.display_path(&reference.location.file),
+                            reference.location.line as i64,
+                        ])
+                        .into_diagnostic()?;
+                }
+            }
+        }
+
+        {
+            let mut insert = tx
+                .prepare(
+                    "INSERT INTO findings (run_id, declaration_id, code, severity, confidence, \
+                     runtime_confirmed, message, file, line, column, module, scope) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                )
+                .into_diagnostic()?;
+            for dc in dead_code {
+                let location = &dc.declaration.location;
+                insert
+                    .execute(params![
+                        run_id,
+                        ids.get(&dc.declaration.id),
+                        dc.issue.code(),
+                        dc.severity.as_str(),
+                        dc.confidence.as_str(),
+                        dc.runtime_confirmed,
+                        dc.message,
+                        self.display_path(&location.file),
+                        location.line as i64,
+                        location.column as i64,
+                        dc.module,
+                        dc.scope.map(|scope| scope.as_str()),
+                    ])
+                    .into_diagnostic()?;
+            }
+        }
+
+        tx.commit().into_diagnostic()?;
+        Ok(run_id)
+    }
+
+    #[cfg(not(feature = "sqlite"))]
+    pub fn export(&self, path: &Path, _graph: &Graph, _dead_code: &[DeadCode]) -> Result<i64> {
+        miette::bail!(
+            "Cannot export to {}: built without the `sqlite` feature",
+            path.display()
+        )
+    }
+
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    fn display_path(&self, path: &Path) -> String {
+        let path = match &self.base_path {
+            Some(base) => path.strip_prefix(base).unwrap_or(path),
+            None => path,
+        };
+        path.display().to_string()
+    }
+}
+
+impl Default for SqliteExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::analysis::ReachabilityAnalyzer;
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+    use rusqlite::Connection;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_sqlite_export() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let source = temp.path().join("Repository.kt");
+        std::fs::write(
+            &source,
+            r#"package com.app.data
+
+class Repository {
+    fun load() = helper()
+    private fun helper() = Unit
+}
+
+class Unused
+"#,
+        )
+        .unwrap();
+        let mut builder = GraphBuilder::new();
+        builder
+            .process_file(&SourceFile::new(source, FileType::Kotlin))
+            .unwrap();
+        let graph = builder.build();
+        let entry_points: HashSet<_> = graph
+            .find_by_name("Repository")
+            .iter()
+            .map(|decl| decl.id.clone())
+            .collect();
+        let (dead_code, _) =
+            ReachabilityAnalyzer::new().find_unreachable_with_reachable(&graph, &entry_points);
+
+        let db = temp.path().join("findings.sqlite");
+        let exporter = SqliteExporter::new().with_base_path(temp.path().to_path_buf());
+        assert_eq!(exporter.export(&db, &graph, &dead_code).unwrap(), 1);
+        assert_eq!(exporter.export(&db, &graph, &dead_code).unwrap(), 2);
+
+        let conn = Connection::open(&db).unwrap();
+        let query = |sql: &str| -> Vec<String> {
+            let mut statement = conn.prepare(sql).unwrap();
+            let rows = statement.query_map([], |row| row.get(0)).unwrap();
+            rows.map(Result::unwrap).collect()
+        };
+        assert_eq!(
+            query("SELECT name || ':' || kind || ':' || file FROM declarations WHERE run_id = 1"),
+            [
+                "Repository:class:Repository.kt",
+                "load:method:Repository.kt",
+                "helper:method:Repository.kt",
+                "Unused:class:Repository.kt"
+            ]
+        );
+        assert_eq!(
+            query("SELECT loc || '/' || complexity FROM declarations WHERE name = 'Repository'"),
+            ["4/1", "4/1"]
+        );
+        assert_eq!(
+            query(
+                "SELECT p.name FROM declarations d JOIN declarations p ON d.parent_id = p.id \
+                 WHERE d.name = 'helper' AND d.run_id = 2"
+            ),
+            ["Repository"]
+        );
+        assert_eq!(
+            query(
+                "SELECT f.name || '->' || t.name || ':' || r.kind FROM refs r \
+                 JOIN declarations f ON r.from_id = f.id JOIN declarations t ON r.to_id = t.id \
+                 WHERE r.run_id = 1 AND t.name = 'helper'"
+            ),
+            ["load->helper:call"]
+        );
+        assert_eq!(
+            query(
+                "SELECT d.name || ':' || f.code FROM findings f \
+                 JOIN declarations d ON f.declaration_id = d.id WHERE f.run_id = 2"
+            ),
+            ["Unused:DC001"]
+        );
+        let version: i64 = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+    }
+}
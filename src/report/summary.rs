@@ -48,6 +48,7 @@ impl SummaryReporter {
     }
 
     /// Mark this as a final summary appended to another report (different footer)
+    #[allow(clippy::wrong_self_convention)]
     pub fn as_final_summary(mut self) -> Self {
         self.is_final_summary = true;
         self
@@ -124,6 +125,25 @@ impl SummaryReporter {
             StructureColors::count(&Self::format_number(stats.total_issues)),
             width = label_width
         );
+
+        if stats.savings.bytes > 0 {
+            println!(
+                "{:>width$}  {} {}",
+                "Removable code:".dimmed(),
+                StructureColors::count(&Self::format_number(stats.savings.loc)),
+                format!("LOC ({})", Self::format_bytes(stats.savings.bytes)).dimmed(),
+                width = label_width
+            );
+        }
+    }
+
+    /// Format a byte count in B, KB or MB
+    fn format_bytes(bytes: usize) -> String {
+        match bytes {
+            0..=1023 => format!("{} B", bytes),
+            1024..=1048575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+            _ => format!("{:.1} MB", bytes as f64 / 1048576.0),
+        }
     }
 
     /// Format a number with thousands separators
@@ -342,10 +362,31 @@ impl SummaryReporter {
             "DC002" => "Unused imports",
             "DC003" => "Unused parameters",
             "DC004" => "Assign-only variables",
-            "DC005" => "Unreachable code",
+            "DC005" => "Unused enum cases",
             "DC010" => "Redundant overrides",
             "DC011" => "Unused Intent extras",
             "DC016" => "Redundant public",
+            "DC017" => "Unused DI bindings",
+            "DC018" => "Unregistered receivers",
+            "DC019" => "Unused constants",
+            "DC020" => "Unused extensions",
+            "DC021" => "Unused test helpers",
+            "DC022" => "Stale previews",
+            "DC023" => "Legacy Parcelable boilerplate",
+            "DC024" => "Write-only columns",
+            "DC025" => "Always-default parameters",
+            "DC026" => "Orphan tests",
+            "DC027" => "Dead callbacks",
+            "DC028" => "Unused type aliases",
+            "DC030" => "Test-only code",
+            "DC031" => "Unmatched intent actions",
+            "DC032" => "Never-enqueued workers",
+            "DC033" => "Unused Koin definitions",
+            "DC034" => "Write-only DataStore keys",
+            "DC035" => "Unused dynamic features",
+            "DC036" => "Stale consumer rules",
+            "DC037" => "Unused AIDL methods",
+            "DC038" => "Unused proto messages",
             "AP001" => "Global mutable state",
             "AP002" => "Deep inheritance",
             "AP003" => "Single-impl interface",
@@ -4,6 +4,7 @@
 
 use crate::analysis::DeadCode;
 use crate::report::colors::{ConfidenceIndicator, SeveritySymbol, StructureColors};
+use crate::report::snippet::SnippetReader;
 use colored::Colorize;
 use miette::Result;
 use std::collections::HashMap;
@@ -13,12 +14,15 @@ use std::path::PathBuf;
 pub struct TerminalReporter {
     /// Show confidence levels in output
     show_confidence: bool,
+    /// Source lines shown around each finding
+    snippets: Option<SnippetReader>,
 }
 
 impl TerminalReporter {
     pub fn new() -> Self {
         Self {
             show_confidence: true,
+            snippets: None,
         }
     }
 
@@ -27,6 +31,12 @@ impl TerminalReporter {
         self
     }
 
+    /// Show each finding's line with `context` lines before and after it
+    pub fn with_context(mut self, context: usize) -> Self {
+        self.snippets = Some(SnippetReader::new(context));
+        self
+    }
+
     pub fn report(&self, dead_code: &[DeadCode]) -> Result<()> {
         if dead_code.is_empty() {
             println!("{}", "No dead code found!".green().bold());
@@ -37,7 +47,7 @@ impl TerminalReporter {
         let mut by_file: HashMap<PathBuf, Vec<&DeadCode>> = HashMap::new();
         for item in dead_code {
             by_file
-                .entry(item.declaration.location.file.clone())
+                .entry(item.declaration.location.file.to_path_buf())
                 .or_default()
                 .push(item);
         }
@@ -144,6 +154,22 @@ impl TerminalReporter {
             item.declaration.kind.display_name().dimmed(),
             StructureColors::symbol_name(&item.declaration.name)
         );
+
+        if let Some(snippet) = self
+            .snippets
+            .as_ref()
+            .and_then(|snippets| snippets.around(&item.declaration.location))
+        {
+            let width = snippet.end_line().to_string().len();
+            for (line, text) in (snippet.start_line..).zip(&snippet.lines) {
+                let gutter = format!("{:>width$} |", line, width = width);
+                if line == item.declaration.location.line {
+                    println!("    {} {}", gutter.bold(), text);
+                } else {
+                    println!("    {} {}", gutter.dimmed(), text.dimmed());
+                }
+            }
+        }
     }
 
 }
@@ -0,0 +1,350 @@
+//! Treemap of dead code by package
+//!
+//! An SVG with one tile per package, sized by the package's lines of code
+//! and colored by the share of them that removing findings would delete,
+//! from green (none) to red (half or more), so the packages where dead code
+//! has piled up stand out at a glance.
+
+use crate::analysis::DeadCode;
+use crate::graph::{DeclarationKind, Graph};
+use crate::report::aggregator::Savings;
+use quick_xml::escape::escape;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Size of the treemap, without the title and legend
+const WIDTH: f64 = 1200.0;
+const HEIGHT: f64 = 720.0;
+const TITLE_HEIGHT: f64 = 40.0;
+const LEGEND_HEIGHT: f64 = 40.0;
+
+/// Dead-code share at and above which tiles are fully red
+const FULL_DENSITY: f64 = 0.5;
+
+/// Label for declarations of files without a `package` statement
+const DEFAULT_PACKAGE: &str = "(default package)";
+
+/// Renderer of the dead-code treemap
+#[derive(Debug, Default)]
+pub struct TreemapExporter;
+
+/// Lines of code of a package, and how many of them are dead
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PackageSize {
+    name: String,
+    loc: usize,
+    dead_loc: usize,
+}
+
+impl PackageSize {
+    fn density(&self) -> f64 {
+        if self.loc == 0 {
+            0.0
+        } else {
+            (self.dead_loc as f64 / self.loc as f64).min(1.0)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+impl TreemapExporter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// SVG document of the treemap
+    pub fn render(&self, graph: &Graph, dead_code: &[DeadCode]) -> String {
+        let packages = package_sizes(graph, dead_code);
+        let loc: usize = packages.iter().map(|p| p.loc).sum();
+        let dead_loc: usize = packages.iter().map(|p| p.dead_loc).sum();
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\">",
+            w = WIDTH,
+            h = TITLE_HEIGHT + HEIGHT + LEGEND_HEIGHT
+        );
+        let _ = writeln!(
+            svg,
+            "  <text x=\"8\" y=\"26\" font-size=\"18\" font-weight=\"bold\">Dead code by package: {} of {} lines ({})</text>",
+            dead_loc,
+            loc,
+            percent(dead_loc, loc)
+        );
+
+        let sizes: Vec<f64> = packages.iter().map(|p| p.loc as f64).collect();
+        let area = Rect {
+            x: 0.0,
+            y: TITLE_HEIGHT,
+            w: WIDTH,
+            h: HEIGHT,
+        };
+        for (package, rect) in packages.iter().zip(squarify(&sizes, area)) {
+            svg.push_str("  <g>\n");
+            let _ = writeln!(
+                svg,
+                "    <title>{}: {} lines, {} dead ({})</title>",
+                escape(package.name.as_str()),
+                package.loc,
+                package.dead_loc,
+                percent(package.dead_loc, package.loc)
+            );
+            let _ = writeln!(
+                svg,
+                "    <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" stroke=\"#fff\"/>",
+                rect.x,
+                rect.y,
+                rect.w,
+                rect.h,
+                color(package.density())
+            );
+            if let Some(label) = label(&package.name, rect) {
+                let _ = writeln!(
+                    svg,
+                    "    <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\">{}</text>",
+                    rect.x + 4.0,
+                    rect.y + 15.0,
+                    escape(label.as_str())
+                );
+            }
+            svg.push_str("  </g>\n");
+        }
+
+        // Legend: the color scale from no dead code to FULL_DENSITY
+        let legend_y = TITLE_HEIGHT + HEIGHT + 12.0;
+        for step in 0..=10 {
+            let density = FULL_DENSITY * step as f64 / 10.0;
+            let _ = writeln!(
+                svg,
+                "  <rect x=\"{}\" y=\"{}\" width=\"20\" height=\"16\" fill=\"{}\"/>",
+                120 + step * 20,
+                legend_y,
+                color(density)
+            );
+        }
+        let _ = writeln!(
+            svg,
+            "  <text x=\"8\" y=\"{y}\" font-size=\"12\">Dead code share</text>\n  <text x=\"345\" y=\"{y}\" font-size=\"12\">0% to {}%+; tile size is lines of code</text>",
+            FULL_DENSITY * 100.0,
+            y = legend_y + 13.0
+        );
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Packages with their lines of code, largest first
+///
+/// A package's size is the lines of code of its top-level declarations;
+/// its dead lines are the estimated savings of its findings.
+fn package_sizes(graph: &Graph, dead_code: &[DeadCode]) -> Vec<PackageSize> {
+    let package_of = |file: &std::path::Path| {
+        graph
+            .file_scope(file)
+            .and_then(|scope| scope.package.clone())
+            .unwrap_or_else(|| DEFAULT_PACKAGE.to_string())
+    };
+
+    let mut loc: BTreeMap<String, usize> = BTreeMap::new();
+    for decl in graph.declarations() {
+        if decl.parent.is_some()
+            || matches!(
+                decl.kind,
+                DeclarationKind::Import | DeclarationKind::Package | DeclarationKind::File
+            )
+        {
+            continue;
+        }
+        *loc.entry(package_of(&decl.location.file)).or_default() += decl.metrics.loc;
+    }
+    let dead = Savings::by(dead_code, |dc| {
+        Some(package_of(&dc.declaration.location.file))
+    });
+
+    let mut packages: Vec<PackageSize> = loc
+        .into_iter()
+        .filter(|(_, loc)| *loc > 0)
+        .map(|(name, loc)| {
+            let dead_loc = dead.get(&name).map_or(0, |savings| savings.loc.min(loc));
+            PackageSize {
+                name,
+                loc,
+                dead_loc,
+            }
+        })
+        .collect();
+    packages.sort_by(|a, b| b.loc.cmp(&a.loc).then_with(|| a.name.cmp(&b.name)));
+    packages
+}
+
+/// Squarified treemap layout (Bruls, Huizing and van Wijk): tiles for
+/// `sizes`, sorted largest first, filling `area` in rows chosen to keep
+/// tiles close to square
+fn squarify(sizes: &[f64], mut area: Rect) -> Vec<Rect> {
+    let total: f64 = sizes.iter().sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    let scale = area.w * area.h / total;
+    let areas: Vec<f64> = sizes.iter().map(|size| size * scale).collect();
+
+    let mut tiles = Vec::with_capacity(areas.len());
+    let mut start = 0;
+    while start < areas.len() {
+        let side = area.w.min(area.h);
+        let mut end = start + 1;
+        while end < areas.len()
+            && worst_ratio(&areas[start..=end], side) <= worst_ratio(&areas[start..end], side)
+        {
+            end += 1;
+        }
+
+        let row = &areas[start..end];
+        let thickness = row.iter().sum::<f64>() / side;
+        let mut offset = 0.0;
+        for tile in row {
+            let length = tile / thickness;
+            tiles.push(if area.w >= area.h {
+                // Column along the left edge
+                Rect {
+                    x: area.x,
+                    y: area.y + offset,
+                    w: thickness,
+                    h: length,
+                }
+            } else {
+                // Row along the top edge
+                Rect {
+                    x: area.x + offset,
+                    y: area.y,
+                    w: length,
+                    h: thickness,
+                }
+            });
+            offset += length;
+        }
+        if area.w >= area.h {
+            area.x += thickness;
+            area.w -= thickness;
+        } else {
+            area.y += thickness;
+            area.h -= thickness;
+        }
+        start = end;
+    }
+    tiles
+}
+
+/// Largest aspect ratio of the tiles of a row laid along a side
+fn worst_ratio(row: &[f64], side: f64) -> f64 {
+    let sum: f64 = row.iter().sum();
+    row.iter()
+        .map(|&tile| {
+            let ratio = side * side * tile / (sum * sum);
+            ratio.max(1.0 / ratio)
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Green for no dead code, through yellow, to red at [`FULL_DENSITY`]
+fn color(density: f64) -> String {
+    let hue = 120.0 * (1.0 - (density / FULL_DENSITY).min(1.0));
+    format!("hsl({:.0}, 65%, 50%)", hue)
+}
+
+/// Package name shortened to fit its tile, from the right, or `None` when
+/// the tile is too small for any label
+fn label(package: &str, rect: Rect) -> Option<String> {
+    // About 7 pixels per character at font size 12
+    let fits = ((rect.w - 8.0) / 7.0) as usize;
+    if rect.h < 20.0 || fits < 4 {
+        return None;
+    }
+    let chars = package.chars().count();
+    if chars <= fits {
+        return Some(package.to_string());
+    }
+    let tail: String = package.chars().skip(chars - (fits - 1)).collect();
+    Some(format!("…{}", tail))
+}
+
+fn percent(part: usize, whole: usize) -> String {
+    if whole == 0 {
+        "0%".to_string()
+    } else {
+        format!("{:.1}%", part as f64 * 100.0 / whole as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::DeadCodeIssue;
+    use crate::discovery::{FileType, SourceFile};
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn test_squarify() {
+        let area = Rect {
+            x: 0.0,
+            y: 0.0,
+            w: 6.0,
+            h: 4.0,
+        };
+        let tiles = squarify(&[6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0], area);
+
+        assert_eq!(tiles.len(), 7);
+        for (tile, size) in tiles.iter().zip([6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0]) {
+            assert!((tile.w * tile.h - size).abs() < 1e-9, "{:?}", tile);
+            assert!(tile.x >= 0.0 && tile.x + tile.w <= 6.0 + 1e-9);
+            assert!(tile.y >= 0.0 && tile.y + tile.h <= 4.0 + 1e-9);
+        }
+        // The paper's example: the first two tiles share the first column
+        assert_eq!(tiles[0].x, tiles[1].x);
+        assert!(squarify(&[], area).is_empty());
+    }
+
+    #[test]
+    fn test_treemap_render() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let feed = temp.path().join("Feed.kt");
+        std::fs::write(
+            &feed,
+            "package com.app.feed\n\nclass FeedScreen {\n    fun show() = Unit\n}\n\nclass Legacy {\n    fun old() = Unit\n}\n",
+        )
+        .unwrap();
+        let util = temp.path().join("Util.kt");
+        std::fs::write(&util, "fun <T> helper(value: T) = value\n").unwrap();
+        let mut builder = GraphBuilder::new();
+        for file in [feed, util] {
+            builder
+                .process_file(&SourceFile::new(file, FileType::Kotlin))
+                .unwrap();
+        }
+        let graph = builder.build();
+        let legacy = graph.find_by_name("Legacy")[0].clone();
+        let dead_code = [DeadCode::new(legacy, DeadCodeIssue::Unreferenced)];
+
+        let packages = package_sizes(&graph, &dead_code);
+        assert_eq!(packages[0].name, "com.app.feed");
+        assert_eq!((packages[0].loc, packages[0].dead_loc), (6, 3));
+        assert_eq!(packages[1].name, DEFAULT_PACKAGE);
+        assert_eq!(packages[1].dead_loc, 0);
+
+        let svg = TreemapExporter::new().render(&graph, &dead_code);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<title>com.app.feed: 6 lines, 3 dead (50.0%)</title>"));
+        assert!(svg.contains("fill=\"hsl(0, 65%, 50%)\""));
+        assert!(svg.contains("<title>(default package): 1 lines, 0 dead (0.0%)</title>"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
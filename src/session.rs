@@ -0,0 +1,984 @@
+//! Embedding API for running a full analysis from library code
+//!
+//! `AnalysisSession` wraps the same pipeline the CLI runs (discovery, parsing,
+//! entry point detection, reachability, coverage/ProGuard enhancement and
+//! detectors) behind a builder, so tools embedding the crate don't have to
+//! replicate the orchestration in `main.rs`.
+//!
+//! ```no_run
+//! use searchdeadcode::{AnalysisSession, Config};
+//!
+//! let results = AnalysisSession::builder()
+//!     .path("path/to/android/project")
+//!     .config(Config::default())
+//!     .with_coverage(["build/reports/jacoco.xml"])
+//!     .run()?;
+//!
+//! for finding in &results.dead_code {
+//!     println!("{}: {}", finding.issue.code(), finding.message);
+//! }
+//! # Ok::<(), miette::Report>(())
+//! ```
+//!
+//! Embedders that want results before the whole run completes (LSP servers,
+//! daemons, CI bots) can use [`AnalysisSession::run_streaming`], which hands
+//! over each batch of findings as soon as reachability or a detector finishes.
+//! Long runs can be aborted from another thread through a
+//! [`CancellationToken`] passed to [`AnalysisSessionBuilder::cancellation`].
+
+use crate::analysis::detectors::{Detector, DetectorMetadata, DetectorRegistry, ProjectContext};
+use crate::analysis::{
+    analyze_per_module, attach_fixes, downgrade_string_referenced, mark_test_only, Confidence,
+    DeadCode, DeepAnalyzer, EnhancedAnalyzer, EntryPointDetector, HybridAnalyzer,
+    ReachabilityAnalyzer,
+};
+use crate::cancel::CancellationToken;
+use crate::config::Config;
+use crate::coverage::{parse_coverage_files, CoverageData};
+use crate::discovery::{FileFinder, SourceFile};
+use crate::graph::{
+    Declaration, DeclarationId, Graph, GraphBuilder, GraphCache, ParallelGraphBuilder,
+};
+use crate::parser::TreeCache;
+use crate::proguard::ProguardUsage;
+use miette::Result;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use tracing::{info, warn};
+
+/// Reachability strategy used by a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnalysisMode {
+    /// Plain reachability from entry points
+    Standard,
+    /// Aggressive mode that also reports unused members of reachable classes
+    #[default]
+    Deep,
+    /// Reachability cross-validated with ProGuard usage.txt (falls back to
+    /// standard when no usage file is configured)
+    Enhanced,
+}
+
+impl std::str::FromStr for AnalysisMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "standard" => Ok(AnalysisMode::Standard),
+            "deep" => Ok(AnalysisMode::Deep),
+            "enhanced" => Ok(AnalysisMode::Enhanced),
+            _ => Err(format!(
+                "unknown mode '{}' (expected standard, deep or enhanced)",
+                s
+            )),
+        }
+    }
+}
+
+/// Typed results of an analysis session
+#[derive(Debug)]
+pub struct AnalysisResults {
+    /// Findings that passed the confidence filter
+    pub dead_code: Vec<DeadCode>,
+    /// The reference graph the findings were computed from
+    pub graph: Graph,
+    /// Declarations treated as roots
+    pub entry_points: HashSet<DeclarationId>,
+    /// Declarations reachable from the entry points
+    pub reachable: HashSet<DeclarationId>,
+    /// Number of source files discovered
+    #[allow(dead_code)] // the CLI uses `files`; kept for library consumers
+    pub files_analyzed: usize,
+    /// The source files discovered
+    pub files: Vec<SourceFile>,
+    /// The ProGuard usage.txt the findings were cross-validated with, when
+    /// one was given and could be parsed
+    pub proguard: Option<ProguardUsage>,
+}
+
+/// Stage of the pipeline a batch of findings comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindingSource {
+    /// Reachability from entry points, after coverage/ProGuard enhancement
+    Reachability,
+    /// A registered detector
+    Detector(DetectorMetadata),
+}
+
+/// Findings delivered by [`AnalysisSession::run_streaming`]
+///
+/// Batches are already filtered by the session's confidence settings. A
+/// detector that found nothing still delivers an empty batch.
+#[derive(Debug, Clone)]
+pub struct FindingsBatch {
+    pub source: FindingSource,
+    pub findings: Vec<DeadCode>,
+}
+
+/// Callback told how many of the discovered files are parsed
+type ParseProgress = Box<dyn Fn(usize, usize) + Send + Sync>;
+
+/// A configured analysis run, created through [`AnalysisSession::builder`]
+pub struct AnalysisSession {
+    path: PathBuf,
+    config: Config,
+    coverage_files: Vec<PathBuf>,
+    proguard_usage: Option<PathBuf>,
+    registry: DetectorRegistry,
+    mode: AnalysisMode,
+    parallel: bool,
+    min_confidence: Confidence,
+    runtime_only: bool,
+    include_runtime_dead: bool,
+    cancel: CancellationToken,
+    graph_cache: Option<Arc<GraphCache>>,
+    tree_cache: Option<Arc<TreeCache>>,
+    parse_progress: Option<ParseProgress>,
+    base_graph: Mutex<Option<(Graph, Vec<PathBuf>)>>,
+}
+
+impl AnalysisSession {
+    /// Apply the changed files to the base graph, if one was given and the
+    /// changes can be applied in place
+    fn update_base_graph(
+        &self,
+        builder: &ParallelGraphBuilder,
+        files: &[SourceFile],
+    ) -> Result<Option<Graph>> {
+        let Some((mut graph, changed)) = self.base_graph.lock().unwrap().take() else {
+            return Ok(None);
+        };
+        // Watchers report absolute paths; discovery joins paths onto the root
+        let root = self
+            .path
+            .canonicalize()
+            .unwrap_or_else(|_| self.path.clone());
+        let changed: HashSet<PathBuf> = changed
+            .into_iter()
+            .map(|path| match path.strip_prefix(&root) {
+                Ok(relative) => self.path.join(relative),
+                Err(_) => path,
+            })
+            .collect();
+        let updated: Vec<SourceFile> = files
+            .iter()
+            .filter(|file| changed.contains(&file.path))
+            .cloned()
+            .collect();
+        let removed: Vec<PathBuf> = changed
+            .into_iter()
+            .filter(|path| !files.iter().any(|file| &file.path == path))
+            .collect();
+        Ok(builder
+            .update_graph(&mut graph, &updated, &removed)?
+            .then_some(graph))
+    }
+
+    /// Start building a session
+    pub fn builder() -> AnalysisSessionBuilder {
+        AnalysisSessionBuilder::new()
+    }
+
+    /// The project root this session analyzes
+    #[allow(dead_code)] // accessors only library consumers call
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The configuration this session analyzes with
+    #[allow(dead_code)]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Detectors available to this session; `config.detection` picks which run
+    #[allow(dead_code)]
+    pub fn registry(&self) -> &DetectorRegistry {
+        &self.registry
+    }
+
+    /// Run the full analysis pipeline
+    pub fn run(&self) -> Result<AnalysisResults> {
+        self.run_streaming(|_| {})
+    }
+
+    /// Run the full analysis pipeline, handing each batch of findings to
+    /// `on_batch` as soon as it is ready
+    ///
+    /// The reachability batch comes first, then one batch per enabled detector
+    /// in completion order. `on_batch` is always called on the calling thread.
+    /// The returned results hold the same findings in a stable order, less
+    /// the reachability findings a detector reports more precisely (unused
+    /// Dagger bindings, see
+    /// [`ProjectDetector::replaces_reachability`](crate::analysis::detectors::ProjectDetector::replaces_reachability)).
+    ///
+    /// ```no_run
+    /// use searchdeadcode::AnalysisSession;
+    ///
+    /// let session = AnalysisSession::builder().path("app").build()?;
+    /// session.run_streaming(|batch| {
+    ///     for finding in &batch.findings {
+    ///         println!("{} {}", finding.issue.code(), finding.declaration.name);
+    ///     }
+    /// })?;
+    /// # Ok::<(), miette::Report>(())
+    /// ```
+    pub fn run_streaming<F>(&self, mut on_batch: F) -> Result<AnalysisResults>
+    where
+        F: FnMut(&FindingsBatch),
+    {
+        let cancel = &self.cancel;
+        let files = FileFinder::new(&self.config)
+            .with_cancellation(cancel.clone())
+            .find_files(&self.path)?;
+        info!("Session: found {} files to analyze", files.len());
+
+        if let Some(trees) = &self.tree_cache {
+            trees.retain(|p| files.iter().any(|f| f.path == p));
+        }
+
+        if files.is_empty() {
+            return Ok(AnalysisResults {
+                dead_code: Vec::new(),
+                graph: Graph::new(),
+                entry_points: HashSet::new(),
+                reachable: HashSet::new(),
+                files_analyzed: 0,
+                files,
+                proguard: None,
+            });
+        }
+
+        // Auxiliary inputs are independent of the sources; parse them alongside
+        let parallel = self.parallel;
+        let proguard_path = self.proguard_usage.as_deref();
+        let coverage_files = self.coverage_files.as_slice();
+        let progress = |parsed| {
+            if let Some(progress) = &self.parse_progress {
+                progress(parsed, files.len());
+            }
+        };
+        let (graph, (proguard_input, coverage_input)) = rayon::join(
+            || -> Result<Graph> {
+                progress(0);
+                if parallel {
+                    let mut builder = ParallelGraphBuilder::new().with_cancellation(cancel.clone());
+                    if let Some(trees) = &self.tree_cache {
+                        builder = builder.with_tree_cache(trees.clone());
+                    }
+                    if let Some(cache) = &self.graph_cache {
+                        builder = builder.with_graph_cache(cache.clone());
+                    }
+                    if let Some(graph) = self.update_base_graph(&builder, &files)? {
+                        progress(files.len());
+                        return Ok(graph);
+                    }
+                    let graph = builder.build_from_files(&files)?;
+                    progress(files.len());
+                    Ok(graph)
+                } else {
+                    let mut builder = GraphBuilder::new();
+                    if let Some(trees) = &self.tree_cache {
+                        builder = builder.with_tree_cache(trees.clone());
+                    }
+                    if let Some(cache) = &self.graph_cache {
+                        builder = builder.with_graph_cache(cache.clone());
+                    }
+                    for (parsed, file) in files.iter().enumerate() {
+                        cancel.check()?;
+                        builder.process_file(file)?;
+                        progress(parsed + 1);
+                    }
+                    Ok(builder.build())
+                }
+            },
+            || {
+                rayon::join(
+                    || proguard_path.map(ProguardUsage::parse),
+                    || (!coverage_files.is_empty()).then(|| parse_coverage_files(coverage_files)),
+                )
+            },
+        );
+        let graph = graph?;
+        let proguard = load_optional(proguard_input, "ProGuard usage.txt");
+        if let Some(proguard) = &proguard {
+            info!("ProGuard usage: {}", proguard.stats());
+        }
+        let coverage: Option<CoverageData> = load_optional(coverage_input, "coverage");
+        if let Some(coverage) = &coverage {
+            let stats = coverage.stats();
+            info!(
+                "Coverage: {} files, {} classes ({:.1}% covered), {} methods ({:.1}% covered)",
+                stats.total_files,
+                stats.total_classes,
+                stats.class_coverage_percent(),
+                stats.total_methods,
+                stats.method_coverage_percent()
+            );
+        }
+
+        let entry_detector = EntryPointDetector::new(&self.config)
+            .with_composable_entry_points(self.mode != AnalysisMode::Deep)
+            .with_cancellation(cancel.clone());
+        let entry_points = entry_detector.detect(&graph, &self.path)?;
+        info!("Found {} entry points", entry_points.len());
+
+        let run_reachability = |graph: &Graph, entry_points: &HashSet<DeclarationId>| {
+            let retain_annotations = self.config.retain_annotations.clone();
+            match self.mode {
+                AnalysisMode::Deep => DeepAnalyzer::new()
+                    .with_parallel(self.parallel)
+                    .with_unused_members(true)
+                    .with_retain_annotations(retain_annotations)
+                    .with_cancellation(cancel.clone())
+                    .analyze(graph, entry_points),
+                AnalysisMode::Enhanced if proguard.is_some() => {
+                    let mut analyzer =
+                        EnhancedAnalyzer::new().with_retain_annotations(retain_annotations);
+                    if let Some(pg) = proguard.clone() {
+                        analyzer = analyzer.with_proguard(pg);
+                    }
+                    analyzer.analyze(graph, entry_points)
+                }
+                // The enhanced analyzer without ProGuard data is the parallel
+                // form of plain reachability
+                AnalysisMode::Standard | AnalysisMode::Enhanced if self.parallel => {
+                    EnhancedAnalyzer::new()
+                        .with_retain_annotations(retain_annotations)
+                        .analyze(graph, entry_points)
+                }
+                AnalysisMode::Standard | AnalysisMode::Enhanced => ReachabilityAnalyzer::new()
+                    .with_retain_annotations(retain_annotations)
+                    .with_cancellation(cancel.clone())
+                    .find_unreachable_with_reachable(graph, entry_points),
+            }
+        };
+        let (mut dead_code, reachable) = run_reachability(&graph, &entry_points);
+        if self.config.report_test_only {
+            let mut with_tests = entry_points.clone();
+            with_tests.extend(entry_detector.test_entry_points(&graph));
+            let (dead_with_tests, _) = run_reachability(&graph, &with_tests);
+            mark_test_only(&mut dead_code, &dead_with_tests);
+        }
+        if self.config.per_module {
+            analyze_per_module(&graph, &entry_points, &mut dead_code, run_reachability);
+        }
+        info!(
+            "Reachability: {} reachable, {} total",
+            reachable.len(),
+            graph.declarations().count()
+        );
+
+        cancel.check()?;
+
+        let mut hybrid = HybridAnalyzer::new();
+        if let Some(coverage) = coverage {
+            hybrid = hybrid.with_coverage(coverage);
+        }
+        if let Some(proguard) = proguard.clone() {
+            hybrid = hybrid.with_proguard(proguard);
+        }
+        let mut dead_code = hybrid.enhance_findings(dead_code);
+        if self.include_runtime_dead {
+            let runtime_dead = hybrid.find_runtime_dead_code(&graph, &reachable);
+            if !runtime_dead.is_empty() {
+                info!(
+                    "Found {} additional runtime-dead code items",
+                    runtime_dead.len()
+                );
+                dead_code.extend(runtime_dead);
+            }
+        }
+        let reachability = FindingsBatch {
+            source: FindingSource::Reachability,
+            findings: self.keep(&graph, dead_code),
+        };
+        on_batch(&reachability);
+        let mut dead_code = reachability.findings;
+
+        // Detectors run on the rayon pool; batches are funneled back here so
+        // the callback needs neither `Send` nor `Sync`
+        let (sender, receiver) = mpsc::channel();
+        let mut batches = Vec::new();
+        let detectors = std::thread::scope(|scope| {
+            let graph = &graph;
+            let project = ProjectContext {
+                root: &self.path,
+                files: &files,
+                graph,
+            };
+            let detectors = scope.spawn(move || {
+                self.registry.run_each_cancellable(
+                    &project,
+                    &self.config.detection,
+                    cancel,
+                    |metadata, findings| {
+                        let _ = sender.send(FindingsBatch {
+                            source: FindingSource::Detector(metadata),
+                            findings: self.keep(graph, findings),
+                        });
+                    },
+                )
+            });
+
+            for batch in receiver {
+                on_batch(&batch);
+                batches.push(batch);
+            }
+            detectors.join()
+        });
+        match detectors {
+            Ok(result) => result?,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+
+        // Registration order, so results don't depend on scheduling
+        let order: Vec<&str> = self.registry.metadata().map(|m| m.code).collect();
+        batches.sort_by_key(|batch| match batch.source {
+            FindingSource::Detector(metadata) => order.iter().position(|c| *c == metadata.code),
+            FindingSource::Reachability => None,
+        });
+        let replacing: Vec<&Declaration> = batches
+            .iter()
+            .filter(|batch| match batch.source {
+                FindingSource::Detector(metadata) => {
+                    self.registry.replaces_reachability(metadata.code)
+                }
+                FindingSource::Reachability => false,
+            })
+            .flat_map(|batch| batch.findings.iter().map(|dc| &dc.declaration))
+            .collect();
+        dead_code.retain(|dc| {
+            !replacing.iter().any(|decl| {
+                decl.name == dc.declaration.name
+                    && decl.location.file == dc.declaration.location.file
+            })
+        });
+        dead_code.extend(batches.into_iter().flat_map(|batch| batch.findings));
+
+        Ok(AnalysisResults {
+            dead_code,
+            graph,
+            entry_points,
+            reachable,
+            files_analyzed: files.len(),
+            files,
+            proguard,
+        })
+    }
+
+    /// Apply the confidence and runtime-only filters, after lowering
+    /// findings on declarations named in strings, and attach quick fixes
+    ///
+    /// Every batch goes through this; embedders running their own analyses
+    /// on the results can filter their findings the same way.
+    pub fn keep(&self, graph: &Graph, mut findings: Vec<DeadCode>) -> Vec<DeadCode> {
+        downgrade_string_referenced(graph, &mut findings);
+        let mut findings: Vec<_> = findings
+            .into_iter()
+            .filter(|dc| dc.confidence >= self.min_confidence)
+            .filter(|dc| !self.runtime_only || dc.runtime_confirmed)
+            .collect();
+        attach_fixes(&mut findings);
+        findings
+    }
+}
+
+/// Unwrap an optional input, downgrading parse failures to a warning like the CLI does
+fn load_optional<T>(input: Option<Result<T>>, what: &str) -> Option<T> {
+    match input? {
+        Ok(data) => Some(data),
+        Err(e) => {
+            warn!("Failed to load {}: {}", what, e);
+            None
+        }
+    }
+}
+
+/// Builder for [`AnalysisSession`]
+pub struct AnalysisSessionBuilder {
+    path: PathBuf,
+    config: Option<Config>,
+    coverage_files: Vec<PathBuf>,
+    proguard_usage: Option<PathBuf>,
+    registry: Option<DetectorRegistry>,
+    extra_detectors: Vec<Box<dyn Detector>>,
+    mode: AnalysisMode,
+    parallel: bool,
+    min_confidence: Confidence,
+    runtime_only: bool,
+    include_runtime_dead: bool,
+    cancel: CancellationToken,
+    graph_cache: Option<Arc<GraphCache>>,
+    tree_cache: Option<Arc<TreeCache>>,
+    parse_progress: Option<ParseProgress>,
+    base_graph: Option<(Graph, Vec<PathBuf>)>,
+}
+
+impl AnalysisSessionBuilder {
+    pub fn new() -> Self {
+        Self {
+            path: PathBuf::from("."),
+            config: None,
+            coverage_files: Vec::new(),
+            proguard_usage: None,
+            registry: None,
+            extra_detectors: Vec::new(),
+            mode: AnalysisMode::default(),
+            parallel: true,
+            min_confidence: Confidence::Medium,
+            runtime_only: false,
+            include_runtime_dead: false,
+            cancel: CancellationToken::new(),
+            graph_cache: None,
+            tree_cache: None,
+            parse_progress: None,
+            base_graph: None,
+        }
+    }
+
+    /// Project root to analyze (defaults to the current directory)
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Configuration to use (defaults to the project's config file, if any)
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Coverage files (JaCoCo XML, Kover XML or LCOV) to merge into the analysis
+    pub fn with_coverage<I, P>(mut self, files: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.coverage_files
+            .extend(files.into_iter().map(Into::into));
+        self
+    }
+
+    /// ProGuard/R8 usage.txt for cross-validation
+    pub fn with_proguard_usage(mut self, path: impl Into<PathBuf>) -> Self {
+        self.proguard_usage = Some(path.into());
+        self
+    }
+
+    /// Replace the built-in detectors with the given detectors
+    #[allow(dead_code)] // the CLI passes a prepared registry instead
+    pub fn detectors<I>(mut self, detectors: I) -> Self
+    where
+        I: IntoIterator<Item = Box<dyn Detector>>,
+    {
+        self.registry = Some(DetectorRegistry::new());
+        self.extra_detectors = detectors.into_iter().collect();
+        self
+    }
+
+    /// Register a detector on top of the current set
+    #[allow(dead_code)]
+    pub fn detector(mut self, detector: Box<dyn Detector>) -> Self {
+        self.extra_detectors.push(detector);
+        self
+    }
+
+    /// Use a prepared registry instead of the built-in detectors
+    pub fn registry(mut self, registry: DetectorRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Reachability strategy (defaults to [`AnalysisMode::Deep`])
+    pub fn mode(mut self, mode: AnalysisMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Parse and analyze in parallel (enabled by default)
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Minimum confidence of reported findings (defaults to medium)
+    pub fn min_confidence(mut self, confidence: Confidence) -> Self {
+        self.min_confidence = confidence;
+        self
+    }
+
+    /// Only keep findings confirmed by runtime coverage
+    pub fn runtime_only(mut self, runtime_only: bool) -> Self {
+        self.runtime_only = runtime_only;
+        self
+    }
+
+    /// Also report reachable code that coverage shows is never executed
+    pub fn include_runtime_dead(mut self, include: bool) -> Self {
+        self.include_runtime_dead = include;
+        self
+    }
+
+    /// Reuse the files an earlier run parsed, and record the ones parsed now
+    pub fn graph_cache(mut self, cache: Arc<GraphCache>) -> Self {
+        self.graph_cache = Some(cache);
+        self
+    }
+
+    /// Keep syntax trees between runs of sessions sharing the cache, so
+    /// changed files are re-parsed incrementally
+    pub fn tree_cache(mut self, trees: Arc<TreeCache>) -> Self {
+        self.tree_cache = Some(trees);
+        self
+    }
+
+    /// Start from the graph of an earlier run instead of building one,
+    /// re-parsing only the files changed or deleted since
+    ///
+    /// Used by the first run only, and only when parsing in parallel. The
+    /// graph is built from scratch when the changes can't be applied in place
+    /// (see [`ParallelGraphBuilder::update_graph`]).
+    pub fn base_graph(mut self, graph: Graph, changed: Vec<PathBuf>) -> Self {
+        self.base_graph = Some((graph, changed));
+        self
+    }
+
+    /// Called with the number of parsed files and the number of discovered
+    /// ones: before parsing starts, then after each file when parsing
+    /// sequentially or once all are parsed in parallel
+    pub fn on_parse_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(usize, usize) + Send + Sync + 'static,
+    {
+        self.parse_progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Token that aborts the run when cancelled or timed out
+    ///
+    /// The run then fails with [`Cancelled`](crate::cancel::Cancelled).
+    pub fn cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Finish building the session
+    ///
+    /// Fails if the configuration or a plugin can't be loaded, or two detectors
+    /// share a rule code.
+    pub fn build(self) -> Result<AnalysisSession> {
+        let mut config = match self.config {
+            Some(config) => config,
+            None => Config::from_default_locations(&self.path)?,
+        };
+        if config.respect_keep_rules {
+            config.add_keep_rule_patterns(&self.path);
+        }
+
+        let mut registry = self
+            .registry
+            .unwrap_or_else(DetectorRegistry::with_builtins);
+        for detector in self.extra_detectors {
+            registry.register(detector)?;
+        }
+        let plugins: Vec<PathBuf> = config.plugins.iter().map(|p| self.path.join(p)).collect();
+        registry.load_plugins(&plugins)?;
+
+        Ok(AnalysisSession {
+            path: self.path,
+            config,
+            coverage_files: self.coverage_files,
+            proguard_usage: self.proguard_usage,
+            registry,
+            mode: self.mode,
+            parallel: self.parallel,
+            min_confidence: self.min_confidence,
+            runtime_only: self.runtime_only,
+            include_runtime_dead: self.include_runtime_dead,
+            cancel: self.cancel,
+            graph_cache: self.graph_cache,
+            tree_cache: self.tree_cache,
+            parse_progress: self.parse_progress,
+            base_graph: Mutex::new(self.base_graph),
+        })
+    }
+
+    /// Build the session and run it
+    pub fn run(self) -> Result<AnalysisResults> {
+        self.build()?.run()
+    }
+}
+
+impl Default for AnalysisSessionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{ModuleScope, Severity};
+    use std::fs;
+
+    #[test]
+    fn test_session_on_empty_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let results = AnalysisSession::builder()
+            .path(dir.path())
+            .config(Config::default())
+            .run()
+            .unwrap();
+
+        assert_eq!(results.files_analyzed, 0);
+        assert!(results.dead_code.is_empty());
+    }
+
+    #[test]
+    fn test_session_reports_unused_class() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Main.kt"),
+            "fun main() { Used().run() }\nclass Used { fun run() {} }\nclass Unused\n",
+        )
+        .unwrap();
+
+        let results = AnalysisSession::builder()
+            .path(dir.path())
+            .config(Config::default())
+            .detectors(Vec::new())
+            .run()
+            .unwrap();
+
+        assert_eq!(results.files_analyzed, 1);
+        let names: Vec<_> = results
+            .dead_code
+            .iter()
+            .map(|dc| dc.declaration.name.as_str())
+            .collect();
+        assert!(names.contains(&"Unused"));
+        assert!(!names.contains(&"Used"));
+    }
+
+    #[test]
+    fn test_streaming_delivers_reachability_then_detectors() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Main.kt"),
+            "fun main() { Used().run(1) }\nclass Used { fun run(unused: Int) {} }\nclass Unused\n",
+        )
+        .unwrap();
+
+        let session = AnalysisSession::builder()
+            .path(dir.path())
+            .config(Config::default())
+            .build()
+            .unwrap();
+
+        let mut sources = Vec::new();
+        let mut streamed = 0;
+        let results = session
+            .run_streaming(|batch| {
+                sources.push(batch.source);
+                streamed += batch.findings.len();
+            })
+            .unwrap();
+
+        assert_eq!(sources.first(), Some(&FindingSource::Reachability));
+        // One batch per default detector (DC002, DC003, DC005, DC008, DC017-DC021, DC023,
+        // DC024, DC028, DC029, DC031, DC032, DC033, DC034, DC035, DC036, DC037)
+        assert_eq!(sources.len(), 21);
+        assert_eq!(streamed, results.dead_code.len());
+    }
+
+    #[test]
+    fn test_base_graph_matches_a_full_build() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("Main.kt");
+        fs::write(&main, "fun main() { Used().run() }\n").unwrap();
+        fs::write(
+            dir.path().join("Used.kt"),
+            "class Used { fun run() {} }\nclass Unused\n",
+        )
+        .unwrap();
+        let run = |base: Option<(Graph, Vec<PathBuf>)>| {
+            let mut session = AnalysisSession::builder()
+                .path(dir.path())
+                .config(Config::default())
+                .detectors(Vec::new());
+            if let Some((graph, changed)) = base {
+                session = session.base_graph(graph, changed);
+            }
+            let results = session.run().unwrap();
+            let mut names: Vec<String> = results
+                .dead_code
+                .iter()
+                .map(|dc| dc.declaration.name.to_string())
+                .collect();
+            names.sort();
+            (names, results.graph)
+        };
+        let (_, graph) = run(None);
+
+        // Shifts the declarations `main` refers to, and stops using `run`
+        fs::write(&main, "// entry point\n\nfun main() { Used() }\n").unwrap();
+        let (incremental, graph) = run(Some((graph, vec![main.clone()])));
+        assert_eq!(incremental, run(None).0);
+        assert!(incremental.contains(&"run".to_string()));
+        assert!(!incremental.contains(&"Used".to_string()));
+
+        // A new declaration needs a full build, which the session falls back to
+        fs::write(&main, "fun main() { Used().run() }\nclass Added\n").unwrap();
+        let (incremental, _) = run(Some((graph, vec![main.clone()])));
+        assert_eq!(incremental, run(None).0);
+        assert!(incremental.contains(&"Added".to_string()));
+    }
+
+    #[test]
+    fn test_session_rejects_duplicate_rule_codes() {
+        let result = AnalysisSession::builder()
+            .config(Config::default())
+            .detector(Box::new(
+                crate::analysis::detectors::UnusedParamDetector::new(),
+            ))
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cancelled_session_fails_with_cancelled() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("Main.kt"), "fun main() {}\n").unwrap();
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = AnalysisSession::builder()
+            .path(dir.path())
+            .config(Config::default())
+            .cancellation(cancel)
+            .run()
+            .unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<crate::cancel::Cancelled>(),
+            Some(&crate::cancel::Cancelled::Requested)
+        );
+    }
+
+    #[test]
+    fn test_test_sources_as_roots_and_test_only_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("src/main/kotlin");
+        let test = dir.path().join("src/test/kotlin");
+        fs::create_dir_all(&main).unwrap();
+        fs::create_dir_all(&test).unwrap();
+        fs::write(
+            main.join("Main.kt"),
+            "fun main() { Used().run() }\nclass Used { fun run() {} }\nclass Fixture\nclass Unused\n",
+        )
+        .unwrap();
+        fs::write(
+            test.join("UsedTest.kt"),
+            "class UsedTest {\n    @Test\n    fun runs() { Fixture() }\n}\n",
+        )
+        .unwrap();
+
+        let findings = |config: Config| -> Vec<(String, &'static str)> {
+            let results = AnalysisSession::builder()
+                .path(dir.path())
+                .config(config)
+                .detectors(Vec::new())
+                .run()
+                .unwrap();
+            let mut findings: Vec<_> = results
+                .dead_code
+                .iter()
+                .map(|dc| (dc.declaration.name.to_string(), dc.issue.code()))
+                .collect();
+            findings.sort();
+            findings
+        };
+
+        let unused = |name: &str| (name.to_string(), "DC001");
+        assert_eq!(
+            findings(Config::default()),
+            [unused("Fixture"), unused("Unused")]
+        );
+        assert_eq!(
+            findings(Config {
+                report_test_only: true,
+                ..Config::default()
+            }),
+            [("Fixture".to_string(), "DC030"), unused("Unused")]
+        );
+        assert_eq!(
+            findings(Config {
+                tests_as_roots: true,
+                ..Config::default()
+            }),
+            [unused("Unused")]
+        );
+    }
+
+    #[test]
+    fn test_per_module_reports_code_only_other_modules_use() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app/src/main/kotlin");
+        let feature = dir.path().join("feature/src/main/kotlin");
+        fs::create_dir_all(&app).unwrap();
+        fs::create_dir_all(&feature).unwrap();
+        fs::write(
+            app.join("App.kt"),
+            "package com.app\n\nimport com.feature.Helper\n\nfun main() { Helper().help() }\n",
+        )
+        .unwrap();
+        fs::write(
+            feature.join("Feature.kt"),
+            "package com.feature\n\nfun main() { Local() }\nclass Local\nclass Helper { fun help() {} }\nclass Unused\n",
+        )
+        .unwrap();
+
+        let results = AnalysisSession::builder()
+            .path(dir.path())
+            .config(Config {
+                per_module: true,
+                ..Config::default()
+            })
+            .detectors(Vec::new())
+            .run()
+            .unwrap();
+        let mut findings: Vec<_> = results
+            .dead_code
+            .iter()
+            .map(|dc| {
+                (
+                    dc.declaration.name.as_str(),
+                    dc.module.as_deref(),
+                    dc.scope,
+                    dc.severity,
+                )
+            })
+            .collect();
+        findings.sort_by_key(|finding| finding.0);
+        assert_eq!(
+            findings,
+            [
+                (
+                    "Helper",
+                    Some(":feature"),
+                    Some(ModuleScope::OwnModule),
+                    Severity::Info
+                ),
+                (
+                    "Unused",
+                    Some(":feature"),
+                    Some(ModuleScope::AllModules),
+                    Severity::Warning
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//! Helpers shared by unit tests
+
+use std::path::Path;
+
+/// Write `contents` to `root/rel`, creating its parent directories
+pub fn write(root: &Path, rel: &str, contents: &(impl AsRef<[u8]> + ?Sized)) {
+    let path = root.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents.as_ref()).unwrap();
+}
@@ -8,7 +8,7 @@
 use colored::Colorize;
 use notify::RecursiveMode;
 use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::channel;
 use std::time::Duration;
 use thiserror::Error;
@@ -73,9 +73,12 @@ impl FileWatcher {
     }
 
     /// Start watching a directory and call the callback on changes
+    ///
+    /// The callback gets the changed (or deleted) files, none for the initial
+    /// run.
     pub fn watch<F>(&self, path: &Path, mut on_change: F) -> Result<(), WatchError>
     where
-        F: FnMut() -> bool, // Returns false to stop watching
+        F: FnMut(&[PathBuf]) -> bool, // Returns false to stop watching
     {
         let (tx, rx) = channel();
 
@@ -94,7 +97,7 @@ impl FileWatcher {
         println!();
 
         // Run initial analysis
-        if !on_change() {
+        if !on_change(&[]) {
             return Ok(());
         }
 
@@ -137,7 +140,9 @@ impl FileWatcher {
                                 }
                                 println!();
 
-                                if !on_change() {
+                                let changed: Vec<PathBuf> =
+                                    relevant.iter().map(|e| e.path.clone()).collect();
+                                if !on_change(&changed) {
                                     break;
                                 }
                             }
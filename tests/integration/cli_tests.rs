@@ -351,6 +351,15 @@ fn test_cli_nonexistent_path() {
     );
 }
 
+#[test]
+fn test_cli_timeout_aborts_analysis() {
+    let (stdout, stderr, success) = run_cli(&[fixtures_path().to_str().unwrap(), "--timeout", "0"]);
+
+    let combined = format!("{}{}", stdout, stderr);
+    assert!(!success, "Expired timeout should fail the run");
+    assert!(combined.contains("timed out"), "Output: {}", combined);
+}
+
 #[test]
 fn test_cli_empty_directory() {
     use tempfile::tempdir;
@@ -385,3 +394,563 @@ fn test_cli_single_file() {
 
     assert!(success, "Should analyze single file successfully");
 }
+
+#[test]
+fn test_cli_trace() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    let source = temp.path().join("src/main/java/com/app/App.kt");
+    std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+    std::fs::write(
+        &source,
+        r#"package com.app
+
+class MainActivity : AppCompatActivity() {
+    fun onCreate() {
+        Repository().load()
+    }
+}
+
+class Repository {
+    fun load() = Unit
+}
+
+class Orphan
+"#,
+    )
+    .unwrap();
+    let root = temp.path().to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&["-q", root, "trace", "Repository.load"]);
+    assert!(success, "Trace should succeed: {}", stderr);
+    assert!(
+        stdout.contains("Reachable from an entry point"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("class 'MainActivity'"), "{}", stdout);
+    assert!(stdout.contains("calls"), "{}", stdout);
+
+    let (stdout, _, success) = run_cli(&["-q", root, "trace", "com.app.Orphan"]);
+    assert!(success);
+    assert!(stdout.contains("Not reachable"), "{}", stdout);
+    assert!(stdout.contains("DC001"), "{}", stdout);
+
+    let (_, stderr, success) = run_cli(&["-q", root, "trace", "Missing"]);
+    assert!(!success, "Unknown symbols should fail");
+    assert!(
+        stderr.contains("No declaration named 'Missing'"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_cli_export_graph() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    let source = temp.path().join("src/main/java/com/app/App.kt");
+    std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+    std::fs::write(
+        &source,
+        r#"package com.app
+
+class MainActivity : AppCompatActivity() {
+    fun onCreate() = Unit
+}
+
+class Orphan
+"#,
+    )
+    .unwrap();
+    let root = temp.path().to_str().unwrap();
+    let output = temp.path().join("graph.dot");
+
+    let (stdout, stderr, success) = run_cli(&[
+        "-q",
+        root,
+        "--export-graph",
+        "dot",
+        "--graph-dead-only",
+        "--graph-output",
+        output.to_str().unwrap(),
+    ]);
+    assert!(success, "Export should succeed: {}", stderr);
+    assert!(stdout.contains("Graph exported"), "{}", stdout);
+
+    let dot = std::fs::read_to_string(&output).expect("Graph should be written");
+    assert!(dot.starts_with("digraph references {"), "{}", dot);
+    assert!(dot.contains("Orphan"), "{}", dot);
+    assert!(!dot.contains("MainActivity"), "{}", dot);
+
+    let (stdout, stderr, success) = run_cli(&["-q", root, "--export-graph", "json"]);
+    assert!(success, "Export should succeed: {}", stderr);
+    let start = stdout.find('{').expect("JSON graph on stdout");
+    let graph: serde_json::Value = serde_json::Deserializer::from_str(&stdout[start..])
+        .into_iter()
+        .next()
+        .unwrap()
+        .expect("Valid JSON graph");
+    let declarations = graph["declarations"].as_array().unwrap();
+    assert!(declarations.iter().any(|d| d["name"] == "Orphan"
+        && d["dead"] == true
+        && d["file"] == "src/main/java/com/app/App.kt"));
+}
+
+#[test]
+fn test_cli_export_db() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    let source = temp.path().join("src/main/java/com/app/App.kt");
+    std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+    std::fs::write(
+        &source,
+        "package com.app\n\nclass MainActivity : AppCompatActivity()\n\nclass Orphan\n",
+    )
+    .unwrap();
+    let root = temp.path().to_str().unwrap();
+    let db = temp.path().join("findings.sqlite");
+
+    let (stdout, stderr, success) = run_cli(&["-q", root, "--export-db", db.to_str().unwrap()]);
+    if cfg!(feature = "sqlite") {
+        assert!(success, "Export should succeed: {}", stderr);
+        assert!(stdout.contains("Findings exported"), "{}", stdout);
+        assert!(db.exists());
+    } else {
+        assert!(!success, "Export needs the sqlite feature");
+        assert!(stderr.contains("built without the"), "{}", stderr);
+    }
+}
+
+#[test]
+fn test_cli_markdown_output() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    let source = temp.path().join("src/main/java/com/app/App.kt");
+    std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+    std::fs::write(
+        &source,
+        "package com.app\n\nclass MainActivity : AppCompatActivity()\n\nclass Orphan\n",
+    )
+    .unwrap();
+    let root = temp.path().to_str().unwrap();
+    let output = temp.path().join("report.md");
+
+    let (stdout, stderr, success) = run_cli(&[
+        "-q",
+        root,
+        "--format",
+        "markdown",
+        "--output",
+        output.to_str().unwrap(),
+    ]);
+    assert!(success, "Analysis should succeed: {}", stderr);
+    assert!(stdout.contains("Markdown report written"), "{}", stdout);
+
+    let markdown = std::fs::read_to_string(&output).expect("Report should be written");
+    assert!(
+        markdown.starts_with("## SearchDeadCode report"),
+        "{}",
+        markdown
+    );
+    assert!(markdown.contains("| `DC001` |"), "{}", markdown);
+    assert!(
+        markdown.contains("<summary><code>src/main/java/com/app/App.kt</code> (1)</summary>"),
+        "{}",
+        markdown
+    );
+    assert!(markdown.contains("Orphan"), "{}", markdown);
+}
+
+#[test]
+fn test_cli_rdjson_output() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    let source = temp.path().join("src/main/java/com/app/App.kt");
+    std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+    std::fs::write(
+        &source,
+        "package com.app\n\nclass MainActivity : AppCompatActivity()\n\nclass Orphan\n",
+    )
+    .unwrap();
+    let root = temp.path().to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&["-q", root, "--format", "rdjson"]);
+    assert!(success, "Analysis should succeed: {}", stderr);
+    let start = stdout.find('{').expect("rdjson on stdout");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).expect("Valid JSON");
+    assert_eq!(report["source"]["name"], "searchdeadcode");
+    let diagnostics = report["diagnostics"].as_array().unwrap();
+    let orphan = diagnostics
+        .iter()
+        .find(|d| d["message"].as_str().unwrap().contains("Orphan"))
+        .expect("Orphan is reported");
+    assert_eq!(orphan["code"]["value"], "DC001");
+    assert_eq!(orphan["severity"], "WARNING");
+    assert_eq!(orphan["location"]["range"]["start"]["line"], 5);
+    assert!(orphan["location"]["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("App.kt"));
+}
+
+#[test]
+fn test_cli_diff() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    let source = temp.path().join("src/main/java/com/app/App.kt");
+    std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+    let root = temp.path().to_str().unwrap();
+    let analyze = |contents: &str, report: &str| {
+        std::fs::write(&source, contents).unwrap();
+        let output = temp.path().join(report);
+        let (_, stderr, success) = run_cli(&[
+            "-q",
+            root,
+            "--format",
+            "json",
+            "--output",
+            output.to_str().unwrap(),
+        ]);
+        assert!(success, "Analysis should succeed: {}", stderr);
+        output
+    };
+    let old = analyze(
+        "package com.app\n\nclass MainActivity : AppCompatActivity()\n\nclass Orphan\n\nclass Legacy\n",
+        "old.json",
+    );
+    let new = analyze(
+        "package com.app\n\nclass MainActivity : AppCompatActivity()\n\n// moved\nclass Orphan\n\nclass Unused\n",
+        "new.json",
+    );
+    let (old, new) = (old.to_str().unwrap(), new.to_str().unwrap());
+
+    let (stdout, stderr, success) = run_cli(&["diff", old, new]);
+    assert!(success, "Diff should succeed: {}", stderr);
+    assert!(
+        stdout.contains("1 new, 1 fixed, 1 persisting"),
+        "{}",
+        stdout
+    );
+
+    let (stdout, _, success) = run_cli(&["--format", "json", "diff", old, new]);
+    assert!(success);
+    let diff: serde_json::Value = serde_json::from_str(&stdout).expect("Valid JSON diff");
+    assert_eq!(diff["new"][0]["declaration"]["name"], "Unused");
+    assert_eq!(diff["fixed"][0]["declaration"]["name"], "Legacy");
+    assert_eq!(
+        diff["persisting"][0]["file"],
+        "src/main/java/com/app/App.kt"
+    );
+
+    let (_, stderr, success) = run_cli(&["diff", old, new, "--fail-on-new"]);
+    assert!(!success, "New findings should fail the diff");
+    assert!(stderr.contains("1 new findings"), "{}", stderr);
+    let (_, _, success) = run_cli(&["diff", new, new, "--fail-on-new"]);
+    assert!(success);
+}
+
+#[test]
+fn test_cli_owners() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    let source = temp
+        .path()
+        .join("feature/payments/src/main/java/com/app/Checkout.kt");
+    std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+    std::fs::write(&source, "package com.app\n\nclass Checkout\n").unwrap();
+    std::fs::create_dir_all(temp.path().join(".github")).unwrap();
+    std::fs::write(
+        temp.path().join(".github/CODEOWNERS"),
+        "*  @org/android\n/feature/payments/  @org/payments\n",
+    )
+    .unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(temp.path())
+            .args([
+                "-c",
+                "user.name=Alice",
+                "-c",
+                "user.email=alice@example.com",
+            ])
+            .args(args)
+            .output()
+            .expect("Failed to run git")
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "Add checkout"]);
+
+    let root = temp.path().to_str().unwrap();
+    let (stdout, stderr, success) =
+        run_cli(&["-q", root, "--owners", "--blame", "--format", "json"]);
+    assert!(success, "Analysis should succeed: {}", stderr);
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect("Valid JSON report");
+    let issue = &report["issues"][0];
+    assert_eq!(issue["declaration"]["name"], "Checkout");
+    assert_eq!(issue["owners"][0], "@org/payments");
+    assert_eq!(issue["last_author"], "Alice");
+
+    let (stdout, _, success) = run_cli(&["-q", root, "--owners", "--format", "csv"]);
+    assert!(success);
+    assert!(
+        stdout.contains("feature/payments/src/main/java/com/app/Checkout.kt,3,"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains(",@org/payments,,"), "{}", stdout);
+
+    let (stdout, _, success) = run_cli(&[root, "--owners", "--group-by", "owner"]);
+    assert!(success);
+    assert!(stdout.contains("@org/payments (1 issues)"), "{}", stdout);
+}
+
+#[test]
+fn test_cli_schema() {
+    let (stdout, stderr, success) = run_cli(&["--schema"]);
+    assert!(success, "Schema should print: {}", stderr);
+    let schema: serde_json::Value = serde_json::from_str(&stdout).expect("Valid JSON Schema");
+
+    let path = fixtures_path().join("kotlin");
+    let (stdout, _, success) = run_cli(&["-q", path.to_str().unwrap(), "--format", "json"]);
+    assert!(success);
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect("Valid JSON report");
+    assert_eq!(
+        report["schema_version"],
+        schema["properties"]["schema_version"]["const"]
+    );
+    for field in schema["required"].as_array().unwrap() {
+        assert!(report.get(field.as_str().unwrap()).is_some(), "{}", field);
+    }
+}
+
+#[test]
+fn test_cli_treemap() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    let treemap = temp.path().join("treemap.svg");
+    let path = fixtures_path().join("kotlin");
+    let (_, stderr, success) = run_cli(&[
+        "-q",
+        path.to_str().unwrap(),
+        "--treemap",
+        treemap.to_str().unwrap(),
+    ]);
+    assert!(success, "Analysis should succeed: {}", stderr);
+
+    let svg = std::fs::read_to_string(&treemap).expect("Treemap should be written");
+    assert!(svg.starts_with("<svg"), "{}", svg);
+    assert!(svg.contains("Dead code by package"), "{}", svg);
+    assert!(svg.contains("<title>"), "{}", svg);
+}
+
+#[test]
+fn test_cli_cycles_format() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    std::fs::write(
+        temp.path().join("Zombie.kt"),
+        "package com.app\n\nclass Alpha {\n    fun run() = Beta().run()\n}\n\nclass Beta {\n    fun run() = Alpha().run()\n}\n",
+    )
+    .unwrap();
+    let root = temp.path().to_str().unwrap();
+
+    let (stdout, stderr, success) =
+        run_cli(&[root, "--detect-cycles", "--cycles-format", "mermaid"]);
+    assert!(success, "Analysis should succeed: {}", stderr);
+    assert!(stdout.contains("flowchart LR"), "{}", stdout);
+    assert!(stdout.contains("[\"method Alpha.run\"]"), "{}", stdout);
+
+    let diagram = temp.path().join("zombies.puml");
+    let (_, _, success) = run_cli(&[
+        root,
+        "--detect-cycles",
+        "--cycles-format",
+        "plantuml",
+        "--cycles-output",
+        diagram.to_str().unwrap(),
+    ]);
+    assert!(success);
+    let plantuml = std::fs::read_to_string(&diagram).expect("Diagram should be written");
+    assert!(plantuml.starts_with("@startuml"), "{}", plantuml);
+
+    let (_, _, success) = run_cli(&[root, "--cycles-format", "mermaid"]);
+    assert!(!success, "--cycles-format requires --detect-cycles");
+}
+
+#[test]
+fn test_cli_filter() {
+    let path = fixtures_path().join("kotlin");
+    let path = path.to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[path, "--format", "json", "-q"]);
+    assert!(success, "Analysis should succeed: {}", stderr);
+    let all: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let total = all["total_issues"].as_u64().unwrap();
+    assert!(total > 0);
+
+    let (stdout, stderr, success) = run_cli(&[
+        path,
+        "--format",
+        "json",
+        "-q",
+        "--filter",
+        "rule == DC001 && confidence >= medium",
+    ]);
+    assert!(success, "Analysis should succeed: {}", stderr);
+    let filtered: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let issues = filtered["issues"].as_array().unwrap();
+    assert!((issues.len() as u64) < total);
+    assert!(issues.iter().all(|issue| issue["code"] == "DC001"));
+
+    let (_, stderr, success) = run_cli(&[path, "--filter", "colour == red"]);
+    assert!(!success);
+    assert!(stderr.contains("unknown field 'colour'"), "{}", stderr);
+}
+
+#[test]
+fn test_cli_fail_on() {
+    use tempfile::tempdir;
+
+    let path = fixtures_path().join("kotlin");
+    let path = path.to_str().unwrap();
+    let exit_code = |args: &[&str]| {
+        Command::new(binary_path())
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+            .status
+            .code()
+    };
+
+    assert_eq!(exit_code(&[path, "-q"]), Some(0));
+    assert_eq!(exit_code(&[path, "-q", "--fail-on", "info"]), Some(1));
+    assert_eq!(exit_code(&[path, "-q", "--fail-on", "error"]), Some(0));
+    assert_eq!(exit_code(&[path, "-q", "--fail-on", "100000"]), Some(0));
+
+    let (_, stderr, success) = run_cli(&[path, "-q", "--fail-on", "warning"]);
+    assert!(!success);
+    assert!(stderr.contains("Quality gate failed"), "{}", stderr);
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    let config = temp.path().join("deadcode.yml");
+    std::fs::write(&config, "gate:\n  max_warnings: 0\n").unwrap();
+    let (_, stderr, success) = run_cli(&[path, "-q", "--config", config.to_str().unwrap()]);
+    assert!(!success);
+    assert!(stderr.contains("more than max_warnings (0)"), "{}", stderr);
+}
+
+#[test]
+fn test_cli_sarif_baseline_suppressions() {
+    use tempfile::tempdir;
+
+    let temp = tempdir().expect("Failed to create temp dir");
+    let baseline = temp.path().join("baseline.json");
+    let path = fixtures_path().join("kotlin");
+    let path = path.to_str().unwrap();
+    let (_, stderr, success) = run_cli(&[
+        path,
+        "-q",
+        "--generate-baseline",
+        baseline.to_str().unwrap(),
+    ]);
+    assert!(success, "Baseline generation should succeed: {}", stderr);
+
+    let sarif = temp.path().join("results.sarif");
+    let (_, stderr, success) = run_cli(&[
+        path,
+        "-q",
+        "--baseline",
+        baseline.to_str().unwrap(),
+        "--format",
+        "sarif",
+        "--output",
+        sarif.to_str().unwrap(),
+    ]);
+    assert!(success, "Analysis should succeed: {}", stderr);
+
+    let sarif: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&sarif).unwrap()).unwrap();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert!(!results.is_empty(), "Baselined findings should be kept");
+    for result in results {
+        assert_eq!(result["baselineState"], "unchanged");
+        assert_eq!(result["suppressions"][0]["kind"], "external");
+    }
+}
+
+#[test]
+fn test_cli_context() {
+    let path = fixtures_path().join("kotlin");
+    let path = path.to_str().unwrap();
+
+    let (stdout, stderr, success) = run_cli(&[path, "-q", "--format", "json", "--context", "2"]);
+    assert!(success, "Analysis should succeed: {}", stderr);
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let issue = &report["issues"][0];
+    let snippet = &issue["snippet"];
+    let start = snippet["start_line"].as_u64().unwrap();
+    let line = issue["line"].as_u64().unwrap();
+    assert!(start <= line && line - start <= 2, "{}", snippet);
+    let text = snippet["lines"][(line - start) as usize].as_str().unwrap();
+    assert!(
+        text.contains(issue["declaration"]["name"].as_str().unwrap()),
+        "{}",
+        snippet
+    );
+
+    let (stdout, _, success) = run_cli(&[path, "--context", "0", "--format", "terminal"]);
+    assert!(success);
+    assert!(stdout.contains(&format!("{} | ", line)), "{}", stdout);
+
+    let (stdout, _, _) = run_cli(&[path, "-q", "--format", "json"]);
+    assert!(!stdout.contains("\"snippet\""));
+}
+
+#[test]
+fn test_cli_quick_fixes() {
+    let path = fixtures_path().join("kotlin");
+    let (stdout, stderr, success) = run_cli(&[
+        path.to_str().unwrap(),
+        "-q",
+        "--format",
+        "json",
+        "--min-confidence",
+        "low",
+    ]);
+    assert!(success, "Analysis should succeed: {}", stderr);
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let issue = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|issue| issue["fix"].is_object())
+        .expect("a finding with a quick fix");
+    assert_eq!(issue["code"], "DC003");
+
+    // The edit removes the parameter, with any separating comma
+    let source = std::fs::read_to_string(path.join(issue["file"].as_str().unwrap())).unwrap();
+    let edit = &issue["fix"]["edits"][0];
+    let start = edit["start_byte"].as_u64().unwrap() as usize;
+    let end = edit["end_byte"].as_u64().unwrap() as usize;
+    let removed = source[start..end].trim_matches(|c: char| c == ',' || c.is_whitespace());
+    assert!(
+        removed.starts_with(issue["declaration"]["name"].as_str().unwrap()),
+        "{:?}",
+        removed
+    );
+    assert_eq!(edit["replacement"], "");
+}
@@ -278,7 +278,7 @@ mod edge_case_tests {
         let graph = build_kotlin_graph("edge_cases.kt");
 
         // Look for backtick identifiers (keywords as names)
-        let names: Vec<_> = graph.declarations().map(|d| d.name.clone()).collect();
+        let names: Vec<_> = graph.declarations().map(|d| d.name.to_string()).collect();
 
         println!("All declaration names count: {}", names.len());
 
@@ -629,7 +629,7 @@ mod java_tests {
         let classes: Vec<_> = graph
             .declarations()
             .filter(|d| d.kind == searchdeadcode::graph::DeclarationKind::Class)
-            .map(|d| d.name.clone())
+            .map(|d| d.name.to_string())
             .collect();
 
         println!("Java classes found: {:?}", classes);
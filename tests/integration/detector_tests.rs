@@ -36,7 +36,7 @@ fn build_kotlin_graph(filename: &str) -> searchdeadcode::graph::Graph {
 
 /// Get declaration names from the graph
 fn get_declaration_names(graph: &searchdeadcode::graph::Graph) -> Vec<String> {
-    graph.declarations().map(|d| d.name.clone()).collect()
+    graph.declarations().map(|d| d.name.to_string()).collect()
 }
 
 // ============================================================================
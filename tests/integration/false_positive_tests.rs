@@ -62,7 +62,7 @@ fn get_dead_code_names(graph: &searchdeadcode::graph::Graph, entry_point: &str)
 
     dead_code
         .iter()
-        .map(|d| d.declaration.name.clone())
+        .map(|d| d.declaration.name.to_string())
         .collect()
 }
 